@@ -1,4 +1,10 @@
-use crate::admin::workspace::WorkspaceId;
+use crate::{
+    admin::workspace::WorkspaceId,
+    tenant::{
+        budget::DEFAULT_THRESHOLDS,
+        timesheet::{AutoStopPolicy, Rounding, TimeEntryPolicy},
+    },
+};
 
 #[derive(Debug, Clone)]
 pub struct WorkspaceView {
@@ -8,6 +14,17 @@ pub struct WorkspaceView {
     pub date_format: String,
     pub currency: String,
     pub week_start: String,
+    /// Budget warning thresholds in percent.
+    pub budget_thresholds: Vec<u8>,
+    /// Refuse to start timers on projects that are over budget.
+    pub block_over_budget: bool,
+    /// Rules every time entry must satisfy.
+    pub time_entry_policy: TimeEntryPolicy,
+    /// Rounding rules for entries of projects and customers without their
+    /// own.
+    pub rounding: Rounding,
+    /// When forgotten timers are stopped automatically.
+    pub auto_stop_policy: AutoStopPolicy,
 }
 
 impl WorkspaceView {
@@ -20,11 +37,16 @@ impl WorkspaceView {
             date_format: "%Y-%m-%d".to_string(),
             currency: "EUR".to_string(),
             week_start: "monday".to_string(),
+            budget_thresholds: DEFAULT_THRESHOLDS.to_vec(),
+            block_over_budget: false,
+            time_entry_policy: TimeEntryPolicy::default(),
+            rounding: Rounding::default(),
+            auto_stop_policy: AutoStopPolicy::default(),
         }
     }
 
     #[must_use]
-    pub const fn new_with_settings(
+    pub fn new_with_settings(
         id: WorkspaceId,
         name: Option<String>,
         timezone: String,
@@ -39,6 +61,11 @@ impl WorkspaceView {
             date_format,
             currency,
             week_start,
+            budget_thresholds: DEFAULT_THRESHOLDS.to_vec(),
+            block_over_budget: false,
+            time_entry_policy: TimeEntryPolicy::default(),
+            rounding: Rounding::default(),
+            auto_stop_policy: AutoStopPolicy::default(),
         }
     }
 
@@ -1,7 +1,11 @@
 use eventually::aggregate::Aggregate;
 use serde::{Deserialize, Serialize};
 
-use crate::{admin::workspace::WorkspaceEvent, shared::AggregateId};
+use crate::{
+    admin::workspace::WorkspaceEvent,
+    shared::AggregateId,
+    tenant::timesheet::{AutoStopPolicy, PeriodLocks, Rounding, TimeEntryPolicy},
+};
 
 pub type WorkspaceId = AggregateId;
 
@@ -13,6 +17,22 @@ pub struct Workspace {
     pub date_format: String,
     pub currency: String,
     pub week_start: String,
+    #[serde(default = "default_budget_thresholds")]
+    pub budget_thresholds: Vec<u8>,
+    #[serde(default)]
+    pub block_over_budget: bool,
+    #[serde(default)]
+    pub time_entry_policy: TimeEntryPolicy,
+    #[serde(default)]
+    pub rounding: Rounding,
+    #[serde(default)]
+    pub auto_stop_policy: AutoStopPolicy,
+    #[serde(default)]
+    pub period_locks: PeriodLocks,
+}
+
+fn default_budget_thresholds() -> Vec<u8> {
+    crate::tenant::budget::DEFAULT_THRESHOLDS.to_vec()
 }
 
 impl Workspace {
@@ -57,6 +77,12 @@ impl Aggregate for Workspace {
                 date_format: "%Y-%m-%d".to_string(),
                 currency: "EUR".to_string(),
                 week_start: "monday".to_string(),
+                budget_thresholds: default_budget_thresholds(),
+                block_over_budget: false,
+                time_entry_policy: TimeEntryPolicy::default(),
+                rounding: Rounding::default(),
+                auto_stop_policy: AutoStopPolicy::default(),
+                period_locks: PeriodLocks::default(),
             }),
             (Some(_), WorkspaceEvent::Created { .. }) => Err(Error::AlreadyExists),
             (None, _) => Err(Error::NotFound),
@@ -84,6 +110,39 @@ impl Aggregate for Workspace {
                 workspace.week_start = week_start;
                 Ok(workspace)
             }
+            (
+                Some(mut workspace),
+                WorkspaceEvent::BudgetPolicyUpdated {
+                    thresholds,
+                    block_over_budget,
+                },
+            ) => {
+                workspace.budget_thresholds = thresholds;
+                workspace.block_over_budget = block_over_budget;
+                Ok(workspace)
+            }
+            (Some(mut workspace), WorkspaceEvent::TimeEntryPolicyUpdated { policy }) => {
+                workspace.time_entry_policy = policy;
+                Ok(workspace)
+            }
+            (Some(mut workspace), WorkspaceEvent::RoundingUpdated { rounding }) => {
+                workspace.rounding = rounding;
+                Ok(workspace)
+            }
+            (Some(mut workspace), WorkspaceEvent::AutoStopPolicyUpdated { policy }) => {
+                workspace.auto_stop_policy = policy;
+                Ok(workspace)
+            }
+            (Some(mut workspace), WorkspaceEvent::PeriodLocked { user_id, until }) => {
+                let user_id = user_id.map(|id| id.to_string());
+                workspace.period_locks.set(user_id.as_deref(), Some(until));
+                Ok(workspace)
+            }
+            (Some(mut workspace), WorkspaceEvent::PeriodUnlocked { user_id, until }) => {
+                let user_id = user_id.map(|id| id.to_string());
+                workspace.period_locks.set(user_id.as_deref(), until);
+                Ok(workspace)
+            }
         }
     }
 }
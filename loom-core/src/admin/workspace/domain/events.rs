@@ -1,8 +1,12 @@
 use eventually::message::Message;
 use serde::{Deserialize, Serialize};
 
-use crate::admin::{
-    permission::PermissionId, user::UserId, workspace::WorkspaceId, workspace_role::WorkspaceRoleId,
+use crate::{
+    admin::{
+        permission::PermissionId, user::UserId, workspace::WorkspaceId,
+        workspace_role::WorkspaceRoleId,
+    },
+    tenant::timesheet::{AutoStopPolicy, Rounding, TimeEntryPolicy},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -34,6 +38,30 @@ pub enum WorkspaceEvent {
         currency: String,
         week_start: String,
     },
+    /// Warning thresholds (percent) for customer and project budgets, and
+    /// whether timers may be started on projects that are over budget.
+    BudgetPolicyUpdated {
+        thresholds: Vec<u8>,
+        block_over_budget: bool,
+    },
+    /// Rules every time entry of the workspace must satisfy.
+    TimeEntryPolicyUpdated { policy: TimeEntryPolicy },
+    /// Rounding rules for entries of projects and customers without their
+    /// own.
+    RoundingUpdated { rounding: Rounding },
+    /// When forgotten timers of the workspace are stopped automatically.
+    AutoStopPolicyUpdated { policy: AutoStopPolicy },
+    /// Locks every time entry starting on or before `until` (`YYYY-MM-DD`),
+    /// for one user or, without `user_id`, for the whole workspace.
+    PeriodLocked {
+        user_id: Option<UserId>,
+        until: String,
+    },
+    /// Moves a lock back to `until`, or lifts it when `until` is `None`.
+    PeriodUnlocked {
+        user_id: Option<UserId>,
+        until: Option<String>,
+    },
 }
 
 impl Message for WorkspaceEvent {
@@ -45,6 +73,12 @@ impl Message for WorkspaceEvent {
             Self::UserPermissionGranted { .. } => "WorkspaceUserPermissionGranted",
             Self::UserPermissionRevoked { .. } => "WorkspaceUserPermissionRevoked",
             Self::SettingsUpdated { .. } => "WorkspaceSettingsUpdated",
+            Self::BudgetPolicyUpdated { .. } => "WorkspaceBudgetPolicyUpdated",
+            Self::TimeEntryPolicyUpdated { .. } => "WorkspaceTimeEntryPolicyUpdated",
+            Self::RoundingUpdated { .. } => "WorkspaceRoundingUpdated",
+            Self::AutoStopPolicyUpdated { .. } => "WorkspaceAutoStopPolicyUpdated",
+            Self::PeriodLocked { .. } => "WorkspacePeriodLocked",
+            Self::PeriodUnlocked { .. } => "WorkspacePeriodUnlocked",
         }
     }
 }
@@ -0,0 +1,270 @@
+//! Turns serialized domain events into human-readable audit entries.
+//!
+//! Events are stored as externally tagged JSON (`{"Updated": {...}}` or a bare
+//! `"Exported"` string for unit variants).  The helpers here work on that raw
+//! shape so that one audit projector can describe every aggregate without
+//! knowing the concrete event enums.
+
+use serde_json::{Map, Value};
+
+/// Metadata key under which the acting user's ID is stored on each envelope.
+pub const ACTOR_METADATA_KEY: &str = "actor";
+
+/// Metadata key under which background jobs explain why they recorded an
+/// event, e.g. that a forgotten timer was stopped automatically.
+pub const REASON_METADATA_KEY: &str = "reason";
+
+/// Event type prefixes mapped to their aggregate `type_name()`.
+///
+/// Longer prefixes come first so that e.g. `ProjectRateSet` is not matched
+/// as a `Project` event.
+const AGGREGATE_PREFIXES: &[(&str, &str)] = &[
+    ("ActivityRate", "activity_rate"),
+    ("ProjectRate", "project_rate"),
+    ("WorkspaceRole", "workspace_role"),
+    ("Workspace", "workspace"),
+    ("Timesheet", "timesheet"),
+    ("Customer", "customer"),
+    ("Project", "project"),
+    ("Activity", "activity"),
+    ("Approval", "approval"),
+    ("WorkContract", "work_contract"),
+    ("Holiday", "holiday"),
+    ("CalendarRule", "calendar_rule"),
+    ("Webhook", "webhook"),
+    ("ReportSchedule", "report_schedule"),
+    ("Favorite", "favorite"),
+    ("Permission", "permission"),
+    ("User", "user"),
+    ("Tag", "tag"),
+];
+
+/// Fields whose values must never appear in the audit log.
+const REDACTED_FIELDS: &[&str] = &["password", "secret"];
+
+/// Event suffixes that link two entities rather than change one.
+///
+/// Their fields are reported as-is and never merged into the tracked state,
+/// otherwise tagging a second timesheet would read as "timesheet id changed".
+const RELATIONSHIP_SUFFIXES: &[&str] = &["Tagged", "Untagged", "Assigned", "Revoked", "Granted"];
+
+/// Payload fields that point at another entity whose history should include
+/// the event (e.g. tagging shows up on the timesheet, not only on the tag).
+const RELATED_FIELDS: &[&str] = &["timesheet_id"];
+
+/// Returns the aggregate type owning an event, based on its `Message::name()`.
+#[must_use]
+pub fn aggregate_type_for(event_type: &str) -> Option<&'static str> {
+    AGGREGATE_PREFIXES
+        .iter()
+        .find(|(prefix, _)| event_type.starts_with(prefix))
+        .map(|(_, aggregate_type)| *aggregate_type)
+}
+
+/// Returns the action part of an event name in lower case words,
+/// e.g. `TagTimesheetTagged` → `timesheet tagged`.
+#[must_use]
+pub fn action_for(event_type: &str) -> String {
+    let suffix = AGGREGATE_PREFIXES
+        .iter()
+        .find_map(|(prefix, _)| event_type.strip_prefix(prefix))
+        .unwrap_or(event_type);
+
+    let mut words = String::new();
+    for (i, c) in suffix.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            words.push(' ');
+        }
+        words.extend(c.to_lowercase());
+    }
+    words
+}
+
+/// Extracts the variant fields from an externally tagged event payload.
+///
+/// Unit variants yield an empty map.
+#[must_use]
+pub fn event_fields(payload: &Value) -> Map<String, Value> {
+    match payload {
+        Value::Object(outer) => outer
+            .values()
+            .next()
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default(),
+        _ => Map::new(),
+    }
+}
+
+/// Whether the event links entities rather than changing the aggregate itself.
+#[must_use]
+pub fn is_relationship_event(event_type: &str) -> bool {
+    RELATIONSHIP_SUFFIXES
+        .iter()
+        .any(|suffix| event_type.ends_with(suffix))
+}
+
+/// Returns the ID of another entity the event should also be listed under.
+#[must_use]
+pub fn related_id(fields: &Map<String, Value>) -> Option<String> {
+    RELATED_FIELDS
+        .iter()
+        .find_map(|field| fields.get(*field))
+        .and_then(|value| match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Null => None,
+            other => Some(other.to_string()),
+        })
+}
+
+/// Describes how `fields` change the previously known `state` of an aggregate.
+///
+/// Unchanged fields are omitted; redacted fields are reported without values.
+#[must_use]
+pub fn describe_changes(
+    event_type: &str,
+    state: &Map<String, Value>,
+    fields: &Map<String, Value>,
+) -> Vec<String> {
+    let relationship = is_relationship_event(event_type);
+    let verb = if event_type.ends_with("TimeUpdated") {
+        "corrected"
+    } else {
+        "changed"
+    };
+
+    fields
+        .iter()
+        .filter(|(field, _)| field.as_str() != "id")
+        .filter_map(|(field, after)| {
+            let label = field.replace('_', " ");
+            if REDACTED_FIELDS.contains(&field.as_str()) {
+                return Some(format!("{label} {verb}"));
+            }
+            if relationship {
+                return Some(format!("{label}: {}", display_value(after)));
+            }
+
+            let before = state.get(field).unwrap_or(&Value::Null);
+            match (before, after) {
+                (b, a) if b == a => None,
+                (Value::Null, a) => Some(format!("{label} set to {}", display_value(a))),
+                (b, Value::Null) => Some(format!("{label} cleared (was {})", display_value(b))),
+                (b, a) => Some(format!(
+                    "{label} {verb} from {} to {}",
+                    display_value(b),
+                    display_value(a)
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Merges the event fields into the tracked aggregate state.
+///
+/// Relationship events and redacted fields are left out.
+pub fn merge_state(event_type: &str, state: &mut Map<String, Value>, fields: &Map<String, Value>) {
+    if is_relationship_event(event_type) {
+        return;
+    }
+    for (field, value) in fields {
+        if !REDACTED_FIELDS.contains(&field.as_str()) {
+            state.insert(field.clone(), value.clone());
+        }
+    }
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => "nothing".to_string(),
+        Value::String(s) => format!("\"{s}\""),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_aggregate_type_prefers_longest_prefix() {
+        assert_eq!(aggregate_type_for("ProjectRateSet"), Some("project_rate"));
+        assert_eq!(aggregate_type_for("ProjectUpdated"), Some("project"));
+        assert_eq!(
+            aggregate_type_for("WorkspaceRolePermissionGranted"),
+            Some("workspace_role")
+        );
+        assert_eq!(
+            aggregate_type_for("CalendarRuleAdded"),
+            Some("calendar_rule")
+        );
+        assert_eq!(
+            aggregate_type_for("ReportScheduleChanged"),
+            Some("report_schedule")
+        );
+        assert_eq!(aggregate_type_for("FavoritePinned"), Some("favorite"));
+        assert_eq!(aggregate_type_for("Unknown"), None);
+    }
+
+    #[test]
+    fn test_action_for_splits_words() {
+        assert_eq!(action_for("TagTimesheetTagged"), "timesheet tagged");
+        assert_eq!(action_for("TimesheetExported"), "exported");
+    }
+
+    #[test]
+    fn test_event_fields_handles_unit_variants() {
+        assert!(event_fields(&json!("Exported")).is_empty());
+        let fields = event_fields(&json!({ "Renamed": { "name": "Urgent" } }));
+        assert_eq!(fields.get("name"), Some(&json!("Urgent")));
+    }
+
+    #[test]
+    fn test_describe_changes_reports_only_differences() {
+        let state = event_fields(&json!({ "Updated": { "description": "old", "billable": true } }));
+        let fields =
+            event_fields(&json!({ "Updated": { "description": "new", "billable": true } }));
+
+        let changes = describe_changes("TimesheetUpdated", &state, &fields);
+
+        assert_eq!(changes, vec!["description changed from \"old\" to \"new\""]);
+    }
+
+    #[test]
+    fn test_describe_changes_uses_corrected_for_time_updates() {
+        let state = event_fields(&json!({ "Stopped": { "end_time": "10:00" } }));
+        let fields = event_fields(&json!({ "TimeUpdated": { "end_time": "11:00" } }));
+
+        let changes = describe_changes("TimesheetTimeUpdated", &state, &fields);
+
+        assert_eq!(
+            changes,
+            vec!["end time corrected from \"10:00\" to \"11:00\""]
+        );
+    }
+
+    #[test]
+    fn test_redacted_fields_are_never_merged_or_shown() {
+        let mut state = Map::new();
+        let fields = event_fields(&json!({ "Created": { "password": "hash" } }));
+
+        let changes = describe_changes("UserCreated", &state, &fields);
+        merge_state("UserCreated", &mut state, &fields);
+
+        assert_eq!(changes, vec!["password changed"]);
+        assert!(state.is_empty());
+    }
+
+    #[test]
+    fn test_relationship_events_do_not_touch_state() {
+        let mut state = Map::new();
+        let fields = event_fields(&json!({ "TimesheetTagged": { "timesheet_id": "abc" } }));
+
+        merge_state("TagTimesheetTagged", &mut state, &fields);
+
+        assert!(state.is_empty());
+        assert_eq!(related_id(&fields), Some("abc".to_string()));
+    }
+}
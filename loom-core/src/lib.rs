@@ -1,4 +1,5 @@
 pub mod admin;
+pub mod audit;
 pub mod permissions;
 pub mod shared;
 pub mod tenant;
@@ -11,19 +11,46 @@
 // Customer domain
 pub const CUSTOMER_CREATE: &str = "customer.create";
 pub const CUSTOMER_UPDATE: &str = "customer.update";
+pub const CUSTOMER_DELETE: &str = "customer.delete";
 
 // Project domain
 pub const PROJECT_CREATE: &str = "project.create";
 pub const PROJECT_UPDATE: &str = "project.update";
+pub const PROJECT_DELETE: &str = "project.delete";
 
 // Activity domain
 pub const ACTIVITY_CREATE: &str = "activity.create";
 pub const ACTIVITY_UPDATE: &str = "activity.update";
+pub const ACTIVITY_DELETE: &str = "activity.delete";
 
 // Timesheet domain
 pub const TIMESHEET_CREATE: &str = "timesheet.create";
 pub const TIMESHEET_UPDATE: &str = "timesheet.update";
 pub const TIMESHEET_EXPORT: &str = "timesheet.export";
+pub const TIMESHEET_DELETE: &str = "timesheet.delete";
+/// Change entries that fall into a locked period.
+pub const TIMESHEET_EDIT_LOCKED: &str = "timesheet.edit_locked";
+/// Submit one's own entries of a period for approval.
+pub const TIMESHEET_SUBMIT: &str = "timesheet.submit";
+/// Approve or reject submitted entries.
+pub const TIMESHEET_APPROVE: &str = "timesheet.approve";
+
+// Period locking
+pub const PERIOD_LOCK: &str = "period.lock";
+pub const PERIOD_UNLOCK: &str = "period.unlock";
+
+// Working time
+/// Manage work contracts and public holidays, and see everyone's overtime
+/// balance.
+pub const WORK_CONTRACT_MANAGE: &str = "work_contract.manage";
+
+// Integrations
+/// Manage outgoing webhooks and see their delivery log.
+pub const WEBHOOK_MANAGE: &str = "webhook.manage";
+
+// Reporting
+/// Schedule reports covering everyone's entries, not just one's own.
+pub const REPORT_MANAGE: &str = "report.manage";
 
 // Cross-cutting
 pub const TAG_MANAGE: &str = "tag.manage";
@@ -34,13 +61,25 @@ pub const RATE_MANAGE: &str = "rate.manage";
 pub const ALL: &[&str] = &[
     CUSTOMER_CREATE,
     CUSTOMER_UPDATE,
+    CUSTOMER_DELETE,
     PROJECT_CREATE,
     PROJECT_UPDATE,
+    PROJECT_DELETE,
     ACTIVITY_CREATE,
     ACTIVITY_UPDATE,
+    ACTIVITY_DELETE,
     TIMESHEET_CREATE,
     TIMESHEET_UPDATE,
     TIMESHEET_EXPORT,
+    TIMESHEET_DELETE,
+    TIMESHEET_EDIT_LOCKED,
+    TIMESHEET_SUBMIT,
+    TIMESHEET_APPROVE,
+    PERIOD_LOCK,
+    PERIOD_UNLOCK,
+    WORK_CONTRACT_MANAGE,
+    WEBHOOK_MANAGE,
+    REPORT_MANAGE,
     TAG_MANAGE,
     RATE_MANAGE,
 ];
@@ -14,6 +14,10 @@ pub struct Activity {
     name: String,
     visible: bool,
     billable: bool,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    deleted: bool,
 }
 
 impl Activity {
@@ -37,6 +41,10 @@ impl Activity {
     pub const fn billable(&self) -> bool {
         self.billable
     }
+    #[must_use]
+    pub const fn archived(&self) -> bool {
+        self.archived
+    }
 }
 
 crate::aggregate_errors!("activity");
@@ -69,9 +77,12 @@ impl Aggregate for Activity {
                 name,
                 visible: true,
                 billable: true,
+                archived: false,
+                deleted: false,
             }),
             (Some(_), ActivityEvent::Created { .. }) => Err(Error::AlreadyExists),
             (None, _) => Err(Error::NotFound),
+            (Some(a), _) if a.deleted => Err(Error::NotFound),
             (
                 Some(mut a),
                 ActivityEvent::Updated {
@@ -86,6 +97,18 @@ impl Aggregate for Activity {
                 a.billable = billable;
                 Ok(a)
             }
+            (Some(mut a), ActivityEvent::Archived) => {
+                a.archived = true;
+                Ok(a)
+            }
+            (Some(mut a), ActivityEvent::Restored) => {
+                a.archived = false;
+                Ok(a)
+            }
+            (Some(mut a), ActivityEvent::Deleted) => {
+                a.deleted = true;
+                Ok(a)
+            }
         }
     }
 }
@@ -18,6 +18,11 @@ pub enum ActivityEvent {
         visible: bool,
         billable: bool,
     },
+    /// Hidden from lists and selectors until restored.
+    Archived,
+    Restored,
+    /// Permanently removed.  Only recorded while no timesheet references it.
+    Deleted,
 }
 
 impl Message for ActivityEvent {
@@ -25,6 +30,9 @@ impl Message for ActivityEvent {
         match self {
             Self::Created { .. } => "ActivityCreated",
             Self::Updated { .. } => "ActivityUpdated",
+            Self::Archived => "ActivityArchived",
+            Self::Restored => "ActivityRestored",
+            Self::Deleted => "ActivityDeleted",
         }
     }
 }
@@ -0,0 +1,86 @@
+use eventually::aggregate;
+
+use crate::tenant::approval::{
+    self,
+    domain::{
+        aggregates::{Approval, ApprovalId},
+        events::ApprovalEvent,
+    },
+};
+use crate::tenant::timesheet::TimesheetId;
+use crate::tenant::timesheet::domain::events::UserId;
+
+#[eventually_macros::aggregate_root(Approval)]
+pub struct ApprovalCommand;
+
+impl ApprovalCommand {
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    #[allow(clippy::too_many_arguments)]
+    pub fn request(
+        &self,
+        id: ApprovalId,
+        user_id: UserId,
+        period_start: String,
+        period_end: String,
+        timesheet_ids: Vec<TimesheetId>,
+        comment: Option<String>,
+        submitted_at: String,
+    ) -> Result<Self, crate::Error> {
+        Ok(aggregate::Root::<Approval>::record_new(
+            ApprovalEvent::Requested {
+                id,
+                user_id,
+                period_start,
+                period_end,
+                timesheet_ids,
+                comment,
+                submitted_at,
+            }
+            .into(),
+        )
+        .map_err(approval::DomainError::from)?
+        .into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn grant(
+        &mut self,
+        reviewer_id: UserId,
+        comment: Option<String>,
+        reviewed_at: String,
+    ) -> Result<(), crate::Error> {
+        self.record_that(
+            ApprovalEvent::Granted {
+                reviewer_id,
+                comment,
+                reviewed_at,
+            }
+            .into(),
+        )
+        .map_err(|e| approval::DomainError::AggregateError(e).into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn reject(
+        &mut self,
+        reviewer_id: UserId,
+        comment: String,
+        reviewed_at: String,
+    ) -> Result<(), crate::Error> {
+        self.record_that(
+            ApprovalEvent::Rejected {
+                reviewer_id,
+                comment,
+                reviewed_at,
+            }
+            .into(),
+        )
+        .map_err(|e| approval::DomainError::AggregateError(e).into())
+    }
+}
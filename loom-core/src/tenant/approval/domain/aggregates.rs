@@ -0,0 +1,222 @@
+use eventually::aggregate::Aggregate;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::AggregateId;
+use crate::tenant::approval::ApprovalEvent;
+use crate::tenant::timesheet::TimesheetId;
+use crate::tenant::timesheet::domain::events::UserId;
+
+pub type ApprovalId = AggregateId;
+
+/// Where a time entry, or the submission covering it, stands in review.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    /// Not submitted yet.  Only time entries are ever in this state.
+    #[default]
+    Draft,
+    Submitted,
+    Approved,
+    Rejected,
+}
+
+impl ApprovalStatus {
+    /// The name stored in projections.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Submitted => "submitted",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+/// The submission of one user's entries for a period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Approval {
+    id: ApprovalId,
+    user_id: UserId,
+    period_start: String,
+    period_end: String,
+    timesheet_ids: Vec<TimesheetId>,
+    status: ApprovalStatus,
+    comment: Option<String>,
+    reviewer_id: Option<UserId>,
+}
+
+impl Approval {
+    #[must_use]
+    pub const fn id(&self) -> &ApprovalId {
+        &self.id
+    }
+    #[must_use]
+    pub const fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+    #[must_use]
+    pub fn period_start(&self) -> &str {
+        &self.period_start
+    }
+    #[must_use]
+    pub fn period_end(&self) -> &str {
+        &self.period_end
+    }
+    #[must_use]
+    pub fn timesheet_ids(&self) -> &[TimesheetId] {
+        &self.timesheet_ids
+    }
+    #[must_use]
+    pub const fn status(&self) -> ApprovalStatus {
+        self.status
+    }
+    /// The submitter's comment, replaced by the reviewer's once reviewed.
+    #[must_use]
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+    #[must_use]
+    pub const fn reviewer_id(&self) -> Option<&UserId> {
+        self.reviewer_id.as_ref()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("approval already exists")]
+    AlreadyExists,
+    #[error("approval not found")]
+    NotFound,
+    #[error("approval already reviewed")]
+    AlreadyReviewed,
+}
+
+impl Aggregate for Approval {
+    type Id = ApprovalId;
+    type Event = ApprovalEvent;
+    type Error = Error;
+
+    fn type_name() -> &'static str {
+        "approval"
+    }
+
+    fn aggregate_id(&self) -> &Self::Id {
+        &self.id
+    }
+
+    fn apply(state: Option<Self>, event: Self::Event) -> Result<Self, Self::Error> {
+        match (state, event) {
+            (
+                None,
+                ApprovalEvent::Requested {
+                    id,
+                    user_id,
+                    period_start,
+                    period_end,
+                    timesheet_ids,
+                    comment,
+                    ..
+                },
+            ) => Ok(Self {
+                id,
+                user_id,
+                period_start,
+                period_end,
+                timesheet_ids,
+                status: ApprovalStatus::Submitted,
+                comment,
+                reviewer_id: None,
+            }),
+            (Some(_), ApprovalEvent::Requested { .. }) => Err(Error::AlreadyExists),
+            (None, _) => Err(Error::NotFound),
+            (Some(a), _) if a.status != ApprovalStatus::Submitted => Err(Error::AlreadyReviewed),
+            (
+                Some(a),
+                ApprovalEvent::Granted {
+                    reviewer_id,
+                    comment,
+                    ..
+                },
+            ) => Ok(Self {
+                status: ApprovalStatus::Approved,
+                comment,
+                reviewer_id: Some(reviewer_id),
+                ..a
+            }),
+            (
+                Some(a),
+                ApprovalEvent::Rejected {
+                    reviewer_id,
+                    comment,
+                    ..
+                },
+            ) => Ok(Self {
+                status: ApprovalStatus::Rejected,
+                comment: Some(comment),
+                reviewer_id: Some(reviewer_id),
+                ..a
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requested() -> Approval {
+        Approval::apply(
+            None,
+            ApprovalEvent::Requested {
+                id: ApprovalId::new(),
+                user_id: UserId::new(),
+                period_start: "2026-10-12".to_string(),
+                period_end: "2026-10-18".to_string(),
+                timesheet_ids: vec![TimesheetId::new()],
+                comment: None,
+                submitted_at: "2026-10-19T08:00:00+00:00".to_string(),
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn rejection_keeps_the_reviewer_comment() {
+        let rejected = Approval::apply(
+            Some(requested()),
+            ApprovalEvent::Rejected {
+                reviewer_id: UserId::new(),
+                comment: "Tuesday is missing".to_string(),
+                reviewed_at: "2026-10-19T09:00:00+00:00".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(rejected.status(), ApprovalStatus::Rejected);
+        assert_eq!(rejected.comment(), Some("Tuesday is missing"));
+    }
+
+    #[test]
+    fn a_reviewed_approval_cannot_be_reviewed_again() {
+        let approved = Approval::apply(
+            Some(requested()),
+            ApprovalEvent::Granted {
+                reviewer_id: UserId::new(),
+                comment: None,
+                reviewed_at: "2026-10-19T09:00:00+00:00".to_string(),
+            },
+        )
+        .unwrap();
+        assert!(matches!(
+            Approval::apply(
+                Some(approved),
+                ApprovalEvent::Rejected {
+                    reviewer_id: UserId::new(),
+                    comment: "Too late".to_string(),
+                    reviewed_at: "2026-10-19T10:00:00+00:00".to_string(),
+                },
+            ),
+            Err(Error::AlreadyReviewed)
+        ));
+    }
+}
@@ -0,0 +1,47 @@
+use eventually::message::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::tenant::approval::ApprovalId;
+use crate::tenant::timesheet::TimesheetId;
+use crate::tenant::timesheet::domain::events::UserId;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ApprovalEvent {
+    /// A user submits the entries of a period for review.
+    Requested {
+        id: ApprovalId,
+        user_id: UserId,
+        /// First day of the period, `YYYY-MM-DD`.
+        period_start: String,
+        /// Last day of the period, `YYYY-MM-DD`.
+        period_end: String,
+        timesheet_ids: Vec<TimesheetId>,
+        comment: Option<String>,
+        /// RFC-3339 timestamp string.
+        submitted_at: String,
+    },
+    /// The submitted entries are approved and become read-only.
+    Granted {
+        reviewer_id: UserId,
+        comment: Option<String>,
+        /// RFC-3339 timestamp string.
+        reviewed_at: String,
+    },
+    /// The submitted entries are sent back to the user for corrections.
+    Rejected {
+        reviewer_id: UserId,
+        comment: String,
+        /// RFC-3339 timestamp string.
+        reviewed_at: String,
+    },
+}
+
+impl Message for ApprovalEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Requested { .. } => "ApprovalRequested",
+            Self::Granted { .. } => "ApprovalGranted",
+            Self::Rejected { .. } => "ApprovalRejected",
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+
+use crate::tenant::approval::domain::aggregates::Approval;
+use eventually::aggregate::repository::{Getter, Saver};
+
+#[async_trait]
+pub trait ApprovalRepository: Getter<Approval> + Saver<Approval> + Send + Sync {}
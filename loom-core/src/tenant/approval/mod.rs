@@ -0,0 +1,16 @@
+pub(crate) mod application;
+pub(crate) mod domain;
+
+pub use application::commands::ApprovalCommand;
+pub use domain::{
+    Error as DomainError,
+    aggregates::{Approval, ApprovalId, ApprovalStatus},
+    events::ApprovalEvent,
+    interfaces::ApprovalRepository,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0:?}")]
+    DomainError(#[from] domain::Error),
+}
@@ -0,0 +1,142 @@
+//! Budget consumption of customers and projects.
+//!
+//! Budgets are set with `BudgetUpdated` (time in seconds, money in cents).
+//! A monthly budget is measured per UTC calendar month, identified by its
+//! period key `YYYY-MM`; any other budget covers the whole lifetime and has no
+//! period.
+
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Warning thresholds in percent used when a workspace configured none.
+pub const DEFAULT_THRESHOLDS: &[u8] = &[80, 100];
+
+/// A warning threshold that has been reached within a budget period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReachedThreshold {
+    pub period: Option<String>,
+    pub threshold: u8,
+}
+
+/// Logged time and money measured against a budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetUsage {
+    pub time_budget: Option<i32>,
+    pub money_budget: Option<i64>,
+    pub used_seconds: i64,
+    pub used_amount: i64,
+}
+
+impl BudgetUsage {
+    #[must_use]
+    pub fn remaining_seconds(&self) -> Option<i64> {
+        self.time_budget
+            .map(|budget| i64::from(budget) - self.used_seconds)
+    }
+
+    #[must_use]
+    pub fn remaining_amount(&self) -> Option<i64> {
+        self.money_budget.map(|budget| budget - self.used_amount)
+    }
+
+    /// The larger of the time and money share used, in percent.
+    ///
+    /// `None` if neither budget is set.  A budget of zero counts as fully
+    /// used.
+    #[must_use]
+    pub fn percent_used(&self) -> Option<i64> {
+        let time = self
+            .time_budget
+            .map(|budget| percent(self.used_seconds, i64::from(budget)));
+        let money = self
+            .money_budget
+            .map(|budget| percent(self.used_amount, budget));
+        time.max(money)
+    }
+
+    #[must_use]
+    pub fn is_over_budget(&self) -> bool {
+        self.percent_used().is_some_and(|p| p >= 100)
+    }
+
+    /// The thresholds (in percent) the current usage has reached.
+    #[must_use]
+    pub fn reached(&self, thresholds: &[u8]) -> Vec<u8> {
+        let Some(used) = self.percent_used() else {
+            return Vec::new();
+        };
+        thresholds
+            .iter()
+            .copied()
+            .filter(|&t| used >= i64::from(t))
+            .collect()
+    }
+}
+
+fn percent(used: i64, budget: i64) -> i64 {
+    if budget <= 0 {
+        100
+    } else {
+        used.saturating_mul(100) / budget
+    }
+}
+
+/// The budget period `instant` falls into.
+#[must_use]
+pub fn period_of(budget_is_monthly: bool, instant: DateTime<Utc>) -> Option<String> {
+    budget_is_monthly.then(|| instant.format("%Y-%m").to_string())
+}
+
+/// Start (inclusive) and end (exclusive) of a monthly period as RFC 3339
+/// instants, or `None` if `period` is not a `YYYY-MM` key.
+#[must_use]
+pub fn period_range(period: &str) -> Option<(String, String)> {
+    let start = NaiveDate::parse_from_str(&format!("{period}-01"), "%Y-%m-%d").ok()?;
+    let end = if start.month() == 12 {
+        NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)?
+    };
+    let instant = |date: NaiveDate| {
+        Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default())
+            .to_rfc3339()
+    };
+    Some((instant(start), instant(end)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(time_budget: Option<i32>, money_budget: Option<i64>) -> BudgetUsage {
+        BudgetUsage {
+            time_budget,
+            money_budget,
+            used_seconds: 3600,
+            used_amount: 9_000,
+        }
+    }
+
+    #[test]
+    fn percent_used_takes_the_larger_share() {
+        assert_eq!(usage(Some(7200), Some(10_000)).percent_used(), Some(90));
+        assert_eq!(usage(Some(3600), None).percent_used(), Some(100));
+        assert_eq!(usage(None, None).percent_used(), None);
+    }
+
+    #[test]
+    fn reached_lists_thresholds_at_or_below_usage() {
+        let u = usage(None, Some(10_000));
+        assert_eq!(u.reached(DEFAULT_THRESHOLDS), vec![80]);
+        assert!(!u.is_over_budget());
+        assert_eq!(u.remaining_amount(), Some(1_000));
+    }
+
+    #[test]
+    fn period_range_covers_the_calendar_month() {
+        let (start, end) = period_range("2026-12").expect("valid period");
+        assert!(start.starts_with("2026-12-01T00:00:00"));
+        assert!(end.starts_with("2027-01-01T00:00:00"));
+        assert_eq!(period_range("december"), None);
+    }
+}
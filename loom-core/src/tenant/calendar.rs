@@ -0,0 +1,399 @@
+//! iCalendar (RFC 5545) feeds of timesheets and imports of calendar events.
+//!
+//! Only the part of the format Loom needs is covered: a feed is a
+//! `VCALENDAR` with one `VEVENT` per entry, and an import reads the
+//! `VEVENT`s of a file and keeps those with a start and an end time.
+//! Floating times, without a `Z` suffix or a `TZID`, are read as UTC like
+//! every other time Loom is given without an offset.  Events whose times
+//! are tied to a time zone other than UTC are skipped rather than imported
+//! at the wrong hour, as are all-day events since they have no working
+//! time.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+
+const PRODUCT_ID: &str = "-//NavilaLabs//Loom//EN";
+/// Lines longer than this many octets are folded.
+const LINE_LIMIT: usize = 75;
+const UTC_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+/// `TZID`s naming UTC itself, whose times can be read as they are.
+const UTC_ZONES: [&str; 4] = ["UTC", "Etc/UTC", "GMT", "Etc/GMT"];
+
+/// A timesheet entry as an event of the feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedEvent {
+    /// Globally unique and stable across renders of the feed.
+    pub uid: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub summary: String,
+    pub description: Option<String>,
+}
+
+/// An event read from an imported calendar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedEvent {
+    pub uid: Option<String>,
+    pub summary: String,
+    pub description: Option<String>,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Renders `events` as a calendar called `name`.
+#[must_use]
+pub fn render(name: &str, events: &[FeedEvent], stamp: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    let mut line = |text: String| {
+        out.push_str(&fold(&text));
+        out.push_str("\r\n");
+    };
+    line("BEGIN:VCALENDAR".to_string());
+    line("VERSION:2.0".to_string());
+    line(format!("PRODID:{PRODUCT_ID}"));
+    line("CALSCALE:GREGORIAN".to_string());
+    line(format!("X-WR-CALNAME:{}", escape(name)));
+    for event in events {
+        line("BEGIN:VEVENT".to_string());
+        line(format!("UID:{}", escape(&event.uid)));
+        line(format!("DTSTAMP:{}", stamp.format(UTC_FORMAT)));
+        line(format!("DTSTART:{}", event.start.format(UTC_FORMAT)));
+        line(format!("DTEND:{}", event.end.format(UTC_FORMAT)));
+        line(format!("SUMMARY:{}", escape(&event.summary)));
+        if let Some(description) = event.description.as_deref().filter(|d| !d.is_empty()) {
+            line(format!("DESCRIPTION:{}", escape(description)));
+        }
+        line("END:VEVENT".to_string());
+    }
+    line("END:VCALENDAR".to_string());
+    out
+}
+
+/// Escapes a text value.
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(c) => out.push(c),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Splits a content line into lines of at most [`LINE_LIMIT`] octets, never
+/// inside a character.  Continuation lines start with a space.
+fn fold(line: &str) -> String {
+    let mut out = String::with_capacity(line.len() + line.len() / LINE_LIMIT * 3);
+    let mut octets = 0;
+    for c in line.chars() {
+        // Continuation lines lose one octet to the leading space.
+        if octets + c.len_utf8() > LINE_LIMIT {
+            out.push_str("\r\n ");
+            octets = 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out
+}
+
+/// Joins folded lines back into content lines.
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in ics.lines() {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ if raw.is_empty() => {}
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+/// A content line split into its name, parameters and value.
+struct Property<'a> {
+    name: String,
+    params: Vec<(String, &'a str)>,
+    value: &'a str,
+}
+
+impl<'a> Property<'a> {
+    fn parse(line: &'a str) -> Option<Self> {
+        // The value starts at the first colon outside a quoted parameter.
+        let mut quoted = false;
+        let split = line.char_indices().find_map(|(i, c)| match c {
+            '"' => {
+                quoted = !quoted;
+                None
+            }
+            ':' if !quoted => Some(i),
+            _ => None,
+        })?;
+        let (head, value) = (&line[..split], &line[split + 1..]);
+        let mut parts = head.split(';');
+        let name = parts.next()?.to_ascii_uppercase();
+        let params = parts
+            .filter_map(|p| p.split_once('='))
+            .map(|(k, v)| (k.to_ascii_uppercase(), v.trim_matches('"')))
+            .collect();
+        Some(Self {
+            name,
+            params,
+            value,
+        })
+    }
+
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|(k, _)| k == name).map(|(_, v)| *v)
+    }
+}
+
+enum Time {
+    At(DateTime<Utc>),
+    AllDay,
+}
+
+/// Parses a date or date-time.  Times in a zone other than UTC yield
+/// `None`, which skips their event.
+fn parse_time(property: &Property<'_>) -> Option<Time> {
+    let value = property.value.trim();
+    if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(|_| Time::AllDay);
+    }
+    if property
+        .param("TZID")
+        .is_some_and(|zone| !UTC_ZONES.iter().any(|utc| zone.eq_ignore_ascii_case(utc)))
+    {
+        return None;
+    }
+    let local = value.strip_suffix('Z').unwrap_or(value);
+    NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S")
+        .ok()
+        .map(|t| Time::At(t.and_utc()))
+}
+
+/// Parses a duration such as `PT1H30M` or `P1D`.  Negative durations are
+/// not valid for an event and yield `None`, as do durations too long to
+/// represent.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut rest = value.trim().strip_prefix('+').unwrap_or(value.trim());
+    rest = rest.strip_prefix('P')?;
+    let mut seconds = 0_i64;
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                let unit = match unit {
+                    'W' => 7 * 86_400,
+                    'D' => 86_400,
+                    'H' => 3_600,
+                    'M' => 60,
+                    'S' => 1,
+                    _ => return None,
+                };
+                seconds = seconds.checked_add(n.checked_mul(unit)?)?;
+            }
+        }
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    Duration::try_seconds(seconds)
+}
+
+/// Reads the timed events of a calendar, in the order they appear.
+/// Events without a start, with neither an end nor a duration, or ending
+/// before they start are skipped.
+#[must_use]
+pub fn parse(ics: &str) -> Vec<ImportedEvent> {
+    #[derive(Default)]
+    struct Draft {
+        uid: Option<String>,
+        summary: Option<String>,
+        description: Option<String>,
+        start: Option<Time>,
+        end: Option<Time>,
+        duration: Option<Duration>,
+        // Nesting depth of components inside the event, such as alarms.
+        nested: usize,
+    }
+
+    impl Draft {
+        fn finish(self) -> Option<ImportedEvent> {
+            let Some(Time::At(start)) = self.start else {
+                return None;
+            };
+            let end = match (self.end, self.duration) {
+                (Some(Time::At(end)), _) => end,
+                (None, Some(duration)) => start.checked_add_signed(duration)?,
+                _ => return None,
+            };
+            (end > start).then(|| ImportedEvent {
+                uid: self.uid,
+                summary: self.summary.unwrap_or_default(),
+                description: self.description.filter(|s| !s.is_empty()),
+                start,
+                end,
+            })
+        }
+    }
+
+    let mut events = Vec::new();
+    let mut draft: Option<Draft> = None;
+    for line in unfold(ics) {
+        let Some(property) = Property::parse(&line) else {
+            continue;
+        };
+        let value = property.value;
+        if property.name == "END" && draft.as_ref().is_some_and(|d| d.nested == 0) {
+            if let Some(event) = draft.take().and_then(Draft::finish) {
+                events.push(event);
+            }
+            continue;
+        }
+        match (property.name.as_str(), draft.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => {
+                draft = Some(Draft::default());
+            }
+            ("BEGIN", Some(d)) => d.nested += 1,
+            ("END", Some(d)) => d.nested -= 1,
+            (_, Some(d)) if d.nested > 0 => {}
+            ("UID", Some(d)) => d.uid = Some(unescape(value)),
+            ("SUMMARY", Some(d)) => d.summary = Some(unescape(value)),
+            ("DESCRIPTION", Some(d)) => d.description = Some(unescape(value)),
+            ("DTSTART", Some(d)) => d.start = parse_time(&property),
+            ("DTEND", Some(d)) => d.end = parse_time(&property),
+            ("DURATION", Some(d)) => d.duration = parse_duration(value),
+            _ => {}
+        }
+    }
+    events
+}
+
+/// The rule whose keyword occurs in `title`, ignoring case.  The longest
+/// keyword wins so that a rule for "Acme Weekly" beats one for "Weekly".
+#[must_use]
+pub fn matching_rule<'a, R>(
+    title: &str,
+    rules: &'a [R],
+    keyword: impl Fn(&R) -> &str,
+) -> Option<&'a R> {
+    let title = title.to_lowercase();
+    rules
+        .iter()
+        .filter(|r| {
+            let k = keyword(r).trim();
+            !k.is_empty() && title.contains(&k.to_lowercase())
+        })
+        .max_by_key(|r| keyword(r).trim().chars().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 19, h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn rendered_feeds_parse_back() {
+        let event = FeedEvent {
+            uid: "42@loom".to_string(),
+            start: at(9, 0),
+            end: at(10, 30),
+            summary: "Acme, Inc. / Design; review".to_string(),
+            description: Some(format!("Line one\n{}", "é".repeat(60))),
+        };
+        let ics = render("Timesheets", std::slice::from_ref(&event), at(12, 0));
+        assert!(
+            ics.lines()
+                .all(|l| l.trim_end_matches('\r').len() <= LINE_LIMIT)
+        );
+        assert!(ics.contains("SUMMARY:Acme\\, Inc. / Design\\; review\r\n"));
+
+        let parsed = parse(&ics);
+        assert_eq!(
+            parsed,
+            vec![ImportedEvent {
+                uid: Some(event.uid),
+                summary: event.summary,
+                description: event.description,
+                start: event.start,
+                end: event.end,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_handles_durations_alarms_zones_and_all_day_events() {
+        let ics = "BEGIN:VCALENDAR\n\
+            BEGIN:VEVENT\n\
+            SUMMARY:Stand-up\n\
+            DTSTART;TZID=Etc/UTC:20261019T091500\n\
+            DURATION:PT15M\n\
+            BEGIN:VALARM\n\
+            DESCRIPTION:Reminder\n\
+            END:VALARM\n\
+            END:VEVENT\n\
+            BEGIN:VEVENT\n\
+            SUMMARY:Planning\n\
+            DTSTART;TZID=Europe/Berlin:20261019T100000\n\
+            DTEND;TZID=Europe/Berlin:20261019T110000\n\
+            END:VEVENT\n\
+            BEGIN:VEVENT\n\
+            SUMMARY:Forever\n\
+            DTSTART:20261019T120000Z\n\
+            DURATION:P99999999999999W\n\
+            END:VEVENT\n\
+            BEGIN:VEVENT\n\
+            SUMMARY:Holiday\n\
+            DTSTART;VALUE=DATE:20261020\n\
+            DTEND;VALUE=DATE:20261021\n\
+            END:VEVENT\n\
+            END:VCALENDAR\n";
+        let parsed = parse(ics);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].summary, "Stand-up");
+        assert_eq!(parsed[0].description, None);
+        assert_eq!((parsed[0].start, parsed[0].end), (at(9, 15), at(9, 30)));
+    }
+
+    #[test]
+    fn the_longest_matching_keyword_wins() {
+        let rules = [("weekly", 1), ("Acme Weekly", 2), ("", 3)];
+        let rule = |title| matching_rule(title, &rules, |r| r.0).map(|r| r.1);
+        assert_eq!(rule("ACME weekly sync"), Some(2));
+        assert_eq!(rule("Team weekly"), Some(1));
+        assert_eq!(rule("Lunch"), None);
+    }
+}
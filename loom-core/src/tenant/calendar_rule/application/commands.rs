@@ -0,0 +1,50 @@
+use eventually::aggregate;
+
+use crate::tenant::activity::ActivityId;
+use crate::tenant::calendar_rule::{
+    self,
+    domain::{
+        aggregates::{CalendarRule, CalendarRuleId},
+        events::CalendarRuleEvent,
+    },
+};
+use crate::tenant::project::ProjectId;
+use crate::tenant::timesheet::domain::events::UserId;
+
+#[eventually_macros::aggregate_root(CalendarRule)]
+pub struct CalendarRuleCommand;
+
+impl CalendarRuleCommand {
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn add(
+        &self,
+        id: CalendarRuleId,
+        user_id: UserId,
+        keyword: String,
+        project_id: ProjectId,
+        activity_id: Option<ActivityId>,
+    ) -> Result<Self, crate::Error> {
+        Ok(aggregate::Root::<CalendarRule>::record_new(
+            CalendarRuleEvent::Added {
+                id,
+                user_id,
+                keyword,
+                project_id,
+                activity_id,
+            }
+            .into(),
+        )
+        .map_err(calendar_rule::DomainError::from)?
+        .into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn remove(&mut self) -> Result<(), crate::Error> {
+        self.record_that(CalendarRuleEvent::Removed.into())
+            .map_err(|e| calendar_rule::DomainError::AggregateError(e).into())
+    }
+}
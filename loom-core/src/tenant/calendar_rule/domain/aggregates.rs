@@ -0,0 +1,112 @@
+use eventually::aggregate::Aggregate;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::AggregateId;
+use crate::tenant::activity::ActivityId;
+use crate::tenant::calendar_rule::CalendarRuleEvent;
+use crate::tenant::project::ProjectId;
+use crate::tenant::timesheet::domain::events::UserId;
+
+pub type CalendarRuleId = AggregateId;
+
+/// Maps imported calendar events to a project, and optionally an activity,
+/// by a keyword of their title.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalendarRule {
+    id: CalendarRuleId,
+    user_id: UserId,
+    keyword: String,
+    project_id: ProjectId,
+    activity_id: Option<ActivityId>,
+    removed: bool,
+}
+
+impl CalendarRule {
+    #[must_use]
+    pub const fn id(&self) -> &CalendarRuleId {
+        &self.id
+    }
+    #[must_use]
+    pub const fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+    #[must_use]
+    pub fn keyword(&self) -> &str {
+        &self.keyword
+    }
+    #[must_use]
+    pub const fn project_id(&self) -> &ProjectId {
+        &self.project_id
+    }
+    #[must_use]
+    pub const fn activity_id(&self) -> Option<&ActivityId> {
+        self.activity_id.as_ref()
+    }
+}
+
+crate::aggregate_errors!("calendar rule");
+
+impl Aggregate for CalendarRule {
+    type Id = CalendarRuleId;
+    type Event = CalendarRuleEvent;
+    type Error = Error;
+
+    fn type_name() -> &'static str {
+        "calendar_rule"
+    }
+
+    fn aggregate_id(&self) -> &Self::Id {
+        &self.id
+    }
+
+    fn apply(state: Option<Self>, event: Self::Event) -> Result<Self, Self::Error> {
+        match (state, event) {
+            (
+                None,
+                CalendarRuleEvent::Added {
+                    id,
+                    user_id,
+                    keyword,
+                    project_id,
+                    activity_id,
+                },
+            ) => Ok(Self {
+                id,
+                user_id,
+                keyword,
+                project_id,
+                activity_id,
+                removed: false,
+            }),
+            (Some(_), CalendarRuleEvent::Added { .. }) => Err(Error::AlreadyExists),
+            (None, _) => Err(Error::NotFound),
+            (Some(r), _) if r.removed => Err(Error::NotFound),
+            (Some(r), CalendarRuleEvent::Removed) => Ok(Self { removed: true, ..r }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removed_rules_are_gone() {
+        let added = CalendarRule::apply(
+            None,
+            CalendarRuleEvent::Added {
+                id: CalendarRuleId::new(),
+                user_id: UserId::new(),
+                keyword: "Stand-up".to_string(),
+                project_id: ProjectId::new(),
+                activity_id: None,
+            },
+        )
+        .unwrap();
+        let removed = CalendarRule::apply(Some(added), CalendarRuleEvent::Removed).unwrap();
+        assert!(matches!(
+            CalendarRule::apply(Some(removed), CalendarRuleEvent::Removed),
+            Err(Error::NotFound)
+        ));
+    }
+}
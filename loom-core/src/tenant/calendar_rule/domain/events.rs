@@ -0,0 +1,30 @@
+use eventually::message::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::tenant::activity::ActivityId;
+use crate::tenant::calendar_rule::CalendarRuleId;
+use crate::tenant::project::ProjectId;
+use crate::tenant::timesheet::domain::events::UserId;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CalendarRuleEvent {
+    /// A user maps calendar events whose title contains `keyword` to a
+    /// project and activity.
+    Added {
+        id: CalendarRuleId,
+        user_id: UserId,
+        keyword: String,
+        project_id: ProjectId,
+        activity_id: Option<ActivityId>,
+    },
+    Removed,
+}
+
+impl Message for CalendarRuleEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Added { .. } => "CalendarRuleAdded",
+            Self::Removed => "CalendarRuleRemoved",
+        }
+    }
+}
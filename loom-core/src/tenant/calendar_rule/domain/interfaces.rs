@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+
+use crate::tenant::calendar_rule::domain::aggregates::CalendarRule;
+use eventually::aggregate::repository::{Getter, Saver};
+
+#[async_trait]
+pub trait CalendarRuleRepository: Getter<CalendarRule> + Saver<CalendarRule> + Send + Sync {}
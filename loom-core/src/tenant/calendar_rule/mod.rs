@@ -0,0 +1,16 @@
+pub(crate) mod application;
+pub(crate) mod domain;
+
+pub use application::commands::CalendarRuleCommand;
+pub use domain::{
+    Error as DomainError,
+    aggregates::{CalendarRule, CalendarRuleId},
+    events::CalendarRuleEvent,
+    interfaces::CalendarRuleRepository,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0:?}")]
+    DomainError(#[from] domain::Error),
+}
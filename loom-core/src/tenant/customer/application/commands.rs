@@ -7,6 +7,7 @@ use crate::tenant::customer::{
         events::CustomerEvent,
     },
 };
+use crate::tenant::timesheet::Rounding;
 
 #[eventually_macros::aggregate_root(Customer)]
 pub struct CustomerCommand;
@@ -80,4 +81,12 @@ impl CustomerCommand {
         )
         .map_err(|e| customer::DomainError::AggregateError(e).into())
     }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn set_rounding(&mut self, rounding: Option<Rounding>) -> Result<(), crate::Error> {
+        self.record_that(CustomerEvent::RoundingUpdated { rounding }.into())
+            .map_err(|e| customer::DomainError::AggregateError(e).into())
+    }
 }
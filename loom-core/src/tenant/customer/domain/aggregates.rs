@@ -1,7 +1,10 @@
 use eventually::aggregate::Aggregate;
 use serde::{Deserialize, Serialize};
 
-use crate::{shared::AggregateId, tenant::customer::CustomerEvent};
+use crate::{
+    shared::AggregateId,
+    tenant::{budget::ReachedThreshold, customer::CustomerEvent},
+};
 
 pub type CustomerId = AggregateId;
 
@@ -12,6 +15,13 @@ pub struct Customer {
     currency: String,
     timezone: String,
     visible: bool,
+    /// Budget thresholds already reported since the budget last changed.
+    #[serde(default)]
+    budget_alerts: Vec<ReachedThreshold>,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    deleted: bool,
 }
 
 impl Customer {
@@ -39,6 +49,19 @@ impl Customer {
     pub const fn visible(&self) -> bool {
         self.visible
     }
+
+    #[must_use]
+    pub const fn archived(&self) -> bool {
+        self.archived
+    }
+
+    /// Whether `threshold` has already been reported for `period`.
+    #[must_use]
+    pub fn has_reached(&self, period: Option<&str>, threshold: u8) -> bool {
+        self.budget_alerts
+            .iter()
+            .any(|a| a.threshold == threshold && a.period.as_deref() == period)
+    }
 }
 
 crate::aggregate_errors!("customer");
@@ -72,9 +95,13 @@ impl Aggregate for Customer {
                 currency,
                 timezone,
                 visible: true,
+                budget_alerts: Vec::new(),
+                archived: false,
+                deleted: false,
             }),
             (Some(_), CustomerEvent::Created { .. }) => Err(Error::AlreadyExists),
             (None, _) => Err(Error::NotFound),
+            (Some(customer), _) if customer.deleted => Err(Error::NotFound),
             (
                 Some(mut customer),
                 CustomerEvent::Updated {
@@ -91,7 +118,34 @@ impl Aggregate for Customer {
                 customer.visible = visible;
                 Ok(customer)
             }
-            (Some(customer), CustomerEvent::BudgetUpdated { .. }) => Ok(customer),
+            (Some(customer), CustomerEvent::RoundingUpdated { .. }) => Ok(customer),
+            (Some(mut customer), CustomerEvent::BudgetUpdated { .. }) => {
+                customer.budget_alerts.clear();
+                Ok(customer)
+            }
+            (
+                Some(mut customer),
+                CustomerEvent::BudgetThresholdReached {
+                    threshold, period, ..
+                },
+            ) => {
+                customer
+                    .budget_alerts
+                    .push(ReachedThreshold { period, threshold });
+                Ok(customer)
+            }
+            (Some(mut customer), CustomerEvent::Archived) => {
+                customer.archived = true;
+                Ok(customer)
+            }
+            (Some(mut customer), CustomerEvent::Restored) => {
+                customer.archived = false;
+                Ok(customer)
+            }
+            (Some(mut customer), CustomerEvent::Deleted) => {
+                customer.deleted = true;
+                Ok(customer)
+            }
         }
     }
 }
@@ -149,4 +203,23 @@ mod tests {
         );
         assert!(matches!(result, Err(Error::AlreadyExists)));
     }
+
+    #[test]
+    fn apply_after_deleted_returns_not_found() {
+        let created = Customer::apply(
+            None,
+            CustomerEvent::Created {
+                id: test_id(),
+                name: "Acme".to_string(),
+                currency: "EUR".to_string(),
+                timezone: "Europe/Berlin".to_string(),
+            },
+        )
+        .unwrap();
+        let archived = Customer::apply(Some(created), CustomerEvent::Archived).unwrap();
+        assert!(archived.archived());
+        let deleted = Customer::apply(Some(archived), CustomerEvent::Deleted).unwrap();
+        let result = Customer::apply(Some(deleted), CustomerEvent::Restored);
+        assert!(matches!(result, Err(Error::NotFound)));
+    }
 }
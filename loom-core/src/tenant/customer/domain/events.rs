@@ -2,6 +2,7 @@ use eventually::message::Message;
 use serde::{Deserialize, Serialize};
 
 use crate::tenant::customer::CustomerId;
+use crate::tenant::timesheet::Rounding;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CustomerEvent {
@@ -24,6 +25,28 @@ pub enum CustomerEvent {
         money_budget: Option<i64>,
         budget_is_monthly: bool,
     },
+    /// Rounding rules for the customer's entries; `None` falls back to the
+    /// workspace rules.
+    RoundingUpdated {
+        rounding: Option<Rounding>,
+    },
+    /// Usage crossed a warning threshold of the budget.  Recorded at most
+    /// once per threshold and period until the budget changes.
+    BudgetThresholdReached {
+        /// Percent of the budget, e.g. `80`.
+        threshold: u8,
+        /// `YYYY-MM` for monthly budgets, `None` for lifetime budgets.
+        period: Option<String>,
+        used_seconds: i64,
+        /// Amount in cents.
+        used_amount: i64,
+    },
+    /// Hidden from lists and selectors until restored.  Its projects are
+    /// archived along with it.
+    Archived,
+    Restored,
+    /// Permanently removed.  Only recorded while no timesheet references it.
+    Deleted,
 }
 
 impl Message for CustomerEvent {
@@ -32,6 +55,11 @@ impl Message for CustomerEvent {
             Self::Created { .. } => "CustomerCreated",
             Self::Updated { .. } => "CustomerUpdated",
             Self::BudgetUpdated { .. } => "CustomerBudgetUpdated",
+            Self::RoundingUpdated { .. } => "CustomerRoundingUpdated",
+            Self::BudgetThresholdReached { .. } => "CustomerBudgetThresholdReached",
+            Self::Archived => "CustomerArchived",
+            Self::Restored => "CustomerRestored",
+            Self::Deleted => "CustomerDeleted",
         }
     }
 }
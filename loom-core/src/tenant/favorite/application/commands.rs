@@ -0,0 +1,53 @@
+use eventually::aggregate;
+
+use crate::tenant::activity::ActivityId;
+use crate::tenant::favorite::{
+    self,
+    domain::{
+        aggregates::{Favorite, FavoriteId},
+        events::FavoriteEvent,
+    },
+};
+use crate::tenant::project::ProjectId;
+use crate::tenant::tag::TagId;
+use crate::tenant::timesheet::domain::events::UserId;
+
+#[eventually_macros::aggregate_root(Favorite)]
+pub struct FavoriteCommand;
+
+impl FavoriteCommand {
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn pin(
+        &self,
+        id: FavoriteId,
+        user_id: UserId,
+        project_id: ProjectId,
+        activity_id: ActivityId,
+        description: Option<String>,
+        tag_ids: Vec<TagId>,
+    ) -> Result<Self, crate::Error> {
+        Ok(aggregate::Root::<Favorite>::record_new(
+            FavoriteEvent::Pinned {
+                id,
+                user_id,
+                project_id,
+                activity_id,
+                description,
+                tag_ids,
+            }
+            .into(),
+        )
+        .map_err(favorite::DomainError::from)?
+        .into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn unpin(&mut self) -> Result<(), crate::Error> {
+        self.record_that(FavoriteEvent::Unpinned.into())
+            .map_err(|e| favorite::DomainError::AggregateError(e).into())
+    }
+}
@@ -0,0 +1,124 @@
+use eventually::aggregate::Aggregate;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::AggregateId;
+use crate::tenant::activity::ActivityId;
+use crate::tenant::favorite::FavoriteEvent;
+use crate::tenant::project::ProjectId;
+use crate::tenant::tag::TagId;
+use crate::tenant::timesheet::domain::events::UserId;
+
+pub type FavoriteId = AggregateId;
+
+/// A combination of project, activity, description and tags a user pinned
+/// for quickly starting timers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Favorite {
+    id: FavoriteId,
+    user_id: UserId,
+    project_id: ProjectId,
+    activity_id: ActivityId,
+    description: Option<String>,
+    tag_ids: Vec<TagId>,
+    unpinned: bool,
+}
+
+impl Favorite {
+    #[must_use]
+    pub const fn id(&self) -> &FavoriteId {
+        &self.id
+    }
+    #[must_use]
+    pub const fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+    #[must_use]
+    pub const fn project_id(&self) -> &ProjectId {
+        &self.project_id
+    }
+    #[must_use]
+    pub const fn activity_id(&self) -> &ActivityId {
+        &self.activity_id
+    }
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    #[must_use]
+    pub fn tag_ids(&self) -> &[TagId] {
+        &self.tag_ids
+    }
+}
+
+crate::aggregate_errors!("favorite");
+
+impl Aggregate for Favorite {
+    type Id = FavoriteId;
+    type Event = FavoriteEvent;
+    type Error = Error;
+
+    fn type_name() -> &'static str {
+        "favorite"
+    }
+
+    fn aggregate_id(&self) -> &Self::Id {
+        &self.id
+    }
+
+    fn apply(state: Option<Self>, event: Self::Event) -> Result<Self, Self::Error> {
+        match (state, event) {
+            (
+                None,
+                FavoriteEvent::Pinned {
+                    id,
+                    user_id,
+                    project_id,
+                    activity_id,
+                    description,
+                    tag_ids,
+                },
+            ) => Ok(Self {
+                id,
+                user_id,
+                project_id,
+                activity_id,
+                description,
+                tag_ids,
+                unpinned: false,
+            }),
+            (Some(_), FavoriteEvent::Pinned { .. }) => Err(Error::AlreadyExists),
+            (None, _) => Err(Error::NotFound),
+            (Some(f), _) if f.unpinned => Err(Error::NotFound),
+            (Some(f), FavoriteEvent::Unpinned) => Ok(Self {
+                unpinned: true,
+                ..f
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpinned_favorites_are_gone() {
+        let pinned = Favorite::apply(
+            None,
+            FavoriteEvent::Pinned {
+                id: FavoriteId::new(),
+                user_id: UserId::new(),
+                project_id: ProjectId::new(),
+                activity_id: ActivityId::new(),
+                description: Some("Stand-up".to_string()),
+                tag_ids: vec![TagId::new()],
+            },
+        )
+        .unwrap();
+        let unpinned = Favorite::apply(Some(pinned), FavoriteEvent::Unpinned).unwrap();
+        assert!(matches!(
+            Favorite::apply(Some(unpinned), FavoriteEvent::Unpinned),
+            Err(Error::NotFound)
+        ));
+    }
+}
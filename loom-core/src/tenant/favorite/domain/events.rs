@@ -0,0 +1,31 @@
+use eventually::message::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::tenant::activity::ActivityId;
+use crate::tenant::favorite::FavoriteId;
+use crate::tenant::project::ProjectId;
+use crate::tenant::tag::TagId;
+use crate::tenant::timesheet::domain::events::UserId;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FavoriteEvent {
+    /// A user pins a combination to start timers from.
+    Pinned {
+        id: FavoriteId,
+        user_id: UserId,
+        project_id: ProjectId,
+        activity_id: ActivityId,
+        description: Option<String>,
+        tag_ids: Vec<TagId>,
+    },
+    Unpinned,
+}
+
+impl Message for FavoriteEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Pinned { .. } => "FavoritePinned",
+            Self::Unpinned => "FavoriteUnpinned",
+        }
+    }
+}
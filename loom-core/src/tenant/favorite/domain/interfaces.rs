@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+
+use crate::tenant::favorite::domain::aggregates::Favorite;
+use eventually::aggregate::repository::{Getter, Saver};
+
+#[async_trait]
+pub trait FavoriteRepository: Getter<Favorite> + Saver<Favorite> + Send + Sync {}
@@ -0,0 +1,16 @@
+pub(crate) mod application;
+pub(crate) mod domain;
+
+pub use application::commands::FavoriteCommand;
+pub use domain::{
+    Error as DomainError,
+    aggregates::{Favorite, FavoriteId},
+    events::FavoriteEvent,
+    interfaces::FavoriteRepository,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0:?}")]
+    DomainError(#[from] domain::Error),
+}
@@ -0,0 +1,45 @@
+use eventually::aggregate;
+
+use crate::tenant::holiday::{
+    self,
+    domain::{
+        aggregates::{Holiday, HolidayId},
+        events::HolidayEvent,
+    },
+};
+
+#[eventually_macros::aggregate_root(Holiday)]
+pub struct HolidayCommand;
+
+impl HolidayCommand {
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn declare(
+        &self,
+        id: HolidayId,
+        region: String,
+        date: String,
+        name: String,
+    ) -> Result<Self, crate::Error> {
+        Ok(aggregate::Root::<Holiday>::record_new(
+            HolidayEvent::Declared {
+                id,
+                region,
+                date,
+                name,
+            }
+            .into(),
+        )
+        .map_err(holiday::DomainError::from)?
+        .into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn remove(&mut self) -> Result<(), crate::Error> {
+        self.record_that(HolidayEvent::Removed.into())
+            .map_err(|e| holiday::DomainError::AggregateError(e).into())
+    }
+}
@@ -0,0 +1,76 @@
+use eventually::aggregate::Aggregate;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::AggregateId;
+use crate::tenant::holiday::HolidayEvent;
+
+pub type HolidayId = AggregateId;
+
+/// A public holiday of a region, defined by the workspace itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Holiday {
+    id: HolidayId,
+    region: String,
+    date: String,
+    name: String,
+    removed: bool,
+}
+
+impl Holiday {
+    #[must_use]
+    pub const fn id(&self) -> &HolidayId {
+        &self.id
+    }
+    #[must_use]
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+    #[must_use]
+    pub fn date(&self) -> &str {
+        &self.date
+    }
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+crate::aggregate_errors!("holiday");
+
+impl Aggregate for Holiday {
+    type Id = HolidayId;
+    type Event = HolidayEvent;
+    type Error = Error;
+
+    fn type_name() -> &'static str {
+        "holiday"
+    }
+
+    fn aggregate_id(&self) -> &Self::Id {
+        &self.id
+    }
+
+    fn apply(state: Option<Self>, event: Self::Event) -> Result<Self, Self::Error> {
+        match (state, event) {
+            (
+                None,
+                HolidayEvent::Declared {
+                    id,
+                    region,
+                    date,
+                    name,
+                },
+            ) => Ok(Self {
+                id,
+                region,
+                date,
+                name,
+                removed: false,
+            }),
+            (Some(_), HolidayEvent::Declared { .. }) => Err(Error::AlreadyExists),
+            (None, _) => Err(Error::NotFound),
+            (Some(h), _) if h.removed => Err(Error::NotFound),
+            (Some(h), HolidayEvent::Removed) => Ok(Self { removed: true, ..h }),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use eventually::message::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::tenant::holiday::HolidayId;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HolidayEvent {
+    /// A public holiday on which nobody in `region` is expected to work.
+    Declared {
+        id: HolidayId,
+        region: String,
+        /// `YYYY-MM-DD`.
+        date: String,
+        name: String,
+    },
+    Removed,
+}
+
+impl Message for HolidayEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Declared { .. } => "HolidayDeclared",
+            Self::Removed => "HolidayRemoved",
+        }
+    }
+}
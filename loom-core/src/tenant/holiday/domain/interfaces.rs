@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+
+use crate::tenant::holiday::domain::aggregates::Holiday;
+use eventually::aggregate::repository::{Getter, Saver};
+
+#[async_trait]
+pub trait HolidayRepository: Getter<Holiday> + Saver<Holiday> + Send + Sync {}
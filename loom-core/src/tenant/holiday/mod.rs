@@ -0,0 +1,16 @@
+pub(crate) mod application;
+pub(crate) mod domain;
+
+pub use application::commands::HolidayCommand;
+pub use domain::{
+    Error as DomainError,
+    aggregates::{Holiday, HolidayId},
+    events::HolidayEvent,
+    interfaces::HolidayRepository,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0:?}")]
+    DomainError(#[from] domain::Error),
+}
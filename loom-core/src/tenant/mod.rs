@@ -1,10 +1,21 @@
 pub mod activity;
 pub mod activity_rate;
+pub mod approval;
+pub mod budget;
+pub mod calendar;
+pub mod calendar_rule;
 pub mod customer;
+pub mod favorite;
+pub mod holiday;
+pub mod overtime;
 pub mod project;
 pub mod project_rate;
+pub mod report;
+pub mod report_schedule;
 pub mod tag;
 pub mod timesheet;
+pub mod webhook;
+pub mod work_contract;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -13,15 +24,29 @@ pub enum Error {
     #[error("{0:?}")]
     ActivityRateError(#[from] activity_rate::Error),
     #[error("{0:?}")]
+    ApprovalError(#[from] approval::Error),
+    #[error("{0:?}")]
+    CalendarRuleError(#[from] calendar_rule::Error),
+    #[error("{0:?}")]
     CustomerError(#[from] customer::Error),
     #[error("{0:?}")]
+    FavoriteError(#[from] favorite::Error),
+    #[error("{0:?}")]
+    HolidayError(#[from] holiday::Error),
+    #[error("{0:?}")]
     ProjectError(#[from] project::Error),
     #[error("{0:?}")]
     ProjectRateError(#[from] project_rate::Error),
     #[error("{0:?}")]
+    ReportScheduleError(#[from] report_schedule::Error),
+    #[error("{0:?}")]
     TagError(#[from] tag::Error),
     #[error("{0:?}")]
     TimesheetError(#[from] timesheet::Error),
+    #[error("{0:?}")]
+    WebhookError(#[from] webhook::Error),
+    #[error("{0:?}")]
+    WorkContractError(#[from] work_contract::Error),
 }
 
 impl From<activity::DomainError> for crate::Error {
@@ -36,12 +61,36 @@ impl From<activity_rate::DomainError> for crate::Error {
     }
 }
 
+impl From<approval::DomainError> for crate::Error {
+    fn from(value: approval::DomainError) -> Self {
+        Self::TenantDatabaseError(Error::ApprovalError(value.into()))
+    }
+}
+
+impl From<calendar_rule::DomainError> for crate::Error {
+    fn from(value: calendar_rule::DomainError) -> Self {
+        Self::TenantDatabaseError(Error::CalendarRuleError(value.into()))
+    }
+}
+
 impl From<customer::DomainError> for crate::Error {
     fn from(value: customer::DomainError) -> Self {
         Self::TenantDatabaseError(Error::CustomerError(value.into()))
     }
 }
 
+impl From<favorite::DomainError> for crate::Error {
+    fn from(value: favorite::DomainError) -> Self {
+        Self::TenantDatabaseError(Error::FavoriteError(value.into()))
+    }
+}
+
+impl From<holiday::DomainError> for crate::Error {
+    fn from(value: holiday::DomainError) -> Self {
+        Self::TenantDatabaseError(Error::HolidayError(value.into()))
+    }
+}
+
 impl From<project::DomainError> for crate::Error {
     fn from(value: project::DomainError) -> Self {
         Self::TenantDatabaseError(Error::ProjectError(value.into()))
@@ -54,6 +103,12 @@ impl From<project_rate::DomainError> for crate::Error {
     }
 }
 
+impl From<report_schedule::DomainError> for crate::Error {
+    fn from(value: report_schedule::DomainError) -> Self {
+        Self::TenantDatabaseError(Error::ReportScheduleError(value.into()))
+    }
+}
+
 impl From<tag::DomainError> for crate::Error {
     fn from(value: tag::DomainError) -> Self {
         Self::TenantDatabaseError(Error::TagError(value.into()))
@@ -65,3 +120,15 @@ impl From<timesheet::DomainError> for crate::Error {
         Self::TenantDatabaseError(Error::TimesheetError(value.into()))
     }
 }
+
+impl From<webhook::DomainError> for crate::Error {
+    fn from(value: webhook::DomainError) -> Self {
+        Self::TenantDatabaseError(Error::WebhookError(value.into()))
+    }
+}
+
+impl From<work_contract::DomainError> for crate::Error {
+    fn from(value: work_contract::DomainError) -> Self {
+        Self::TenantDatabaseError(Error::WorkContractError(value.into()))
+    }
+}
@@ -0,0 +1,217 @@
+//! Expected working time and the overtime balance of a user.
+//!
+//! A work contract says how many minutes a week a user is expected to work
+//! and on which weekdays.  The contract with the latest start date on or
+//! before a day governs that day; its weekly minutes are spread evenly over
+//! its working days, and public holidays of its region expect nothing.
+//! Worked time is counted on the UTC calendar day an entry started, like
+//! budget periods.
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// The part of a work contract that determines expected time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    pub starts_on: NaiveDate,
+    pub weekly_minutes: i32,
+    /// ISO weekday numbers, 1 (Monday) to 7 (Sunday).
+    pub working_days: Vec<u8>,
+    pub region: Option<String>,
+}
+
+/// Public holidays as `(region, date)` pairs.
+pub type Holidays = HashSet<(String, NaiveDate)>;
+
+/// Expected and worked time of one week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeekBalance {
+    /// First day of the week.
+    pub week: NaiveDate,
+    pub expected_seconds: i64,
+    pub worked_seconds: i64,
+}
+
+impl WeekBalance {
+    /// Overtime if positive, undertime if negative.
+    #[must_use]
+    pub const fn balance_seconds(&self) -> i64 {
+        self.worked_seconds - self.expected_seconds
+    }
+}
+
+/// The first day of the week for the workspace `week_start` setting
+/// (`monday`, `sunday` or `saturday`).  Anything else means Monday.
+#[must_use]
+pub fn first_weekday(week_start: &str) -> Weekday {
+    match week_start {
+        "sunday" => Weekday::Sun,
+        "saturday" => Weekday::Sat,
+        _ => Weekday::Mon,
+    }
+}
+
+/// The first day of the week `day` falls into.
+#[must_use]
+pub fn week_of(day: NaiveDate, first: Weekday) -> NaiveDate {
+    let offset = (7 + day.weekday().num_days_from_monday() - first.num_days_from_monday()) % 7;
+    day - Duration::days(i64::from(offset))
+}
+
+/// The schedule governing `day`, if any contract has started by then.
+#[must_use]
+pub fn schedule_on(day: NaiveDate, schedules: &[Schedule]) -> Option<&Schedule> {
+    schedules
+        .iter()
+        .filter(|s| s.starts_on <= day)
+        .max_by_key(|s| s.starts_on)
+}
+
+/// Seconds a user is expected to work on `day`.
+#[must_use]
+pub fn expected_seconds(day: NaiveDate, schedules: &[Schedule], holidays: &Holidays) -> i64 {
+    let Some(schedule) = schedule_on(day, schedules) else {
+        return 0;
+    };
+    let weekday = u8::try_from(day.weekday().number_from_monday()).unwrap_or_default();
+    if !schedule.working_days.contains(&weekday) {
+        return 0;
+    }
+    if let Some(region) = &schedule.region
+        && holidays.contains(&(region.clone(), day))
+    {
+        return 0;
+    }
+    let days = i64::try_from(schedule.working_days.len())
+        .unwrap_or(1)
+        .max(1);
+    i64::from(schedule.weekly_minutes) * 60 / days
+}
+
+/// Expected and worked time per week, from the start of the earliest
+/// contract up to and including `until`.
+///
+/// `worked` holds the worked seconds per day; days before the first contract
+/// are not counted.  Weeks are returned oldest first.
+#[must_use]
+pub fn weekly_balances(
+    schedules: &[Schedule],
+    holidays: &Holidays,
+    worked: &BTreeMap<NaiveDate, i64>,
+    until: NaiveDate,
+    first: Weekday,
+) -> Vec<WeekBalance> {
+    let Some(start) = schedules.iter().map(|s| s.starts_on).min() else {
+        return Vec::new();
+    };
+    let mut weeks: BTreeMap<NaiveDate, WeekBalance> = BTreeMap::new();
+    let mut day = start;
+    while day <= until {
+        let week = week_of(day, first);
+        let entry = weeks.entry(week).or_insert(WeekBalance {
+            week,
+            expected_seconds: 0,
+            worked_seconds: 0,
+        });
+        entry.expected_seconds += expected_seconds(day, schedules, holidays);
+        entry.worked_seconds += worked.get(&day).copied().unwrap_or_default();
+        day += Duration::days(1);
+    }
+    weeks.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").expect("valid date")
+    }
+
+    fn full_time(starts_on: &str) -> Schedule {
+        Schedule {
+            starts_on: date(starts_on),
+            weekly_minutes: 40 * 60,
+            working_days: vec![1, 2, 3, 4, 5],
+            region: Some("BY".to_string()),
+        }
+    }
+
+    #[test]
+    fn weekly_minutes_are_spread_over_working_days_except_holidays() {
+        let schedules = [full_time("2026-10-01")];
+        let holidays: Holidays = [("BY".to_string(), date("2026-10-19"))].into();
+        assert_eq!(
+            expected_seconds(date("2026-10-19"), &schedules, &holidays),
+            0
+        );
+        assert_eq!(
+            expected_seconds(date("2026-10-20"), &schedules, &holidays),
+            8 * 3600
+        );
+        assert_eq!(
+            expected_seconds(date("2026-10-24"), &schedules, &holidays),
+            0
+        );
+        assert_eq!(
+            expected_seconds(date("2026-09-30"), &schedules, &holidays),
+            0
+        );
+    }
+
+    #[test]
+    fn later_contracts_supersede_earlier_ones() {
+        let part_time = Schedule {
+            starts_on: date("2026-10-12"),
+            weekly_minutes: 20 * 60,
+            working_days: vec![1, 2],
+            region: None,
+        };
+        let schedules = [full_time("2026-10-01"), part_time];
+        let none = Holidays::new();
+        assert_eq!(
+            expected_seconds(date("2026-10-06"), &schedules, &none),
+            8 * 3600
+        );
+        assert_eq!(
+            expected_seconds(date("2026-10-13"), &schedules, &none),
+            10 * 3600
+        );
+        assert_eq!(expected_seconds(date("2026-10-14"), &schedules, &none), 0);
+    }
+
+    #[test]
+    fn weeks_follow_the_workspace_week_start() {
+        // 2026-10-18 is a Sunday.
+        assert_eq!(
+            week_of(date("2026-10-18"), Weekday::Mon),
+            date("2026-10-12")
+        );
+        assert_eq!(
+            week_of(date("2026-10-18"), Weekday::Sun),
+            date("2026-10-18")
+        );
+        assert_eq!(
+            week_of(date("2026-10-16"), first_weekday("saturday")),
+            date("2026-10-10")
+        );
+
+        let worked: BTreeMap<_, _> = [
+            (date("2026-10-16"), 9 * 3600),
+            (date("2026-10-19"), 7 * 3600),
+        ]
+        .into();
+        let weeks = weekly_balances(
+            &[full_time("2026-10-15")],
+            &Holidays::new(),
+            &worked,
+            date("2026-10-19"),
+            Weekday::Sun,
+        );
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(weeks[0].week, date("2026-10-11"));
+        assert_eq!(weeks[0].balance_seconds(), -7 * 3600);
+        assert_eq!(weeks[1].balance_seconds(), -3600);
+    }
+}
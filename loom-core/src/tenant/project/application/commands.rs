@@ -8,6 +8,7 @@ use crate::tenant::project::{
         events::ProjectEvent,
     },
 };
+use crate::tenant::timesheet::Rounding;
 
 #[eventually_macros::aggregate_root(Project)]
 pub struct ProjectCommand;
@@ -77,4 +78,12 @@ impl ProjectCommand {
         )
         .map_err(|e| project::DomainError::AggregateError(e).into())
     }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn set_rounding(&mut self, rounding: Option<Rounding>) -> Result<(), crate::Error> {
+        self.record_that(ProjectEvent::RoundingUpdated { rounding }.into())
+            .map_err(|e| project::DomainError::AggregateError(e).into())
+    }
 }
@@ -2,6 +2,7 @@ use eventually::aggregate::Aggregate;
 use serde::{Deserialize, Serialize};
 
 use crate::shared::AggregateId;
+use crate::tenant::budget::ReachedThreshold;
 use crate::tenant::customer::CustomerId;
 use crate::tenant::project::ProjectEvent;
 
@@ -14,6 +15,16 @@ pub struct Project {
     name: String,
     visible: bool,
     billable: bool,
+    /// Budget thresholds already reported since the budget last changed.
+    #[serde(default)]
+    budget_alerts: Vec<ReachedThreshold>,
+    #[serde(default)]
+    archived: bool,
+    /// Archived together with its customer rather than on its own.
+    #[serde(default)]
+    archived_with_customer: bool,
+    #[serde(default)]
+    deleted: bool,
 }
 
 impl Project {
@@ -37,6 +48,22 @@ impl Project {
     pub const fn billable(&self) -> bool {
         self.billable
     }
+    #[must_use]
+    pub const fn archived(&self) -> bool {
+        self.archived
+    }
+    #[must_use]
+    pub const fn archived_with_customer(&self) -> bool {
+        self.archived_with_customer
+    }
+
+    /// Whether `threshold` has already been reported for `period`.
+    #[must_use]
+    pub fn has_reached(&self, period: Option<&str>, threshold: u8) -> bool {
+        self.budget_alerts
+            .iter()
+            .any(|a| a.threshold == threshold && a.period.as_deref() == period)
+    }
 }
 
 crate::aggregate_errors!("project");
@@ -69,9 +96,14 @@ impl Aggregate for Project {
                 name,
                 visible: true,
                 billable: true,
+                budget_alerts: Vec::new(),
+                archived: false,
+                archived_with_customer: false,
+                deleted: false,
             }),
             (Some(_), ProjectEvent::Created { .. }) => Err(Error::AlreadyExists),
             (None, _) => Err(Error::NotFound),
+            (Some(p), _) if p.deleted => Err(Error::NotFound),
             (
                 Some(mut p),
                 ProjectEvent::Updated {
@@ -86,7 +118,34 @@ impl Aggregate for Project {
                 p.billable = billable;
                 Ok(p)
             }
-            (Some(p), ProjectEvent::BudgetUpdated { .. }) => Ok(p),
+            (Some(p), ProjectEvent::RoundingUpdated { .. }) => Ok(p),
+            (Some(mut p), ProjectEvent::BudgetUpdated { .. }) => {
+                p.budget_alerts.clear();
+                Ok(p)
+            }
+            (
+                Some(mut p),
+                ProjectEvent::BudgetThresholdReached {
+                    threshold, period, ..
+                },
+            ) => {
+                p.budget_alerts.push(ReachedThreshold { period, threshold });
+                Ok(p)
+            }
+            (Some(mut p), ProjectEvent::Archived { with_customer }) => {
+                p.archived = true;
+                p.archived_with_customer = with_customer;
+                Ok(p)
+            }
+            (Some(mut p), ProjectEvent::Restored) => {
+                p.archived = false;
+                p.archived_with_customer = false;
+                Ok(p)
+            }
+            (Some(mut p), ProjectEvent::Deleted) => {
+                p.deleted = true;
+                Ok(p)
+            }
         }
     }
 }
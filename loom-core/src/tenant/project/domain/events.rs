@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::tenant::customer::CustomerId;
 use crate::tenant::project::ProjectId;
+use crate::tenant::timesheet::Rounding;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProjectEvent {
@@ -23,6 +24,31 @@ pub enum ProjectEvent {
         money_budget: Option<i64>,
         budget_is_monthly: bool,
     },
+    /// Rounding rules for the project's entries; `None` falls back to the
+    /// customer's rules.
+    RoundingUpdated {
+        rounding: Option<Rounding>,
+    },
+    /// Usage crossed a warning threshold of the budget.  Recorded at most
+    /// once per threshold and period until the budget changes.
+    BudgetThresholdReached {
+        /// Percent of the budget, e.g. `80`.
+        threshold: u8,
+        /// `YYYY-MM` for monthly budgets, `None` for lifetime budgets.
+        period: Option<String>,
+        used_seconds: i64,
+        /// Amount in cents.
+        used_amount: i64,
+    },
+    /// Hidden from lists and selectors until restored.
+    Archived {
+        /// Archived as part of archiving its customer; restoring the
+        /// customer restores the project as well.
+        with_customer: bool,
+    },
+    Restored,
+    /// Permanently removed.  Only recorded while no timesheet references it.
+    Deleted,
 }
 
 impl Message for ProjectEvent {
@@ -31,6 +57,11 @@ impl Message for ProjectEvent {
             Self::Created { .. } => "ProjectCreated",
             Self::Updated { .. } => "ProjectUpdated",
             Self::BudgetUpdated { .. } => "ProjectBudgetUpdated",
+            Self::RoundingUpdated { .. } => "ProjectRoundingUpdated",
+            Self::BudgetThresholdReached { .. } => "ProjectBudgetThresholdReached",
+            Self::Archived { .. } => "ProjectArchived",
+            Self::Restored => "ProjectRestored",
+            Self::Deleted => "ProjectDeleted",
         }
     }
 }
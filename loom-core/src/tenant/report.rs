@@ -0,0 +1,350 @@
+//! Hours and amounts per project over a period, rendered for email.
+//!
+//! A report covers stopped entries that started within its period, counted
+//! on their UTC start like budget periods.  Amounts are the entries' `rate`
+//! in the currency of the project's customer; lines are never summed across
+//! currencies.
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::tenant::overtime::week_of;
+
+/// The days a report covers, relative to the day it is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportPeriod {
+    PreviousDay,
+    /// The last full week, starting on the workspace's first weekday.
+    PreviousWeek,
+    PreviousMonth,
+}
+
+impl ReportPeriod {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::PreviousDay => "previous_day",
+            Self::PreviousWeek => "previous_week",
+            Self::PreviousMonth => "previous_month",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "previous_day" => Some(Self::PreviousDay),
+            "previous_week" => Some(Self::PreviousWeek),
+            "previous_month" => Some(Self::PreviousMonth),
+            _ => None,
+        }
+    }
+
+    /// The first day covered and the day after the last one, for a report
+    /// sent on `today`.
+    #[must_use]
+    pub fn range(self, today: NaiveDate, first: Weekday) -> (NaiveDate, NaiveDate) {
+        match self {
+            Self::PreviousDay => (today - Duration::days(1), today),
+            Self::PreviousWeek => {
+                let this_week = week_of(today, first);
+                (this_week - Duration::days(7), this_week)
+            }
+            Self::PreviousMonth => {
+                let this_month = today.with_day(1).unwrap_or(today);
+                let previous = (this_month - Duration::days(1))
+                    .with_day(1)
+                    .unwrap_or(today);
+                (previous, this_month)
+            }
+        }
+    }
+}
+
+/// The time and amount booked on one project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectLine {
+    pub customer: String,
+    pub project: String,
+    pub seconds: i64,
+    /// In cents.
+    pub amount: i64,
+    pub currency: String,
+}
+
+/// Where a project belongs, for [`summarize`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectInfo {
+    pub customer: String,
+    pub project: String,
+    pub currency: String,
+}
+
+/// One line per project of `entries`, given as `(project_id, seconds,
+/// amount)`, ordered by customer and project.  Entries without a known
+/// project are collected in a line without customer, in `fallback_currency`.
+#[must_use]
+pub fn summarize<'a>(
+    entries: impl IntoIterator<Item = (Option<&'a str>, i64, i64)>,
+    project: impl Fn(&str) -> Option<ProjectInfo>,
+    fallback_currency: &str,
+) -> Vec<ProjectLine> {
+    let mut lines: BTreeMap<(String, String, String), (i64, i64)> = BTreeMap::new();
+    for (project_id, seconds, amount) in entries {
+        let info = project_id
+            .and_then(&project)
+            .unwrap_or_else(|| ProjectInfo {
+                customer: String::new(),
+                project: "No project".to_string(),
+                currency: fallback_currency.to_string(),
+            });
+        let totals = lines
+            .entry((info.customer, info.project, info.currency))
+            .or_default();
+        totals.0 += seconds;
+        totals.1 += amount;
+    }
+    lines
+        .into_iter()
+        .map(
+            |((customer, project, currency), (seconds, amount))| ProjectLine {
+                customer,
+                project,
+                seconds,
+                amount,
+                currency,
+            },
+        )
+        .collect()
+}
+
+/// Totals of `lines` per currency.
+#[must_use]
+pub fn totals(lines: &[ProjectLine]) -> Vec<(String, i64, i64)> {
+    let mut totals: BTreeMap<&str, (i64, i64)> = BTreeMap::new();
+    for line in lines {
+        let total = totals.entry(&line.currency).or_default();
+        total.0 += line.seconds;
+        total.1 += line.amount;
+    }
+    totals
+        .into_iter()
+        .map(|(currency, (seconds, amount))| (currency.to_string(), seconds, amount))
+        .collect()
+}
+
+/// Seconds as decimal hours, e.g. `7.50`.
+#[must_use]
+pub fn hours(seconds: i64) -> String {
+    let hundredths = (seconds * 100 + 1800) / 3600;
+    format!("{}.{:02}", hundredths / 100, hundredths % 100)
+}
+
+/// Cents as a decimal amount, e.g. `1234.50`.
+#[must_use]
+pub fn amount(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    format!("{sign}{}.{:02}", cents.abs() / 100, cents.abs() % 100)
+}
+
+/// The lines as CSV (RFC 4180) with a header row.
+#[must_use]
+pub fn render_csv(lines: &[ProjectLine]) -> String {
+    let mut out = String::from("Customer,Project,Hours,Amount,Currency\r\n");
+    for line in lines {
+        let cells = [
+            csv_cell(&line.customer),
+            csv_cell(&line.project),
+            hours(line.seconds),
+            amount(line.amount),
+            csv_cell(&line.currency),
+        ];
+        out.push_str(&cells.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// The report as an HTML document titled `title`, covering `from` up to and
+/// including `until`.
+#[must_use]
+pub fn render_html(
+    title: &str,
+    from: NaiveDate,
+    until: NaiveDate,
+    lines: &[ProjectLine],
+) -> String {
+    let cell = "padding:4px 12px;border-bottom:1px solid #ddd";
+    let number = format!("{cell};text-align:right");
+    let mut rows = String::new();
+    for line in lines {
+        rows.push_str(&format!(
+            "<tr><td style=\"{cell}\">{}</td><td style=\"{cell}\">{}</td>\
+             <td style=\"{number}\">{}</td><td style=\"{number}\">{} {}</td></tr>",
+            escape(&line.customer),
+            escape(&line.project),
+            hours(line.seconds),
+            amount(line.amount),
+            escape(&line.currency),
+        ));
+    }
+    for (currency, seconds, total) in totals(lines) {
+        rows.push_str(&format!(
+            "<tr style=\"font-weight:bold\"><td style=\"{cell}\" colspan=\"2\">Total</td>\
+             <td style=\"{number}\">{}</td><td style=\"{number}\">{} {}</td></tr>",
+            hours(seconds),
+            amount(total),
+            escape(&currency),
+        ));
+    }
+    if lines.is_empty() {
+        rows.push_str(&format!(
+            "<tr><td style=\"{cell}\" colspan=\"4\">No time was tracked.</td></tr>"
+        ));
+    }
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{title}</title></head>\
+         <body style=\"font-family:sans-serif;color:#222\">\
+         <h2>{title}</h2><p>{from} to {until}</p>\
+         <table style=\"border-collapse:collapse\"><thead><tr>\
+         <th style=\"{cell};text-align:left\">Customer</th>\
+         <th style=\"{cell};text-align:left\">Project</th>\
+         <th style=\"{number}\">Hours</th><th style=\"{number}\">Amount</th>\
+         </tr></thead><tbody>{rows}</tbody></table></body></html>",
+        title = escape(title),
+    )
+}
+
+/// The report as plain text, for mail clients without HTML.
+#[must_use]
+pub fn render_text(
+    title: &str,
+    from: NaiveDate,
+    until: NaiveDate,
+    lines: &[ProjectLine],
+) -> String {
+    let mut out = format!("{title}\n{from} to {until}\n\n");
+    if lines.is_empty() {
+        out.push_str("No time was tracked.\n");
+    }
+    for line in lines {
+        let name = if line.customer.is_empty() {
+            line.project.clone()
+        } else {
+            format!("{} / {}", line.customer, line.project)
+        };
+        out.push_str(&format!(
+            "{name}: {} h, {} {}\n",
+            hours(line.seconds),
+            amount(line.amount),
+            line.currency
+        ));
+    }
+    for (currency, seconds, total) in totals(lines) {
+        out.push_str(&format!(
+            "Total: {} h, {} {currency}\n",
+            hours(seconds),
+            amount(total)
+        ));
+    }
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn periods_end_before_the_day_the_report_is_sent() {
+        let monday = date("2026-10-19");
+        assert_eq!(
+            ReportPeriod::PreviousWeek.range(monday, Weekday::Mon),
+            (date("2026-10-12"), date("2026-10-19"))
+        );
+        assert_eq!(
+            ReportPeriod::PreviousWeek.range(monday, Weekday::Sun),
+            (date("2026-10-11"), date("2026-10-18"))
+        );
+        assert_eq!(
+            ReportPeriod::PreviousMonth.range(date("2026-03-01"), Weekday::Mon),
+            (date("2026-02-01"), date("2026-03-01"))
+        );
+        assert_eq!(
+            ReportPeriod::PreviousDay.range(monday, Weekday::Mon),
+            (date("2026-10-18"), monday)
+        );
+    }
+
+    #[test]
+    fn lines_are_grouped_per_project_and_currency() {
+        let project = |id: &str| {
+            (id != "gone").then(|| ProjectInfo {
+                customer: "ACME".to_string(),
+                project: format!("Project {id}"),
+                currency: "EUR".to_string(),
+            })
+        };
+        let lines = summarize(
+            [
+                (Some("b"), 3600, 10_000),
+                (Some("a"), 1800, 5_000),
+                (Some("b"), 1800, 5_000),
+                (None, 900, 0),
+            ],
+            project,
+            "USD",
+        );
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].project, "No project");
+        assert_eq!(lines[0].currency, "USD");
+        assert_eq!((lines[2].seconds, lines[2].amount), (5400, 15_000));
+        assert_eq!(
+            totals(&lines),
+            vec![
+                ("EUR".to_string(), 7200, 20_000),
+                ("USD".to_string(), 900, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_and_html_escape_names() {
+        let lines = vec![ProjectLine {
+            customer: "Smith, Jones & Co".to_string(),
+            project: "<Relaunch>".to_string(),
+            seconds: 27_000,
+            amount: 123_450,
+            currency: "EUR".to_string(),
+        }];
+        assert_eq!(
+            render_csv(&lines),
+            "Customer,Project,Hours,Amount,Currency\r\n\
+             \"Smith, Jones & Co\",<Relaunch>,7.50,1234.50,EUR\r\n"
+        );
+        let html = render_html("Weekly", date("2026-10-12"), date("2026-10-18"), &lines);
+        assert!(html.contains("Smith, Jones &amp; Co"));
+        assert!(html.contains("&lt;Relaunch&gt;"));
+        assert!(html.contains("1234.50 EUR"));
+    }
+}
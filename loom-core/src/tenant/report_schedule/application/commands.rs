@@ -0,0 +1,79 @@
+use eventually::aggregate;
+
+use crate::tenant::report_schedule::{
+    self, ReportFormat, ReportParameters, ReportScope,
+    domain::{
+        aggregates::{ReportSchedule, ReportScheduleId},
+        events::ReportScheduleEvent,
+    },
+};
+use crate::tenant::timesheet::domain::events::UserId;
+
+#[eventually_macros::aggregate_root(ReportSchedule)]
+pub struct ReportScheduleCommand;
+
+impl ReportScheduleCommand {
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        &self,
+        id: ReportScheduleId,
+        owner_id: UserId,
+        name: String,
+        scope: ReportScope,
+        schedule: String,
+        parameters: ReportParameters,
+        format: ReportFormat,
+        recipients: Vec<String>,
+    ) -> Result<Self, crate::Error> {
+        Ok(aggregate::Root::<ReportSchedule>::record_new(
+            ReportScheduleEvent::Created {
+                id,
+                owner_id,
+                name,
+                scope,
+                schedule,
+                parameters,
+                format,
+                recipients,
+            }
+            .into(),
+        )
+        .map_err(report_schedule::DomainError::from)?
+        .into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn change(
+        &mut self,
+        name: String,
+        schedule: String,
+        parameters: ReportParameters,
+        format: ReportFormat,
+        recipients: Vec<String>,
+    ) -> Result<(), crate::Error> {
+        self.record_that(
+            ReportScheduleEvent::Changed {
+                name,
+                schedule,
+                parameters,
+                format,
+                recipients,
+            }
+            .into(),
+        )
+        .map_err(|e| report_schedule::DomainError::AggregateError(e).into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn remove(&mut self) -> Result<(), crate::Error> {
+        self.record_that(ReportScheduleEvent::Removed.into())
+            .map_err(|e| report_schedule::DomainError::AggregateError(e).into())
+    }
+}
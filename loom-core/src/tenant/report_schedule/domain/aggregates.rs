@@ -0,0 +1,191 @@
+use eventually::aggregate::Aggregate;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::AggregateId;
+use crate::tenant::project::ProjectId;
+use crate::tenant::report::ReportPeriod;
+use crate::tenant::report_schedule::ReportScheduleEvent;
+use crate::tenant::timesheet::domain::events::UserId;
+
+pub type ReportScheduleId = AggregateId;
+
+/// Whose entries a report covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportScope {
+    /// Only the owner's.
+    Personal,
+    /// Everyone's in the workspace.
+    Workspace,
+}
+
+impl ReportScope {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Personal => "personal",
+            Self::Workspace => "workspace",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "personal" => Some(Self::Personal),
+            "workspace" => Some(Self::Workspace),
+            _ => None,
+        }
+    }
+}
+
+/// How a report is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    /// An HTML email with the CSV attached.
+    Html,
+    /// A short plain text email with the CSV attached.
+    Csv,
+}
+
+impl ReportFormat {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Csv => "csv",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "html" => Some(Self::Html),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// What a report covers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportParameters {
+    pub period: ReportPeriod,
+    /// Only these projects; all if empty.
+    #[serde(default)]
+    pub project_ids: Vec<ProjectId>,
+}
+
+/// A report emailed on a schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportSchedule {
+    id: ReportScheduleId,
+    owner_id: UserId,
+    name: String,
+    scope: ReportScope,
+    schedule: String,
+    parameters: ReportParameters,
+    format: ReportFormat,
+    recipients: Vec<String>,
+    removed: bool,
+}
+
+impl ReportSchedule {
+    #[must_use]
+    pub const fn id(&self) -> &ReportScheduleId {
+        &self.id
+    }
+    #[must_use]
+    pub const fn owner_id(&self) -> &UserId {
+        &self.owner_id
+    }
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    #[must_use]
+    pub const fn scope(&self) -> ReportScope {
+        self.scope
+    }
+    #[must_use]
+    pub fn schedule(&self) -> &str {
+        &self.schedule
+    }
+    #[must_use]
+    pub const fn parameters(&self) -> &ReportParameters {
+        &self.parameters
+    }
+    #[must_use]
+    pub const fn format(&self) -> ReportFormat {
+        self.format
+    }
+    #[must_use]
+    pub fn recipients(&self) -> &[String] {
+        &self.recipients
+    }
+}
+
+crate::aggregate_errors!("report schedule");
+
+impl Aggregate for ReportSchedule {
+    type Id = ReportScheduleId;
+    type Event = ReportScheduleEvent;
+    type Error = Error;
+
+    fn type_name() -> &'static str {
+        "report_schedule"
+    }
+
+    fn aggregate_id(&self) -> &Self::Id {
+        &self.id
+    }
+
+    fn apply(state: Option<Self>, event: Self::Event) -> Result<Self, Self::Error> {
+        match (state, event) {
+            (
+                None,
+                ReportScheduleEvent::Created {
+                    id,
+                    owner_id,
+                    name,
+                    scope,
+                    schedule,
+                    parameters,
+                    format,
+                    recipients,
+                },
+            ) => Ok(Self {
+                id,
+                owner_id,
+                name,
+                scope,
+                schedule,
+                parameters,
+                format,
+                recipients,
+                removed: false,
+            }),
+            (Some(_), ReportScheduleEvent::Created { .. }) => Err(Error::AlreadyExists),
+            (None, _) => Err(Error::NotFound),
+            (Some(r), _) if r.removed => Err(Error::NotFound),
+            (
+                Some(r),
+                ReportScheduleEvent::Changed {
+                    name,
+                    schedule,
+                    parameters,
+                    format,
+                    recipients,
+                },
+            ) => Ok(Self {
+                name,
+                schedule,
+                parameters,
+                format,
+                recipients,
+                ..r
+            }),
+            (Some(r), ReportScheduleEvent::Removed) => Ok(Self { removed: true, ..r }),
+        }
+    }
+}
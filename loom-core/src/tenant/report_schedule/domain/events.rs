@@ -0,0 +1,41 @@
+use eventually::message::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::tenant::report_schedule::{
+    ReportFormat, ReportParameters, ReportScheduleId, ReportScope,
+};
+use crate::tenant::timesheet::domain::events::UserId;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportScheduleEvent {
+    /// `owner_id` has the report sent to `recipients` whenever the cron
+    /// expression `schedule` fires.
+    Created {
+        id: ReportScheduleId,
+        owner_id: UserId,
+        name: String,
+        scope: ReportScope,
+        schedule: String,
+        parameters: ReportParameters,
+        format: ReportFormat,
+        recipients: Vec<String>,
+    },
+    Changed {
+        name: String,
+        schedule: String,
+        parameters: ReportParameters,
+        format: ReportFormat,
+        recipients: Vec<String>,
+    },
+    Removed,
+}
+
+impl Message for ReportScheduleEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Created { .. } => "ReportScheduleCreated",
+            Self::Changed { .. } => "ReportScheduleChanged",
+            Self::Removed => "ReportScheduleRemoved",
+        }
+    }
+}
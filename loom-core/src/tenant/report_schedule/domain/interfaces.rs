@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+
+use crate::tenant::report_schedule::domain::aggregates::ReportSchedule;
+use eventually::aggregate::repository::{Getter, Saver};
+
+#[async_trait]
+pub trait ReportScheduleRepository:
+    Getter<ReportSchedule> + Saver<ReportSchedule> + Send + Sync
+{
+}
@@ -0,0 +1,10 @@
+pub mod aggregates;
+pub mod events;
+pub mod interfaces;
+pub mod schedule;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0:?}")]
+    AggregateError(#[from] aggregates::Error),
+}
@@ -0,0 +1,246 @@
+//! Cron expressions saying when a report is sent.
+//!
+//! The usual five fields are supported: minute, hour, day of month, month
+//! and day of week, each `*`, a number, a range `a-b` or a list of them,
+//! optionally stepped with `/n`.  Months and weekdays may be given by their
+//! English three-letter names, and Sunday is both `0` and `7`.  As in cron,
+//! a day matches if either day field does when both are restricted.  The
+//! aliases `@hourly`, `@daily`, `@weekly` and `@monthly` are accepted too.
+//! Times are UTC.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Timelike, Utc};
+
+/// How far ahead [`Schedule::next_after`] looks before giving up, e.g. for
+/// `0 0 31 2 *`.
+const HORIZON_DAYS: i64 = 5 * 366;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// A parsed cron expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Schedule {
+    minutes: u64,
+    hours: u32,
+    days: u32,
+    months: u16,
+    weekdays: u8,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Schedule {
+    /// # Errors
+    ///
+    /// Returns a message naming the offending field if `expression` is not a
+    /// valid cron expression.
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            other => other,
+        };
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(
+                "A schedule needs five fields: minute, hour, day, month and weekday".into(),
+            );
+        };
+        let weekdays = field(weekday, 0, 7, &WEEKDAYS, "weekday")?;
+        Ok(Self {
+            minutes: field(minute, 0, 59, &[], "minute")?,
+            hours: u32::try_from(field(hour, 0, 23, &[], "hour")?).unwrap_or_default(),
+            days: u32::try_from(field(day, 1, 31, &[], "day")?).unwrap_or_default(),
+            months: u16::try_from(field(month, 1, 12, &MONTHS, "month")? >> 1).unwrap_or_default(),
+            // Sunday is 0 and 7.
+            weekdays: u8::try_from((weekdays | (weekdays >> 7)) & 0x7f).unwrap_or_default(),
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+
+    /// The first time after `after` the schedule fires, or `None` if it
+    /// never does within the next five years.
+    #[must_use]
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.naive_utc().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = start + Duration::days(HORIZON_DAYS);
+        let mut t = start;
+        while t <= limit {
+            let date = t.date();
+            if self.months & (1 << date.month0()) == 0 {
+                t = first_of_next_month(date)?.and_hms_opt(0, 0, 0)?;
+            } else if !self.matches_day(date) {
+                t = date.succ_opt()?.and_hms_opt(0, 0, 0)?;
+            } else if self.hours & (1 << t.hour()) == 0 {
+                t = next_hour(t);
+            } else if self.minutes & (1 << t.minute()) == 0 {
+                t += Duration::minutes(1);
+            } else {
+                return Some(t.and_utc());
+            }
+        }
+        None
+    }
+
+    /// The latest time in `(after, until]` the schedule fires, so that
+    /// occurrences missed while nothing was running are caught up on once.
+    #[must_use]
+    pub fn last_between(
+        &self,
+        after: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let mut last = None;
+        let mut t = after;
+        while let Some(next) = self.next_after(t).filter(|next| *next <= until) {
+            last = Some(next);
+            t = next;
+        }
+        last
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+}
+
+/// The bits of the values `field` selects, bit `n` standing for value `n`.
+fn field(text: &str, min: u32, max: u32, names: &[&str], name: &str) -> Result<u64, String> {
+    let invalid = || format!("Invalid {name} in schedule: {text}");
+    let value = |s: &str| -> Result<u32, String> {
+        let n = names
+            .iter()
+            .position(|n| n.eq_ignore_ascii_case(s))
+            .map(|i| u32::try_from(i).unwrap_or_default() + min)
+            .or_else(|| s.parse().ok())
+            .ok_or_else(invalid)?;
+        if (min..=max).contains(&n) {
+            Ok(n)
+        } else {
+            Err(invalid())
+        }
+    };
+
+    let mut bits = 0u64;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(invalid());
+        }
+        let (from, to) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((from, to)) => (value(from)?, value(to)?),
+                // `5/15` means from 5 to the end in steps of 15.
+                None if step > 1 => (value(range)?, max),
+                None => {
+                    let n = value(range)?;
+                    (n, n)
+                }
+            },
+        };
+        if from > to {
+            return Err(invalid());
+        }
+        for n in (from..=to).step_by(usize::try_from(step).unwrap_or(1)) {
+            bits |= 1 << n;
+        }
+    }
+    Ok(bits)
+}
+
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDate> {
+    if date.month() == 12 {
+        NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
+    }
+}
+
+fn next_hour(t: NaiveDateTime) -> NaiveDateTime {
+    t.with_minute(0).unwrap_or(t) + Duration::hours(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn weekly_schedules_fire_on_the_next_matching_weekday() {
+        let monday_morning = Schedule::parse("0 8 * * mon").unwrap();
+        assert_eq!(
+            monday_morning.next_after(at("2026-10-19T07:59:30Z")),
+            Some(at("2026-10-19T08:00:00Z"))
+        );
+        assert_eq!(
+            monday_morning.next_after(at("2026-10-19T08:00:00Z")),
+            Some(at("2026-10-26T08:00:00Z"))
+        );
+        assert_eq!(
+            Schedule::parse("@monthly")
+                .unwrap()
+                .next_after(at("2026-12-15T10:00:00Z")),
+            Some(at("2027-01-01T00:00:00Z"))
+        );
+        assert_eq!(
+            monday_morning.last_between(at("2026-10-01T00:00:00Z"), at("2026-10-20T00:00:00Z")),
+            Some(at("2026-10-19T08:00:00Z"))
+        );
+        assert_eq!(
+            monday_morning.last_between(at("2026-10-19T08:00:00Z"), at("2026-10-20T00:00:00Z")),
+            None
+        );
+    }
+
+    #[test]
+    fn lists_ranges_and_steps() {
+        let schedule = Schedule::parse("*/30 9-17 * * 1-5").unwrap();
+        assert_eq!(
+            schedule.next_after(at("2026-10-16T17:45:00Z")),
+            Some(at("2026-10-19T09:00:00Z"))
+        );
+        assert_eq!(
+            schedule.next_after(at("2026-10-19T09:10:00Z")),
+            Some(at("2026-10-19T09:30:00Z"))
+        );
+        // Either day field matches once both are restricted: the 1st, or a
+        // Sunday (given as 7).
+        let either = Schedule::parse("0 0 1 * 7").unwrap();
+        assert_eq!(
+            either.next_after(at("2026-10-19T00:00:00Z")),
+            Some(at("2026-10-25T00:00:00Z"))
+        );
+        assert_eq!(
+            Schedule::parse("0 0 31 2 *")
+                .unwrap()
+                .next_after(at("2026-10-19T00:00:00Z")),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_expressions_are_rejected() {
+        assert!(Schedule::parse("0 8 * *").is_err());
+        assert!(Schedule::parse("60 8 * * *").is_err());
+        assert!(Schedule::parse("0 8 * * funday").is_err());
+        assert!(Schedule::parse("0 17-9 * * *").is_err());
+        assert!(Schedule::parse("*/0 * * * *").is_err());
+    }
+}
@@ -0,0 +1,17 @@
+pub(crate) mod application;
+pub(crate) mod domain;
+
+pub use application::commands::ReportScheduleCommand;
+pub use domain::{
+    Error as DomainError,
+    aggregates::{ReportFormat, ReportParameters, ReportSchedule, ReportScheduleId, ReportScope},
+    events::ReportScheduleEvent,
+    interfaces::ReportScheduleRepository,
+    schedule::Schedule,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0:?}")]
+    DomainError(#[from] domain::Error),
+}
@@ -10,6 +10,10 @@ pub type TagId = AggregateId;
 pub struct Tag {
     id: TagId,
     name: String,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    deleted: bool,
 }
 
 impl Tag {
@@ -21,6 +25,10 @@ impl Tag {
     pub fn name(&self) -> &str {
         &self.name
     }
+    #[must_use]
+    pub const fn archived(&self) -> bool {
+        self.archived
+    }
 }
 
 crate::aggregate_errors!("tag");
@@ -40,9 +48,15 @@ impl Aggregate for Tag {
 
     fn apply(state: Option<Self>, event: Self::Event) -> Result<Self, Self::Error> {
         match (state, event) {
-            (None, TagEvent::Created { id, name }) => Ok(Self { id, name }),
+            (None, TagEvent::Created { id, name }) => Ok(Self {
+                id,
+                name,
+                archived: false,
+                deleted: false,
+            }),
             (Some(_), TagEvent::Created { .. }) => Err(Error::AlreadyExists),
             (None, _) => Err(Error::NotFound),
+            (Some(t), _) if t.deleted => Err(Error::NotFound),
             (Some(mut t), TagEvent::Renamed { name }) => {
                 t.name = name;
                 Ok(t)
@@ -50,6 +64,18 @@ impl Aggregate for Tag {
             (Some(t), TagEvent::TimesheetTagged { .. } | TagEvent::TimesheetUntagged { .. }) => {
                 Ok(t)
             }
+            (Some(mut t), TagEvent::Archived) => {
+                t.archived = true;
+                Ok(t)
+            }
+            (Some(mut t), TagEvent::Restored) => {
+                t.archived = false;
+                Ok(t)
+            }
+            (Some(mut t), TagEvent::Deleted) => {
+                t.deleted = true;
+                Ok(t)
+            }
         }
     }
 }
@@ -6,10 +6,24 @@ use crate::tenant::timesheet::TimesheetId;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TagEvent {
-    Created { id: TagId, name: String },
-    Renamed { name: String },
-    TimesheetTagged { timesheet_id: TimesheetId },
-    TimesheetUntagged { timesheet_id: TimesheetId },
+    Created {
+        id: TagId,
+        name: String,
+    },
+    Renamed {
+        name: String,
+    },
+    TimesheetTagged {
+        timesheet_id: TimesheetId,
+    },
+    TimesheetUntagged {
+        timesheet_id: TimesheetId,
+    },
+    /// Hidden from lists and selectors until restored.
+    Archived,
+    Restored,
+    /// Permanently removed.  Only recorded while no timesheet references it.
+    Deleted,
 }
 
 impl Message for TagEvent {
@@ -19,6 +33,9 @@ impl Message for TagEvent {
             Self::Renamed { .. } => "TagRenamed",
             Self::TimesheetTagged { .. } => "TagTimesheetTagged",
             Self::TimesheetUntagged { .. } => "TagTimesheetUntagged",
+            Self::Archived => "TagArchived",
+            Self::Restored => "TagRestored",
+            Self::Deleted => "TagDeleted",
         }
     }
 }
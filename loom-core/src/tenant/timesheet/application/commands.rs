@@ -65,6 +65,31 @@ impl TimesheetCommand {
                 fixed_rate,
                 internal_rate,
                 rate,
+                billed_duration: None,
+                rounding: None,
+                break_duration: 0,
+            }
+            .into(),
+        )
+        .map_err(|e| timesheet::DomainError::AggregateError(e).into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the timer is not running or already paused.
+    pub fn pause(&mut self, paused_at: String) -> Result<(), crate::Error> {
+        self.record_that(TimesheetEvent::Paused { paused_at }.into())
+            .map_err(|e| timesheet::DomainError::AggregateError(e).into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the timer is not paused.
+    pub fn resume(&mut self, resumed_at: String, break_duration: i32) -> Result<(), crate::Error> {
+        self.record_that(
+            TimesheetEvent::Resumed {
+                resumed_at,
+                break_duration,
             }
             .into(),
         )
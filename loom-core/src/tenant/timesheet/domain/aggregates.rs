@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::shared::AggregateId;
 use crate::tenant::activity::ActivityId;
+use crate::tenant::approval::{ApprovalId, ApprovalStatus};
 use crate::tenant::project::ProjectId;
 use crate::tenant::timesheet::TimesheetEvent;
 use crate::tenant::timesheet::domain::events::UserId;
@@ -22,6 +23,16 @@ pub struct Timesheet {
     timezone: String,
     billable: bool,
     exported: bool,
+    #[serde(default)]
+    deleted: bool,
+    #[serde(default)]
+    approval: ApprovalStatus,
+    #[serde(default)]
+    approval_id: Option<ApprovalId>,
+    #[serde(default)]
+    paused_at: Option<String>,
+    #[serde(default)]
+    break_duration: i32,
 }
 
 impl Timesheet {
@@ -69,6 +80,29 @@ impl Timesheet {
     pub const fn exported(&self) -> bool {
         self.exported
     }
+    #[must_use]
+    pub const fn deleted(&self) -> bool {
+        self.deleted
+    }
+    #[must_use]
+    pub const fn approval(&self) -> ApprovalStatus {
+        self.approval
+    }
+    /// The submission that last covered this entry.
+    #[must_use]
+    pub const fn approval_id(&self) -> Option<&ApprovalId> {
+        self.approval_id.as_ref()
+    }
+    /// When the running timer was paused, if it is paused.
+    #[must_use]
+    pub fn paused_at(&self) -> Option<&str> {
+        self.paused_at.as_deref()
+    }
+    /// Total length of the breaks in seconds.
+    #[must_use]
+    pub const fn break_duration(&self) -> i32 {
+        self.break_duration
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -79,6 +113,20 @@ pub enum Error {
     NotFound,
     #[error("timesheet already exported")]
     AlreadyExported,
+    #[error("timesheet is awaiting approval")]
+    AwaitingApproval,
+    #[error("timesheet is approved")]
+    Approved,
+    #[error("timesheet is not awaiting approval")]
+    NotSubmitted,
+    #[error("timesheet is not running")]
+    NotRunning,
+    #[error("timesheet is not stopped")]
+    NotStopped,
+    #[error("timesheet is already paused")]
+    AlreadyPaused,
+    #[error("timesheet is not paused")]
+    NotPaused,
 }
 
 impl Aggregate for Timesheet {
@@ -119,17 +167,102 @@ impl Aggregate for Timesheet {
                 timezone,
                 billable,
                 exported: false,
+                deleted: false,
+                approval: ApprovalStatus::Draft,
+                approval_id: None,
+                paused_at: None,
+                break_duration: 0,
             }),
             (Some(_), TimesheetEvent::Started { .. }) => Err(Error::AlreadyExists),
             (None, _) => Err(Error::NotFound),
+            (Some(t), _) if t.deleted => Err(Error::NotFound),
+            (Some(t), TimesheetEvent::Submitted { approval_id }) => match t.approval {
+                ApprovalStatus::Draft | ApprovalStatus::Rejected => Ok(Self {
+                    approval: ApprovalStatus::Submitted,
+                    approval_id: Some(approval_id),
+                    ..t
+                }),
+                ApprovalStatus::Submitted => Err(Error::AwaitingApproval),
+                ApprovalStatus::Approved => Err(Error::Approved),
+            },
+            (Some(t), TimesheetEvent::Approved) => {
+                if t.approval != ApprovalStatus::Submitted {
+                    return Err(Error::NotSubmitted);
+                }
+                Ok(Self {
+                    approval: ApprovalStatus::Approved,
+                    ..t
+                })
+            }
+            (Some(t), TimesheetEvent::Rejected) => {
+                if t.approval != ApprovalStatus::Submitted {
+                    return Err(Error::NotSubmitted);
+                }
+                Ok(Self {
+                    approval: ApprovalStatus::Rejected,
+                    ..t
+                })
+            }
+            // Export is the only change allowed while an entry is in review
+            // or approved.
+            (Some(t), TimesheetEvent::Exported) => {
+                if t.exported {
+                    return Err(Error::AlreadyExported);
+                }
+                Ok(Self {
+                    exported: true,
+                    ..t
+                })
+            }
+            (Some(t), _) if t.approval == ApprovalStatus::Submitted => Err(Error::AwaitingApproval),
+            (Some(t), _) if t.approval == ApprovalStatus::Approved => Err(Error::Approved),
             (
                 Some(mut t),
                 TimesheetEvent::Stopped {
-                    end_time, duration, ..
+                    end_time,
+                    duration,
+                    break_duration,
+                    ..
+                },
+            ) => {
+                t.end_time = Some(end_time);
+                t.duration = Some(duration);
+                t.paused_at = None;
+                t.break_duration = break_duration;
+                Ok(t)
+            }
+            (
+                Some(mut t),
+                TimesheetEvent::Amended {
+                    end_time,
+                    duration,
+                    break_duration,
+                    ..
                 },
             ) => {
+                if t.end_time.is_none() {
+                    return Err(Error::NotStopped);
+                }
                 t.end_time = Some(end_time);
                 t.duration = Some(duration);
+                t.break_duration = break_duration;
+                Ok(t)
+            }
+            (Some(mut t), TimesheetEvent::Paused { paused_at }) => {
+                if t.end_time.is_some() {
+                    return Err(Error::NotRunning);
+                }
+                if t.paused_at.is_some() {
+                    return Err(Error::AlreadyPaused);
+                }
+                t.paused_at = Some(paused_at);
+                Ok(t)
+            }
+            (Some(mut t), TimesheetEvent::Resumed { break_duration, .. }) => {
+                if t.paused_at.take().is_none() {
+                    return Err(Error::NotPaused);
+                }
+                t.break_duration += break_duration;
                 Ok(t)
             }
             (
@@ -167,15 +300,182 @@ impl Aggregate for Timesheet {
                 t.duration = duration;
                 Ok(t)
             }
-            (Some(t), TimesheetEvent::Exported) => {
+            (Some(t), TimesheetEvent::Deleted) => {
                 if t.exported {
                     return Err(Error::AlreadyExported);
                 }
-                Ok(Self {
-                    exported: true,
-                    ..t
-                })
+                Ok(Self { deleted: true, ..t })
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn started() -> Timesheet {
+        Timesheet::apply(
+            None,
+            TimesheetEvent::Started {
+                id: TimesheetId::new(),
+                user_id: UserId::new(),
+                project_id: None,
+                activity_id: None,
+                start_time: "2026-10-19T09:00:00+00:00".to_string(),
+                timezone: "UTC".to_string(),
+                billable: true,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn deleting_an_exported_timesheet_is_rejected() {
+        let exported = Timesheet::apply(Some(started()), TimesheetEvent::Exported).unwrap();
+        assert!(matches!(
+            Timesheet::apply(Some(exported), TimesheetEvent::Deleted),
+            Err(Error::AlreadyExported)
+        ));
+    }
+
+    #[test]
+    fn approved_timesheets_are_read_only_and_rejected_ones_reopen() {
+        let submitted = Timesheet::apply(
+            Some(started()),
+            TimesheetEvent::Submitted {
+                approval_id: ApprovalId::new(),
+            },
+        )
+        .unwrap();
+        let update = TimesheetEvent::Updated {
+            description: Some("Fixed".to_string()),
+            billable: true,
+        };
+        assert!(matches!(
+            Timesheet::apply(Some(submitted.clone()), update.clone()),
+            Err(Error::AwaitingApproval)
+        ));
+
+        let approved = Timesheet::apply(Some(submitted.clone()), TimesheetEvent::Approved).unwrap();
+        assert!(matches!(
+            Timesheet::apply(Some(approved), update.clone()),
+            Err(Error::Approved)
+        ));
+
+        let rejected = Timesheet::apply(Some(submitted), TimesheetEvent::Rejected).unwrap();
+        assert!(Timesheet::apply(Some(rejected), update).is_ok());
+    }
+
+    #[test]
+    fn breaks_add_up_and_stopping_ends_a_pause() {
+        let paused = Timesheet::apply(
+            Some(started()),
+            TimesheetEvent::Paused {
+                paused_at: "2026-10-19T12:00:00+00:00".to_string(),
+            },
+        )
+        .unwrap();
+        assert!(matches!(
+            Timesheet::apply(
+                Some(paused.clone()),
+                TimesheetEvent::Paused {
+                    paused_at: "2026-10-19T12:05:00+00:00".to_string(),
+                },
+            ),
+            Err(Error::AlreadyPaused)
+        ));
+
+        let resumed = Timesheet::apply(
+            Some(paused),
+            TimesheetEvent::Resumed {
+                resumed_at: "2026-10-19T12:30:00+00:00".to_string(),
+                break_duration: 1800,
+            },
+        )
+        .unwrap();
+        assert_eq!(resumed.paused_at(), None);
+        assert_eq!(resumed.break_duration(), 1800);
+
+        let paused = Timesheet::apply(
+            Some(resumed),
+            TimesheetEvent::Paused {
+                paused_at: "2026-10-19T15:00:00+00:00".to_string(),
+            },
+        )
+        .unwrap();
+        let stopped = Timesheet::apply(
+            Some(paused),
+            TimesheetEvent::Stopped {
+                end_time: "2026-10-19T15:00:00+00:00".to_string(),
+                duration: 4 * 3600,
+                hourly_rate: None,
+                fixed_rate: None,
+                internal_rate: None,
+                rate: None,
+                billed_duration: None,
+                rounding: None,
+                break_duration: 1800,
+            },
+        )
+        .unwrap();
+        assert_eq!(stopped.paused_at(), None);
+        assert!(matches!(
+            Timesheet::apply(
+                Some(stopped),
+                TimesheetEvent::Paused {
+                    paused_at: "2026-10-19T16:00:00+00:00".to_string(),
+                },
+            ),
+            Err(Error::NotRunning)
+        ));
+    }
+
+    #[test]
+    fn only_stopped_timesheets_can_be_amended() {
+        let amended = |end_time: &str| TimesheetEvent::Amended {
+            end_time: end_time.to_string(),
+            duration: 3600,
+            hourly_rate: Some(6000),
+            fixed_rate: None,
+            internal_rate: None,
+            rate: Some(6000),
+            billed_duration: Some(3600),
+            rounding: None,
+            break_duration: 0,
+        };
+        assert!(matches!(
+            Timesheet::apply(Some(started()), amended("2026-10-19T10:00:00+00:00")),
+            Err(Error::NotStopped)
+        ));
+
+        let stopped = Timesheet::apply(
+            Some(started()),
+            TimesheetEvent::Stopped {
+                end_time: "2026-10-19T11:00:00+00:00".to_string(),
+                duration: 2 * 3600,
+                hourly_rate: None,
+                fixed_rate: None,
+                internal_rate: None,
+                rate: None,
+                billed_duration: None,
+                rounding: None,
+                break_duration: 0,
+            },
+        )
+        .unwrap();
+        let corrected =
+            Timesheet::apply(Some(stopped), amended("2026-10-19T10:00:00+00:00")).unwrap();
+        assert_eq!(corrected.end_time(), Some("2026-10-19T10:00:00+00:00"));
+        assert_eq!(corrected.duration(), Some(3600));
+    }
+
+    #[test]
+    fn apply_after_deleted_returns_not_found() {
+        let deleted = Timesheet::apply(Some(started()), TimesheetEvent::Deleted).unwrap();
+        assert!(matches!(
+            Timesheet::apply(Some(deleted), TimesheetEvent::Exported),
+            Err(Error::NotFound)
+        ));
+    }
+}
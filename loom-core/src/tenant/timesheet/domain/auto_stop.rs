@@ -0,0 +1,234 @@
+//! Stopping forgotten timers.
+//!
+//! The policy is configured per workspace and enforced by a background job,
+//! not by the timesheet commands: a timer that has run for longer than the
+//! maximum duration, or past the daily stop time, is stopped on the user's
+//! behalf.  Both rules are off by default.
+
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::policy::format_seconds;
+
+/// Where a timer that ran for too long ends.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoStopEnd {
+    /// When the maximum duration was reached.
+    #[default]
+    Cutoff,
+    /// At the user's last activity in the workspace, if that lies between
+    /// the start and the cutoff; else at the cutoff.
+    LastActivity,
+}
+
+impl AutoStopEnd {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Cutoff => "cutoff",
+            Self::LastActivity => "last_activity",
+        }
+    }
+}
+
+impl std::str::FromStr for AutoStopEnd {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cutoff" => Ok(Self::Cutoff),
+            "last_activity" => Ok(Self::LastActivity),
+            other => Err(format!("Unknown auto-stop end: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoStopPolicy {
+    /// Stop timers that have run for longer than this many seconds.
+    pub max_duration: Option<i32>,
+    /// Where a timer stopped for its duration ends.
+    pub end: AutoStopEnd,
+    /// Stop timers still running at this time of day (`HH:MM`, UTC).
+    pub daily_stop_at: Option<String>,
+    /// Email users whose timer was stopped.
+    pub notify: bool,
+}
+
+/// Why a timer was stopped automatically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoStopReason {
+    /// Ran for longer than the maximum duration of this many seconds.
+    MaxDuration(i32),
+    /// Still running at the daily stop time.
+    DailyStop(NaiveTime),
+}
+
+impl std::fmt::Display for AutoStopReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MaxDuration(seconds) => write!(
+                f,
+                "Stopped automatically after running for more than {}",
+                format_seconds(*seconds)
+            ),
+            Self::DailyStop(time) => write!(
+                f,
+                "Stopped automatically at the daily stop time of {} UTC",
+                time.format("%H:%M")
+            ),
+        }
+    }
+}
+
+/// When and why a timer is stopped automatically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoStop {
+    pub end: DateTime<Utc>,
+    pub reason: AutoStopReason,
+}
+
+impl AutoStopPolicy {
+    #[must_use]
+    pub const fn is_off(&self) -> bool {
+        self.max_duration.is_none() && self.daily_stop_at.is_none()
+    }
+
+    /// # Errors
+    ///
+    /// Returns a message if the maximum duration is not positive or the
+    /// daily stop time is not `HH:MM`.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.max_duration.is_some_and(|max| max <= 0) {
+            return Err("The maximum timer duration must be positive");
+        }
+        if self
+            .daily_stop_at
+            .as_deref()
+            .is_some_and(|time| Self::parse_time(time).is_none())
+        {
+            return Err("The daily stop time must be given as HH:MM");
+        }
+        Ok(())
+    }
+
+    /// Parse a time of day as `HH:MM`.
+    #[must_use]
+    pub fn parse_time(time: &str) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(time, "%H:%M").ok()
+    }
+
+    /// Whether a timer running since `start` is stopped at `now`, and where
+    /// it ends.  `last_activity` is the user's last activity in the
+    /// workspace, if known.  When both rules apply, the earlier end wins.
+    #[must_use]
+    pub fn check(
+        &self,
+        start: DateTime<Utc>,
+        last_activity: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Option<AutoStop> {
+        let by_duration = self.max_duration.filter(|max| *max > 0).and_then(|max| {
+            let cutoff = start + Duration::seconds(i64::from(max));
+            (cutoff <= now).then(|| {
+                let end = match (self.end, last_activity) {
+                    (AutoStopEnd::LastActivity, Some(last)) if last > start && last < cutoff => {
+                        last
+                    }
+                    _ => cutoff,
+                };
+                AutoStop {
+                    end,
+                    reason: AutoStopReason::MaxDuration(max),
+                }
+            })
+        });
+        let by_time = self
+            .daily_stop_at
+            .as_deref()
+            .and_then(Self::parse_time)
+            .and_then(|time| {
+                let mut end = start.date_naive().and_time(time).and_utc();
+                if end <= start {
+                    end += Duration::days(1);
+                }
+                (end <= now).then_some(AutoStop {
+                    end,
+                    reason: AutoStopReason::DailyStop(time),
+                })
+            });
+        match (by_duration, by_time) {
+            (Some(a), Some(b)) => Some(if b.end < a.end { b } else { a }),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn overlong_timers_end_at_the_cutoff_or_last_activity() {
+        let mut policy = AutoStopPolicy {
+            max_duration: Some(10 * 3600),
+            ..AutoStopPolicy::default()
+        };
+        let start = at("2026-10-19T08:00:00Z");
+        assert_eq!(policy.check(start, None, at("2026-10-19T17:59:59Z")), None);
+
+        let stop = policy
+            .check(
+                start,
+                Some(at("2026-10-19T16:30:00Z")),
+                at("2026-10-20T07:00:00Z"),
+            )
+            .unwrap();
+        assert_eq!(stop.end, at("2026-10-19T18:00:00Z"));
+        assert_eq!(stop.reason, AutoStopReason::MaxDuration(36_000));
+
+        policy.end = AutoStopEnd::LastActivity;
+        let last = Some(at("2026-10-19T16:30:00Z"));
+        assert_eq!(
+            policy
+                .check(start, last, at("2026-10-20T07:00:00Z"))
+                .unwrap()
+                .end,
+            at("2026-10-19T16:30:00Z")
+        );
+        let before_start = Some(at("2026-10-18T16:30:00Z"));
+        assert_eq!(
+            policy
+                .check(start, before_start, at("2026-10-20T07:00:00Z"))
+                .unwrap()
+                .end,
+            at("2026-10-19T18:00:00Z")
+        );
+    }
+
+    #[test]
+    fn daily_stop_applies_to_the_first_stop_time_after_the_start() {
+        let policy = AutoStopPolicy {
+            max_duration: Some(16 * 3600),
+            daily_stop_at: Some("20:00".to_string()),
+            ..AutoStopPolicy::default()
+        };
+        let late_start = at("2026-10-19T21:00:00Z");
+        assert_eq!(
+            policy.check(late_start, None, at("2026-10-20T08:00:00Z")),
+            None
+        );
+
+        let stop = policy
+            .check(at("2026-10-19T08:00:00Z"), None, at("2026-10-20T08:00:00Z"))
+            .unwrap();
+        assert_eq!(stop.end, at("2026-10-19T20:00:00Z"));
+        assert!(stop.reason.to_string().contains("20:00 UTC"));
+    }
+}
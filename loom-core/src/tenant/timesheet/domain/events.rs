@@ -3,7 +3,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::shared::AggregateId;
 use crate::tenant::activity::ActivityId;
+use crate::tenant::approval::ApprovalId;
 use crate::tenant::project::ProjectId;
+use crate::tenant::timesheet::Rounding;
 use crate::tenant::timesheet::TimesheetId;
 
 /// User ID references an admin-domain user — stored as a plain `AggregateId`.
@@ -31,8 +33,50 @@ pub enum TimesheetEvent {
         hourly_rate: Option<i64>,
         fixed_rate: Option<i64>,
         internal_rate: Option<i64>,
-        /// Total amount in cents.
+        /// Total amount in cents, calculated from `billed_duration`.
         rate: Option<i64>,
+        /// Duration in seconds the amount is calculated from.  `None` for
+        /// entries stopped before rounding existed, which bill `duration`.
+        #[serde(default)]
+        billed_duration: Option<i32>,
+        /// The rounding rules applied, kept so the amount can be reproduced.
+        #[serde(default)]
+        rounding: Option<Rounding>,
+        /// Total length of the breaks in seconds, excluded from `duration`.
+        #[serde(default)]
+        break_duration: i32,
+    },
+    /// Re-rates a stopped timesheet after a correction, e.g. of its times.
+    /// Carries the same fields as `Stopped`.
+    Amended {
+        /// RFC-3339 timestamp string.
+        end_time: String,
+        /// Duration in seconds.
+        duration: i32,
+        hourly_rate: Option<i64>,
+        fixed_rate: Option<i64>,
+        internal_rate: Option<i64>,
+        /// Total amount in cents, calculated from `billed_duration`.
+        rate: Option<i64>,
+        /// Duration in seconds the amount is calculated from.
+        billed_duration: Option<i32>,
+        /// The rounding rules applied, kept so the amount can be reproduced.
+        rounding: Option<Rounding>,
+        /// Total length of the breaks in seconds, excluded from `duration`.
+        break_duration: i32,
+    },
+    /// Interrupts a running timer, e.g. for a lunch break.
+    Paused {
+        /// RFC-3339 timestamp string.
+        paused_at: String,
+    },
+    /// Continues a paused timer.  The break does not count towards the
+    /// duration.
+    Resumed {
+        /// RFC-3339 timestamp string.
+        resumed_at: String,
+        /// Length of the break in seconds.
+        break_duration: i32,
     },
     Updated {
         description: Option<String>,
@@ -53,6 +97,16 @@ pub enum TimesheetEvent {
         duration: Option<i32>,
     },
     Exported,
+    /// Removes a mistaken entry.  Exported timesheets cannot be deleted.
+    Deleted,
+    /// Included in a submission for approval; read-only until reviewed.
+    Submitted {
+        approval_id: ApprovalId,
+    },
+    /// Approved by a reviewer; read-only from now on.
+    Approved,
+    /// Sent back by a reviewer; editable again and may be resubmitted.
+    Rejected,
 }
 
 impl Message for TimesheetEvent {
@@ -60,10 +114,39 @@ impl Message for TimesheetEvent {
         match self {
             Self::Started { .. } => "TimesheetStarted",
             Self::Stopped { .. } => "TimesheetStopped",
+            Self::Amended { .. } => "TimesheetAmended",
+            Self::Paused { .. } => "TimesheetPaused",
+            Self::Resumed { .. } => "TimesheetResumed",
             Self::Updated { .. } => "TimesheetUpdated",
             Self::Reassigned { .. } => "TimesheetReassigned",
             Self::TimeUpdated { .. } => "TimesheetTimeUpdated",
             Self::Exported => "TimesheetExported",
+            Self::Deleted => "TimesheetDeleted",
+            Self::Submitted { .. } => "TimesheetSubmitted",
+            Self::Approved => "TimesheetApproved",
+            Self::Rejected => "TimesheetRejected",
+        }
+    }
+}
+
+impl TimesheetEvent {
+    /// Whether the event may be recorded on an offline replica and pushed to
+    /// the server later.  These are the changes a user makes to their own
+    /// entries; exporting and the approval workflow need the server's view
+    /// of the workspace and are only ever recorded there.
+    #[must_use]
+    pub const fn is_offline_change(&self) -> bool {
+        match self {
+            Self::Started { .. }
+            | Self::Stopped { .. }
+            | Self::Amended { .. }
+            | Self::Paused { .. }
+            | Self::Resumed { .. }
+            | Self::Updated { .. }
+            | Self::Reassigned { .. }
+            | Self::TimeUpdated { .. }
+            | Self::Deleted => true,
+            Self::Exported | Self::Submitted { .. } | Self::Approved | Self::Rejected => false,
         }
     }
 }
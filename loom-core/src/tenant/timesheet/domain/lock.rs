@@ -0,0 +1,96 @@
+//! Period locks that close time entries once payroll or invoicing is done.
+//!
+//! A lock covers every entry that starts on or before its date (UTC).  The
+//! workspace lock applies to everyone; a user lock applies to one user on top
+//! of it, so the later of the two dates wins.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PeriodLocks {
+    /// Workspace-wide lock date, `YYYY-MM-DD`.
+    pub until: Option<String>,
+    /// Lock dates of single users by user ID, `YYYY-MM-DD`.
+    pub users: BTreeMap<String, String>,
+}
+
+impl PeriodLocks {
+    /// Parse a `YYYY-MM-DD` lock date.
+    #[must_use]
+    pub fn parse_date(date: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+    }
+
+    /// The last locked day for `user_id`, if any.
+    #[must_use]
+    pub fn locked_until(&self, user_id: &str) -> Option<NaiveDate> {
+        let workspace = self.until.as_deref().and_then(Self::parse_date);
+        let user = self.users.get(user_id).and_then(|d| Self::parse_date(d));
+        workspace.max(user)
+    }
+
+    /// Whether an entry of `user_id` starting at `start` is locked.
+    #[must_use]
+    pub fn is_locked(&self, user_id: &str, start: DateTime<Utc>) -> bool {
+        self.locked_until(user_id)
+            .is_some_and(|until| start.date_naive() <= until)
+    }
+
+    /// The lock date of one user, or of the workspace without `user_id`.
+    #[must_use]
+    pub fn lock_of(&self, user_id: Option<&str>) -> Option<&str> {
+        match user_id {
+            Some(user_id) => self.users.get(user_id).map(String::as_str),
+            None => self.until.as_deref(),
+        }
+    }
+
+    /// Set or lift the lock of one user, or of the workspace without
+    /// `user_id`.
+    pub fn set(&mut self, user_id: Option<&str>, until: Option<String>) {
+        match (user_id, until) {
+            (Some(user_id), Some(until)) => {
+                self.users.insert(user_id.to_string(), until);
+            }
+            (Some(user_id), None) => {
+                self.users.remove(user_id);
+            }
+            (None, until) => self.until = until,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn later_of_workspace_and_user_lock_applies() {
+        let mut locks = PeriodLocks::default();
+        locks.set(None, Some("2026-09-30".to_string()));
+        locks.set(Some("alice"), Some("2026-10-15".to_string()));
+
+        let oct_10 = Utc.with_ymd_and_hms(2026, 10, 10, 23, 59, 0).unwrap();
+        assert!(locks.is_locked("alice", oct_10));
+        assert!(!locks.is_locked("bob", oct_10));
+        assert!(locks.is_locked("bob", Utc.with_ymd_and_hms(2026, 9, 30, 12, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn lifting_a_user_lock_falls_back_to_the_workspace_lock() {
+        let mut locks = PeriodLocks::default();
+        locks.set(None, Some("2026-09-30".to_string()));
+        locks.set(Some("alice"), Some("2026-10-15".to_string()));
+        locks.set(Some("alice"), None);
+        assert_eq!(
+            locks.locked_until("alice"),
+            PeriodLocks::parse_date("2026-09-30")
+        );
+    }
+}
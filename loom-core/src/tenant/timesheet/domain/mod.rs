@@ -1,6 +1,10 @@
 pub mod aggregates;
+pub mod auto_stop;
 pub mod events;
 pub mod interfaces;
+pub mod lock;
+pub mod policy;
+pub mod rounding;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
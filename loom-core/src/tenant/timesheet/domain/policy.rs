@@ -0,0 +1,253 @@
+//! Workspace rules every time entry must satisfy.
+//!
+//! The policy is configured per workspace and checked by the timesheet
+//! commands before any event is recorded.  Every rule is off by default, so
+//! a workspace without a policy accepts any entry whose end lies after its
+//! start.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimeEntryPolicy {
+    /// Refuse entries that overlap another entry of the same user.
+    pub disallow_overlaps: bool,
+    /// Refuse entries that start or end in the future.
+    pub disallow_future: bool,
+    /// Shortest allowed entry in seconds.
+    pub min_duration: Option<i32>,
+    /// Longest allowed entry in seconds.
+    pub max_duration: Option<i32>,
+    /// An entry must be assigned to a project when it is stopped.
+    pub require_project: bool,
+    /// An entry must be assigned to an activity when it is stopped.
+    pub require_activity: bool,
+    /// An entry must carry a description when it is stopped.
+    pub require_description: bool,
+}
+
+/// A time entry that breaks the workspace's [`TimeEntryPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PolicyViolation {
+    #[error("End time must be after start time")]
+    EndBeforeStart,
+    #[error("Time entries cannot lie in the future")]
+    InFuture,
+    #[error("Time entries must be at least {} long", format_seconds(*.0))]
+    TooShort(i32),
+    #[error("Time entries cannot be longer than {}", format_seconds(*.0))]
+    TooLong(i32),
+    #[error("The entry overlaps another time entry")]
+    Overlap,
+    #[error("A project is required")]
+    ProjectRequired,
+    #[error("An activity is required")]
+    ActivityRequired,
+    #[error("A description is required")]
+    DescriptionRequired,
+}
+
+impl TimeEntryPolicy {
+    /// Check the times of an entry.  `end` is `None` for a running timer,
+    /// which is only checked for starting in the future.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the times break.
+    pub fn check_times(
+        &self,
+        start: DateTime<Utc>,
+        end: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Result<(), PolicyViolation> {
+        if self.disallow_future && (start > now || end.is_some_and(|end| end > now)) {
+            return Err(PolicyViolation::InFuture);
+        }
+        let Some(end) = end else {
+            return Ok(());
+        };
+        if end <= start {
+            return Err(PolicyViolation::EndBeforeStart);
+        }
+        let duration = (end - start).num_seconds();
+        if let Some(min) = self.min_duration
+            && duration < i64::from(min)
+        {
+            return Err(PolicyViolation::TooShort(min));
+        }
+        if let Some(max) = self.max_duration
+            && duration > i64::from(max)
+        {
+            return Err(PolicyViolation::TooLong(max));
+        }
+        Ok(())
+    }
+
+    /// Check the fields that must be filled in once an entry is stopped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first missing field.
+    pub fn check_on_stop(
+        &self,
+        project_id: Option<&str>,
+        activity_id: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<(), PolicyViolation> {
+        if self.require_project && project_id.is_none() {
+            return Err(PolicyViolation::ProjectRequired);
+        }
+        if self.require_activity && activity_id.is_none() {
+            return Err(PolicyViolation::ActivityRequired);
+        }
+        if self.require_description && description.is_none_or(|d| d.trim().is_empty()) {
+            return Err(PolicyViolation::DescriptionRequired);
+        }
+        Ok(())
+    }
+
+    /// Check an entry against the user's other entries, given as
+    /// `(start, end)` with `None` for a running timer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PolicyViolation::Overlap`] if overlaps are disallowed and
+    /// any other entry shares time with this one.
+    pub fn check_overlaps(
+        &self,
+        start: DateTime<Utc>,
+        end: Option<DateTime<Utc>>,
+        others: &[(DateTime<Utc>, Option<DateTime<Utc>>)],
+    ) -> Result<(), PolicyViolation> {
+        if !self.disallow_overlaps {
+            return Ok(());
+        }
+        let overlaps = |&(other_start, other_end): &(DateTime<Utc>, Option<DateTime<Utc>>)| {
+            end.is_none_or(|end| other_start < end) && other_end.is_none_or(|other| start < other)
+        };
+        if others.iter().any(overlaps) {
+            return Err(PolicyViolation::Overlap);
+        }
+        Ok(())
+    }
+
+    /// Whether the limits themselves make sense.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the invalid limit.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.min_duration.is_some_and(|min| min <= 0)
+            || self.max_duration.is_some_and(|max| max <= 0)
+        {
+            return Err("Duration limits must be positive");
+        }
+        if let (Some(min), Some(max)) = (self.min_duration, self.max_duration)
+            && min > max
+        {
+            return Err("The minimum duration cannot exceed the maximum duration");
+        }
+        Ok(())
+    }
+}
+
+pub(super) fn format_seconds(seconds: i32) -> String {
+    let (h, m) = (seconds / 3600, (seconds % 3600) / 60);
+    match (h, m) {
+        (0, 0) => format!("{seconds}s"),
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h {m}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 19, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn default_policy_only_requires_end_after_start() {
+        let policy = TimeEntryPolicy::default();
+        assert_eq!(policy.check_times(at(9), Some(at(23)), at(1)), Ok(()));
+        assert_eq!(
+            policy.check_times(at(9), Some(at(9)), at(12)),
+            Err(PolicyViolation::EndBeforeStart)
+        );
+        assert_eq!(policy.check_on_stop(None, None, None), Ok(()));
+    }
+
+    #[test]
+    fn duration_limits_are_enforced() {
+        let policy = TimeEntryPolicy {
+            min_duration: Some(3600),
+            max_duration: Some(4 * 3600),
+            ..TimeEntryPolicy::default()
+        };
+        let now = at(23);
+        assert_eq!(policy.check_times(at(9), Some(at(10)), now), Ok(()));
+        assert_eq!(
+            policy.check_times(at(9), Some(at(14)), now),
+            Err(PolicyViolation::TooLong(4 * 3600))
+        );
+        assert_eq!(
+            PolicyViolation::TooLong(4 * 3600).to_string(),
+            "Time entries cannot be longer than 4h"
+        );
+    }
+
+    #[test]
+    fn future_entries_are_refused() {
+        let policy = TimeEntryPolicy {
+            disallow_future: true,
+            ..TimeEntryPolicy::default()
+        };
+        assert_eq!(
+            policy.check_times(at(9), Some(at(12)), at(11)),
+            Err(PolicyViolation::InFuture)
+        );
+        assert_eq!(policy.check_times(at(9), None, at(11)), Ok(()));
+    }
+
+    #[test]
+    fn touching_entries_do_not_overlap() {
+        let policy = TimeEntryPolicy {
+            disallow_overlaps: true,
+            ..TimeEntryPolicy::default()
+        };
+        let others = [(at(8), Some(at(9))), (at(12), None)];
+        assert_eq!(policy.check_overlaps(at(9), Some(at(10)), &others), Ok(()));
+        assert_eq!(
+            policy.check_overlaps(at(10), Some(at(13)), &others),
+            Err(PolicyViolation::Overlap)
+        );
+    }
+
+    #[test]
+    fn blank_description_counts_as_missing() {
+        let policy = TimeEntryPolicy {
+            require_description: true,
+            ..TimeEntryPolicy::default()
+        };
+        assert_eq!(
+            policy.check_on_stop(Some("p"), Some("a"), Some("  ")),
+            Err(PolicyViolation::DescriptionRequired)
+        );
+    }
+
+    #[test]
+    fn minimum_above_maximum_is_invalid() {
+        let policy = TimeEntryPolicy {
+            min_duration: Some(7200),
+            max_duration: Some(3600),
+            ..TimeEntryPolicy::default()
+        };
+        assert!(policy.validate().is_err());
+    }
+}
@@ -0,0 +1,273 @@
+//! Rounding of time entries for billing.
+//!
+//! Rules are configured per workspace and may be overridden per customer and
+//! project; the most specific one applies.  They are applied whenever an
+//! entry is stopped or its times change, and the applied rules are stored on
+//! the `Stopped` event together with the billed duration so amounts stay
+//! reproducible when the rules change later.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    #[default]
+    Closest,
+    Up,
+    Down,
+}
+
+impl RoundingMode {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Closest => "closest",
+            Self::Up => "up",
+            Self::Down => "down",
+        }
+    }
+}
+
+impl std::str::FromStr for RoundingMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "closest" => Ok(Self::Closest),
+            "up" => Ok(Self::Up),
+            "down" => Ok(Self::Down),
+            other => Err(format!("Unknown rounding mode: {other}")),
+        }
+    }
+}
+
+/// Round to a multiple of `minutes` in the given direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundingRule {
+    pub mode: RoundingMode,
+    pub minutes: u32,
+}
+
+impl RoundingRule {
+    /// Round a number of seconds.
+    #[must_use]
+    pub fn round_seconds(self, seconds: i64) -> i64 {
+        let step = i64::from(self.minutes) * 60;
+        if step == 0 {
+            return seconds;
+        }
+        let down = seconds.div_euclid(step) * step;
+        let rest = seconds - down;
+        match self.mode {
+            _ if rest == 0 => seconds,
+            RoundingMode::Down => down,
+            RoundingMode::Up => down + step,
+            RoundingMode::Closest if rest * 2 >= step => down + step,
+            RoundingMode::Closest => down,
+        }
+    }
+
+    /// Round a point in time to a multiple of `minutes` past the full hour
+    /// (UTC).  Sub-second precision is dropped.
+    #[must_use]
+    pub fn round_time(self, time: DateTime<Utc>) -> DateTime<Utc> {
+        DateTime::from_timestamp(self.round_seconds(time.timestamp()), 0).unwrap_or(time)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rounding {
+    pub begin: Option<RoundingRule>,
+    pub end: Option<RoundingRule>,
+    pub duration: Option<RoundingRule>,
+    /// Only round the billed duration; the entry keeps its real times.
+    pub billed_only: bool,
+}
+
+/// Times of an entry after rounding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rounded {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// Duration in seconds.
+    pub duration: i32,
+    /// Duration in seconds the amount is calculated from.
+    pub billed_duration: i32,
+}
+
+impl Rounding {
+    /// Whether no rule is configured.
+    #[must_use]
+    pub const fn is_off(&self) -> bool {
+        self.begin.is_none() && self.end.is_none() && self.duration.is_none()
+    }
+
+    /// Apply the rules to an entry running from `start` to `end`.
+    #[must_use]
+    pub fn apply(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Rounded {
+        self.apply_with_breaks(start, end, 0)
+    }
+
+    /// Apply the rules to an entry running from `start` to `end` with
+    /// `breaks` seconds of breaks in between.
+    ///
+    /// Begin and end are rounded first, then the time worked between them; a
+    /// rounded duration moves the end.  With `billed_only` the entry keeps
+    /// its real times and only the billed duration is rounded.
+    #[must_use]
+    pub fn apply_with_breaks(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        breaks: i32,
+    ) -> Rounded {
+        let breaks = i64::from(breaks.max(0));
+        let rounded_start = self.begin.map_or(start, |rule| rule.round_time(start));
+        let rounded_end = self
+            .end
+            .map_or(end, |rule| rule.round_time(end))
+            .max(rounded_start);
+        let seconds = ((rounded_end - rounded_start).num_seconds() - breaks).max(0);
+        let seconds = self
+            .duration
+            .map_or(seconds, |rule| rule.round_seconds(seconds))
+            .max(0);
+        let billed_duration = clamp(seconds);
+
+        if self.billed_only {
+            return Rounded {
+                start,
+                end,
+                duration: clamp(((end - start).num_seconds() - breaks).max(0)),
+                billed_duration,
+            };
+        }
+        Rounded {
+            start: rounded_start,
+            end: rounded_start + chrono::Duration::seconds(seconds + breaks),
+            duration: billed_duration,
+            billed_duration,
+        }
+    }
+
+    /// Whether the increments themselves make sense.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the invalid rule.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        let rules = [self.begin, self.end, self.duration];
+        if rules
+            .iter()
+            .flatten()
+            .any(|rule| rule.minutes == 0 || rule.minutes > 24 * 60)
+        {
+            return Err("Rounding increments must be between 1 minute and 24 hours");
+        }
+        Ok(())
+    }
+}
+
+fn clamp(seconds: i64) -> i32 {
+    i32::try_from(seconds).unwrap_or(i32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(hour: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 19, hour, min, 0).unwrap()
+    }
+
+    fn rule(mode: RoundingMode, minutes: u32) -> Option<RoundingRule> {
+        Some(RoundingRule { mode, minutes })
+    }
+
+    #[test]
+    fn modes_round_in_their_direction() {
+        assert_eq!(rule(RoundingMode::Up, 15).unwrap().round_seconds(61), 900);
+        assert_eq!(rule(RoundingMode::Down, 15).unwrap().round_seconds(899), 0);
+        assert_eq!(
+            rule(RoundingMode::Closest, 6).unwrap().round_seconds(180),
+            360
+        );
+        assert_eq!(
+            rule(RoundingMode::Closest, 6).unwrap().round_seconds(179),
+            0
+        );
+        assert_eq!(
+            rule(RoundingMode::Up, 30).unwrap().round_seconds(1800),
+            1800
+        );
+    }
+
+    #[test]
+    fn no_rules_keep_the_entry_as_is() {
+        let rounded = Rounding::default().apply(at(9, 7), at(9, 52));
+        assert_eq!(rounded.start, at(9, 7));
+        assert_eq!(rounded.end, at(9, 52));
+        assert_eq!(rounded.duration, 45 * 60);
+        assert_eq!(rounded.billed_duration, 45 * 60);
+    }
+
+    #[test]
+    fn begin_end_and_duration_are_rounded_separately() {
+        let rounding = Rounding {
+            begin: rule(RoundingMode::Down, 15),
+            end: rule(RoundingMode::Up, 15),
+            duration: rule(RoundingMode::Up, 30),
+            billed_only: false,
+        };
+        let rounded = rounding.apply(at(9, 7), at(9, 52));
+        assert_eq!(rounded.start, at(9, 0));
+        assert_eq!(rounded.end, at(10, 0));
+        assert_eq!(rounded.duration, 3600);
+        assert_eq!(rounded.billed_duration, 3600);
+
+        let rounded = Rounding {
+            end: None,
+            ..rounding
+        }
+        .apply(at(9, 7), at(9, 52));
+        assert_eq!(rounded.end, at(10, 0));
+    }
+
+    #[test]
+    fn billed_only_keeps_the_real_times() {
+        let rounding = Rounding {
+            duration: rule(RoundingMode::Up, 6),
+            billed_only: true,
+            ..Rounding::default()
+        };
+        let rounded = rounding.apply(at(9, 0), at(9, 7));
+        assert_eq!(rounded.start, at(9, 0));
+        assert_eq!(rounded.end, at(9, 7));
+        assert_eq!(rounded.duration, 7 * 60);
+        assert_eq!(rounded.billed_duration, 12 * 60);
+    }
+
+    #[test]
+    fn breaks_are_not_rounded_into_the_duration() {
+        let rounding = Rounding {
+            duration: rule(RoundingMode::Up, 15),
+            ..Rounding::default()
+        };
+        let rounded = rounding.apply_with_breaks(at(9, 0), at(13, 10), 30 * 60);
+        assert_eq!(rounded.duration, 3 * 3600 + 45 * 60);
+        assert_eq!(rounded.end, at(13, 15));
+    }
+
+    #[test]
+    fn zero_increments_are_invalid() {
+        let rounding = Rounding {
+            begin: rule(RoundingMode::Up, 0),
+            ..Rounding::default()
+        };
+        assert!(rounding.validate().is_err());
+    }
+}
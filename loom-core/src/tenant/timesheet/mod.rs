@@ -4,9 +4,13 @@ pub(crate) mod domain;
 pub use application::{commands::TimesheetCommand, views::TimesheetView};
 pub use domain::{
     Error as DomainError,
-    aggregates::{Timesheet, TimesheetId},
+    aggregates::{Error as AggregateError, Timesheet, TimesheetId},
+    auto_stop::{AutoStop, AutoStopEnd, AutoStopPolicy, AutoStopReason},
     events::TimesheetEvent,
     interfaces::TimesheetRepository,
+    lock::PeriodLocks,
+    policy::{PolicyViolation, TimeEntryPolicy},
+    rounding::{Rounded, Rounding, RoundingMode, RoundingRule},
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -0,0 +1,61 @@
+use eventually::aggregate;
+
+use crate::tenant::webhook::{
+    self,
+    domain::{
+        aggregates::{Webhook, WebhookId},
+        events::WebhookEvent,
+    },
+};
+
+#[eventually_macros::aggregate_root(Webhook)]
+pub struct WebhookCommand;
+
+impl WebhookCommand {
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn subscribe(
+        &self,
+        id: WebhookId,
+        url: String,
+        secret: String,
+        event_types: Vec<String>,
+    ) -> Result<Self, crate::Error> {
+        Ok(aggregate::Root::<Webhook>::record_new(
+            WebhookEvent::Subscribed {
+                id,
+                url,
+                secret,
+                event_types,
+            }
+            .into(),
+        )
+        .map_err(webhook::DomainError::from)?
+        .into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn change(&mut self, url: String, event_types: Vec<String>) -> Result<(), crate::Error> {
+        self.record_that(WebhookEvent::Changed { url, event_types }.into())
+            .map_err(|e| webhook::DomainError::AggregateError(e).into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn rotate_secret(&mut self, secret: String) -> Result<(), crate::Error> {
+        self.record_that(WebhookEvent::SecretRotated { secret }.into())
+            .map_err(|e| webhook::DomainError::AggregateError(e).into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn remove(&mut self) -> Result<(), crate::Error> {
+        self.record_that(WebhookEvent::Removed.into())
+            .map_err(|e| webhook::DomainError::AggregateError(e).into())
+    }
+}
@@ -0,0 +1,78 @@
+use eventually::aggregate::Aggregate;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::AggregateId;
+use crate::tenant::webhook::WebhookEvent;
+
+pub type WebhookId = AggregateId;
+
+/// An outgoing webhook subscription of the workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Webhook {
+    id: WebhookId,
+    url: String,
+    secret: String,
+    event_types: Vec<String>,
+    removed: bool,
+}
+
+impl Webhook {
+    #[must_use]
+    pub const fn id(&self) -> &WebhookId {
+        &self.id
+    }
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+    #[must_use]
+    pub fn event_types(&self) -> &[String] {
+        &self.event_types
+    }
+}
+
+crate::aggregate_errors!("webhook");
+
+impl Aggregate for Webhook {
+    type Id = WebhookId;
+    type Event = WebhookEvent;
+    type Error = Error;
+
+    fn type_name() -> &'static str {
+        "webhook"
+    }
+
+    fn aggregate_id(&self) -> &Self::Id {
+        &self.id
+    }
+
+    fn apply(state: Option<Self>, event: Self::Event) -> Result<Self, Self::Error> {
+        match (state, event) {
+            (
+                None,
+                WebhookEvent::Subscribed {
+                    id,
+                    url,
+                    secret,
+                    event_types,
+                },
+            ) => Ok(Self {
+                id,
+                url,
+                secret,
+                event_types,
+                removed: false,
+            }),
+            (Some(_), WebhookEvent::Subscribed { .. }) => Err(Error::AlreadyExists),
+            (None, _) => Err(Error::NotFound),
+            (Some(w), _) if w.removed => Err(Error::NotFound),
+            (Some(w), WebhookEvent::Changed { url, event_types }) => Ok(Self {
+                url,
+                event_types,
+                ..w
+            }),
+            (Some(w), WebhookEvent::SecretRotated { secret }) => Ok(Self { secret, ..w }),
+            (Some(w), WebhookEvent::Removed) => Ok(Self { removed: true, ..w }),
+        }
+    }
+}
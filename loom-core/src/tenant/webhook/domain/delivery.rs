@@ -0,0 +1,84 @@
+//! Which events can be subscribed to, and when failed deliveries are retried.
+
+use chrono::Duration;
+
+/// Events a webhook may subscribe to.
+///
+/// Webhook events themselves are left out on purpose: their payload carries
+/// the signing secret.
+pub const SUBSCRIBABLE_EVENTS: &[&str] = &[
+    "TimesheetStarted",
+    "TimesheetStopped",
+    "TimesheetAmended",
+    "TimesheetUpdated",
+    "TimesheetTimeUpdated",
+    "TimesheetReassigned",
+    "TimesheetDeleted",
+    "TimesheetExported",
+    "TimesheetSubmitted",
+    "TimesheetApproved",
+    "TimesheetRejected",
+    "ApprovalRequested",
+    "ApprovalGranted",
+    "ApprovalRejected",
+    "CustomerCreated",
+    "CustomerUpdated",
+    "CustomerBudgetUpdated",
+    "CustomerBudgetThresholdReached",
+    "CustomerArchived",
+    "CustomerDeleted",
+    "ProjectCreated",
+    "ProjectUpdated",
+    "ProjectBudgetUpdated",
+    "ProjectBudgetThresholdReached",
+    "ProjectArchived",
+    "ProjectDeleted",
+    "ActivityCreated",
+    "ActivityUpdated",
+    "ActivityArchived",
+    "ActivityDeleted",
+    "TagCreated",
+    "TagRenamed",
+    "TagDeleted",
+];
+
+/// Attempts after which a delivery is given up on.
+pub const MAX_ATTEMPTS: u32 = 8;
+
+#[must_use]
+pub fn is_subscribable(event_type: &str) -> bool {
+    SUBSCRIBABLE_EVENTS.contains(&event_type)
+}
+
+/// How long to wait after the `attempts`-th failed attempt before the next
+/// one: 30 seconds, doubling each time, or `None` once [`MAX_ATTEMPTS`] have
+/// failed.
+#[must_use]
+pub fn retry_delay(attempts: u32) -> Option<Duration> {
+    if attempts == 0 || attempts >= MAX_ATTEMPTS {
+        return None;
+    }
+    Some(Duration::seconds(30 << (attempts - 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_back_off_exponentially_until_given_up() {
+        assert_eq!(retry_delay(1), Some(Duration::seconds(30)));
+        assert_eq!(retry_delay(2), Some(Duration::seconds(60)));
+        assert_eq!(retry_delay(7), Some(Duration::seconds(30 * 64)));
+        assert_eq!(retry_delay(MAX_ATTEMPTS), None);
+        assert_eq!(retry_delay(0), None);
+    }
+
+    #[test]
+    fn webhook_events_cannot_be_subscribed_to() {
+        assert!(is_subscribable("TimesheetStopped"));
+        assert!(is_subscribable("ProjectBudgetUpdated"));
+        assert!(!is_subscribable("WebhookSubscribed"));
+        assert!(!is_subscribable("WebhookSecretRotated"));
+    }
+}
@@ -0,0 +1,35 @@
+use eventually::message::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::tenant::webhook::WebhookId;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookEvent {
+    /// `url` is called for every event named in `event_types`, signed with
+    /// `secret`.
+    Subscribed {
+        id: WebhookId,
+        url: String,
+        secret: String,
+        event_types: Vec<String>,
+    },
+    Changed {
+        url: String,
+        event_types: Vec<String>,
+    },
+    SecretRotated {
+        secret: String,
+    },
+    Removed,
+}
+
+impl Message for WebhookEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Subscribed { .. } => "WebhookSubscribed",
+            Self::Changed { .. } => "WebhookChanged",
+            Self::SecretRotated { .. } => "WebhookSecretRotated",
+            Self::Removed => "WebhookRemoved",
+        }
+    }
+}
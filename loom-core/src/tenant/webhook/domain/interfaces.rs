@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+
+use crate::tenant::webhook::domain::aggregates::Webhook;
+use eventually::aggregate::repository::{Getter, Saver};
+
+#[async_trait]
+pub trait WebhookRepository: Getter<Webhook> + Saver<Webhook> + Send + Sync {}
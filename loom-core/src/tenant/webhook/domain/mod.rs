@@ -0,0 +1,10 @@
+pub mod aggregates;
+pub mod delivery;
+pub mod events;
+pub mod interfaces;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0:?}")]
+    AggregateError(#[from] aggregates::Error),
+}
@@ -0,0 +1,17 @@
+pub(crate) mod application;
+pub(crate) mod domain;
+
+pub use application::commands::WebhookCommand;
+pub use domain::{
+    Error as DomainError,
+    aggregates::{Webhook, WebhookId},
+    delivery::{MAX_ATTEMPTS, SUBSCRIBABLE_EVENTS, is_subscribable, retry_delay},
+    events::WebhookEvent,
+    interfaces::WebhookRepository,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0:?}")]
+    DomainError(#[from] domain::Error),
+}
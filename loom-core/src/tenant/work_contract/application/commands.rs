@@ -0,0 +1,72 @@
+use eventually::aggregate;
+
+use crate::tenant::timesheet::domain::events::UserId;
+use crate::tenant::work_contract::{
+    self,
+    domain::{
+        aggregates::{WorkContract, WorkContractId},
+        events::WorkContractEvent,
+    },
+};
+
+#[eventually_macros::aggregate_root(WorkContract)]
+pub struct WorkContractCommand;
+
+impl WorkContractCommand {
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn define(
+        &self,
+        id: WorkContractId,
+        user_id: UserId,
+        weekly_minutes: i32,
+        working_days: Vec<u8>,
+        starts_on: String,
+        region: Option<String>,
+    ) -> Result<Self, crate::Error> {
+        Ok(aggregate::Root::<WorkContract>::record_new(
+            WorkContractEvent::Defined {
+                id,
+                user_id,
+                weekly_minutes,
+                working_days,
+                starts_on,
+                region,
+            }
+            .into(),
+        )
+        .map_err(work_contract::DomainError::from)?
+        .into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn change(
+        &mut self,
+        weekly_minutes: i32,
+        working_days: Vec<u8>,
+        starts_on: String,
+        region: Option<String>,
+    ) -> Result<(), crate::Error> {
+        self.record_that(
+            WorkContractEvent::Changed {
+                weekly_minutes,
+                working_days,
+                starts_on,
+                region,
+            }
+            .into(),
+        )
+        .map_err(|e| work_contract::DomainError::AggregateError(e).into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the domain event cannot be applied to the aggregate.
+    pub fn remove(&mut self) -> Result<(), crate::Error> {
+        self.record_that(WorkContractEvent::Removed.into())
+            .map_err(|e| work_contract::DomainError::AggregateError(e).into())
+    }
+}
@@ -0,0 +1,109 @@
+use eventually::aggregate::Aggregate;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::AggregateId;
+use crate::tenant::timesheet::domain::events::UserId;
+use crate::tenant::work_contract::WorkContractEvent;
+
+pub type WorkContractId = AggregateId;
+
+/// The working time a user is expected to put in from a start date on.
+///
+/// A later contract of the same user supersedes an earlier one from its own
+/// start date.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkContract {
+    id: WorkContractId,
+    user_id: UserId,
+    weekly_minutes: i32,
+    working_days: Vec<u8>,
+    starts_on: String,
+    region: Option<String>,
+    removed: bool,
+}
+
+impl WorkContract {
+    #[must_use]
+    pub const fn id(&self) -> &WorkContractId {
+        &self.id
+    }
+    #[must_use]
+    pub const fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+    #[must_use]
+    pub const fn weekly_minutes(&self) -> i32 {
+        self.weekly_minutes
+    }
+    #[must_use]
+    pub fn working_days(&self) -> &[u8] {
+        &self.working_days
+    }
+    #[must_use]
+    pub fn starts_on(&self) -> &str {
+        &self.starts_on
+    }
+    #[must_use]
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+}
+
+crate::aggregate_errors!("work contract");
+
+impl Aggregate for WorkContract {
+    type Id = WorkContractId;
+    type Event = WorkContractEvent;
+    type Error = Error;
+
+    fn type_name() -> &'static str {
+        "work_contract"
+    }
+
+    fn aggregate_id(&self) -> &Self::Id {
+        &self.id
+    }
+
+    fn apply(state: Option<Self>, event: Self::Event) -> Result<Self, Self::Error> {
+        match (state, event) {
+            (
+                None,
+                WorkContractEvent::Defined {
+                    id,
+                    user_id,
+                    weekly_minutes,
+                    working_days,
+                    starts_on,
+                    region,
+                },
+            ) => Ok(Self {
+                id,
+                user_id,
+                weekly_minutes,
+                working_days,
+                starts_on,
+                region,
+                removed: false,
+            }),
+            (Some(_), WorkContractEvent::Defined { .. }) => Err(Error::AlreadyExists),
+            (None, _) => Err(Error::NotFound),
+            (Some(c), _) if c.removed => Err(Error::NotFound),
+            (
+                Some(c),
+                WorkContractEvent::Changed {
+                    weekly_minutes,
+                    working_days,
+                    starts_on,
+                    region,
+                },
+            ) => Ok(Self {
+                weekly_minutes,
+                working_days,
+                starts_on,
+                region,
+                ..c
+            }),
+            (Some(c), WorkContractEvent::Removed) => Ok(Self { removed: true, ..c }),
+        }
+    }
+}
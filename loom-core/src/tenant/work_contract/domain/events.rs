@@ -0,0 +1,39 @@
+use eventually::message::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::tenant::timesheet::domain::events::UserId;
+use crate::tenant::work_contract::WorkContractId;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorkContractEvent {
+    /// A user is expected to work `weekly_minutes` a week from `starts_on`
+    /// on, spread evenly over the working days.
+    Defined {
+        id: WorkContractId,
+        user_id: UserId,
+        weekly_minutes: i32,
+        /// ISO weekday numbers, 1 (Monday) to 7 (Sunday).
+        working_days: Vec<u8>,
+        /// `YYYY-MM-DD`.
+        starts_on: String,
+        /// Region whose public holidays apply.
+        region: Option<String>,
+    },
+    Changed {
+        weekly_minutes: i32,
+        working_days: Vec<u8>,
+        starts_on: String,
+        region: Option<String>,
+    },
+    Removed,
+}
+
+impl Message for WorkContractEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Defined { .. } => "WorkContractDefined",
+            Self::Changed { .. } => "WorkContractChanged",
+            Self::Removed => "WorkContractRemoved",
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+
+use crate::tenant::work_contract::domain::aggregates::WorkContract;
+use eventually::aggregate::repository::{Getter, Saver};
+
+#[async_trait]
+pub trait WorkContractRepository: Getter<WorkContract> + Saver<WorkContract> + Send + Sync {}
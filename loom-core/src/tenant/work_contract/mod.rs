@@ -0,0 +1,16 @@
+pub(crate) mod application;
+pub(crate) mod domain;
+
+pub use application::commands::WorkContractCommand;
+pub use domain::{
+    Error as DomainError,
+    aggregates::{WorkContract, WorkContractId},
+    events::WorkContractEvent,
+    interfaces::WorkContractRepository,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0:?}")]
+    DomainError(#[from] domain::Error),
+}
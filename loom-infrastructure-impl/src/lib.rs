@@ -27,4 +27,6 @@ pub enum Error {
     JwtError(#[from] jsonwebtoken::errors::Error),
     #[error("invalid credentials")]
     InvalidCredentials,
+    #[error("aggregate error: {0}")]
+    AggregateError(String),
 }
@@ -0,0 +1,157 @@
+use chrono::{SecondsFormat, Utc};
+use sqlx::{Row, any::AnyRow};
+
+use crate::ConnectedAdminPool;
+
+/// Personal access tokens in `api_tokens`.
+///
+/// Tokens are looked up by the SHA-256 hash the caller computed; the
+/// repository never sees a token in clear text.
+pub struct ApiTokenRepository {
+    pool: ConnectedAdminPool,
+}
+
+impl ApiTokenRepository {
+    const COLUMNS: &'static str =
+        "id, user_id, workspace_id, name, created_at, last_used_at, revoked_at";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedAdminPool) -> Self {
+        Self { pool }
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails, e.g. because the hash
+    /// is already taken.
+    pub async fn insert(
+        &self,
+        id: &str,
+        user_id: &str,
+        workspace_id: &str,
+        name: &str,
+        token_hash: &str,
+    ) -> Result<ApiTokenRow, crate::Error> {
+        let created_at = now();
+        sqlx::query(
+            "INSERT INTO api_tokens (id, user_id, workspace_id, name, token_hash, created_at) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(workspace_id)
+        .bind(name)
+        .bind(token_hash)
+        .bind(created_at.as_str())
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(ApiTokenRow {
+            id: id.to_string(),
+            user_id: user_id.to_string(),
+            workspace_id: workspace_id.to_string(),
+            name: name.to_string(),
+            created_at,
+            last_used_at: None,
+            revoked_at: None,
+        })
+    }
+
+    /// Every token of a user in a workspace, newest first, revoked ones
+    /// included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn for_user(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+    ) -> Result<Vec<ApiTokenRow>, crate::Error> {
+        let sql = format!(
+            "SELECT {} FROM api_tokens WHERE user_id = ? AND workspace_id = ? \
+             ORDER BY created_at DESC, id DESC",
+            Self::COLUMNS
+        );
+        let rows = sqlx::query(&sql)
+            .bind(user_id)
+            .bind(workspace_id)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.iter().map(Self::map_row).collect()
+    }
+
+    /// The unrevoked token with the given hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find_active(&self, token_hash: &str) -> Result<Option<ApiTokenRow>, crate::Error> {
+        let sql = format!(
+            "SELECT {} FROM api_tokens WHERE token_hash = ? AND revoked_at IS NULL",
+            Self::COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(token_hash)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.as_ref().map(Self::map_row).transpose()
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn touch(&self, id: &str) -> Result<(), crate::Error> {
+        sqlx::query("UPDATE api_tokens SET last_used_at = ? WHERE id = ?")
+            .bind(now())
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// Revoke one of the user's tokens.  Returns `false` if the user has no
+    /// active token with that id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn revoke(&self, id: &str, user_id: &str) -> Result<bool, crate::Error> {
+        let result = sqlx::query(
+            "UPDATE api_tokens SET revoked_at = ? \
+             WHERE id = ? AND user_id = ? AND revoked_at IS NULL",
+        )
+        .bind(now())
+        .bind(id)
+        .bind(user_id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn map_row(row: &AnyRow) -> Result<ApiTokenRow, crate::Error> {
+        Ok(ApiTokenRow {
+            id: row.try_get("id")?,
+            user_id: row.try_get("user_id")?,
+            workspace_id: row.try_get("workspace_id")?,
+            name: row.try_get("name")?,
+            created_at: row.try_get("created_at")?,
+            last_used_at: row.try_get("last_used_at")?,
+            revoked_at: row.try_get("revoked_at")?,
+        })
+    }
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiTokenRow {
+    pub id: String,
+    pub user_id: String,
+    pub workspace_id: String,
+    pub name: String,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+}
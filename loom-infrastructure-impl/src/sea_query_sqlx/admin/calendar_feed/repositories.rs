@@ -0,0 +1,123 @@
+use chrono::{SecondsFormat, Utc};
+use sqlx::{Row, any::AnyRow};
+
+use crate::ConnectedAdminPool;
+
+/// Private iCalendar feeds in `calendar_feeds`, one per user and workspace.
+///
+/// Like API tokens, feeds are looked up by the SHA-256 hash of the token in
+/// their URL; the repository never sees a token in clear text.
+pub struct CalendarFeedRepository {
+    pool: ConnectedAdminPool,
+}
+
+impl CalendarFeedRepository {
+    #[must_use]
+    pub const fn new(pool: ConnectedAdminPool) -> Self {
+        Self { pool }
+    }
+
+    /// Store the feed of a user in a workspace, replacing any previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails, e.g. because the hash
+    /// is already taken.
+    pub async fn replace(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+        token_hash: &str,
+    ) -> Result<CalendarFeedRow, crate::Error> {
+        let created_at = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        let mut tx = self.pool.as_ref().begin().await?;
+        sqlx::query("DELETE FROM calendar_feeds WHERE user_id = ? AND workspace_id = ?")
+            .bind(user_id)
+            .bind(workspace_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "INSERT INTO calendar_feeds (user_id, workspace_id, token_hash, created_at) \
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(user_id)
+        .bind(workspace_id)
+        .bind(token_hash)
+        .bind(created_at.as_str())
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(CalendarFeedRow {
+            user_id: user_id.to_string(),
+            workspace_id: workspace_id.to_string(),
+            created_at,
+        })
+    }
+
+    /// The feed of a user in a workspace, if they have one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn for_user(
+        &self,
+        user_id: &str,
+        workspace_id: &str,
+    ) -> Result<Option<CalendarFeedRow>, crate::Error> {
+        let row = sqlx::query(
+            "SELECT user_id, workspace_id, created_at FROM calendar_feeds \
+             WHERE user_id = ? AND workspace_id = ?",
+        )
+        .bind(user_id)
+        .bind(workspace_id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        row.as_ref().map(Self::map_row).transpose()
+    }
+
+    /// The feed with the given hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find(&self, token_hash: &str) -> Result<Option<CalendarFeedRow>, crate::Error> {
+        let row = sqlx::query(
+            "SELECT user_id, workspace_id, created_at FROM calendar_feeds WHERE token_hash = ?",
+        )
+        .bind(token_hash)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        row.as_ref().map(Self::map_row).transpose()
+    }
+
+    /// Turn off the feed of a user in a workspace.  Returns `false` if they
+    /// had none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn delete(&self, user_id: &str, workspace_id: &str) -> Result<bool, crate::Error> {
+        let result =
+            sqlx::query("DELETE FROM calendar_feeds WHERE user_id = ? AND workspace_id = ?")
+                .bind(user_id)
+                .bind(workspace_id)
+                .execute(self.pool.as_ref())
+                .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn map_row(row: &AnyRow) -> Result<CalendarFeedRow, crate::Error> {
+        Ok(CalendarFeedRow {
+            user_id: row.try_get("user_id")?,
+            workspace_id: row.try_get("workspace_id")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CalendarFeedRow {
+    pub user_id: String,
+    pub workspace_id: String,
+    pub created_at: String,
+}
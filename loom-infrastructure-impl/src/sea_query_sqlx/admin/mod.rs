@@ -1,4 +1,6 @@
+pub mod api_token;
 pub mod authentication;
+pub mod calendar_feed;
 pub mod permission;
 pub mod projectors;
 pub mod user;
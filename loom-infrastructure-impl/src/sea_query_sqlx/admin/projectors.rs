@@ -3,10 +3,13 @@ use eventually_projection::{Projector, RawEvent};
 
 use crate::{
     Pool, ScopeAdmin, StateConnected,
-    sea_query_sqlx::admin::{
-        permission::projectors::PermissionProjector, user::projectors::UserProjector,
-        workspace::projectors::WorkspaceProjector,
-        workspace_role::projectors::WorkspaceRoleProjector,
+    sea_query_sqlx::{
+        admin::{
+            permission::projectors::PermissionProjector, user::projectors::UserProjector,
+            workspace::projectors::WorkspaceProjector,
+            workspace_role::projectors::WorkspaceRoleProjector,
+        },
+        audit_log::projectors::AuditLogProjector,
     },
 };
 
@@ -24,6 +27,7 @@ pub struct AdminProjector {
     workspace: WorkspaceProjector,
     workspace_role: WorkspaceRoleProjector,
     permission: PermissionProjector,
+    audit_log: AuditLogProjector<ScopeAdmin>,
 }
 
 impl AdminProjector {
@@ -33,7 +37,8 @@ impl AdminProjector {
             user: UserProjector::new(pool.clone()),
             workspace: WorkspaceProjector::new(pool.clone()),
             workspace_role: WorkspaceRoleProjector::new(pool.clone()),
-            permission: PermissionProjector::new(pool),
+            permission: PermissionProjector::new(pool.clone()),
+            audit_log: AuditLogProjector::new(pool),
         }
     }
 }
@@ -46,7 +51,8 @@ impl Projector for AdminProjector {
         self.user.handle(event.clone()).await?;
         self.workspace.handle(event.clone()).await?;
         self.workspace_role.handle(event.clone()).await?;
-        self.permission.handle(event).await?;
+        self.permission.handle(event.clone()).await?;
+        self.audit_log.handle(event).await?;
         Ok(())
     }
 }
@@ -11,7 +11,7 @@ use loom_infrastructure::query::{Query, RowToView};
 use sea_query::{Alias, Condition, Expr, ExprTrait, Func, SelectStatement};
 use sqlx::{Row, any::AnyRow, types::Uuid};
 
-use crate::ConnectedAdminPool;
+use crate::{ConnectedAdminPool, snapshot::SnapshotStore};
 
 const TABLE: &str = "projections__users";
 
@@ -258,14 +258,18 @@ impl Query<AnyRow> for UserRepository {
 #[async_trait]
 impl Getter<User> for UserRepository {
     async fn get(&self, id: &UserId) -> Result<eventually::aggregate::Root<User>, GetError> {
-        self.repository.get(id).await
+        SnapshotStore::new(self.database.clone())
+            .get(&self.repository, id)
+            .await
     }
 }
 
 #[async_trait]
 impl Saver<User> for UserRepository {
     async fn save(&self, root: &mut eventually::aggregate::Root<User>) -> Result<(), SaveError> {
-        self.repository.save(root).await
+        SnapshotStore::new(self.database.clone())
+            .save(&self.repository, root)
+            .await
     }
 }
 
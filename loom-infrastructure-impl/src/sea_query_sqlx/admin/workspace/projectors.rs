@@ -17,11 +17,53 @@ impl WorkspaceProjector {
     const TABLE: &'static str = "projections__workspaces";
     const USER_ROLES_TABLE: &'static str = "projections__workspace_user_roles";
     const USER_PERMISSIONS_TABLE: &'static str = "projections__workspace_user_permissions";
+    const PERIOD_LOCKS_TABLE: &'static str = "projections__workspace_period_locks";
 
     #[must_use]
     pub const fn new(pool: Pool<ScopeAdmin, StateConnected>) -> Self {
         Self { pool }
     }
+
+    /// Store the lock date of a user (or of the workspace for an empty
+    /// `user_id`); `None` lifts the lock.
+    async fn set_period_lock(
+        &self,
+        workspace_id: &str,
+        user_id: &str,
+        until: Option<String>,
+    ) -> Result<(), crate::Error> {
+        let (sql, values) = if let Some(until) = until {
+            let query = Query::insert()
+                .into_table(TableRef::from(Self::PERIOD_LOCKS_TABLE))
+                .columns([
+                    DynIden::from("workspace_id"),
+                    DynIden::from("user_id"),
+                    DynIden::from("locked_until"),
+                ])
+                .values_panic([workspace_id.into(), user_id.into(), until.into()])
+                .on_conflict(
+                    OnConflict::columns([DynIden::from("workspace_id"), DynIden::from("user_id")])
+                        .update_column(DynIden::from("locked_until"))
+                        .to_owned(),
+                )
+                .to_owned();
+            self.pool.build_query(&query)
+        } else {
+            let query = Query::delete()
+                .from_table(TableRef::from(Self::PERIOD_LOCKS_TABLE))
+                .cond_where(
+                    Condition::all()
+                        .add(Expr::col("workspace_id").eq(Expr::val(workspace_id)))
+                        .add(Expr::col("user_id").eq(Expr::val(user_id))),
+                )
+                .to_owned();
+            self.pool.build_query(&query)
+        };
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -217,6 +259,148 @@ impl Projector for WorkspaceProjector {
                     .execute(self.pool.as_ref())
                     .await?;
             }
+            "WorkspaceBudgetPolicyUpdated" => {
+                let WorkspaceEvent::BudgetPolicyUpdated {
+                    thresholds,
+                    block_over_budget,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::update()
+                    .table(TableRef::from(Self::TABLE))
+                    .values([
+                        (
+                            DynIden::from("budget_thresholds"),
+                            thresholds
+                                .iter()
+                                .map(ToString::to_string)
+                                .collect::<Vec<_>>()
+                                .join(",")
+                                .into(),
+                        ),
+                        (DynIden::from("block_over_budget"), block_over_budget.into()),
+                    ])
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = match self.pool.get_database_type() {
+                    DatabaseType::Sqlite => query.build_sqlx(SqliteQueryBuilder),
+                    DatabaseType::Postgres => query.build_sqlx(PostgresQueryBuilder),
+                };
+
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "WorkspaceTimeEntryPolicyUpdated" => {
+                let WorkspaceEvent::TimeEntryPolicyUpdated { policy } =
+                    serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::update()
+                    .table(TableRef::from(Self::TABLE))
+                    .values([(
+                        DynIden::from("time_entry_policy"),
+                        serde_json::to_string(&policy)?.into(),
+                    )])
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = match self.pool.get_database_type() {
+                    DatabaseType::Sqlite => query.build_sqlx(SqliteQueryBuilder),
+                    DatabaseType::Postgres => query.build_sqlx(PostgresQueryBuilder),
+                };
+
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "WorkspaceRoundingUpdated" => {
+                let WorkspaceEvent::RoundingUpdated { rounding } =
+                    serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::update()
+                    .table(TableRef::from(Self::TABLE))
+                    .values([(
+                        DynIden::from("rounding"),
+                        serde_json::to_string(&rounding)?.into(),
+                    )])
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = match self.pool.get_database_type() {
+                    DatabaseType::Sqlite => query.build_sqlx(SqliteQueryBuilder),
+                    DatabaseType::Postgres => query.build_sqlx(PostgresQueryBuilder),
+                };
+
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "WorkspaceAutoStopPolicyUpdated" => {
+                let WorkspaceEvent::AutoStopPolicyUpdated { policy } =
+                    serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::update()
+                    .table(TableRef::from(Self::TABLE))
+                    .values([(
+                        DynIden::from("auto_stop_policy"),
+                        serde_json::to_string(&policy)?.into(),
+                    )])
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = match self.pool.get_database_type() {
+                    DatabaseType::Sqlite => query.build_sqlx(SqliteQueryBuilder),
+                    DatabaseType::Postgres => query.build_sqlx(PostgresQueryBuilder),
+                };
+
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "WorkspacePeriodLocked" => {
+                let WorkspaceEvent::PeriodLocked { user_id, until } =
+                    serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+                let user_id = user_id.map(|id| id.to_string()).unwrap_or_default();
+                self.set_period_lock(&event.stream_id, &user_id, Some(until))
+                    .await?;
+            }
+            "WorkspacePeriodUnlocked" => {
+                let WorkspaceEvent::PeriodUnlocked { user_id, until } =
+                    serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+                let user_id = user_id.map(|id| id.to_string()).unwrap_or_default();
+                self.set_period_lock(&event.stream_id, &user_id, until)
+                    .await?;
+            }
             _ => {}
         }
 
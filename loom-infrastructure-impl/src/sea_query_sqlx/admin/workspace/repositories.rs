@@ -4,15 +4,18 @@ use async_trait::async_trait;
 use eventually::aggregate::repository::{GetError, Getter, SaveError, Saver};
 use eventually::serde::Json;
 use eventually_any::snapshot::Repository;
-use loom_core::admin::workspace::{
-    Workspace, WorkspaceEvent, WorkspaceId, WorkspaceRepository as WorkspaceRepositoryTrait,
-    WorkspaceView,
+use loom_core::{
+    admin::workspace::{
+        Workspace, WorkspaceEvent, WorkspaceId, WorkspaceRepository as WorkspaceRepositoryTrait,
+        WorkspaceView,
+    },
+    tenant::timesheet::PeriodLocks,
 };
 use loom_infrastructure::query::{Query, RowToView};
 use sea_query::{Alias, Condition, Expr, ExprTrait, Func, SelectStatement};
 use sqlx::{Row, any::AnyRow, types::Uuid};
 
-use crate::ConnectedAdminPool;
+use crate::{ConnectedAdminPool, snapshot::SnapshotStore};
 
 const TABLE: &str = "projections__workspaces";
 
@@ -89,6 +92,34 @@ impl WorkspaceRepository {
             .collect()
     }
 
+    /// Returns the (`user_id`, `name`) pairs of every member of the workspace,
+    /// ordered by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn members(&self, workspace_id: &str) -> Result<Vec<(String, String)>, crate::Error> {
+        let rows = sqlx::query(
+            "SELECT DISTINCT u.id, u.name \
+             FROM projections__users u \
+             INNER JOIN projections__workspace_user_roles r ON u.id = r.user_id \
+             WHERE r.workspace_id = ? \
+             ORDER BY u.name",
+        )
+        .bind(workspace_id)
+        .fetch_all(self.database.as_ref())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| -> Result<_, crate::Error> {
+                Ok((
+                    row.try_get::<String, _>("id")?,
+                    row.try_get::<String, _>("name")?,
+                ))
+            })
+            .collect()
+    }
+
     /// Returns the first workspace ID the given user belongs to, or `None`.
     ///
     /// # Errors
@@ -116,6 +147,32 @@ impl WorkspaceRepository {
             .transpose()
     }
 
+    /// Current period locks of the workspace and its users.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn period_locks(&self, workspace_id: &str) -> Result<PeriodLocks, crate::Error> {
+        let rows = sqlx::query(
+            "SELECT user_id, locked_until FROM projections__workspace_period_locks \
+             WHERE workspace_id = ?",
+        )
+        .bind(workspace_id)
+        .fetch_all(self.database.as_ref())
+        .await?;
+
+        let mut locks = PeriodLocks::default();
+        for row in rows {
+            let user_id: String = row.try_get("user_id")?;
+            let until: String = row.try_get("locked_until")?;
+            locks.set(
+                (!user_id.is_empty()).then_some(user_id.as_str()),
+                Some(until),
+            );
+        }
+        Ok(locks)
+    }
+
     /// Fetch a `WorkspaceView` by string ID, avoiding the `AnyPool` UUID-type panic.
     ///
     /// # Errors
@@ -171,14 +228,34 @@ impl RowToView<AnyRow> for WorkspaceRepository {
         let week_start: String = row
             .try_get("week_start")
             .unwrap_or_else(|_| "monday".to_string());
-        Ok(WorkspaceView::new_with_settings(
+        let mut view = WorkspaceView::new_with_settings(
             id.into(),
             name,
             timezone,
             date_format,
             currency,
             week_start,
-        ))
+        );
+        if let Ok(thresholds) = row.try_get::<String, _>("budget_thresholds") {
+            view.budget_thresholds = thresholds
+                .split(',')
+                .filter_map(|t| t.trim().parse().ok())
+                .collect();
+        }
+        view.block_over_budget = row
+            .try_get::<bool, _>("block_over_budget")
+            .or_else(|_| row.try_get::<i64, _>("block_over_budget").map(|v| v != 0))
+            .unwrap_or(false);
+        if let Ok(policy) = row.try_get::<String, _>("time_entry_policy") {
+            view.time_entry_policy = serde_json::from_str(&policy).unwrap_or_default();
+        }
+        if let Ok(rounding) = row.try_get::<String, _>("rounding") {
+            view.rounding = serde_json::from_str(&rounding).unwrap_or_default();
+        }
+        if let Ok(policy) = row.try_get::<String, _>("auto_stop_policy") {
+            view.auto_stop_policy = serde_json::from_str(&policy).unwrap_or_default();
+        }
+        Ok(view)
     }
 }
 
@@ -267,7 +344,9 @@ impl Getter<Workspace> for WorkspaceRepository {
         &self,
         id: &WorkspaceId,
     ) -> Result<eventually::aggregate::Root<Workspace>, GetError> {
-        self.repository.get(id).await
+        SnapshotStore::new(self.database.clone())
+            .get(&self.repository, id)
+            .await
     }
 }
 
@@ -277,7 +356,9 @@ impl Saver<Workspace> for WorkspaceRepository {
         &self,
         root: &mut eventually::aggregate::Root<Workspace>,
     ) -> Result<(), SaveError> {
-        self.repository.save(root).await
+        SnapshotStore::new(self.database.clone())
+            .save(&self.repository, root)
+            .await
     }
 }
 
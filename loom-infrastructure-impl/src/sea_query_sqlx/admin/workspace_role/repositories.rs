@@ -11,7 +11,7 @@ use loom_infrastructure::query::{Query, RowToView};
 use sea_query::{Condition, Expr, ExprTrait, Func, SelectStatement};
 use sqlx::{Row, any::AnyRow, types::Uuid};
 
-use crate::ConnectedAdminPool;
+use crate::{ConnectedAdminPool, snapshot::SnapshotStore};
 
 const TABLE: &str = "projections__workspace_roles";
 
@@ -181,7 +181,9 @@ impl Getter<WorkspaceRole> for WorkspaceRoleRepository {
         &self,
         id: &WorkspaceRoleId,
     ) -> Result<eventually::aggregate::Root<WorkspaceRole>, GetError> {
-        self.repository.get(id).await
+        SnapshotStore::new(self.database.clone())
+            .get(&self.repository, id)
+            .await
     }
 }
 
@@ -191,6 +193,8 @@ impl Saver<WorkspaceRole> for WorkspaceRoleRepository {
         &self,
         root: &mut eventually::aggregate::Root<WorkspaceRole>,
     ) -> Result<(), SaveError> {
-        self.repository.save(root).await
+        SnapshotStore::new(self.database.clone())
+            .save(&self.repository, root)
+            .await
     }
 }
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use eventually_projection::{Projector, RawEvent};
+use loom_core::audit;
+use sea_query::{DynIden, OnConflict, Query, TableRef};
+use serde_json::{Map, Value};
+use sqlx::Row;
+
+use crate::{Pool, StateConnected};
+
+/// Records every event of a database (admin or tenant) in the audit log.
+///
+/// The acting user comes from the envelope metadata, as does the reason a
+/// background job gave, which is listed first among the changes.  The
+/// wall-clock time is read back from `events.recorded_at`, which
+/// [`RawEvent`] does not carry.
+/// The unique `global_position` makes replays after a checkpoint reset a no-op.
+pub struct AuditLogProjector<Scope> {
+    pool: Pool<Scope, StateConnected>,
+}
+
+impl<Scope> AuditLogProjector<Scope> {
+    const LOG_TABLE: &'static str = "projections__audit_log";
+    const STATE_TABLE: &'static str = "projections__audit_state";
+
+    #[must_use]
+    pub const fn new(pool: Pool<Scope, StateConnected>) -> Self {
+        Self { pool }
+    }
+
+    async fn recorded_at(&self, event: &RawEvent) -> Result<Option<String>, crate::Error> {
+        let row = sqlx::query("SELECT recorded_at FROM events WHERE global_position = ?")
+            .bind(event.global_position)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        Ok(row
+            .map(|r| r.try_get::<Option<String>, _>("recorded_at"))
+            .transpose()?
+            .flatten())
+    }
+
+    async fn load_state(&self, aggregate_id: &str) -> Result<Map<String, Value>, crate::Error> {
+        let row = sqlx::query("SELECT state FROM projections__audit_state WHERE aggregate_id = ?")
+            .bind(aggregate_id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        let Some(row) = row else {
+            return Ok(Map::new());
+        };
+        let state: String = row.try_get("state")?;
+        Ok(serde_json::from_str(&state)?)
+    }
+}
+
+#[async_trait]
+impl<Scope> Projector for AuditLogProjector<Scope>
+where
+    Scope: Send + Sync + 'static,
+{
+    type Error = crate::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        let Some(aggregate_type) = audit::aggregate_type_for(&event.event_type) else {
+            return Ok(());
+        };
+        let recorded_at = self.recorded_at(&event).await?;
+        let actor = event
+            .metadata
+            .get(audit::ACTOR_METADATA_KEY)
+            .and_then(Value::as_str)
+            .map(str::to_owned);
+
+        let payload: Value = serde_json::from_slice(&event.payload_bytes)?;
+        let fields = audit::event_fields(&payload);
+        let mut state = self.load_state(&event.stream_id).await?;
+        let mut changes = audit::describe_changes(&event.event_type, &state, &fields);
+        if let Some(reason) = event
+            .metadata
+            .get(audit::REASON_METADATA_KEY)
+            .and_then(Value::as_str)
+        {
+            changes.insert(0, reason.to_owned());
+        }
+        audit::merge_state(&event.event_type, &mut state, &fields);
+
+        let query = Query::insert()
+            .into_table(TableRef::from(Self::LOG_TABLE))
+            .columns([
+                DynIden::from("global_position"),
+                DynIden::from("aggregate_type"),
+                DynIden::from("aggregate_id"),
+                DynIden::from("related_id"),
+                DynIden::from("event_type"),
+                DynIden::from("action"),
+                DynIden::from("actor"),
+                DynIden::from("recorded_at"),
+                DynIden::from("changes"),
+            ])
+            .values_panic([
+                event.global_position.into(),
+                aggregate_type.into(),
+                event.stream_id.clone().into(),
+                audit::related_id(&fields).into(),
+                event.event_type.clone().into(),
+                audit::action_for(&event.event_type).into(),
+                actor.into(),
+                recorded_at.into(),
+                serde_json::to_string(&changes)?.into(),
+            ])
+            .on_conflict(OnConflict::new().do_nothing().to_owned())
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        let query = Query::insert()
+            .into_table(TableRef::from(Self::STATE_TABLE))
+            .columns([DynIden::from("aggregate_id"), DynIden::from("state")])
+            .values_panic([
+                event.stream_id.clone().into(),
+                serde_json::to_string(&state)?.into(),
+            ])
+            .on_conflict(
+                OnConflict::column(DynIden::from("aggregate_id"))
+                    .update_column(DynIden::from("state"))
+                    .to_owned(),
+            )
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        Ok(())
+    }
+}
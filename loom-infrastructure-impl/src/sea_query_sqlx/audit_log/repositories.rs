@@ -0,0 +1,104 @@
+use sea_query::{Alias, Condition, Expr, ExprTrait, Order};
+use sqlx::{Row, any::AnyRow};
+
+use crate::{Pool, StateConnected};
+
+const TABLE: &str = "projections__audit_log";
+
+/// Read access to `projections__audit_log` of either the admin or a tenant
+/// database.
+pub struct AuditLogRepository<Scope> {
+    pool: Pool<Scope, StateConnected>,
+}
+
+/// Optional filters for [`AuditLogRepository::find`]; unset fields match all rows.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogFilter {
+    pub aggregate_type: Option<String>,
+    /// Matches the aggregate itself and events of other aggregates that refer
+    /// to it (e.g. tagging a timesheet).
+    pub entity_id: Option<String>,
+    pub actor: Option<String>,
+    /// Inclusive lower bound on `recorded_at` (RFC 3339, UTC).
+    pub from: Option<String>,
+    /// Exclusive upper bound on `recorded_at` (RFC 3339, UTC).
+    pub to: Option<String>,
+    pub limit: u64,
+    pub offset: u64,
+}
+
+impl<Scope> AuditLogRepository<Scope> {
+    #[must_use]
+    pub const fn new(pool: Pool<Scope, StateConnected>) -> Self {
+        Self { pool }
+    }
+
+    /// Returns matching entries, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find(&self, filter: &AuditLogFilter) -> Result<Vec<AuditLogRow>, crate::Error> {
+        let mut condition = Condition::all();
+        if let Some(aggregate_type) = &filter.aggregate_type {
+            condition = condition.add(Expr::col("aggregate_type").eq(aggregate_type.as_str()));
+        }
+        if let Some(entity_id) = &filter.entity_id {
+            condition = condition.add(
+                Condition::any()
+                    .add(Expr::col("aggregate_id").eq(entity_id.as_str()))
+                    .add(Expr::col("related_id").eq(entity_id.as_str())),
+            );
+        }
+        if let Some(actor) = &filter.actor {
+            condition = condition.add(Expr::col("actor").eq(actor.as_str()));
+        }
+        if let Some(from) = &filter.from {
+            condition = condition.add(Expr::col("recorded_at").gte(from.as_str()));
+        }
+        if let Some(to) = &filter.to {
+            condition = condition.add(Expr::col("recorded_at").lt(to.as_str()));
+        }
+
+        let statement = sea_query::Query::select()
+            .expr(Expr::col(sea_query::Asterisk))
+            .from(Alias::new(TABLE))
+            .cond_where(condition)
+            .order_by(Alias::new("global_position"), Order::Desc)
+            .limit(filter.limit)
+            .offset(filter.offset)
+            .to_owned();
+
+        let (sql, arguments) = self.pool.build_query(&statement);
+        let rows = sqlx::query_with(&sql, arguments)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.iter().map(Self::map_row).collect()
+    }
+
+    fn map_row(row: &AnyRow) -> Result<AuditLogRow, crate::Error> {
+        let changes: String = row.try_get("changes")?;
+        Ok(AuditLogRow {
+            global_position: row.try_get("global_position")?,
+            aggregate_type: row.try_get("aggregate_type")?,
+            aggregate_id: row.try_get("aggregate_id")?,
+            event_type: row.try_get("event_type")?,
+            action: row.try_get("action")?,
+            actor: row.try_get("actor")?,
+            recorded_at: row.try_get("recorded_at")?,
+            changes: serde_json::from_str(&changes)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditLogRow {
+    pub global_position: i64,
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub event_type: String,
+    pub action: String,
+    pub actor: Option<String>,
+    pub recorded_at: Option<String>,
+    pub changes: Vec<String>,
+}
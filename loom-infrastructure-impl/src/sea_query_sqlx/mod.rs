@@ -1,9 +1,15 @@
 pub mod admin;
+pub mod audit_log;
 pub mod infrastructure;
+pub mod projection_health;
+pub mod snapshot;
+pub mod sync;
 pub mod tenant;
+pub mod time_travel;
 
 pub use infrastructure::*;
 
 pub use eventually_projection::{
-    BackoffConfig, ProjectionDaemon, ProjectionRunner, ProjectionSource, SqlCheckpoint,
+    BackoffConfig, ProjectionDaemon, ProjectionRunner, ProjectionSource, Projector, RawEvent,
+    SqlCheckpoint,
 };
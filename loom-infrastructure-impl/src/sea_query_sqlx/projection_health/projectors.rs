@@ -0,0 +1,200 @@
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use eventually_projection::{Projector, RawEvent};
+use sea_query::{DynIden, OnConflict, Query, TableRef, Value};
+use sqlx::Row;
+
+use super::repositories::DeadLetterRepository;
+use crate::{Pool, StateConnected};
+
+/// Wraps a projector and records its progress in `projection_status`.
+///
+/// A failed event is recorded with its error and a retry counter that grows
+/// while the daemon keeps retrying the same global position.  By default the
+/// error is passed through and the daemon retries forever; with
+/// [`with_max_retries`](Self::with_max_retries) the event is parked in
+/// `projection_dead_letters` once the limit is reached and the runner moves on.
+pub struct MonitoredProjector<Scope, P> {
+    runner: String,
+    pool: Pool<Scope, StateConnected>,
+    inner: P,
+    max_retries: Option<i32>,
+}
+
+impl<Scope, P> MonitoredProjector<Scope, P> {
+    const TABLE: &'static str = "projection_status";
+
+    #[must_use]
+    pub fn new(runner: impl Into<String>, pool: Pool<Scope, StateConnected>, inner: P) -> Self {
+        Self {
+            runner: runner.into(),
+            pool,
+            inner,
+            max_retries: None,
+        }
+    }
+
+    /// Park an event in the dead-letter table after `max_retries` failed retries.
+    #[must_use]
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(i32::try_from(max_retries).unwrap_or(i32::MAX));
+        self
+    }
+
+    pub const fn inner_mut(&mut self) -> &mut P {
+        &mut self.inner
+    }
+
+    /// The stored checkpoint and, if the last event failed, its position and
+    /// retry count.
+    async fn previous(&self) -> Result<Option<(i64, Option<(i64, i32)>)>, crate::Error> {
+        let row = sqlx::query(
+            "SELECT position, error_position, retry_count FROM projection_status WHERE runner = ?",
+        )
+        .bind(self.runner.as_str())
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let position: i64 = row.try_get("position")?;
+        let error_position: Option<i64> = row.try_get("error_position")?;
+        let retries: i32 = row.try_get("retry_count")?;
+        Ok(Some((position, error_position.map(|p| (p, retries)))))
+    }
+
+    async fn record_success(&self, event: &RawEvent) -> Result<(), crate::Error> {
+        self.upsert(
+            event.global_position,
+            [
+                None::<String>.into(),
+                None::<i64>.into(),
+                None::<String>.into(),
+                None::<String>.into(),
+                None::<String>.into(),
+                0.into(),
+            ],
+        )
+        .await
+    }
+
+    /// Returns how often the event has been retried so far.
+    async fn record_failure(&self, event: &RawEvent, error: String) -> Result<i32, crate::Error> {
+        let previous = self.previous().await?;
+        let retries = match previous {
+            Some((_, Some((position, retries)))) if position == event.global_position => {
+                retries + 1
+            }
+            _ => 0,
+        };
+        // The checkpoint has not moved past the failing event.
+        let position = previous.map_or(event.global_position - 1, |(position, _)| position);
+        self.upsert(
+            position,
+            [
+                Some(error).into(),
+                Some(event.global_position).into(),
+                Some(event.stream_id.clone()).into(),
+                Some(event.event_type.clone()).into(),
+                Some(String::from_utf8_lossy(&event.payload_bytes).into_owned()).into(),
+                retries.into(),
+            ],
+        )
+        .await?;
+        Ok(retries)
+    }
+
+    async fn upsert(&self, position: i64, error_columns: [Value; 6]) -> Result<(), crate::Error> {
+        let [
+            last_error,
+            error_position,
+            error_stream_id,
+            error_event_type,
+            error_payload,
+            retry_count,
+        ] = error_columns;
+        let columns = [
+            "position",
+            "last_error",
+            "error_position",
+            "error_stream_id",
+            "error_event_type",
+            "error_payload",
+            "retry_count",
+            "updated_at",
+        ];
+
+        let query = Query::insert()
+            .into_table(TableRef::from(Self::TABLE))
+            .columns(std::iter::once("runner").chain(columns).map(DynIden::from))
+            .values_panic([
+                self.runner.clone().into(),
+                position.into(),
+                last_error.into(),
+                error_position.into(),
+                error_stream_id.into(),
+                error_event_type.into(),
+                error_payload.into(),
+                retry_count.into(),
+                Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true).into(),
+            ])
+            .on_conflict(
+                OnConflict::column(DynIden::from("runner"))
+                    .update_columns(columns.map(DynIden::from))
+                    .to_owned(),
+            )
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Scope, P> Projector for MonitoredProjector<Scope, P>
+where
+    Scope: Clone + Send + Sync + 'static,
+    P: Projector<Error = crate::Error> + Send,
+{
+    type Error = crate::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        let error = match self.inner.handle(event.clone()).await {
+            Ok(()) => return self.record_success(&event).await,
+            Err(error) => error,
+        };
+        tracing::warn!(
+            runner = %self.runner,
+            global_position = event.global_position,
+            event_type = %event.event_type,
+            error = %error,
+            "Projector failed; the event will be retried."
+        );
+
+        let retries = match self.record_failure(&event, error.to_string()).await {
+            Ok(retries) => retries,
+            Err(status_error) => {
+                tracing::error!(error = %status_error, "Failed to record projection status.");
+                return Err(error);
+            }
+        };
+        if self.max_retries.is_none_or(|max| retries < max) {
+            return Err(error);
+        }
+
+        DeadLetterRepository::new(self.pool.clone())
+            .park(&self.runner, &event, &error.to_string(), retries)
+            .await?;
+        tracing::error!(
+            runner = %self.runner,
+            global_position = event.global_position,
+            event_type = %event.event_type,
+            retries,
+            "Parked event in the dead-letter table; continuing with the next event."
+        );
+        self.record_success(&event).await
+    }
+}
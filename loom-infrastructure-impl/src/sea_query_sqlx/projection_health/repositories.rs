@@ -0,0 +1,464 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use eventually_projection::RawEvent;
+use sea_query::{DynIden, OnConflict, Query, TableRef};
+use serde::Serialize;
+use sqlx::{Row, any::AnyRow};
+
+use crate::{Pool, ScopeAdmin, ScopeTenant, StateConnected};
+
+/// Reads `projection_status` of the admin or a tenant database and relates it
+/// to the head of the event store.
+pub struct ProjectionStatusRepository<Scope> {
+    pool: Pool<Scope, StateConnected>,
+}
+
+impl<Scope> ProjectionStatusRepository<Scope> {
+    #[must_use]
+    pub const fn new(pool: Pool<Scope, StateConnected>) -> Self {
+        Self { pool }
+    }
+
+    /// Status of every runner that has handled at least one event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn all(&self) -> Result<Vec<ProjectionStatus>, crate::Error> {
+        let head = sqlx::query(
+            "SELECT global_position, recorded_at FROM events \
+             ORDER BY global_position DESC LIMIT 1",
+        )
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        let (head_position, head_recorded_at) = match head {
+            Some(row) => (
+                row.try_get::<i64, _>("global_position")?,
+                row.try_get::<Option<String>, _>("recorded_at")?,
+            ),
+            None => (0, None),
+        };
+
+        let rows = sqlx::query(
+            "SELECT s.*, (SELECT COUNT(*) FROM projection_dead_letters d \
+             WHERE d.runner = s.runner AND d.status = 'parked') AS parked \
+             FROM projection_status s ORDER BY s.runner",
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        let mut statuses = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let mut status = Self::map_row(row, head_position)?;
+            status.lag_seconds = self
+                .lag_seconds(status.position, head_recorded_at.as_deref())
+                .await?;
+            statuses.push(status);
+        }
+        Ok(statuses)
+    }
+
+    /// Global position of the last event `runner` projected, or `None` if it
+    /// has not handled any yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn position(&self, runner: &str) -> Result<Option<i64>, crate::Error> {
+        let row = sqlx::query("SELECT position FROM projection_status WHERE runner = ?")
+            .bind(runner)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        Ok(row.map(|r| r.try_get("position")).transpose()?)
+    }
+
+    /// Age of the oldest unprocessed event relative to the newest one.
+    async fn lag_seconds(
+        &self,
+        position: i64,
+        head_recorded_at: Option<&str>,
+    ) -> Result<Option<i64>, crate::Error> {
+        let Some(head) = head_recorded_at.and_then(|t| DateTime::parse_from_rfc3339(t).ok()) else {
+            return Ok(None);
+        };
+        let next = sqlx::query(
+            "SELECT recorded_at FROM events WHERE global_position > ? \
+             ORDER BY global_position LIMIT 1",
+        )
+        .bind(position)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        let Some(next) = next else {
+            return Ok(Some(0));
+        };
+        let next: Option<String> = next.try_get("recorded_at")?;
+        Ok(next
+            .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+            .map(|t| (head - t).num_seconds().max(0)))
+    }
+
+    fn map_row(row: &AnyRow, head_position: i64) -> Result<ProjectionStatus, crate::Error> {
+        let position: i64 = row.try_get("position")?;
+        Ok(ProjectionStatus {
+            runner: row.try_get("runner")?,
+            position,
+            head_position,
+            lag_events: (head_position - position).max(0),
+            lag_seconds: None,
+            last_error: row.try_get("last_error")?,
+            error_position: row.try_get("error_position")?,
+            error_stream_id: row.try_get("error_stream_id")?,
+            error_event_type: row.try_get("error_event_type")?,
+            error_payload: row.try_get("error_payload")?,
+            retry_count: row.try_get("retry_count")?,
+            parked: row.try_get("parked")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectionStatus {
+    pub runner: String,
+    /// Global position of the last successfully projected event.
+    pub position: i64,
+    /// Global position of the newest event in the store.
+    pub head_position: i64,
+    pub lag_events: i64,
+    /// Time between the oldest unprocessed event and the newest event;
+    /// `None` if the events carry no timestamp.
+    pub lag_seconds: Option<i64>,
+    pub last_error: Option<String>,
+    pub error_position: Option<i64>,
+    pub error_stream_id: Option<String>,
+    pub error_event_type: Option<String>,
+    /// Raw JSON payload of the failing event.
+    pub error_payload: Option<String>,
+    /// How often the failing event has been retried.
+    pub retry_count: i32,
+    /// Number of events parked in the dead-letter table awaiting a decision.
+    pub parked: i64,
+    pub updated_at: String,
+}
+
+impl ProjectionStatus {
+    /// Whether the runner is currently stuck on a failing event.
+    #[must_use]
+    pub const fn is_failing(&self) -> bool {
+        self.last_error.is_some()
+    }
+}
+
+/// Dead-letter table of the admin or a tenant database.
+pub struct DeadLetterRepository<Scope> {
+    pool: Pool<Scope, StateConnected>,
+}
+
+impl<Scope> DeadLetterRepository<Scope> {
+    const TABLE: &'static str = "projection_dead_letters";
+
+    #[must_use]
+    pub const fn new(pool: Pool<Scope, StateConnected>) -> Self {
+        Self { pool }
+    }
+
+    /// Park `event` for `runner`; parking the same event again updates the error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn park(
+        &self,
+        runner: &str,
+        event: &RawEvent,
+        error: &str,
+        retry_count: i32,
+    ) -> Result<(), crate::Error> {
+        let update = ["error", "retry_count", "status", "parked_at", "resolved_at"];
+        let query = Query::insert()
+            .into_table(TableRef::from(Self::TABLE))
+            .columns(
+                [
+                    "runner",
+                    "global_position",
+                    "stream_id",
+                    "version",
+                    "event_type",
+                    "payload",
+                    "metadata",
+                    "schema_version",
+                ]
+                .into_iter()
+                .chain(update)
+                .map(DynIden::from),
+            )
+            .values_panic([
+                runner.into(),
+                event.global_position.into(),
+                event.stream_id.clone().into(),
+                event.version.into(),
+                event.event_type.clone().into(),
+                String::from_utf8_lossy(&event.payload_bytes)
+                    .into_owned()
+                    .into(),
+                event.metadata.to_string().into(),
+                event.schema_version.into(),
+                error.into(),
+                retry_count.into(),
+                DeadLetterStatus::Parked.as_str().into(),
+                now().into(),
+                None::<String>.into(),
+            ])
+            .on_conflict(
+                OnConflict::columns([DynIden::from("runner"), DynIden::from("global_position")])
+                    .update_columns(update.map(DynIden::from))
+                    .to_owned(),
+            )
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// Dead letters with the given status (all if `None`), oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list(
+        &self,
+        status: Option<DeadLetterStatus>,
+    ) -> Result<Vec<DeadLetterRow>, crate::Error> {
+        let rows = match status {
+            Some(status) => sqlx::query(
+                "SELECT * FROM projection_dead_letters WHERE status = ? ORDER BY global_position",
+            )
+            .bind(status.as_str())
+            .fetch_all(self.pool.as_ref())
+            .await?,
+            None => {
+                sqlx::query("SELECT * FROM projection_dead_letters ORDER BY global_position")
+                    .fetch_all(self.pool.as_ref())
+                    .await?
+            }
+        };
+        rows.iter().map(Self::map_row).collect()
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find(&self, id: i64) -> Result<Option<DeadLetterRow>, crate::Error> {
+        let row = sqlx::query("SELECT * FROM projection_dead_letters WHERE id = ?")
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.as_ref().map(Self::map_row).transpose()
+    }
+
+    /// Mark a dead letter as replayed or skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn resolve(&self, id: i64, status: DeadLetterStatus) -> Result<(), crate::Error> {
+        sqlx::query("UPDATE projection_dead_letters SET status = ?, resolved_at = ? WHERE id = ?")
+            .bind(status.as_str())
+            .bind(now())
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// Record that a replay attempt failed again; the event stays parked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn record_replay_failure(&self, id: i64, error: &str) -> Result<(), crate::Error> {
+        sqlx::query(
+            "UPDATE projection_dead_letters SET error = ?, retry_count = retry_count + 1 \
+             WHERE id = ?",
+        )
+        .bind(error)
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    fn map_row(row: &AnyRow) -> Result<DeadLetterRow, crate::Error> {
+        let status: String = row.try_get("status")?;
+        Ok(DeadLetterRow {
+            id: row.try_get("id")?,
+            runner: row.try_get("runner")?,
+            global_position: row.try_get("global_position")?,
+            stream_id: row.try_get("stream_id")?,
+            version: row.try_get("version")?,
+            event_type: row.try_get("event_type")?,
+            payload: row.try_get("payload")?,
+            metadata: row.try_get("metadata")?,
+            schema_version: row.try_get("schema_version")?,
+            error: row.try_get("error")?,
+            retry_count: row.try_get("retry_count")?,
+            status: DeadLetterStatus::parse(&status),
+            parked_at: row.try_get("parked_at")?,
+            resolved_at: row.try_get("resolved_at")?,
+        })
+    }
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum DeadLetterStatus {
+    /// Waiting for an admin to replay or skip it.
+    Parked,
+    /// Successfully re-applied after a fix.
+    Replayed,
+    /// Deliberately dropped; the projections never saw it.
+    Skipped,
+}
+
+impl DeadLetterStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Parked => "parked",
+            Self::Replayed => "replayed",
+            Self::Skipped => "skipped",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "replayed" => Self::Replayed,
+            "skipped" => Self::Skipped,
+            _ => Self::Parked,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeadLetterRow {
+    pub id: i64,
+    pub runner: String,
+    pub global_position: i64,
+    pub stream_id: String,
+    pub version: i64,
+    pub event_type: String,
+    /// Event payload as stored in the event store (JSON).
+    pub payload: String,
+    pub metadata: String,
+    pub schema_version: i32,
+    pub error: String,
+    pub retry_count: i32,
+    pub status: DeadLetterStatus,
+    pub parked_at: String,
+    pub resolved_at: Option<String>,
+}
+
+impl DeadLetterRow {
+    /// Rebuild the event as the projection runner delivered it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stored metadata is not valid JSON.
+    pub fn to_raw_event(&self) -> Result<RawEvent, crate::Error> {
+        Ok(RawEvent {
+            stream_id: self.stream_id.clone(),
+            version: self.version.try_into().unwrap_or_default(),
+            global_position: self.global_position,
+            event_type: self.event_type.clone(),
+            payload_bytes: self.payload.clone().into_bytes(),
+            metadata: serde_json::from_str(&self.metadata)?,
+            schema_version: self.schema_version.try_into().unwrap_or_default(),
+        })
+    }
+}
+
+/// Projection tables of the admin database, dependents before the tables
+/// they reference.
+const ADMIN_PROJECTION_TABLES: &[&str] = &[
+    "projections__workspace_role_permissions",
+    "projections__workspace_user_permissions",
+    "projections__workspace_user_roles",
+    "projections__workspace_roles",
+    "projections__workspace_period_locks",
+    "projections__workspaces",
+    "projections__users",
+    "projections__audit_log",
+    "projections__audit_state",
+];
+
+/// Projection tables of a tenant database, dependents before the tables
+/// they reference.
+const TENANT_PROJECTION_TABLES: &[&str] = &[
+    "projections__timesheet_tags",
+    "projections__timesheet_combinations",
+    "projections__approvals",
+    "projections__budget_alerts",
+    "projections__favorites",
+    "projections__calendar_rules",
+    "projections__activity_rates",
+    "projections__project_rates",
+    "projections__timesheets",
+    "projections__activities",
+    "projections__projects",
+    "projections__customers",
+    "projections__tags",
+    "projections__holidays",
+    "projections__work_contracts",
+    "projections__webhooks",
+    "projections__report_schedules",
+    "projections__search",
+    "projections__audit_log",
+    "projections__audit_state",
+];
+
+/// Empties the projection tables of a database ahead of a rebuild.
+///
+/// The event store, `projection_status` and the dead letters are left alone.
+pub struct ProjectionTablesRepository<Scope> {
+    pool: Pool<Scope, StateConnected>,
+}
+
+impl<Scope> ProjectionTablesRepository<Scope> {
+    #[must_use]
+    pub const fn new(pool: Pool<Scope, StateConnected>) -> Self {
+        Self { pool }
+    }
+
+    async fn clear_tables(&self, tables: &[&str]) -> Result<(), crate::Error> {
+        let mut tx = self.pool.as_ref().begin().await?;
+        for table in tables {
+            sqlx::query(&format!("DELETE FROM {table}"))
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+impl ProjectionTablesRepository<ScopeAdmin> {
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn clear(&self) -> Result<(), crate::Error> {
+        self.clear_tables(ADMIN_PROJECTION_TABLES).await
+    }
+}
+
+impl ProjectionTablesRepository<ScopeTenant> {
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn clear(&self) -> Result<(), crate::Error> {
+        self.clear_tables(TENANT_PROJECTION_TABLES).await
+    }
+}
@@ -0,0 +1,359 @@
+//! Policy-driven aggregate snapshots and load-time metrics.
+//!
+//! [`SnapshotStore`] sits in front of the event store repositories.  After a
+//! save it takes a snapshot once the aggregate's policy from the `snapshots`
+//! section of `database.yaml` is due; on load it starts from the newest
+//! snapshot and only replays the events recorded after it.
+//!
+//! A snapshot whose stored JSON no longer matches the aggregate's serialized
+//! shape (a field was added, renamed or removed) is deleted and the aggregate
+//! is replayed from its first event instead.
+//!
+//! Every load is timed; [`load_metrics`] reports the figures per aggregate
+//! type for the current process.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    sync::{LazyLock, Mutex},
+    time::Instant,
+};
+
+use eventually::aggregate::{
+    Aggregate, Root,
+    repository::{GetError, Getter, SaveError, Saver},
+};
+use loom_infrastructure::config::CONFIG;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use sqlx::Row;
+
+use crate::{Pool, StateConnected};
+
+static LOAD_METRICS: LazyLock<Mutex<BTreeMap<&'static str, LoadMetrics>>> =
+    LazyLock::new(Mutex::default);
+
+/// Load statistics of one aggregate type since the process started.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LoadMetrics {
+    pub aggregate_type: String,
+    pub loads: u64,
+    /// Loads that started from a snapshot.
+    pub snapshot_hits: u64,
+    /// Events applied while loading; on a snapshot hit only those after it.
+    pub events_replayed: u64,
+    pub total_micros: u64,
+    pub max_micros: u64,
+    pub snapshots_taken: u64,
+    /// Snapshots dropped because the aggregate's shape changed.
+    pub snapshots_invalidated: u64,
+}
+
+impl LoadMetrics {
+    #[must_use]
+    pub const fn average_micros(&self) -> u64 {
+        if self.loads == 0 {
+            0
+        } else {
+            self.total_micros / self.loads
+        }
+    }
+}
+
+/// Load statistics of every aggregate type loaded in this process, by name.
+#[must_use]
+pub fn load_metrics() -> Vec<LoadMetrics> {
+    LOAD_METRICS
+        .lock()
+        .map(|metrics| metrics.values().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn record<T: Aggregate>(update: impl FnOnce(&mut LoadMetrics)) {
+    if let Ok(mut metrics) = LOAD_METRICS.lock() {
+        let entry = metrics
+            .entry(T::type_name())
+            .or_insert_with(|| LoadMetrics {
+                aggregate_type: T::type_name().to_string(),
+                ..LoadMetrics::default()
+            });
+        update(entry);
+    }
+}
+
+/// Reads and writes the `snapshots` table of one database.
+pub struct SnapshotStore<Scope> {
+    pool: Pool<Scope, StateConnected>,
+}
+
+impl<Scope> SnapshotStore<Scope> {
+    #[must_use]
+    pub const fn new(pool: Pool<Scope, StateConnected>) -> Self {
+        Self { pool }
+    }
+
+    /// Load an aggregate, starting from its newest valid snapshot if there is
+    /// one and falling back to `inner` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GetError::NotFound`] if the aggregate has no events, or an
+    /// internal error if the events cannot be read or applied.
+    pub async fn get<T, R>(&self, inner: &R, id: &T::Id) -> Result<Root<T>, GetError>
+    where
+        T: Aggregate + DeserializeOwned + Serialize + Send + Sync,
+        T::Id: Display + Send + Sync,
+        T::Event: DeserializeOwned,
+        T::Error: std::error::Error + Send + Sync + 'static,
+        R: Getter<T> + Sync,
+    {
+        let started = Instant::now();
+        let snapshot = match self.from_snapshot::<T>(id).await {
+            Ok(snapshot) => snapshot,
+            Err(error) => {
+                tracing::warn!(
+                    aggregate_type = T::type_name(),
+                    aggregate_id = %id,
+                    error = %error,
+                    "Failed to load snapshot; replaying all events."
+                );
+                None
+            }
+        };
+        let (root, hit, replayed) = match snapshot {
+            Some((root, replayed)) => (root, true, replayed),
+            None => {
+                let root = inner.get(id).await?;
+                let replayed = u64::try_from(root.version()).unwrap_or_default();
+                (root, false, replayed)
+            }
+        };
+
+        let micros = u64::try_from(started.elapsed().as_micros()).unwrap_or(u64::MAX);
+        tracing::debug!(
+            aggregate_type = T::type_name(),
+            aggregate_id = %id,
+            snapshot = hit,
+            events_replayed = replayed,
+            micros,
+            "Loaded aggregate."
+        );
+        record::<T>(|m| {
+            m.loads += 1;
+            m.snapshot_hits += u64::from(hit);
+            m.events_replayed += replayed;
+            m.total_micros += micros;
+            m.max_micros = m.max_micros.max(micros);
+        });
+        Ok(root)
+    }
+
+    /// Save through `inner` and take a snapshot if the policy is due.
+    ///
+    /// A failed snapshot is logged and does not fail the save.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `inner`.
+    pub async fn save<T, R>(&self, inner: &R, root: &mut Root<T>) -> Result<(), SaveError>
+    where
+        T: Aggregate + Serialize + Send + Sync,
+        T::Id: Display + Send + Sync,
+        R: Saver<T> + Sync,
+    {
+        inner.save(root).await?;
+        if let Err(error) = self.take_if_due(root).await {
+            tracing::warn!(
+                aggregate_type = T::type_name(),
+                aggregate_id = %root.aggregate_id(),
+                error = %error,
+                "Failed to take snapshot."
+            );
+        }
+        Ok(())
+    }
+
+    /// Store the current state of `root` as a snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state cannot be serialized or stored.
+    pub async fn take<T>(&self, root: &Root<T>) -> Result<(), crate::Error>
+    where
+        T: Aggregate + Serialize,
+        T::Id: Display,
+    {
+        let id = root.aggregate_id().to_string();
+        let state = serde_json::to_vec(&**root)?;
+        sqlx::query(
+            "INSERT INTO snapshots (event_stream_id, aggregate_type, aggregate_id, state, version) \
+             VALUES (?, ?, ?, ?, ?) ON CONFLICT DO NOTHING",
+        )
+        .bind(id.as_str())
+        .bind(T::type_name())
+        .bind(id.as_str())
+        .bind(state)
+        .bind(i64::try_from(root.version()).unwrap_or(i64::MAX))
+        .execute(self.pool.as_ref())
+        .await?;
+        record::<T>(|m| m.snapshots_taken += 1);
+        Ok(())
+    }
+
+    /// Delete every snapshot of one aggregate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn invalidate(
+        &self,
+        aggregate_type: &str,
+        aggregate_id: &str,
+    ) -> Result<(), crate::Error> {
+        sqlx::query("DELETE FROM snapshots WHERE aggregate_type = ? AND aggregate_id = ?")
+            .bind(aggregate_type)
+            .bind(aggregate_id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn take_if_due<T>(&self, root: &Root<T>) -> Result<(), crate::Error>
+    where
+        T: Aggregate + Serialize,
+        T::Id: Display,
+    {
+        let policy = CONFIG
+            .get_database()
+            .get_snapshots()
+            .policy_for(T::type_name());
+        if !policy.is_enabled() {
+            return Ok(());
+        }
+
+        let id = root.aggregate_id().to_string();
+        let version = i64::try_from(root.version()).unwrap_or(i64::MAX);
+        let last: i64 = sqlx::query(
+            "SELECT CAST(COALESCE(MAX(version), 0) AS BIGINT) AS version FROM snapshots \
+             WHERE aggregate_type = ? AND aggregate_id = ?",
+        )
+        .bind(T::type_name())
+        .bind(id.as_str())
+        .fetch_one(self.pool.as_ref())
+        .await?
+        .try_get("version")?;
+
+        let new_events = u64::try_from(version - last).unwrap_or_default();
+        let mut due = policy.get_every_events().is_some_and(|n| new_events >= n);
+        if !due && let Some(max_bytes) = policy.get_max_stream_bytes() {
+            let bytes: i64 = sqlx::query(
+                "SELECT CAST(COALESCE(SUM(LENGTH(event)), 0) AS BIGINT) AS bytes FROM events \
+                 WHERE event_stream_id = ? AND version > ?",
+            )
+            .bind(id.as_str())
+            .bind(last)
+            .fetch_one(self.pool.as_ref())
+            .await?
+            .try_get("bytes")?;
+            due = u64::try_from(bytes).unwrap_or_default() >= max_bytes;
+        }
+
+        if due && new_events > 0 {
+            self.take(root).await?;
+        }
+        Ok(())
+    }
+
+    /// The aggregate rebuilt from its newest snapshot plus the events after
+    /// it, and the number of those events.
+    async fn from_snapshot<T>(&self, id: &T::Id) -> Result<Option<(Root<T>, u64)>, crate::Error>
+    where
+        T: Aggregate + DeserializeOwned + Serialize,
+        T::Id: Display,
+        T::Event: DeserializeOwned,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let id = id.to_string();
+        let row = sqlx::query(
+            "SELECT CAST(version AS BIGINT) AS version, state FROM snapshots \
+             WHERE aggregate_type = ? AND aggregate_id = ? ORDER BY version DESC LIMIT 1",
+        )
+        .bind(T::type_name())
+        .bind(id.as_str())
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let mut version: i64 = row.try_get("version")?;
+        let stored: Vec<u8> = row.try_get("state")?;
+
+        let Some(mut state) = Self::decode::<T>(&stored) else {
+            tracing::info!(
+                aggregate_type = T::type_name(),
+                aggregate_id = %id,
+                "Aggregate shape changed; dropping its snapshots."
+            );
+            self.invalidate(T::type_name(), &id).await?;
+            record::<T>(|m| m.snapshots_invalidated += 1);
+            return Ok(None);
+        };
+
+        let rows = sqlx::query(
+            "SELECT CAST(version AS BIGINT) AS version, event FROM events \
+             WHERE event_stream_id = ? AND version > ? ORDER BY version",
+        )
+        .bind(id.as_str())
+        .bind(version)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        let replayed = rows.len() as u64;
+        for row in rows {
+            let payload: Vec<u8> = row.try_get("event")?;
+            let event: T::Event = serde_json::from_slice(&payload)?;
+            state = T::apply(Some(state), event)
+                .map_err(|e| crate::Error::AggregateError(e.to_string()))?;
+            version = row.try_get("version")?;
+        }
+
+        Ok(Some((
+            Root::rehydrate_from_state(version.try_into().unwrap_or_default(), state),
+            replayed,
+        )))
+    }
+
+    /// Deserialize a snapshot, or `None` if it does not round-trip to the same
+    /// JSON shape, i.e. the aggregate's fields changed since it was taken.
+    fn decode<T: DeserializeOwned + Serialize>(stored: &[u8]) -> Option<T> {
+        let stored: Value = serde_json::from_slice(stored).ok()?;
+        let state: T = serde_json::from_value(stored.clone()).ok()?;
+        let current = serde_json::to_value(&state).ok()?;
+        (shape(&stored) == shape(&current)).then_some(state)
+    }
+}
+
+/// The key paths of a JSON value, ignoring the values themselves.
+fn shape(value: &Value) -> BTreeSet<String> {
+    fn collect(prefix: &str, value: &Value, out: &mut BTreeSet<String>) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    let path = format!("{prefix}.{key}");
+                    collect(&path, value, out);
+                    out.insert(path);
+                }
+            }
+            Value::Array(items) => {
+                let path = format!("{prefix}[]");
+                for item in items {
+                    collect(&path, item, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = BTreeSet::new();
+    collect("", value, &mut out);
+    out
+}
@@ -0,0 +1,340 @@
+use chrono::{SecondsFormat, Utc};
+use eventually_projection::RawEvent;
+use sqlx::{Row, any::AnyRow};
+
+use crate::{
+    ConnectedTenantPool,
+    time_travel::{RAW_EVENT_COLUMNS, map_raw_event},
+};
+
+/// The event store of a tenant database as seen by offline sync.
+///
+/// On the server it hands out events in global order; on an offline replica
+/// it also keeps track of what has been pushed (`sync_streams`), the sync
+/// state (`sync_state`) and refused local changes (`sync_conflicts`).
+pub struct SyncRepository {
+    pool: ConnectedTenantPool,
+}
+
+impl SyncRepository {
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn state(&self, key: &str) -> Result<Option<String>, crate::Error> {
+        let row = sqlx::query("SELECT value FROM sync_state WHERE key = ?")
+            .bind(key)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        Ok(row.map(|r| r.try_get("value")).transpose()?)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn set_state(&self, key: &str, value: &str) -> Result<(), crate::Error> {
+        sqlx::query(
+            "INSERT INTO sync_state (key, value) VALUES (?, ?) \
+             ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+        )
+        .bind(key)
+        .bind(value)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn clear_state(&self, key: &str) -> Result<(), crate::Error> {
+        sqlx::query("DELETE FROM sync_state WHERE key = ?")
+            .bind(key)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// Up to `limit` events after the global position `after`, in global
+    /// order.  Webhook events carry signing secrets and are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn events_after(
+        &self,
+        after: i64,
+        limit: i64,
+    ) -> Result<Vec<RawEvent>, crate::Error> {
+        let sql = format!(
+            "SELECT {RAW_EVENT_COLUMNS} FROM events \
+             WHERE global_position > ? AND type NOT LIKE 'Webhook%' \
+             ORDER BY global_position LIMIT ?"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(after)
+            .bind(limit)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.iter().map(map_raw_event).collect()
+    }
+
+    /// The event that created a stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn first_event(&self, stream_id: &str) -> Result<Option<RawEvent>, crate::Error> {
+        let sql = format!(
+            "SELECT {RAW_EVENT_COLUMNS} FROM events WHERE event_stream_id = ? AND version = 1"
+        );
+        let row = sqlx::query(&sql)
+            .bind(stream_id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.as_ref().map(map_raw_event).transpose()
+    }
+
+    /// Every event of a stream, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn stream_events(&self, stream_id: &str) -> Result<Vec<RawEvent>, crate::Error> {
+        let sql = format!(
+            "SELECT {RAW_EVENT_COLUMNS} FROM events WHERE event_stream_id = ? ORDER BY version"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(stream_id)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.iter().map(map_raw_event).collect()
+    }
+
+    /// The last version of a stream the server has confirmed, 0 if none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn synced_version(&self, stream_id: &str) -> Result<i64, crate::Error> {
+        let row = sqlx::query("SELECT synced_version FROM sync_streams WHERE stream_id = ?")
+            .bind(stream_id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        Ok(row
+            .map(|r| r.try_get::<i64, _>("synced_version"))
+            .transpose()?
+            .unwrap_or_default())
+    }
+
+    /// Local timesheet events the server has not confirmed yet, in the
+    /// order they were recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn pending(&self) -> Result<Vec<RawEvent>, crate::Error> {
+        let sql = format!(
+            "SELECT {RAW_EVENT_COLUMNS} FROM events \
+             WHERE type LIKE 'Timesheet%' AND version > COALESCE( \
+                 (SELECT synced_version FROM sync_streams WHERE stream_id = event_stream_id), 0) \
+             ORDER BY global_position"
+        );
+        let rows = sqlx::query(&sql).fetch_all(self.pool.as_ref()).await?;
+        rows.iter().map(map_raw_event).collect()
+    }
+
+    /// Number of [`pending`](Self::pending) events.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn pending_count(&self) -> Result<i64, crate::Error> {
+        let row = sqlx::query(
+            "SELECT COUNT(*) AS n FROM events \
+             WHERE type LIKE 'Timesheet%' AND version > COALESCE( \
+                 (SELECT synced_version FROM sync_streams WHERE stream_id = event_stream_id), 0)",
+        )
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        Ok(row.try_get("n")?)
+    }
+
+    /// Store an event pulled from the server under its server version and
+    /// mark the stream synced up to it.  The local global position and
+    /// recording time are assigned as for any new event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails, e.g. because the
+    /// version is already taken by a local event.
+    pub async fn append(&self, event: &RawEvent) -> Result<(), crate::Error> {
+        let version = i64::try_from(event.version).unwrap_or_default();
+        let schema_version = i64::try_from(event.schema_version).unwrap_or_default();
+        let mut tx = self.pool.as_ref().begin().await?;
+        sqlx::query(
+            "INSERT INTO event_streams (event_stream_id, version) VALUES (?, ?) \
+             ON CONFLICT (event_stream_id) DO UPDATE SET version = excluded.version",
+        )
+        .bind(event.stream_id.as_str())
+        .bind(version)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "INSERT INTO events (event_stream_id, type, version, event, metadata, schema_version) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(event.stream_id.as_str())
+        .bind(event.event_type.as_str())
+        .bind(version)
+        .bind(event.payload_bytes.clone())
+        .bind(serde_json::to_string(&event.metadata)?)
+        .bind(schema_version)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "INSERT INTO sync_streams (stream_id, synced_version) VALUES (?, ?) \
+             ON CONFLICT (stream_id) DO UPDATE SET synced_version = excluded.synced_version",
+        )
+        .bind(event.stream_id.as_str())
+        .bind(version)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Drop the unconfirmed local events of a stream and return them.  The
+    /// stream is rolled back to its synced version, or removed if it was
+    /// never synced; its snapshots are dropped as they may include the
+    /// discarded events.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn discard_pending(&self, stream_id: &str) -> Result<Vec<RawEvent>, crate::Error> {
+        let synced = self.synced_version(stream_id).await?;
+        let sql = format!(
+            "SELECT {RAW_EVENT_COLUMNS} FROM events \
+             WHERE event_stream_id = ? AND version > ? ORDER BY version"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(stream_id)
+            .bind(synced)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        let discarded = rows
+            .iter()
+            .map(map_raw_event)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut tx = self.pool.as_ref().begin().await?;
+        sqlx::query("DELETE FROM snapshots WHERE event_stream_id = ?")
+            .bind(stream_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM events WHERE event_stream_id = ? AND version > ?")
+            .bind(stream_id)
+            .bind(synced)
+            .execute(&mut *tx)
+            .await?;
+        if synced == 0 {
+            sqlx::query("DELETE FROM event_streams WHERE event_stream_id = ?")
+                .bind(stream_id)
+                .execute(&mut *tx)
+                .await?;
+        } else {
+            sqlx::query("UPDATE event_streams SET version = ? WHERE event_stream_id = ?")
+                .bind(synced)
+                .bind(stream_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(discarded)
+    }
+
+    /// Keep local changes the server refused.  `local_events` is the JSON
+    /// array of the discarded events.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn record_conflict(
+        &self,
+        stream_id: &str,
+        server_version: i64,
+        local_events: &str,
+        reason: &str,
+    ) -> Result<(), crate::Error> {
+        sqlx::query(
+            "INSERT INTO sync_conflicts \
+             (stream_id, server_version, local_events, reason, detected_at) \
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(stream_id)
+        .bind(server_version)
+        .bind(local_events)
+        .bind(reason)
+        .bind(now())
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Conflicts not dismissed yet, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn conflicts(&self) -> Result<Vec<ConflictRow>, crate::Error> {
+        let rows =
+            sqlx::query("SELECT * FROM sync_conflicts WHERE dismissed_at IS NULL ORDER BY id DESC")
+                .fetch_all(self.pool.as_ref())
+                .await?;
+        rows.iter().map(Self::map_conflict).collect()
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn dismiss_conflict(&self, id: i64) -> Result<(), crate::Error> {
+        sqlx::query("UPDATE sync_conflicts SET dismissed_at = ? WHERE id = ?")
+            .bind(now())
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    fn map_conflict(row: &AnyRow) -> Result<ConflictRow, crate::Error> {
+        Ok(ConflictRow {
+            id: row.try_get("id")?,
+            stream_id: row.try_get("stream_id")?,
+            server_version: row.try_get("server_version")?,
+            local_events: row.try_get("local_events")?,
+            reason: row.try_get("reason")?,
+            detected_at: row.try_get("detected_at")?,
+        })
+    }
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[derive(Debug, Clone)]
+pub struct ConflictRow {
+    pub id: i64,
+    pub stream_id: String,
+    /// Version of the stream on the server when the conflict was found.
+    pub server_version: i64,
+    /// JSON array of the discarded local events.
+    pub local_events: String,
+    pub reason: String,
+    pub detected_at: String,
+}
@@ -17,6 +17,33 @@ impl ActivityProjector {
     pub const fn new(pool: ConnectedTenantPool) -> Self {
         Self { pool }
     }
+
+    async fn set_archived(&self, id: &str, archived: bool) -> Result<(), crate::Error> {
+        let query = Query::update()
+            .table(TableRef::from(Self::TABLE))
+            .values([(DynIden::from("archived"), archived.into())])
+            .cond_where(Condition::all().add(Expr::col("id").eq(Expr::val(id))))
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), crate::Error> {
+        let query = Query::delete()
+            .from_table(TableRef::from(Self::TABLE))
+            .cond_where(Condition::all().add(Expr::col("id").eq(Expr::val(id))))
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -88,6 +115,9 @@ impl Projector for ActivityProjector {
                     .execute(self.pool.as_ref())
                     .await?;
             }
+            "ActivityArchived" => self.set_archived(&event.stream_id, true).await?,
+            "ActivityRestored" => self.set_archived(&event.stream_id, false).await?,
+            "ActivityDeleted" => self.delete(&event.stream_id).await?,
             _ => {}
         }
 
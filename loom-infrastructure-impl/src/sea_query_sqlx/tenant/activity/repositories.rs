@@ -9,7 +9,7 @@ use loom_core::tenant::activity::{
 };
 use sqlx::{Row, any::AnyRow};
 
-use crate::ConnectedTenantPool;
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
 
 pub struct ActivityRepository {
     pool: ConnectedTenantPool,
@@ -38,8 +38,8 @@ impl ActivityRepository {
     /// Returns an error if the database query fails.
     pub async fn all(&self) -> Result<Vec<ActivityRow>, crate::Error> {
         let rows = sqlx::query(
-            "SELECT id, project_id, name, comment, visible, billable \
-             FROM projections__activities ORDER BY name",
+            "SELECT id, project_id, name, comment, visible, billable, archived \
+             FROM projections__activities WHERE archived = 0 ORDER BY name",
         )
         .fetch_all(self.pool.as_ref())
         .await?;
@@ -51,8 +51,9 @@ impl ActivityRepository {
     /// Returns an error if the database query fails.
     pub async fn by_project(&self, project_id: &str) -> Result<Vec<ActivityRow>, crate::Error> {
         let rows = sqlx::query(
-            "SELECT id, project_id, name, comment, visible, billable \
-             FROM projections__activities WHERE project_id = ? OR project_id IS NULL ORDER BY name",
+            "SELECT id, project_id, name, comment, visible, billable, archived \
+             FROM projections__activities \
+             WHERE (project_id = ? OR project_id IS NULL) AND archived = 0 ORDER BY name",
         )
         .bind(project_id)
         .fetch_all(self.pool.as_ref())
@@ -60,6 +61,34 @@ impl ActivityRepository {
         rows.into_iter().map(|r| Self::map_row(&r)).collect()
     }
 
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn archived(&self) -> Result<Vec<ActivityRow>, crate::Error> {
+        let rows = sqlx::query(
+            "SELECT id, project_id, name, comment, visible, billable, archived \
+             FROM projections__activities WHERE archived <> 0 ORDER BY name",
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// Number of activities bound to the project, archived ones included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn count_for_project(&self, project_id: &str) -> Result<i64, crate::Error> {
+        let row = sqlx::query(
+            "SELECT CAST(COUNT(*) AS BIGINT) AS n FROM projections__activities WHERE project_id = ?",
+        )
+        .bind(project_id)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        Ok(row.try_get("n")?)
+    }
+
     fn map_row(row: &AnyRow) -> Result<ActivityRow, crate::Error> {
         Ok(ActivityRow {
             id: row.try_get("id")?,
@@ -68,6 +97,7 @@ impl ActivityRepository {
             comment: row.try_get("comment")?,
             visible: bool_col(row, "visible"),
             billable: bool_col(row, "billable"),
+            archived: bool_col(row, "archived"),
         })
     }
 }
@@ -85,6 +115,7 @@ pub struct ActivityRow {
     pub comment: Option<String>,
     pub visible: bool,
     pub billable: bool,
+    pub archived: bool,
 }
 
 #[async_trait]
@@ -93,7 +124,9 @@ impl Getter<Activity> for ActivityRepository {
         &self,
         id: &ActivityId,
     ) -> Result<eventually::aggregate::Root<Activity>, GetError> {
-        self.repository.get(id).await
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
     }
 }
 
@@ -103,7 +136,9 @@ impl Saver<Activity> for ActivityRepository {
         &self,
         root: &mut eventually::aggregate::Root<Activity>,
     ) -> Result<(), SaveError> {
-        self.repository.save(root).await
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
     }
 }
 
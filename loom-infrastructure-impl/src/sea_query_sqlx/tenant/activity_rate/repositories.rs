@@ -10,7 +10,7 @@ use loom_core::tenant::activity_rate::{
 };
 use sqlx::{Row, any::AnyRow};
 
-use crate::ConnectedTenantPool;
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
 
 pub struct ActivityRateRepository {
     pool: ConnectedTenantPool,
@@ -98,7 +98,9 @@ impl Getter<ActivityRate> for ActivityRateRepository {
         &self,
         id: &ActivityRateId,
     ) -> Result<eventually::aggregate::Root<ActivityRate>, GetError> {
-        self.repository.get(id).await
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
     }
 }
 
@@ -108,7 +110,9 @@ impl Saver<ActivityRate> for ActivityRateRepository {
         &self,
         root: &mut eventually::aggregate::Root<ActivityRate>,
     ) -> Result<(), SaveError> {
-        self.repository.save(root).await
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
     }
 }
 
@@ -0,0 +1,139 @@
+use async_trait::async_trait;
+use eventually_projection::{Projector, RawEvent};
+use loom_core::tenant::approval::{ApprovalEvent, ApprovalStatus};
+use sea_query::{Condition, DynIden, Expr, ExprTrait, OnConflict, Query, TableRef};
+
+use crate::ConnectedTenantPool;
+
+pub struct ApprovalProjector {
+    pool: ConnectedTenantPool,
+}
+
+impl ApprovalProjector {
+    const TABLE: &'static str = "projections__approvals";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+
+    async fn review(
+        &self,
+        id: &str,
+        status: ApprovalStatus,
+        reviewer_id: String,
+        comment: Option<String>,
+        reviewed_at: String,
+    ) -> Result<(), crate::Error> {
+        let query = Query::update()
+            .table(TableRef::from(Self::TABLE))
+            .values([
+                (DynIden::from("status"), status.as_str().into()),
+                (DynIden::from("reviewer_id"), reviewer_id.into()),
+                (DynIden::from("comment"), comment.into()),
+                (DynIden::from("reviewed_at"), reviewed_at.into()),
+            ])
+            .cond_where(Condition::all().add(Expr::col("id").eq(Expr::val(id))))
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Projector for ApprovalProjector {
+    type Error = crate::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        match event.event_type.as_str() {
+            "ApprovalRequested" => {
+                let ApprovalEvent::Requested {
+                    id,
+                    user_id,
+                    period_start,
+                    period_end,
+                    timesheet_ids,
+                    comment,
+                    submitted_at,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let entry_count = i32::try_from(timesheet_ids.len()).unwrap_or(i32::MAX);
+                let query = Query::insert()
+                    .into_table(TableRef::from(Self::TABLE))
+                    .columns([
+                        DynIden::from("id"),
+                        DynIden::from("user_id"),
+                        DynIden::from("period_start"),
+                        DynIden::from("period_end"),
+                        DynIden::from("status"),
+                        DynIden::from("comment"),
+                        DynIden::from("entry_count"),
+                        DynIden::from("submitted_at"),
+                    ])
+                    .values_panic([
+                        id.to_string().into(),
+                        user_id.to_string().into(),
+                        period_start.into(),
+                        period_end.into(),
+                        ApprovalStatus::Submitted.as_str().into(),
+                        comment.into(),
+                        entry_count.into(),
+                        submitted_at.into(),
+                    ])
+                    .on_conflict(OnConflict::new().do_nothing().to_owned())
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "ApprovalGranted" => {
+                let ApprovalEvent::Granted {
+                    reviewer_id,
+                    comment,
+                    reviewed_at,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+                self.review(
+                    &event.stream_id,
+                    ApprovalStatus::Approved,
+                    reviewer_id.to_string(),
+                    comment,
+                    reviewed_at,
+                )
+                .await?;
+            }
+            "ApprovalRejected" => {
+                let ApprovalEvent::Rejected {
+                    reviewer_id,
+                    comment,
+                    reviewed_at,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+                self.review(
+                    &event.stream_id,
+                    ApprovalStatus::Rejected,
+                    reviewer_id.to_string(),
+                    Some(comment),
+                    reviewed_at,
+                )
+                .await?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
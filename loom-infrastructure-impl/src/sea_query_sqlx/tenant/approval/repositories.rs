@@ -0,0 +1,141 @@
+use std::ops::Deref;
+
+use async_trait::async_trait;
+use eventually::aggregate::repository::{GetError, Getter, SaveError, Saver};
+use eventually::serde::Json;
+use eventually_any::snapshot::Repository;
+use loom_core::tenant::approval::{
+    Approval, ApprovalEvent, ApprovalId, ApprovalRepository as ApprovalRepositoryTrait,
+};
+use sqlx::{Row, any::AnyRow};
+
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
+
+pub struct ApprovalRepository {
+    pool: ConnectedTenantPool,
+    repository: Repository<Approval, Json<Approval>, Json<ApprovalEvent>>,
+}
+
+impl Deref for ApprovalRepository {
+    type Target = Repository<Approval, Json<Approval>, Json<ApprovalEvent>>;
+    fn deref(&self) -> &Self::Target {
+        &self.repository
+    }
+}
+
+impl ApprovalRepository {
+    const SELECT: &'static str = "SELECT id, user_id, period_start, period_end, status, comment, \
+         entry_count, submitted_at, reviewer_id, reviewed_at \
+         FROM projections__approvals";
+
+    /// # Errors
+    ///
+    /// Returns an error if the event store repository cannot be initialized.
+    pub async fn from_pool(pool: ConnectedTenantPool) -> Result<Self, sqlx::migrate::MigrateError> {
+        let repository =
+            Repository::new(pool.as_ref().clone(), Json::default(), Json::default()).await?;
+        Ok(Self { pool, repository })
+    }
+
+    /// A user's submissions, newest period first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn for_user(&self, user_id: &str) -> Result<Vec<ApprovalRow>, crate::Error> {
+        let sql = format!(
+            "{} WHERE user_id = ? ORDER BY period_start DESC, submitted_at DESC",
+            Self::SELECT
+        );
+        let rows = sqlx::query(&sql)
+            .bind(user_id)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// Submissions awaiting review, oldest submission first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn pending(&self) -> Result<Vec<ApprovalRow>, crate::Error> {
+        let sql = format!(
+            "{} WHERE status = 'submitted' ORDER BY submitted_at ASC",
+            Self::SELECT
+        );
+        let rows = sqlx::query(&sql).fetch_all(self.pool.as_ref()).await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find(&self, id: &str) -> Result<Option<ApprovalRow>, crate::Error> {
+        let sql = format!("{} WHERE id = ?", Self::SELECT);
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.map(|r| Self::map_row(&r)).transpose()
+    }
+
+    fn map_row(row: &AnyRow) -> Result<ApprovalRow, crate::Error> {
+        Ok(ApprovalRow {
+            id: row.try_get("id")?,
+            user_id: row.try_get("user_id")?,
+            period_start: row.try_get("period_start")?,
+            period_end: row.try_get("period_end")?,
+            status: row.try_get("status")?,
+            comment: row.try_get("comment")?,
+            entry_count: row.try_get("entry_count")?,
+            submitted_at: row.try_get("submitted_at")?,
+            reviewer_id: row.try_get("reviewer_id")?,
+            reviewed_at: row.try_get("reviewed_at")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ApprovalRow {
+    pub id: String,
+    pub user_id: String,
+    /// First day of the period, `YYYY-MM-DD`.
+    pub period_start: String,
+    /// Last day of the period, `YYYY-MM-DD`.
+    pub period_end: String,
+    /// `submitted`, `approved` or `rejected`.
+    pub status: String,
+    /// The submitter's comment, or the reviewer's once reviewed.
+    pub comment: Option<String>,
+    pub entry_count: i32,
+    pub submitted_at: String,
+    pub reviewer_id: Option<String>,
+    pub reviewed_at: Option<String>,
+}
+
+#[async_trait]
+impl Getter<Approval> for ApprovalRepository {
+    async fn get(
+        &self,
+        id: &ApprovalId,
+    ) -> Result<eventually::aggregate::Root<Approval>, GetError> {
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
+    }
+}
+
+#[async_trait]
+impl Saver<Approval> for ApprovalRepository {
+    async fn save(
+        &self,
+        root: &mut eventually::aggregate::Root<Approval>,
+    ) -> Result<(), SaveError> {
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
+    }
+}
+
+impl ApprovalRepositoryTrait for ApprovalRepository {}
@@ -0,0 +1,125 @@
+use async_trait::async_trait;
+use eventually_projection::{Projector, RawEvent};
+use loom_core::tenant::{customer::CustomerEvent, project::ProjectEvent};
+use sea_query::{DynIden, OnConflict, Query, TableRef};
+
+use crate::ConnectedTenantPool;
+
+/// Projects reached budget thresholds of customers and projects into
+/// `projections__budget_alerts`.
+pub struct BudgetAlertProjector {
+    pool: ConnectedTenantPool,
+}
+
+impl BudgetAlertProjector {
+    const TABLE: &'static str = "projections__budget_alerts";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+
+    async fn insert(
+        &self,
+        scope: &str,
+        event: &RawEvent,
+        threshold: u8,
+        period: Option<String>,
+        used_seconds: i64,
+        used_amount: i64,
+    ) -> Result<(), crate::Error> {
+        let query = Query::insert()
+            .into_table(TableRef::from(Self::TABLE))
+            .columns([
+                DynIden::from("scope"),
+                DynIden::from("target_id"),
+                DynIden::from("period"),
+                DynIden::from("threshold"),
+                DynIden::from("used_seconds"),
+                DynIden::from("used_amount"),
+                DynIden::from("global_position"),
+            ])
+            .values_panic([
+                scope.into(),
+                event.stream_id.clone().into(),
+                period.unwrap_or_default().into(),
+                i32::from(threshold).into(),
+                used_seconds.into(),
+                used_amount.into(),
+                event.global_position.into(),
+            ])
+            .on_conflict(OnConflict::new().do_nothing().to_owned())
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// A changed budget starts over; earlier alerts no longer apply.
+    async fn clear(&self, scope: &str, target_id: &str) -> Result<(), crate::Error> {
+        sqlx::query("DELETE FROM projections__budget_alerts WHERE scope = ? AND target_id = ?")
+            .bind(scope)
+            .bind(target_id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Projector for BudgetAlertProjector {
+    type Error = crate::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        match event.event_type.as_str() {
+            "ProjectBudgetThresholdReached" => {
+                let ProjectEvent::BudgetThresholdReached {
+                    threshold,
+                    period,
+                    used_seconds,
+                    used_amount,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+                self.insert(
+                    "project",
+                    &event,
+                    threshold,
+                    period,
+                    used_seconds,
+                    used_amount,
+                )
+                .await?;
+            }
+            "CustomerBudgetThresholdReached" => {
+                let CustomerEvent::BudgetThresholdReached {
+                    threshold,
+                    period,
+                    used_seconds,
+                    used_amount,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+                self.insert(
+                    "customer",
+                    &event,
+                    threshold,
+                    period,
+                    used_seconds,
+                    used_amount,
+                )
+                .await?;
+            }
+            "ProjectBudgetUpdated" => self.clear("project", &event.stream_id).await?,
+            "CustomerBudgetUpdated" => self.clear("customer", &event.stream_id).await?,
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
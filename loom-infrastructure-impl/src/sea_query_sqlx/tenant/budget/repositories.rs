@@ -0,0 +1,138 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Row, any::AnyRow};
+
+use crate::ConnectedTenantPool;
+
+/// Reads logged usage and reached thresholds of customer and project budgets.
+pub struct BudgetRepository {
+    pool: ConnectedTenantPool,
+}
+
+impl BudgetRepository {
+    const USAGE: &'static str = "SELECT CAST(COALESCE(SUM(t.duration), 0) AS BIGINT) AS used_seconds, \
+         CAST(COALESCE(SUM(t.rate), 0) AS BIGINT) AS used_amount \
+         FROM projections__timesheets t";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+
+    /// Logged seconds and amount (cents) of the finished timesheets of a
+    /// project, optionally limited to those started within `[from, to)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn project_usage(
+        &self,
+        project_id: &str,
+        range: Option<&(String, String)>,
+    ) -> Result<(i64, i64), crate::Error> {
+        self.usage("t.project_id = ?", project_id, range).await
+    }
+
+    /// Logged seconds and amount (cents) over all projects of a customer,
+    /// optionally limited to timesheets started within `[from, to)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn customer_usage(
+        &self,
+        customer_id: &str,
+        range: Option<&(String, String)>,
+    ) -> Result<(i64, i64), crate::Error> {
+        self.usage(
+            "t.project_id IN (SELECT id FROM projections__projects WHERE customer_id = ?)",
+            customer_id,
+            range,
+        )
+        .await
+    }
+
+    async fn usage(
+        &self,
+        target: &str,
+        id: &str,
+        range: Option<&(String, String)>,
+    ) -> Result<(i64, i64), crate::Error> {
+        let period = if range.is_some() {
+            " AND t.start_time >= ? AND t.start_time < ?"
+        } else {
+            ""
+        };
+        let sql = format!(
+            "{} WHERE {target} AND t.end_time IS NOT NULL{period}",
+            Self::USAGE
+        );
+
+        let mut query = sqlx::query(&sql).bind(id);
+        if let Some((from, to)) = range {
+            query = query.bind(from.as_str()).bind(to.as_str());
+        }
+        let row = query.fetch_one(self.pool.as_ref()).await?;
+        Ok((row.try_get("used_seconds")?, row.try_get("used_amount")?))
+    }
+
+    /// When the event at `global_position` was recorded; `None` for events
+    /// stored before the time was kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn recorded_at(
+        &self,
+        global_position: i64,
+    ) -> Result<Option<DateTime<Utc>>, crate::Error> {
+        let row = sqlx::query("SELECT recorded_at FROM events WHERE global_position = ?")
+            .bind(global_position)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        Ok(row
+            .map(|r| r.try_get::<Option<String>, _>("recorded_at"))
+            .transpose()?
+            .flatten()
+            .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+            .map(|t| t.with_timezone(&Utc)))
+    }
+
+    /// Every reached threshold, highest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn alerts(&self) -> Result<Vec<BudgetAlertRow>, crate::Error> {
+        let rows = sqlx::query(
+            "SELECT scope, target_id, period, threshold, used_seconds, used_amount \
+             FROM projections__budget_alerts ORDER BY threshold DESC, global_position DESC",
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(Self::map_row).collect()
+    }
+
+    fn map_row(row: &AnyRow) -> Result<BudgetAlertRow, crate::Error> {
+        let period: String = row.try_get("period")?;
+        Ok(BudgetAlertRow {
+            scope: row.try_get("scope")?,
+            target_id: row.try_get("target_id")?,
+            period: (!period.is_empty()).then_some(period),
+            threshold: u8::try_from(row.try_get::<i32, _>("threshold")?).unwrap_or(u8::MAX),
+            used_seconds: row.try_get("used_seconds")?,
+            used_amount: row.try_get("used_amount")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BudgetAlertRow {
+    /// `customer` or `project`.
+    pub scope: String,
+    pub target_id: String,
+    /// `YYYY-MM` for monthly budgets.
+    pub period: Option<String>,
+    pub threshold: u8,
+    pub used_seconds: i64,
+    pub used_amount: i64,
+}
@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use eventually_projection::{Projector, RawEvent};
+use loom_core::tenant::calendar_rule::CalendarRuleEvent;
+use sea_query::{Condition, DynIden, Expr, ExprTrait, OnConflict, Query, TableRef};
+
+use crate::ConnectedTenantPool;
+
+pub struct CalendarRuleProjector {
+    pool: ConnectedTenantPool,
+}
+
+impl CalendarRuleProjector {
+    const TABLE: &'static str = "projections__calendar_rules";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl Projector for CalendarRuleProjector {
+    type Error = crate::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        match event.event_type.as_str() {
+            "CalendarRuleAdded" => {
+                let CalendarRuleEvent::Added {
+                    id,
+                    user_id,
+                    keyword,
+                    project_id,
+                    activity_id,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::insert()
+                    .into_table(TableRef::from(Self::TABLE))
+                    .columns([
+                        DynIden::from("id"),
+                        DynIden::from("user_id"),
+                        DynIden::from("keyword"),
+                        DynIden::from("project_id"),
+                        DynIden::from("activity_id"),
+                        DynIden::from("global_position"),
+                    ])
+                    .values_panic([
+                        id.to_string().into(),
+                        user_id.to_string().into(),
+                        keyword.into(),
+                        project_id.to_string().into(),
+                        activity_id.map(|a| a.to_string()).into(),
+                        event.global_position.into(),
+                    ])
+                    .on_conflict(OnConflict::new().do_nothing().to_owned())
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "CalendarRuleRemoved" => {
+                let query = Query::delete()
+                    .from_table(TableRef::from(Self::TABLE))
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
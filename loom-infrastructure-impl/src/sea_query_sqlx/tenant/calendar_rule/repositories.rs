@@ -0,0 +1,101 @@
+use std::ops::Deref;
+
+use async_trait::async_trait;
+use eventually::aggregate::repository::{GetError, Getter, SaveError, Saver};
+use eventually::serde::Json;
+use eventually_any::snapshot::Repository;
+use loom_core::tenant::calendar_rule::{
+    CalendarRule, CalendarRuleEvent, CalendarRuleId,
+    CalendarRuleRepository as CalendarRuleRepositoryTrait,
+};
+use sqlx::{Row, any::AnyRow};
+
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
+
+pub struct CalendarRuleRepository {
+    pool: ConnectedTenantPool,
+    repository: Repository<CalendarRule, Json<CalendarRule>, Json<CalendarRuleEvent>>,
+}
+
+impl Deref for CalendarRuleRepository {
+    type Target = Repository<CalendarRule, Json<CalendarRule>, Json<CalendarRuleEvent>>;
+    fn deref(&self) -> &Self::Target {
+        &self.repository
+    }
+}
+
+impl CalendarRuleRepository {
+    const SELECT: &'static str = "SELECT id, user_id, keyword, project_id, activity_id \
+         FROM projections__calendar_rules";
+
+    /// # Errors
+    ///
+    /// Returns an error if the event store repository cannot be initialized.
+    pub async fn from_pool(pool: ConnectedTenantPool) -> Result<Self, sqlx::migrate::MigrateError> {
+        let repository =
+            Repository::new(pool.as_ref().clone(), Json::default(), Json::default()).await?;
+        Ok(Self { pool, repository })
+    }
+
+    /// A user's calendar rules in the order they were added.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn for_user(&self, user_id: &str) -> Result<Vec<CalendarRuleRow>, crate::Error> {
+        let sql = format!(
+            "{} WHERE user_id = ? ORDER BY global_position ASC",
+            Self::SELECT
+        );
+        let rows = sqlx::query(&sql)
+            .bind(user_id)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    fn map_row(row: &AnyRow) -> Result<CalendarRuleRow, crate::Error> {
+        Ok(CalendarRuleRow {
+            id: row.try_get("id")?,
+            user_id: row.try_get("user_id")?,
+            keyword: row.try_get("keyword")?,
+            project_id: row.try_get("project_id")?,
+            activity_id: row.try_get("activity_id")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CalendarRuleRow {
+    pub id: String,
+    pub user_id: String,
+    pub keyword: String,
+    pub project_id: String,
+    pub activity_id: Option<String>,
+}
+
+#[async_trait]
+impl Getter<CalendarRule> for CalendarRuleRepository {
+    async fn get(
+        &self,
+        id: &CalendarRuleId,
+    ) -> Result<eventually::aggregate::Root<CalendarRule>, GetError> {
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
+    }
+}
+
+#[async_trait]
+impl Saver<CalendarRule> for CalendarRuleRepository {
+    async fn save(
+        &self,
+        root: &mut eventually::aggregate::Root<CalendarRule>,
+    ) -> Result<(), SaveError> {
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
+    }
+}
+
+impl CalendarRuleRepositoryTrait for CalendarRuleRepository {}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use eventually_projection::{Projector, RawEvent};
+use loom_core::tenant::tag::TagEvent;
+use sea_query::{DynIden, Query, TableRef};
+use sqlx::Row;
+
+use crate::ConnectedTenantPool;
+
+/// How far back from a user's latest entry uses are counted.
+const WINDOW_DAYS: i64 = 90;
+
+/// Projects the combinations of project, activity and description each user
+/// worked on into `projections__timesheet_combinations`.
+///
+/// Entries change in many ways after they are stopped — rounding, edits,
+/// splits, merges, tags — so instead of counting events the user's rows are
+/// recomputed from `projections__timesheets` whenever one of their entries
+/// changes.  Runs after the timesheet and tag projectors.
+pub struct CombinationProjector {
+    pool: ConnectedTenantPool,
+}
+
+impl CombinationProjector {
+    const TABLE: &'static str = "projections__timesheet_combinations";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+
+    async fn user_of_timesheet(&self, timesheet_id: &str) -> Result<Option<String>, crate::Error> {
+        let row = sqlx::query("SELECT user_id FROM projections__timesheets WHERE id = ?")
+            .bind(timesheet_id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        Ok(row.map(|r| r.try_get("user_id")).transpose()?)
+    }
+
+    /// Rebuild the user's rows from their stopped, assigned entries.
+    async fn refresh(&self, user_id: &str) -> Result<(), crate::Error> {
+        let latest: Option<String> = sqlx::query(
+            "SELECT MAX(start_time) AS latest FROM projections__timesheets \
+             WHERE user_id = ? AND end_time IS NOT NULL",
+        )
+        .bind(user_id)
+        .fetch_one(self.pool.as_ref())
+        .await?
+        .try_get("latest")?;
+
+        // (project, activity, description) → (uses, last start, last entry)
+        let mut combinations: BTreeMap<(String, String, String), (i32, String, String)> =
+            BTreeMap::new();
+        if let Some(latest) = latest {
+            let since = chrono::DateTime::parse_from_rfc3339(&latest)?.with_timezone(&chrono::Utc)
+                - chrono::Duration::days(WINDOW_DAYS);
+            let rows = sqlx::query(
+                "SELECT id, project_id, activity_id, description, start_time \
+                 FROM projections__timesheets \
+                 WHERE user_id = ? AND end_time IS NOT NULL AND project_id IS NOT NULL \
+                 AND activity_id IS NOT NULL AND start_time >= ? ORDER BY start_time ASC",
+            )
+            .bind(user_id)
+            .bind(since.to_rfc3339())
+            .fetch_all(self.pool.as_ref())
+            .await?;
+            for row in rows {
+                let description: Option<String> = row.try_get("description")?;
+                let key = (
+                    row.try_get("project_id")?,
+                    row.try_get("activity_id")?,
+                    description.unwrap_or_default(),
+                );
+                let entry = combinations.entry(key).or_default();
+                entry.0 += 1;
+                entry.1 = row.try_get("start_time")?;
+                entry.2 = row.try_get("id")?;
+            }
+        }
+
+        sqlx::query("DELETE FROM projections__timesheet_combinations WHERE user_id = ?")
+            .bind(user_id)
+            .execute(self.pool.as_ref())
+            .await?;
+        for ((project_id, activity_id, description), (uses, last_used_at, last_id)) in combinations
+        {
+            let tag_ids: Vec<String> = sqlx::query(
+                "SELECT tag_id FROM projections__timesheet_tags WHERE timesheet_id = ?",
+            )
+            .bind(&last_id)
+            .fetch_all(self.pool.as_ref())
+            .await?
+            .iter()
+            .map(|r| r.try_get("tag_id"))
+            .collect::<Result<_, _>>()?;
+
+            let query = Query::insert()
+                .into_table(TableRef::from(Self::TABLE))
+                .columns([
+                    DynIden::from("user_id"),
+                    DynIden::from("project_id"),
+                    DynIden::from("activity_id"),
+                    DynIden::from("description"),
+                    DynIden::from("tag_ids"),
+                    DynIden::from("use_count"),
+                    DynIden::from("last_used_at"),
+                    DynIden::from("last_timesheet_id"),
+                ])
+                .values_panic([
+                    user_id.into(),
+                    project_id.into(),
+                    activity_id.into(),
+                    description.into(),
+                    serde_json::to_string(&tag_ids)?.into(),
+                    uses.into(),
+                    last_used_at.into(),
+                    last_id.into(),
+                ])
+                .to_owned();
+
+            let (sql, values) = self.pool.build_query(&query);
+            sqlx::query_with(&sql, values)
+                .execute(self.pool.as_ref())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Projector for CombinationProjector {
+    type Error = crate::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        let user_id = match event.event_type.as_str() {
+            "TimesheetStopped"
+            | "TimesheetAmended"
+            | "TimesheetUpdated"
+            | "TimesheetReassigned"
+            | "TimesheetTimeUpdated" => self.user_of_timesheet(&event.stream_id).await?,
+            "TagTimesheetTagged" | "TagTimesheetUntagged" => {
+                let (TagEvent::TimesheetTagged { timesheet_id }
+                | TagEvent::TimesheetUntagged { timesheet_id }) =
+                    serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+                self.user_of_timesheet(&timesheet_id.to_string()).await?
+            }
+            // The entry is gone already; its user is only known while it is
+            // the latest use of a combination.  Otherwise the counts catch up
+            // with the user's next change.
+            "TimesheetDeleted" => sqlx::query(
+                "SELECT user_id FROM projections__timesheet_combinations \
+                 WHERE last_timesheet_id = ?",
+            )
+            .bind(&event.stream_id)
+            .fetch_optional(self.pool.as_ref())
+            .await?
+            .map(|r| r.try_get("user_id"))
+            .transpose()?,
+            _ => return Ok(()),
+        };
+        if let Some(user_id) = user_id {
+            self.refresh(&user_id).await?;
+        }
+        Ok(())
+    }
+}
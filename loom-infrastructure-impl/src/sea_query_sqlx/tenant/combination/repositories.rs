@@ -0,0 +1,93 @@
+use sqlx::{Row, any::AnyRow};
+
+use crate::ConnectedTenantPool;
+
+/// Reads the combinations of project, activity, description and tags users
+/// recently worked on.
+pub struct CombinationRepository {
+    pool: ConnectedTenantPool,
+}
+
+impl CombinationRepository {
+    const SELECT: &'static str = "SELECT project_id, activity_id, description, tag_ids, \
+         use_count, last_used_at, last_timesheet_id \
+         FROM projections__timesheet_combinations";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+
+    /// The user's `limit` most recently used combinations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn recent(
+        &self,
+        user_id: &str,
+        limit: i64,
+    ) -> Result<Vec<CombinationRow>, crate::Error> {
+        self.top(user_id, "last_used_at DESC", limit).await
+    }
+
+    /// The user's `limit` most frequently used combinations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn frequent(
+        &self,
+        user_id: &str,
+        limit: i64,
+    ) -> Result<Vec<CombinationRow>, crate::Error> {
+        self.top(user_id, "use_count DESC, last_used_at DESC", limit)
+            .await
+    }
+
+    async fn top(
+        &self,
+        user_id: &str,
+        order: &str,
+        limit: i64,
+    ) -> Result<Vec<CombinationRow>, crate::Error> {
+        let sql = format!(
+            "{} WHERE user_id = ? ORDER BY {order} LIMIT ?",
+            Self::SELECT
+        );
+        let rows = sqlx::query(&sql)
+            .bind(user_id)
+            .bind(limit)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    fn map_row(row: &AnyRow) -> Result<CombinationRow, crate::Error> {
+        let description: String = row.try_get("description")?;
+        let tag_ids: String = row.try_get("tag_ids")?;
+        Ok(CombinationRow {
+            project_id: row.try_get("project_id")?,
+            activity_id: row.try_get("activity_id")?,
+            description: (!description.is_empty()).then_some(description),
+            tag_ids: serde_json::from_str(&tag_ids).unwrap_or_default(),
+            use_count: row.try_get("use_count")?,
+            last_used_at: row.try_get("last_used_at")?,
+            last_timesheet_id: row.try_get("last_timesheet_id")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CombinationRow {
+    pub project_id: String,
+    pub activity_id: String,
+    pub description: Option<String>,
+    pub tag_ids: Vec<String>,
+    /// Entries with this combination in the 90 days up to the user's latest.
+    pub use_count: i32,
+    /// Start of the latest entry, RFC-3339.
+    pub last_used_at: String,
+    /// The latest entry; restarting it repeats the combination.
+    pub last_timesheet_id: String,
+}
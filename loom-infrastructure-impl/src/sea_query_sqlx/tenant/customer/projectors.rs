@@ -17,6 +17,33 @@ impl CustomerProjector {
     pub const fn new(pool: ConnectedTenantPool) -> Self {
         Self { pool }
     }
+
+    async fn set_archived(&self, id: &str, archived: bool) -> Result<(), crate::Error> {
+        let query = Query::update()
+            .table(TableRef::from(Self::TABLE))
+            .values([(DynIden::from("archived"), archived.into())])
+            .cond_where(Condition::all().add(Expr::col("id").eq(Expr::val(id))))
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), crate::Error> {
+        let query = Query::delete()
+            .from_table(TableRef::from(Self::TABLE))
+            .cond_where(Condition::all().add(Expr::col("id").eq(Expr::val(id))))
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -139,6 +166,45 @@ impl Projector for CustomerProjector {
                     .execute(self.pool.as_ref())
                     .await?;
             }
+            "CustomerRoundingUpdated" => {
+                let CustomerEvent::RoundingUpdated { rounding } =
+                    serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::update()
+                    .table(TableRef::from(Self::TABLE))
+                    .values([(
+                        DynIden::from("rounding"),
+                        rounding
+                            .map(|r| serde_json::to_string(&r))
+                            .transpose()?
+                            .into(),
+                    )])
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = match self.pool.get_database_type() {
+                    DatabaseType::Sqlite => {
+                        use sea_query::SqliteQueryBuilder;
+                        query.build_sqlx(SqliteQueryBuilder)
+                    }
+                    DatabaseType::Postgres => {
+                        use sea_query::PostgresQueryBuilder;
+                        query.build_sqlx(PostgresQueryBuilder)
+                    }
+                };
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "CustomerArchived" => self.set_archived(&event.stream_id, true).await?,
+            "CustomerRestored" => self.set_archived(&event.stream_id, false).await?,
+            "CustomerDeleted" => self.delete(&event.stream_id).await?,
             _ => {}
         }
 
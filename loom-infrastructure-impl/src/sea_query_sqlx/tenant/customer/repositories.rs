@@ -7,9 +7,10 @@ use eventually_any::snapshot::Repository;
 use loom_core::tenant::customer::{
     Customer, CustomerEvent, CustomerId, CustomerRepository as CustomerRepositoryTrait,
 };
+use loom_core::tenant::timesheet::Rounding;
 use sqlx::{Row, any::AnyRow};
 
-use crate::ConnectedTenantPool;
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
 
 pub struct CustomerRepository {
     pool: ConnectedTenantPool,
@@ -39,14 +40,59 @@ impl CustomerRepository {
     pub async fn all(&self) -> Result<Vec<CustomerRow>, crate::Error> {
         let rows = sqlx::query(
             "SELECT id, name, comment, currency, timezone, country, visible, \
-             time_budget, money_budget, budget_is_monthly \
-             FROM projections__customers ORDER BY name",
+             time_budget, money_budget, budget_is_monthly, archived \
+             FROM projections__customers WHERE archived = 0 ORDER BY name",
         )
         .fetch_all(self.pool.as_ref())
         .await?;
         rows.into_iter().map(|r| Self::map_row(&r)).collect()
     }
 
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find(&self, id: &str) -> Result<Option<CustomerRow>, crate::Error> {
+        let row = sqlx::query(
+            "SELECT id, name, comment, currency, timezone, country, visible, \
+             time_budget, money_budget, budget_is_monthly, archived \
+             FROM projections__customers WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        row.map(|r| Self::map_row(&r)).transpose()
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn archived(&self) -> Result<Vec<CustomerRow>, crate::Error> {
+        let rows = sqlx::query(
+            "SELECT id, name, comment, currency, timezone, country, visible, \
+             time_budget, money_budget, budget_is_monthly, archived \
+             FROM projections__customers WHERE archived <> 0 ORDER BY name",
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// The customer's own rounding rules, `None` when it uses the
+    /// workspace rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn rounding(&self, id: &str) -> Result<Option<Rounding>, crate::Error> {
+        let row = sqlx::query("SELECT rounding FROM projections__customers WHERE id = ?")
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        Ok(row
+            .and_then(|r| r.try_get::<Option<String>, _>("rounding").ok().flatten())
+            .and_then(|json| serde_json::from_str(&json).ok()))
+    }
+
     fn map_row(row: &AnyRow) -> Result<CustomerRow, crate::Error> {
         Ok(CustomerRow {
             id: row.try_get("id")?,
@@ -59,6 +105,7 @@ impl CustomerRepository {
             time_budget: row.try_get("time_budget")?,
             money_budget: row.try_get("money_budget")?,
             budget_is_monthly: bool_col(row, "budget_is_monthly"),
+            archived: bool_col(row, "archived"),
         })
     }
 }
@@ -81,6 +128,7 @@ pub struct CustomerRow {
     pub time_budget: Option<i32>,
     pub money_budget: Option<i64>,
     pub budget_is_monthly: bool,
+    pub archived: bool,
 }
 
 #[async_trait]
@@ -89,7 +137,9 @@ impl Getter<Customer> for CustomerRepository {
         &self,
         id: &CustomerId,
     ) -> Result<eventually::aggregate::Root<Customer>, GetError> {
-        self.repository.get(id).await
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
     }
 }
 
@@ -99,7 +149,9 @@ impl Saver<Customer> for CustomerRepository {
         &self,
         root: &mut eventually::aggregate::Root<Customer>,
     ) -> Result<(), SaveError> {
-        self.repository.save(root).await
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
     }
 }
 
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use eventually_projection::{Projector, RawEvent};
+use loom_core::tenant::favorite::FavoriteEvent;
+use sea_query::{Condition, DynIden, Expr, ExprTrait, OnConflict, Query, TableRef};
+
+use crate::ConnectedTenantPool;
+
+pub struct FavoriteProjector {
+    pool: ConnectedTenantPool,
+}
+
+impl FavoriteProjector {
+    const TABLE: &'static str = "projections__favorites";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl Projector for FavoriteProjector {
+    type Error = crate::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        match event.event_type.as_str() {
+            "FavoritePinned" => {
+                let FavoriteEvent::Pinned {
+                    id,
+                    user_id,
+                    project_id,
+                    activity_id,
+                    description,
+                    tag_ids,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let tag_ids: Vec<String> = tag_ids.iter().map(ToString::to_string).collect();
+                let query = Query::insert()
+                    .into_table(TableRef::from(Self::TABLE))
+                    .columns([
+                        DynIden::from("id"),
+                        DynIden::from("user_id"),
+                        DynIden::from("project_id"),
+                        DynIden::from("activity_id"),
+                        DynIden::from("description"),
+                        DynIden::from("tag_ids"),
+                        DynIden::from("global_position"),
+                    ])
+                    .values_panic([
+                        id.to_string().into(),
+                        user_id.to_string().into(),
+                        project_id.to_string().into(),
+                        activity_id.to_string().into(),
+                        description.into(),
+                        serde_json::to_string(&tag_ids)?.into(),
+                        event.global_position.into(),
+                    ])
+                    .on_conflict(OnConflict::new().do_nothing().to_owned())
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "FavoriteUnpinned" => {
+                let query = Query::delete()
+                    .from_table(TableRef::from(Self::TABLE))
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
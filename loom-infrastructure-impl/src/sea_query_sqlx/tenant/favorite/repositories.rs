@@ -0,0 +1,103 @@
+use std::ops::Deref;
+
+use async_trait::async_trait;
+use eventually::aggregate::repository::{GetError, Getter, SaveError, Saver};
+use eventually::serde::Json;
+use eventually_any::snapshot::Repository;
+use loom_core::tenant::favorite::{
+    Favorite, FavoriteEvent, FavoriteId, FavoriteRepository as FavoriteRepositoryTrait,
+};
+use sqlx::{Row, any::AnyRow};
+
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
+
+pub struct FavoriteRepository {
+    pool: ConnectedTenantPool,
+    repository: Repository<Favorite, Json<Favorite>, Json<FavoriteEvent>>,
+}
+
+impl Deref for FavoriteRepository {
+    type Target = Repository<Favorite, Json<Favorite>, Json<FavoriteEvent>>;
+    fn deref(&self) -> &Self::Target {
+        &self.repository
+    }
+}
+
+impl FavoriteRepository {
+    const SELECT: &'static str = "SELECT id, user_id, project_id, activity_id, description, tag_ids \
+         FROM projections__favorites";
+
+    /// # Errors
+    ///
+    /// Returns an error if the event store repository cannot be initialized.
+    pub async fn from_pool(pool: ConnectedTenantPool) -> Result<Self, sqlx::migrate::MigrateError> {
+        let repository =
+            Repository::new(pool.as_ref().clone(), Json::default(), Json::default()).await?;
+        Ok(Self { pool, repository })
+    }
+
+    /// A user's favorites in the order they were pinned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn for_user(&self, user_id: &str) -> Result<Vec<FavoriteRow>, crate::Error> {
+        let sql = format!(
+            "{} WHERE user_id = ? ORDER BY global_position ASC",
+            Self::SELECT
+        );
+        let rows = sqlx::query(&sql)
+            .bind(user_id)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    fn map_row(row: &AnyRow) -> Result<FavoriteRow, crate::Error> {
+        let tag_ids: String = row.try_get("tag_ids")?;
+        Ok(FavoriteRow {
+            id: row.try_get("id")?,
+            user_id: row.try_get("user_id")?,
+            project_id: row.try_get("project_id")?,
+            activity_id: row.try_get("activity_id")?,
+            description: row.try_get("description")?,
+            tag_ids: serde_json::from_str(&tag_ids).unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FavoriteRow {
+    pub id: String,
+    pub user_id: String,
+    pub project_id: String,
+    pub activity_id: String,
+    pub description: Option<String>,
+    pub tag_ids: Vec<String>,
+}
+
+#[async_trait]
+impl Getter<Favorite> for FavoriteRepository {
+    async fn get(
+        &self,
+        id: &FavoriteId,
+    ) -> Result<eventually::aggregate::Root<Favorite>, GetError> {
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
+    }
+}
+
+#[async_trait]
+impl Saver<Favorite> for FavoriteRepository {
+    async fn save(
+        &self,
+        root: &mut eventually::aggregate::Root<Favorite>,
+    ) -> Result<(), SaveError> {
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
+    }
+}
+
+impl FavoriteRepositoryTrait for FavoriteRepository {}
@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use eventually_projection::{Projector, RawEvent};
+use loom_core::tenant::holiday::HolidayEvent;
+use sea_query::{Condition, DynIden, Expr, ExprTrait, OnConflict, Query, TableRef};
+
+use crate::ConnectedTenantPool;
+
+pub struct HolidayProjector {
+    pool: ConnectedTenantPool,
+}
+
+impl HolidayProjector {
+    const TABLE: &'static str = "projections__holidays";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl Projector for HolidayProjector {
+    type Error = crate::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        match event.event_type.as_str() {
+            "HolidayDeclared" => {
+                let HolidayEvent::Declared {
+                    id,
+                    region,
+                    date,
+                    name,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::insert()
+                    .into_table(TableRef::from(Self::TABLE))
+                    .columns([
+                        DynIden::from("id"),
+                        DynIden::from("region"),
+                        DynIden::from("date"),
+                        DynIden::from("name"),
+                    ])
+                    .values_panic([
+                        id.to_string().into(),
+                        region.into(),
+                        date.into(),
+                        name.into(),
+                    ])
+                    .on_conflict(OnConflict::new().do_nothing().to_owned())
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "HolidayRemoved" => {
+                let query = Query::delete()
+                    .from_table(TableRef::from(Self::TABLE))
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
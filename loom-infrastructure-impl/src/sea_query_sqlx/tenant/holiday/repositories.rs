@@ -0,0 +1,88 @@
+use std::ops::Deref;
+
+use async_trait::async_trait;
+use eventually::aggregate::repository::{GetError, Getter, SaveError, Saver};
+use eventually::serde::Json;
+use eventually_any::snapshot::Repository;
+use loom_core::tenant::holiday::{
+    Holiday, HolidayEvent, HolidayId, HolidayRepository as HolidayRepositoryTrait,
+};
+use sqlx::{Row, any::AnyRow};
+
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
+
+pub struct HolidayRepository {
+    pool: ConnectedTenantPool,
+    repository: Repository<Holiday, Json<Holiday>, Json<HolidayEvent>>,
+}
+
+impl Deref for HolidayRepository {
+    type Target = Repository<Holiday, Json<Holiday>, Json<HolidayEvent>>;
+    fn deref(&self) -> &Self::Target {
+        &self.repository
+    }
+}
+
+impl HolidayRepository {
+    /// # Errors
+    ///
+    /// Returns an error if the event store repository cannot be initialized.
+    pub async fn from_pool(pool: ConnectedTenantPool) -> Result<Self, sqlx::migrate::MigrateError> {
+        let repository =
+            Repository::new(pool.as_ref().clone(), Json::default(), Json::default()).await?;
+        Ok(Self { pool, repository })
+    }
+
+    /// Every holiday, ordered by date and region.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn all(&self) -> Result<Vec<HolidayRow>, crate::Error> {
+        let rows = sqlx::query(
+            "SELECT id, region, date, name FROM projections__holidays \
+             ORDER BY date ASC, region ASC",
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    fn map_row(row: &AnyRow) -> Result<HolidayRow, crate::Error> {
+        Ok(HolidayRow {
+            id: row.try_get("id")?,
+            region: row.try_get("region")?,
+            date: row.try_get("date")?,
+            name: row.try_get("name")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HolidayRow {
+    pub id: String,
+    pub region: String,
+    /// `YYYY-MM-DD`.
+    pub date: String,
+    pub name: String,
+}
+
+#[async_trait]
+impl Getter<Holiday> for HolidayRepository {
+    async fn get(&self, id: &HolidayId) -> Result<eventually::aggregate::Root<Holiday>, GetError> {
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
+    }
+}
+
+#[async_trait]
+impl Saver<Holiday> for HolidayRepository {
+    async fn save(&self, root: &mut eventually::aggregate::Root<Holiday>) -> Result<(), SaveError> {
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
+    }
+}
+
+impl HolidayRepositoryTrait for HolidayRepository {}
@@ -1,8 +1,18 @@
 pub mod activity;
 pub mod activity_rate;
+pub mod approval;
+pub mod budget;
+pub mod calendar_rule;
+pub mod combination;
 pub mod customer;
+pub mod favorite;
+pub mod holiday;
 pub mod project;
 pub mod project_rate;
 pub mod projectors;
+pub mod report_schedule;
+pub mod search;
 pub mod tag;
 pub mod timesheet;
+pub mod webhook;
+pub mod work_contract;
@@ -17,6 +17,33 @@ impl ProjectProjector {
     pub const fn new(pool: ConnectedTenantPool) -> Self {
         Self { pool }
     }
+
+    async fn set_archived(&self, id: &str, archived: bool) -> Result<(), crate::Error> {
+        let query = Query::update()
+            .table(TableRef::from(Self::TABLE))
+            .values([(DynIden::from("archived"), archived.into())])
+            .cond_where(Condition::all().add(Expr::col("id").eq(Expr::val(id))))
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), crate::Error> {
+        let query = Query::delete()
+            .from_table(TableRef::from(Self::TABLE))
+            .cond_where(Condition::all().add(Expr::col("id").eq(Expr::val(id))))
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -121,6 +148,39 @@ impl Projector for ProjectProjector {
                     .execute(self.pool.as_ref())
                     .await?;
             }
+            "ProjectRoundingUpdated" => {
+                let ProjectEvent::RoundingUpdated { rounding } =
+                    serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::update()
+                    .table(TableRef::from(Self::TABLE))
+                    .values([(
+                        DynIden::from("rounding"),
+                        rounding
+                            .map(|r| serde_json::to_string(&r))
+                            .transpose()?
+                            .into(),
+                    )])
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = match self.pool.get_database_type() {
+                    DatabaseType::Sqlite => query.build_sqlx(sea_query::SqliteQueryBuilder),
+                    DatabaseType::Postgres => query.build_sqlx(sea_query::PostgresQueryBuilder),
+                };
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "ProjectArchived" => self.set_archived(&event.stream_id, true).await?,
+            "ProjectRestored" => self.set_archived(&event.stream_id, false).await?,
+            "ProjectDeleted" => self.delete(&event.stream_id).await?,
             _ => {}
         }
 
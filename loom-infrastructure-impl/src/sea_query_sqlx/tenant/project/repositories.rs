@@ -7,9 +7,10 @@ use eventually_any::snapshot::Repository;
 use loom_core::tenant::project::{
     Project, ProjectEvent, ProjectId, ProjectRepository as ProjectRepositoryTrait,
 };
+use loom_core::tenant::timesheet::Rounding;
 use sqlx::{Row, any::AnyRow};
 
-use crate::ConnectedTenantPool;
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
 
 pub struct ProjectRepository {
     pool: ConnectedTenantPool,
@@ -39,21 +40,37 @@ impl ProjectRepository {
     pub async fn all(&self) -> Result<Vec<ProjectRow>, crate::Error> {
         let rows = sqlx::query(
             "SELECT id, customer_id, name, comment, order_number, visible, billable, \
-             time_budget, money_budget, budget_is_monthly \
-             FROM projections__projects ORDER BY name",
+             time_budget, money_budget, budget_is_monthly, archived \
+             FROM projections__projects WHERE archived = 0 ORDER BY name",
         )
         .fetch_all(self.pool.as_ref())
         .await?;
         rows.into_iter().map(|r| Self::map_row(&r)).collect()
     }
 
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn archived(&self) -> Result<Vec<ProjectRow>, crate::Error> {
+        let rows = sqlx::query(
+            "SELECT id, customer_id, name, comment, order_number, visible, billable, \
+             time_budget, money_budget, budget_is_monthly, archived \
+             FROM projections__projects WHERE archived <> 0 ORDER BY name",
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// Every project of the customer, archived ones included.
+    ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
     pub async fn by_customer(&self, customer_id: &str) -> Result<Vec<ProjectRow>, crate::Error> {
         let rows = sqlx::query(
             "SELECT id, customer_id, name, comment, order_number, visible, billable, \
-             time_budget, money_budget, budget_is_monthly \
+             time_budget, money_budget, budget_is_monthly, archived \
              FROM projections__projects WHERE customer_id = ? ORDER BY name",
         )
         .bind(customer_id)
@@ -62,6 +79,37 @@ impl ProjectRepository {
         rows.into_iter().map(|r| Self::map_row(&r)).collect()
     }
 
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find(&self, id: &str) -> Result<Option<ProjectRow>, crate::Error> {
+        let row = sqlx::query(
+            "SELECT id, customer_id, name, comment, order_number, visible, billable, \
+             time_budget, money_budget, budget_is_monthly, archived \
+             FROM projections__projects WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        row.map(|r| Self::map_row(&r)).transpose()
+    }
+
+    /// The project's own rounding rules, `None` when it uses the
+    /// customer's rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn rounding(&self, id: &str) -> Result<Option<Rounding>, crate::Error> {
+        let row = sqlx::query("SELECT rounding FROM projections__projects WHERE id = ?")
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        Ok(row
+            .and_then(|r| r.try_get::<Option<String>, _>("rounding").ok().flatten())
+            .and_then(|json| serde_json::from_str(&json).ok()))
+    }
+
     fn map_row(row: &AnyRow) -> Result<ProjectRow, crate::Error> {
         Ok(ProjectRow {
             id: row.try_get("id")?,
@@ -74,6 +122,7 @@ impl ProjectRepository {
             time_budget: row.try_get("time_budget")?,
             money_budget: row.try_get("money_budget")?,
             budget_is_monthly: bool_col(row, "budget_is_monthly"),
+            archived: bool_col(row, "archived"),
         })
     }
 }
@@ -95,19 +144,24 @@ pub struct ProjectRow {
     pub time_budget: Option<i32>,
     pub money_budget: Option<i64>,
     pub budget_is_monthly: bool,
+    pub archived: bool,
 }
 
 #[async_trait]
 impl Getter<Project> for ProjectRepository {
     async fn get(&self, id: &ProjectId) -> Result<eventually::aggregate::Root<Project>, GetError> {
-        self.repository.get(id).await
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
     }
 }
 
 #[async_trait]
 impl Saver<Project> for ProjectRepository {
     async fn save(&self, root: &mut eventually::aggregate::Root<Project>) -> Result<(), SaveError> {
-        self.repository.save(root).await
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
     }
 }
 
@@ -10,7 +10,7 @@ use loom_core::tenant::project_rate::{
 };
 use sqlx::{Row, any::AnyRow};
 
-use crate::ConnectedTenantPool;
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
 
 pub struct ProjectRateRepository {
     pool: ConnectedTenantPool,
@@ -95,7 +95,9 @@ impl Getter<ProjectRate> for ProjectRateRepository {
         &self,
         id: &ProjectRateId,
     ) -> Result<eventually::aggregate::Root<ProjectRate>, GetError> {
-        self.repository.get(id).await
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
     }
 }
 
@@ -105,7 +107,9 @@ impl Saver<ProjectRate> for ProjectRateRepository {
         &self,
         root: &mut eventually::aggregate::Root<ProjectRate>,
     ) -> Result<(), SaveError> {
-        self.repository.save(root).await
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
     }
 }
 
@@ -2,12 +2,22 @@ use async_trait::async_trait;
 use eventually_projection::{Projector, RawEvent};
 
 use crate::{
-    ConnectedTenantPool,
-    sea_query_sqlx::tenant::{
-        activity::projectors::ActivityProjector, activity_rate::projectors::ActivityRateProjector,
-        customer::projectors::CustomerProjector, project::projectors::ProjectProjector,
-        project_rate::projectors::ProjectRateProjector, tag::projectors::TagProjector,
-        timesheet::projectors::TimesheetProjector,
+    ConnectedTenantPool, ScopeTenant,
+    sea_query_sqlx::{
+        audit_log::projectors::AuditLogProjector,
+        tenant::{
+            activity::projectors::ActivityProjector,
+            activity_rate::projectors::ActivityRateProjector,
+            approval::projectors::ApprovalProjector, budget::projectors::BudgetAlertProjector,
+            calendar_rule::projectors::CalendarRuleProjector,
+            combination::projectors::CombinationProjector, customer::projectors::CustomerProjector,
+            favorite::projectors::FavoriteProjector, holiday::projectors::HolidayProjector,
+            project::projectors::ProjectProjector, project_rate::projectors::ProjectRateProjector,
+            report_schedule::projectors::ReportScheduleProjector,
+            search::projectors::SearchProjector, tag::projectors::TagProjector,
+            timesheet::projectors::TimesheetProjector, webhook::projectors::WebhookProjector,
+            work_contract::projectors::WorkContractProjector,
+        },
     },
 };
 
@@ -25,8 +35,19 @@ pub struct TenantProjector {
     activity: ActivityProjector,
     timesheet: TimesheetProjector,
     tag: TagProjector,
+    approval: ApprovalProjector,
     project_rate: ProjectRateProjector,
     activity_rate: ActivityRateProjector,
+    budget_alert: BudgetAlertProjector,
+    favorite: FavoriteProjector,
+    calendar_rule: CalendarRuleProjector,
+    combination: CombinationProjector,
+    work_contract: WorkContractProjector,
+    holiday: HolidayProjector,
+    webhook: WebhookProjector,
+    report_schedule: ReportScheduleProjector,
+    search: SearchProjector,
+    audit_log: AuditLogProjector<ScopeTenant>,
 }
 
 impl TenantProjector {
@@ -38,8 +59,19 @@ impl TenantProjector {
             activity: ActivityProjector::new(pool.clone()),
             timesheet: TimesheetProjector::new(pool.clone()),
             tag: TagProjector::new(pool.clone()),
+            approval: ApprovalProjector::new(pool.clone()),
             project_rate: ProjectRateProjector::new(pool.clone()),
-            activity_rate: ActivityRateProjector::new(pool),
+            activity_rate: ActivityRateProjector::new(pool.clone()),
+            budget_alert: BudgetAlertProjector::new(pool.clone()),
+            favorite: FavoriteProjector::new(pool.clone()),
+            calendar_rule: CalendarRuleProjector::new(pool.clone()),
+            combination: CombinationProjector::new(pool.clone()),
+            work_contract: WorkContractProjector::new(pool.clone()),
+            holiday: HolidayProjector::new(pool.clone()),
+            webhook: WebhookProjector::new(pool.clone()),
+            report_schedule: ReportScheduleProjector::new(pool.clone()),
+            search: SearchProjector::new(pool.clone()),
+            audit_log: AuditLogProjector::new(pool),
         }
     }
 }
@@ -54,8 +86,19 @@ impl Projector for TenantProjector {
         self.activity.handle(event.clone()).await?;
         self.timesheet.handle(event.clone()).await?;
         self.tag.handle(event.clone()).await?;
+        self.approval.handle(event.clone()).await?;
         self.project_rate.handle(event.clone()).await?;
-        self.activity_rate.handle(event).await?;
+        self.activity_rate.handle(event.clone()).await?;
+        self.budget_alert.handle(event.clone()).await?;
+        self.favorite.handle(event.clone()).await?;
+        self.calendar_rule.handle(event.clone()).await?;
+        self.combination.handle(event.clone()).await?;
+        self.work_contract.handle(event.clone()).await?;
+        self.holiday.handle(event.clone()).await?;
+        self.webhook.handle(event.clone()).await?;
+        self.report_schedule.handle(event.clone()).await?;
+        self.search.handle(event.clone()).await?;
+        self.audit_log.handle(event).await?;
         Ok(())
     }
 }
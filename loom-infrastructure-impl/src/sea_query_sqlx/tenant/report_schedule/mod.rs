@@ -0,0 +1,2 @@
+pub mod projectors;
+pub mod repositories;
@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use eventually_projection::{Projector, RawEvent};
+use loom_core::tenant::report_schedule::ReportScheduleEvent;
+use sea_query::{Condition, DynIden, Expr, ExprTrait, OnConflict, Query, TableRef};
+
+use crate::ConnectedTenantPool;
+
+pub struct ReportScheduleProjector {
+    pool: ConnectedTenantPool,
+}
+
+impl ReportScheduleProjector {
+    const TABLE: &'static str = "projections__report_schedules";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl Projector for ReportScheduleProjector {
+    type Error = crate::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        match event.event_type.as_str() {
+            "ReportScheduleCreated" => {
+                let ReportScheduleEvent::Created {
+                    id,
+                    owner_id,
+                    name,
+                    scope,
+                    schedule,
+                    parameters,
+                    format,
+                    recipients,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::insert()
+                    .into_table(TableRef::from(Self::TABLE))
+                    .columns([
+                        DynIden::from("id"),
+                        DynIden::from("owner_id"),
+                        DynIden::from("name"),
+                        DynIden::from("scope"),
+                        DynIden::from("schedule"),
+                        DynIden::from("parameters"),
+                        DynIden::from("format"),
+                        DynIden::from("recipients"),
+                        DynIden::from("global_position"),
+                    ])
+                    .values_panic([
+                        id.to_string().into(),
+                        owner_id.to_string().into(),
+                        name.into(),
+                        scope.as_str().into(),
+                        schedule.into(),
+                        serde_json::to_string(&parameters)?.into(),
+                        format.as_str().into(),
+                        serde_json::to_string(&recipients)?.into(),
+                        event.global_position.into(),
+                    ])
+                    .on_conflict(OnConflict::new().do_nothing().to_owned())
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "ReportScheduleChanged" => {
+                let ReportScheduleEvent::Changed {
+                    name,
+                    schedule,
+                    parameters,
+                    format,
+                    recipients,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+                let query = Query::update()
+                    .table(TableRef::from(Self::TABLE))
+                    .values([
+                        (DynIden::from("name"), name.into()),
+                        (DynIden::from("schedule"), schedule.into()),
+                        (
+                            DynIden::from("parameters"),
+                            serde_json::to_string(&parameters)?.into(),
+                        ),
+                        (DynIden::from("format"), format.as_str().into()),
+                        (
+                            DynIden::from("recipients"),
+                            serde_json::to_string(&recipients)?.into(),
+                        ),
+                    ])
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "ReportScheduleRemoved" => {
+                let query = Query::delete()
+                    .from_table(TableRef::from(Self::TABLE))
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+use std::ops::Deref;
+
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use eventually::aggregate::repository::{GetError, Getter, SaveError, Saver};
+use eventually::serde::Json;
+use eventually_any::snapshot::Repository;
+use loom_core::tenant::report_schedule::{
+    ReportFormat, ReportParameters, ReportSchedule, ReportScheduleEvent, ReportScheduleId,
+    ReportScheduleRepository as ReportScheduleRepositoryTrait, ReportScope,
+};
+use sea_query::{DynIden, OnConflict, Query, TableRef};
+use sqlx::{Row, any::AnyRow};
+
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
+
+pub struct ReportScheduleRepository {
+    pool: ConnectedTenantPool,
+    repository: Repository<ReportSchedule, Json<ReportSchedule>, Json<ReportScheduleEvent>>,
+}
+
+impl Deref for ReportScheduleRepository {
+    type Target = Repository<ReportSchedule, Json<ReportSchedule>, Json<ReportScheduleEvent>>;
+    fn deref(&self) -> &Self::Target {
+        &self.repository
+    }
+}
+
+impl ReportScheduleRepository {
+    const SELECT: &'static str = "SELECT id, owner_id, name, scope, schedule, parameters, format, \
+         recipients FROM projections__report_schedules";
+
+    /// # Errors
+    ///
+    /// Returns an error if the event store repository cannot be initialized.
+    pub async fn from_pool(pool: ConnectedTenantPool) -> Result<Self, sqlx::migrate::MigrateError> {
+        let repository =
+            Repository::new(pool.as_ref().clone(), Json::default(), Json::default()).await?;
+        Ok(Self { pool, repository })
+    }
+
+    /// Every scheduled report of the workspace, ordered by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn all(&self) -> Result<Vec<ReportScheduleRow>, crate::Error> {
+        let sql = format!("{} ORDER BY name ASC, id ASC", Self::SELECT);
+        let rows = sqlx::query(&sql).fetch_all(self.pool.as_ref()).await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// The scheduled reports a user owns, ordered by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn for_owner(&self, owner_id: &str) -> Result<Vec<ReportScheduleRow>, crate::Error> {
+        let sql = format!(
+            "{} WHERE owner_id = ? ORDER BY name ASC, id ASC",
+            Self::SELECT
+        );
+        let rows = sqlx::query(&sql)
+            .bind(owner_id)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find(&self, id: &str) -> Result<Option<ReportScheduleRow>, crate::Error> {
+        let sql = format!("{} WHERE id = ?", Self::SELECT);
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.map(|r| Self::map_row(&r)).transpose()
+    }
+
+    fn map_row(row: &AnyRow) -> Result<ReportScheduleRow, crate::Error> {
+        let scope: String = row.try_get("scope")?;
+        let parameters: String = row.try_get("parameters")?;
+        let format: String = row.try_get("format")?;
+        let recipients: String = row.try_get("recipients")?;
+        Ok(ReportScheduleRow {
+            id: row.try_get("id")?,
+            owner_id: row.try_get("owner_id")?,
+            name: row.try_get("name")?,
+            scope: ReportScope::parse(&scope).unwrap_or(ReportScope::Personal),
+            schedule: row.try_get("schedule")?,
+            parameters: serde_json::from_str(&parameters)?,
+            format: ReportFormat::parse(&format).unwrap_or(ReportFormat::Html),
+            recipients: serde_json::from_str(&recipients).unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportScheduleRow {
+    pub id: String,
+    pub owner_id: String,
+    pub name: String,
+    pub scope: ReportScope,
+    /// Cron expression, see [`loom_core::tenant::report_schedule::Schedule`].
+    pub schedule: String,
+    pub parameters: ReportParameters,
+    pub format: ReportFormat,
+    pub recipients: Vec<String>,
+}
+
+#[async_trait]
+impl Getter<ReportSchedule> for ReportScheduleRepository {
+    async fn get(
+        &self,
+        id: &ReportScheduleId,
+    ) -> Result<eventually::aggregate::Root<ReportSchedule>, GetError> {
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
+    }
+}
+
+#[async_trait]
+impl Saver<ReportSchedule> for ReportScheduleRepository {
+    async fn save(
+        &self,
+        root: &mut eventually::aggregate::Root<ReportSchedule>,
+    ) -> Result<(), SaveError> {
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
+    }
+}
+
+impl ReportScheduleRepositoryTrait for ReportScheduleRepository {}
+
+/// When each scheduled report was last due, and whether it went out.
+pub struct ReportRunRepository {
+    pool: ConnectedTenantPool,
+}
+
+impl ReportRunRepository {
+    const TABLE: &'static str = "report_schedule_runs";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+
+    /// Every run recorded, one per report.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn all(&self) -> Result<Vec<ReportRunRow>, crate::Error> {
+        let rows = sqlx::query(
+            "SELECT schedule_id, last_due_at, last_sent_at, last_error FROM report_schedule_runs",
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(Self::map_row).collect()
+    }
+
+    /// Start counting a report's schedule from `due_at`, unless it is
+    /// counted already.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn begin(&self, schedule_id: &str, due_at: &str) -> Result<(), crate::Error> {
+        let query = Query::insert()
+            .into_table(TableRef::from(Self::TABLE))
+            .columns([DynIden::from("schedule_id"), DynIden::from("last_due_at")])
+            .values_panic([schedule_id.into(), due_at.into()])
+            .on_conflict(
+                OnConflict::column(DynIden::from("schedule_id"))
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// Record that the report due at `due_at` was sent, or why it was not.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn record(
+        &self,
+        schedule_id: &str,
+        due_at: &str,
+        error: Option<&str>,
+    ) -> Result<(), crate::Error> {
+        self.begin(schedule_id, due_at).await?;
+        let query = if error.is_some() {
+            "UPDATE report_schedule_runs SET last_due_at = ?, last_error = ? WHERE schedule_id = ?"
+        } else {
+            "UPDATE report_schedule_runs SET last_due_at = ?, last_error = ?, last_sent_at = ? \
+             WHERE schedule_id = ?"
+        };
+        let mut query = sqlx::query(query).bind(due_at).bind(error);
+        if error.is_none() {
+            query = query.bind(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true));
+        }
+        query.bind(schedule_id).execute(self.pool.as_ref()).await?;
+        Ok(())
+    }
+
+    fn map_row(row: &AnyRow) -> Result<ReportRunRow, crate::Error> {
+        Ok(ReportRunRow {
+            schedule_id: row.try_get("schedule_id")?,
+            last_due_at: row.try_get("last_due_at")?,
+            last_sent_at: row.try_get("last_sent_at")?,
+            last_error: row.try_get("last_error")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportRunRow {
+    pub schedule_id: String,
+    /// RFC-3339; the schedule is next due after this time.
+    pub last_due_at: String,
+    pub last_sent_at: Option<String>,
+    /// Why the last report could not be sent, if it could not.
+    pub last_error: Option<String>,
+}
@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use eventually_projection::{Projector, RawEvent};
+use loom_core::audit;
+use sea_query::{Condition, DynIden, Expr, ExprTrait, Query, TableRef};
+use sqlx::Row;
+
+use crate::ConnectedTenantPool;
+
+/// Keeps `projections__search` in step with the entities it covers.
+///
+/// Instead of decoding every event, the projector re-reads the projection
+/// row of the entity an event belongs to and indexes it anew, so it must
+/// run after the projectors maintaining those rows.  Entities that no
+/// longer have a row, archived ones and time entries without a description
+/// are removed from the index.
+pub struct SearchProjector {
+    pool: ConnectedTenantPool,
+}
+
+/// What the index holds about one entity.
+struct Document {
+    user_id: Option<String>,
+    start_time: Option<String>,
+    title: String,
+    body: String,
+}
+
+impl SearchProjector {
+    const TABLE: &'static str = "projections__search";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+
+    /// Index the entity `id` of `entity_type` as its projection row is now.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn index(&self, entity_type: &str, id: &str) -> Result<(), crate::Error> {
+        let document = self.document(entity_type, id).await?;
+
+        let query = Query::delete()
+            .from_table(TableRef::from(Self::TABLE))
+            .cond_where(Condition::all().add(Expr::col("entity_id").eq(Expr::val(id))))
+            .to_owned();
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        let Some(document) = document else {
+            return Ok(());
+        };
+        let query = Query::insert()
+            .into_table(TableRef::from(Self::TABLE))
+            .columns([
+                DynIden::from("entity_type"),
+                DynIden::from("entity_id"),
+                DynIden::from("user_id"),
+                DynIden::from("start_time"),
+                DynIden::from("title"),
+                DynIden::from("body"),
+            ])
+            .values_panic([
+                entity_type.into(),
+                id.into(),
+                document.user_id.into(),
+                document.start_time.into(),
+                document.title.into(),
+                document.body.into(),
+            ])
+            .to_owned();
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn document(
+        &self,
+        entity_type: &str,
+        id: &str,
+    ) -> Result<Option<Document>, crate::Error> {
+        let sql = match entity_type {
+            "customer" => {
+                "SELECT NULL AS user_id, NULL AS start_time, name AS title, \
+                 COALESCE(comment, '') AS body FROM projections__customers WHERE id = ? AND archived = 0"
+            }
+            "project" => {
+                "SELECT NULL AS user_id, NULL AS start_time, name AS title, \
+                 COALESCE(comment, '') || ' ' || COALESCE(order_number, '') AS body \
+                 FROM projections__projects WHERE id = ? AND archived = 0"
+            }
+            "activity" => {
+                "SELECT NULL AS user_id, NULL AS start_time, name AS title, \
+                 COALESCE(comment, '') AS body FROM projections__activities WHERE id = ? AND archived = 0"
+            }
+            "tag" => {
+                "SELECT NULL AS user_id, NULL AS start_time, name AS title, '' AS body \
+                 FROM projections__tags WHERE id = ? AND archived = 0"
+            }
+            "timesheet" => {
+                "SELECT user_id, start_time, description AS title, '' AS body \
+                 FROM projections__timesheets \
+                 WHERE id = ? AND description IS NOT NULL AND description <> ''"
+            }
+            _ => return Ok(None),
+        };
+        let row = sqlx::query(sql)
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.map(|row| {
+            Ok(Document {
+                user_id: row.try_get("user_id")?,
+                start_time: row.try_get("start_time")?,
+                title: row.try_get("title")?,
+                body: row.try_get("body")?,
+            })
+        })
+        .transpose()
+    }
+}
+
+#[async_trait]
+impl Projector for SearchProjector {
+    type Error = crate::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        match audit::aggregate_type_for(&event.event_type) {
+            Some(entity_type @ ("customer" | "project" | "activity" | "tag" | "timesheet")) => {
+                self.index(entity_type, &event.stream_id).await
+            }
+            _ => Ok(()),
+        }
+    }
+}
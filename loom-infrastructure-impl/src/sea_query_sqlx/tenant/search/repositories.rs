@@ -0,0 +1,144 @@
+use sqlx::{Row, any::AnyRow};
+
+use crate::{ConnectedTenantPool, DatabaseType};
+
+/// Words of a query beyond this many are ignored.
+const MAX_TERMS: usize = 8;
+
+/// Ranked full-text search over `projections__search`.
+pub struct SearchRepository {
+    pool: ConnectedTenantPool,
+}
+
+/// Whose time entries a search may return.  Customers, projects,
+/// activities and tags are visible to every member.
+#[derive(Debug, Clone, Copy)]
+pub enum EntryScope<'a> {
+    /// Only the entries of this user.
+    Own(&'a str),
+    /// The entries of every user.
+    All,
+}
+
+impl SearchRepository {
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+
+    /// Entities matching every word of `query` as a prefix, best match
+    /// first.  Names and descriptions rank above comments and order
+    /// numbers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn search(
+        &self,
+        query: &str,
+        entries: EntryScope<'_>,
+        limit: u32,
+    ) -> Result<Vec<SearchHit>, crate::Error> {
+        let terms = terms(query);
+        if terms.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let (sql, query) = match self.pool.get_database_type() {
+            DatabaseType::Sqlite => (
+                format!(
+                    "SELECT entity_type, entity_id, start_time, title, body, \
+                     -bm25(projections__search, 0.0, 0.0, 0.0, 0.0, 10.0, 1.0) AS score \
+                     FROM projections__search \
+                     WHERE projections__search MATCH ?1 AND {} \
+                     ORDER BY score DESC LIMIT ?2",
+                    visibility(entries, "?3")
+                ),
+                fts5_query(&terms),
+            ),
+            DatabaseType::Postgres => (
+                format!(
+                    "SELECT entity_type, entity_id, start_time, title, body, \
+                     CAST(ts_rank(document, to_tsquery('simple', $1)) AS DOUBLE PRECISION) AS score \
+                     FROM projections__search \
+                     WHERE document @@ to_tsquery('simple', $1) AND {} \
+                     ORDER BY score DESC LIMIT $2",
+                    visibility(entries, "$3")
+                ),
+                tsquery(&terms),
+            ),
+        };
+
+        let mut statement = sqlx::query(&sql).bind(query).bind(i64::from(limit));
+        if let EntryScope::Own(user_id) = entries {
+            statement = statement.bind(user_id);
+        }
+        let rows = statement.fetch_all(self.pool.as_ref()).await?;
+        rows.iter().map(Self::map_row).collect()
+    }
+
+    fn map_row(row: &AnyRow) -> Result<SearchHit, crate::Error> {
+        Ok(SearchHit {
+            entity_type: row.try_get("entity_type")?,
+            entity_id: row.try_get("entity_id")?,
+            start_time: row.try_get("start_time")?,
+            title: row.try_get("title")?,
+            body: row.try_get("body")?,
+            score: row.try_get("score")?,
+        })
+    }
+}
+
+/// Restricts time entries to `entries`; `placeholder` binds the user.
+fn visibility(entries: EntryScope<'_>, placeholder: &str) -> String {
+    match entries {
+        EntryScope::Own(_) => {
+            format!("(entity_type <> 'timesheet' OR user_id = {placeholder})")
+        }
+        EntryScope::All => "1 = 1".to_string(),
+    }
+}
+
+/// The words of a query in lower case, without punctuation, so that no
+/// user input reaches the query syntax of either database.
+fn terms(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(MAX_TERMS)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// An FTS5 query matching every term as a prefix.
+fn fts5_query(terms: &[String]) -> String {
+    terms
+        .iter()
+        .map(|term| format!("\"{term}\"*"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A `tsquery` matching every term as a prefix.
+fn tsquery(terms: &[String]) -> String {
+    terms
+        .iter()
+        .map(|term| format!("{term}:*"))
+        .collect::<Vec<_>>()
+        .join(" & ")
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    /// `customer`, `project`, `activity`, `tag` or `timesheet`.
+    pub entity_type: String,
+    pub entity_id: String,
+    /// When the time entry started; `None` for other entities.
+    pub start_time: Option<String>,
+    /// The name, or the description of a time entry.
+    pub title: String,
+    /// Comment and order number.
+    pub body: String,
+    /// Higher is better; only comparable within one search.
+    pub score: f64,
+}
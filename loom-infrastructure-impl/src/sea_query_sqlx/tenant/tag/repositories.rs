@@ -7,7 +7,7 @@ use eventually_any::snapshot::Repository;
 use loom_core::tenant::tag::{Tag, TagEvent, TagId, TagRepository as TagRepositoryTrait};
 use sqlx::{Row, any::AnyRow};
 
-use crate::ConnectedTenantPool;
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
 
 pub struct TagRepository {
     pool: ConnectedTenantPool,
@@ -35,18 +35,47 @@ impl TagRepository {
     ///
     /// Returns an error if the database query fails.
     pub async fn all(&self) -> Result<Vec<TagRow>, crate::Error> {
-        let rows = sqlx::query("SELECT id, name FROM projections__tags ORDER BY name")
-            .fetch_all(self.pool.as_ref())
-            .await?;
+        let rows = sqlx::query(
+            "SELECT id, name, archived FROM projections__tags WHERE archived = 0 ORDER BY name",
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn archived(&self) -> Result<Vec<TagRow>, crate::Error> {
+        let rows = sqlx::query(
+            "SELECT id, name, archived FROM projections__tags WHERE archived <> 0 ORDER BY name",
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
         rows.into_iter().map(|r| Self::map_row(&r)).collect()
     }
 
+    /// Number of timesheets carrying the tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn timesheet_count(&self, tag_id: &str) -> Result<i64, crate::Error> {
+        let row = sqlx::query(
+            "SELECT CAST(COUNT(*) AS BIGINT) AS n FROM projections__timesheet_tags WHERE tag_id = ?",
+        )
+        .bind(tag_id)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        Ok(row.try_get("n")?)
+    }
+
     /// # Errors
     ///
     /// Returns an error if the database query fails.
     pub async fn for_timesheet(&self, timesheet_id: &str) -> Result<Vec<TagRow>, crate::Error> {
         let rows = sqlx::query(
-            "SELECT t.id, t.name \
+            "SELECT t.id, t.name, t.archived \
              FROM projections__tags t \
              JOIN projections__timesheet_tags tt ON tt.tag_id = t.id \
              WHERE tt.timesheet_id = ? \
@@ -62,27 +91,38 @@ impl TagRepository {
         Ok(TagRow {
             id: row.try_get("id")?,
             name: row.try_get("name")?,
+            archived: bool_col(row, "archived"),
         })
     }
 }
 
+fn bool_col(row: &AnyRow, col: &str) -> bool {
+    row.try_get::<bool, _>(col)
+        .unwrap_or_else(|_| row.try_get::<i64, _>(col).map(|v| v != 0).unwrap_or(false))
+}
+
 #[derive(Debug, Clone)]
 pub struct TagRow {
     pub id: String,
     pub name: String,
+    pub archived: bool,
 }
 
 #[async_trait]
 impl Getter<Tag> for TagRepository {
     async fn get(&self, id: &TagId) -> Result<eventually::aggregate::Root<Tag>, GetError> {
-        self.repository.get(id).await
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
     }
 }
 
 #[async_trait]
 impl Saver<Tag> for TagRepository {
     async fn save(&self, root: &mut eventually::aggregate::Root<Tag>) -> Result<(), SaveError> {
-        self.repository.save(root).await
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
     }
 }
 
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use eventually_projection::{Projector, RawEvent};
-use loom_core::tenant::timesheet::TimesheetEvent;
+use loom_core::tenant::{approval::ApprovalStatus, timesheet::TimesheetEvent};
 use sea_query::{Condition, DynIden, Expr, ExprTrait, OnConflict, Query, TableRef};
 use sea_query_sqlx::SqlxBinder;
 
@@ -12,11 +12,65 @@ pub struct TimesheetProjector {
 
 impl TimesheetProjector {
     const TABLE: &'static str = "projections__timesheets";
+    const TIMESHEET_TAGS_TABLE: &'static str = "projections__timesheet_tags";
 
     #[must_use]
     pub const fn new(pool: ConnectedTenantPool) -> Self {
         Self { pool }
     }
+
+    /// Remove the row of a timesheet and its tag links, e.g. before its
+    /// events are replayed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn forget(&self, id: &str) -> Result<(), crate::Error> {
+        // Tag links go first so no dangling rows remain when foreign keys
+        // are not enforced.
+        let query = Query::delete()
+            .from_table(TableRef::from(Self::TIMESHEET_TAGS_TABLE))
+            .cond_where(Condition::all().add(Expr::col("timesheet_id").eq(Expr::val(id))))
+            .to_owned();
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        let query = Query::delete()
+            .from_table(TableRef::from(Self::TABLE))
+            .cond_where(Condition::all().add(Expr::col("id").eq(Expr::val(id))))
+            .to_owned();
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn set_approval_status(
+        &self,
+        id: &str,
+        status: ApprovalStatus,
+        approval_id: Option<String>,
+    ) -> Result<(), crate::Error> {
+        let mut values: Vec<(DynIden, Expr)> =
+            vec![(DynIden::from("approval_status"), status.as_str().into())];
+        if let Some(approval_id) = approval_id {
+            values.push((DynIden::from("approval_id"), approval_id.into()));
+        }
+        let query = Query::update()
+            .table(TableRef::from(Self::TABLE))
+            .values(values)
+            .cond_where(Condition::all().add(Expr::col("id").eq(Expr::val(id))))
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -68,15 +122,29 @@ impl Projector for TimesheetProjector {
                     .execute(self.pool.as_ref())
                     .await?;
             }
-            "TimesheetStopped" => {
-                let TimesheetEvent::Stopped {
+            "TimesheetStopped" | "TimesheetAmended" => {
+                let (TimesheetEvent::Stopped {
                     end_time,
                     duration,
                     hourly_rate,
                     fixed_rate,
                     internal_rate,
                     rate,
-                } = serde_json::from_slice(&event.payload_bytes)?
+                    billed_duration,
+                    rounding,
+                    break_duration,
+                }
+                | TimesheetEvent::Amended {
+                    end_time,
+                    duration,
+                    hourly_rate,
+                    fixed_rate,
+                    internal_rate,
+                    rate,
+                    billed_duration,
+                    rounding,
+                    break_duration,
+                }) = serde_json::from_slice(&event.payload_bytes)?
                 else {
                     return Ok(());
                 };
@@ -90,6 +158,19 @@ impl Projector for TimesheetProjector {
                         (DynIden::from("fixed_rate"), fixed_rate.into()),
                         (DynIden::from("internal_rate"), internal_rate.into()),
                         (DynIden::from("rate"), rate.into()),
+                        (
+                            DynIden::from("billed_duration"),
+                            billed_duration.or(Some(duration)).into(),
+                        ),
+                        (
+                            DynIden::from("rounding"),
+                            rounding
+                                .map(|r| serde_json::to_string(&r))
+                                .transpose()?
+                                .into(),
+                        ),
+                        (DynIden::from("break_duration"), break_duration.into()),
+                        (DynIden::from("paused_at"), Option::<String>::None.into()),
                     ])
                     .cond_where(
                         Condition::all()
@@ -105,6 +186,54 @@ impl Projector for TimesheetProjector {
                     .execute(self.pool.as_ref())
                     .await?;
             }
+            "TimesheetPaused" => {
+                let TimesheetEvent::Paused { paused_at } =
+                    serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::update()
+                    .table(TableRef::from(Self::TABLE))
+                    .values([(DynIden::from("paused_at"), paused_at.into())])
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "TimesheetResumed" => {
+                let TimesheetEvent::Resumed { break_duration, .. } =
+                    serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::update()
+                    .table(TableRef::from(Self::TABLE))
+                    .values([
+                        (DynIden::from("paused_at"), Option::<String>::None.into()),
+                        (
+                            DynIden::from("break_duration"),
+                            Expr::col("break_duration").add(break_duration),
+                        ),
+                    ])
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
             "TimesheetUpdated" => {
                 let TimesheetEvent::Updated {
                     description,
@@ -212,6 +341,28 @@ impl Projector for TimesheetProjector {
                     .execute(self.pool.as_ref())
                     .await?;
             }
+            "TimesheetDeleted" => self.forget(&event.stream_id).await?,
+            "TimesheetSubmitted" => {
+                let TimesheetEvent::Submitted { approval_id } =
+                    serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+                self.set_approval_status(
+                    &event.stream_id,
+                    ApprovalStatus::Submitted,
+                    Some(approval_id.to_string()),
+                )
+                .await?;
+            }
+            "TimesheetApproved" => {
+                self.set_approval_status(&event.stream_id, ApprovalStatus::Approved, None)
+                    .await?;
+            }
+            "TimesheetRejected" => {
+                self.set_approval_status(&event.stream_id, ApprovalStatus::Rejected, None)
+                    .await?;
+            }
             _ => {}
         }
 
@@ -9,7 +9,7 @@ use loom_core::tenant::timesheet::{
 };
 use sqlx::{Row, any::AnyRow};
 
-use crate::ConnectedTenantPool;
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
 
 pub struct TimesheetRepository {
     pool: ConnectedTenantPool,
@@ -35,7 +35,8 @@ impl TimesheetRepository {
 
     const SELECT: &'static str = "SELECT id, user_id, project_id, activity_id, start_time, end_time, \
          duration, description, timezone, billable, exported, \
-         hourly_rate, fixed_rate, internal_rate, rate \
+         hourly_rate, fixed_rate, internal_rate, rate, billed_duration, approval_status, approval_id, \
+         break_duration, paused_at \
          FROM projections__timesheets";
 
     /// Most-recent 50 timesheets for a user, newest first.
@@ -55,6 +56,199 @@ impl TimesheetRepository {
         rows.into_iter().map(|r| Self::map_row(&r)).collect()
     }
 
+    /// All timesheets booked on a project, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn for_project(&self, project_id: &str) -> Result<Vec<TimesheetRow>, crate::Error> {
+        let sql = format!(
+            "{} WHERE project_id = ? ORDER BY start_time ASC",
+            Self::SELECT
+        );
+        let rows = sqlx::query(&sql)
+            .bind(project_id)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find(&self, id: &str) -> Result<Option<TimesheetRow>, crate::Error> {
+        let sql = format!("{} WHERE id = ?", Self::SELECT);
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.map(|r| Self::map_row(&r)).transpose()
+    }
+
+    /// Number of timesheets booked on the project.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn count_for_project(&self, project_id: &str) -> Result<i64, crate::Error> {
+        self.count("project_id = ?", project_id).await
+    }
+
+    /// Number of timesheets booked on the activity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn count_for_activity(&self, activity_id: &str) -> Result<i64, crate::Error> {
+        self.count("activity_id = ?", activity_id).await
+    }
+
+    /// Number of timesheets booked on any project of the customer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn count_for_customer(&self, customer_id: &str) -> Result<i64, crate::Error> {
+        self.count(
+            "project_id IN (SELECT id FROM projections__projects WHERE customer_id = ?)",
+            customer_id,
+        )
+        .await
+    }
+
+    async fn count(&self, condition: &str, id: &str) -> Result<i64, crate::Error> {
+        let sql = format!(
+            "SELECT CAST(COUNT(*) AS BIGINT) AS n FROM projections__timesheets WHERE {condition}"
+        );
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_one(self.pool.as_ref())
+            .await?;
+        Ok(row.try_get("n")?)
+    }
+
+    /// The user's timesheets that share time with `from..to`, running timers
+    /// included.  Bounds are RFC-3339 UTC strings compared as text, so callers
+    /// should re-check the exact times.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn for_user_between(
+        &self,
+        user_id: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<TimesheetRow>, crate::Error> {
+        let sql = format!(
+            "{} WHERE user_id = ? AND start_time < ? AND (end_time IS NULL OR end_time > ?) \
+             ORDER BY start_time ASC",
+            Self::SELECT
+        );
+        let rows = sqlx::query(&sql)
+            .bind(user_id)
+            .bind(to)
+            .bind(from)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// The user's timesheets that start within `[from, to)`, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn starting_between(
+        &self,
+        user_id: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<TimesheetRow>, crate::Error> {
+        let sql = format!(
+            "{} WHERE user_id = ? AND start_time >= ? AND start_time < ? ORDER BY start_time ASC",
+            Self::SELECT
+        );
+        let rows = sqlx::query(&sql)
+            .bind(user_id)
+            .bind(from)
+            .bind(to)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// The timesheets last covered by a submission, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn for_approval(&self, approval_id: &str) -> Result<Vec<TimesheetRow>, crate::Error> {
+        let sql = format!(
+            "{} WHERE approval_id = ? ORDER BY start_time ASC",
+            Self::SELECT
+        );
+        let rows = sqlx::query(&sql)
+            .bind(approval_id)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// Stopped timesheets of all users that start within `[from, to)`, oldest
+    /// first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn stopped_between(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<TimesheetRow>, crate::Error> {
+        let sql = format!(
+            "{} WHERE end_time IS NOT NULL AND start_time >= ? AND start_time < ? \
+             ORDER BY start_time ASC",
+            Self::SELECT
+        );
+        let rows = sqlx::query(&sql)
+            .bind(from)
+            .bind(to)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// Stopped, not yet exported timesheets of all users that start within
+    /// `[from, to)`, optionally only approved ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn exportable_between(
+        &self,
+        from: &str,
+        to: &str,
+        approved_only: bool,
+    ) -> Result<Vec<TimesheetRow>, crate::Error> {
+        let approved = if approved_only {
+            " AND approval_status = 'approved'"
+        } else {
+            ""
+        };
+        let sql = format!(
+            "{} WHERE exported = 0 AND end_time IS NOT NULL \
+             AND start_time >= ? AND start_time < ?{approved} ORDER BY start_time ASC",
+            Self::SELECT
+        );
+        let rows = sqlx::query(&sql)
+            .bind(from)
+            .bind(to)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
     /// Returns the running timesheet for a user (`end_time` IS NULL), if any.
     ///
     /// # Errors
@@ -75,6 +269,20 @@ impl TimesheetRepository {
         row.map(|r| Self::map_row(&r)).transpose()
     }
 
+    /// Returns the running timesheets of all users, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn running(&self) -> Result<Vec<TimesheetRow>, crate::Error> {
+        let sql = format!(
+            "{} WHERE end_time IS NULL ORDER BY start_time ASC",
+            Self::SELECT
+        );
+        let rows = sqlx::query(&sql).fetch_all(self.pool.as_ref()).await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
     fn map_row(row: &AnyRow) -> Result<TimesheetRow, crate::Error> {
         Ok(TimesheetRow {
             id: row.try_get("id")?,
@@ -92,6 +300,11 @@ impl TimesheetRepository {
             fixed_rate: row.try_get("fixed_rate")?,
             internal_rate: row.try_get("internal_rate")?,
             rate: row.try_get("rate")?,
+            billed_duration: row.try_get("billed_duration").ok().flatten(),
+            approval_status: row.try_get("approval_status")?,
+            approval_id: row.try_get("approval_id")?,
+            break_duration: row.try_get("break_duration").unwrap_or(0),
+            paused_at: row.try_get("paused_at").ok().flatten(),
         })
     }
 }
@@ -120,8 +333,18 @@ pub struct TimesheetRow {
     pub fixed_rate: Option<i64>,
     /// Internal (cost) rate in cents for profitability calculations.
     pub internal_rate: Option<i64>,
-    /// Total billable amount in cents: `hourly_rate * duration / 3600`.
+    /// Total billable amount in cents: `hourly_rate * billed_duration / 3600`.
     pub rate: Option<i64>,
+    /// Duration in seconds after rounding, the basis of `rate`.
+    pub billed_duration: Option<i32>,
+    /// `draft`, `submitted`, `approved` or `rejected`.
+    pub approval_status: String,
+    /// The submission that last covered this timesheet.
+    pub approval_id: Option<String>,
+    /// Total length of the breaks in seconds, excluded from `duration`.
+    pub break_duration: i32,
+    /// When the running timer was paused, if it is paused.
+    pub paused_at: Option<String>,
 }
 
 #[async_trait]
@@ -130,7 +353,9 @@ impl Getter<Timesheet> for TimesheetRepository {
         &self,
         id: &TimesheetId,
     ) -> Result<eventually::aggregate::Root<Timesheet>, GetError> {
-        self.repository.get(id).await
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
     }
 }
 
@@ -140,7 +365,9 @@ impl Saver<Timesheet> for TimesheetRepository {
         &self,
         root: &mut eventually::aggregate::Root<Timesheet>,
     ) -> Result<(), SaveError> {
-        self.repository.save(root).await
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
     }
 }
 
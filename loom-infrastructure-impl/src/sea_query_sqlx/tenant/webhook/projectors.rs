@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use eventually_projection::{Projector, RawEvent};
+use loom_core::tenant::webhook::WebhookEvent;
+use sea_query::{Condition, DynIden, Expr, ExprTrait, OnConflict, Query, TableRef};
+
+use crate::ConnectedTenantPool;
+
+pub struct WebhookProjector {
+    pool: ConnectedTenantPool,
+}
+
+impl WebhookProjector {
+    const TABLE: &'static str = "projections__webhooks";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl Projector for WebhookProjector {
+    type Error = crate::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        match event.event_type.as_str() {
+            "WebhookSubscribed" => {
+                let WebhookEvent::Subscribed {
+                    id,
+                    url,
+                    secret,
+                    event_types,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::insert()
+                    .into_table(TableRef::from(Self::TABLE))
+                    .columns([
+                        DynIden::from("id"),
+                        DynIden::from("url"),
+                        DynIden::from("secret"),
+                        DynIden::from("event_types"),
+                        DynIden::from("since_position"),
+                    ])
+                    .values_panic([
+                        id.to_string().into(),
+                        url.into(),
+                        secret.into(),
+                        serde_json::to_string(&event_types)?.into(),
+                        event.global_position.into(),
+                    ])
+                    .on_conflict(OnConflict::new().do_nothing().to_owned())
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "WebhookChanged" => {
+                let WebhookEvent::Changed { url, event_types } =
+                    serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+                let query = Query::update()
+                    .table(TableRef::from(Self::TABLE))
+                    .values([
+                        (DynIden::from("url"), url.into()),
+                        (
+                            DynIden::from("event_types"),
+                            serde_json::to_string(&event_types)?.into(),
+                        ),
+                    ])
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "WebhookSecretRotated" => {
+                let WebhookEvent::SecretRotated { secret } =
+                    serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+                let query = Query::update()
+                    .table(TableRef::from(Self::TABLE))
+                    .values([(DynIden::from("secret"), secret.into())])
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "WebhookRemoved" => {
+                let query = Query::delete()
+                    .from_table(TableRef::from(Self::TABLE))
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,420 @@
+use std::ops::Deref;
+
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use eventually::aggregate::repository::{GetError, Getter, SaveError, Saver};
+use eventually::serde::Json;
+use eventually_any::snapshot::Repository;
+use loom_core::tenant::webhook::{
+    Webhook, WebhookEvent, WebhookId, WebhookRepository as WebhookRepositoryTrait,
+};
+use sea_query::{DynIden, OnConflict, Query, TableRef};
+use sqlx::{Row, any::AnyRow};
+
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
+
+pub struct WebhookRepository {
+    pool: ConnectedTenantPool,
+    repository: Repository<Webhook, Json<Webhook>, Json<WebhookEvent>>,
+}
+
+impl Deref for WebhookRepository {
+    type Target = Repository<Webhook, Json<Webhook>, Json<WebhookEvent>>;
+    fn deref(&self) -> &Self::Target {
+        &self.repository
+    }
+}
+
+impl WebhookRepository {
+    /// # Errors
+    ///
+    /// Returns an error if the event store repository cannot be initialized.
+    pub async fn from_pool(pool: ConnectedTenantPool) -> Result<Self, sqlx::migrate::MigrateError> {
+        let repository =
+            Repository::new(pool.as_ref().clone(), Json::default(), Json::default()).await?;
+        Ok(Self { pool, repository })
+    }
+
+    /// Every subscription, ordered by URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn all(&self) -> Result<Vec<WebhookRow>, crate::Error> {
+        let rows = sqlx::query(
+            "SELECT id, url, secret, event_types, since_position FROM projections__webhooks \
+             ORDER BY url ASC, id ASC",
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    fn map_row(row: &AnyRow) -> Result<WebhookRow, crate::Error> {
+        let event_types: String = row.try_get("event_types")?;
+        Ok(WebhookRow {
+            id: row.try_get("id")?,
+            url: row.try_get("url")?,
+            secret: row.try_get("secret")?,
+            event_types: serde_json::from_str(&event_types).unwrap_or_default(),
+            since_position: row.try_get("since_position")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookRow {
+    pub id: String,
+    pub url: String,
+    pub secret: String,
+    pub event_types: Vec<String>,
+    /// Global position of the subscribing event; only later events are
+    /// delivered.
+    pub since_position: i64,
+}
+
+impl WebhookRow {
+    /// Whether `event_type` at `global_position` is to be sent to this
+    /// subscription.
+    #[must_use]
+    pub fn wants(&self, event_type: &str, global_position: i64) -> bool {
+        global_position > self.since_position && self.event_types.iter().any(|t| t == event_type)
+    }
+}
+
+#[async_trait]
+impl Getter<Webhook> for WebhookRepository {
+    async fn get(&self, id: &WebhookId) -> Result<eventually::aggregate::Root<Webhook>, GetError> {
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
+    }
+}
+
+#[async_trait]
+impl Saver<Webhook> for WebhookRepository {
+    async fn save(&self, root: &mut eventually::aggregate::Root<Webhook>) -> Result<(), SaveError> {
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
+    }
+}
+
+impl WebhookRepositoryTrait for WebhookRepository {}
+
+/// The delivery log of the workspace's webhooks, which doubles as the queue
+/// of the delivery worker.
+pub struct WebhookDeliveryRepository {
+    pool: ConnectedTenantPool,
+}
+
+impl WebhookDeliveryRepository {
+    const TABLE: &'static str = "webhook_deliveries";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+
+    /// Queue `payload` for immediate delivery to `webhook_id`.  An event
+    /// that is already queued for the subscription is left alone, so that a
+    /// rebuilt projection does not send it again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn enqueue(
+        &self,
+        webhook_id: &str,
+        global_position: i64,
+        stream_id: &str,
+        event_type: &str,
+        payload: &str,
+    ) -> Result<(), crate::Error> {
+        self.insert(
+            webhook_id,
+            global_position,
+            0,
+            stream_id,
+            event_type,
+            payload,
+        )
+        .await
+    }
+
+    async fn insert(
+        &self,
+        webhook_id: &str,
+        global_position: i64,
+        redelivery: i32,
+        stream_id: &str,
+        event_type: &str,
+        payload: &str,
+    ) -> Result<(), crate::Error> {
+        let now = now();
+        let query = Query::insert()
+            .into_table(TableRef::from(Self::TABLE))
+            .columns(
+                [
+                    "webhook_id",
+                    "global_position",
+                    "redelivery",
+                    "stream_id",
+                    "event_type",
+                    "payload",
+                    "status",
+                    "attempts",
+                    "next_attempt_at",
+                    "created_at",
+                ]
+                .map(DynIden::from),
+            )
+            .values_panic([
+                webhook_id.into(),
+                global_position.into(),
+                redelivery.into(),
+                stream_id.into(),
+                event_type.into(),
+                payload.into(),
+                DeliveryStatus::Pending.as_str().into(),
+                0.into(),
+                now.clone().into(),
+                now.into(),
+            ])
+            .on_conflict(
+                OnConflict::columns(
+                    ["webhook_id", "global_position", "redelivery"].map(DynIden::from),
+                )
+                .do_nothing()
+                .to_owned(),
+            )
+            .to_owned();
+
+        let (sql, values) = self.pool.build_query(&query);
+        sqlx::query_with(&sql, values)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// Pending deliveries whose next attempt is due, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn due(&self, limit: i64) -> Result<Vec<DeliveryRow>, crate::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM webhook_deliveries WHERE status = ? AND next_attempt_at <= ? \
+             ORDER BY id LIMIT ?",
+        )
+        .bind(DeliveryStatus::Pending.as_str())
+        .bind(now())
+        .bind(limit)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(Self::map_row).collect()
+    }
+
+    /// The most recent deliveries, of one subscription or of all, newest
+    /// first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn list(
+        &self,
+        webhook_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<DeliveryRow>, crate::Error> {
+        let rows = match webhook_id {
+            Some(webhook_id) => sqlx::query(
+                "SELECT * FROM webhook_deliveries WHERE webhook_id = ? ORDER BY id DESC LIMIT ?",
+            )
+            .bind(webhook_id)
+            .bind(limit)
+            .fetch_all(self.pool.as_ref())
+            .await?,
+            None => {
+                sqlx::query("SELECT * FROM webhook_deliveries ORDER BY id DESC LIMIT ?")
+                    .bind(limit)
+                    .fetch_all(self.pool.as_ref())
+                    .await?
+            }
+        };
+        rows.iter().map(Self::map_row).collect()
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn find(&self, id: i64) -> Result<Option<DeliveryRow>, crate::Error> {
+        let row = sqlx::query("SELECT * FROM webhook_deliveries WHERE id = ?")
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.as_ref().map(Self::map_row).transpose()
+    }
+
+    /// Queue the event of `delivery` once more, as a new delivery with fresh
+    /// attempts.  The original stays in the log as it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn redeliver(&self, delivery: &DeliveryRow) -> Result<(), crate::Error> {
+        let row = sqlx::query(
+            "SELECT MAX(redelivery) AS redelivery FROM webhook_deliveries \
+             WHERE webhook_id = ? AND global_position = ?",
+        )
+        .bind(delivery.webhook_id.as_str())
+        .bind(delivery.global_position)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        let latest: Option<i32> = row.try_get("redelivery")?;
+        self.insert(
+            &delivery.webhook_id,
+            delivery.global_position,
+            latest.unwrap_or_default() + 1,
+            &delivery.stream_id,
+            &delivery.event_type,
+            &delivery.payload,
+        )
+        .await
+    }
+
+    /// Record a successful attempt.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn mark_delivered(&self, id: i64, response_status: i32) -> Result<(), crate::Error> {
+        self.record_attempt(
+            id,
+            DeliveryStatus::Delivered,
+            None,
+            Some(response_status),
+            None,
+        )
+        .await
+    }
+
+    /// Record a failed attempt.  With `next_attempt_at` the delivery is
+    /// retried then, without it it is given up.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn mark_failed(
+        &self,
+        id: i64,
+        next_attempt_at: Option<String>,
+        response_status: Option<i32>,
+        error: &str,
+    ) -> Result<(), crate::Error> {
+        let status = if next_attempt_at.is_some() {
+            DeliveryStatus::Pending
+        } else {
+            DeliveryStatus::Failed
+        };
+        self.record_attempt(id, status, next_attempt_at, response_status, Some(error))
+            .await
+    }
+
+    async fn record_attempt(
+        &self,
+        id: i64,
+        status: DeliveryStatus,
+        next_attempt_at: Option<String>,
+        response_status: Option<i32>,
+        error: Option<&str>,
+    ) -> Result<(), crate::Error> {
+        sqlx::query(
+            "UPDATE webhook_deliveries SET status = ?, attempts = attempts + 1, \
+             next_attempt_at = ?, last_attempt_at = ?, response_status = ?, error = ? \
+             WHERE id = ?",
+        )
+        .bind(status.as_str())
+        .bind(next_attempt_at)
+        .bind(now())
+        .bind(response_status)
+        .bind(error)
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    fn map_row(row: &AnyRow) -> Result<DeliveryRow, crate::Error> {
+        let status: String = row.try_get("status")?;
+        Ok(DeliveryRow {
+            id: row.try_get("id")?,
+            webhook_id: row.try_get("webhook_id")?,
+            global_position: row.try_get("global_position")?,
+            redelivery: row.try_get("redelivery")?,
+            stream_id: row.try_get("stream_id")?,
+            event_type: row.try_get("event_type")?,
+            payload: row.try_get("payload")?,
+            status: DeliveryStatus::parse(&status),
+            attempts: row.try_get("attempts")?,
+            next_attempt_at: row.try_get("next_attempt_at")?,
+            last_attempt_at: row.try_get("last_attempt_at")?,
+            response_status: row.try_get("response_status")?,
+            error: row.try_get("error")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    /// Waiting for its first or next attempt.
+    Pending,
+    /// Answered with a 2xx status.
+    Delivered,
+    /// Given up after the last retry.
+    Failed,
+}
+
+impl DeliveryStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::Failed => "failed",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "delivered" => Self::Delivered,
+            "failed" => Self::Failed,
+            _ => Self::Pending,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeliveryRow {
+    pub id: i64,
+    pub webhook_id: String,
+    pub global_position: i64,
+    /// 0 for the original delivery, counting up with each manual redelivery.
+    pub redelivery: i32,
+    pub stream_id: String,
+    pub event_type: String,
+    /// The request body (JSON).
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: Option<String>,
+    pub last_attempt_at: Option<String>,
+    /// HTTP status of the last attempt, if the endpoint answered at all.
+    pub response_status: Option<i32>,
+    pub error: Option<String>,
+    pub created_at: String,
+}
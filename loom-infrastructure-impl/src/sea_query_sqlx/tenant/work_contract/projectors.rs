@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use eventually_projection::{Projector, RawEvent};
+use loom_core::tenant::work_contract::WorkContractEvent;
+use sea_query::{Condition, DynIden, Expr, ExprTrait, OnConflict, Query, TableRef};
+
+use crate::ConnectedTenantPool;
+
+pub struct WorkContractProjector {
+    pool: ConnectedTenantPool,
+}
+
+impl WorkContractProjector {
+    const TABLE: &'static str = "projections__work_contracts";
+
+    #[must_use]
+    pub const fn new(pool: ConnectedTenantPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl Projector for WorkContractProjector {
+    type Error = crate::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        match event.event_type.as_str() {
+            "WorkContractDefined" => {
+                let WorkContractEvent::Defined {
+                    id,
+                    user_id,
+                    weekly_minutes,
+                    working_days,
+                    starts_on,
+                    region,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::insert()
+                    .into_table(TableRef::from(Self::TABLE))
+                    .columns([
+                        DynIden::from("id"),
+                        DynIden::from("user_id"),
+                        DynIden::from("weekly_minutes"),
+                        DynIden::from("working_days"),
+                        DynIden::from("starts_on"),
+                        DynIden::from("region"),
+                    ])
+                    .values_panic([
+                        id.to_string().into(),
+                        user_id.to_string().into(),
+                        weekly_minutes.into(),
+                        serde_json::to_string(&working_days)?.into(),
+                        starts_on.into(),
+                        region.into(),
+                    ])
+                    .on_conflict(OnConflict::new().do_nothing().to_owned())
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "WorkContractChanged" => {
+                let WorkContractEvent::Changed {
+                    weekly_minutes,
+                    working_days,
+                    starts_on,
+                    region,
+                } = serde_json::from_slice(&event.payload_bytes)?
+                else {
+                    return Ok(());
+                };
+
+                let query = Query::update()
+                    .table(TableRef::from(Self::TABLE))
+                    .values([
+                        (DynIden::from("weekly_minutes"), weekly_minutes.into()),
+                        (
+                            DynIden::from("working_days"),
+                            serde_json::to_string(&working_days)?.into(),
+                        ),
+                        (DynIden::from("starts_on"), starts_on.into()),
+                        (DynIden::from("region"), region.into()),
+                    ])
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            "WorkContractRemoved" => {
+                let query = Query::delete()
+                    .from_table(TableRef::from(Self::TABLE))
+                    .cond_where(
+                        Condition::all()
+                            .add(Expr::col("id").eq(Expr::val(event.stream_id.clone()))),
+                    )
+                    .to_owned();
+
+                let (sql, values) = self.pool.build_query(&query);
+                sqlx::query_with(&sql, values)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
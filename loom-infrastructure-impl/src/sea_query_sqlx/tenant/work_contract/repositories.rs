@@ -0,0 +1,142 @@
+use std::ops::Deref;
+
+use async_trait::async_trait;
+use eventually::aggregate::repository::{GetError, Getter, SaveError, Saver};
+use eventually::serde::Json;
+use eventually_any::snapshot::Repository;
+use loom_core::tenant::work_contract::{
+    WorkContract, WorkContractEvent, WorkContractId,
+    WorkContractRepository as WorkContractRepositoryTrait,
+};
+use sqlx::{Row, any::AnyRow};
+
+use crate::{ConnectedTenantPool, snapshot::SnapshotStore};
+
+pub struct WorkContractRepository {
+    pool: ConnectedTenantPool,
+    repository: Repository<WorkContract, Json<WorkContract>, Json<WorkContractEvent>>,
+}
+
+impl Deref for WorkContractRepository {
+    type Target = Repository<WorkContract, Json<WorkContract>, Json<WorkContractEvent>>;
+    fn deref(&self) -> &Self::Target {
+        &self.repository
+    }
+}
+
+impl WorkContractRepository {
+    const SELECT: &'static str = "SELECT id, user_id, weekly_minutes, working_days, starts_on, region \
+         FROM projections__work_contracts";
+
+    /// # Errors
+    ///
+    /// Returns an error if the event store repository cannot be initialized.
+    pub async fn from_pool(pool: ConnectedTenantPool) -> Result<Self, sqlx::migrate::MigrateError> {
+        let repository =
+            Repository::new(pool.as_ref().clone(), Json::default(), Json::default()).await?;
+        Ok(Self { pool, repository })
+    }
+
+    /// Every contract, grouped by user and oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn all(&self) -> Result<Vec<WorkContractRow>, crate::Error> {
+        let sql = format!("{} ORDER BY user_id ASC, starts_on ASC", Self::SELECT);
+        let rows = sqlx::query(&sql).fetch_all(self.pool.as_ref()).await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// A user's contracts, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn for_user(&self, user_id: &str) -> Result<Vec<WorkContractRow>, crate::Error> {
+        let sql = format!("{} WHERE user_id = ? ORDER BY starts_on ASC", Self::SELECT);
+        let rows = sqlx::query(&sql)
+            .bind(user_id)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.into_iter().map(|r| Self::map_row(&r)).collect()
+    }
+
+    /// Seconds a user logged per UTC day (`YYYY-MM-DD`) on finished
+    /// timesheets started within `[from, to)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn worked_by_day(
+        &self,
+        user_id: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<(String, i64)>, crate::Error> {
+        let rows = sqlx::query(
+            "SELECT SUBSTR(start_time, 1, 10) AS day, \
+             CAST(COALESCE(SUM(duration), 0) AS BIGINT) AS worked_seconds \
+             FROM projections__timesheets \
+             WHERE user_id = ? AND end_time IS NOT NULL AND start_time >= ? AND start_time < ? \
+             GROUP BY SUBSTR(start_time, 1, 10)",
+        )
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter()
+            .map(|r| Ok((r.try_get("day")?, r.try_get("worked_seconds")?)))
+            .collect()
+    }
+
+    fn map_row(row: &AnyRow) -> Result<WorkContractRow, crate::Error> {
+        let working_days: String = row.try_get("working_days")?;
+        Ok(WorkContractRow {
+            id: row.try_get("id")?,
+            user_id: row.try_get("user_id")?,
+            weekly_minutes: row.try_get("weekly_minutes")?,
+            working_days: serde_json::from_str(&working_days).unwrap_or_default(),
+            starts_on: row.try_get("starts_on")?,
+            region: row.try_get("region")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkContractRow {
+    pub id: String,
+    pub user_id: String,
+    pub weekly_minutes: i32,
+    /// ISO weekday numbers, 1 (Monday) to 7 (Sunday).
+    pub working_days: Vec<u8>,
+    pub starts_on: String,
+    pub region: Option<String>,
+}
+
+#[async_trait]
+impl Getter<WorkContract> for WorkContractRepository {
+    async fn get(
+        &self,
+        id: &WorkContractId,
+    ) -> Result<eventually::aggregate::Root<WorkContract>, GetError> {
+        SnapshotStore::new(self.pool.clone())
+            .get(&self.repository, id)
+            .await
+    }
+}
+
+#[async_trait]
+impl Saver<WorkContract> for WorkContractRepository {
+    async fn save(
+        &self,
+        root: &mut eventually::aggregate::Root<WorkContract>,
+    ) -> Result<(), SaveError> {
+        SnapshotStore::new(self.pool.clone())
+            .save(&self.repository, root)
+            .await
+    }
+}
+
+impl WorkContractRepositoryTrait for WorkContractRepository {}
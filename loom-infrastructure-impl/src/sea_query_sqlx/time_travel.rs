@@ -0,0 +1,211 @@
+//! Point-in-time reads of the event store.
+//!
+//! [`HistoricalRepository`] loads any aggregate the way it looked at a
+//! [`Cutoff`], and [`rebuild_tenant`] replays a tenant's events up to a cutoff
+//! into a private in-memory database so the regular projection repositories
+//! can answer reporting queries against the past.  The rebuild is only
+//! available for SQLite workspaces.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use eventually::aggregate::{
+    Aggregate, Root,
+    repository::{GetError, Getter},
+};
+use eventually_projection::{Projector, RawEvent};
+use loom_infrastructure::database::Migrate;
+use serde::de::DeserializeOwned;
+use sqlx::{Row, any::AnyRow};
+use url::Url;
+
+use crate::{
+    ConnectedTenantPool, DatabaseType, Pool, ScopeTenant, StateConnected,
+    sea_query_sqlx::infrastructure::Error as InfrastructureError,
+    tenant::projectors::TenantProjector,
+};
+
+/// The point in history a read is truncated at (inclusive).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cutoff {
+    /// Include events up to and including this global position.
+    GlobalPosition(i64),
+    /// Include events recorded at or before this instant.
+    ///
+    /// Events stored before `recorded_at` existed have no time and count as
+    /// recorded before every stamped event.  The cutoff is taken as the last
+    /// global position up to the instant, so a read never starts mid-stream
+    /// when clocks disagree.
+    RecordedAt(DateTime<Utc>),
+}
+
+/// Event store reader that ignores every event after its [`Cutoff`].
+pub struct HistoricalRepository<Scope> {
+    pool: Pool<Scope, StateConnected>,
+    cutoff: Cutoff,
+}
+
+/// Columns of `events` as read by [`map_raw_event`].
+pub(crate) const RAW_EVENT_COLUMNS: &str = "event_stream_id, version, global_position, type, \
+     event, CAST(metadata AS TEXT) AS metadata, schema_version";
+
+/// Read a row selected with [`RAW_EVENT_COLUMNS`] the way the projection
+/// runner delivers it.
+pub(crate) fn map_raw_event(row: &AnyRow) -> Result<RawEvent, crate::Error> {
+    let metadata: Option<String> = row.try_get("metadata")?;
+    Ok(RawEvent {
+        stream_id: row.try_get("event_stream_id")?,
+        version: row
+            .try_get::<i64, _>("version")?
+            .try_into()
+            .unwrap_or_default(),
+        global_position: row.try_get("global_position")?,
+        event_type: row.try_get("type")?,
+        payload_bytes: row.try_get("event")?,
+        metadata: metadata
+            .map(|m| serde_json::from_str(&m))
+            .transpose()?
+            .unwrap_or_default(),
+        schema_version: row
+            .try_get::<i64, _>("schema_version")?
+            .try_into()
+            .unwrap_or_default(),
+    })
+}
+
+impl<Scope> HistoricalRepository<Scope> {
+    #[must_use]
+    pub const fn new(pool: Pool<Scope, StateConnected>, cutoff: Cutoff) -> Self {
+        Self { pool, cutoff }
+    }
+
+    /// Events before the cutoff in global order, optionally limited to one stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn events(&self, stream_id: Option<&str>) -> Result<Vec<RawEvent>, crate::Error> {
+        let bound = match &self.cutoff {
+            Cutoff::GlobalPosition(_) => "global_position <= ?",
+            Cutoff::RecordedAt(_) => {
+                "global_position <= (SELECT COALESCE(MAX(global_position), 0) FROM events \
+                 WHERE recorded_at IS NULL OR recorded_at <= ?)"
+            }
+        };
+        let stream = if stream_id.is_some() {
+            " AND event_stream_id = ?"
+        } else {
+            ""
+        };
+        let sql = format!(
+            "SELECT {RAW_EVENT_COLUMNS} FROM events WHERE {bound}{stream} ORDER BY global_position"
+        );
+
+        let mut query = sqlx::query(&sql);
+        query = match &self.cutoff {
+            Cutoff::GlobalPosition(position) => query.bind(*position),
+            // The format `recorded_at` is stored in, so that text order is
+            // time order.
+            Cutoff::RecordedAt(instant) => {
+                query.bind(instant.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+            }
+        };
+        if let Some(stream_id) = stream_id {
+            query = query.bind(stream_id);
+        }
+        let rows = query.fetch_all(self.pool.as_ref()).await?;
+        rows.iter().map(map_raw_event).collect()
+    }
+}
+
+#[async_trait]
+impl<Scope, T> Getter<T> for HistoricalRepository<Scope>
+where
+    Scope: Send + Sync,
+    T: Aggregate + Send + Sync,
+    T::Id: Send + Sync,
+    T::Event: DeserializeOwned,
+    T::Error: std::error::Error + Send + Sync + 'static,
+{
+    async fn get(&self, id: &T::Id) -> Result<Root<T>, GetError> {
+        let events = self
+            .events(Some(&id.to_string()))
+            .await
+            .map_err(|e| GetError::Internal(e.into()))?;
+
+        let mut state: Option<T> = None;
+        let mut version = 0;
+        for event in events {
+            let payload: T::Event = serde_json::from_slice(&event.payload_bytes)
+                .map_err(|e| GetError::Internal(e.into()))?;
+            state = Some(T::apply(state, payload).map_err(|e| GetError::Internal(e.into()))?);
+            version = event.version;
+        }
+
+        state
+            .map(|state| Root::rehydrate_from_state(version, state))
+            .ok_or(GetError::NotFound)
+    }
+}
+
+/// A throw-away tenant database holding projections rebuilt up to a cutoff.
+///
+/// Backed by a shared-cache in-memory SQLite database that disappears once
+/// the last pool connection is closed.
+pub struct ScratchTenantDatabase {
+    pool: ConnectedTenantPool,
+}
+
+impl ScratchTenantDatabase {
+    #[must_use]
+    pub fn pool(&self) -> ConnectedTenantPool {
+        self.pool.clone()
+    }
+}
+
+/// Replay the events of `source` up to `cutoff` into a fresh in-memory
+/// tenant database.
+///
+/// The scratch database is SQLite and migrated with the SQLite migrations,
+/// so only SQLite workspaces can be rebuilt, and only with the `sqlite`
+/// feature.
+///
+/// # Errors
+///
+/// Returns [`InfrastructureError::UnsupportedDatabaseType`] if `source` is
+/// not a SQLite database or the `sqlite` feature is off, and an error if the
+/// scratch database cannot be created or migrated, or if a projector rejects
+/// one of the replayed events.
+pub async fn rebuild_tenant(
+    source: &ConnectedTenantPool,
+    cutoff: Cutoff,
+) -> Result<ScratchTenantDatabase, crate::Error> {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    if !cfg!(feature = "sqlite") || source.get_database_type() != &DatabaseType::Sqlite {
+        return Err(InfrastructureError::UnsupportedDatabaseType(format!(
+            "{} (point-in-time views need a SQLite workspace database)",
+            source.get_database_type()
+        ))
+        .into());
+    }
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let uri = Url::parse(&format!(
+        "sqlite:///file:loom_as_of_{}_{id}?mode=memory&cache=shared",
+        std::process::id()
+    ))?;
+    let pool = Pool::<ScopeTenant, _>::connect(&uri).await?;
+    pool.migrate_database().await?;
+
+    let mut projector = TenantProjector::new(pool.clone());
+    for event in HistoricalRepository::new(source.clone(), cutoff)
+        .events(None)
+        .await?
+    {
+        projector.handle(event).await?;
+    }
+
+    Ok(ScratchTenantDatabase { pool })
+}
@@ -0,0 +1,77 @@
+use loom_infrastructure_impl::admin::api_token::repositories::ApiTokenRepository;
+use loom_tests::TestFixture;
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+pub mod tests {
+    use super::*;
+
+    /// A token is found by its hash until it is revoked; only its owner can
+    /// revoke it.
+    #[tokio::test]
+    async fn test_find_and_revoke() {
+        let db = TestFixture::setup().await;
+        let repo = ApiTokenRepository::new(db.admin.clone());
+
+        repo.insert("token-1", "user-1", "workspace-1", "laptop", "hash-1")
+            .await
+            .expect("token must be stored");
+
+        let found = repo
+            .find_active("hash-1")
+            .await
+            .expect("lookup must succeed")
+            .expect("token must be active");
+        assert_eq!(found.user_id, "user-1");
+        assert_eq!(found.workspace_id, "workspace-1");
+        assert!(found.last_used_at.is_none());
+
+        repo.touch("token-1").await.expect("touch must succeed");
+        let listed = repo
+            .for_user("user-1", "workspace-1")
+            .await
+            .expect("list must succeed");
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].last_used_at.is_some());
+
+        assert!(
+            !repo
+                .revoke("token-1", "user-2")
+                .await
+                .expect("revoke must succeed")
+        );
+        assert!(
+            repo.revoke("token-1", "user-1")
+                .await
+                .expect("revoke must succeed")
+        );
+        assert!(
+            repo.find_active("hash-1")
+                .await
+                .expect("lookup must succeed")
+                .is_none()
+        );
+        assert!(
+            !repo
+                .revoke("token-1", "user-1")
+                .await
+                .expect("revoke must succeed")
+        );
+    }
+
+    /// Two tokens can never share a hash.
+    #[tokio::test]
+    async fn test_hash_is_unique() {
+        let db = TestFixture::setup().await;
+        let repo = ApiTokenRepository::new(db.admin.clone());
+
+        repo.insert("token-1", "user-1", "workspace-1", "laptop", "hash-1")
+            .await
+            .expect("token must be stored");
+        assert!(
+            repo.insert("token-2", "user-1", "workspace-1", "desktop", "hash-1")
+                .await
+                .is_err()
+        );
+    }
+}
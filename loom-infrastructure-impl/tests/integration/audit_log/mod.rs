@@ -0,0 +1,105 @@
+use eventually_projection::{Projector, RawEvent};
+use loom_core::tenant::customer::CustomerEvent;
+use loom_infrastructure_impl::audit_log::{
+    projectors::AuditLogProjector,
+    repositories::{AuditLogFilter, AuditLogRepository},
+};
+use loom_tests::TestFixture;
+
+// ── helpers ───────────────────────────────────────────────────────────────────
+
+const CUSTOMER_ID: &str = "019d0ce8-facb-7c90-b9d7-287ae4f17c91";
+
+fn raw_event(global_position: i64, event: &CustomerEvent, actor: Option<&str>) -> RawEvent {
+    use eventually::message::Message;
+
+    let metadata = actor.map_or(
+        serde_json::Value::Null,
+        |a| serde_json::json!({ "actor": a }),
+    );
+    RawEvent {
+        stream_id: CUSTOMER_ID.to_string(),
+        version: 1,
+        global_position,
+        event_type: event.name().to_string(),
+        payload_bytes: serde_json::to_vec(event).expect("serialization must succeed"),
+        metadata,
+        schema_version: 1,
+    }
+}
+
+fn updated(name: &str) -> CustomerEvent {
+    CustomerEvent::Updated {
+        name: name.to_string(),
+        comment: None,
+        currency: "EUR".to_string(),
+        timezone: "Europe/Berlin".to_string(),
+        country: None,
+        visible: true,
+    }
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+pub mod tests {
+    use super::*;
+
+    /// The second update must be described relative to the first one, and the
+    /// actor from the envelope metadata must be recorded.
+    #[tokio::test]
+    async fn test_projector_records_diff_and_actor() {
+        let db = TestFixture::setup().await;
+        let mut projector = AuditLogProjector::new(db.tenant.clone());
+
+        projector
+            .handle(raw_event(1, &updated("Acme"), None))
+            .await
+            .expect("first update must be audited");
+        projector
+            .handle(raw_event(2, &updated("Acme Corp"), Some("user-1")))
+            .await
+            .expect("second update must be audited");
+
+        let rows = AuditLogRepository::new(db.tenant)
+            .find(&AuditLogFilter {
+                entity_id: Some(CUSTOMER_ID.to_string()),
+                limit: 10,
+                ..AuditLogFilter::default()
+            })
+            .await
+            .expect("query must succeed");
+
+        assert_eq!(rows.len(), 2);
+        let latest = &rows[0];
+        assert_eq!(latest.aggregate_type, "customer");
+        assert_eq!(latest.actor.as_deref(), Some("user-1"));
+        assert_eq!(
+            latest.changes,
+            vec!["name changed from \"Acme\" to \"Acme Corp\"".to_string()]
+        );
+    }
+
+    /// Handling the same event twice (e.g. after a checkpoint reset) must not
+    /// duplicate the audit entry.
+    #[tokio::test]
+    async fn test_projector_is_idempotent_per_global_position() {
+        let db = TestFixture::setup().await;
+        let mut projector = AuditLogProjector::new(db.tenant.clone());
+
+        for _ in 0..2 {
+            projector
+                .handle(raw_event(1, &updated("Acme"), None))
+                .await
+                .expect("projector must handle the event");
+        }
+
+        let rows = AuditLogRepository::new(db.tenant)
+            .find(&AuditLogFilter {
+                limit: 10,
+                ..AuditLogFilter::default()
+            })
+            .await
+            .expect("query must succeed");
+        assert_eq!(rows.len(), 1);
+    }
+}
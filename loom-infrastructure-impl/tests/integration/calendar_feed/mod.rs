@@ -0,0 +1,65 @@
+use loom_infrastructure_impl::admin::calendar_feed::repositories::CalendarFeedRepository;
+use loom_tests::TestFixture;
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+pub mod tests {
+    use super::*;
+
+    /// Replacing a feed invalidates the old hash; a user has one feed per
+    /// workspace.
+    #[tokio::test]
+    async fn test_replace_and_delete() {
+        let db = TestFixture::setup().await;
+        let repo = CalendarFeedRepository::new(db.admin.clone());
+
+        repo.replace("user-1", "workspace-1", "hash-1")
+            .await
+            .expect("feed must be stored");
+        repo.replace("user-1", "workspace-2", "hash-2")
+            .await
+            .expect("feed must be stored");
+
+        let found = repo
+            .find("hash-1")
+            .await
+            .expect("lookup must succeed")
+            .expect("feed must exist");
+        assert_eq!(found.user_id, "user-1");
+        assert_eq!(found.workspace_id, "workspace-1");
+
+        repo.replace("user-1", "workspace-1", "hash-3")
+            .await
+            .expect("feed must be replaced");
+        assert!(
+            repo.find("hash-1")
+                .await
+                .expect("lookup must succeed")
+                .is_none()
+        );
+        assert!(
+            repo.find("hash-3")
+                .await
+                .expect("lookup must succeed")
+                .is_some()
+        );
+
+        assert!(
+            repo.delete("user-1", "workspace-1")
+                .await
+                .expect("delete must succeed")
+        );
+        assert!(
+            repo.for_user("user-1", "workspace-1")
+                .await
+                .expect("lookup must succeed")
+                .is_none()
+        );
+        assert!(
+            repo.for_user("user-1", "workspace-2")
+                .await
+                .expect("lookup must succeed")
+                .is_some()
+        );
+    }
+}
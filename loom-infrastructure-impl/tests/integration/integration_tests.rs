@@ -1,2 +1,11 @@
+mod api_token;
+mod audit_log;
+mod calendar_feed;
 mod database;
+mod projection_health;
+mod report_schedule;
+mod search;
+mod snapshot;
+mod sync;
+mod time_travel;
 mod user;
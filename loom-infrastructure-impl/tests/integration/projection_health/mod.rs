@@ -0,0 +1,144 @@
+use async_trait::async_trait;
+use eventually_projection::{Projector, RawEvent};
+use loom_infrastructure_impl::projection_health::{
+    projectors::MonitoredProjector,
+    repositories::{DeadLetterRepository, DeadLetterStatus, ProjectionStatusRepository},
+};
+use loom_tests::TestFixture;
+
+// ── helpers ───────────────────────────────────────────────────────────────────
+
+/// Fails every event while `failing` is set.
+struct FlakyProjector {
+    failing: bool,
+}
+
+#[async_trait]
+impl Projector for FlakyProjector {
+    type Error = loom_infrastructure_impl::Error;
+
+    async fn handle(&mut self, _event: RawEvent) -> Result<(), Self::Error> {
+        if self.failing {
+            Err(loom_infrastructure_impl::Error::InvalidCredentials)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn raw_event(global_position: i64) -> RawEvent {
+    RawEvent {
+        stream_id: "stream-1".to_string(),
+        version: 1,
+        global_position,
+        event_type: "CustomerCreated".to_string(),
+        payload_bytes: b"{}".to_vec(),
+        metadata: serde_json::Value::Null,
+        schema_version: 1,
+    }
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+pub mod tests {
+    use super::*;
+
+    /// A failing event is recorded with its error and retry count while the
+    /// checkpoint stays put; the next success clears the error.
+    #[tokio::test]
+    async fn test_status_tracks_failures_and_recovery() {
+        let db = TestFixture::setup().await;
+        let mut projector = MonitoredProjector::new(
+            "test_runner",
+            db.tenant.clone(),
+            FlakyProjector { failing: false },
+        );
+        let repo = ProjectionStatusRepository::new(db.tenant.clone());
+
+        projector
+            .handle(raw_event(1))
+            .await
+            .expect("first event must be projected");
+
+        projector.inner_mut().failing = true;
+        for _ in 0..3 {
+            assert!(projector.handle(raw_event(2)).await.is_err());
+        }
+
+        let status = repo.all().await.expect("status must load");
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].runner, "test_runner");
+        assert_eq!(status[0].position, 1);
+        assert_eq!(status[0].error_position, Some(2));
+        assert_eq!(status[0].retry_count, 2);
+        assert!(status[0].is_failing());
+
+        projector.inner_mut().failing = false;
+        projector
+            .handle(raw_event(2))
+            .await
+            .expect("retried event must be projected");
+
+        let status = repo.all().await.expect("status must load");
+        assert_eq!(status[0].position, 2);
+        assert_eq!(status[0].retry_count, 0);
+        assert!(!status[0].is_failing());
+        assert_eq!(
+            repo.position("test_runner").await.expect("position must load"),
+            Some(2)
+        );
+        assert_eq!(
+            repo.position("unknown_runner").await.expect("position must load"),
+            None
+        );
+    }
+
+    /// Once the retry limit is reached the event is parked and the runner
+    /// continues; the parked event can be rebuilt and resolved.
+    #[tokio::test]
+    async fn test_poison_event_is_parked_after_max_retries() {
+        let db = TestFixture::setup().await;
+        let mut projector = MonitoredProjector::new(
+            "test_runner",
+            db.tenant.clone(),
+            FlakyProjector { failing: true },
+        )
+        .with_max_retries(2);
+
+        assert!(projector.handle(raw_event(1)).await.is_err());
+        assert!(projector.handle(raw_event(1)).await.is_err());
+        projector
+            .handle(raw_event(1))
+            .await
+            .expect("third failure must park the event");
+
+        let status = ProjectionStatusRepository::new(db.tenant.clone())
+            .all()
+            .await
+            .expect("status must load");
+        assert_eq!(status[0].position, 1);
+        assert_eq!(status[0].parked, 1);
+        assert!(!status[0].is_failing());
+
+        let dead_letters = DeadLetterRepository::new(db.tenant.clone());
+        let parked = dead_letters
+            .list(Some(DeadLetterStatus::Parked))
+            .await
+            .expect("dead letters must load");
+        assert_eq!(parked.len(), 1);
+        assert_eq!(parked[0].global_position, 1);
+        assert_eq!(parked[0].retry_count, 2);
+        let event = parked[0].to_raw_event().expect("event must be rebuilt");
+        assert_eq!(event.payload_bytes, b"{}".to_vec());
+
+        dead_letters
+            .resolve(parked[0].id, DeadLetterStatus::Skipped)
+            .await
+            .expect("resolve must succeed");
+        let parked = dead_letters
+            .list(Some(DeadLetterStatus::Parked))
+            .await
+            .expect("dead letters must load");
+        assert!(parked.is_empty());
+    }
+}
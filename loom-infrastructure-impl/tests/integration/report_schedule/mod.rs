@@ -0,0 +1,48 @@
+use loom_infrastructure_impl::tenant::report_schedule::repositories::ReportRunRepository;
+use loom_tests::TestFixture;
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+pub mod tests {
+    use super::*;
+
+    /// A schedule is counted from when it was first seen; every run moves
+    /// that on and keeps the last successful send.
+    #[tokio::test]
+    async fn test_runs_are_counted_from_the_first_sighting() {
+        let db = TestFixture::setup().await;
+        let repo = ReportRunRepository::new(db.tenant.clone());
+
+        repo.begin("report-1", "2026-10-19T07:00:00Z")
+            .await
+            .expect("run must be stored");
+        repo.begin("report-1", "2026-10-19T09:00:00Z")
+            .await
+            .expect("beginning again must succeed");
+        let runs = repo.all().await.expect("runs must load");
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].last_due_at, "2026-10-19T07:00:00Z");
+        assert!(runs[0].last_sent_at.is_none());
+
+        repo.record("report-1", "2026-10-19T08:00:00Z", None)
+            .await
+            .expect("run must be recorded");
+        repo.record(
+            "report-1",
+            "2026-10-26T08:00:00Z",
+            Some("connection refused"),
+        )
+        .await
+        .expect("failed run must be recorded");
+        let run = repo
+            .all()
+            .await
+            .expect("runs must load")
+            .into_iter()
+            .next()
+            .expect("run must exist");
+        assert_eq!(run.last_due_at, "2026-10-26T08:00:00Z");
+        assert_eq!(run.last_error.as_deref(), Some("connection refused"));
+        assert!(run.last_sent_at.is_some(), "the earlier send is kept");
+    }
+}
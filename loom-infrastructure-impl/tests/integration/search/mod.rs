@@ -0,0 +1,181 @@
+use eventually::message::Message;
+use eventually_projection::{Projector, RawEvent};
+use loom_core::{
+    shared::AggregateId,
+    tenant::{customer::CustomerEvent, timesheet::TimesheetEvent},
+};
+use loom_infrastructure_impl::tenant::{
+    projectors::TenantProjector,
+    search::repositories::{EntryScope, SearchRepository},
+};
+use loom_tests::TestFixture;
+
+// ── helpers ───────────────────────────────────────────────────────────────────
+
+const CUSTOMER_ID: &str = "019d0ce8-facb-7c90-b9d7-287ae4f17ca1";
+const OWN_ENTRY_ID: &str = "019d0ce8-facb-7c90-b9d7-287ae4f17ca2";
+const OTHER_ENTRY_ID: &str = "019d0ce8-facb-7c90-b9d7-287ae4f17ca3";
+const USER_ID: &str = "019d0ce8-facb-7c90-b9d7-287ae4f17ca4";
+const OTHER_USER_ID: &str = "019d0ce8-facb-7c90-b9d7-287ae4f17ca5";
+
+fn id(s: &str) -> AggregateId {
+    s.parse().expect("ID must be a UUID")
+}
+
+fn raw_event<E: Message + serde::Serialize>(
+    stream_id: &str,
+    global_position: i64,
+    event: &E,
+) -> RawEvent {
+    RawEvent {
+        stream_id: stream_id.to_string(),
+        version: 1,
+        global_position,
+        event_type: event.name().to_string(),
+        payload_bytes: serde_json::to_vec(event).expect("serialization must succeed"),
+        metadata: serde_json::Value::Null,
+        schema_version: 1,
+    }
+}
+
+fn customer_events() -> [CustomerEvent; 2] {
+    [
+        CustomerEvent::Created {
+            id: id(CUSTOMER_ID),
+            name: "Acme".to_string(),
+            currency: "EUR".to_string(),
+            timezone: "Europe/Berlin".to_string(),
+        },
+        CustomerEvent::Updated {
+            name: "Acme Corp".to_string(),
+            comment: Some("Hosting and database support".to_string()),
+            currency: "EUR".to_string(),
+            timezone: "Europe/Berlin".to_string(),
+            country: None,
+            visible: true,
+        },
+    ]
+}
+
+fn entry_events(entry_id: &str, user_id: &str) -> [TimesheetEvent; 2] {
+    [
+        TimesheetEvent::Started {
+            id: id(entry_id),
+            user_id: id(user_id),
+            project_id: None,
+            activity_id: None,
+            start_time: "2026-05-12T09:00:00Z".to_string(),
+            timezone: "UTC".to_string(),
+            billable: true,
+        },
+        TimesheetEvent::Updated {
+            description: Some("Database migration to Postgres".to_string()),
+            billable: true,
+        },
+    ]
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+pub mod tests {
+    use super::*;
+
+    /// Entities are found by prefixes of any word of their name or comment,
+    /// follow their updates and drop out of the index while archived and
+    /// once deleted.
+    #[tokio::test]
+    async fn test_entities_are_indexed_as_they_change() {
+        let db = TestFixture::setup().await;
+        let mut projector = TenantProjector::new(db.tenant.clone());
+        let repo = SearchRepository::new(db.tenant.clone());
+
+        for (i, event) in customer_events().iter().enumerate() {
+            projector
+                .handle(raw_event(CUSTOMER_ID, i64::try_from(i).unwrap() + 1, event))
+                .await
+                .expect("customer event must be projected");
+        }
+
+        let hits = repo
+            .search("datab acme", EntryScope::All, 10)
+            .await
+            .expect("search must succeed");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entity_type, "customer");
+        assert_eq!(hits[0].entity_id, CUSTOMER_ID);
+        assert_eq!(hits[0].title, "Acme Corp");
+
+        let hits = repo
+            .search("\"acme\" OR (*) NEAR -", EntryScope::All, 10)
+            .await
+            .expect("query syntax in the input must not break the search");
+        assert_eq!(hits.len(), 1);
+
+        projector
+            .handle(raw_event(CUSTOMER_ID, 3, &CustomerEvent::Archived))
+            .await
+            .expect("archiving must be projected");
+        let hits = repo
+            .search("acme", EntryScope::All, 10)
+            .await
+            .expect("search must succeed");
+        assert!(hits.is_empty(), "archived entities are not found");
+        projector
+            .handle(raw_event(CUSTOMER_ID, 4, &CustomerEvent::Restored))
+            .await
+            .expect("restoring must be projected");
+        let hits = repo
+            .search("acme", EntryScope::All, 10)
+            .await
+            .expect("search must succeed");
+        assert_eq!(hits.len(), 1, "restored entities are found again");
+
+        projector
+            .handle(raw_event(CUSTOMER_ID, 5, &CustomerEvent::Deleted))
+            .await
+            .expect("deletion must be projected");
+        let hits = repo
+            .search("acme", EntryScope::All, 10)
+            .await
+            .expect("search must succeed");
+        assert!(hits.is_empty());
+    }
+
+    /// Members only find their own time entries.
+    #[tokio::test]
+    async fn test_entries_of_other_users_are_filtered() {
+        let db = TestFixture::setup().await;
+        let mut projector = TenantProjector::new(db.tenant.clone());
+        let repo = SearchRepository::new(db.tenant.clone());
+
+        let mut position = 0;
+        for (entry_id, user_id) in [(OWN_ENTRY_ID, USER_ID), (OTHER_ENTRY_ID, OTHER_USER_ID)] {
+            for event in entry_events(entry_id, user_id) {
+                position += 1;
+                projector
+                    .handle(raw_event(entry_id, position, &event))
+                    .await
+                    .expect("timesheet event must be projected");
+            }
+        }
+
+        let own = repo
+            .search("migration", EntryScope::Own(USER_ID), 10)
+            .await
+            .expect("search must succeed");
+        assert_eq!(own.len(), 1);
+        assert_eq!(own[0].entity_id, OWN_ENTRY_ID);
+        assert!(
+            own[0]
+                .start_time
+                .as_deref()
+                .is_some_and(|t| t.starts_with("2026-05-12"))
+        );
+
+        let all = repo
+            .search("migration", EntryScope::All, 10)
+            .await
+            .expect("search must succeed");
+        assert_eq!(all.len(), 2);
+    }
+}
@@ -0,0 +1,97 @@
+use eventually::aggregate::repository::{Getter, Saver};
+use eventually_any::snapshot::Repository;
+use loom_core::admin::user::{User, UserEvent, UserId};
+use loom_infrastructure_impl::snapshot::{SnapshotStore, load_metrics};
+use loom_tests::TestFixture;
+use sqlx::Row;
+
+fn test_id() -> UserId {
+    "019d0ce8-facb-7c90-b9d7-287ae4f17c95"
+        .parse()
+        .expect("valid UUID")
+}
+
+type UserRepo = Repository<User, eventually::serde::Json<User>, eventually::serde::Json<UserEvent>>;
+
+async fn snapshot_count(db: &TestFixture) -> i64 {
+    sqlx::query("SELECT COUNT(*) AS n FROM snapshots WHERE aggregate_type = 'user'")
+        .fetch_one(db.admin.as_ref())
+        .await
+        .expect("query must succeed")
+        .try_get("n")
+        .expect("count")
+}
+
+pub mod tests {
+    use super::*;
+
+    /// A load starts from the snapshot and replays only the later events; a
+    /// snapshot whose JSON shape no longer matches the aggregate is dropped and
+    /// the aggregate is replayed in full.
+    #[tokio::test]
+    async fn test_load_from_snapshot_and_invalidate_on_shape_change() {
+        let db = TestFixture::setup().await;
+        let repo: UserRepo = Repository::new(
+            db.admin.as_ref().clone(),
+            eventually::serde::Json::default(),
+            eventually::serde::Json::default(),
+        )
+        .await
+        .expect("repository must be created");
+        let store = SnapshotStore::new(db.admin.clone());
+        let id = test_id();
+
+        let mut root = eventually::aggregate::Root::<User>::record_new(
+            UserEvent::Created {
+                id: id.clone(),
+                name: "Alice".to_string(),
+                email: "alice@example.com".to_string(),
+                password: String::new(),
+            }
+            .into(),
+        )
+        .expect("Created event on a new aggregate is always valid");
+        repo.save(&mut root).await.expect("save must succeed");
+        store.take(&root).await.expect("snapshot must be taken");
+
+        root.record_that(
+            UserEvent::SettingsUpdated {
+                timezone: "Europe/Berlin".to_string(),
+                date_format: "%d.%m.%Y".to_string(),
+                language: "de".to_string(),
+            }
+            .into(),
+        )
+        .expect("settings update is valid");
+        repo.save(&mut root).await.expect("save must succeed");
+
+        let loaded = store.get(&repo, &id).await.expect("get must succeed");
+        assert_eq!(loaded.version(), 2);
+        assert_eq!(loaded.name(), "Alice");
+        let metrics = load_metrics()
+            .into_iter()
+            .find(|m| m.aggregate_type == "user")
+            .expect("user loads are recorded");
+        assert!(metrics.snapshot_hits >= 1);
+
+        // Simulate a snapshot taken before the aggregate lost a field.
+        let state: Vec<u8> =
+            sqlx::query("SELECT state FROM snapshots WHERE aggregate_type = 'user'")
+                .fetch_one(db.admin.as_ref())
+                .await
+                .expect("query must succeed")
+                .try_get("state")
+                .expect("state");
+        let mut json: serde_json::Value = serde_json::from_slice(&state).expect("snapshot is JSON");
+        json["removed_field"] = serde_json::Value::Bool(true);
+        sqlx::query("UPDATE snapshots SET state = ? WHERE aggregate_type = 'user'")
+            .bind(serde_json::to_vec(&json).expect("serializable"))
+            .execute(db.admin.as_ref())
+            .await
+            .expect("update must succeed");
+
+        let loaded = store.get(&repo, &id).await.expect("get must succeed");
+        assert_eq!(loaded.version(), 2);
+        assert_eq!(snapshot_count(&db).await, 0);
+    }
+}
@@ -0,0 +1,144 @@
+use eventually_projection::RawEvent;
+use loom_infrastructure_impl::sync::repositories::SyncRepository;
+use loom_tests::TestFixture;
+use sqlx::Row;
+
+const STREAM_ID: &str = "019d0ce8-facb-7c90-b9d7-287ae4f17c93";
+
+fn raw_event(version: u32, event_type: &str) -> RawEvent {
+    RawEvent {
+        stream_id: STREAM_ID.to_string(),
+        version: version.into(),
+        global_position: 0,
+        event_type: event_type.to_string(),
+        payload_bytes: b"{}".to_vec(),
+        metadata: serde_json::json!({ "actor": "someone" }),
+        schema_version: 1,
+    }
+}
+
+/// Record an event the way a local command does: without touching
+/// `sync_streams`.
+async fn record_locally(db: &TestFixture, version: i64, event_type: &str) {
+    sqlx::query(
+        "INSERT INTO event_streams (event_stream_id, version) VALUES (?, ?) \
+         ON CONFLICT (event_stream_id) DO UPDATE SET version = excluded.version",
+    )
+    .bind(STREAM_ID)
+    .bind(version)
+    .execute(db.tenant.as_ref())
+    .await
+    .expect("stream upsert must succeed");
+    sqlx::query(
+        "INSERT INTO events (event_stream_id, type, version, event, metadata, schema_version) \
+         VALUES (?, ?, ?, ?, '{}', 1)",
+    )
+    .bind(STREAM_ID)
+    .bind(event_type)
+    .bind(version)
+    .bind(b"{}".to_vec())
+    .execute(db.tenant.as_ref())
+    .await
+    .expect("event insert must succeed");
+}
+
+async fn stream_version(db: &TestFixture) -> Option<i64> {
+    sqlx::query("SELECT version FROM event_streams WHERE event_stream_id = ?")
+        .bind(STREAM_ID)
+        .fetch_optional(db.tenant.as_ref())
+        .await
+        .expect("query must succeed")
+        .map(|row| row.try_get("version").expect("version"))
+}
+
+pub mod tests {
+    use super::*;
+
+    /// Pulled events count as synced; later local events are pending until
+    /// discarded, which rolls the stream back to the synced version.
+    #[tokio::test]
+    async fn test_pending_events_are_discarded_down_to_the_synced_version() {
+        let db = TestFixture::setup().await;
+        let repo = SyncRepository::new(db.tenant.clone());
+
+        repo.append(&raw_event(1, "TimesheetStarted"))
+            .await
+            .expect("append must succeed");
+        assert_eq!(repo.synced_version(STREAM_ID).await.expect("synced"), 1);
+        assert!(repo.pending().await.expect("pending").is_empty());
+
+        let pulled = repo.stream_events(STREAM_ID).await.expect("events");
+        assert_eq!(pulled.len(), 1);
+        assert_eq!(
+            pulled[0].metadata,
+            serde_json::json!({ "actor": "someone" })
+        );
+
+        record_locally(&db, 2, "TimesheetStopped").await;
+        let pending = repo.pending().await.expect("pending");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].event_type, "TimesheetStopped");
+        assert_eq!(repo.pending_count().await.expect("count"), 1);
+
+        let discarded = repo.discard_pending(STREAM_ID).await.expect("discard");
+        assert_eq!(discarded.len(), 1);
+        assert_eq!(discarded[0].version, 2);
+        assert!(repo.pending().await.expect("pending").is_empty());
+        assert_eq!(
+            repo.stream_events(STREAM_ID).await.expect("events").len(),
+            1
+        );
+        assert_eq!(stream_version(&db).await, Some(1));
+    }
+
+    /// A stream that never reached the server disappears entirely.
+    #[tokio::test]
+    async fn test_discarding_an_unsynced_stream_removes_it() {
+        let db = TestFixture::setup().await;
+        let repo = SyncRepository::new(db.tenant.clone());
+
+        record_locally(&db, 1, "TimesheetStarted").await;
+        assert_eq!(
+            repo.discard_pending(STREAM_ID)
+                .await
+                .expect("discard")
+                .len(),
+            1
+        );
+        assert_eq!(stream_version(&db).await, None);
+        assert!(repo.first_event(STREAM_ID).await.expect("first").is_none());
+    }
+
+    /// Conflicts are listed until dismissed; the sync state is a plain
+    /// key-value store.
+    #[tokio::test]
+    async fn test_conflicts_and_state() {
+        let db = TestFixture::setup().await;
+        let repo = SyncRepository::new(db.tenant.clone());
+
+        repo.record_conflict(STREAM_ID, 3, "[]", "Changed on the server")
+            .await
+            .expect("record must succeed");
+        let conflicts = repo.conflicts().await.expect("conflicts");
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].server_version, 3);
+        repo.dismiss_conflict(conflicts[0].id)
+            .await
+            .expect("dismiss must succeed");
+        assert!(repo.conflicts().await.expect("conflicts").is_empty());
+
+        assert_eq!(repo.state("server").await.expect("state"), None);
+        repo.set_state("server", "https://a.example")
+            .await
+            .expect("set");
+        repo.set_state("server", "https://b.example")
+            .await
+            .expect("set");
+        assert_eq!(
+            repo.state("server").await.expect("state").as_deref(),
+            Some("https://b.example")
+        );
+        repo.clear_state("server").await.expect("clear");
+        assert_eq!(repo.state("server").await.expect("state"), None);
+    }
+}
@@ -0,0 +1,135 @@
+use chrono::{TimeZone, Utc};
+use eventually::aggregate::repository::{GetError, Getter, Saver};
+use eventually_any::snapshot::Repository;
+use loom_core::admin::user::{User, UserEvent, UserId};
+use loom_infrastructure_impl::time_travel::{Cutoff, HistoricalRepository};
+use loom_tests::TestFixture;
+use sqlx::Row;
+
+fn test_id() -> UserId {
+    "019d0ce8-facb-7c90-b9d7-287ae4f17c93"
+        .parse()
+        .expect("valid UUID")
+}
+
+type UserRepository =
+    Repository<User, eventually::serde::Json<User>, eventually::serde::Json<UserEvent>>;
+
+async fn user_repository(db: &TestFixture) -> UserRepository {
+    Repository::new(
+        db.admin.as_ref().clone(),
+        eventually::serde::Json::default(),
+        eventually::serde::Json::default(),
+    )
+    .await
+    .expect("repository must be created")
+}
+
+/// Record a user and then change their settings, returning the global
+/// positions of both events.
+async fn created_and_updated(repo: &UserRepository, db: &TestFixture) -> (i64, i64) {
+    let last_position = || async {
+        sqlx::query("SELECT MAX(global_position) AS p FROM events")
+            .fetch_one(db.admin.as_ref())
+            .await
+            .expect("query must succeed")
+            .try_get::<i64, _>("p")
+            .expect("global position")
+    };
+
+    let mut root = eventually::aggregate::Root::<User>::record_new(
+        UserEvent::Created {
+            id: test_id(),
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+            password: String::new(),
+        }
+        .into(),
+    )
+    .expect("Created event on a new aggregate is always valid");
+    repo.save(&mut root).await.expect("save must succeed");
+    let created = last_position().await;
+
+    root.record_that(
+        UserEvent::SettingsUpdated {
+            timezone: "Europe/Berlin".to_string(),
+            date_format: "%d.%m.%Y".to_string(),
+            language: "de".to_string(),
+        }
+        .into(),
+    )
+    .expect("settings update is valid");
+    repo.save(&mut root).await.expect("save must succeed");
+    (created, last_position().await)
+}
+
+async fn stamp(db: &TestFixture, position: i64, recorded_at: Option<&str>) {
+    sqlx::query("UPDATE events SET recorded_at = ? WHERE global_position = ?")
+        .bind(recorded_at)
+        .bind(position)
+        .execute(db.admin.as_ref())
+        .await
+        .expect("update must succeed");
+}
+
+pub mod tests {
+    use super::*;
+
+    /// A cutoff between two events yields the aggregate as it was after the
+    /// first one; a cutoff before its creation reports it as not found.
+    #[tokio::test]
+    async fn test_get_truncated_at_global_position() {
+        let db = TestFixture::setup().await;
+        let repo = user_repository(&db).await;
+        let id = test_id();
+        let (created_at, _) = created_and_updated(&repo, &db).await;
+
+        let past = HistoricalRepository::new(db.admin.clone(), Cutoff::GlobalPosition(created_at));
+        let loaded: eventually::aggregate::Root<User> =
+            past.get(&id).await.expect("get must succeed");
+        assert_eq!(loaded.version(), 1);
+        assert_eq!(loaded.name(), "Alice");
+
+        let now = HistoricalRepository::new(db.admin.clone(), Cutoff::GlobalPosition(i64::MAX));
+        let loaded: eventually::aggregate::Root<User> =
+            now.get(&id).await.expect("get must succeed");
+        assert_eq!(loaded.version(), 2);
+
+        let before = HistoricalRepository::new(db.admin.clone(), Cutoff::GlobalPosition(0));
+        let result: Result<eventually::aggregate::Root<User>, GetError> = before.get(&id).await;
+        assert!(matches!(result, Err(GetError::NotFound)));
+    }
+
+    /// An instant cutoff includes the events recorded up to it.  Events
+    /// without a recording time count as older than every stamped one.
+    #[tokio::test]
+    async fn test_get_truncated_at_recorded_at() {
+        let db = TestFixture::setup().await;
+        let repo = user_repository(&db).await;
+        let id = test_id();
+        let (created, updated) = created_and_updated(&repo, &db).await;
+        stamp(&db, created, Some("2026-01-01T09:00:00Z")).await;
+        stamp(&db, updated, Some("2026-02-01T09:00:00Z")).await;
+
+        let at = |month, hour| {
+            Cutoff::RecordedAt(Utc.with_ymd_and_hms(2026, month, 1, hour, 0, 0).unwrap())
+        };
+        let version = |cutoff| {
+            let repo = HistoricalRepository::new(db.admin.clone(), cutoff);
+            let id = id.clone();
+            async move {
+                let loaded: Result<eventually::aggregate::Root<User>, GetError> =
+                    repo.get(&id).await;
+                loaded.map(|root| root.version())
+            }
+        };
+
+        assert!(matches!(version(at(1, 8)).await, Err(GetError::NotFound)));
+        assert_eq!(version(at(1, 9)).await.expect("get must succeed"), 1);
+        assert_eq!(version(at(2, 8)).await.expect("get must succeed"), 1);
+        assert_eq!(version(at(2, 9)).await.expect("get must succeed"), 2);
+
+        stamp(&db, created, None).await;
+        assert_eq!(version(at(1, 8)).await.expect("get must succeed"), 1);
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -5,6 +7,8 @@ pub struct Database {
     base_uri: String,
     databases: Databases,
     pool: Pool,
+    #[serde(default)]
+    snapshots: Snapshots,
 }
 
 impl Database {
@@ -19,6 +23,10 @@ impl Database {
     pub const fn get_pool(&self) -> &Pool {
         &self.pool
     }
+
+    pub const fn get_snapshots(&self) -> &Snapshots {
+        &self.snapshots
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,3 +87,51 @@ impl Pool {
         self.timeout_seconds
     }
 }
+
+/// When aggregates are snapshotted.  `aggregates` overrides `default` per
+/// aggregate type name (e.g. `timesheet`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Snapshots {
+    #[serde(default)]
+    default: SnapshotPolicy,
+    #[serde(default)]
+    aggregates: HashMap<String, SnapshotPolicy>,
+}
+
+impl Snapshots {
+    pub const fn get_default(&self) -> &SnapshotPolicy {
+        &self.default
+    }
+
+    pub const fn get_aggregates(&self) -> &HashMap<String, SnapshotPolicy> {
+        &self.aggregates
+    }
+
+    #[must_use]
+    pub fn policy_for(&self, aggregate_type: &str) -> &SnapshotPolicy {
+        self.aggregates.get(aggregate_type).unwrap_or(&self.default)
+    }
+}
+
+/// A snapshot is taken once either threshold is reached since the last one.
+/// With neither set the aggregate is never snapshotted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotPolicy {
+    every_events: Option<u64>,
+    max_stream_bytes: Option<u64>,
+}
+
+impl SnapshotPolicy {
+    pub const fn get_every_events(&self) -> Option<u64> {
+        self.every_events
+    }
+
+    pub const fn get_max_stream_bytes(&self) -> Option<u64> {
+        self.max_stream_bytes
+    }
+
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.every_events.is_some() || self.max_stream_bytes.is_some()
+    }
+}
@@ -17,6 +17,24 @@ mod m20260410_000002_add_user_settings;
 mod m20260410_000003_add_workspace_settings;
 mod m20260410_000004_fix_date_format_strings;
 mod m20260410_000005_add_aggregate_type_to_event_streams;
+mod m20261019_000001_add_recorded_at_to_events;
+mod m20261019_000002_create_audit_log_projection_tables;
+mod m20261019_000003_create_projection_status_table;
+mod m20261019_000004_create_projection_dead_letters_table;
+mod m20261019_000005_add_workspace_budget_policy;
+mod m20261019_000006_add_workspace_time_entry_policy;
+mod m20261019_000007_create_workspace_period_locks_table;
+mod m20261019_000008_seed_period_lock_permissions;
+mod m20261019_000009_seed_approval_permissions;
+mod m20261019_000010_add_workspace_rounding;
+mod m20261019_000011_seed_work_contract_permission;
+mod m20261019_000012_seed_webhook_permission;
+mod m20261019_000013_create_api_tokens_table;
+mod m20261019_000014_create_calendar_feeds_table;
+mod m20261019_000015_seed_report_permission;
+mod m20261019_000016_add_workspace_auto_stop_policy;
+mod m20261019_000017_seed_deletion_permissions;
+mod m20261019_000018_seed_timesheet_delete_permission;
 
 pub struct Migrator;
 
@@ -44,6 +62,24 @@ impl MigratorTrait for Migrator {
             Box::new(m20260410_000003_add_workspace_settings::Migration),
             Box::new(m20260410_000004_fix_date_format_strings::Migration),
             Box::new(m20260410_000005_add_aggregate_type_to_event_streams::Migration),
+            Box::new(m20261019_000001_add_recorded_at_to_events::Migration),
+            Box::new(m20261019_000002_create_audit_log_projection_tables::Migration),
+            Box::new(m20261019_000003_create_projection_status_table::Migration),
+            Box::new(m20261019_000004_create_projection_dead_letters_table::Migration),
+            Box::new(m20261019_000005_add_workspace_budget_policy::Migration),
+            Box::new(m20261019_000006_add_workspace_time_entry_policy::Migration),
+            Box::new(m20261019_000007_create_workspace_period_locks_table::Migration),
+            Box::new(m20261019_000008_seed_period_lock_permissions::Migration),
+            Box::new(m20261019_000009_seed_approval_permissions::Migration),
+            Box::new(m20261019_000010_add_workspace_rounding::Migration),
+            Box::new(m20261019_000011_seed_work_contract_permission::Migration),
+            Box::new(m20261019_000012_seed_webhook_permission::Migration),
+            Box::new(m20261019_000013_create_api_tokens_table::Migration),
+            Box::new(m20261019_000014_create_calendar_feeds_table::Migration),
+            Box::new(m20261019_000015_seed_report_permission::Migration),
+            Box::new(m20261019_000016_add_workspace_auto_stop_policy::Migration),
+            Box::new(m20261019_000017_seed_deletion_permissions::Migration),
+            Box::new(m20261019_000018_seed_timesheet_delete_permission::Migration),
         ]
     }
 }
@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds a `recorded_at` timestamp to every row in `events`.
+///
+/// **Why:** The event store only tracks ordering (`version`,
+/// `global_position`), not wall-clock time.  The audit log needs to show when
+/// a change happened, so the database stamps each event on insert.
+///
+/// The value is stored as RFC 3339 text (`YYYY-MM-DDTHH:MM:SSZ`, UTC) in both
+/// backends because the `Any` driver cannot decode native timestamp types.
+/// `SQLite` does not allow non-constant defaults on `ALTER TABLE ADD COLUMN`,
+/// so a trigger fills the column instead, mirroring the `global_position`
+/// trigger.  Existing rows stay `NULL`: their time is unknown.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        match manager.get_database_backend() {
+            sea_orm::DatabaseBackend::Postgres => {
+                db.execute_unprepared(
+                    "ALTER TABLE events ADD COLUMN IF NOT EXISTS recorded_at TEXT \
+                        DEFAULT to_char(now() AT TIME ZONE 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"')",
+                )
+                .await?;
+            }
+            sea_orm::DatabaseBackend::Sqlite => {
+                db.execute_unprepared("ALTER TABLE events ADD COLUMN recorded_at TEXT")
+                    .await?;
+                db.execute_unprepared("DROP TRIGGER IF EXISTS events_assign_recorded_at")
+                    .await?;
+                db.execute_unprepared(
+                    "CREATE TRIGGER events_assign_recorded_at \
+                    AFTER INSERT ON events \
+                    FOR EACH ROW \
+                    WHEN NEW.recorded_at IS NULL \
+                    BEGIN \
+                        UPDATE events \
+                        SET recorded_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now') \
+                        WHERE rowid = NEW.rowid; \
+                    END",
+                )
+                .await?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        if manager.get_database_backend() == sea_orm::DatabaseBackend::Sqlite {
+            db.execute_unprepared("DROP TRIGGER IF EXISTS events_assign_recorded_at")
+                .await?;
+        }
+        db.execute_unprepared("ALTER TABLE events DROP COLUMN recorded_at")
+            .await?;
+
+        Ok(())
+    }
+}
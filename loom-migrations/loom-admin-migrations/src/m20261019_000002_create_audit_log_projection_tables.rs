@@ -0,0 +1,76 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_integer, pk_auto, string, string_null, text},
+};
+
+/// Creates the audit log projection.
+///
+/// `projections__audit_log` holds one row per event with the acting user and
+/// a human-readable list of changes.  `projections__audit_state` keeps the last
+/// known field values per aggregate so the projector can describe what an
+/// event changed ("description changed from … to …") without replaying the
+/// stream.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("projections__audit_log")
+                    .if_not_exists()
+                    .col(pk_auto("id"))
+                    .col(big_integer("global_position").unique_key())
+                    .col(string("aggregate_type"))
+                    .col(string("aggregate_id"))
+                    .col(string_null("related_id"))
+                    .col(string("event_type"))
+                    .col(string("action"))
+                    .col(string_null("actor"))
+                    .col(string_null("recorded_at"))
+                    // JSON array of human-readable change descriptions.
+                    .col(text("changes"))
+                    .to_owned(),
+            )
+            .await?;
+
+        for (name, col) in [
+            ("idx_audit_log_aggregate_id", "aggregate_id"),
+            ("idx_audit_log_related_id", "related_id"),
+            ("idx_audit_log_actor", "actor"),
+        ] {
+            manager
+                .create_index(
+                    Index::create()
+                        .table("projections__audit_log")
+                        .name(name)
+                        .col(col)
+                        .if_not_exists()
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("projections__audit_state")
+                    .if_not_exists()
+                    .col(string("aggregate_id").primary_key())
+                    .col(text("state"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("projections__audit_state").to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table("projections__audit_log").to_owned())
+            .await
+    }
+}
@@ -0,0 +1,60 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_integer, integer, pk_auto, string, string_null, text},
+};
+
+/// Creates `projection_dead_letters`.
+///
+/// Events a projection runner could not handle after its configured number
+/// of retries are parked here with their error so the runner can move on.
+/// Admins replay or skip them once the cause has been fixed.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("projection_dead_letters")
+                    .if_not_exists()
+                    .col(pk_auto("id"))
+                    .col(string("runner"))
+                    .col(big_integer("global_position"))
+                    .col(string("stream_id"))
+                    .col(big_integer("version"))
+                    .col(string("event_type"))
+                    .col(text("payload"))
+                    .col(text("metadata"))
+                    .col(integer("schema_version"))
+                    .col(text("error"))
+                    .col(integer("retry_count"))
+                    // parked | replayed | skipped
+                    .col(string("status"))
+                    .col(string("parked_at"))
+                    .col(string_null("resolved_at"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table("projection_dead_letters")
+                    .name("uq_projection_dead_letters_runner_position")
+                    .unique()
+                    .col("runner")
+                    .col("global_position")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("projection_dead_letters").to_owned())
+            .await
+    }
+}
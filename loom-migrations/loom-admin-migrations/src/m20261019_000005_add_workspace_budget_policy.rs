@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds the budget alert policy columns to `projections__workspaces`.
+///
+/// `budget_thresholds` holds comma-separated percentages.  `SQLite` swallows
+/// duplicate-column errors so the migration is idempotent.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        for (col, definition) in [
+            ("budget_thresholds", "TEXT NOT NULL DEFAULT '80,100'"),
+            ("block_over_budget", "INTEGER NOT NULL DEFAULT 0"),
+        ] {
+            let sql = if db == sea_orm::DatabaseBackend::Sqlite {
+                format!("ALTER TABLE projections__workspaces ADD COLUMN {col} {definition}")
+            } else {
+                format!(
+                    "ALTER TABLE projections__workspaces ADD COLUMN IF NOT EXISTS {col} {definition}"
+                )
+            };
+            let _ = conn.execute_unprepared(&sql).await;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_database_backend();
+        if db != sea_orm::DatabaseBackend::Sqlite {
+            let conn = manager.get_connection();
+            for col in ["budget_thresholds", "block_over_budget"] {
+                conn.execute_unprepared(&format!(
+                    "ALTER TABLE projections__workspaces DROP COLUMN IF EXISTS {col}"
+                ))
+                .await?;
+            }
+        }
+        Ok(())
+    }
+}
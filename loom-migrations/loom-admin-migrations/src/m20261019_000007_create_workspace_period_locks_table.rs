@@ -0,0 +1,37 @@
+use sea_orm_migration::{prelude::*, schema::string};
+
+/// Creates `projections__workspace_period_locks`, the current lock date of a
+/// workspace and of its single users.
+///
+/// The workspace-wide lock is stored with an empty `user_id` so both kinds
+/// share one primary key.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("projections__workspace_period_locks")
+                    .if_not_exists()
+                    .col(string("workspace_id"))
+                    .col(string("user_id").default(""))
+                    .col(string("locked_until"))
+                    .primary_key(Index::create().col("workspace_id").col("user_id"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table("projections__workspace_period_locks")
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds the rounding rules column to `projections__workspaces`.
+///
+/// The rules are stored as JSON; `{}` means nothing is rounded.  `SQLite`
+/// swallows duplicate-column errors so the migration is idempotent.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let definition = "rounding TEXT NOT NULL DEFAULT '{}'";
+        let sql = if db == sea_orm::DatabaseBackend::Sqlite {
+            format!("ALTER TABLE projections__workspaces ADD COLUMN {definition}")
+        } else {
+            format!("ALTER TABLE projections__workspaces ADD COLUMN IF NOT EXISTS {definition}")
+        };
+        let _ = conn.execute_unprepared(&sql).await;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_database_backend();
+        if db != sea_orm::DatabaseBackend::Sqlite {
+            manager
+                .get_connection()
+                .execute_unprepared(
+                    "ALTER TABLE projections__workspaces DROP COLUMN IF EXISTS rounding",
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{string, string_null, string_uniq},
+};
+
+/// Creates `api_tokens`, personal access tokens for scripts and the CLI.
+///
+/// Only a SHA-256 hash of each token is stored; the token itself is shown
+/// once when it is issued.  A token is bound to the workspace it was issued
+/// in and stops working once `revoked_at` is set.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("api_tokens")
+                    .if_not_exists()
+                    .col(string("id").primary_key())
+                    .col(string("user_id"))
+                    .col(string("workspace_id"))
+                    .col(string("name"))
+                    .col(string_uniq("token_hash"))
+                    .col(string("created_at"))
+                    .col(string_null("last_used_at"))
+                    .col(string_null("revoked_at"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table("api_tokens")
+                    .name("idx_api_tokens_user_id")
+                    .col("user_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("api_tokens").to_owned())
+            .await
+    }
+}
@@ -0,0 +1,38 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{string, string_uniq},
+};
+
+/// Creates `calendar_feeds`, the private iCalendar feeds of users'
+/// timesheets.
+///
+/// A user has at most one feed per workspace.  Like `api_tokens`, only a
+/// SHA-256 hash of the token in the feed URL is stored; resetting the feed
+/// replaces the row and so invalidates the old URL.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("calendar_feeds")
+                    .if_not_exists()
+                    .col(string("user_id"))
+                    .col(string("workspace_id"))
+                    .col(string_uniq("token_hash"))
+                    .col(string("created_at"))
+                    .primary_key(Index::create().col("user_id").col("workspace_id"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("calendar_feeds").to_owned())
+            .await
+    }
+}
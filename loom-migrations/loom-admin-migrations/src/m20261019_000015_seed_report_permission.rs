@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+/// Seeds the permission for scheduling reports over the whole workspace.
+///
+/// Continues the fixed ID sequence of `m20260410_000001_seed_permissions` and
+/// is idempotent in the same way.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const PERMISSIONS: &[(&str, &str)] = &[("01100000-0000-7000-8000-000000000017", "report.manage")];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        for (id, name) in PERMISSIONS {
+            let sql = if db == sea_orm::DatabaseBackend::Sqlite {
+                format!("INSERT OR IGNORE INTO permissions (id, name) VALUES ('{id}', '{name}')")
+            } else {
+                format!(
+                    "INSERT INTO permissions (id, name) VALUES ('{id}', '{name}') \
+                     ON CONFLICT (id) DO NOTHING"
+                )
+            };
+            conn.execute_unprepared(&sql).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+
+        for (id, _) in PERMISSIONS {
+            conn.execute_unprepared(&format!("DELETE FROM permissions WHERE id = '{id}'"))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
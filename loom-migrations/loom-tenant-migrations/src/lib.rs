@@ -10,6 +10,23 @@ mod m20260408_000002_create_timesheets_projection_table;
 mod m20260408_000003_create_timesheet_tags_projection_table;
 mod m20260408_000004_create_rates_projection_tables;
 mod m20260409_000001_fix_timesheets_user_id_fk;
+mod m20261019_000001_add_recorded_at_to_events;
+mod m20261019_000002_create_audit_log_projection_tables;
+mod m20261019_000003_create_projection_status_table;
+mod m20261019_000004_create_projection_dead_letters_table;
+mod m20261019_000005_create_budget_alerts_projection_table;
+mod m20261019_000006_add_archived_columns;
+mod m20261019_000007_add_timesheet_approval_columns;
+mod m20261019_000008_create_approvals_projection_table;
+mod m20261019_000009_add_rounding_columns;
+mod m20261019_000010_add_timesheet_break_columns;
+mod m20261019_000011_create_favorites_and_combinations_projection_tables;
+mod m20261019_000012_create_work_contracts_and_holidays_projection_tables;
+mod m20261019_000013_create_webhooks_tables;
+mod m20261019_000014_create_sync_tables;
+mod m20261019_000015_create_calendar_rules_projection_table;
+mod m20261019_000016_create_report_schedules_tables;
+mod m20261019_000017_create_search_index;
 
 pub struct Migrator;
 
@@ -28,6 +45,27 @@ impl MigratorTrait for Migrator {
             Box::new(m20260408_000003_create_timesheet_tags_projection_table::Migration),
             Box::new(m20260408_000004_create_rates_projection_tables::Migration),
             Box::new(m20260409_000001_fix_timesheets_user_id_fk::Migration),
+            Box::new(m20261019_000001_add_recorded_at_to_events::Migration),
+            Box::new(m20261019_000002_create_audit_log_projection_tables::Migration),
+            Box::new(m20261019_000003_create_projection_status_table::Migration),
+            Box::new(m20261019_000004_create_projection_dead_letters_table::Migration),
+            Box::new(m20261019_000005_create_budget_alerts_projection_table::Migration),
+            Box::new(m20261019_000006_add_archived_columns::Migration),
+            Box::new(m20261019_000007_add_timesheet_approval_columns::Migration),
+            Box::new(m20261019_000008_create_approvals_projection_table::Migration),
+            Box::new(m20261019_000009_add_rounding_columns::Migration),
+            Box::new(m20261019_000010_add_timesheet_break_columns::Migration),
+            Box::new(
+                m20261019_000011_create_favorites_and_combinations_projection_tables::Migration,
+            ),
+            Box::new(
+                m20261019_000012_create_work_contracts_and_holidays_projection_tables::Migration,
+            ),
+            Box::new(m20261019_000013_create_webhooks_tables::Migration),
+            Box::new(m20261019_000014_create_sync_tables::Migration),
+            Box::new(m20261019_000015_create_calendar_rules_projection_table::Migration),
+            Box::new(m20261019_000016_create_report_schedules_tables::Migration),
+            Box::new(m20261019_000017_create_search_index::Migration),
         ]
     }
 }
@@ -0,0 +1,41 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_integer, big_integer_null, integer, string, string_null, text_null},
+};
+
+/// Creates `projection_status`, one row per projection runner.
+///
+/// The runner wrapper updates it after every handled or failed event so the
+/// daemon health endpoint and the developer page can report the checkpoint,
+/// lag, the last error with its offending event and the retry count.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("projection_status")
+                    .if_not_exists()
+                    .col(string("runner").primary_key())
+                    .col(big_integer("position").default(0))
+                    .col(string_null("last_error"))
+                    .col(big_integer_null("error_position"))
+                    .col(string_null("error_stream_id"))
+                    .col(string_null("error_event_type"))
+                    .col(text_null("error_payload"))
+                    .col(integer("retry_count").default(0))
+                    .col(string("updated_at"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("projection_status").to_owned())
+            .await
+    }
+}
@@ -0,0 +1,55 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_integer, integer, pk_auto, string},
+};
+
+/// Creates `projections__budget_alerts`.
+///
+/// One row per budget warning threshold a customer or project reached.
+/// `period` is `YYYY-MM` for monthly budgets and empty for lifetime budgets.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("projections__budget_alerts")
+                    .if_not_exists()
+                    .col(pk_auto("id"))
+                    // customer | project
+                    .col(string("scope"))
+                    .col(string("target_id"))
+                    .col(string("period"))
+                    .col(integer("threshold"))
+                    .col(big_integer("used_seconds"))
+                    .col(big_integer("used_amount"))
+                    .col(big_integer("global_position"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table("projections__budget_alerts")
+                    .name("uq_projections__budget_alerts_target_period_threshold")
+                    .unique()
+                    .col("scope")
+                    .col("target_id")
+                    .col("period")
+                    .col("threshold")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("projections__budget_alerts").to_owned())
+            .await
+    }
+}
@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds an `archived` flag to the customer, project, activity and tag
+/// projections.
+///
+/// `SQLite` swallows duplicate-column errors so the migration is idempotent.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const TABLES: [&str; 4] = [
+    "projections__customers",
+    "projections__projects",
+    "projections__activities",
+    "projections__tags",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        for table in TABLES {
+            let sql = if db == sea_orm::DatabaseBackend::Sqlite {
+                format!("ALTER TABLE {table} ADD COLUMN archived INTEGER NOT NULL DEFAULT 0")
+            } else {
+                format!(
+                    "ALTER TABLE {table} ADD COLUMN IF NOT EXISTS archived INTEGER NOT NULL DEFAULT 0"
+                )
+            };
+            let _ = conn.execute_unprepared(&sql).await;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_database_backend();
+        if db != sea_orm::DatabaseBackend::Sqlite {
+            let conn = manager.get_connection();
+            for table in TABLES {
+                conn.execute_unprepared(&format!(
+                    "ALTER TABLE {table} DROP COLUMN IF EXISTS archived"
+                ))
+                .await?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds the approval state to `projections__timesheets`: `approval_status`
+/// (`draft`, `submitted`, `approved` or `rejected`) and the `approval_id` of
+/// the submission that last covered the entry.
+///
+/// `SQLite` swallows duplicate-column errors so the migration is idempotent.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const COLUMNS: [(&str, &str); 2] = [
+    ("approval_status", "TEXT NOT NULL DEFAULT 'draft'"),
+    ("approval_id", "TEXT NULL"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        for (column, definition) in COLUMNS {
+            let sql = if db == sea_orm::DatabaseBackend::Sqlite {
+                format!("ALTER TABLE projections__timesheets ADD COLUMN {column} {definition}")
+            } else {
+                format!(
+                    "ALTER TABLE projections__timesheets ADD COLUMN IF NOT EXISTS {column} {definition}"
+                )
+            };
+            let _ = conn.execute_unprepared(&sql).await;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_database_backend();
+        if db != sea_orm::DatabaseBackend::Sqlite {
+            let conn = manager.get_connection();
+            for (column, _) in COLUMNS {
+                conn.execute_unprepared(&format!(
+                    "ALTER TABLE projections__timesheets DROP COLUMN IF EXISTS {column}"
+                ))
+                .await?;
+            }
+        }
+        Ok(())
+    }
+}
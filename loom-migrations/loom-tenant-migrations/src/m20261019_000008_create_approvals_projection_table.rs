@@ -0,0 +1,54 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{integer, pk_uuid, string, string_null, uuid, uuid_null},
+};
+
+/// Creates `projections__approvals`, one row per submission of a user's
+/// entries for a period.  `comment` holds the submitter's comment until the
+/// reviewer replaces it with theirs.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("projections__approvals")
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    // No FK on user_id or reviewer_id — users live in the admin database.
+                    .col(uuid("user_id"))
+                    .col(string("period_start"))
+                    .col(string("period_end"))
+                    // submitted | approved | rejected
+                    .col(string("status"))
+                    .col(string_null("comment"))
+                    .col(integer("entry_count"))
+                    .col(string("submitted_at"))
+                    .col(uuid_null("reviewer_id"))
+                    .col(string_null("reviewed_at"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table("projections__approvals")
+                    .name("idx_projections__approvals_user_period")
+                    .col("user_id")
+                    .col("period_start")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("projections__approvals").to_owned())
+            .await
+    }
+}
@@ -0,0 +1,52 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds rounding to the projections.
+///
+/// Customers and projects get a `rounding` override stored as JSON, `NULL`
+/// meaning the next broader rules apply.  Timesheets get the
+/// `billed_duration` their amount was calculated from and the `rounding`
+/// rules that were applied.
+///
+/// `SQLite` swallows duplicate-column errors so the migration is idempotent.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const COLUMNS: [(&str, &str, &str); 4] = [
+    ("projections__customers", "rounding", "TEXT NULL"),
+    ("projections__projects", "rounding", "TEXT NULL"),
+    ("projections__timesheets", "billed_duration", "INTEGER NULL"),
+    ("projections__timesheets", "rounding", "TEXT NULL"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        for (table, column, definition) in COLUMNS {
+            let sql = if db == sea_orm::DatabaseBackend::Sqlite {
+                format!("ALTER TABLE {table} ADD COLUMN {column} {definition}")
+            } else {
+                format!("ALTER TABLE {table} ADD COLUMN IF NOT EXISTS {column} {definition}")
+            };
+            let _ = conn.execute_unprepared(&sql).await;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_database_backend();
+        if db != sea_orm::DatabaseBackend::Sqlite {
+            let conn = manager.get_connection();
+            for (table, column, _) in COLUMNS {
+                conn.execute_unprepared(&format!(
+                    "ALTER TABLE {table} DROP COLUMN IF EXISTS {column}"
+                ))
+                .await?;
+            }
+        }
+        Ok(())
+    }
+}
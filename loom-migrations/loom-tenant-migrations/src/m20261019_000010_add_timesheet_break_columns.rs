@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds breaks to the timesheets projection: `break_duration` totals the
+/// breaks in seconds and `paused_at` is set while a timer is paused.
+///
+/// `SQLite` swallows duplicate-column errors so the migration is idempotent.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const COLUMNS: [(&str, &str, &str); 2] = [
+    (
+        "projections__timesheets",
+        "break_duration",
+        "INTEGER NOT NULL DEFAULT 0",
+    ),
+    ("projections__timesheets", "paused_at", "TEXT NULL"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        for (table, column, definition) in COLUMNS {
+            let sql = if db == sea_orm::DatabaseBackend::Sqlite {
+                format!("ALTER TABLE {table} ADD COLUMN {column} {definition}")
+            } else {
+                format!("ALTER TABLE {table} ADD COLUMN IF NOT EXISTS {column} {definition}")
+            };
+            let _ = conn.execute_unprepared(&sql).await;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_database_backend();
+        if db != sea_orm::DatabaseBackend::Sqlite {
+            let conn = manager.get_connection();
+            for (table, column, _) in COLUMNS {
+                conn.execute_unprepared(&format!(
+                    "ALTER TABLE {table} DROP COLUMN IF EXISTS {column}"
+                ))
+                .await?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_integer, integer, pk_uuid, string, string_null, text, uuid},
+};
+
+/// Creates the projections behind quick timer start:
+///
+/// - `projections__favorites`, the combinations users pinned, in pinning
+///   order.
+/// - `projections__timesheet_combinations`, one row per user and combination
+///   of project, activity and description used in the 90 days up to the
+///   user's latest entry.  `last_timesheet_id` and `tag_ids` (a JSON array)
+///   refer to its latest use; an empty `description` stands for none.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("projections__favorites")
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    // No FK on user_id — users live in the admin database.
+                    .col(uuid("user_id"))
+                    .col(uuid("project_id"))
+                    .col(uuid("activity_id"))
+                    .col(string_null("description"))
+                    .col(text("tag_ids"))
+                    .col(big_integer("global_position"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table("projections__favorites")
+                    .name("idx_projections__favorites_user")
+                    .col("user_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("projections__timesheet_combinations")
+                    .if_not_exists()
+                    .col(uuid("user_id"))
+                    .col(uuid("project_id"))
+                    .col(uuid("activity_id"))
+                    .col(string("description"))
+                    .col(text("tag_ids"))
+                    .col(integer("use_count"))
+                    .col(string("last_used_at"))
+                    .col(uuid("last_timesheet_id"))
+                    .primary_key(
+                        Index::create()
+                            .col("user_id")
+                            .col("project_id")
+                            .col("activity_id")
+                            .col("description"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table("projections__timesheet_combinations")
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table("projections__favorites").to_owned())
+            .await
+    }
+}
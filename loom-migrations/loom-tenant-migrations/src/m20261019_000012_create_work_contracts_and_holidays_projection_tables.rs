@@ -0,0 +1,71 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{integer, pk_uuid, string, string_null, text, uuid},
+};
+
+/// Creates the projections behind expected working time:
+///
+/// - `projections__work_contracts`, the contracts of each user.  `working_days`
+///   is a JSON array of ISO weekday numbers and `starts_on` a `YYYY-MM-DD`
+///   date.
+/// - `projections__holidays`, the public holidays of each region.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("projections__work_contracts")
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    // No FK on user_id — users live in the admin database.
+                    .col(uuid("user_id"))
+                    .col(integer("weekly_minutes"))
+                    .col(text("working_days"))
+                    .col(string("starts_on"))
+                    .col(string_null("region"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table("projections__work_contracts")
+                    .name("idx_projections__work_contracts_user")
+                    .col("user_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("projections__holidays")
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(string("region"))
+                    .col(string("date"))
+                    .col(string("name"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("projections__holidays").to_owned())
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table("projections__work_contracts")
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,97 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{
+        big_integer, integer, integer_null, pk_auto, pk_uuid, string, string_null, text, text_null,
+    },
+};
+
+/// Creates the tables behind outgoing webhooks:
+///
+/// - `projections__webhooks`, the subscriptions.  `event_types` is a JSON
+///   array of event names and `since_position` the `global_position` of the
+///   subscribing event; earlier events are never delivered, even when the
+///   projection is rebuilt.
+/// - `webhook_deliveries`, one row per event sent (or to be sent) to a
+///   subscription, with its retry state.  The unique index keeps a rebuilt
+///   projection from enqueueing the same event twice; `redelivery` counts
+///   the manual redeliveries of an event.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("projections__webhooks")
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    .col(string("url"))
+                    .col(string("secret"))
+                    .col(text("event_types"))
+                    .col(big_integer("since_position"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("webhook_deliveries")
+                    .if_not_exists()
+                    .col(pk_auto("id"))
+                    .col(string("webhook_id"))
+                    .col(big_integer("global_position"))
+                    .col(integer("redelivery"))
+                    .col(string("stream_id"))
+                    .col(string("event_type"))
+                    .col(text("payload"))
+                    // pending | delivered | failed
+                    .col(string("status"))
+                    .col(integer("attempts"))
+                    .col(string_null("next_attempt_at"))
+                    .col(string_null("last_attempt_at"))
+                    .col(integer_null("response_status"))
+                    .col(text_null("error"))
+                    .col(string("created_at"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table("webhook_deliveries")
+                    .name("uq_webhook_deliveries_event")
+                    .unique()
+                    .col("webhook_id")
+                    .col("global_position")
+                    .col("redelivery")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table("webhook_deliveries")
+                    .name("idx_webhook_deliveries_due")
+                    .col("status")
+                    .col("next_attempt_at")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("webhook_deliveries").to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table("projections__webhooks").to_owned())
+            .await
+    }
+}
@@ -0,0 +1,72 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_integer, integer, pk_auto, string, string_null, text},
+};
+
+/// Creates the bookkeeping tables of an offline replica (the desktop app's
+/// local tenant database).  They stay empty on the server.
+///
+/// - `sync_state`, key/value pairs such as the server URL, the session and
+///   the server `global_position` pulled up to.
+/// - `sync_streams`, per event stream the last version the server has
+///   confirmed.  Local events above it are still to be pushed.
+/// - `sync_conflicts`, local changes the server refused because the entry
+///   had been changed there in the meantime.  `local_events` is a JSON array
+///   of the dropped events, kept so the user can redo them.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("sync_state")
+                    .if_not_exists()
+                    .col(string("key").primary_key())
+                    .col(text("value"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("sync_streams")
+                    .if_not_exists()
+                    .col(string("stream_id").primary_key())
+                    .col(integer("synced_version"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("sync_conflicts")
+                    .if_not_exists()
+                    .col(pk_auto("id"))
+                    .col(string("stream_id"))
+                    .col(big_integer("server_version"))
+                    .col(text("local_events"))
+                    .col(string("reason"))
+                    .col(string("detected_at"))
+                    .col(string_null("dismissed_at"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("sync_conflicts").to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table("sync_streams").to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table("sync_state").to_owned())
+            .await
+    }
+}
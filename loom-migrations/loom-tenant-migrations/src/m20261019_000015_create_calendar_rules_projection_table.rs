@@ -0,0 +1,51 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_integer, pk_uuid, string, uuid, uuid_null},
+};
+
+/// Creates `projections__calendar_rules`, the keywords by which a user's
+/// imported calendar events are mapped to a project and activity.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("projections__calendar_rules")
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    // No FK on user_id — users live in the admin database.
+                    .col(uuid("user_id"))
+                    .col(string("keyword"))
+                    .col(uuid("project_id"))
+                    .col(uuid_null("activity_id"))
+                    .col(big_integer("global_position"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table("projections__calendar_rules")
+                    .name("idx_projections__calendar_rules_user")
+                    .col("user_id")
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table("projections__calendar_rules")
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,67 @@
+use sea_orm_migration::{
+    prelude::*,
+    schema::{big_integer, pk_uuid, string, string_null, text, text_null, uuid},
+};
+
+/// Creates the tables behind scheduled reports:
+///
+/// - `projections__report_schedules`, the report definitions.
+///   `parameters` is the JSON of the report parameters and `recipients` a
+///   JSON array of email addresses.
+/// - `report_schedule_runs`, when each report was last due and how sending
+///   it went.  The scheduler adds a row the first time it sees a schedule,
+///   so a new report is first sent at the next time its schedule fires.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("projections__report_schedules")
+                    .if_not_exists()
+                    .col(pk_uuid("id"))
+                    // No FK on owner_id — users live in the admin database.
+                    .col(uuid("owner_id"))
+                    .col(string("name"))
+                    // personal | workspace
+                    .col(string("scope"))
+                    .col(string("schedule"))
+                    .col(text("parameters"))
+                    // html | csv
+                    .col(string("format"))
+                    .col(text("recipients"))
+                    .col(big_integer("global_position"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("report_schedule_runs")
+                    .if_not_exists()
+                    .col(string("schedule_id").primary_key())
+                    .col(string("last_due_at"))
+                    .col(string_null("last_sent_at"))
+                    .col(text_null("last_error"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("report_schedule_runs").to_owned())
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table("projections__report_schedules")
+                    .to_owned(),
+            )
+            .await
+    }
+}
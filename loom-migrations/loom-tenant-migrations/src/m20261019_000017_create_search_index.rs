@@ -0,0 +1,84 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `projections__search`, the full-text index over entity names,
+/// comments, order numbers and time entry descriptions, and fills it from
+/// the existing projections.  Archived entities are left out.
+///
+/// `SQLite` gets an FTS5 table; Postgres a regular table with a generated
+/// `tsvector` in which titles weigh more than bodies.  Both share the same
+/// columns so the projector can write to either.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const SQLITE_TABLE: &str = "CREATE VIRTUAL TABLE IF NOT EXISTS projections__search USING fts5(
+    entity_type UNINDEXED,
+    entity_id UNINDEXED,
+    user_id UNINDEXED,
+    start_time UNINDEXED,
+    title,
+    body,
+    tokenize = 'unicode61 remove_diacritics 2'
+)";
+
+const POSTGRES_TABLE: &str = "CREATE TABLE IF NOT EXISTS projections__search (
+    entity_id TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    user_id TEXT NULL,
+    start_time TEXT NULL,
+    title TEXT NOT NULL,
+    body TEXT NOT NULL DEFAULT '',
+    document tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', title), 'A')
+            || setweight(to_tsvector('simple', body), 'B')
+    ) STORED
+)";
+
+const POSTGRES_INDEX: &str = "CREATE INDEX IF NOT EXISTS idx_projections__search_document \
+     ON projections__search USING GIN (document)";
+
+const BACKFILL: [&str; 5] = [
+    "SELECT 'customer', CAST(id AS TEXT), NULL, NULL, name, COALESCE(comment, '') \
+     FROM projections__customers WHERE archived = 0",
+    "SELECT 'project', CAST(id AS TEXT), NULL, NULL, name, \
+     COALESCE(comment, '') || ' ' || COALESCE(order_number, '') \
+     FROM projections__projects WHERE archived = 0",
+    "SELECT 'activity', CAST(id AS TEXT), NULL, NULL, name, COALESCE(comment, '') \
+     FROM projections__activities WHERE archived = 0",
+    "SELECT 'tag', CAST(id AS TEXT), NULL, NULL, name, '' \
+     FROM projections__tags WHERE archived = 0",
+    "SELECT 'timesheet', CAST(id AS TEXT), CAST(user_id AS TEXT), CAST(start_time AS TEXT), \
+     description, '' FROM projections__timesheets \
+     WHERE description IS NOT NULL AND description <> ''",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        if manager.get_database_backend() == sea_orm::DatabaseBackend::Sqlite {
+            conn.execute_unprepared(SQLITE_TABLE).await?;
+        } else {
+            conn.execute_unprepared(POSTGRES_TABLE).await?;
+            conn.execute_unprepared(POSTGRES_INDEX).await?;
+        }
+
+        conn.execute_unprepared("DELETE FROM projections__search")
+            .await?;
+        for select in BACKFILL {
+            conn.execute_unprepared(&format!(
+                "INSERT INTO projections__search \
+                 (entity_type, entity_id, user_id, start_time, title, body) {select}"
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS projections__search")
+            .await?;
+        Ok(())
+    }
+}
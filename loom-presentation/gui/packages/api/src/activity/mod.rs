@@ -15,7 +15,7 @@ pub struct ActivityDto {
 pub async fn list_activities() -> Result<Vec<ActivityDto>, ServerFnError> {
     #[cfg(feature = "server")]
     {
-        _list_activities().await
+        _list_activities(false).await
     }
     #[cfg(not(feature = "server"))]
     {
@@ -62,14 +62,74 @@ pub async fn update_activity(
     }
 }
 
+/// Archived activities, hidden from [`list_activities`].
+#[get("/api/activities/archived")]
+pub async fn list_archived_activities() -> Result<Vec<ActivityDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_activities(true).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+/// Hide a activity from lists and selectors.
+#[post("/api/activities/archive")]
+pub async fn archive_activity(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _archive_activity(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+/// Bring an archived activity back.
+#[post("/api/activities/restore")]
+pub async fn restore_activity(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _restore_activity(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+/// Permanently delete a activity.
+///
+/// Only allowed while no timesheets are booked on the activity.
+#[post("/api/activities/delete")]
+pub async fn delete_activity(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _delete_activity(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
 #[cfg(feature = "server")]
-async fn _list_activities() -> Result<Vec<ActivityDto>, ServerFnError> {
+async fn _list_activities(archived: bool) -> Result<Vec<ActivityDto>, ServerFnError> {
     use crate::session;
 
     let (_, workspace_id) = session::session_workspace().await?;
-    let rows = loom::tenant::activity::list(&workspace_id)
-        .await
-        .map_err(session::internal)?;
+    let rows = if archived {
+        loom::tenant::activity::list_archived(&workspace_id).await
+    } else {
+        loom::tenant::activity::list(&workspace_id).await
+    }
+    .map_err(session::internal)?;
     Ok(rows
         .into_iter()
         .map(|r| ActivityDto {
@@ -125,3 +185,42 @@ async fn _update_activity(
         .await
         .map_err(session::internal)
 }
+
+#[cfg(feature = "server")]
+async fn _archive_activity(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::ACTIVITY_UPDATE).await?;
+
+    loom::tenant::activity::archive(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _restore_activity(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::ACTIVITY_UPDATE).await?;
+
+    loom::tenant::activity::restore(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _delete_activity(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::ACTIVITY_DELETE).await?;
+
+    loom::tenant::activity::delete(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
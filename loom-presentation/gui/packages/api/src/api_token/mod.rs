@@ -0,0 +1,106 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A personal API token, without the token itself.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiTokenDto {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+}
+
+/// Your API tokens in the current workspace, newest first.
+#[get("/api/api-tokens")]
+pub async fn list_api_tokens() -> Result<Vec<ApiTokenDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_api_tokens().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+/// Returns the token; it cannot be shown again.
+#[post("/api/api-tokens/create")]
+pub async fn create_api_token(name: String) -> Result<String, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _create_api_token(name).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = name;
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
+#[post("/api/api-tokens/revoke")]
+pub async fn revoke_api_token(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _revoke_api_token(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _list_api_tokens() -> Result<Vec<ApiTokenDto>, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let rows = loom::api_token::tokens(&user.id, &workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(rows
+        .into_iter()
+        .map(|r| ApiTokenDto {
+            id: r.id,
+            name: r.name,
+            created_at: r.created_at,
+            last_used_at: r.last_used_at,
+            revoked_at: r.revoked_at,
+        })
+        .collect())
+}
+
+#[cfg(feature = "server")]
+async fn _create_api_token(name: String) -> Result<String, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let (_, token) = loom::api_token::issue(&user.id, &workspace_id, &name)
+        .await
+        .map_err(session::internal)?;
+    Ok(token)
+}
+
+#[cfg(feature = "server")]
+async fn _revoke_api_token(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+
+    let user = session::session_user().await?;
+    let revoked = loom::api_token::revoke(&user.id, &id)
+        .await
+        .map_err(session::internal)?;
+    if revoked {
+        Ok(())
+    } else {
+        Err(ServerFnError::ServerError {
+            message: "API token not found".into(),
+            code: 404,
+            details: None,
+        })
+    }
+}
@@ -0,0 +1,241 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::timesheet::TimesheetDto;
+
+/// A user's entries of a period submitted for approval.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalDto {
+    pub id: String,
+    pub user_id: String,
+    pub user_name: Option<String>,
+    /// First day of the period, `YYYY-MM-DD`.
+    pub period_start: String,
+    /// Last day of the period, `YYYY-MM-DD`.
+    pub period_end: String,
+    /// `submitted`, `approved` or `rejected`.
+    pub status: String,
+    /// The submitter's comment, or the reviewer's once reviewed.
+    pub comment: Option<String>,
+    pub entry_count: i32,
+    pub submitted_at: String,
+    pub reviewed_at: Option<String>,
+}
+
+/// The current user's submissions, newest period first.
+#[get("/api/approvals/mine")]
+pub async fn my_approvals() -> Result<Vec<ApprovalDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _my_approvals().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+/// Submissions awaiting review.
+#[get("/api/approvals/pending")]
+pub async fn pending_approvals() -> Result<Vec<ApprovalDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _pending_approvals().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+/// The entries covered by a submission.  Visible to the submitter and to
+/// reviewers.
+#[post("/api/approvals/entries")]
+pub async fn approval_entries(approval_id: String) -> Result<Vec<TimesheetDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _approval_entries(approval_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = approval_id;
+        Ok(vec![])
+    }
+}
+
+/// Submit the current user's entries of `from..=to` (`YYYY-MM-DD`).
+#[post("/api/approvals/submit")]
+pub async fn submit_period(
+    from: String,
+    to: String,
+    comment: Option<String>,
+) -> Result<ApprovalDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _submit_period(from, to, comment).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (from, to, comment);
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
+#[post("/api/approvals/approve")]
+pub async fn approve(approval_id: String, comment: Option<String>) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _approve(approval_id, comment).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (approval_id, comment);
+        Ok(())
+    }
+}
+
+/// Send a submission back for corrections.  `comment` is required.
+#[post("/api/approvals/reject")]
+pub async fn reject(approval_id: String, comment: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _reject(approval_id, comment).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (approval_id, comment);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _my_approvals() -> Result<Vec<ApprovalDto>, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let rows = loom::tenant::approval::for_user(&workspace_id, &user.id)
+        .await
+        .map_err(session::internal)?;
+    rows_to_dtos(rows).await
+}
+
+#[cfg(feature = "server")]
+async fn _pending_approvals() -> Result<Vec<ApprovalDto>, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_APPROVE).await?;
+
+    let rows = loom::tenant::approval::pending(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    rows_to_dtos(rows).await
+}
+
+#[cfg(feature = "server")]
+async fn _approval_entries(approval_id: String) -> Result<Vec<TimesheetDto>, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let Some(approval) = loom::tenant::approval::find(&workspace_id, &approval_id)
+        .await
+        .map_err(session::internal)?
+    else {
+        return Ok(vec![]);
+    };
+    if approval.user_id != user.id {
+        session::require_permission(&user, permissions::TIMESHEET_APPROVE).await?;
+    }
+
+    let rows = loom::tenant::approval::entries(&workspace_id, &approval_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(rows.into_iter().map(crate::timesheet::row_to_dto).collect())
+}
+
+#[cfg(feature = "server")]
+async fn _submit_period(
+    from: String,
+    to: String,
+    comment: Option<String>,
+) -> Result<ApprovalDto, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_SUBMIT).await?;
+
+    let row = loom::tenant::approval::submit(&workspace_id, &user.id, &from, &to, comment)
+        .await
+        .map_err(session::internal)?;
+    rows_to_dtos(vec![row])
+        .await
+        .map(|mut dtos| dtos.remove(0))
+}
+
+#[cfg(feature = "server")]
+async fn _approve(approval_id: String, comment: Option<String>) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_APPROVE).await?;
+
+    loom::tenant::approval::approve(&workspace_id, &approval_id, &user.id, comment)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _reject(approval_id: String, comment: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_APPROVE).await?;
+
+    loom::tenant::approval::reject(&workspace_id, &approval_id, &user.id, comment)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn rows_to_dtos(
+    rows: Vec<loom::infrastructure::tenant::approval::repositories::ApprovalRow>,
+) -> Result<Vec<ApprovalDto>, ServerFnError> {
+    let names = loom::tenant::approval::user_names(&rows)
+        .await
+        .map_err(crate::session::internal)?;
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let name = names.get(&r.user_id).cloned();
+            row_to_dto(r, name)
+        })
+        .collect())
+}
+
+#[cfg(feature = "server")]
+fn row_to_dto(
+    r: loom::infrastructure::tenant::approval::repositories::ApprovalRow,
+    user_name: Option<String>,
+) -> ApprovalDto {
+    ApprovalDto {
+        id: r.id,
+        user_id: r.user_id,
+        user_name,
+        period_start: r.period_start,
+        period_end: r.period_end,
+        status: r.status,
+        comment: r.comment,
+        entry_count: r.entry_count,
+        submitted_at: r.submitted_at,
+        reviewed_at: r.reviewed_at,
+    }
+}
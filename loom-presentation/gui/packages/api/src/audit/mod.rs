@@ -0,0 +1,137 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntryDto {
+    pub global_position: i64,
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub event_type: String,
+    pub action: String,
+    pub actor_id: Option<String>,
+    /// Display name of the actor, `None` for system actions or deleted users.
+    pub actor_name: Option<String>,
+    pub recorded_at: Option<String>,
+    pub changes: Vec<String>,
+}
+
+/// Which database the audit log is read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AuditScope {
+    /// The current workspace (timesheets, customers, projects, …).
+    #[default]
+    Workspace,
+    /// The admin database (users, workspaces, roles, permissions).
+    Admin,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AuditFilterDto {
+    pub scope: AuditScope,
+    pub aggregate_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub actor_id: Option<String>,
+    /// Inclusive lower bound, RFC 3339 UTC.
+    pub from: Option<String>,
+    /// Exclusive upper bound, RFC 3339 UTC.
+    pub to: Option<String>,
+    pub page: u64,
+    pub page_size: u64,
+}
+
+/// Filterable audit log. Admin-only.
+#[post("/api/audit")]
+pub async fn list_audit_log(filter: AuditFilterDto) -> Result<Vec<AuditEntryDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_audit_log(filter).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = filter;
+        Ok(vec![])
+    }
+}
+
+/// Change history of one entity in the current workspace.
+#[post("/api/audit/history")]
+pub async fn entity_history(entity_id: String) -> Result<Vec<AuditEntryDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _entity_history(entity_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = entity_id;
+        Ok(vec![])
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _list_audit_log(filter: AuditFilterDto) -> Result<Vec<AuditEntryDto>, ServerFnError> {
+    use crate::session;
+    use loom::infrastructure::audit_log::repositories::AuditLogFilter;
+
+    let user = session::session_user().await?;
+    session::require_admin(&user).await?;
+
+    let page_size = if filter.page_size == 0 {
+        50
+    } else {
+        filter.page_size
+    };
+    let repo_filter = AuditLogFilter {
+        aggregate_type: filter.aggregate_type,
+        entity_id: filter.entity_id,
+        actor: filter.actor_id,
+        from: filter.from,
+        to: filter.to,
+        limit: page_size,
+        offset: filter.page * page_size,
+    };
+
+    let rows = match filter.scope {
+        AuditScope::Workspace => {
+            let (_, workspace_id) = session::session_workspace().await?;
+            loom::audit::list(&workspace_id, repo_filter).await
+        }
+        AuditScope::Admin => loom::audit::list_admin(repo_filter).await,
+    }
+    .map_err(session::internal)?;
+
+    rows_to_dtos(rows).await
+}
+
+#[cfg(feature = "server")]
+async fn _entity_history(entity_id: String) -> Result<Vec<AuditEntryDto>, ServerFnError> {
+    use crate::session;
+
+    let (_, workspace_id) = session::session_workspace().await?;
+    let rows = loom::audit::history(&workspace_id, &entity_id)
+        .await
+        .map_err(session::internal)?;
+    rows_to_dtos(rows).await
+}
+
+#[cfg(feature = "server")]
+async fn rows_to_dtos(
+    rows: Vec<loom::infrastructure::audit_log::repositories::AuditLogRow>,
+) -> Result<Vec<AuditEntryDto>, ServerFnError> {
+    let names = loom::audit::actor_names(&rows)
+        .await
+        .map_err(crate::session::internal)?;
+    Ok(rows
+        .into_iter()
+        .map(|r| AuditEntryDto {
+            actor_name: r.actor.as_ref().and_then(|a| names.get(a).cloned()),
+            global_position: r.global_position,
+            aggregate_type: r.aggregate_type,
+            aggregate_id: r.aggregate_id,
+            event_type: r.event_type,
+            action: r.action,
+            actor_id: r.actor,
+            recorded_at: r.recorded_at,
+            changes: r.changes,
+        })
+        .collect())
+}
@@ -0,0 +1,64 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetConsumptionDto {
+    /// `customer` or `project`.
+    pub scope: String,
+    pub id: String,
+    pub name: String,
+    /// `YYYY-MM` for monthly budgets.
+    pub period: Option<String>,
+    /// Time budget in seconds.
+    pub time_budget: Option<i32>,
+    /// Money budget in cents.
+    pub money_budget: Option<i64>,
+    pub used_seconds: i64,
+    pub used_amount: i64,
+    pub remaining_seconds: Option<i64>,
+    pub remaining_amount: Option<i64>,
+    pub percent_used: Option<i64>,
+    /// Highest warning threshold reached in the current period.
+    pub reached_threshold: Option<u8>,
+}
+
+/// Budget usage of every customer and project in the current workspace that
+/// has a budget.
+#[get("/api/budget")]
+pub async fn list_budget_consumption() -> Result<Vec<BudgetConsumptionDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_budget_consumption().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _list_budget_consumption() -> Result<Vec<BudgetConsumptionDto>, ServerFnError> {
+    use crate::session;
+
+    let (_user, workspace_id) = session::session_workspace().await?;
+    let rows = loom::tenant::budget::consumption(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(rows
+        .into_iter()
+        .map(|c| BudgetConsumptionDto {
+            scope: c.scope,
+            id: c.id,
+            name: c.name,
+            period: c.period,
+            time_budget: c.time_budget,
+            money_budget: c.money_budget,
+            used_seconds: c.used_seconds,
+            used_amount: c.used_amount,
+            remaining_seconds: c.remaining_seconds,
+            remaining_amount: c.remaining_amount,
+            percent_used: c.percent_used,
+            reached_threshold: c.reached_threshold,
+        })
+        .collect())
+}
@@ -0,0 +1,314 @@
+//! Calendar integration: your private iCalendar feed, served from
+//! [`FEED_PATH`], and importing calendar events as timesheets.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::timesheet::TimesheetDto;
+
+/// Where feeds are served; the URL of a feed is this path, its token and
+/// `.ics`.
+pub const FEED_PATH: &str = "/api/v1/calendar";
+
+/// Your feed in the current workspace, without its token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalendarFeedDto {
+    pub created_at: String,
+}
+
+/// Maps imported events whose title contains `keyword`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalendarRuleDto {
+    pub id: String,
+    pub keyword: String,
+    pub project_id: String,
+    pub activity_id: Option<String>,
+}
+
+/// An event of an uploaded calendar, with the project and activity your
+/// rules suggest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportCandidateDto {
+    pub title: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub project_id: Option<String>,
+    pub activity_id: Option<String>,
+    /// You already have an entry with exactly these times.
+    pub tracked: bool,
+}
+
+/// An event to import as a timesheet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportSelectionDto {
+    pub title: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub project_id: Option<String>,
+    pub activity_id: Option<String>,
+    pub billable: bool,
+}
+
+/// `None` while your feed is off.
+#[get("/api/calendar/feed")]
+pub async fn get_calendar_feed() -> Result<Option<CalendarFeedDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _get_calendar_feed().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(None)
+    }
+}
+
+/// Turns your feed on, or replaces its URL.  Returns the feed's path; it
+/// cannot be shown again.
+#[post("/api/calendar/feed/reset")]
+pub async fn reset_calendar_feed() -> Result<String, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _reset_calendar_feed().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
+#[post("/api/calendar/feed/disable")]
+pub async fn disable_calendar_feed() -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _disable_calendar_feed().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(())
+    }
+}
+
+#[get("/api/calendar/rules")]
+pub async fn list_calendar_rules() -> Result<Vec<CalendarRuleDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_calendar_rules().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+#[post("/api/calendar/rules/add")]
+pub async fn add_calendar_rule(
+    keyword: String,
+    project_id: String,
+    activity_id: Option<String>,
+) -> Result<CalendarRuleDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _add_calendar_rule(keyword, project_id, activity_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (keyword, project_id, activity_id);
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
+#[post("/api/calendar/rules/remove")]
+pub async fn remove_calendar_rule(rule_id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _remove_calendar_rule(rule_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = rule_id;
+        Ok(())
+    }
+}
+
+/// The timed events of an iCalendar file, oldest first.
+#[post("/api/calendar/import/preview")]
+pub async fn preview_calendar_import(
+    ics: String,
+) -> Result<Vec<ImportCandidateDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _preview_calendar_import(ics).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = ics;
+        Ok(vec![])
+    }
+}
+
+/// Creates a timesheet per event; stops at the first one that cannot be
+/// imported.
+#[post("/api/calendar/import")]
+pub async fn import_calendar_events(
+    events: Vec<ImportSelectionDto>,
+) -> Result<Vec<TimesheetDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _import_calendar_events(events).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = events;
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
+#[cfg(feature = "server")]
+fn rule_to_dto(
+    r: loom::infrastructure::tenant::calendar_rule::repositories::CalendarRuleRow,
+) -> CalendarRuleDto {
+    CalendarRuleDto {
+        id: r.id,
+        keyword: r.keyword,
+        project_id: r.project_id,
+        activity_id: r.activity_id,
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _get_calendar_feed() -> Result<Option<CalendarFeedDto>, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let feed = loom::tenant::calendar::feed(&user.id, &workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(feed.map(|f| CalendarFeedDto {
+        created_at: f.created_at,
+    }))
+}
+
+#[cfg(feature = "server")]
+async fn _reset_calendar_feed() -> Result<String, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let token = loom::tenant::calendar::reset_feed(&user.id, &workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(format!("{FEED_PATH}/{token}.ics"))
+}
+
+#[cfg(feature = "server")]
+async fn _disable_calendar_feed() -> Result<(), ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    loom::tenant::calendar::disable_feed(&user.id, &workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+async fn _list_calendar_rules() -> Result<Vec<CalendarRuleDto>, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let rows = loom::tenant::calendar::rules(&workspace_id, &user.id)
+        .await
+        .map_err(session::internal)?;
+    Ok(rows.into_iter().map(rule_to_dto).collect())
+}
+
+#[cfg(feature = "server")]
+async fn _add_calendar_rule(
+    keyword: String,
+    project_id: String,
+    activity_id: Option<String>,
+) -> Result<CalendarRuleDto, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let r = loom::tenant::calendar::add_rule(
+        &workspace_id,
+        &user.id,
+        &keyword,
+        &project_id,
+        activity_id.as_deref(),
+    )
+    .await
+    .map_err(session::internal)?;
+    Ok(rule_to_dto(r))
+}
+
+#[cfg(feature = "server")]
+async fn _remove_calendar_rule(rule_id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    loom::tenant::calendar::remove_rule(&workspace_id, &user.id, &rule_id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _preview_calendar_import(ics: String) -> Result<Vec<ImportCandidateDto>, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let candidates = loom::tenant::calendar::preview_import(&workspace_id, &user.id, &ics)
+        .await
+        .map_err(session::internal)?;
+    Ok(candidates
+        .into_iter()
+        .map(|c| ImportCandidateDto {
+            title: c.title,
+            start_time: c.start_time,
+            end_time: c.end_time,
+            project_id: c.project_id,
+            activity_id: c.activity_id,
+            tracked: c.tracked,
+        })
+        .collect())
+}
+
+#[cfg(feature = "server")]
+async fn _import_calendar_events(
+    events: Vec<ImportSelectionDto>,
+) -> Result<Vec<TimesheetDto>, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+    use loom::tenant::calendar::ImportSelection;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_CREATE).await?;
+
+    let selections = events
+        .into_iter()
+        .map(|e| ImportSelection {
+            title: e.title,
+            start_time: e.start_time,
+            end_time: e.end_time,
+            project_id: e.project_id,
+            activity_id: e.activity_id,
+            billable: e.billable,
+        })
+        .collect();
+    let rows = loom::tenant::calendar::import(&workspace_id, &user.id, selections)
+        .await
+        .map_err(session::internal)?;
+    Ok(rows.into_iter().map(crate::timesheet::row_to_dto).collect())
+}
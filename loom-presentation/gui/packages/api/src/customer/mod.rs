@@ -1,6 +1,8 @@
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::settings::RoundingDto;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CustomerDto {
     pub id: String,
@@ -19,7 +21,7 @@ pub struct CustomerDto {
 pub async fn list_customers() -> Result<Vec<CustomerDto>, ServerFnError> {
     #[cfg(feature = "server")]
     {
-        _list_customers().await
+        _list_customers(false).await
     }
     #[cfg(not(feature = "server"))]
     {
@@ -66,6 +68,39 @@ pub async fn set_customer_budget(
     }
 }
 
+/// The customer's own rounding rules, `None` when it uses the
+/// workspace rules.
+#[get("/api/customers/rounding")]
+pub async fn get_customer_rounding(id: String) -> Result<Option<RoundingDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _get_customer_rounding(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(None)
+    }
+}
+
+/// Set the customer's own rounding rules; `None` falls back to the
+/// workspace rules.
+#[post("/api/customers/rounding")]
+pub async fn set_customer_rounding(
+    id: String,
+    rounding: Option<RoundingDto>,
+) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _set_customer_rounding(id, rounding).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, rounding);
+        Ok(())
+    }
+}
+
 #[post("/api/customers/update")]
 pub async fn update_customer(
     id: String,
@@ -87,14 +122,78 @@ pub async fn update_customer(
     }
 }
 
+/// Archived customers, hidden from [`list_customers`].
+#[get("/api/customers/archived")]
+pub async fn list_archived_customers() -> Result<Vec<CustomerDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_customers(true).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+/// Hide a customer from lists and selectors.
+///
+/// Its projects are archived along with it.
+#[post("/api/customers/archive")]
+pub async fn archive_customer(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _archive_customer(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+/// Bring an archived customer back.
+///
+/// Projects archived along with it are restored as well.
+#[post("/api/customers/restore")]
+pub async fn restore_customer(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _restore_customer(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+/// Permanently delete a customer.
+///
+/// Only allowed while the customer has no projects.
+#[post("/api/customers/delete")]
+pub async fn delete_customer(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _delete_customer(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
 #[cfg(feature = "server")]
-async fn _list_customers() -> Result<Vec<CustomerDto>, ServerFnError> {
+async fn _list_customers(archived: bool) -> Result<Vec<CustomerDto>, ServerFnError> {
     use crate::session;
 
     let (_, workspace_id) = session::session_workspace().await?;
-    let rows = loom::tenant::customer::list(&workspace_id)
-        .await
-        .map_err(session::internal)?;
+    let rows = if archived {
+        loom::tenant::customer::list_archived(&workspace_id).await
+    } else {
+        loom::tenant::customer::list(&workspace_id).await
+    }
+    .map_err(session::internal)?;
     Ok(rows
         .into_iter()
         .map(|r| CustomerDto {
@@ -194,3 +293,70 @@ async fn _set_customer_budget(
     .await
     .map_err(session::internal)
 }
+
+#[cfg(feature = "server")]
+async fn _get_customer_rounding(id: String) -> Result<Option<RoundingDto>, ServerFnError> {
+    use crate::session;
+
+    let (_, workspace_id) = session::session_workspace().await?;
+    let rounding = loom::tenant::customer::rounding(&workspace_id, &id)
+        .await
+        .map_err(session::internal)?;
+    Ok(rounding.map(Into::into))
+}
+
+#[cfg(feature = "server")]
+async fn _set_customer_rounding(
+    id: String,
+    rounding: Option<RoundingDto>,
+) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::CUSTOMER_UPDATE).await?;
+
+    let rounding = rounding.map(RoundingDto::into_rounding).transpose()?;
+    loom::tenant::customer::set_rounding(&workspace_id, &id, rounding)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _archive_customer(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::CUSTOMER_UPDATE).await?;
+
+    loom::tenant::customer::archive(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _restore_customer(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::CUSTOMER_UPDATE).await?;
+
+    loom::tenant::customer::restore(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _delete_customer(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::CUSTOMER_DELETE).await?;
+
+    loom::tenant::customer::delete(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
@@ -1,4 +1,5 @@
 use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Migrates the admin database. Admin-only.
 #[post("/api/database/migrate")]
@@ -195,3 +196,225 @@ async fn _migrate_tenant_database() -> Result<(), ServerFnError> {
 
     Ok(())
 }
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectionStatusDto {
+    pub runner: String,
+    /// Global position of the last projected event.
+    pub position: i64,
+    /// Global position of the newest event in the store.
+    pub head_position: i64,
+    pub lag_events: i64,
+    pub lag_seconds: Option<i64>,
+    pub last_error: Option<String>,
+    pub error_position: Option<i64>,
+    pub error_stream_id: Option<String>,
+    pub error_event_type: Option<String>,
+    pub error_payload: Option<String>,
+    pub retry_count: i32,
+    /// Events parked in the dead-letter table.
+    pub parked: i64,
+    pub updated_at: String,
+}
+
+/// Status of every projection runner (admin and all workspaces). Admin-only.
+#[get("/api/developer/projections")]
+pub async fn projection_status() -> Result<Vec<ProjectionStatusDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _projection_status().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _projection_status() -> Result<Vec<ProjectionStatusDto>, ServerFnError> {
+    use crate::session;
+
+    let user = session::session_user().await?;
+    session::require_admin(&user).await?;
+
+    let statuses = loom::projection_health::all_status()
+        .await
+        .map_err(session::internal)?;
+    Ok(statuses
+        .into_iter()
+        .map(|s| ProjectionStatusDto {
+            runner: s.runner,
+            position: s.position,
+            head_position: s.head_position,
+            lag_events: s.lag_events,
+            lag_seconds: s.lag_seconds,
+            last_error: s.last_error,
+            error_position: s.error_position,
+            error_stream_id: s.error_stream_id,
+            error_event_type: s.error_event_type,
+            error_payload: s.error_payload,
+            retry_count: s.retry_count,
+            parked: s.parked,
+            updated_at: s.updated_at,
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetterDto {
+    /// `"admin"` or the workspace ID whose database holds the event.
+    pub database: String,
+    pub id: i64,
+    pub runner: String,
+    pub global_position: i64,
+    pub stream_id: String,
+    pub event_type: String,
+    pub payload: String,
+    pub error: String,
+    pub retry_count: i32,
+    pub parked_at: String,
+}
+
+/// Events parked by any projection runner. Admin-only.
+#[get("/api/developer/dead-letters")]
+pub async fn list_dead_letters() -> Result<Vec<DeadLetterDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_dead_letters().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+/// Re-apply a parked event after a fix. Admin-only.
+#[post("/api/developer/dead-letters/replay")]
+pub async fn replay_dead_letter(database: String, id: i64) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _resolve_dead_letter(database, id, true).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (database, id);
+        Ok(())
+    }
+}
+
+/// Drop a parked event for good. Admin-only.
+#[post("/api/developer/dead-letters/skip")]
+pub async fn skip_dead_letter(database: String, id: i64) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _resolve_dead_letter(database, id, false).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (database, id);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "server")]
+const ADMIN_DATABASE: &str = "admin";
+
+#[cfg(feature = "server")]
+async fn _list_dead_letters() -> Result<Vec<DeadLetterDto>, ServerFnError> {
+    use crate::session;
+    use loom::projection_health::ProjectionDatabase;
+
+    let user = session::session_user().await?;
+    session::require_admin(&user).await?;
+
+    let parked = loom::projection_health::all_dead_letters()
+        .await
+        .map_err(session::internal)?;
+    Ok(parked
+        .into_iter()
+        .map(|(database, r)| DeadLetterDto {
+            database: match database {
+                ProjectionDatabase::Admin => ADMIN_DATABASE.to_string(),
+                ProjectionDatabase::Tenant(workspace_id) => workspace_id,
+            },
+            id: r.id,
+            runner: r.runner,
+            global_position: r.global_position,
+            stream_id: r.stream_id,
+            event_type: r.event_type,
+            payload: r.payload,
+            error: r.error,
+            retry_count: r.retry_count,
+            parked_at: r.parked_at,
+        })
+        .collect())
+}
+
+#[cfg(feature = "server")]
+async fn _resolve_dead_letter(database: String, id: i64, replay: bool) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::projection_health::ProjectionDatabase;
+
+    let user = session::session_user().await?;
+    session::require_admin(&user).await?;
+
+    let database = if database == ADMIN_DATABASE {
+        ProjectionDatabase::Admin
+    } else {
+        ProjectionDatabase::Tenant(database)
+    };
+    if replay {
+        loom::projection_health::replay_dead_letter(&database, id).await
+    } else {
+        loom::projection_health::skip_dead_letter(&database, id).await
+    }
+    .map_err(session::internal)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregateLoadDto {
+    pub aggregate_type: String,
+    pub loads: u64,
+    /// Loads that started from a snapshot.
+    pub snapshot_hits: u64,
+    pub events_replayed: u64,
+    pub average_micros: u64,
+    pub max_micros: u64,
+    pub snapshots_taken: u64,
+    pub snapshots_invalidated: u64,
+}
+
+/// Aggregate load times and snapshot usage of this server process. Admin-only.
+#[get("/api/developer/aggregate-loads")]
+pub async fn aggregate_loads() -> Result<Vec<AggregateLoadDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _aggregate_loads().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _aggregate_loads() -> Result<Vec<AggregateLoadDto>, ServerFnError> {
+    use crate::session;
+
+    let user = session::session_user().await?;
+    session::require_admin(&user).await?;
+
+    Ok(loom::infrastructure::snapshot::load_metrics()
+        .into_iter()
+        .map(|m| AggregateLoadDto {
+            average_micros: m.average_micros(),
+            aggregate_type: m.aggregate_type,
+            loads: m.loads,
+            snapshot_hits: m.snapshot_hits,
+            events_replayed: m.events_replayed,
+            max_micros: m.max_micros,
+            snapshots_taken: m.snapshots_taken,
+            snapshots_invalidated: m.snapshots_invalidated,
+        })
+        .collect())
+}
@@ -0,0 +1,196 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::timesheet::TimesheetDto;
+
+/// A combination of project, activity, description and tags the user pinned.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FavoriteDto {
+    pub id: String,
+    pub project_id: String,
+    pub activity_id: String,
+    pub description: Option<String>,
+    pub tag_ids: Vec<String>,
+}
+
+/// A combination the user worked on recently.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CombinationDto {
+    pub project_id: String,
+    pub activity_id: String,
+    pub description: Option<String>,
+    pub tag_ids: Vec<String>,
+    pub use_count: i32,
+    pub last_used_at: String,
+    /// Restarting this entry repeats the combination.
+    pub last_timesheet_id: String,
+}
+
+/// Everything a timer can be quick-started from, in one request.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuickStartDto {
+    pub favorites: Vec<FavoriteDto>,
+    pub recent: Vec<CombinationDto>,
+    pub frequent: Vec<CombinationDto>,
+}
+
+#[get("/api/quick-start")]
+pub async fn quick_start() -> Result<QuickStartDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _quick_start().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(QuickStartDto::default())
+    }
+}
+
+#[post("/api/favorites/pin")]
+pub async fn pin_favorite(
+    project_id: String,
+    activity_id: String,
+    description: Option<String>,
+    tag_ids: Vec<String>,
+) -> Result<FavoriteDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _pin_favorite(project_id, activity_id, description, tag_ids).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (project_id, activity_id, description, tag_ids);
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
+#[post("/api/favorites/unpin")]
+pub async fn unpin_favorite(favorite_id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _unpin_favorite(favorite_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = favorite_id;
+        Ok(())
+    }
+}
+
+/// Start a timer from a favorite.
+#[post("/api/favorites/start")]
+pub async fn start_favorite(favorite_id: String, billable: bool) -> Result<TimesheetDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _start_favorite(favorite_id, billable).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (favorite_id, billable);
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
+#[cfg(feature = "server")]
+fn favorite_to_dto(
+    r: loom::infrastructure::tenant::favorite::repositories::FavoriteRow,
+) -> FavoriteDto {
+    FavoriteDto {
+        id: r.id,
+        project_id: r.project_id,
+        activity_id: r.activity_id,
+        description: r.description,
+        tag_ids: r.tag_ids,
+    }
+}
+
+#[cfg(feature = "server")]
+fn combination_to_dto(
+    r: loom::infrastructure::tenant::combination::repositories::CombinationRow,
+) -> CombinationDto {
+    CombinationDto {
+        project_id: r.project_id,
+        activity_id: r.activity_id,
+        description: r.description,
+        tag_ids: r.tag_ids,
+        use_count: r.use_count,
+        last_used_at: r.last_used_at,
+        last_timesheet_id: r.last_timesheet_id,
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _quick_start() -> Result<QuickStartDto, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let favorites = loom::tenant::favorite::list(&workspace_id, &user.id)
+        .await
+        .map_err(session::internal)?;
+    let recent = loom::tenant::favorite::recent(&workspace_id, &user.id)
+        .await
+        .map_err(session::internal)?;
+    let frequent = loom::tenant::favorite::frequent(&workspace_id, &user.id)
+        .await
+        .map_err(session::internal)?;
+    Ok(QuickStartDto {
+        favorites: favorites.into_iter().map(favorite_to_dto).collect(),
+        recent: recent.into_iter().map(combination_to_dto).collect(),
+        frequent: frequent.into_iter().map(combination_to_dto).collect(),
+    })
+}
+
+#[cfg(feature = "server")]
+async fn _pin_favorite(
+    project_id: String,
+    activity_id: String,
+    description: Option<String>,
+    tag_ids: Vec<String>,
+) -> Result<FavoriteDto, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let r = loom::tenant::favorite::pin(
+        &workspace_id,
+        &user.id,
+        &project_id,
+        &activity_id,
+        description,
+        tag_ids,
+    )
+    .await
+    .map_err(session::internal)?;
+    Ok(favorite_to_dto(r))
+}
+
+#[cfg(feature = "server")]
+async fn _unpin_favorite(favorite_id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    loom::tenant::favorite::unpin(&workspace_id, &user.id, &favorite_id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _start_favorite(favorite_id: String, billable: bool) -> Result<TimesheetDto, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_CREATE).await?;
+
+    let r = loom::tenant::favorite::start(&workspace_id, &user.id, &favorite_id, billable)
+        .await
+        .map_err(session::internal)?;
+    Ok(crate::timesheet::row_to_dto(r))
+}
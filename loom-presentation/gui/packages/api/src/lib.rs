@@ -2,15 +2,30 @@
 
 pub mod activity;
 pub mod activity_rate;
+pub mod api_token;
+pub mod approval;
+pub mod audit;
 pub mod auth;
+pub mod budget;
+pub mod calendar;
 pub mod customer;
 pub mod developer;
+pub mod favorite;
+pub mod live;
 pub mod login;
 pub mod project;
 pub mod project_rate;
+pub mod report;
+#[cfg(feature = "server")]
+pub mod rest;
+pub mod search;
 pub mod session;
 pub mod settings;
 pub mod setup;
+pub mod sync;
 pub mod tag;
+pub mod time_travel;
 pub mod timesheet;
+pub mod webhook;
+pub mod work_contract;
 pub mod workspace;
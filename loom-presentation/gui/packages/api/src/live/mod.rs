@@ -0,0 +1,61 @@
+//! Live updates of the current workspace, streamed as server-sent events
+//! from [`LIVE_PATH`].
+//!
+//! Each `change` event carries one [`Topic`] as its data; clients reload what
+//! they cache of it.  Changes to timesheets only reach the user who tracked
+//! them.
+
+/// Where the event stream is served, with the session cookie or an API
+/// token.
+pub const LIVE_PATH: &str = "/api/v1/live";
+
+/// Name of the server-sent events that announce a change.
+pub const CHANGE_EVENT: &str = "change";
+
+/// A part of the workspace that changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topic {
+    /// Timesheets, including the running timer.
+    Timesheets,
+    Approvals,
+    Customers,
+    Projects,
+    Activities,
+    Tags,
+    Rates,
+    Favorites,
+    WorkContracts,
+    Holidays,
+}
+
+impl Topic {
+    /// The topic named by the data of a `change` event; `None` for topics
+    /// this client does not know yet.
+    pub fn parse(data: &str) -> Option<Self> {
+        Some(match data.trim() {
+            "timesheets" => Self::Timesheets,
+            "approvals" => Self::Approvals,
+            "customers" => Self::Customers,
+            "projects" => Self::Projects,
+            "activities" => Self::Activities,
+            "tags" => Self::Tags,
+            "rates" => Self::Rates,
+            "favorites" => Self::Favorites,
+            "work_contracts" => Self::WorkContracts,
+            "holidays" => Self::Holidays,
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_server_topic_parses() {
+        for topic in loom::live::Topic::ALL {
+            assert!(Topic::parse(topic.as_str()).is_some(), "{topic:?}");
+        }
+    }
+}
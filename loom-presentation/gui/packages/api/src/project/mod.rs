@@ -1,6 +1,8 @@
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::settings::RoundingDto;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProjectDto {
     pub id: String,
@@ -19,7 +21,7 @@ pub struct ProjectDto {
 pub async fn list_projects() -> Result<Vec<ProjectDto>, ServerFnError> {
     #[cfg(feature = "server")]
     {
-        _list_projects().await
+        _list_projects(false).await
     }
     #[cfg(not(feature = "server"))]
     {
@@ -85,14 +87,107 @@ pub async fn set_project_budget(
     }
 }
 
+/// The project's own rounding rules, `None` when it uses the
+/// customer's rules.
+#[get("/api/projects/rounding")]
+pub async fn get_project_rounding(id: String) -> Result<Option<RoundingDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _get_project_rounding(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(None)
+    }
+}
+
+/// Set the project's own rounding rules; `None` falls back to the
+/// customer's rules.
+#[post("/api/projects/rounding")]
+pub async fn set_project_rounding(
+    id: String,
+    rounding: Option<RoundingDto>,
+) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _set_project_rounding(id, rounding).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, rounding);
+        Ok(())
+    }
+}
+
+/// Archived projects, hidden from [`list_projects`].
+#[get("/api/projects/archived")]
+pub async fn list_archived_projects() -> Result<Vec<ProjectDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_projects(true).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+/// Hide a project from lists and selectors.
+#[post("/api/projects/archive")]
+pub async fn archive_project(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _archive_project(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+/// Bring an archived project back.
+#[post("/api/projects/restore")]
+pub async fn restore_project(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _restore_project(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+/// Permanently delete a project.
+///
+/// Only allowed while no timesheets are booked on the project and it has no activities of its own.
+#[post("/api/projects/delete")]
+pub async fn delete_project(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _delete_project(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
 #[cfg(feature = "server")]
-async fn _list_projects() -> Result<Vec<ProjectDto>, ServerFnError> {
+async fn _list_projects(archived: bool) -> Result<Vec<ProjectDto>, ServerFnError> {
     use crate::session;
 
     let (_, workspace_id) = session::session_workspace().await?;
-    let rows = loom::tenant::project::list(&workspace_id)
-        .await
-        .map_err(session::internal)?;
+    let rows = if archived {
+        loom::tenant::project::list_archived(&workspace_id).await
+    } else {
+        loom::tenant::project::list(&workspace_id).await
+    }
+    .map_err(session::internal)?;
     Ok(rows
         .into_iter()
         .map(|r| ProjectDto {
@@ -186,3 +281,70 @@ async fn _set_project_budget(
     .await
     .map_err(session::internal)
 }
+
+#[cfg(feature = "server")]
+async fn _get_project_rounding(id: String) -> Result<Option<RoundingDto>, ServerFnError> {
+    use crate::session;
+
+    let (_, workspace_id) = session::session_workspace().await?;
+    let rounding = loom::tenant::project::rounding(&workspace_id, &id)
+        .await
+        .map_err(session::internal)?;
+    Ok(rounding.map(Into::into))
+}
+
+#[cfg(feature = "server")]
+async fn _set_project_rounding(
+    id: String,
+    rounding: Option<RoundingDto>,
+) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::PROJECT_UPDATE).await?;
+
+    let rounding = rounding.map(RoundingDto::into_rounding).transpose()?;
+    loom::tenant::project::set_rounding(&workspace_id, &id, rounding)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _archive_project(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::PROJECT_UPDATE).await?;
+
+    loom::tenant::project::archive(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _restore_project(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::PROJECT_UPDATE).await?;
+
+    loom::tenant::project::restore(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _delete_project(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::PROJECT_DELETE).await?;
+
+    loom::tenant::project::delete(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
@@ -62,7 +62,7 @@ pub async fn remove_project_rate(project_id: String) -> Result<(), ServerFnError
 }
 
 #[cfg(feature = "server")]
-fn row_to_dto(
+pub(crate) fn row_to_dto(
     r: loom::infrastructure::tenant::project_rate::repositories::ProjectRateRow,
 ) -> ProjectRateDto {
     ProjectRateDto {
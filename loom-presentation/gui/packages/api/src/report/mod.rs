@@ -0,0 +1,231 @@
+//! Reports of hours and amounts per project, emailed on a schedule.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A report emailed on a schedule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportScheduleDto {
+    pub id: String,
+    pub name: String,
+    /// `personal` or `workspace`.
+    pub scope: String,
+    /// Cron expression in UTC, e.g. `0 8 * * mon`.
+    pub schedule: String,
+    /// `previous_day`, `previous_week` or `previous_month`.
+    pub period: String,
+    /// Only these projects; all if empty.
+    pub project_ids: Vec<String>,
+    /// `html` or `csv`.
+    pub format: String,
+    pub recipients: Vec<String>,
+    /// RFC-3339; when the report is sent next.
+    pub next_run_at: Option<String>,
+    pub last_sent_at: Option<String>,
+    /// Why the report could not be sent last time it was due.
+    pub last_error: Option<String>,
+}
+
+/// What a report covers and where it goes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportScheduleInput {
+    pub name: String,
+    pub schedule: String,
+    pub period: String,
+    pub project_ids: Vec<String>,
+    pub format: String,
+    pub recipients: Vec<String>,
+}
+
+/// Your scheduled reports.
+#[get("/api/reports/schedules")]
+pub async fn list_report_schedules() -> Result<Vec<ReportScheduleDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_report_schedules().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+/// Reports covering the whole workspace (`scope` `workspace`) need the
+/// `report.manage` permission.
+#[post("/api/reports/schedules/create")]
+pub async fn create_report_schedule(
+    scope: String,
+    input: ReportScheduleInput,
+) -> Result<ReportScheduleDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _create_report_schedule(scope, input).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (scope, input);
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
+#[post("/api/reports/schedules/change")]
+pub async fn change_report_schedule(
+    id: String,
+    input: ReportScheduleInput,
+) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _change_report_schedule(id, input).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, input);
+        Ok(())
+    }
+}
+
+#[post("/api/reports/schedules/remove")]
+pub async fn remove_report_schedule(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _remove_report_schedule(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+/// Sends the report right away, as if it were due today.
+#[post("/api/reports/schedules/send")]
+pub async fn send_report_now(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _send_report_now(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "server")]
+fn definition(input: ReportScheduleInput) -> loom::tenant::report::ReportDefinition {
+    loom::tenant::report::ReportDefinition {
+        name: input.name,
+        schedule: input.schedule,
+        period: input.period,
+        project_ids: input.project_ids,
+        format: input.format,
+        recipients: input.recipients,
+    }
+}
+
+#[cfg(feature = "server")]
+fn schedule_to_dto(
+    r: loom::infrastructure::tenant::report_schedule::repositories::ReportScheduleRow,
+    run: Option<&loom::infrastructure::tenant::report_schedule::repositories::ReportRunRow>,
+) -> ReportScheduleDto {
+    let next_run_at = loom::tenant::report::next_run_at(&r);
+    ReportScheduleDto {
+        id: r.id,
+        name: r.name,
+        scope: r.scope.as_str().to_string(),
+        schedule: r.schedule,
+        period: r.parameters.period.as_str().to_string(),
+        project_ids: r
+            .parameters
+            .project_ids
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        format: r.format.as_str().to_string(),
+        recipients: r.recipients,
+        next_run_at,
+        last_sent_at: run.and_then(|run| run.last_sent_at.clone()),
+        last_error: run.and_then(|run| run.last_error.clone()),
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _list_report_schedules() -> Result<Vec<ReportScheduleDto>, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let rows = loom::tenant::report::schedules(&workspace_id, &user.id)
+        .await
+        .map_err(session::internal)?;
+    let runs = loom::tenant::report::runs(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let run = runs.iter().find(|run| run.schedule_id == r.id);
+            schedule_to_dto(r, run)
+        })
+        .collect())
+}
+
+#[cfg(feature = "server")]
+async fn _create_report_schedule(
+    scope: String,
+    input: ReportScheduleInput,
+) -> Result<ReportScheduleDto, ServerFnError> {
+    use crate::session;
+    use loom::core::{permissions, tenant::report_schedule::ReportScope};
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let scope = ReportScope::parse(&scope).ok_or_else(|| ServerFnError::ServerError {
+        message: format!("Unknown scope: {scope}"),
+        code: 422,
+        details: None,
+    })?;
+    if scope == ReportScope::Workspace {
+        session::require_permission(&user, permissions::REPORT_MANAGE).await?;
+    }
+
+    let row = loom::tenant::report::create(&workspace_id, &user.id, scope, &definition(input))
+        .await
+        .map_err(session::internal)?;
+    Ok(schedule_to_dto(row, None))
+}
+
+#[cfg(feature = "server")]
+async fn _change_report_schedule(
+    id: String,
+    input: ReportScheduleInput,
+) -> Result<(), ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    loom::tenant::report::change(&workspace_id, &user.id, &id, &definition(input))
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _remove_report_schedule(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    loom::tenant::report::remove(&workspace_id, &user.id, &id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _send_report_now(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    loom::tenant::report::send_now(&workspace_id, &user.id, &id)
+        .await
+        .map_err(session::internal)
+}
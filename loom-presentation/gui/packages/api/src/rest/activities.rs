@@ -0,0 +1,120 @@
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::Json;
+use loom::core::permissions;
+use loom::infrastructure::tenant::activity::repositories::ActivityRow;
+use serde::{Deserialize, Serialize};
+
+use super::error::ApiError;
+use super::extract::{Body, Caller, Params};
+use super::page::{ListParams, Page};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Activity {
+    pub id: String,
+    /// `None` for global activities available in every project.
+    pub project_id: Option<String>,
+    pub name: String,
+    pub comment: Option<String>,
+    pub visible: bool,
+    pub billable: bool,
+    pub archived: bool,
+}
+
+impl From<ActivityRow> for Activity {
+    fn from(r: ActivityRow) -> Self {
+        Self {
+            id: r.id,
+            project_id: r.project_id,
+            name: r.name,
+            comment: r.comment,
+            visible: r.visible,
+            billable: r.billable,
+            archived: r.archived,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewActivity {
+    pub project_id: Option<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivityChanges {
+    pub name: String,
+    pub comment: Option<String>,
+    pub visible: bool,
+    pub billable: bool,
+}
+
+pub(super) async fn find(workspace_id: &str, id: &str) -> Result<ActivityRow, ApiError> {
+    let mut rows = loom::tenant::activity::list(workspace_id).await?;
+    rows.extend(loom::tenant::activity::list_archived(workspace_id).await?);
+    rows.into_iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| ApiError::not_found("activity"))
+}
+
+pub async fn list(
+    caller: Caller,
+    Params(params): Params<ListParams>,
+) -> Result<Json<Page<Activity>>, ApiError> {
+    let workspace_id = caller.workspace()?;
+    let rows = if params.archived.unwrap_or(false) {
+        loom::tenant::activity::list_archived(workspace_id).await?
+    } else {
+        loom::tenant::activity::list(workspace_id).await?
+    };
+    let items = rows.into_iter().map(Activity::from).collect();
+    Ok(Json(Page::of(items, params.page, params.per_page)?))
+}
+
+pub async fn create(
+    caller: Caller,
+    Body(input): Body<NewActivity>,
+) -> Result<(StatusCode, Json<Activity>), ApiError> {
+    caller.require(permissions::ACTIVITY_CREATE).await?;
+    let row =
+        loom::tenant::activity::create(caller.workspace()?, input.project_id, input.name).await?;
+    Ok((StatusCode::CREATED, Json(row.into())))
+}
+
+pub async fn show(caller: Caller, Path(id): Path<String>) -> Result<Json<Activity>, ApiError> {
+    Ok(Json(find(caller.workspace()?, &id).await?.into()))
+}
+
+pub async fn update(
+    caller: Caller,
+    Path(id): Path<String>,
+    Body(input): Body<ActivityChanges>,
+) -> Result<Json<Activity>, ApiError> {
+    caller.require(permissions::ACTIVITY_UPDATE).await?;
+    let workspace_id = caller.workspace()?;
+    let current = find(workspace_id, &id).await?;
+    loom::tenant::activity::update(
+        workspace_id,
+        &id,
+        input.name.clone(),
+        input.comment.clone(),
+        input.visible,
+        input.billable,
+    )
+    .await?;
+    Ok(Json(Activity {
+        name: input.name,
+        comment: input.comment,
+        visible: input.visible,
+        billable: input.billable,
+        ..Activity::from(current)
+    }))
+}
+
+pub async fn delete(caller: Caller, Path(id): Path<String>) -> Result<StatusCode, ApiError> {
+    caller.require(permissions::ACTIVITY_DELETE).await?;
+    let workspace_id = caller.workspace()?;
+    find(workspace_id, &id).await?;
+    loom::tenant::activity::delete(workspace_id, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
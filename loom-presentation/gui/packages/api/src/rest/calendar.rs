@@ -0,0 +1,21 @@
+//! Private iCalendar feeds of timesheets; see [`crate::calendar`].
+
+use axum::extract::Path;
+use axum::http::header;
+use axum::response::IntoResponse;
+
+use super::error::ApiError;
+
+/// The feed the token in the path belongs to.  Calendar apps get the token
+/// as part of the URL, so no other authentication is needed; a trailing
+/// `.ics` is ignored.
+pub async fn feed(Path(token): Path<String>) -> Result<impl IntoResponse, ApiError> {
+    let token = token.strip_suffix(".ics").unwrap_or(&token);
+    let ics = loom::tenant::calendar::render_feed(token)
+        .await?
+        .ok_or_else(|| ApiError::not_found("calendar feed"))?;
+    Ok((
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        ics,
+    ))
+}
@@ -0,0 +1,143 @@
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::Json;
+use loom::core::permissions;
+use loom::infrastructure::tenant::customer::repositories::CustomerRow;
+use serde::{Deserialize, Serialize};
+
+use super::error::ApiError;
+use super::extract::{Body, Caller, Params};
+use super::page::{ListParams, Page};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Customer {
+    pub id: String,
+    pub name: String,
+    pub comment: Option<String>,
+    pub currency: String,
+    pub timezone: String,
+    pub country: Option<String>,
+    pub visible: bool,
+    /// Seconds.
+    pub time_budget: Option<i32>,
+    /// Cents.
+    pub money_budget: Option<i64>,
+    pub budget_is_monthly: bool,
+    pub archived: bool,
+}
+
+impl From<CustomerRow> for Customer {
+    fn from(r: CustomerRow) -> Self {
+        Self {
+            id: r.id,
+            name: r.name,
+            comment: r.comment,
+            currency: r.currency,
+            timezone: r.timezone,
+            country: r.country,
+            visible: r.visible,
+            time_budget: r.time_budget,
+            money_budget: r.money_budget,
+            budget_is_monthly: r.budget_is_monthly,
+            archived: r.archived,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewCustomer {
+    pub name: String,
+    pub currency: String,
+    pub timezone: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomerChanges {
+    pub name: String,
+    pub comment: Option<String>,
+    pub currency: String,
+    pub timezone: String,
+    pub country: Option<String>,
+    pub visible: bool,
+}
+
+async fn find(workspace_id: &str, id: &str) -> Result<CustomerRow, ApiError> {
+    let mut rows = loom::tenant::customer::list(workspace_id).await?;
+    rows.extend(loom::tenant::customer::list_archived(workspace_id).await?);
+    rows.into_iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| ApiError::not_found("customer"))
+}
+
+pub async fn list(
+    caller: Caller,
+    Params(params): Params<ListParams>,
+) -> Result<Json<Page<Customer>>, ApiError> {
+    let workspace_id = caller.workspace()?;
+    let rows = if params.archived.unwrap_or(false) {
+        loom::tenant::customer::list_archived(workspace_id).await?
+    } else {
+        loom::tenant::customer::list(workspace_id).await?
+    };
+    let items = rows.into_iter().map(Customer::from).collect();
+    Ok(Json(Page::of(items, params.page, params.per_page)?))
+}
+
+pub async fn create(
+    caller: Caller,
+    Body(input): Body<NewCustomer>,
+) -> Result<(StatusCode, Json<Customer>), ApiError> {
+    caller.require(permissions::CUSTOMER_CREATE).await?;
+    let row = loom::tenant::customer::create(
+        caller.workspace()?,
+        input.name,
+        input.currency,
+        input.timezone,
+    )
+    .await?;
+    Ok((StatusCode::CREATED, Json(row.into())))
+}
+
+pub async fn show(caller: Caller, Path(id): Path<String>) -> Result<Json<Customer>, ApiError> {
+    Ok(Json(find(caller.workspace()?, &id).await?.into()))
+}
+
+pub async fn update(
+    caller: Caller,
+    Path(id): Path<String>,
+    Body(input): Body<CustomerChanges>,
+) -> Result<Json<Customer>, ApiError> {
+    caller.require(permissions::CUSTOMER_UPDATE).await?;
+    let workspace_id = caller.workspace()?;
+    let current = find(workspace_id, &id).await?;
+    loom::tenant::customer::update(
+        workspace_id,
+        &id,
+        input.name.clone(),
+        input.comment.clone(),
+        input.currency.clone(),
+        input.timezone.clone(),
+        input.country.clone(),
+        input.visible,
+    )
+    .await?;
+    // Projections catch up asynchronously, so answer with the changes applied
+    // rather than re-reading them.
+    Ok(Json(Customer {
+        name: input.name,
+        comment: input.comment,
+        currency: input.currency,
+        timezone: input.timezone,
+        country: input.country,
+        visible: input.visible,
+        ..Customer::from(current)
+    }))
+}
+
+pub async fn delete(caller: Caller, Path(id): Path<String>) -> Result<StatusCode, ApiError> {
+    caller.require(permissions::CUSTOMER_DELETE).await?;
+    let workspace_id = caller.workspace()?;
+    find(workspace_id, &id).await?;
+    loom::tenant::customer::delete(workspace_id, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,146 @@
+//! Extractors that answer with the [`ApiError`] envelope instead of axum's
+//! plain-text rejections.
+
+use axum::extract::{FromRequest, FromRequestParts, Query, Request};
+use axum::http::header::AUTHORIZATION;
+use axum::http::request::Parts;
+use axum::http::HeaderMap;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::de::DeserializeOwned;
+use tower_sessions::Session;
+
+use super::error::ApiError;
+use crate::auth::UserInfo;
+
+/// The owner of the bearer API token, or else the user of the session
+/// cookie set by `/api/login`.
+pub struct Caller {
+    pub user: UserInfo,
+}
+
+impl Caller {
+    /// The selected workspace, or 401 if none is selected yet.
+    pub fn workspace(&self) -> Result<&str, ApiError> {
+        self.user
+            .workspace_id
+            .as_deref()
+            .ok_or_else(|| ApiError::unauthorized("no workspace selected"))
+    }
+
+    /// 403 unless the caller holds `permission` in their current workspace.
+    pub async fn require(&self, permission: &str) -> Result<(), ApiError> {
+        use loom::auth::CurrentUser;
+        use loom::authorization::AuthorizationService;
+
+        let workspace_id = self.workspace()?;
+        let current_user = CurrentUser {
+            id: self.user.id.clone(),
+            email: self.user.email.clone(),
+        };
+        AuthorizationService::require_permission(&current_user, workspace_id, permission)
+            .await
+            .map_err(|_| ApiError::forbidden())
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Caller {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(TokenUser(user)) = parts.extensions.get::<TokenUser>() {
+            return Ok(Self { user: user.clone() });
+        }
+        if let Some(token) = bearer_token(&parts.headers) {
+            return token_user(token).await.map(|user| Self { user });
+        }
+
+        let session = Session::from_request_parts(parts, state)
+            .await
+            .map_err(|(_, message)| ApiError::internal(message))?;
+        let user: Option<UserInfo> = session
+            .get("user")
+            .await
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+        user.map(|user| Self { user })
+            .ok_or_else(|| ApiError::unauthorized("not authenticated"))
+    }
+}
+
+/// The owner of the request's bearer token, as resolved by [`token_actor`].
+#[derive(Clone)]
+struct TokenUser(UserInfo);
+
+/// Records the owner of the bearer token as the actor of every event
+/// emitted while the request is handled, as the apps do for the session
+/// user.  Unknown or revoked tokens are refused with a 401 right away.
+pub async fn token_actor(mut request: Request, next: Next) -> Response {
+    let Some(token) = bearer_token(request.headers()) else {
+        return next.run(request).await;
+    };
+    match token_user(token).await {
+        Ok(user) => {
+            let actor = user.id.clone();
+            request.extensions_mut().insert(TokenUser(user));
+            loom::audit::with_actor(actor, next.run(request)).await
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+/// A token that is unknown or revoked is a 401, not a fallback to the session.
+async fn token_user(token: &str) -> Result<UserInfo, ApiError> {
+    use loom::authorization::AuthorizationService;
+
+    let owner = loom::api_token::authenticate(token)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .ok_or_else(|| ApiError::unauthorized("invalid API token"))?;
+    let is_admin = AuthorizationService::is_admin(&owner.user.id)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    Ok(UserInfo {
+        id: owner.user.id,
+        email: owner.user.email,
+        is_admin,
+        workspace_id: Some(owner.workspace_id),
+    })
+}
+
+/// A JSON request body; malformed bodies are a 400.
+pub struct Body<T>(pub T);
+
+impl<S: Send + Sync, T: DeserializeOwned> FromRequest<S> for Body<T> {
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(|e| ApiError::bad_request(e.body_text()))?;
+        Ok(Self(value))
+    }
+}
+
+/// Query string parameters; malformed ones are a 400.
+pub struct Params<T>(pub T);
+
+impl<S: Send + Sync, T: DeserializeOwned> FromRequestParts<S> for Params<T> {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(|e| ApiError::bad_request(e.body_text()))?;
+        Ok(Self(value))
+    }
+}
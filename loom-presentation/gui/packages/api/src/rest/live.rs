@@ -0,0 +1,39 @@
+//! Live updates of the caller's workspace; see [`crate::live`].
+
+use std::convert::Infallible;
+
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures::stream::{self, Stream, StreamExt};
+
+use super::error::ApiError;
+use super::extract::Caller;
+use crate::live::CHANGE_EVENT;
+
+/// A `change` event per topic that changed for the caller, for as long as
+/// the connection stays open.
+pub async fn stream(
+    caller: Caller,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let subscription = loom::live::subscribe(caller.workspace()?);
+    let user_id = caller.user.id;
+    let topics = stream::unfold(
+        (subscription, user_id),
+        |(mut subscription, user_id)| async move {
+            while let Some(changes) = subscription.recv().await {
+                let topics = changes.topics_for(&user_id);
+                if !topics.is_empty() {
+                    return Some((topics, (subscription, user_id)));
+                }
+            }
+            None
+        },
+    );
+    let events = topics.flat_map(|topics| {
+        stream::iter(
+            topics
+                .into_iter()
+                .map(|t| Ok(Event::default().event(CHANGE_EVENT).data(t.as_str()))),
+        )
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
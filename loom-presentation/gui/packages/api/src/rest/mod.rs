@@ -0,0 +1,605 @@
+//! Versioned REST API for third parties, mounted at `/api/v1`.
+//!
+//! Unlike the server functions in the rest of this crate, which follow the
+//! needs of the web UI, this surface is kept stable: resources are plain JSON
+//! objects, lists are paginated with `?page=&per_page=`, and every failure
+//! answers with the [`error::ErrorBody`] envelope.  Callers authenticate with
+//! the session cookie of `/api/login` and act on the workspace selected with
+//! `/api/workspaces/select`, or send `Authorization: Bearer <token>` with a
+//! personal API token, which is bound to the workspace it was issued in.
+//!
+//! `/api/v1/calendar/{token}` serves a user's private iCalendar feed; the
+//! token in the path is its only authentication.
+//!
+//! `/api/v1/live` streams which parts of the workspace change as
+//! server-sent events, so clients can keep their copies current; see
+//! [`crate::live`].
+//!
+//! [`endpoints`] is the single list of routes; both [`router`] and the
+//! OpenAPI document served at `/api/v1/openapi.json` are built from it.
+
+pub mod activities;
+pub mod calendar;
+pub mod customers;
+pub mod error;
+pub mod extract;
+pub mod live;
+pub mod openapi;
+pub mod page;
+pub mod projects;
+pub mod rates;
+pub mod reports;
+pub mod sync;
+pub mod tags;
+pub mod timesheets;
+pub mod tokens;
+pub mod workspaces;
+
+use axum::routing::{on, MethodFilter, MethodRouter};
+use axum::{Json, Router};
+use loom::core::permissions;
+
+use error::ApiError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verb {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+impl Verb {
+    /// Lower case, as used for OpenAPI operations.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Get => "get",
+            Self::Post => "post",
+            Self::Put => "put",
+            Self::Delete => "delete",
+        }
+    }
+
+    const fn filter(self) -> MethodFilter {
+        match self {
+            Self::Get => MethodFilter::GET,
+            Self::Post => MethodFilter::POST,
+            Self::Put => MethodFilter::PUT,
+            Self::Delete => MethodFilter::DELETE,
+        }
+    }
+}
+
+/// What a successful call answers with; names refer to OpenAPI schemas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reply {
+    /// 204 without a body.
+    Empty,
+    /// 200 with one resource.
+    One(&'static str),
+    /// 201 with the new resource.
+    Created(&'static str),
+    /// 200 with all resources.
+    List(&'static str),
+    /// 200 with one [`page::Page`] of resources.
+    Page(&'static str),
+    /// 200 with the OpenAPI document.
+    Document,
+    /// 200 with a `text/event-stream` that stays open.
+    Events,
+    /// 200 with a `text/calendar` feed, authenticated by the token in the
+    /// path.
+    Calendar,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryParam {
+    pub name: &'static str,
+    /// JSON schema type: `string`, `integer` or `boolean`.
+    pub kind: &'static str,
+    pub required: bool,
+    pub description: &'static str,
+}
+
+const ARCHIVED: QueryParam = QueryParam {
+    name: "archived",
+    kind: "boolean",
+    required: false,
+    description: "List archived instead of active resources.",
+};
+
+pub struct Endpoint {
+    pub verb: Verb,
+    /// Relative to `/api/v1`, with `{id}` style parameters.
+    pub path: &'static str,
+    pub tag: &'static str,
+    pub summary: &'static str,
+    pub permission: Option<&'static str>,
+    pub query: Vec<QueryParam>,
+    /// Schema of the JSON request body.
+    pub body: Option<&'static str>,
+    pub reply: Reply,
+    route: fn(MethodFilter) -> MethodRouter,
+}
+
+impl Endpoint {
+    fn new(
+        verb: Verb,
+        path: &'static str,
+        tag: &'static str,
+        summary: &'static str,
+        reply: Reply,
+        route: fn(MethodFilter) -> MethodRouter,
+    ) -> Self {
+        Self {
+            verb,
+            path,
+            tag,
+            summary,
+            permission: None,
+            query: Vec::new(),
+            body: None,
+            reply,
+            route,
+        }
+    }
+
+    fn permission(mut self, permission: &'static str) -> Self {
+        self.permission = Some(permission);
+        self
+    }
+
+    fn query(mut self, param: QueryParam) -> Self {
+        self.query.push(param);
+        self
+    }
+
+    fn body(mut self, schema: &'static str) -> Self {
+        self.body = Some(schema);
+        self
+    }
+
+    /// Whether the endpoint needs a logged-in caller.
+    pub fn authenticated(&self) -> bool {
+        !matches!(self.reply, Reply::Document | Reply::Calendar)
+    }
+
+    /// Names of the `{…}` path parameters.
+    pub fn path_params(&self) -> impl Iterator<Item = &'static str> {
+        self.path
+            .split('/')
+            .filter_map(|s| s.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+    }
+}
+
+/// Every `/api/v1` route.
+#[allow(clippy::too_many_lines)]
+pub fn endpoints() -> Vec<Endpoint> {
+    use Reply::{Calendar, Created, Document, Empty, Events, List, One, Page};
+    use Verb::{Delete, Get, Post, Put};
+
+    vec![
+        Endpoint::new(
+            Get,
+            "/openapi.json",
+            "meta",
+            "This API description",
+            Document,
+            |m| on(m, || async { Json(openapi::document()) }),
+        ),
+        // Workspaces
+        Endpoint::new(
+            Get,
+            "/workspaces",
+            "workspaces",
+            "List your workspaces",
+            List("Workspace"),
+            |m| on(m, workspaces::list),
+        ),
+        Endpoint::new(
+            Get,
+            "/workspaces/current/members",
+            "workspaces",
+            "List the members of the current workspace",
+            List("Member"),
+            |m| on(m, workspaces::members),
+        ),
+        // Customers
+        Endpoint::new(
+            Get,
+            "/customers",
+            "customers",
+            "List customers",
+            Page("Customer"),
+            |m| on(m, customers::list),
+        )
+        .query(ARCHIVED),
+        Endpoint::new(
+            Post,
+            "/customers",
+            "customers",
+            "Create a customer",
+            Created("Customer"),
+            |m| on(m, customers::create),
+        )
+        .body("NewCustomer")
+        .permission(permissions::CUSTOMER_CREATE),
+        Endpoint::new(
+            Get,
+            "/customers/{id}",
+            "customers",
+            "Get a customer",
+            One("Customer"),
+            |m| on(m, customers::show),
+        ),
+        Endpoint::new(
+            Put,
+            "/customers/{id}",
+            "customers",
+            "Update a customer",
+            One("Customer"),
+            |m| on(m, customers::update),
+        )
+        .body("CustomerChanges")
+        .permission(permissions::CUSTOMER_UPDATE),
+        Endpoint::new(
+            Delete,
+            "/customers/{id}",
+            "customers",
+            "Delete a customer",
+            Empty,
+            |m| on(m, customers::delete),
+        )
+        .permission(permissions::CUSTOMER_DELETE),
+        // Projects
+        Endpoint::new(
+            Get,
+            "/projects",
+            "projects",
+            "List projects",
+            Page("Project"),
+            |m| on(m, projects::list),
+        )
+        .query(ARCHIVED),
+        Endpoint::new(
+            Post,
+            "/projects",
+            "projects",
+            "Create a project",
+            Created("Project"),
+            |m| on(m, projects::create),
+        )
+        .body("NewProject")
+        .permission(permissions::PROJECT_CREATE),
+        Endpoint::new(
+            Get,
+            "/projects/{id}",
+            "projects",
+            "Get a project",
+            One("Project"),
+            |m| on(m, projects::show),
+        ),
+        Endpoint::new(
+            Put,
+            "/projects/{id}",
+            "projects",
+            "Update a project",
+            One("Project"),
+            |m| on(m, projects::update),
+        )
+        .body("ProjectChanges")
+        .permission(permissions::PROJECT_UPDATE),
+        Endpoint::new(
+            Delete,
+            "/projects/{id}",
+            "projects",
+            "Delete a project",
+            Empty,
+            |m| on(m, projects::delete),
+        )
+        .permission(permissions::PROJECT_DELETE),
+        Endpoint::new(
+            Get,
+            "/projects/{id}/rates",
+            "rates",
+            "List the rates of a project",
+            List("Rate"),
+            |m| on(m, rates::list_for_project),
+        ),
+        Endpoint::new(
+            Put,
+            "/projects/{id}/rates/default",
+            "rates",
+            "Set the default rate of a project",
+            One("Rate"),
+            |m| on(m, rates::set_project_default),
+        )
+        .body("RateInput")
+        .permission(permissions::RATE_MANAGE),
+        Endpoint::new(
+            Delete,
+            "/projects/{id}/rates/default",
+            "rates",
+            "Remove the default rate of a project",
+            Empty,
+            |m| on(m, rates::remove_project_default),
+        )
+        .permission(permissions::RATE_MANAGE),
+        // Activities
+        Endpoint::new(
+            Get,
+            "/activities",
+            "activities",
+            "List activities",
+            Page("Activity"),
+            |m| on(m, activities::list),
+        )
+        .query(ARCHIVED),
+        Endpoint::new(
+            Post,
+            "/activities",
+            "activities",
+            "Create an activity",
+            Created("Activity"),
+            |m| on(m, activities::create),
+        )
+        .body("NewActivity")
+        .permission(permissions::ACTIVITY_CREATE),
+        Endpoint::new(
+            Get,
+            "/activities/{id}",
+            "activities",
+            "Get an activity",
+            One("Activity"),
+            |m| on(m, activities::show),
+        ),
+        Endpoint::new(
+            Put,
+            "/activities/{id}",
+            "activities",
+            "Update an activity",
+            One("Activity"),
+            |m| on(m, activities::update),
+        )
+        .body("ActivityChanges")
+        .permission(permissions::ACTIVITY_UPDATE),
+        Endpoint::new(
+            Delete,
+            "/activities/{id}",
+            "activities",
+            "Delete an activity",
+            Empty,
+            |m| on(m, activities::delete),
+        )
+        .permission(permissions::ACTIVITY_DELETE),
+        Endpoint::new(
+            Get,
+            "/activities/{id}/rates",
+            "rates",
+            "List the rates of an activity",
+            List("Rate"),
+            |m| on(m, rates::list_for_activity),
+        ),
+        Endpoint::new(
+            Put,
+            "/activities/{id}/rates/default",
+            "rates",
+            "Set the default rate of an activity",
+            One("Rate"),
+            |m| on(m, rates::set_activity_default),
+        )
+        .body("RateInput")
+        .permission(permissions::RATE_MANAGE),
+        Endpoint::new(
+            Delete,
+            "/activities/{id}/rates/default",
+            "rates",
+            "Remove the default rate of an activity",
+            Empty,
+            |m| on(m, rates::remove_activity_default),
+        )
+        .permission(permissions::RATE_MANAGE),
+        // Tags
+        Endpoint::new(Get, "/tags", "tags", "List tags", Page("Tag"), |m| {
+            on(m, tags::list)
+        })
+        .query(ARCHIVED),
+        Endpoint::new(Post, "/tags", "tags", "Create a tag", Created("Tag"), |m| {
+            on(m, tags::create)
+        })
+        .body("TagInput")
+        .permission(permissions::TAG_MANAGE),
+        Endpoint::new(Get, "/tags/{id}", "tags", "Get a tag", One("Tag"), |m| {
+            on(m, tags::show)
+        }),
+        Endpoint::new(Put, "/tags/{id}", "tags", "Rename a tag", One("Tag"), |m| {
+            on(m, tags::update)
+        })
+        .body("TagInput")
+        .permission(permissions::TAG_MANAGE),
+        Endpoint::new(Delete, "/tags/{id}", "tags", "Delete a tag", Empty, |m| {
+            on(m, tags::delete)
+        })
+        .permission(permissions::TAG_MANAGE),
+        // Timesheets
+        Endpoint::new(
+            Get,
+            "/timesheets",
+            "timesheets",
+            "List your timesheets",
+            Page("Timesheet"),
+            |m| on(m, timesheets::list),
+        )
+        .query(QueryParam {
+            name: "from",
+            kind: "string",
+            required: false,
+            description:
+                "First day (YYYY-MM-DD); without a range the most recent entries are listed.",
+        })
+        .query(QueryParam {
+            name: "to",
+            kind: "string",
+            required: false,
+            description: "Last day (YYYY-MM-DD), inclusive.",
+        }),
+        Endpoint::new(
+            Post,
+            "/timesheets",
+            "timesheets",
+            "Start a timer, or book a completed entry",
+            Created("Timesheet"),
+            |m| on(m, timesheets::create),
+        )
+        .body("NewTimesheet")
+        .permission(permissions::TIMESHEET_CREATE),
+        Endpoint::new(
+            Get,
+            "/timesheets/{id}",
+            "timesheets",
+            "Get one of your timesheets",
+            One("Timesheet"),
+            |m| on(m, timesheets::show),
+        ),
+        Endpoint::new(
+            Put,
+            "/timesheets/{id}",
+            "timesheets",
+            "Update one of your timesheets",
+            Empty,
+            |m| on(m, timesheets::update),
+        )
+        .body("TimesheetChanges")
+        .permission(permissions::TIMESHEET_UPDATE),
+        Endpoint::new(
+            Post,
+            "/timesheets/{id}/stop",
+            "timesheets",
+            "Stop a running timer",
+            Empty,
+            |m| on(m, timesheets::stop),
+        )
+        .permission(permissions::TIMESHEET_UPDATE),
+        Endpoint::new(
+            Delete,
+            "/timesheets/{id}",
+            "timesheets",
+            "Delete one of your timesheets",
+            Empty,
+            |m| on(m, timesheets::delete),
+        )
+        .permission(permissions::TIMESHEET_DELETE),
+        // Reports
+        Endpoint::new(
+            Get,
+            "/reports/budgets",
+            "reports",
+            "Budget usage",
+            List("BudgetUsage"),
+            |m| on(m, reports::budgets),
+        ),
+        Endpoint::new(
+            Get,
+            "/reports/overtime",
+            "reports",
+            "Overtime balances",
+            List("OvertimeBalance"),
+            |m| on(m, reports::overtime),
+        )
+        .query(QueryParam {
+            name: "until",
+            kind: "string",
+            required: true,
+            description: "Last day (YYYY-MM-DD), inclusive.",
+        })
+        .permission(permissions::WORK_CONTRACT_MANAGE),
+        // Offline sync
+        Endpoint::new(
+            Get,
+            "/sync/events",
+            "sync",
+            "Pull the workspace's events for an offline replica",
+            One("SyncBatch"),
+            |m| on(m, sync::events),
+        )
+        .query(QueryParam {
+            name: "after",
+            kind: "integer",
+            required: false,
+            description: "Global position to continue after; defaults to 0.",
+        })
+        .query(QueryParam {
+            name: "limit",
+            kind: "integer",
+            required: false,
+            description: "Most events to return, at most 500; defaults to 500.",
+        })
+        .permission(permissions::TIMESHEET_CREATE),
+        Endpoint::new(
+            Post,
+            "/sync/timesheets",
+            "sync",
+            "Push timesheet changes recorded offline",
+            List("StreamResult"),
+            |m| on(m, sync::push),
+        )
+        .body("SyncPush")
+        .permission(permissions::TIMESHEET_UPDATE),
+        // Live updates
+        Endpoint::new(
+            Get,
+            "/live",
+            "live",
+            "Follow changes to the workspace as server-sent events",
+            Events,
+            |m| on(m, live::stream),
+        ),
+        // Calendar feeds
+        Endpoint::new(
+            Get,
+            "/calendar/{token}",
+            "calendar",
+            "Get a private iCalendar feed of your timesheets",
+            Calendar,
+            |m| on(m, calendar::feed),
+        ),
+        // API tokens
+        Endpoint::new(
+            Get,
+            "/tokens",
+            "tokens",
+            "List your API tokens in the current workspace",
+            List("ApiToken"),
+            |m| on(m, tokens::list),
+        ),
+        Endpoint::new(
+            Post,
+            "/tokens",
+            "tokens",
+            "Issue an API token; the answer is the only time it is shown",
+            Created("IssuedApiToken"),
+            |m| on(m, tokens::create),
+        )
+        .body("NewApiToken"),
+        Endpoint::new(
+            Delete,
+            "/tokens/{id}",
+            "tokens",
+            "Revoke one of your API tokens",
+            Empty,
+            |m| on(m, tokens::revoke),
+        ),
+    ]
+}
+
+/// The `/api/v1` routes, to be nested inside the session layer.  Requests
+/// with a bearer token run with its owner as the audit actor.
+pub fn router() -> Router {
+    endpoints()
+        .into_iter()
+        .fold(Router::new(), |router, e| {
+            router.route(e.path, (e.route)(e.verb.filter()))
+        })
+        .fallback(|| async { ApiError::not_found("endpoint") })
+        .layer(axum::middleware::from_fn(extract::token_actor))
+}
@@ -0,0 +1,560 @@
+//! The OpenAPI 3.1 description of `/api/v1`, generated from
+//! [`super::endpoints`] and the resource schemas below.
+
+use serde_json::{json, Map, Value};
+
+use super::{endpoints, Endpoint, Reply};
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn integer() -> Value {
+    json!({ "type": "integer" })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+fn nullable(kind: &str) -> Value {
+    json!({ "type": [kind, "null"] })
+}
+
+fn reference(schema: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{schema}") })
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+/// An object schema; properties whose schema allows `null` are optional.
+fn object(properties: &[(&str, Value)]) -> Value {
+    let required: Vec<&str> = properties
+        .iter()
+        .filter(|(_, schema)| {
+            !schema["type"]
+                .as_array()
+                .is_some_and(|kinds| kinds.contains(&json!("null")))
+        })
+        .map(|(name, _)| *name)
+        .collect();
+    let properties: Map<String, Value> = properties
+        .iter()
+        .map(|(name, schema)| ((*name).to_string(), schema.clone()))
+        .collect();
+    json!({ "type": "object", "properties": properties, "required": required })
+}
+
+fn page(schema: &str) -> Value {
+    object(&[
+        ("data", array(reference(schema))),
+        ("page", integer()),
+        ("per_page", integer()),
+        ("total", integer()),
+    ])
+}
+
+#[allow(clippy::too_many_lines)]
+fn schemas() -> Value {
+    json!({
+        "Error": object(&[(
+            "error",
+            object(&[("code", string()), ("message", string())]),
+        )]),
+        "Workspace": object(&[
+            ("id", string()),
+            ("name", nullable("string")),
+            ("current", boolean()),
+        ]),
+        "Member": object(&[("user_id", string()), ("name", string())]),
+        "Customer": object(&[
+            ("id", string()),
+            ("name", string()),
+            ("comment", nullable("string")),
+            ("currency", string()),
+            ("timezone", string()),
+            ("country", nullable("string")),
+            ("visible", boolean()),
+            ("time_budget", nullable("integer")),
+            ("money_budget", nullable("integer")),
+            ("budget_is_monthly", boolean()),
+            ("archived", boolean()),
+        ]),
+        "NewCustomer": object(&[
+            ("name", string()),
+            ("currency", string()),
+            ("timezone", string()),
+        ]),
+        "CustomerChanges": object(&[
+            ("name", string()),
+            ("comment", nullable("string")),
+            ("currency", string()),
+            ("timezone", string()),
+            ("country", nullable("string")),
+            ("visible", boolean()),
+        ]),
+        "Project": object(&[
+            ("id", string()),
+            ("customer_id", string()),
+            ("name", string()),
+            ("comment", nullable("string")),
+            ("order_number", nullable("string")),
+            ("visible", boolean()),
+            ("billable", boolean()),
+            ("time_budget", nullable("integer")),
+            ("money_budget", nullable("integer")),
+            ("budget_is_monthly", boolean()),
+            ("archived", boolean()),
+        ]),
+        "NewProject": object(&[("customer_id", string()), ("name", string())]),
+        "ProjectChanges": object(&[
+            ("name", string()),
+            ("comment", nullable("string")),
+            ("order_number", nullable("string")),
+            ("visible", boolean()),
+            ("billable", boolean()),
+        ]),
+        "Activity": object(&[
+            ("id", string()),
+            ("project_id", nullable("string")),
+            ("name", string()),
+            ("comment", nullable("string")),
+            ("visible", boolean()),
+            ("billable", boolean()),
+            ("archived", boolean()),
+        ]),
+        "NewActivity": object(&[("project_id", nullable("string")), ("name", string())]),
+        "ActivityChanges": object(&[
+            ("name", string()),
+            ("comment", nullable("string")),
+            ("visible", boolean()),
+            ("billable", boolean()),
+        ]),
+        "Tag": object(&[("id", string()), ("name", string()), ("archived", boolean())]),
+        "TagInput": object(&[("name", string())]),
+        "Rate": object(&[
+            ("id", string()),
+            ("user_id", nullable("string")),
+            ("hourly_rate", integer()),
+            ("internal_rate", nullable("integer")),
+        ]),
+        "RateInput": object(&[
+            ("hourly_rate", integer()),
+            ("internal_rate", nullable("integer")),
+        ]),
+        "Timesheet": object(&[
+            ("id", string()),
+            ("user_id", string()),
+            ("project_id", nullable("string")),
+            ("activity_id", nullable("string")),
+            ("start_time", string()),
+            ("end_time", nullable("string")),
+            ("duration", nullable("integer")),
+            ("description", nullable("string")),
+            ("timezone", string()),
+            ("billable", boolean()),
+            ("exported", boolean()),
+            ("hourly_rate", nullable("integer")),
+            ("fixed_rate", nullable("integer")),
+            ("internal_rate", nullable("integer")),
+            ("rate", nullable("integer")),
+            ("billed_duration", nullable("integer")),
+            ("approval_status", string()),
+            ("break_duration", integer()),
+            ("paused_at", nullable("string")),
+        ]),
+        "NewTimesheet": object(&[
+            ("project_id", nullable("string")),
+            ("activity_id", nullable("string")),
+            ("start_time", nullable("string")),
+            ("end_time", nullable("string")),
+            ("description", nullable("string")),
+            ("billable", boolean()),
+        ]),
+        "TimesheetChanges": object(&[
+            ("description", nullable("string")),
+            ("billable", nullable("boolean")),
+            ("start_time", nullable("string")),
+            ("end_time", nullable("string")),
+        ]),
+        "BudgetUsage": object(&[
+            ("scope", string()),
+            ("id", string()),
+            ("name", string()),
+            ("period", nullable("string")),
+            ("time_budget", nullable("integer")),
+            ("money_budget", nullable("integer")),
+            ("used_seconds", integer()),
+            ("used_amount", integer()),
+            ("remaining_seconds", nullable("integer")),
+            ("remaining_amount", nullable("integer")),
+            ("percent_used", nullable("integer")),
+            ("reached_threshold", nullable("integer")),
+        ]),
+        "WeekBalance": object(&[
+            ("week", string()),
+            ("expected_seconds", integer()),
+            ("worked_seconds", integer()),
+        ]),
+        "OvertimeBalance": object(&[
+            ("user_id", string()),
+            ("user_name", nullable("string")),
+            ("until", string()),
+            ("expected_seconds", integer()),
+            ("worked_seconds", integer()),
+            ("balance_seconds", integer()),
+            ("weeks", array(reference("WeekBalance"))),
+        ]),
+        "SyncEvent": object(&[
+            ("stream_id", string()),
+            ("version", integer()),
+            ("position", integer()),
+            ("event_type", string()),
+            ("payload", string()),
+            ("metadata", string()),
+            ("schema_version", integer()),
+        ]),
+        "SyncBatch": object(&[
+            ("events", array(reference("SyncEvent"))),
+            ("position", integer()),
+        ]),
+        "StreamChanges": object(&[
+            ("stream_id", string()),
+            ("base_version", integer()),
+            ("events", array(reference("SyncEvent"))),
+        ]),
+        "SyncPush": object(&[("streams", array(reference("StreamChanges")))]),
+        "StreamResult": object(&[
+            ("stream_id", string()),
+            (
+                "outcome",
+                json!({ "type": "string", "enum": ["accepted", "conflict", "rejected"] }),
+            ),
+            ("version", integer()),
+            ("reason", nullable("string")),
+        ]),
+        "ApiToken": object(&[
+            ("id", string()),
+            ("name", string()),
+            ("created_at", string()),
+            ("last_used_at", nullable("string")),
+            ("revoked_at", nullable("string")),
+        ]),
+        "NewApiToken": object(&[("name", string())]),
+        "IssuedApiToken": object(&[
+            ("id", string()),
+            ("name", string()),
+            ("token", string()),
+        ]),
+    })
+}
+
+fn json_content(schema: Value) -> Value {
+    json!({ "content": { "application/json": { "schema": schema } } })
+}
+
+fn error_response(description: &str) -> Value {
+    let mut response = json_content(reference("Error"));
+    response["description"] = json!(description);
+    response
+}
+
+fn operation(e: &Endpoint) -> Value {
+    let mut parameters: Vec<Value> = e
+        .path_params()
+        .map(|name| json!({ "name": name, "in": "path", "required": true, "schema": string() }))
+        .collect();
+    let mut query = e.query.clone();
+    if matches!(e.reply, Reply::Page(_)) {
+        query.push(super::QueryParam {
+            name: "page",
+            kind: "integer",
+            required: false,
+            description: "1-based page number; defaults to 1.",
+        });
+        query.push(super::QueryParam {
+            name: "per_page",
+            kind: "integer",
+            required: false,
+            description: "Items per page, at most 200; defaults to 50.",
+        });
+    }
+    parameters.extend(query.iter().map(|p| {
+        json!({
+            "name": p.name,
+            "in": "query",
+            "required": p.required,
+            "description": p.description,
+            "schema": { "type": p.kind },
+        })
+    }));
+
+    let mut responses = Map::new();
+    let mut success = |status: &str, description: &str, schema: Option<Value>| {
+        let mut response = schema.map_or_else(|| json!({}), json_content);
+        response["description"] = json!(description);
+        responses.insert(status.to_string(), response);
+    };
+    match e.reply {
+        Reply::Empty => success("204", "Done", None),
+        Reply::One(schema) => success("200", "The resource", Some(reference(schema))),
+        Reply::Created(schema) => success("201", "The new resource", Some(reference(schema))),
+        Reply::List(schema) => success("200", "All resources", Some(array(reference(schema)))),
+        Reply::Page(schema) => success("200", "One page of resources", Some(page(schema))),
+        Reply::Document => success("200", "OpenAPI document", Some(json!({ "type": "object" }))),
+        Reply::Events => {
+            responses.insert(
+                "200".into(),
+                json!({
+                    "description": "Server-sent `change` events, each naming the topic that changed: \
+                        timesheets, approvals, customers, projects, activities, tags, rates, \
+                        favorites, work_contracts or holidays",
+                    "content": { "text/event-stream": { "schema": string() } },
+                }),
+            );
+        }
+        Reply::Calendar => {
+            responses.insert(
+                "200".into(),
+                json!({
+                    "description": "iCalendar feed with one event per stopped timesheet \
+                        of the last year",
+                    "content": { "text/calendar": { "schema": string() } },
+                }),
+            );
+        }
+    }
+    if e.body.is_some() || !parameters.is_empty() {
+        responses.insert("400".into(), error_response("Malformed request"));
+    }
+    if e.authenticated() {
+        responses.insert(
+            "401".into(),
+            error_response("Not logged in, or no workspace selected"),
+        );
+    }
+    if e.permission.is_some() {
+        responses.insert("403".into(), error_response("Permission missing"));
+    }
+    if e.path_params().next().is_some() {
+        responses.insert("404".into(), error_response("Not found"));
+    }
+    if e.body.is_some() {
+        responses.insert("422".into(), error_response("Validation failed"));
+    }
+    responses.insert("500".into(), error_response("Internal error"));
+
+    let mut op = json!({
+        "tags": [e.tag],
+        "summary": e.summary,
+        "operationId": operation_id(e),
+        "parameters": parameters,
+        "responses": responses,
+    });
+    if let Some(permission) = e.permission {
+        op["description"] = json!(format!("Requires the `{permission}` permission."));
+    }
+    if let Some(body) = e.body {
+        op["requestBody"] = json_content(reference(body));
+        op["requestBody"]["required"] = json!(true);
+    }
+    if !e.authenticated() {
+        op["security"] = json!([]);
+    }
+    op
+}
+
+/// `get /customers/{id}` becomes `get_customers_id`.
+fn operation_id(e: &Endpoint) -> String {
+    let path: String = e
+        .path
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let mut id = format!("{}_{}", e.verb.as_str(), path.trim_matches('_'));
+    while id.contains("__") {
+        id = id.replace("__", "_");
+    }
+    id
+}
+
+/// The OpenAPI document served at `/api/v1/openapi.json`.
+pub fn document() -> Value {
+    let mut paths = Map::new();
+    for e in endpoints() {
+        let item = paths.entry(e.path).or_insert_with(|| json!({}));
+        item[e.verb.as_str()] = operation(&e);
+    }
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "Loom",
+            "version": "1",
+            "description": "Time tracking of the workspace selected in the session or bound to the API token.",
+        },
+        "servers": [{ "url": "/api/v1" }],
+        "security": [{ "session": [] }, { "token": [] }],
+        "paths": paths,
+        "components": {
+            "schemas": schemas(),
+            "securitySchemes": {
+                "session": { "type": "apiKey", "in": "cookie", "name": "id" },
+                "token": { "type": "http", "scheme": "bearer" },
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use serde::Serialize;
+
+    use super::*;
+    use crate::rest::{
+        activities, customers, error, page, projects, rates, reports, sync, tags, timesheets,
+        tokens, workspaces,
+    };
+
+    fn keys(value: &Value) -> BTreeSet<String> {
+        value.as_object().expect("object").keys().cloned().collect()
+    }
+
+    /// The schema's properties must be the resource's fields, and exactly
+    /// the ones that are never `null` must be required.
+    fn assert_matches<T: Default + Serialize>(name: &str, schema: &Value) {
+        let sample = serde_json::to_value(T::default()).expect("serializable");
+        assert_eq!(
+            keys(&schema["properties"]),
+            keys(&sample),
+            "{name} properties"
+        );
+        let required: BTreeSet<String> = schema["required"]
+            .as_array()
+            .expect("required list")
+            .iter()
+            .map(|v| v.as_str().expect("name").to_string())
+            .collect();
+        let non_null: BTreeSet<String> = sample
+            .as_object()
+            .expect("object")
+            .iter()
+            .filter(|(_, v)| !v.is_null())
+            .map(|(k, _)| k.clone())
+            .collect();
+        assert_eq!(required, non_null, "{name} required");
+    }
+
+    #[test]
+    fn every_route_is_documented_once() {
+        let doc = document();
+        let mut documented = 0;
+        for (path, item) in doc["paths"].as_object().expect("paths") {
+            for verb in item.as_object().expect("path item").keys() {
+                documented += 1;
+                assert!(
+                    endpoints()
+                        .iter()
+                        .any(|e| e.path == path && e.verb.as_str() == verb),
+                    "{verb} {path} has no handler"
+                );
+            }
+        }
+        assert_eq!(documented, endpoints().len());
+
+        let mut ids = BTreeSet::new();
+        for e in endpoints() {
+            assert!(
+                ids.insert(operation_id(&e)),
+                "{} {} twice",
+                e.verb.as_str(),
+                e.path
+            );
+        }
+
+        // Panics on duplicate or malformed routes.
+        let _ = crate::rest::router();
+    }
+
+    #[test]
+    fn every_schema_reference_resolves() {
+        fn refs(value: &Value, out: &mut Vec<String>) {
+            match value {
+                Value::Object(map) => {
+                    if let Some(Value::String(r)) = map.get("$ref") {
+                        out.push(r.clone());
+                    }
+                    map.values().for_each(|v| refs(v, out));
+                }
+                Value::Array(items) => items.iter().for_each(|v| refs(v, out)),
+                _ => {}
+            }
+        }
+
+        let doc = document();
+        let mut found = Vec::new();
+        refs(&doc, &mut found);
+        assert!(!found.is_empty());
+        for r in found {
+            let name = r.trim_start_matches("#/components/schemas/");
+            assert!(
+                doc["components"]["schemas"].get(name).is_some(),
+                "missing {r}"
+            );
+        }
+    }
+
+    #[test]
+    fn calendar_feeds_need_no_login() {
+        let public: Vec<&str> = endpoints()
+            .iter()
+            .filter(|e| !e.authenticated())
+            .map(|e| e.path)
+            .collect();
+        assert_eq!(public, ["/openapi.json", "/calendar/{token}"]);
+        let feed = &document()["paths"]["/calendar/{token}"]["get"];
+        assert_eq!(feed["security"], json!([]));
+        assert!(feed["responses"].get("401").is_none());
+    }
+
+    #[test]
+    fn schemas_match_the_handler_types() {
+        let s = schemas();
+        assert_matches::<error::ErrorBody>("Error", &s["Error"]);
+        assert_matches::<error::ErrorDetail>("Error.error", &s["Error"]["properties"]["error"]);
+        assert_matches::<workspaces::Workspace>("Workspace", &s["Workspace"]);
+        assert_matches::<workspaces::Member>("Member", &s["Member"]);
+        assert_matches::<customers::Customer>("Customer", &s["Customer"]);
+        assert_matches::<customers::NewCustomer>("NewCustomer", &s["NewCustomer"]);
+        assert_matches::<customers::CustomerChanges>("CustomerChanges", &s["CustomerChanges"]);
+        assert_matches::<projects::Project>("Project", &s["Project"]);
+        assert_matches::<projects::NewProject>("NewProject", &s["NewProject"]);
+        assert_matches::<projects::ProjectChanges>("ProjectChanges", &s["ProjectChanges"]);
+        assert_matches::<activities::Activity>("Activity", &s["Activity"]);
+        assert_matches::<activities::NewActivity>("NewActivity", &s["NewActivity"]);
+        assert_matches::<activities::ActivityChanges>("ActivityChanges", &s["ActivityChanges"]);
+        assert_matches::<tags::Tag>("Tag", &s["Tag"]);
+        assert_matches::<tags::TagInput>("TagInput", &s["TagInput"]);
+        assert_matches::<rates::Rate>("Rate", &s["Rate"]);
+        assert_matches::<rates::RateInput>("RateInput", &s["RateInput"]);
+        assert_matches::<timesheets::Timesheet>("Timesheet", &s["Timesheet"]);
+        assert_matches::<timesheets::NewTimesheet>("NewTimesheet", &s["NewTimesheet"]);
+        assert_matches::<timesheets::TimesheetChanges>("TimesheetChanges", &s["TimesheetChanges"]);
+        assert_matches::<reports::BudgetUsage>("BudgetUsage", &s["BudgetUsage"]);
+        assert_matches::<reports::WeekBalance>("WeekBalance", &s["WeekBalance"]);
+        assert_matches::<reports::OvertimeBalance>("OvertimeBalance", &s["OvertimeBalance"]);
+        assert_matches::<sync::SyncEvent>("SyncEvent", &s["SyncEvent"]);
+        assert_matches::<sync::SyncBatch>("SyncBatch", &s["SyncBatch"]);
+        assert_matches::<sync::StreamChanges>("StreamChanges", &s["StreamChanges"]);
+        assert_matches::<sync::SyncPush>("SyncPush", &s["SyncPush"]);
+        assert_matches::<sync::StreamResult>("StreamResult", &s["StreamResult"]);
+        assert_matches::<tokens::ApiToken>("ApiToken", &s["ApiToken"]);
+        assert_matches::<tokens::NewApiToken>("NewApiToken", &s["NewApiToken"]);
+        assert_matches::<tokens::IssuedApiToken>("IssuedApiToken", &s["IssuedApiToken"]);
+        assert_matches::<page::Page<()>>("Page", &page("Tag"));
+        assert_eq!(keys(&s).len(), 30, "a schema without a check above");
+    }
+}
@@ -0,0 +1,65 @@
+//! Page-number pagination of list endpoints: `?page=2&per_page=20`.
+
+use serde::{Deserialize, Serialize};
+
+use super::error::ApiError;
+
+pub const DEFAULT_PER_PAGE: usize = 50;
+pub const MAX_PER_PAGE: usize = 200;
+
+/// Query parameters of the catalog list endpoints.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ListParams {
+    /// Archived instead of active resources.
+    pub archived: Option<bool>,
+    pub page: Option<usize>,
+    pub per_page: Option<usize>,
+}
+
+/// One page of a list, plus what is needed to fetch the others.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    /// 1-based.
+    pub page: usize,
+    pub per_page: usize,
+    /// Number of items across all pages.
+    pub total: usize,
+}
+
+impl<T> Page<T> {
+    /// Cut page `page` (default 1) of `per_page` items (default
+    /// [`DEFAULT_PER_PAGE`]) out of `items`.
+    ///
+    /// # Errors
+    ///
+    /// 400 if `page` is 0 or `per_page` is not within `1..=MAX_PER_PAGE`.
+    pub fn of(
+        items: Vec<T>,
+        page: Option<usize>,
+        per_page: Option<usize>,
+    ) -> Result<Self, ApiError> {
+        let page = page.unwrap_or(1);
+        let per_page = per_page.unwrap_or(DEFAULT_PER_PAGE);
+        if page == 0 {
+            return Err(ApiError::bad_request("page starts at 1"));
+        }
+        if !(1..=MAX_PER_PAGE).contains(&per_page) {
+            return Err(ApiError::bad_request(format!(
+                "per_page must be between 1 and {MAX_PER_PAGE}"
+            )));
+        }
+        let total = items.len();
+        let data = items
+            .into_iter()
+            .skip((page - 1).saturating_mul(per_page))
+            .take(per_page)
+            .collect();
+        Ok(Self {
+            data,
+            page,
+            per_page,
+            total,
+        })
+    }
+}
@@ -0,0 +1,132 @@
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::Json;
+use loom::core::permissions;
+use loom::infrastructure::tenant::project::repositories::ProjectRow;
+use serde::{Deserialize, Serialize};
+
+use super::error::ApiError;
+use super::extract::{Body, Caller, Params};
+use super::page::{ListParams, Page};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Project {
+    pub id: String,
+    pub customer_id: String,
+    pub name: String,
+    pub comment: Option<String>,
+    pub order_number: Option<String>,
+    pub visible: bool,
+    pub billable: bool,
+    /// Seconds.
+    pub time_budget: Option<i32>,
+    /// Cents.
+    pub money_budget: Option<i64>,
+    pub budget_is_monthly: bool,
+    pub archived: bool,
+}
+
+impl From<ProjectRow> for Project {
+    fn from(r: ProjectRow) -> Self {
+        Self {
+            id: r.id,
+            customer_id: r.customer_id,
+            name: r.name,
+            comment: r.comment,
+            order_number: r.order_number,
+            visible: r.visible,
+            billable: r.billable,
+            time_budget: r.time_budget,
+            money_budget: r.money_budget,
+            budget_is_monthly: r.budget_is_monthly,
+            archived: r.archived,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewProject {
+    pub customer_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectChanges {
+    pub name: String,
+    pub comment: Option<String>,
+    pub order_number: Option<String>,
+    pub visible: bool,
+    pub billable: bool,
+}
+
+pub(super) async fn find(workspace_id: &str, id: &str) -> Result<ProjectRow, ApiError> {
+    let mut rows = loom::tenant::project::list(workspace_id).await?;
+    rows.extend(loom::tenant::project::list_archived(workspace_id).await?);
+    rows.into_iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| ApiError::not_found("project"))
+}
+
+pub async fn list(
+    caller: Caller,
+    Params(params): Params<ListParams>,
+) -> Result<Json<Page<Project>>, ApiError> {
+    let workspace_id = caller.workspace()?;
+    let rows = if params.archived.unwrap_or(false) {
+        loom::tenant::project::list_archived(workspace_id).await?
+    } else {
+        loom::tenant::project::list(workspace_id).await?
+    };
+    let items = rows.into_iter().map(Project::from).collect();
+    Ok(Json(Page::of(items, params.page, params.per_page)?))
+}
+
+pub async fn create(
+    caller: Caller,
+    Body(input): Body<NewProject>,
+) -> Result<(StatusCode, Json<Project>), ApiError> {
+    caller.require(permissions::PROJECT_CREATE).await?;
+    let row =
+        loom::tenant::project::create(caller.workspace()?, input.customer_id, input.name).await?;
+    Ok((StatusCode::CREATED, Json(row.into())))
+}
+
+pub async fn show(caller: Caller, Path(id): Path<String>) -> Result<Json<Project>, ApiError> {
+    Ok(Json(find(caller.workspace()?, &id).await?.into()))
+}
+
+pub async fn update(
+    caller: Caller,
+    Path(id): Path<String>,
+    Body(input): Body<ProjectChanges>,
+) -> Result<Json<Project>, ApiError> {
+    caller.require(permissions::PROJECT_UPDATE).await?;
+    let workspace_id = caller.workspace()?;
+    let current = find(workspace_id, &id).await?;
+    loom::tenant::project::update(
+        workspace_id,
+        &id,
+        input.name.clone(),
+        input.comment.clone(),
+        input.order_number.clone(),
+        input.visible,
+        input.billable,
+    )
+    .await?;
+    Ok(Json(Project {
+        name: input.name,
+        comment: input.comment,
+        order_number: input.order_number,
+        visible: input.visible,
+        billable: input.billable,
+        ..Project::from(current)
+    }))
+}
+
+pub async fn delete(caller: Caller, Path(id): Path<String>) -> Result<StatusCode, ApiError> {
+    caller.require(permissions::PROJECT_DELETE).await?;
+    let workspace_id = caller.workspace()?;
+    find(workspace_id, &id).await?;
+    loom::tenant::project::delete(workspace_id, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,130 @@
+//! Hourly rates of projects and activities, nested under their owner:
+//! `/projects/{id}/rates` and `/activities/{id}/rates`.
+
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::Json;
+use loom::core::permissions;
+use loom::infrastructure::tenant::activity_rate::repositories::ActivityRateRow;
+use loom::infrastructure::tenant::project_rate::repositories::ProjectRateRow;
+use serde::{Deserialize, Serialize};
+
+use super::error::ApiError;
+use super::extract::{Body, Caller};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rate {
+    pub id: String,
+    /// `None` for the default rate, which applies to every user.
+    pub user_id: Option<String>,
+    /// Cents per hour.
+    pub hourly_rate: i64,
+    /// Internal (cost) rate in cents per hour.
+    pub internal_rate: Option<i64>,
+}
+
+impl From<ProjectRateRow> for Rate {
+    fn from(r: ProjectRateRow) -> Self {
+        Self {
+            id: r.id,
+            user_id: r.user_id,
+            hourly_rate: r.hourly_rate,
+            internal_rate: r.internal_rate,
+        }
+    }
+}
+
+impl From<ActivityRateRow> for Rate {
+    fn from(r: ActivityRateRow) -> Self {
+        Self {
+            id: r.id,
+            user_id: r.user_id,
+            hourly_rate: r.hourly_rate,
+            internal_rate: r.internal_rate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateInput {
+    pub hourly_rate: i64,
+    pub internal_rate: Option<i64>,
+}
+
+pub async fn list_for_project(
+    caller: Caller,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<Rate>>, ApiError> {
+    let workspace_id = caller.workspace()?;
+    super::projects::find(workspace_id, &id).await?;
+    let rows = loom::tenant::project_rate::list_for_project(workspace_id, &id).await?;
+    Ok(Json(rows.into_iter().map(Rate::from).collect()))
+}
+
+pub async fn set_project_default(
+    caller: Caller,
+    Path(id): Path<String>,
+    Body(input): Body<RateInput>,
+) -> Result<Json<Rate>, ApiError> {
+    caller.require(permissions::RATE_MANAGE).await?;
+    let workspace_id = caller.workspace()?;
+    super::projects::find(workspace_id, &id).await?;
+    let row = loom::tenant::project_rate::set_default(
+        workspace_id,
+        id,
+        input.hourly_rate,
+        input.internal_rate,
+    )
+    .await?;
+    Ok(Json(row.into()))
+}
+
+pub async fn remove_project_default(
+    caller: Caller,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    caller.require(permissions::RATE_MANAGE).await?;
+    let workspace_id = caller.workspace()?;
+    super::projects::find(workspace_id, &id).await?;
+    loom::tenant::project_rate::remove_default(workspace_id, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_for_activity(
+    caller: Caller,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<Rate>>, ApiError> {
+    let workspace_id = caller.workspace()?;
+    super::activities::find(workspace_id, &id).await?;
+    let rows = loom::tenant::activity_rate::list_for_activity(workspace_id, &id).await?;
+    Ok(Json(rows.into_iter().map(Rate::from).collect()))
+}
+
+pub async fn set_activity_default(
+    caller: Caller,
+    Path(id): Path<String>,
+    Body(input): Body<RateInput>,
+) -> Result<Json<Rate>, ApiError> {
+    caller.require(permissions::RATE_MANAGE).await?;
+    let workspace_id = caller.workspace()?;
+    super::activities::find(workspace_id, &id).await?;
+    let row = loom::tenant::activity_rate::set_default(
+        workspace_id,
+        id,
+        input.hourly_rate,
+        input.internal_rate,
+    )
+    .await?;
+    Ok(Json(row.into()))
+}
+
+pub async fn remove_activity_default(
+    caller: Caller,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    caller.require(permissions::RATE_MANAGE).await?;
+    let workspace_id = caller.workspace()?;
+    super::activities::find(workspace_id, &id).await?;
+    loom::tenant::activity_rate::remove_default(workspace_id, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
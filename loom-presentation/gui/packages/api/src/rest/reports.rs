@@ -0,0 +1,109 @@
+use axum::Json;
+use loom::core::permissions;
+use serde::{Deserialize, Serialize};
+
+use super::error::ApiError;
+use super::extract::{Caller, Params};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    /// `customer` or `project`.
+    pub scope: String,
+    pub id: String,
+    pub name: String,
+    /// `YYYY-MM` for monthly budgets.
+    pub period: Option<String>,
+    /// Seconds.
+    pub time_budget: Option<i32>,
+    /// Cents.
+    pub money_budget: Option<i64>,
+    pub used_seconds: i64,
+    pub used_amount: i64,
+    pub remaining_seconds: Option<i64>,
+    pub remaining_amount: Option<i64>,
+    pub percent_used: Option<i64>,
+    /// Highest warning threshold reached in the current period.
+    pub reached_threshold: Option<u8>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeekBalance {
+    /// First day of the week, `YYYY-MM-DD`.
+    pub week: String,
+    pub expected_seconds: i64,
+    pub worked_seconds: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OvertimeBalance {
+    pub user_id: String,
+    pub user_name: Option<String>,
+    /// `YYYY-MM-DD`, inclusive.
+    pub until: String,
+    pub expected_seconds: i64,
+    pub worked_seconds: i64,
+    /// Overtime if positive, undertime if negative.
+    pub balance_seconds: i64,
+    pub weeks: Vec<WeekBalance>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OvertimeParams {
+    /// `YYYY-MM-DD`, inclusive.
+    pub until: String,
+}
+
+/// Usage of every customer and project budget.
+pub async fn budgets(caller: Caller) -> Result<Json<Vec<BudgetUsage>>, ApiError> {
+    let rows = loom::tenant::budget::consumption(caller.workspace()?).await?;
+    Ok(Json(
+        rows.into_iter()
+            .map(|c| BudgetUsage {
+                scope: c.scope,
+                id: c.id,
+                name: c.name,
+                period: c.period,
+                time_budget: c.time_budget,
+                money_budget: c.money_budget,
+                used_seconds: c.used_seconds,
+                used_amount: c.used_amount,
+                remaining_seconds: c.remaining_seconds,
+                remaining_amount: c.remaining_amount,
+                percent_used: c.percent_used,
+                reached_threshold: c.reached_threshold,
+            })
+            .collect(),
+    ))
+}
+
+/// Overtime balance of every user with a work contract.
+pub async fn overtime(
+    caller: Caller,
+    Params(params): Params<OvertimeParams>,
+) -> Result<Json<Vec<OvertimeBalance>>, ApiError> {
+    caller.require(permissions::WORK_CONTRACT_MANAGE).await?;
+    let balances =
+        loom::tenant::work_contract::balances(caller.workspace()?, &params.until).await?;
+    Ok(Json(
+        balances
+            .into_iter()
+            .map(|b| OvertimeBalance {
+                user_id: b.user_id,
+                user_name: b.user_name,
+                until: b.until,
+                expected_seconds: b.expected_seconds,
+                worked_seconds: b.worked_seconds,
+                balance_seconds: b.balance_seconds,
+                weeks: b
+                    .weeks
+                    .into_iter()
+                    .map(|w| WeekBalance {
+                        week: w.week.format("%Y-%m-%d").to_string(),
+                        expected_seconds: w.expected_seconds,
+                        worked_seconds: w.worked_seconds,
+                    })
+                    .collect(),
+            })
+            .collect(),
+    ))
+}
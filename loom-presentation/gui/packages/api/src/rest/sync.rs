@@ -0,0 +1,57 @@
+//! Offline sync of the desktop app; see [`loom::sync`].
+
+use axum::Json;
+use loom::core::permissions;
+use loom::sync::server::{self, MAX_PULL};
+use serde::Deserialize;
+
+pub use loom::sync::{Outcome, StreamChanges, StreamResult, SyncBatch, SyncEvent, SyncPush};
+
+use super::error::ApiError;
+use super::extract::{Body, Caller, Params};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EventParams {
+    /// Global position to continue after; 0 for everything.
+    pub after: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// The workspace's events in global order, as far as the caller may see
+/// them; see [`server::events_after`].
+pub async fn events(
+    caller: Caller,
+    Params(params): Params<EventParams>,
+) -> Result<Json<SyncBatch>, ApiError> {
+    caller.require(permissions::TIMESHEET_CREATE).await?;
+    let batch = server::events_after(
+        caller.workspace()?,
+        &caller.user.id,
+        params.after.unwrap_or_default(),
+        params.limit.unwrap_or(MAX_PULL),
+    )
+    .await?;
+    Ok(Json(batch))
+}
+
+/// Record timesheet changes made offline.  Each event needs the permission
+/// the matching endpoint needs, e.g. `timesheet.create` to start a timer;
+/// events whose `event_type` does not match their payload are refused.
+pub async fn push(
+    caller: Caller,
+    Body(input): Body<SyncPush>,
+) -> Result<Json<Vec<StreamResult>>, ApiError> {
+    let mut needed = Vec::new();
+    for event in input.streams.iter().flat_map(|s| &s.events) {
+        needed.push(server::required_permission(&server::decode(event)?));
+    }
+    needed.sort_unstable();
+    needed.dedup();
+    for permission in needed {
+        caller.require(permission).await?;
+    }
+    let results =
+        server::apply_timesheet_changes(caller.workspace()?, &caller.user.id, input.streams)
+            .await?;
+    Ok(Json(results))
+}
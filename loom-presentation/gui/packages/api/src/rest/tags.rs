@@ -0,0 +1,91 @@
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::Json;
+use loom::core::permissions;
+use loom::infrastructure::tenant::tag::repositories::TagRow;
+use serde::{Deserialize, Serialize};
+
+use super::error::ApiError;
+use super::extract::{Body, Caller, Params};
+use super::page::{ListParams, Page};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: String,
+    pub name: String,
+    pub archived: bool,
+}
+
+impl From<TagRow> for Tag {
+    fn from(r: TagRow) -> Self {
+        Self {
+            id: r.id,
+            name: r.name,
+            archived: r.archived,
+        }
+    }
+}
+
+/// Body of both creating and renaming a tag.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagInput {
+    pub name: String,
+}
+
+async fn find(workspace_id: &str, id: &str) -> Result<TagRow, ApiError> {
+    let mut rows = loom::tenant::tag::list(workspace_id).await?;
+    rows.extend(loom::tenant::tag::list_archived(workspace_id).await?);
+    rows.into_iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| ApiError::not_found("tag"))
+}
+
+pub async fn list(
+    caller: Caller,
+    Params(params): Params<ListParams>,
+) -> Result<Json<Page<Tag>>, ApiError> {
+    let workspace_id = caller.workspace()?;
+    let rows = if params.archived.unwrap_or(false) {
+        loom::tenant::tag::list_archived(workspace_id).await?
+    } else {
+        loom::tenant::tag::list(workspace_id).await?
+    };
+    let items = rows.into_iter().map(Tag::from).collect();
+    Ok(Json(Page::of(items, params.page, params.per_page)?))
+}
+
+pub async fn create(
+    caller: Caller,
+    Body(input): Body<TagInput>,
+) -> Result<(StatusCode, Json<Tag>), ApiError> {
+    caller.require(permissions::TAG_MANAGE).await?;
+    let row = loom::tenant::tag::create(caller.workspace()?, input.name).await?;
+    Ok((StatusCode::CREATED, Json(row.into())))
+}
+
+pub async fn show(caller: Caller, Path(id): Path<String>) -> Result<Json<Tag>, ApiError> {
+    Ok(Json(find(caller.workspace()?, &id).await?.into()))
+}
+
+pub async fn update(
+    caller: Caller,
+    Path(id): Path<String>,
+    Body(input): Body<TagInput>,
+) -> Result<Json<Tag>, ApiError> {
+    caller.require(permissions::TAG_MANAGE).await?;
+    let workspace_id = caller.workspace()?;
+    let current = find(workspace_id, &id).await?;
+    loom::tenant::tag::rename(workspace_id, &id, input.name.clone()).await?;
+    Ok(Json(Tag {
+        name: input.name,
+        ..Tag::from(current)
+    }))
+}
+
+pub async fn delete(caller: Caller, Path(id): Path<String>) -> Result<StatusCode, ApiError> {
+    caller.require(permissions::TAG_MANAGE).await?;
+    let workspace_id = caller.workspace()?;
+    find(workspace_id, &id).await?;
+    loom::tenant::tag::delete(workspace_id, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,228 @@
+//! The caller's own timesheets.  Entries of other users answer 404.
+
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::Json;
+use loom::core::permissions;
+use loom::infrastructure::tenant::timesheet::repositories::TimesheetRow;
+use serde::{Deserialize, Serialize};
+
+use super::error::ApiError;
+use super::extract::{Body, Caller, Params};
+use super::page::Page;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timesheet {
+    pub id: String,
+    pub user_id: String,
+    pub project_id: Option<String>,
+    pub activity_id: Option<String>,
+    /// RFC 3339.
+    pub start_time: String,
+    /// RFC 3339; `None` while the timer runs.
+    pub end_time: Option<String>,
+    /// Seconds, breaks excluded.
+    pub duration: Option<i32>,
+    pub description: Option<String>,
+    pub timezone: String,
+    pub billable: bool,
+    pub exported: bool,
+    /// Cents per hour.
+    pub hourly_rate: Option<i64>,
+    /// Cents.
+    pub fixed_rate: Option<i64>,
+    /// Cents per hour.
+    pub internal_rate: Option<i64>,
+    /// Billable amount in cents.
+    pub rate: Option<i64>,
+    /// Seconds after rounding, the basis of `rate`.
+    pub billed_duration: Option<i32>,
+    /// `draft`, `submitted`, `approved` or `rejected`.
+    pub approval_status: String,
+    /// Seconds.
+    pub break_duration: i32,
+    /// RFC 3339; set while a running timer is paused.
+    pub paused_at: Option<String>,
+}
+
+impl From<TimesheetRow> for Timesheet {
+    fn from(r: TimesheetRow) -> Self {
+        Self {
+            id: r.id,
+            user_id: r.user_id,
+            project_id: r.project_id,
+            activity_id: r.activity_id,
+            start_time: r.start_time,
+            end_time: r.end_time,
+            duration: r.duration,
+            description: r.description,
+            timezone: r.timezone,
+            billable: r.billable,
+            exported: r.exported,
+            hourly_rate: r.hourly_rate,
+            fixed_rate: r.fixed_rate,
+            internal_rate: r.internal_rate,
+            rate: r.rate,
+            billed_duration: r.billed_duration,
+            approval_status: r.approval_status,
+            break_duration: r.break_duration,
+            paused_at: r.paused_at,
+        }
+    }
+}
+
+/// Without times this starts a timer; with both it books a completed entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewTimesheet {
+    pub project_id: Option<String>,
+    pub activity_id: Option<String>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub description: Option<String>,
+    pub billable: bool,
+}
+
+/// Omitted fields are left as they are; an empty `description` clears it.
+/// `start_time` and `end_time` move the entry, and `end_time` stops a running
+/// timer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimesheetChanges {
+    pub description: Option<String>,
+    pub billable: Option<bool>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TimesheetParams {
+    /// `YYYY-MM-DD`, inclusive; requires `to`.
+    pub from: Option<String>,
+    /// `YYYY-MM-DD`, inclusive; requires `from`.
+    pub to: Option<String>,
+    pub page: Option<usize>,
+    pub per_page: Option<usize>,
+}
+
+async fn find_own(caller: &Caller, id: &str) -> Result<TimesheetRow, ApiError> {
+    loom::tenant::timesheet::find(caller.workspace()?, id)
+        .await?
+        .filter(|r| r.user_id == caller.user.id)
+        .ok_or_else(|| ApiError::not_found("timesheet"))
+}
+
+/// Entries starting within `from..=to`, oldest first, or the most recent
+/// ones, newest first, without a range.
+pub async fn list(
+    caller: Caller,
+    Params(params): Params<TimesheetParams>,
+) -> Result<Json<Page<Timesheet>>, ApiError> {
+    let workspace_id = caller.workspace()?;
+    let rows = match (&params.from, &params.to) {
+        (Some(from), Some(to)) => {
+            loom::tenant::timesheet::between(workspace_id, &caller.user.id, from, to).await?
+        }
+        (None, None) => loom::tenant::timesheet::recent(workspace_id, &caller.user.id).await?,
+        _ => return Err(ApiError::bad_request("from and to go together")),
+    };
+    let items = rows.into_iter().map(Timesheet::from).collect();
+    Ok(Json(Page::of(items, params.page, params.per_page)?))
+}
+
+pub async fn create(
+    caller: Caller,
+    Body(input): Body<NewTimesheet>,
+) -> Result<(StatusCode, Json<Timesheet>), ApiError> {
+    caller.require(permissions::TIMESHEET_CREATE).await?;
+    let workspace_id = caller.workspace()?;
+    let row = match (input.start_time, input.end_time) {
+        (Some(start_time), Some(end_time)) => {
+            loom::tenant::timesheet::create_manual(
+                workspace_id,
+                &caller.user.id,
+                input.project_id,
+                input.activity_id,
+                start_time,
+                end_time,
+                input.description,
+                input.billable,
+            )
+            .await?
+        }
+        (None, None) => {
+            loom::tenant::timesheet::start(
+                workspace_id,
+                &caller.user.id,
+                input.project_id,
+                input.activity_id,
+                input.description,
+                input.billable,
+            )
+            .await?
+        }
+        _ => {
+            return Err(ApiError::validation(
+                "Give both start_time and end_time, or neither to start a timer",
+            ))
+        }
+    };
+    Ok((StatusCode::CREATED, Json(row.into())))
+}
+
+pub async fn show(caller: Caller, Path(id): Path<String>) -> Result<Json<Timesheet>, ApiError> {
+    Ok(Json(find_own(&caller, &id).await?.into()))
+}
+
+/// Answers 204: a moved entry is rounded and rated as it is saved, but
+/// projections catch up asynchronously, so it is only worth fetching again
+/// once they did.
+pub async fn update(
+    caller: Caller,
+    Path(id): Path<String>,
+    Body(input): Body<TimesheetChanges>,
+) -> Result<StatusCode, ApiError> {
+    caller.require(permissions::TIMESHEET_UPDATE).await?;
+    let workspace_id = caller.workspace()?;
+    let current = find_own(&caller, &id).await?;
+    if input.start_time.is_some() || input.end_time.is_some() {
+        loom::tenant::timesheet::update_time(
+            workspace_id,
+            &caller.user.id,
+            &id,
+            input.start_time.unwrap_or(current.start_time),
+            input.end_time.or(current.end_time),
+        )
+        .await?;
+    }
+    if input.description.is_some() || input.billable.is_some() {
+        let description = match input.description {
+            Some(description) if description.is_empty() => None,
+            Some(description) => Some(description),
+            None => current.description,
+        };
+        loom::tenant::timesheet::update(
+            workspace_id,
+            &caller.user.id,
+            &id,
+            description,
+            input.billable.unwrap_or(current.billable),
+        )
+        .await?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn stop(caller: Caller, Path(id): Path<String>) -> Result<StatusCode, ApiError> {
+    caller.require(permissions::TIMESHEET_UPDATE).await?;
+    let workspace_id = caller.workspace()?;
+    find_own(&caller, &id).await?;
+    loom::tenant::timesheet::stop(workspace_id, &caller.user.id, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn delete(caller: Caller, Path(id): Path<String>) -> Result<StatusCode, ApiError> {
+    caller.require(permissions::TIMESHEET_DELETE).await?;
+    let workspace_id = caller.workspace()?;
+    find_own(&caller, &id).await?;
+    loom::tenant::timesheet::delete(workspace_id, &caller.user.id, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,76 @@
+//! The caller's personal API tokens in the current workspace.
+
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::Json;
+use loom::infrastructure::admin::api_token::repositories::ApiTokenRow;
+use serde::{Deserialize, Serialize};
+
+use super::error::ApiError;
+use super::extract::{Body, Caller};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    /// RFC 3339.
+    pub created_at: String,
+    /// RFC 3339.
+    pub last_used_at: Option<String>,
+    /// RFC 3339; revoked tokens no longer authenticate.
+    pub revoked_at: Option<String>,
+}
+
+impl From<ApiTokenRow> for ApiToken {
+    fn from(r: ApiTokenRow) -> Self {
+        Self {
+            id: r.id,
+            name: r.name,
+            created_at: r.created_at,
+            last_used_at: r.last_used_at,
+            revoked_at: r.revoked_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewApiToken {
+    pub name: String,
+}
+
+/// The only answer that contains the token itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssuedApiToken {
+    pub id: String,
+    pub name: String,
+    pub token: String,
+}
+
+pub async fn list(caller: Caller) -> Result<Json<Vec<ApiToken>>, ApiError> {
+    let rows = loom::api_token::tokens(&caller.user.id, caller.workspace()?).await?;
+    Ok(Json(rows.into_iter().map(ApiToken::from).collect()))
+}
+
+pub async fn create(
+    caller: Caller,
+    Body(input): Body<NewApiToken>,
+) -> Result<(StatusCode, Json<IssuedApiToken>), ApiError> {
+    let (row, token) =
+        loom::api_token::issue(&caller.user.id, caller.workspace()?, &input.name).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(IssuedApiToken {
+            id: row.id,
+            name: row.name,
+            token,
+        }),
+    ))
+}
+
+pub async fn revoke(caller: Caller, Path(id): Path<String>) -> Result<StatusCode, ApiError> {
+    if loom::api_token::revoke(&caller.user.id, &id).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::not_found("API token"))
+    }
+}
@@ -0,0 +1,47 @@
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use super::error::ApiError;
+use super::extract::Caller;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub id: String,
+    pub name: Option<String>,
+    /// Whether this is the workspace the other endpoints act on.
+    pub current: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Member {
+    pub user_id: String,
+    pub name: String,
+}
+
+/// The workspaces of the caller; needs no workspace to be selected.
+pub async fn list(caller: Caller) -> Result<Json<Vec<Workspace>>, ApiError> {
+    let workspaces = loom::workspace::list_user_workspaces(&caller.user.id).await?;
+    Ok(Json(
+        workspaces
+            .into_iter()
+            .map(|w| Workspace {
+                current: caller.user.workspace_id.as_deref() == Some(w.id.as_str()),
+                id: w.id,
+                name: w.name,
+            })
+            .collect(),
+    ))
+}
+
+pub async fn members(caller: Caller) -> Result<Json<Vec<Member>>, ApiError> {
+    let members = loom::workspace::members(caller.workspace()?).await?;
+    Ok(Json(
+        members
+            .into_iter()
+            .map(|m| Member {
+                user_id: m.user_id,
+                name: m.name,
+            })
+            .collect(),
+    ))
+}
@@ -0,0 +1,53 @@
+//! Full-text search across the workspace.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHitDto {
+    /// `customer`, `project`, `activity`, `tag` or `timesheet`.
+    pub entity_type: String,
+    pub entity_id: String,
+    /// The name, or the description of a time entry.
+    pub title: String,
+    /// Comment and order number, if any.
+    pub body: String,
+    /// When the time entry started; `None` for other entities.
+    pub start_time: Option<String>,
+}
+
+/// Customers, projects, activities, tags and time entries matching every
+/// word of `query`, best match first.  Time entries of other users are only
+/// found by admins and approvers.
+#[post("/api/search")]
+pub async fn search(query: String) -> Result<Vec<SearchHitDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _search(query).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = query;
+        Ok(vec![])
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _search(query: String) -> Result<Vec<SearchHitDto>, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let hits = loom::tenant::search::search(&workspace_id, &user.id, &query)
+        .await
+        .map_err(session::internal)?;
+    Ok(hits
+        .into_iter()
+        .map(|hit| SearchHitDto {
+            entity_type: hit.entity_type,
+            entity_id: hit.entity_id,
+            title: hit.title,
+            body: hit.body.trim().to_string(),
+            start_time: hit.start_time,
+        })
+        .collect())
+}
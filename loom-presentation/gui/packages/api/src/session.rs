@@ -85,6 +85,27 @@ pub async fn require_permission(
         })
 }
 
+/// Require that the session user holds the "admin" role in any workspace.
+///
+/// Returns 403 when the user is not an admin.
+#[cfg(feature = "server")]
+pub async fn require_admin(user: &crate::auth::UserInfo) -> Result<(), ServerFnError> {
+    use loom::auth::CurrentUser;
+    use loom::authorization::AuthorizationService;
+
+    let current_user = CurrentUser {
+        id: user.id.clone(),
+        email: user.email.clone(),
+    };
+    AuthorizationService::require_admin(&current_user)
+        .await
+        .map_err(|_| ServerFnError::ServerError {
+            message: "forbidden".into(),
+            code: 403,
+            details: None,
+        })
+}
+
 /// Map an `anyhow::Error` to a `ServerFnError`.
 ///
 /// Returns 422 Unprocessable Entity when the error is a `loom::error::ValidationError`
@@ -17,6 +17,128 @@ pub struct WorkspaceSettingsDto {
     pub week_start: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetPolicyDto {
+    /// Warning thresholds in percent of a budget.
+    pub thresholds: Vec<u8>,
+    /// Whether timers may not be started on over-budget projects.
+    pub block_over_budget: bool,
+}
+
+/// Rules every time entry of a workspace must satisfy.  Durations are in
+/// seconds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimeEntryPolicyDto {
+    pub disallow_overlaps: bool,
+    pub disallow_future: bool,
+    pub min_duration: Option<i32>,
+    pub max_duration: Option<i32>,
+    pub require_project: bool,
+    pub require_activity: bool,
+    pub require_description: bool,
+}
+
+/// Rounding of one value to a multiple of `minutes`; `mode` is `closest`,
+/// `up` or `down`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundingRuleDto {
+    pub mode: String,
+    pub minutes: u32,
+}
+
+/// Rounding rules for time entries.  A missing rule leaves the value as is;
+/// `billed_only` rounds the billed duration but keeps the real times.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoundingDto {
+    pub begin: Option<RoundingRuleDto>,
+    pub end: Option<RoundingRuleDto>,
+    pub duration: Option<RoundingRuleDto>,
+    pub billed_only: bool,
+}
+
+#[cfg(feature = "server")]
+impl From<loom::core::tenant::timesheet::Rounding> for RoundingDto {
+    fn from(rounding: loom::core::tenant::timesheet::Rounding) -> Self {
+        let rule = |rule: Option<loom::core::tenant::timesheet::RoundingRule>| {
+            rule.map(|rule| RoundingRuleDto {
+                mode: rule.mode.as_str().to_string(),
+                minutes: rule.minutes,
+            })
+        };
+        Self {
+            begin: rule(rounding.begin),
+            end: rule(rounding.end),
+            duration: rule(rounding.duration),
+            billed_only: rounding.billed_only,
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl RoundingDto {
+    pub(crate) fn into_rounding(
+        self,
+    ) -> Result<loom::core::tenant::timesheet::Rounding, ServerFnError> {
+        use loom::core::tenant::timesheet::{Rounding, RoundingRule};
+
+        let rule = |rule: Option<RoundingRuleDto>| {
+            rule.map(|rule| {
+                Ok(RoundingRule {
+                    mode: rule.mode.parse().map_err(|e: String| {
+                        crate::session::internal(loom::error::ValidationError::new(e).into())
+                    })?,
+                    minutes: rule.minutes,
+                })
+            })
+            .transpose()
+        };
+        Ok(Rounding {
+            begin: rule(self.begin)?,
+            end: rule(self.end)?,
+            duration: rule(self.duration)?,
+            billed_only: self.billed_only,
+        })
+    }
+}
+
+/// When forgotten timers of a workspace are stopped automatically.
+/// `max_duration` is in seconds, `end` is `cutoff` or `last_activity`, and
+/// `daily_stop_at` is a UTC time of day as `HH:MM`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutoStopPolicyDto {
+    pub max_duration: Option<i32>,
+    pub end: String,
+    pub daily_stop_at: Option<String>,
+    pub notify: bool,
+}
+
+impl Default for AutoStopPolicyDto {
+    fn default() -> Self {
+        Self {
+            max_duration: None,
+            end: "cutoff".to_string(),
+            daily_stop_at: None,
+            notify: false,
+        }
+    }
+}
+
+/// Period locks of a workspace.  Dates are `YYYY-MM-DD`; entries starting on
+/// or before them cannot be changed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PeriodLocksDto {
+    /// Lock that applies to everyone.
+    pub until: Option<String>,
+    pub users: Vec<UserPeriodLockDto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserPeriodLockDto {
+    pub user_id: String,
+    pub user_name: Option<String>,
+    pub until: String,
+}
+
 /// Returns the settings of the currently authenticated user.
 #[get("/api/settings/user")]
 pub async fn get_user_settings() -> Result<UserSettingsDto, ServerFnError> {
@@ -91,6 +213,174 @@ pub async fn update_workspace_settings(
     }
 }
 
+/// Returns the budget alert policy of the currently selected workspace.
+#[get("/api/settings/budget")]
+pub async fn get_budget_policy() -> Result<BudgetPolicyDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _get_budget_policy().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(BudgetPolicyDto {
+            thresholds: vec![80, 100],
+            block_over_budget: false,
+        })
+    }
+}
+
+/// Saves the budget alert policy of the currently selected workspace.
+///
+/// Only admins may change it.
+#[post("/api/settings/budget")]
+pub async fn update_budget_policy(
+    thresholds: Vec<u8>,
+    block_over_budget: bool,
+) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _update_budget_policy(thresholds, block_over_budget).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (thresholds, block_over_budget);
+        Ok(())
+    }
+}
+
+/// Returns the time entry policy of the currently selected workspace.
+#[get("/api/settings/time-entries")]
+pub async fn get_time_entry_policy() -> Result<TimeEntryPolicyDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _get_time_entry_policy().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(TimeEntryPolicyDto::default())
+    }
+}
+
+/// Saves the time entry policy of the currently selected workspace.
+///
+/// Only admins may change it.
+#[post("/api/settings/time-entries")]
+pub async fn update_time_entry_policy(policy: TimeEntryPolicyDto) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _update_time_entry_policy(policy).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = policy;
+        Ok(())
+    }
+}
+
+/// Returns the rounding rules of the currently selected workspace.
+#[get("/api/settings/rounding")]
+pub async fn get_rounding() -> Result<RoundingDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _get_rounding().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(RoundingDto::default())
+    }
+}
+
+/// Saves the rounding rules of the currently selected workspace.  Customers
+/// and projects without their own rules use them.
+///
+/// Only admins may change them.
+#[post("/api/settings/rounding")]
+pub async fn update_rounding(rounding: RoundingDto) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _update_rounding(rounding).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = rounding;
+        Ok(())
+    }
+}
+
+/// Returns the auto-stop policy of the currently selected workspace.
+#[get("/api/settings/auto-stop")]
+pub async fn get_auto_stop_policy() -> Result<AutoStopPolicyDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _get_auto_stop_policy().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(AutoStopPolicyDto::default())
+    }
+}
+
+/// Saves the auto-stop policy of the currently selected workspace.
+///
+/// Only admins may change it.
+#[post("/api/settings/auto-stop")]
+pub async fn update_auto_stop_policy(policy: AutoStopPolicyDto) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _update_auto_stop_policy(policy).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = policy;
+        Ok(())
+    }
+}
+
+/// Returns the period locks of the currently selected workspace.
+#[get("/api/settings/period-locks")]
+pub async fn get_period_locks() -> Result<PeriodLocksDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _get_period_locks().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(PeriodLocksDto::default())
+    }
+}
+
+/// Locks every entry up to and including `until` for `user_id`, or for the
+/// whole workspace without it.
+#[post("/api/settings/period-locks/lock")]
+pub async fn lock_period(user_id: Option<String>, until: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _lock_period(user_id, until).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (user_id, until);
+        Ok(())
+    }
+}
+
+/// Moves a lock back to `until`, or lifts it without `until`.
+#[post("/api/settings/period-locks/unlock")]
+pub async fn unlock_period(
+    user_id: Option<String>,
+    until: Option<String>,
+) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _unlock_period(user_id, until).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (user_id, until);
+        Ok(())
+    }
+}
+
 #[cfg(feature = "server")]
 async fn _get_user_settings() -> Result<UserSettingsDto, ServerFnError> {
     use crate::session;
@@ -152,3 +442,179 @@ async fn _update_workspace_settings(
         .await
         .map_err(session::internal)
 }
+
+#[cfg(feature = "server")]
+async fn _get_budget_policy() -> Result<BudgetPolicyDto, ServerFnError> {
+    use crate::session;
+
+    let (_user, workspace_id) = session::session_workspace().await?;
+    let view = loom::workspace::get_workspace_settings(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(BudgetPolicyDto {
+        thresholds: view.budget_thresholds,
+        block_over_budget: view.block_over_budget,
+    })
+}
+
+#[cfg(feature = "server")]
+async fn _update_budget_policy(
+    thresholds: Vec<u8>,
+    block_over_budget: bool,
+) -> Result<(), ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_admin(&user).await?;
+    loom::workspace::update_budget_policy(&workspace_id, thresholds, block_over_budget)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _get_time_entry_policy() -> Result<TimeEntryPolicyDto, ServerFnError> {
+    use crate::session;
+
+    let (_user, workspace_id) = session::session_workspace().await?;
+    let policy = loom::workspace::get_workspace_settings(&workspace_id)
+        .await
+        .map_err(session::internal)?
+        .time_entry_policy;
+    Ok(TimeEntryPolicyDto {
+        disallow_overlaps: policy.disallow_overlaps,
+        disallow_future: policy.disallow_future,
+        min_duration: policy.min_duration,
+        max_duration: policy.max_duration,
+        require_project: policy.require_project,
+        require_activity: policy.require_activity,
+        require_description: policy.require_description,
+    })
+}
+
+#[cfg(feature = "server")]
+async fn _update_time_entry_policy(policy: TimeEntryPolicyDto) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::tenant::timesheet::TimeEntryPolicy;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_admin(&user).await?;
+    let policy = TimeEntryPolicy {
+        disallow_overlaps: policy.disallow_overlaps,
+        disallow_future: policy.disallow_future,
+        min_duration: policy.min_duration,
+        max_duration: policy.max_duration,
+        require_project: policy.require_project,
+        require_activity: policy.require_activity,
+        require_description: policy.require_description,
+    };
+    loom::workspace::update_time_entry_policy(&workspace_id, policy)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _get_rounding() -> Result<RoundingDto, ServerFnError> {
+    use crate::session;
+
+    let (_user, workspace_id) = session::session_workspace().await?;
+    let view = loom::workspace::get_workspace_settings(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(view.rounding.into())
+}
+
+#[cfg(feature = "server")]
+async fn _update_rounding(rounding: RoundingDto) -> Result<(), ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_admin(&user).await?;
+    loom::workspace::update_rounding(&workspace_id, rounding.into_rounding()?)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _get_auto_stop_policy() -> Result<AutoStopPolicyDto, ServerFnError> {
+    use crate::session;
+
+    let (_user, workspace_id) = session::session_workspace().await?;
+    let policy = loom::workspace::get_workspace_settings(&workspace_id)
+        .await
+        .map_err(session::internal)?
+        .auto_stop_policy;
+    Ok(AutoStopPolicyDto {
+        max_duration: policy.max_duration,
+        end: policy.end.as_str().to_string(),
+        daily_stop_at: policy.daily_stop_at,
+        notify: policy.notify,
+    })
+}
+
+#[cfg(feature = "server")]
+async fn _update_auto_stop_policy(policy: AutoStopPolicyDto) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::tenant::timesheet::AutoStopPolicy;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_admin(&user).await?;
+    let policy = AutoStopPolicy {
+        max_duration: policy.max_duration,
+        end: policy.end.parse().map_err(|e: String| {
+            session::internal(loom::error::ValidationError::new(e).into())
+        })?,
+        daily_stop_at: policy.daily_stop_at.filter(|time| !time.trim().is_empty()),
+        notify: policy.notify,
+    };
+    loom::workspace::update_auto_stop_policy(&workspace_id, policy)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _get_period_locks() -> Result<PeriodLocksDto, ServerFnError> {
+    use crate::session;
+
+    let (_user, workspace_id) = session::session_workspace().await?;
+    let locks = loom::workspace::period_locks(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    let users = loom::workspace::user_period_locks(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(PeriodLocksDto {
+        until: locks.until,
+        users: users
+            .into_iter()
+            .map(|l| UserPeriodLockDto {
+                user_id: l.user_id,
+                user_name: l.user_name,
+                until: l.until,
+            })
+            .collect(),
+    })
+}
+
+#[cfg(feature = "server")]
+async fn _lock_period(user_id: Option<String>, until: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::PERIOD_LOCK).await?;
+    loom::workspace::lock_period(&workspace_id, user_id, until)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _unlock_period(user_id: Option<String>, until: Option<String>) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::PERIOD_UNLOCK).await?;
+    loom::workspace::unlock_period(&workspace_id, user_id, until)
+        .await
+        .map_err(session::internal)
+}
@@ -0,0 +1,225 @@
+//! Server functions of the desktop app's offline sync; they run against the
+//! local replica, see `loom::sync`.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncStatusDto {
+    /// The server the replica syncs with; `None` before connecting.
+    pub server: Option<String>,
+    /// `false` once the server session has expired.
+    pub signed_in: bool,
+    pub last_sync: Option<String>,
+    /// Why the last sync failed, e.g. because the server is unreachable.
+    pub last_error: Option<String>,
+    /// Local timesheet changes not on the server yet.
+    pub pending: i64,
+    pub conflicts: usize,
+}
+
+/// Local changes to a time entry the server refused.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncConflictDto {
+    pub id: i64,
+    pub timesheet_id: String,
+    pub server_version: i64,
+    /// Event types of the dropped local changes, oldest first.
+    pub local_changes: Vec<String>,
+    pub reason: String,
+    pub detected_at: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncReportDto {
+    pub pulled: usize,
+    pub pushed: usize,
+    pub conflicts: usize,
+}
+
+/// Signs in to `server` and, on first use, sets up the local replica of the
+/// workspace.  Signs in locally too.
+#[post("/api/sync/connect")]
+pub async fn connect_server(
+    server: String,
+    email: String,
+    password: String,
+    workspace_id: Option<String>,
+) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _connect_server(server, email, password, workspace_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (server, email, password, workspace_id);
+        Ok(())
+    }
+}
+
+#[get("/api/sync/status")]
+pub async fn sync_status() -> Result<SyncStatusDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _sync_status().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(SyncStatusDto::default())
+    }
+}
+
+/// Syncs right away instead of waiting for the next round.
+#[post("/api/sync/now")]
+pub async fn sync_now() -> Result<SyncReportDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _sync_now().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(SyncReportDto::default())
+    }
+}
+
+#[get("/api/sync/conflicts")]
+pub async fn list_sync_conflicts() -> Result<Vec<SyncConflictDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_sync_conflicts().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+#[post("/api/sync/conflicts/dismiss")]
+pub async fn dismiss_sync_conflict(id: i64) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _dismiss_sync_conflict(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _connect_server(
+    server: String,
+    email: String,
+    password: String,
+    workspace_id: Option<String>,
+) -> Result<(), ServerFnError> {
+    use crate::{auth::UserInfo, session};
+    use dioxus::fullstack::extract;
+    use tower_sessions::Session;
+
+    let account = loom::sync::client::connect(&server, &email, &password, workspace_id.as_deref())
+        .await
+        .map_err(|e| {
+            if e.is::<loom::sync::client::SignInRequired>() {
+                ServerFnError::ServerError {
+                    message: e.to_string(),
+                    code: 401,
+                    details: None,
+                }
+            } else {
+                session::internal(e)
+            }
+        })?;
+    let is_admin = loom::authorization::AuthorizationService::is_admin(&account.user_id)
+        .await
+        .map_err(session::internal)?;
+
+    let session: Session = extract().await?;
+    session
+        .insert(
+            "user",
+            UserInfo {
+                id: account.user_id,
+                email: account.email,
+                is_admin,
+                workspace_id: Some(account.workspace_id),
+            },
+        )
+        .await
+        .map_err(|e| ServerFnError::ServerError {
+            message: e.to_string(),
+            code: 500,
+            details: None,
+        })
+}
+
+#[cfg(feature = "server")]
+async fn _sync_status() -> Result<SyncStatusDto, ServerFnError> {
+    use crate::session;
+
+    let (_, workspace_id) = session::session_workspace().await?;
+    let status = loom::sync::client::status(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(SyncStatusDto {
+        server: status.server,
+        signed_in: status.signed_in,
+        last_sync: status.last_sync,
+        last_error: status.last_error,
+        pending: status.pending,
+        conflicts: status.conflicts,
+    })
+}
+
+#[cfg(feature = "server")]
+async fn _sync_now() -> Result<SyncReportDto, ServerFnError> {
+    use crate::session;
+
+    let (_, workspace_id) = session::session_workspace().await?;
+    let report = loom::sync::client::sync_once(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(SyncReportDto {
+        pulled: report.pulled,
+        pushed: report.pushed,
+        conflicts: report.conflicts,
+    })
+}
+
+#[cfg(feature = "server")]
+async fn _list_sync_conflicts() -> Result<Vec<SyncConflictDto>, ServerFnError> {
+    use crate::session;
+
+    let (_, workspace_id) = session::session_workspace().await?;
+    let rows = loom::sync::client::conflicts(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let local_changes =
+                serde_json::from_str::<Vec<loom::sync::SyncEvent>>(&row.local_events)
+                    .map(|events| events.into_iter().map(|e| e.event_type).collect())
+                    .unwrap_or_default();
+            SyncConflictDto {
+                id: row.id,
+                timesheet_id: row.stream_id,
+                server_version: row.server_version,
+                local_changes,
+                reason: row.reason,
+                detected_at: row.detected_at,
+            }
+        })
+        .collect())
+}
+
+#[cfg(feature = "server")]
+async fn _dismiss_sync_conflict(id: i64) -> Result<(), ServerFnError> {
+    use crate::session;
+
+    let (_, workspace_id) = session::session_workspace().await?;
+    loom::sync::client::dismiss_conflict(&workspace_id, id)
+        .await
+        .map_err(session::internal)
+}
@@ -11,7 +11,7 @@ pub struct TagDto {
 pub async fn list_tags() -> Result<Vec<TagDto>, ServerFnError> {
     #[cfg(feature = "server")]
     {
-        _list_tags().await
+        _list_tags(false).await
     }
     #[cfg(not(feature = "server"))]
     {
@@ -88,22 +88,81 @@ pub async fn untag_timesheet(tag_id: String, timesheet_id: String) -> Result<(),
     }
 }
 
+/// Archived tags, hidden from [`list_tags`].
+#[get("/api/tags/archived")]
+pub async fn list_archived_tags() -> Result<Vec<TagDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_tags(true).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+/// Hide a tag from lists and selectors.
+#[post("/api/tags/archive")]
+pub async fn archive_tag(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _archive_tag(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+/// Bring an archived tag back.
+#[post("/api/tags/restore")]
+pub async fn restore_tag(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _restore_tag(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+/// Permanently delete a tag.
+///
+/// Only allowed while no timesheet carries the tag.
+#[post("/api/tags/delete")]
+pub async fn delete_tag(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _delete_tag(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
 #[cfg(feature = "server")]
-async fn _list_tags() -> Result<Vec<TagDto>, ServerFnError> {
+async fn _list_tags(archived: bool) -> Result<Vec<TagDto>, ServerFnError> {
     use crate::session;
 
     let (_, workspace_id) = session::session_workspace().await?;
-    loom::tenant::tag::list(&workspace_id)
-        .await
-        .map(|rows| {
-            rows.into_iter()
-                .map(|r| TagDto {
-                    id: r.id,
-                    name: r.name,
-                })
-                .collect()
+    let rows = if archived {
+        loom::tenant::tag::list_archived(&workspace_id).await
+    } else {
+        loom::tenant::tag::list(&workspace_id).await
+    }
+    .map_err(session::internal)?;
+    Ok(rows
+        .into_iter()
+        .map(|r| TagDto {
+            id: r.id,
+            name: r.name,
         })
-        .map_err(session::internal)
+        .collect())
 }
 
 #[cfg(feature = "server")]
@@ -179,3 +238,42 @@ async fn _untag_timesheet(tag_id: String, timesheet_id: String) -> Result<(), Se
         .await
         .map_err(session::internal)
 }
+
+#[cfg(feature = "server")]
+async fn _archive_tag(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TAG_MANAGE).await?;
+
+    loom::tenant::tag::archive(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _restore_tag(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TAG_MANAGE).await?;
+
+    loom::tenant::tag::restore(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _delete_tag(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TAG_MANAGE).await?;
+
+    loom::tenant::tag::delete(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
@@ -0,0 +1,100 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{project::ProjectDto, project_rate::ProjectRateDto, timesheet::TimesheetDto};
+
+/// The point in history a time-travel query is evaluated at (inclusive).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AsOfDto {
+    /// RFC 3339 instant.
+    Instant(String),
+    /// Event store global position.
+    GlobalPosition(i64),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectAsOfDto {
+    pub project: ProjectDto,
+    pub rates: Vec<ProjectRateDto>,
+    pub timesheets: Vec<TimesheetDto>,
+    /// Total logged time in seconds.
+    pub logged_seconds: i64,
+    /// Total billable amount in cents.
+    pub logged_amount: i64,
+}
+
+/// A project's rates and logged time as they were at `as_of`.  Members only
+/// see their own entries, admins and approvers everyone's.  With
+/// `approved_only` only entries approved at that point are reported.
+///
+/// Returns `None` if the project did not exist yet.
+#[post("/api/time-travel/project")]
+pub async fn project_as_of(
+    project_id: String,
+    as_of: AsOfDto,
+    approved_only: bool,
+) -> Result<Option<ProjectAsOfDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _project_as_of(project_id, as_of, approved_only).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (project_id, as_of, approved_only);
+        Ok(None)
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _project_as_of(
+    project_id: String,
+    as_of: AsOfDto,
+    approved_only: bool,
+) -> Result<Option<ProjectAsOfDto>, ServerFnError> {
+    use crate::session;
+    use loom::tenant::time_travel::Cutoff;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let cutoff = match as_of {
+        AsOfDto::Instant(instant) => {
+            loom::tenant::time_travel::recorded_at(&instant).map_err(session::internal)?
+        }
+        AsOfDto::GlobalPosition(position) => Cutoff::GlobalPosition(position),
+    };
+    let report = loom::tenant::time_travel::project_as_of(
+        &workspace_id,
+        &user.id,
+        &project_id,
+        cutoff,
+        approved_only,
+    )
+    .await
+    .map_err(session::internal)?;
+
+    Ok(report.map(|r| ProjectAsOfDto {
+        project: ProjectDto {
+            id: r.project.id,
+            customer_id: r.project.customer_id,
+            name: r.project.name,
+            comment: r.project.comment,
+            order_number: r.project.order_number,
+            visible: r.project.visible,
+            billable: r.project.billable,
+            time_budget: r.project.time_budget,
+            money_budget: r.project.money_budget,
+            budget_is_monthly: r.project.budget_is_monthly,
+        },
+        rates: r
+            .rates
+            .into_iter()
+            .map(crate::project_rate::row_to_dto)
+            .collect(),
+        timesheets: r
+            .timesheets
+            .into_iter()
+            .map(crate::timesheet::row_to_dto)
+            .collect(),
+        logged_seconds: r.logged_seconds,
+        logged_amount: r.logged_amount,
+    }))
+}
@@ -18,8 +18,16 @@ pub struct TimesheetDto {
     pub hourly_rate: Option<i64>,
     /// Internal (cost) rate snapshot in cents.
     pub internal_rate: Option<i64>,
-    /// Total billable amount in cents (`hourly_rate * duration / 3600`).
+    /// Total billable amount in cents (`hourly_rate * billed_duration / 3600`).
     pub rate: Option<i64>,
+    /// Duration in seconds after rounding, the basis of `rate`.
+    pub billed_duration: Option<i32>,
+    /// `draft`, `submitted`, `approved` or `rejected`.
+    pub approval_status: String,
+    /// Total length of the breaks in seconds, excluded from `duration`.
+    pub break_duration: i32,
+    /// When the running timer was paused, if it is paused.
+    pub paused_at: Option<String>,
 }
 
 #[get("/api/timesheets/recent")]
@@ -46,6 +54,38 @@ pub async fn running_timesheet() -> Result<Option<TimesheetDto>, ServerFnError>
     }
 }
 
+/// The user's entries starting on the days `from..=to` (`YYYY-MM-DD`),
+/// oldest first.
+#[post("/api/timesheets/between")]
+pub async fn list_timesheets_between(
+    from: String,
+    to: String,
+) -> Result<Vec<TimesheetDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_timesheets_between(from, to).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (from, to);
+        Ok(vec![])
+    }
+}
+
+/// One of the user's own entries, `None` if there is no such entry.
+#[post("/api/timesheets/get")]
+pub async fn get_timesheet(timesheet_id: String) -> Result<Option<TimesheetDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _get_timesheet(timesheet_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = timesheet_id;
+        Ok(None)
+    }
+}
+
 #[post("/api/timesheets/start")]
 pub async fn start_timesheet(
     project_id: Option<String>,
@@ -68,6 +108,24 @@ pub async fn start_timesheet(
     }
 }
 
+/// Start a new timer cloned from one of the user's entries, tags included.
+#[post("/api/timesheets/restart")]
+pub async fn restart_timesheet(timesheet_id: String) -> Result<TimesheetDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _restart_timesheet(timesheet_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = timesheet_id;
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
 #[post("/api/timesheets/reassign")]
 pub async fn reassign_timesheet(
     timesheet_id: String,
@@ -156,6 +214,34 @@ pub async fn stop_timesheet(timesheet_id: String) -> Result<(), ServerFnError> {
     }
 }
 
+/// Pause the running timer, e.g. for a lunch break.
+#[post("/api/timesheets/pause")]
+pub async fn pause_timesheet(timesheet_id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _pause_timesheet(timesheet_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = timesheet_id;
+        Ok(())
+    }
+}
+
+/// Resume a paused timer; the break does not count towards the duration.
+#[post("/api/timesheets/resume")]
+pub async fn resume_timesheet(timesheet_id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _resume_timesheet(timesheet_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = timesheet_id;
+        Ok(())
+    }
+}
+
 #[post("/api/timesheets/export")]
 pub async fn export_timesheet(timesheet_id: String) -> Result<(), ServerFnError> {
     #[cfg(feature = "server")]
@@ -169,8 +255,96 @@ pub async fn export_timesheet(timesheet_id: String) -> Result<(), ServerFnError>
     }
 }
 
+/// Export every stopped, not yet exported entry that starts within
+/// `from..=to` (`YYYY-MM-DD`), optionally only approved ones.
+#[post("/api/timesheets/export-period")]
+pub async fn export_period(
+    from: String,
+    to: String,
+    approved_only: bool,
+) -> Result<Vec<TimesheetDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _export_period(from, to, approved_only).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (from, to, approved_only);
+        Ok(vec![])
+    }
+}
+
+/// Delete a mistaken entry.  Exported entries cannot be deleted.
+#[post("/api/timesheets/delete")]
+pub async fn delete_timesheet(timesheet_id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _delete_timesheet(timesheet_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = timesheet_id;
+        Ok(())
+    }
+}
+
+/// Copy a stopped entry with its tags.  Without `start_time` the copy starts
+/// at the same time of day, today.
+#[post("/api/timesheets/duplicate")]
+pub async fn duplicate_timesheet(
+    timesheet_id: String,
+    start_time: Option<String>,
+) -> Result<TimesheetDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _duplicate_timesheet(timesheet_id, start_time).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (timesheet_id, start_time);
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
+/// Split a stopped entry at `at`; returns the newly created second piece.
+#[post("/api/timesheets/split")]
+pub async fn split_timesheet(timesheet_id: String, at: String) -> Result<TimesheetDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _split_timesheet(timesheet_id, at).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (timesheet_id, at);
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
+/// Merge two adjacent entries of the same project and activity into the
+/// earlier one.
+#[post("/api/timesheets/merge")]
+pub async fn merge_timesheets(first_id: String, second_id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _merge_timesheets(first_id, second_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (first_id, second_id);
+        Ok(())
+    }
+}
+
 #[cfg(feature = "server")]
-fn row_to_dto(
+pub(crate) fn row_to_dto(
     r: loom::infrastructure::tenant::timesheet::repositories::TimesheetRow,
 ) -> TimesheetDto {
     TimesheetDto {
@@ -188,6 +362,10 @@ fn row_to_dto(
         hourly_rate: r.hourly_rate,
         internal_rate: r.internal_rate,
         rate: r.rate,
+        billed_duration: r.billed_duration,
+        approval_status: r.approval_status,
+        break_duration: r.break_duration,
+        paused_at: r.paused_at,
     }
 }
 
@@ -202,6 +380,31 @@ async fn _list_timesheets() -> Result<Vec<TimesheetDto>, ServerFnError> {
     Ok(rows.into_iter().map(row_to_dto).collect())
 }
 
+#[cfg(feature = "server")]
+async fn _list_timesheets_between(
+    from: String,
+    to: String,
+) -> Result<Vec<TimesheetDto>, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let rows = loom::tenant::timesheet::between(&workspace_id, &user.id, &from, &to)
+        .await
+        .map_err(session::internal)?;
+    Ok(rows.into_iter().map(row_to_dto).collect())
+}
+
+#[cfg(feature = "server")]
+async fn _get_timesheet(timesheet_id: String) -> Result<Option<TimesheetDto>, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let row = loom::tenant::timesheet::find(&workspace_id, &timesheet_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(row.filter(|r| r.user_id == user.id).map(row_to_dto))
+}
+
 #[cfg(feature = "server")]
 async fn _running_timesheet() -> Result<Option<TimesheetDto>, ServerFnError> {
     use crate::session;
@@ -239,6 +442,20 @@ async fn _start_timesheet(
     Ok(row_to_dto(r))
 }
 
+#[cfg(feature = "server")]
+async fn _restart_timesheet(timesheet_id: String) -> Result<TimesheetDto, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_CREATE).await?;
+
+    let r = loom::tenant::timesheet::restart(&workspace_id, &user.id, &timesheet_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(row_to_dto(r))
+}
+
 #[cfg(feature = "server")]
 async fn _reassign_timesheet(
     timesheet_id: String,
@@ -251,9 +468,15 @@ async fn _reassign_timesheet(
     let (user, workspace_id) = session::session_workspace().await?;
     session::require_permission(&user, permissions::TIMESHEET_UPDATE).await?;
 
-    loom::tenant::timesheet::reassign(&workspace_id, &timesheet_id, project_id, activity_id)
-        .await
-        .map_err(session::internal)
+    loom::tenant::timesheet::reassign(
+        &workspace_id,
+        &user.id,
+        &timesheet_id,
+        project_id,
+        activity_id,
+    )
+    .await
+    .map_err(session::internal)
 }
 
 #[cfg(feature = "server")]
@@ -268,9 +491,15 @@ async fn _update_timesheet(
     let (user, workspace_id) = session::session_workspace().await?;
     session::require_permission(&user, permissions::TIMESHEET_UPDATE).await?;
 
-    loom::tenant::timesheet::update(&workspace_id, &timesheet_id, description, billable)
-        .await
-        .map_err(session::internal)
+    loom::tenant::timesheet::update(
+        &workspace_id,
+        &user.id,
+        &timesheet_id,
+        description,
+        billable,
+    )
+    .await
+    .map_err(session::internal)
 }
 
 #[cfg(feature = "server")]
@@ -282,7 +511,33 @@ async fn _stop_timesheet(timesheet_id: String) -> Result<(), ServerFnError> {
     let (user, workspace_id) = session::session_workspace().await?;
     session::require_permission(&user, permissions::TIMESHEET_UPDATE).await?;
 
-    loom::tenant::timesheet::stop(&workspace_id, &timesheet_id)
+    loom::tenant::timesheet::stop(&workspace_id, &user.id, &timesheet_id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _pause_timesheet(timesheet_id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_UPDATE).await?;
+
+    loom::tenant::timesheet::pause(&workspace_id, &user.id, &timesheet_id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _resume_timesheet(timesheet_id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_UPDATE).await?;
+
+    loom::tenant::timesheet::resume(&workspace_id, &user.id, &timesheet_id)
         .await
         .map_err(session::internal)
 }
@@ -326,12 +581,18 @@ async fn _update_timesheet_time(
     use crate::session;
     use loom::core::permissions;
 
-    let (_user, workspace_id) = session::session_workspace().await?;
-    session::require_permission(&_user, permissions::TIMESHEET_UPDATE).await?;
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_UPDATE).await?;
 
-    loom::tenant::timesheet::update_time(&workspace_id, &timesheet_id, start_time, end_time)
-        .await
-        .map_err(session::internal)
+    loom::tenant::timesheet::update_time(
+        &workspace_id,
+        &user.id,
+        &timesheet_id,
+        start_time,
+        end_time,
+    )
+    .await
+    .map_err(session::internal)
 }
 
 #[cfg(feature = "server")]
@@ -346,3 +607,80 @@ async fn _export_timesheet(timesheet_id: String) -> Result<(), ServerFnError> {
         .await
         .map_err(session::internal)
 }
+
+#[cfg(feature = "server")]
+async fn _export_period(
+    from: String,
+    to: String,
+    approved_only: bool,
+) -> Result<Vec<TimesheetDto>, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_EXPORT).await?;
+
+    let rows = loom::tenant::timesheet::export_period(&workspace_id, &from, &to, approved_only)
+        .await
+        .map_err(session::internal)?;
+    Ok(rows.into_iter().map(row_to_dto).collect())
+}
+
+#[cfg(feature = "server")]
+async fn _delete_timesheet(timesheet_id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_DELETE).await?;
+
+    loom::tenant::timesheet::delete(&workspace_id, &user.id, &timesheet_id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _duplicate_timesheet(
+    timesheet_id: String,
+    start_time: Option<String>,
+) -> Result<TimesheetDto, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_CREATE).await?;
+
+    let r = loom::tenant::timesheet::duplicate(&workspace_id, &user.id, &timesheet_id, start_time)
+        .await
+        .map_err(session::internal)?;
+    Ok(row_to_dto(r))
+}
+
+#[cfg(feature = "server")]
+async fn _split_timesheet(timesheet_id: String, at: String) -> Result<TimesheetDto, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_UPDATE).await?;
+
+    let r = loom::tenant::timesheet::split(&workspace_id, &user.id, &timesheet_id, at)
+        .await
+        .map_err(session::internal)?;
+    Ok(row_to_dto(r))
+}
+
+#[cfg(feature = "server")]
+async fn _merge_timesheets(first_id: String, second_id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    // The later entry is deleted.
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::TIMESHEET_UPDATE).await?;
+    session::require_permission(&user, permissions::TIMESHEET_DELETE).await?;
+
+    loom::tenant::timesheet::merge(&workspace_id, &user.id, &first_id, &second_id)
+        .await
+        .map_err(session::internal)
+}
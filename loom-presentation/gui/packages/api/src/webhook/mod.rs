@@ -0,0 +1,290 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// An outgoing webhook subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookDto {
+    pub id: String,
+    pub url: String,
+    /// Key of the `X-Loom-Signature` HMAC.
+    pub secret: String,
+    pub event_types: Vec<String>,
+}
+
+/// One event sent, or to be sent, to a webhook.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookDeliveryDto {
+    pub id: i64,
+    pub webhook_id: String,
+    pub event_type: String,
+    pub aggregate_id: String,
+    /// 0 for the original delivery, counting up with each redelivery.
+    pub redelivery: i32,
+    /// The request body (JSON).
+    pub payload: String,
+    /// `pending`, `delivered` or `failed`.
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: Option<String>,
+    pub last_attempt_at: Option<String>,
+    pub response_status: Option<i32>,
+    pub error: Option<String>,
+    pub created_at: String,
+}
+
+/// Names of the events a webhook can subscribe to.
+#[get("/api/webhooks/events")]
+pub async fn list_subscribable_events() -> Result<Vec<String>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        Ok(loom::core::tenant::webhook::SUBSCRIBABLE_EVENTS
+            .iter()
+            .map(ToString::to_string)
+            .collect())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+#[get("/api/webhooks")]
+pub async fn list_webhooks() -> Result<Vec<WebhookDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_webhooks().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+#[post("/api/webhooks/subscribe")]
+pub async fn subscribe_webhook(
+    url: String,
+    event_types: Vec<String>,
+) -> Result<WebhookDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _subscribe_webhook(url, event_types).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (url, event_types);
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
+#[post("/api/webhooks/change")]
+pub async fn change_webhook(
+    id: String,
+    url: String,
+    event_types: Vec<String>,
+) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _change_webhook(id, url, event_types).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, url, event_types);
+        Ok(())
+    }
+}
+
+/// Returns the new secret.
+#[post("/api/webhooks/rotate-secret")]
+pub async fn rotate_webhook_secret(id: String) -> Result<String, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _rotate_webhook_secret(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(String::new())
+    }
+}
+
+#[post("/api/webhooks/remove")]
+pub async fn remove_webhook(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _remove_webhook(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+/// The most recent deliveries of one webhook, or of all, newest first.
+#[post("/api/webhooks/deliveries")]
+pub async fn list_webhook_deliveries(
+    webhook_id: Option<String>,
+) -> Result<Vec<WebhookDeliveryDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_webhook_deliveries(webhook_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = webhook_id;
+        Ok(vec![])
+    }
+}
+
+#[post("/api/webhooks/redeliver")]
+pub async fn redeliver_webhook(delivery_id: i64) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _redeliver_webhook(delivery_id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = delivery_id;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "server")]
+fn webhook_to_dto(
+    r: loom::infrastructure::tenant::webhook::repositories::WebhookRow,
+) -> WebhookDto {
+    WebhookDto {
+        id: r.id,
+        url: r.url,
+        secret: r.secret,
+        event_types: r.event_types,
+    }
+}
+
+#[cfg(feature = "server")]
+fn delivery_to_dto(
+    r: loom::infrastructure::tenant::webhook::repositories::DeliveryRow,
+) -> WebhookDeliveryDto {
+    WebhookDeliveryDto {
+        id: r.id,
+        webhook_id: r.webhook_id,
+        event_type: r.event_type,
+        aggregate_id: r.stream_id,
+        redelivery: r.redelivery,
+        payload: r.payload,
+        status: r.status.as_str().to_string(),
+        attempts: r.attempts,
+        next_attempt_at: r.next_attempt_at,
+        last_attempt_at: r.last_attempt_at,
+        response_status: r.response_status,
+        error: r.error,
+        created_at: r.created_at,
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _list_webhooks() -> Result<Vec<WebhookDto>, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WEBHOOK_MANAGE).await?;
+
+    let rows = loom::tenant::webhook::webhooks(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(rows.into_iter().map(webhook_to_dto).collect())
+}
+
+#[cfg(feature = "server")]
+async fn _subscribe_webhook(
+    url: String,
+    event_types: Vec<String>,
+) -> Result<WebhookDto, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WEBHOOK_MANAGE).await?;
+
+    let row = loom::tenant::webhook::subscribe(&workspace_id, url, event_types)
+        .await
+        .map_err(session::internal)?;
+    Ok(webhook_to_dto(row))
+}
+
+#[cfg(feature = "server")]
+async fn _change_webhook(
+    id: String,
+    url: String,
+    event_types: Vec<String>,
+) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WEBHOOK_MANAGE).await?;
+
+    loom::tenant::webhook::change(&workspace_id, &id, url, event_types)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _rotate_webhook_secret(id: String) -> Result<String, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WEBHOOK_MANAGE).await?;
+
+    loom::tenant::webhook::rotate_secret(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _remove_webhook(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WEBHOOK_MANAGE).await?;
+
+    loom::tenant::webhook::remove(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _list_webhook_deliveries(
+    webhook_id: Option<String>,
+) -> Result<Vec<WebhookDeliveryDto>, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WEBHOOK_MANAGE).await?;
+
+    let rows = loom::tenant::webhook::deliveries(&workspace_id, webhook_id.as_deref())
+        .await
+        .map_err(session::internal)?;
+    Ok(rows.into_iter().map(delivery_to_dto).collect())
+}
+
+#[cfg(feature = "server")]
+async fn _redeliver_webhook(delivery_id: i64) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WEBHOOK_MANAGE).await?;
+
+    loom::tenant::webhook::redeliver(&workspace_id, delivery_id)
+        .await
+        .map_err(session::internal)
+}
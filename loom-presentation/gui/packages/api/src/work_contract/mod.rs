@@ -0,0 +1,376 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Working time a user is expected to put in from `starts_on` on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkContractDto {
+    pub id: String,
+    pub user_id: String,
+    pub weekly_minutes: i32,
+    /// ISO weekday numbers, 1 (Monday) to 7 (Sunday).
+    pub working_days: Vec<u8>,
+    /// `YYYY-MM-DD`.
+    pub starts_on: String,
+    pub region: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HolidayDto {
+    pub id: String,
+    pub region: String,
+    /// `YYYY-MM-DD`.
+    pub date: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeekBalanceDto {
+    /// First day of the week, `YYYY-MM-DD`.
+    pub week: String,
+    pub expected_seconds: i64,
+    pub worked_seconds: i64,
+}
+
+/// Overtime (positive) or undertime (negative) of a user up to `until`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OvertimeBalanceDto {
+    pub user_id: String,
+    pub user_name: Option<String>,
+    pub until: String,
+    pub expected_seconds: i64,
+    pub worked_seconds: i64,
+    pub balance_seconds: i64,
+    pub weeks: Vec<WeekBalanceDto>,
+}
+
+#[get("/api/work-contracts")]
+pub async fn list_work_contracts() -> Result<Vec<WorkContractDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_work_contracts().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+#[post("/api/work-contracts/define")]
+pub async fn define_work_contract(
+    user_id: String,
+    weekly_minutes: i32,
+    working_days: Vec<u8>,
+    starts_on: String,
+    region: Option<String>,
+) -> Result<WorkContractDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _define_work_contract(user_id, weekly_minutes, working_days, starts_on, region).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (user_id, weekly_minutes, working_days, starts_on, region);
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
+#[post("/api/work-contracts/change")]
+pub async fn change_work_contract(
+    id: String,
+    weekly_minutes: i32,
+    working_days: Vec<u8>,
+    starts_on: String,
+    region: Option<String>,
+) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _change_work_contract(id, weekly_minutes, working_days, starts_on, region).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (id, weekly_minutes, working_days, starts_on, region);
+        Ok(())
+    }
+}
+
+#[post("/api/work-contracts/remove")]
+pub async fn remove_work_contract(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _remove_work_contract(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+#[get("/api/holidays")]
+pub async fn list_holidays() -> Result<Vec<HolidayDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_holidays().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
+#[post("/api/holidays/declare")]
+pub async fn declare_holiday(
+    region: String,
+    date: String,
+    name: String,
+) -> Result<HolidayDto, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _declare_holiday(region, date, name).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = (region, date, name);
+        Err(ServerFnError::ServerError {
+            message: "server only".into(),
+            code: 500,
+            details: None,
+        })
+    }
+}
+
+#[post("/api/holidays/remove")]
+pub async fn remove_holiday(id: String) -> Result<(), ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _remove_holiday(id).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = id;
+        Ok(())
+    }
+}
+
+/// The session user's balance up to today, or `None` without a contract.
+#[get("/api/overtime/mine")]
+pub async fn my_overtime_balance() -> Result<Option<OvertimeBalanceDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _my_overtime_balance().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(None)
+    }
+}
+
+/// Everyone's balance up to and including `until` (`YYYY-MM-DD`).
+#[post("/api/overtime/report")]
+pub async fn overtime_balances(until: String) -> Result<Vec<OvertimeBalanceDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _overtime_balances(until).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        let _ = until;
+        Ok(vec![])
+    }
+}
+
+#[cfg(feature = "server")]
+fn contract_to_dto(
+    r: loom::infrastructure::tenant::work_contract::repositories::WorkContractRow,
+) -> WorkContractDto {
+    WorkContractDto {
+        id: r.id,
+        user_id: r.user_id,
+        weekly_minutes: r.weekly_minutes,
+        working_days: r.working_days,
+        starts_on: r.starts_on,
+        region: r.region,
+    }
+}
+
+#[cfg(feature = "server")]
+fn holiday_to_dto(r: loom::infrastructure::tenant::holiday::repositories::HolidayRow) -> HolidayDto {
+    HolidayDto {
+        id: r.id,
+        region: r.region,
+        date: r.date,
+        name: r.name,
+    }
+}
+
+#[cfg(feature = "server")]
+fn balance_to_dto(b: loom::tenant::work_contract::OvertimeBalance) -> OvertimeBalanceDto {
+    OvertimeBalanceDto {
+        user_id: b.user_id,
+        user_name: b.user_name,
+        until: b.until,
+        expected_seconds: b.expected_seconds,
+        worked_seconds: b.worked_seconds,
+        balance_seconds: b.balance_seconds,
+        weeks: b
+            .weeks
+            .into_iter()
+            .map(|w| WeekBalanceDto {
+                week: w.week.format("%Y-%m-%d").to_string(),
+                expected_seconds: w.expected_seconds,
+                worked_seconds: w.worked_seconds,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(feature = "server")]
+async fn _list_work_contracts() -> Result<Vec<WorkContractDto>, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WORK_CONTRACT_MANAGE).await?;
+
+    let rows = loom::tenant::work_contract::contracts(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(rows.into_iter().map(contract_to_dto).collect())
+}
+
+#[cfg(feature = "server")]
+async fn _define_work_contract(
+    user_id: String,
+    weekly_minutes: i32,
+    working_days: Vec<u8>,
+    starts_on: String,
+    region: Option<String>,
+) -> Result<WorkContractDto, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WORK_CONTRACT_MANAGE).await?;
+
+    let row = loom::tenant::work_contract::define(
+        &workspace_id,
+        &user_id,
+        weekly_minutes,
+        working_days,
+        starts_on,
+        region,
+    )
+    .await
+    .map_err(session::internal)?;
+    Ok(contract_to_dto(row))
+}
+
+#[cfg(feature = "server")]
+async fn _change_work_contract(
+    id: String,
+    weekly_minutes: i32,
+    working_days: Vec<u8>,
+    starts_on: String,
+    region: Option<String>,
+) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WORK_CONTRACT_MANAGE).await?;
+
+    loom::tenant::work_contract::change(
+        &workspace_id,
+        &id,
+        weekly_minutes,
+        working_days,
+        starts_on,
+        region,
+    )
+    .await
+    .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _remove_work_contract(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WORK_CONTRACT_MANAGE).await?;
+
+    loom::tenant::work_contract::remove(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _list_holidays() -> Result<Vec<HolidayDto>, ServerFnError> {
+    use crate::session;
+
+    let (_, workspace_id) = session::session_workspace().await?;
+    let rows = loom::tenant::work_contract::holidays(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(rows.into_iter().map(holiday_to_dto).collect())
+}
+
+#[cfg(feature = "server")]
+async fn _declare_holiday(
+    region: String,
+    date: String,
+    name: String,
+) -> Result<HolidayDto, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WORK_CONTRACT_MANAGE).await?;
+
+    let row = loom::tenant::work_contract::declare_holiday(&workspace_id, region, date, name)
+        .await
+        .map_err(session::internal)?;
+    Ok(holiday_to_dto(row))
+}
+
+#[cfg(feature = "server")]
+async fn _remove_holiday(id: String) -> Result<(), ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WORK_CONTRACT_MANAGE).await?;
+
+    loom::tenant::work_contract::remove_holiday(&workspace_id, &id)
+        .await
+        .map_err(session::internal)
+}
+
+#[cfg(feature = "server")]
+async fn _my_overtime_balance() -> Result<Option<OvertimeBalanceDto>, ServerFnError> {
+    use crate::session;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    let balance = loom::tenant::work_contract::balance_today(&workspace_id, &user.id)
+        .await
+        .map_err(session::internal)?;
+    Ok(balance.map(balance_to_dto))
+}
+
+#[cfg(feature = "server")]
+async fn _overtime_balances(until: String) -> Result<Vec<OvertimeBalanceDto>, ServerFnError> {
+    use crate::session;
+    use loom::core::permissions;
+
+    let (user, workspace_id) = session::session_workspace().await?;
+    session::require_permission(&user, permissions::WORK_CONTRACT_MANAGE).await?;
+
+    let balances = loom::tenant::work_contract::balances(&workspace_id, &until)
+        .await
+        .map_err(session::internal)?;
+    Ok(balances.into_iter().map(balance_to_dto).collect())
+}
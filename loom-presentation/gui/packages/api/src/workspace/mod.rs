@@ -20,6 +20,25 @@ pub async fn list_workspaces() -> Result<Vec<WorkspaceDto>, ServerFnError> {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemberDto {
+    pub user_id: String,
+    pub name: String,
+}
+
+/// Returns the members of the current workspace, ordered by name.
+#[get("/api/workspaces/members")]
+pub async fn list_members() -> Result<Vec<MemberDto>, ServerFnError> {
+    #[cfg(feature = "server")]
+    {
+        _list_members().await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        Ok(vec![])
+    }
+}
+
 /// Stores the selected workspace_id in the session.
 #[post("/api/workspaces/select")]
 pub async fn select_workspace(workspace_id: String) -> Result<(), ServerFnError> {
@@ -73,6 +92,23 @@ async fn _list_workspaces() -> Result<Vec<WorkspaceDto>, ServerFnError> {
         .collect())
 }
 
+#[cfg(feature = "server")]
+async fn _list_members() -> Result<Vec<MemberDto>, ServerFnError> {
+    use crate::session;
+
+    let (_, workspace_id) = session::session_workspace().await?;
+    let members = loom::workspace::members(&workspace_id)
+        .await
+        .map_err(session::internal)?;
+    Ok(members
+        .into_iter()
+        .map(|m| MemberDto {
+            user_id: m.user_id,
+            name: m.name,
+        })
+        .collect())
+}
+
 #[cfg(feature = "server")]
 async fn _select_workspace(workspace_id: String) -> Result<(), ServerFnError> {
     use crate::auth::UserInfo;
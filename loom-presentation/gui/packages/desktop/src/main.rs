@@ -1,42 +1,333 @@
-use dioxus::prelude::*;
+//! The desktop app: the timesheet views of the web app on top of a local
+//! copy of one workspace, so time can be tracked without a connection.
+//!
+//! The server functions run in the same process against a local SQLite
+//! database (see `loom::sync`), which syncs with the Loom server whenever it
+//! is reachable.
 
-use views::{Blog, Home};
+use api::auth::UserInfo;
+use api::settings::{UserSettingsDto, WorkspaceSettingsDto};
+use dioxus::prelude::*;
+use ui::{
+    components::{
+        atoms::{ToastMessage, ToastStack},
+        organisms::{Header, Sidebar},
+    },
+    guards::{AuthGuard, WorkspaceGuard},
+    views::{Dashboard, Login, SelectWorkspace, Timesheets, WorkTime},
+    ActivitiesCache, AuthState, CustomersCache, GlobalStyles, ProjectsCache, RunningElapsed,
+    RunningTimer, TagsCache, TimesheetsCache, UserSettings, WorkspaceSettings, FAVICON,
+};
+use views::{Connect, SyncBadge, SyncStatus};
 
 mod views;
 
+const MAIN_CSS: Asset = asset!("/assets/main.css");
+
+/// Where the embedded server listens when running as one process.
+#[cfg(all(feature = "server", feature = "desktop"))]
+const LOCAL_ADDRESS: &str = "127.0.0.1:8765";
+#[cfg(all(feature = "server", feature = "desktop"))]
+const LOCAL_URL: &str = "http://127.0.0.1:8765";
+
 #[derive(Debug, Clone, Routable, PartialEq)]
 #[rustfmt::skip]
 enum Route {
-    #[layout(DesktopNavbar)]
-    #[route("/")]
-    Home {},
-    #[route("/blog/:id")]
-    Blog { id: i32 },
+    #[layout(Layout)]
+
+        // Sign in to a server; sets up the local copy on first use.
+        #[route("/connect")]
+        Connect {},
+
+        // All remaining routes — only once the local copy is set up.
+        #[layout(RequireReplica)]
+            #[route("/login")]
+            Login {},
+
+            #[layout(RequireAuth)]
+                #[route("/select-workspace")]
+                SelectWorkspace {},
+
+                #[layout(RequireWorkspace)]
+                    #[route("/dashboard")]
+                    Dashboard {},
+
+                    #[route("/timesheets")]
+                    Timesheets {},
+
+                    #[route("/work-time")]
+                    WorkTime {},
+
+                    #[route("/sync")]
+                    SyncStatus {},
+                #[end_layout]
+            #[end_layout]
+        #[end_layout]
+
+    #[end_layout]
+
+    #[redirect("/", || Route::Dashboard {})]
+    #[route("/:..route")]
+    NotFound { route: Vec<String> },
 }
 
-const MAIN_CSS: Asset = asset!("/assets/main.css");
+#[component]
+fn NotFound(route: Vec<String>) -> Element {
+    rsx! {
+        h1 { "Only available in the web app" }
+    }
+}
 
+#[cfg(not(feature = "server"))]
 fn main() {
+    dotenvy::from_filename_override(".env.dev").ok();
     dioxus::launch(App);
 }
 
+#[cfg(feature = "server")]
+fn main() {
+    dotenvy::from_filename_override(".env.dev").ok();
+    use_local_data_dir();
+
+    let runtime = tokio::runtime::Runtime::new().expect("failed to start the async runtime");
+    runtime
+        .block_on(loom::setup::init_admin_db())
+        .expect("failed to initialise admin database");
+    runtime.spawn(async {
+        if let Err(e) = loom::sync::replica::run().await {
+            eprintln!("local replica stopped: {e:#}");
+        }
+    });
+
+    #[cfg(feature = "desktop")]
+    {
+        let _guard = runtime.enter();
+        runtime.spawn(serve(LOCAL_ADDRESS.parse().expect("invalid local address")));
+        dioxus::fullstack::set_server_url(LOCAL_URL);
+        dioxus::launch(App);
+    }
+    #[cfg(not(feature = "desktop"))]
+    runtime.block_on(serve(dioxus::cli_config::fullstack_address_or_localhost()));
+}
+
+/// Keeps the local databases in the user's home directory unless
+/// `DATABASE_BASE_URI` says otherwise.
+#[cfg(feature = "server")]
+fn use_local_data_dir() {
+    if std::env::var_os("DATABASE_BASE_URI").is_some() {
+        return;
+    }
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map_or_else(|| std::path::PathBuf::from("."), std::path::PathBuf::from);
+    let dir = home.join(".loom");
+    std::fs::create_dir_all(&dir).expect("failed to create the data directory");
+    std::env::set_var("DATABASE_BASE_URI", format!("sqlite://{}", dir.display()));
+    if std::env::var_os("ADMIN_DATABASE_NAME").is_none() {
+        std::env::set_var("ADMIN_DATABASE_NAME", "loom_admin");
+    }
+}
+
+#[cfg(feature = "server")]
+async fn serve(address: std::net::SocketAddr) {
+    let session_store = tower_sessions::MemoryStore::default();
+    let session_layer = tower_sessions::SessionManagerLayer::new(session_store)
+        .with_secure(false)
+        .with_same_site(tower_sessions::cookie::SameSite::Lax);
+
+    let router = axum::Router::new()
+        .serve_dioxus_application(dioxus_server::ServeConfig::new(), App)
+        .layer(axum::middleware::from_fn(audit_actor))
+        .layer(session_layer);
+
+    let listener = tokio::net::TcpListener::bind(address).await.unwrap();
+    axum::serve(listener, router.into_make_service())
+        .await
+        .unwrap();
+}
+
+/// Records the session user as the actor of every event emitted while the
+/// request is handled; the actor travels to the server with the events.
+/// Callers of the REST API with a bearer token are recorded by its router.
+///
+/// Must run inside the session layer.
+#[cfg(feature = "server")]
+async fn audit_actor(
+    session: tower_sessions::Session,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let user: Option<UserInfo> = session.get("user").await.ok().flatten();
+    match user {
+        Some(user) => loom::audit::with_actor(user.id, next.run(request)).await,
+        None => next.run(request).await,
+    }
+}
+
 #[component]
 fn App() -> Element {
-    // Build cool things ✌️
+    use_context_provider(|| Signal::new(None::<Option<UserInfo>>));
 
     rsx! {
-        // Global app resources
+        GlobalStyles {}
+        document::Title { "Loom" }
+        document::Link { rel: "icon", href: FAVICON }
         document::Link { rel: "stylesheet", href: MAIN_CSS }
 
         Router::<Route> {}
     }
 }
 
-/// A desktop-specific Router around the shared `Navbar` component
-/// which allows us to use the desktop-specific `Route` enum.
+// ── Top-level layout ──────────────────────────────────────────────────────────
+
+#[component]
+fn Layout() -> Element {
+    let mut auth: AuthState = use_context();
+
+    use_context_provider(|| Signal::new(Vec::<ToastMessage>::new()));
+
+    let mut running: RunningTimer =
+        use_context_provider(|| Signal::new(None::<api::timesheet::TimesheetDto>));
+
+    let mut customers_cache: CustomersCache = use_context_provider(|| Signal::new(Vec::new()));
+    let mut projects_cache: ProjectsCache = use_context_provider(|| Signal::new(Vec::new()));
+    let mut activities_cache: ActivitiesCache = use_context_provider(|| Signal::new(Vec::new()));
+    let mut tags_cache: TagsCache = use_context_provider(|| Signal::new(Vec::new()));
+    let mut timesheets_cache: TimesheetsCache = use_context_provider(|| Signal::new(Vec::new()));
+
+    let mut user_settings: UserSettings = use_context_provider(|| {
+        Signal::new(UserSettingsDto {
+            timezone: "Europe/Berlin".to_string(),
+            date_format: "%Y-%m-%d".to_string(),
+            language: "en".to_string(),
+        })
+    });
+    let mut workspace_settings: WorkspaceSettings = use_context_provider(|| {
+        Signal::new(WorkspaceSettingsDto {
+            name: None,
+            timezone: "Europe/Berlin".to_string(),
+            date_format: "%Y-%m-%d".to_string(),
+            currency: "EUR".to_string(),
+            week_start: "monday".to_string(),
+        })
+    });
+
+    let mut elapsed: RunningElapsed = use_context_provider(|| Signal::new(0u64));
+
+    // Single coroutine that owns the tick; computes immediately then every second.
+    let _timer = use_coroutine(move |_: UnboundedReceiver<()>| async move {
+        loop {
+            let secs = running.read().as_ref().and_then(|ts| {
+                let start = chrono::DateTime::parse_from_rfc3339(&ts.start_time).ok()?;
+                // A paused timer stands still; breaks do not count.
+                let now = match ts.paused_at.as_deref() {
+                    Some(paused_at) => chrono::DateTime::parse_from_rfc3339(paused_at).ok()?,
+                    None => chrono::Utc::now().fixed_offset(),
+                };
+                let worked = (now - start).num_seconds() - i64::from(ts.break_duration);
+                u64::try_from(worked).ok()
+            });
+            elapsed.set(secs.unwrap_or_default());
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+    });
+
+    use_resource(move || async move {
+        let user = api::auth::get_current_user().await.ok().flatten();
+        auth.set(Some(user));
+    });
+
+    // Re-fetch running timer, settings, and entity caches whenever auth/workspace changes.
+    use_resource(move || async move {
+        let _ = auth.read();
+        if let Ok(r) = api::timesheet::running_timesheet().await {
+            running.set(r);
+        }
+        if let Ok(s) = api::settings::get_user_settings().await {
+            user_settings.set(s);
+        }
+        if let Ok(s) = api::settings::get_workspace_settings().await {
+            workspace_settings.set(s);
+        }
+        if let Ok(list) = api::customer::list_customers().await {
+            customers_cache.set(list);
+        }
+        if let Ok(list) = api::project::list_projects().await {
+            projects_cache.set(list);
+        }
+        if let Ok(list) = api::activity::list_activities().await {
+            activities_cache.set(list);
+        }
+        if let Ok(list) = api::tag::list_tags().await {
+            tags_cache.set(list);
+        }
+        if let Ok(list) = api::timesheet::list_timesheets().await {
+            timesheets_cache.set(list);
+        }
+    });
+
+    let route: Route = use_route();
+    let title = match &route {
+        Route::Dashboard {} => "Dashboard",
+        Route::Timesheets {} => "Timesheets",
+        Route::WorkTime {} => "Work Time",
+        Route::SyncStatus {} => "Sync",
+        Route::SelectWorkspace {} => "Workspaces",
+        Route::Connect {} | Route::Login {} => "",
+        Route::NotFound { .. } => "Not Found",
+    };
+    let signed_in = auth
+        .read()
+        .as_ref()
+        .is_some_and(|user| user.as_ref().is_some_and(|u| u.workspace_id.is_some()));
+
+    rsx! {
+        div { class: "app-shell",
+            Sidebar { offline: true }
+            div { class: "app-right",
+                Header { title: title.to_string(), offline: true }
+                if signed_in {
+                    div { class: "flex justify-end px-6 pt-2", SyncBadge {} }
+                }
+                main { class: "app-main",
+                    Outlet::<Route> {}
+                }
+            }
+        }
+        ToastStack {}
+    }
+}
+
+// ── Route guards ──────────────────────────────────────────────────────────────
+
+/// Redirects to /connect until the local copy of a workspace is set up.
+#[component]
+fn RequireReplica() -> Element {
+    let nav = use_navigator();
+    let complete = use_resource(|| async { api::setup::is_setup_complete().await });
+
+    match complete.value().cloned() {
+        None => rsx! {},
+        Some(Ok(true)) => rsx! { Outlet::<Route> {} },
+        Some(Ok(false)) | Some(Err(_)) => {
+            nav.replace(Route::Connect {});
+            rsx! {}
+        }
+    }
+}
+
+/// Redirects to /login when unauthenticated.
+#[component]
+fn RequireAuth() -> Element {
+    rsx! {
+        AuthGuard { Outlet::<Route> {} }
+    }
+}
+
+/// Redirects to /select-workspace until a workspace is chosen.
 #[component]
-fn DesktopNavbar() -> Element {
+fn RequireWorkspace() -> Element {
     rsx! {
-        Outlet::<Route> {}
+        WorkspaceGuard { Outlet::<Route> {} }
     }
 }
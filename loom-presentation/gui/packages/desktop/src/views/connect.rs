@@ -0,0 +1,115 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::{HiLink, HiRefresh};
+use dioxus_free_icons::Icon;
+use ui::components::atoms::{Button, Card, CardContent, CardFooter, Form, FormField, Input, Label};
+use ui::layouts::DefaultLayout;
+use ui::AuthState;
+
+/// Connects the app to a Loom server.  On first use this also sets up the
+/// local copy of the workspace; later it just signs in again, e.g. after the
+/// server session has expired.
+#[component]
+pub fn Connect() -> Element {
+    let mut server = use_signal(String::new);
+    let mut email = use_signal(String::new);
+    let mut password = use_signal(String::new);
+    let mut workspace_id = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut submitting = use_signal(|| false);
+
+    let navigator = use_navigator();
+    let mut auth: AuthState = use_context();
+
+    let on_submit = move |_| {
+        let server = server.read().clone();
+        let email = email.read().clone();
+        let password = password.read().clone();
+        let workspace_id = Some(workspace_id.read().trim().to_string()).filter(|w| !w.is_empty());
+
+        async move {
+            submitting.set(true);
+            error.set(None);
+
+            match api::sync::connect_server(server, email, password, workspace_id).await {
+                Ok(()) => {
+                    if let Ok(user) = api::auth::get_current_user().await {
+                        auth.set(Some(user));
+                    }
+                    navigator.replace("/dashboard");
+                }
+                Err(e) => {
+                    error.set(Some(e.to_string()));
+                    submitting.set(false);
+                }
+            }
+        }
+    };
+
+    rsx! {
+        DefaultLayout {
+            Card {
+                class: "w-full",
+                data_size: "md",
+                CardContent {
+                    Form {
+                        FormField {
+                            Label { html_for: "server", class: "w-full", "Server" }
+                            Input {
+                                id: "server",
+                                r#type: "url",
+                                class: "w-full",
+                                placeholder: "https://loom.example.com",
+                                oninput: move |e: FormEvent| server.set(e.value()),
+                            }
+                        }
+                        FormField {
+                            Label { html_for: "email", class: "w-full", "Email" }
+                            Input {
+                                id: "email",
+                                r#type: "email",
+                                class: "w-full",
+                                oninput: move |e: FormEvent| email.set(e.value()),
+                            }
+                        }
+                        FormField {
+                            Label { html_for: "password", class: "w-full", "Password" }
+                            Input {
+                                id: "password",
+                                r#type: "password",
+                                class: "w-full",
+                                oninput: move |e: FormEvent| password.set(e.value()),
+                            }
+                        }
+                        FormField {
+                            Label { html_for: "workspace", class: "w-full", "Workspace ID (optional)" }
+                            Input {
+                                id: "workspace",
+                                class: "w-full",
+                                placeholder: "Your first workspace if empty",
+                                oninput: move |e: FormEvent| workspace_id.set(e.value()),
+                            }
+                        }
+                        if let Some(msg) = error.read().as_deref() {
+                            p { class: "text-red-500 text-sm mt-2", "{msg}" }
+                        }
+                    }
+                }
+                CardFooter {
+                    Button {
+                        class: "ms-auto",
+                        r#type: "submit",
+                        disabled: *submitting.read(),
+                        onclick: on_submit,
+                        if *submitting.read() {
+                            Icon { icon: HiRefresh, width: 16, height: 16 }
+                            "Connecting…"
+                        } else {
+                            Icon { icon: HiLink, width: 16, height: 16 }
+                            "Connect"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
-mod home;
-pub use home::Home;
+mod connect;
+pub use connect::Connect;
 
-mod blog;
-pub use blog::Blog;
+mod sync_status;
+pub use sync_status::{SyncBadge, SyncStatus};
@@ -0,0 +1,185 @@
+use api::sync::{SyncConflictDto, SyncStatusDto};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::{HiLink, HiRefresh, HiX};
+use dioxus_free_icons::Icon;
+use ui::components::atoms::{
+    Button, ButtonVariant, ColumnDef, DataTable, TableCell, TableRow, ToastExt, Toasts,
+};
+use ui::layouts::DefaultLayout;
+
+/// How often the sync status is refreshed while shown.
+const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// The sync status, refreshed in the background while the component lives.
+fn use_sync_status() -> (Signal<Option<SyncStatusDto>>, Signal<u32>) {
+    let mut status = use_signal(|| None::<SyncStatusDto>);
+    let revision = use_signal(|| 0_u32);
+    use_resource(move || async move {
+        let _ = revision();
+        loop {
+            if let Ok(s) = api::sync::sync_status().await {
+                status.set(Some(s));
+            }
+            tokio::time::sleep(REFRESH_INTERVAL).await;
+        }
+    });
+    (status, revision)
+}
+
+fn summary(status: &SyncStatusDto) -> (&'static str, String) {
+    if status.server.is_none() {
+        ("text-secondary", "Not connected".to_string())
+    } else if !status.signed_in {
+        ("text-warning", "Sign in again to sync".to_string())
+    } else if status.last_error.is_some() {
+        ("text-warning", "Offline".to_string())
+    } else if status.pending > 0 {
+        (
+            "text-secondary",
+            format!("{} changes to sync", status.pending),
+        )
+    } else {
+        ("text-success", "Synced".to_string())
+    }
+}
+
+/// Compact sync status for the header, linking to the sync view.
+#[component]
+pub fn SyncBadge() -> Element {
+    let (status, _) = use_sync_status();
+    let Some(status) = status.read().clone() else {
+        return rsx! {};
+    };
+    let (class, text) = summary(&status);
+
+    rsx! {
+        Link { to: "/sync", class: "flex items-center gap-2 text-sm {class}",
+            Icon { icon: HiRefresh, width: 14, height: 14 }
+            "{text}"
+            if status.conflicts > 0 {
+                span { class: "text-warning", " · {status.conflicts} conflicts" }
+            }
+        }
+    }
+}
+
+/// Sync state of the local copy and the local changes the server refused.
+#[component]
+pub fn SyncStatus() -> Element {
+    let mut toasts: Toasts = use_context();
+    let (status, mut revision) = use_sync_status();
+    let mut conflicts = use_signal(Vec::<SyncConflictDto>::new);
+    let mut syncing = use_signal(|| false);
+
+    use_resource(move || async move {
+        let _ = revision();
+        match api::sync::list_sync_conflicts().await {
+            Ok(list) => conflicts.set(list),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    });
+
+    let on_sync = move |_| async move {
+        syncing.set(true);
+        match api::sync::sync_now().await {
+            Ok(report) => toasts.push_success(format!(
+                "Synced: {} received, {} sent, {} conflicts",
+                report.pulled, report.pushed, report.conflicts
+            )),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        syncing.set(false);
+        revision += 1;
+    };
+
+    let columns = vec![
+        ColumnDef::new("Detected").width("180px"),
+        ColumnDef::new("Time Entry").width("300px"),
+        ColumnDef::new("Dropped Changes"),
+        ColumnDef::new("Reason"),
+        ColumnDef::new("").width("60px"),
+    ];
+
+    let status = status.read().clone().unwrap_or_default();
+    let (class, text) = summary(&status);
+    let list = conflicts.read().clone();
+
+    rsx! {
+        DefaultLayout {
+            div { class: "space-y-6",
+                div { class: "island",
+                    div { class: "island-header",
+                        span { class: "island-title", "Sync" }
+                        span { class: "island-subtitle {class}", "{text}" }
+                    }
+                    div { class: "space-y-2 text-sm",
+                        p { "Server: " {status.server.clone().unwrap_or_else(|| "—".to_string())} }
+                        p { "Last sync: " {status.last_sync.clone().unwrap_or_else(|| "never".to_string())} }
+                        p { "Changes to sync: {status.pending}" }
+                        if let Some(error) = status.last_error.as_deref() {
+                            p { class: "text-warning", "Last error: {error}" }
+                        }
+                    }
+                    div { class: "flex justify-end gap-2 mt-4",
+                        if !status.signed_in {
+                            Link { to: "/connect",
+                                Button { variant: ButtonVariant::Secondary,
+                                    Icon { icon: HiLink, width: 14, height: 14 }
+                                    "Sign in"
+                                }
+                            }
+                        }
+                        Button {
+                            disabled: *syncing.read() || !status.signed_in,
+                            onclick: on_sync,
+                            Icon { icon: HiRefresh, width: 14, height: 14 }
+                            if *syncing.read() { "Syncing…" } else { "Sync now" }
+                        }
+                    }
+                }
+
+                div { class: "island",
+                    div { class: "island-header",
+                        span { class: "island-title", "Conflicts" }
+                        span { class: "island-subtitle",
+                            "changes made here while the entry was changed elsewhere; the server's version was kept"
+                        }
+                    }
+                    DataTable {
+                        columns,
+                        total: list.len(),
+                        page: 0,
+                        page_size: list.len().max(1),
+                        on_page_change: move |_| {},
+
+                        for c in list {
+                            {
+                                let id = c.id;
+                                rsx! {
+                                    TableRow { key: "{c.id}",
+                                        TableCell { "{c.detected_at}" }
+                                        TableCell { mono: true, "{c.timesheet_id}" }
+                                        TableCell { {c.local_changes.join(", ")} }
+                                        TableCell { "{c.reason}" }
+                                        TableCell {
+                                            Button {
+                                                variant: ButtonVariant::Ghost,
+                                                onclick: move |_| async move {
+                                                    match api::sync::dismiss_sync_conflict(id).await {
+                                                        Ok(()) => revision += 1,
+                                                        Err(e) => toasts.push_error(e.to_string()),
+                                                    }
+                                                },
+                                                Icon { icon: HiX, width: 14, height: 14 }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
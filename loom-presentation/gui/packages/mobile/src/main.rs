@@ -1,42 +1,280 @@
-use dioxus::prelude::*;
+//! The mobile app: a timer-first client for tracking time on the go.
+//!
+//! Start the timer with one tap, assign the project, activity and tags when
+//! stopping, check today's and this week's entries, and add entries after
+//! the fact.  Everything else lives in the web app.
 
-use views::{Blog, Home};
+use api::auth::UserInfo;
+use api::settings::{UserSettingsDto, WorkspaceSettingsDto};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::{HiClock, HiPlay, HiPlus};
+use dioxus_free_icons::Icon;
+use ui::{
+    components::{
+        atoms::{ToastMessage, ToastStack},
+        organisms::Header,
+    },
+    guards::{AuthGuard, WorkspaceGuard},
+    views::{Login, SelectWorkspace},
+    ActivitiesCache, AuthState, GlobalStyles, ProjectsCache, RunningElapsed, RunningTimer,
+    TagsCache, UserSettings, WorkspaceSettings, FAVICON,
+};
+use views::{Entries, Entry, NewEntry, Timer};
 
 mod views;
 
+const MAIN_CSS: Asset = asset!("/assets/main.css");
+
 #[derive(Debug, Clone, Routable, PartialEq)]
 #[rustfmt::skip]
 enum Route {
-    #[layout(MobileNavbar)]
-    #[route("/")]
-    Home {},
-    #[route("/blog/:id")]
-    Blog { id: i32 },
+    #[layout(Layout)]
+        #[route("/login")]
+        Login {},
+
+        #[layout(RequireAuth)]
+            #[route("/select-workspace")]
+            SelectWorkspace {},
+
+            #[layout(RequireWorkspace)]
+                #[route("/timer")]
+                Timer {},
+
+                #[route("/entries")]
+                Entries {},
+
+                #[route("/entries/new")]
+                NewEntry {},
+
+                #[route("/entries/:id")]
+                Entry { id: String },
+            #[end_layout]
+        #[end_layout]
+    #[end_layout]
+
+    // Shared views navigate to the web app's start page.
+    #[redirect("/", || Route::Timer {})]
+    #[redirect("/dashboard", || Route::Timer {})]
+    #[redirect("/timesheets", || Route::Entries {})]
+    #[route("/:..route")]
+    NotFound { route: Vec<String> },
 }
 
-const MAIN_CSS: Asset = asset!("/assets/main.css");
+#[component]
+fn NotFound(route: Vec<String>) -> Element {
+    rsx! {
+        h1 { "Only available in the web app" }
+    }
+}
 
+#[cfg(not(feature = "server"))]
 fn main() {
+    dotenvy::from_filename_override(".env.dev").ok();
     dioxus::launch(App);
 }
 
+#[cfg(feature = "server")]
+#[tokio::main]
+async fn main() {
+    dotenvy::from_filename_override(".env.dev").ok();
+
+    loom::setup::init_admin_db()
+        .await
+        .expect("failed to initialise admin database");
+
+    let address = dioxus::cli_config::fullstack_address_or_localhost();
+
+    let session_store = tower_sessions::MemoryStore::default();
+    let session_layer = tower_sessions::SessionManagerLayer::new(session_store)
+        .with_secure(false)
+        .with_same_site(tower_sessions::cookie::SameSite::Lax);
+
+    let router = axum::Router::new()
+        .serve_dioxus_application(dioxus_server::ServeConfig::new(), App)
+        .layer(axum::middleware::from_fn(audit_actor))
+        .layer(session_layer);
+
+    let listener = tokio::net::TcpListener::bind(address).await.unwrap();
+    axum::serve(listener, router.into_make_service())
+        .await
+        .unwrap();
+}
+
+/// Records the session user as the actor of every event emitted while the
+/// request is handled, so the audit log can tell who changed what.  Callers
+/// of the REST API with a bearer token are recorded by its router.
+///
+/// Must run inside the session layer.
+#[cfg(feature = "server")]
+async fn audit_actor(
+    session: tower_sessions::Session,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let user: Option<UserInfo> = session.get("user").await.ok().flatten();
+    match user {
+        Some(user) => loom::audit::with_actor(user.id, next.run(request)).await,
+        None => next.run(request).await,
+    }
+}
+
 #[component]
 fn App() -> Element {
-    // Build cool things ✌️
+    use_context_provider(|| Signal::new(None::<Option<UserInfo>>));
 
     rsx! {
-        // Global app resources
+        GlobalStyles {}
+        document::Title { "Loom" }
+        document::Meta { name: "viewport", content: "width=device-width, initial-scale=1" }
+        document::Link { rel: "icon", href: FAVICON }
         document::Link { rel: "stylesheet", href: MAIN_CSS }
 
         Router::<Route> {}
     }
 }
 
-/// A mobile-specific Router around the shared `Navbar` component
-/// which allows us to use the mobile-specific `Route` enum.
+// ── Top-level layout ──────────────────────────────────────────────────────────
+
+#[component]
+fn Layout() -> Element {
+    let mut auth: AuthState = use_context();
+
+    use_context_provider(|| Signal::new(Vec::<ToastMessage>::new()));
+
+    let mut running: RunningTimer =
+        use_context_provider(|| Signal::new(None::<api::timesheet::TimesheetDto>));
+
+    // The caches the timer views need for names and pickers.
+    let mut projects_cache: ProjectsCache = use_context_provider(|| Signal::new(Vec::new()));
+    let mut activities_cache: ActivitiesCache = use_context_provider(|| Signal::new(Vec::new()));
+    let mut tags_cache: TagsCache = use_context_provider(|| Signal::new(Vec::new()));
+
+    let mut user_settings: UserSettings = use_context_provider(|| {
+        Signal::new(UserSettingsDto {
+            timezone: "Europe/Berlin".to_string(),
+            date_format: "%Y-%m-%d".to_string(),
+            language: "en".to_string(),
+        })
+    });
+    let mut workspace_settings: WorkspaceSettings = use_context_provider(|| {
+        Signal::new(WorkspaceSettingsDto {
+            name: None,
+            timezone: "Europe/Berlin".to_string(),
+            date_format: "%Y-%m-%d".to_string(),
+            currency: "EUR".to_string(),
+            week_start: "monday".to_string(),
+        })
+    });
+
+    let mut elapsed: RunningElapsed = use_context_provider(|| Signal::new(0u64));
+
+    // Single coroutine that owns the tick; computes immediately then every second.
+    let _timer = use_coroutine(move |_: UnboundedReceiver<()>| async move {
+        loop {
+            let secs = running.read().as_ref().and_then(|ts| {
+                let start = chrono::DateTime::parse_from_rfc3339(&ts.start_time).ok()?;
+                // A paused timer stands still; breaks do not count.
+                let now = match ts.paused_at.as_deref() {
+                    Some(paused_at) => chrono::DateTime::parse_from_rfc3339(paused_at).ok()?,
+                    None => chrono::Utc::now().fixed_offset(),
+                };
+                let worked = (now - start).num_seconds() - i64::from(ts.break_duration);
+                u64::try_from(worked).ok()
+            });
+            elapsed.set(secs.unwrap_or_default());
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+    });
+
+    use_resource(move || async move {
+        let user = api::auth::get_current_user().await.ok().flatten();
+        auth.set(Some(user));
+    });
+
+    // Re-fetch running timer, settings, and caches whenever auth/workspace changes.
+    use_resource(move || async move {
+        let _ = auth.read();
+        if let Ok(r) = api::timesheet::running_timesheet().await {
+            running.set(r);
+        }
+        if let Ok(s) = api::settings::get_user_settings().await {
+            user_settings.set(s);
+        }
+        if let Ok(s) = api::settings::get_workspace_settings().await {
+            workspace_settings.set(s);
+        }
+        if let Ok(list) = api::project::list_projects().await {
+            projects_cache.set(list);
+        }
+        if let Ok(list) = api::activity::list_activities().await {
+            activities_cache.set(list);
+        }
+        if let Ok(list) = api::tag::list_tags().await {
+            tags_cache.set(list);
+        }
+    });
+
+    let route: Route = use_route();
+    let title = match &route {
+        Route::Timer {} => "Timer",
+        Route::Entries {} => "Entries",
+        Route::NewEntry {} => "New Entry",
+        Route::Entry { .. } => "Entry",
+        Route::SelectWorkspace {} => "Workspaces",
+        Route::Login {} => "",
+        Route::NotFound { .. } => "Not Found",
+    };
+    let in_workspace = auth
+        .read()
+        .as_ref()
+        .is_some_and(|user| user.as_ref().is_some_and(|u| u.workspace_id.is_some()));
+    let is_running = running.read().is_some();
+
+    rsx! {
+        div { class: "mobile-shell",
+            Header { title: title.to_string() }
+            main { class: "mobile-main",
+                Outlet::<Route> {}
+            }
+            if in_workspace {
+                nav { class: "mobile-tabs",
+                    Link { to: Route::Timer {}, class: "mobile-tab", active_class: "mobile-tab--active",
+                        if is_running {
+                            Icon { icon: HiClock, width: 20, height: 20 }
+                        } else {
+                            Icon { icon: HiPlay, width: 20, height: 20 }
+                        }
+                        "Timer"
+                    }
+                    Link { to: Route::Entries {}, class: "mobile-tab", active_class: "mobile-tab--active",
+                        Icon { icon: HiClock, width: 20, height: 20 }
+                        "Entries"
+                    }
+                    Link { to: Route::NewEntry {}, class: "mobile-tab", active_class: "mobile-tab--active",
+                        Icon { icon: HiPlus, width: 20, height: 20 }
+                        "Add"
+                    }
+                }
+            }
+        }
+        ToastStack {}
+    }
+}
+
+// ── Route guards ──────────────────────────────────────────────────────────────
+
+/// Redirects to /login when unauthenticated.
+#[component]
+fn RequireAuth() -> Element {
+    rsx! {
+        AuthGuard { Outlet::<Route> {} }
+    }
+}
+
+/// Redirects to /select-workspace until a workspace is chosen.
 #[component]
-fn MobileNavbar() -> Element {
+fn RequireWorkspace() -> Element {
     rsx! {
-        Outlet::<Route> {}
+        WorkspaceGuard { Outlet::<Route> {} }
     }
 }
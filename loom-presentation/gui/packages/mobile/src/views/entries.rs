@@ -0,0 +1,132 @@
+use api::timesheet::TimesheetDto;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::HiPlus;
+use dioxus_free_icons::Icon;
+use ui::components::atoms::{ToastExt, Toasts};
+use ui::{
+    formatting, ActivitiesCache, ProjectsCache, RunningTimer, UserSettings, WorkspaceSettings,
+};
+
+use super::{activity_name, hours_minutes, project_name};
+
+/// First day of the week containing `day`.
+fn week_start(day: NaiveDate, starts_on_sunday: bool) -> NaiveDate {
+    let offset = if starts_on_sunday {
+        day.weekday().num_days_from_sunday()
+    } else {
+        day.weekday().num_days_from_monday()
+    };
+    day - Duration::days(offset.into())
+}
+
+fn total(entries: &[TimesheetDto]) -> i64 {
+    entries
+        .iter()
+        .filter_map(|ts| ts.duration)
+        .map(i64::from)
+        .sum()
+}
+
+/// Today's and this week's entries.
+#[component]
+pub fn Entries() -> Element {
+    let running: RunningTimer = use_context();
+    let user_settings: UserSettings = use_context();
+    let workspace_settings: WorkspaceSettings = use_context();
+    let mut toasts: Toasts = use_context();
+    let mut entries = use_signal(Vec::<TimesheetDto>::new);
+
+    let today = Local::now().date_naive();
+
+    use_resource(move || async move {
+        // Refetch when the timer starts or stops.
+        let _ = running.read().is_some();
+        let sunday = workspace_settings.read().week_start == "sunday";
+        let from = week_start(today, sunday).to_string();
+        match api::timesheet::list_timesheets_between(from, today.to_string()).await {
+            Ok(mut list) => {
+                list.reverse();
+                entries.set(list);
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    });
+
+    let today_str = today.to_string();
+    let tz = user_settings.read().timezone.clone();
+    let (today_entries, earlier): (Vec<_>, Vec<_>) = entries
+        .read()
+        .iter()
+        .cloned()
+        .partition(|ts| formatting::format_date(&ts.start_time, &tz, "%Y-%m-%d") == today_str);
+    let week_total = total(&today_entries) + total(&earlier);
+
+    rsx! {
+        div { class: "mobile-entries",
+            div { class: "mobile-summary",
+                div {
+                    span { class: "mobile-summary-label", "Today" }
+                    span { class: "mobile-summary-value", {hours_minutes(total(&today_entries))} }
+                }
+                div {
+                    span { class: "mobile-summary-label", "This week" }
+                    span { class: "mobile-summary-value", {hours_minutes(week_total)} }
+                }
+            }
+            Link { to: "/entries/new", class: "mobile-add",
+                Icon { icon: HiPlus, width: 16, height: 16 }
+                "Add entry"
+            }
+            EntryList { title: "Today", entries: today_entries }
+            EntryList { title: "Earlier this week", entries: earlier }
+        }
+    }
+}
+
+#[component]
+fn EntryList(title: &'static str, entries: Vec<TimesheetDto>) -> Element {
+    let projects: ProjectsCache = use_context();
+    let activities: ActivitiesCache = use_context();
+    let user_settings: UserSettings = use_context();
+
+    if entries.is_empty() {
+        return rsx! {};
+    }
+    let tz = user_settings.read().timezone.clone();
+    let date_format = user_settings.read().date_format.clone();
+
+    rsx! {
+        section { class: "mobile-section",
+            h2 { class: "mobile-section-title", "{title}" }
+            ul { class: "mobile-list",
+                for ts in entries {
+                    {
+                        let project = project_name(&projects.read(), ts.project_id.as_deref());
+                        let activity = activity_name(&activities.read(), ts.activity_id.as_deref())
+                            .unwrap_or_default();
+                        let when = formatting::format_datetime(&ts.start_time, &tz, &date_format);
+                        let duration = ts
+                            .duration
+                            .map_or_else(|| "running".to_string(), |d| hours_minutes(d.into()));
+                        rsx! {
+                            li { key: "{ts.id}",
+                                Link { to: format!("/entries/{}", ts.id), class: "mobile-list-item",
+                                    div {
+                                        if let Some(project) = project {
+                                            p { class: "mobile-list-title", "{project}" }
+                                        } else {
+                                            p { class: "mobile-list-title text-warning", "Unassigned" }
+                                        }
+                                        p { class: "mobile-list-sub", "{activity} {when}" }
+                                    }
+                                    span { class: "mobile-list-duration", "{duration}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
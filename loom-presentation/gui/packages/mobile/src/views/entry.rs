@@ -0,0 +1,175 @@
+use api::tag::TagDto;
+use api::timesheet::TimesheetDto;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::HiSave;
+use dioxus_free_icons::Icon;
+use ui::components::atoms::{Button, Input, Select, SelectOption, ToastExt, Toasts};
+use ui::{formatting, ActivitiesCache, ProjectsCache, TagsCache, UserSettings};
+
+use super::hours_minutes;
+
+/// Assign the project, activity and tags of an entry, e.g. right after
+/// stopping the timer.
+#[component]
+pub fn Entry(id: String) -> Element {
+    let projects: ProjectsCache = use_context();
+    let activities: ActivitiesCache = use_context();
+    let tags: TagsCache = use_context();
+    let user_settings: UserSettings = use_context();
+    let mut toasts: Toasts = use_context();
+    let nav = use_navigator();
+
+    let mut entry = use_signal(|| None::<TimesheetDto>);
+    let mut entry_tags = use_signal(Vec::<TagDto>::new);
+    let mut project_id = use_signal(|| None::<String>);
+    let mut activity_id = use_signal(|| None::<String>);
+    let mut description = use_signal(String::new);
+    let mut billable = use_signal(|| true);
+
+    let load_id = id.clone();
+    use_resource(move || {
+        let id = load_id.clone();
+        async move {
+            match api::timesheet::get_timesheet(id.clone()).await {
+                Ok(Some(ts)) => {
+                    project_id.set(ts.project_id.clone());
+                    activity_id.set(ts.activity_id.clone());
+                    description.set(ts.description.clone().unwrap_or_default());
+                    billable.set(ts.billable);
+                    entry.set(Some(ts));
+                }
+                Ok(None) => toasts.push_error("This entry no longer exists"),
+                Err(e) => toasts.push_error(e.to_string()),
+            }
+            if let Ok(list) = api::tag::list_timesheet_tags(id).await {
+                entry_tags.set(list);
+            }
+        }
+    });
+
+    let save_id = id.clone();
+    let on_save = move |_| {
+        let id = save_id.clone();
+        async move {
+            let Some(ts) = entry.peek().clone() else {
+                return;
+            };
+            let desc = Some(description.peek().clone()).filter(|d| !d.is_empty());
+            if let Err(e) =
+                api::timesheet::update_timesheet(id.clone(), desc, *billable.peek()).await
+            {
+                toasts.push_error(e.to_string());
+                return;
+            }
+            let (pid, aid) = (project_id.peek().clone(), activity_id.peek().clone());
+            if let (Some(pid), Some(aid)) = (pid, aid) {
+                let changed = ts.project_id.as_deref() != Some(pid.as_str())
+                    || ts.activity_id.as_deref() != Some(aid.as_str());
+                if changed {
+                    if let Err(e) = api::timesheet::reassign_timesheet(id, pid, aid).await {
+                        toasts.push_error(e.to_string());
+                        return;
+                    }
+                }
+            }
+            toasts.push_success("Entry saved");
+            nav.push("/entries");
+        }
+    };
+
+    let Some(ts) = entry.read().clone() else {
+        return rsx! {};
+    };
+    let settings = user_settings.read().clone();
+    let started =
+        formatting::format_datetime(&ts.start_time, &settings.timezone, &settings.date_format);
+    let duration = ts
+        .duration
+        .map_or_else(|| "running".to_string(), |d| hours_minutes(d.into()));
+    let assigned: Vec<String> = entry_tags.read().iter().map(|t| t.id.clone()).collect();
+
+    rsx! {
+        div { class: "mobile-form",
+            p { class: "mobile-list-sub", "{started} · {duration}" }
+            div { class: "form-field",
+                label { class: "form-label", "Project" }
+                Select::<String> {
+                    options: projects.read().iter()
+                        .map(|p| SelectOption::new(p.id.clone(), p.name.clone()))
+                        .collect(),
+                    value: project_id.read().clone(),
+                    on_change: move |id: String| project_id.set(Some(id)),
+                    placeholder: "Select project…".to_string(),
+                }
+            }
+            div { class: "form-field",
+                label { class: "form-label", "Activity" }
+                Select::<String> {
+                    options: activities.read().iter()
+                        .map(|a| SelectOption::new(a.id.clone(), a.name.clone()))
+                        .collect(),
+                    value: activity_id.read().clone(),
+                    on_change: move |id: String| activity_id.set(Some(id)),
+                    placeholder: "Select activity…".to_string(),
+                }
+            }
+            div { class: "form-field",
+                label { class: "form-label", r#for: "entry-desc", "Description" }
+                Input {
+                    id: "entry-desc",
+                    placeholder: "What did you work on?",
+                    value: description.read().clone(),
+                    oninput: move |e: FormEvent| description.set(e.value()),
+                }
+            }
+            div { class: "form-field flex items-center gap-3",
+                label { class: "form-label", "Billable" }
+                input {
+                    r#type: "checkbox",
+                    class: "form-checkbox",
+                    checked: *billable.read(),
+                    oninput: move |_| { let v = *billable.peek(); billable.set(!v); },
+                }
+            }
+            div { class: "form-field",
+                label { class: "form-label", "Tags" }
+                div { class: "mobile-tags",
+                    for tag in tags.read().clone() {
+                        {
+                            let on = assigned.contains(&tag.id);
+                            let timesheet_id = id.clone();
+                            let toggled = tag.clone();
+                            rsx! {
+                                button {
+                                    key: "{tag.id}",
+                                    class: if on { "tab-pill tab-pill--active" } else { "tab-pill" },
+                                    onclick: move |_| {
+                                        let timesheet_id = timesheet_id.clone();
+                                        let tag = toggled.clone();
+                                        async move {
+                                            let result = if on {
+                                                api::tag::untag_timesheet(tag.id.clone(), timesheet_id).await
+                                            } else {
+                                                api::tag::tag_timesheet(tag.id.clone(), timesheet_id).await
+                                            };
+                                            match result {
+                                                Ok(()) if on => entry_tags.write().retain(|t| t.id != tag.id),
+                                                Ok(()) => entry_tags.write().push(tag),
+                                                Err(e) => toasts.push_error(e.to_string()),
+                                            }
+                                        }
+                                    },
+                                    "{tag.name}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            Button { class: "w-full", onclick: on_save,
+                Icon { icon: HiSave, width: 16, height: 16 }
+                "Save"
+            }
+        }
+    }
+}
@@ -1,6 +0,0 @@
-use dioxus::prelude::*;
-
-#[component]
-pub fn Home() -> Element {
-    rsx! {}
-}
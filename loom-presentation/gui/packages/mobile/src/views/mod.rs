@@ -1,5 +1,31 @@
-mod home;
-pub use home::Home;
+mod entries;
+pub use entries::Entries;
 
-mod blog;
-pub use blog::Blog;
+mod entry;
+pub use entry::Entry;
+
+mod new_entry;
+pub use new_entry::NewEntry;
+
+mod timer;
+pub use timer::Timer;
+
+/// `1:05` for 3900 seconds.
+fn hours_minutes(seconds: i64) -> String {
+    let minutes = seconds.max(0) / 60;
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// The name of the project, or `None` if unassigned or unknown.
+fn project_name(projects: &[api::project::ProjectDto], id: Option<&str>) -> Option<String> {
+    let id = id?;
+    projects.iter().find(|p| p.id == id).map(|p| p.name.clone())
+}
+
+fn activity_name(activities: &[api::activity::ActivityDto], id: Option<&str>) -> Option<String> {
+    let id = id?;
+    activities
+        .iter()
+        .find(|a| a.id == id)
+        .map(|a| a.name.clone())
+}
@@ -0,0 +1,110 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::HiPlus;
+use dioxus_free_icons::Icon;
+use ui::components::atoms::{Button, Input, Select, SelectOption, ToastExt, Toasts};
+use ui::{formatting, ActivitiesCache, ProjectsCache, UserSettings};
+
+/// Record time after the fact; project and activity can be assigned later.
+#[component]
+pub fn NewEntry() -> Element {
+    let projects: ProjectsCache = use_context();
+    let activities: ActivitiesCache = use_context();
+    let user_settings: UserSettings = use_context();
+    let mut toasts: Toasts = use_context();
+    let nav = use_navigator();
+
+    let mut start = use_signal(String::new);
+    let mut end = use_signal(String::new);
+    let mut project_id = use_signal(|| None::<String>);
+    let mut activity_id = use_signal(|| None::<String>);
+    let mut description = use_signal(String::new);
+    let mut submitting = use_signal(|| false);
+
+    let on_create = move |_| async move {
+        let (start_local, end_local) = (start.peek().clone(), end.peek().clone());
+        if start_local.is_empty() || end_local.is_empty() {
+            toasts.push_error("Start and end time are required");
+            return;
+        }
+        submitting.set(true);
+        let tz = user_settings.peek().timezone.clone();
+        let desc = Some(description.peek().clone()).filter(|d| !d.is_empty());
+        match api::timesheet::create_timesheet_manual(
+            project_id.peek().clone(),
+            activity_id.peek().clone(),
+            formatting::from_input(&start_local, &tz),
+            formatting::from_input(&end_local, &tz),
+            desc,
+            true,
+        )
+        .await
+        {
+            Ok(_) => {
+                toasts.push_success("Entry created");
+                nav.push("/entries");
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        submitting.set(false);
+    };
+
+    rsx! {
+        div { class: "mobile-form",
+            div { class: "form-field",
+                label { class: "form-label", r#for: "new-start", "Start" }
+                input {
+                    id: "new-start",
+                    r#type: "datetime-local",
+                    class: "input",
+                    value: start.read().clone(),
+                    oninput: move |e: FormEvent| start.set(e.value()),
+                }
+            }
+            div { class: "form-field",
+                label { class: "form-label", r#for: "new-end", "End" }
+                input {
+                    id: "new-end",
+                    r#type: "datetime-local",
+                    class: "input",
+                    value: end.read().clone(),
+                    oninput: move |e: FormEvent| end.set(e.value()),
+                }
+            }
+            div { class: "form-field",
+                label { class: "form-label", "Project" }
+                Select::<String> {
+                    options: projects.read().iter()
+                        .map(|p| SelectOption::new(p.id.clone(), p.name.clone()))
+                        .collect(),
+                    value: project_id.read().clone(),
+                    on_change: move |id: String| project_id.set(Some(id)),
+                    placeholder: "Later…".to_string(),
+                }
+            }
+            div { class: "form-field",
+                label { class: "form-label", "Activity" }
+                Select::<String> {
+                    options: activities.read().iter()
+                        .map(|a| SelectOption::new(a.id.clone(), a.name.clone()))
+                        .collect(),
+                    value: activity_id.read().clone(),
+                    on_change: move |id: String| activity_id.set(Some(id)),
+                    placeholder: "Later…".to_string(),
+                }
+            }
+            div { class: "form-field",
+                label { class: "form-label", r#for: "new-desc", "Description" }
+                Input {
+                    id: "new-desc",
+                    placeholder: "Optional notes…",
+                    value: description.read().clone(),
+                    oninput: move |e: FormEvent| description.set(e.value()),
+                }
+            }
+            Button { class: "w-full", disabled: *submitting.read(), onclick: on_create,
+                Icon { icon: HiPlus, width: 16, height: 16 }
+                "Create"
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::{HiPause, HiPlay, HiStop};
+use dioxus_free_icons::Icon;
+use ui::components::atoms::{Button, ButtonVariant, ToastExt, Toasts};
+use ui::views::QuickStart;
+use ui::{ActivitiesCache, ProjectsCache, RunningElapsed, RunningTimer};
+
+use super::{activity_name, project_name};
+
+/// The timer, one tap away: start now and assign the project, activity and
+/// tags when stopping.
+#[component]
+pub fn Timer() -> Element {
+    let mut running: RunningTimer = use_context();
+    let elapsed: RunningElapsed = use_context();
+    let projects: ProjectsCache = use_context();
+    let activities: ActivitiesCache = use_context();
+    let mut toasts: Toasts = use_context();
+    let nav = use_navigator();
+    let billable = use_signal(|| true);
+
+    let on_start = move |_| async move {
+        match api::timesheet::start_timesheet(None, None, None, true).await {
+            Ok(ts) => running.set(Some(ts)),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
+
+    // Stopping leads straight to the entry so it can be assigned.
+    let on_stop = move |_| async move {
+        let Some(ts) = running.peek().clone() else {
+            return;
+        };
+        match api::timesheet::stop_timesheet(ts.id.clone()).await {
+            Ok(()) => {
+                running.set(None);
+                nav.push(format!("/entries/{}", ts.id));
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
+
+    let on_pause_toggle = move |_| async move {
+        let Some(ts) = running.peek().clone() else {
+            return;
+        };
+        let result = if ts.paused_at.is_some() {
+            api::timesheet::resume_timesheet(ts.id).await
+        } else {
+            api::timesheet::pause_timesheet(ts.id).await
+        };
+        if let Err(e) = result {
+            toasts.push_error(e.to_string());
+            return;
+        }
+        if let Ok(r) = api::timesheet::running_timesheet().await {
+            running.set(r);
+        }
+    };
+
+    let current = running.read().clone();
+    let e = *elapsed.read();
+
+    rsx! {
+        div { class: "mobile-timer",
+            match current {
+                Some(ts) => {
+                    let project = project_name(&projects.read(), ts.project_id.as_deref())
+                        .unwrap_or_else(|| "No project yet".to_string());
+                    let activity = activity_name(&activities.read(), ts.activity_id.as_deref())
+                        .unwrap_or_else(|| "No activity yet".to_string());
+                    let paused = ts.paused_at.is_some();
+                    rsx! {
+                        p { class: "mobile-timer-state",
+                            if paused { "Paused" } else { "Running" }
+                        }
+                        p { class: "mobile-timer-elapsed",
+                            {format!("{:02}:{:02}:{:02}", e / 3600, (e % 3600) / 60, e % 60)}
+                        }
+                        p { class: "mobile-timer-label", "{project} · {activity}" }
+                        div { class: "mobile-timer-actions",
+                            Button { variant: ButtonVariant::Secondary, onclick: on_pause_toggle,
+                                if paused {
+                                    Icon { icon: HiPlay, width: 20, height: 20 }
+                                    "Resume"
+                                } else {
+                                    Icon { icon: HiPause, width: 20, height: 20 }
+                                    "Pause"
+                                }
+                            }
+                            Button { variant: ButtonVariant::Destructive, onclick: on_stop,
+                                Icon { icon: HiStop, width: 20, height: 20 }
+                                "Stop"
+                            }
+                        }
+                    }
+                }
+                None => rsx! {
+                    p { class: "mobile-timer-elapsed", "00:00:00" }
+                    div { class: "mobile-timer-actions",
+                        Button { class: "mobile-timer-start", onclick: on_start,
+                            Icon { icon: HiPlay, width: 24, height: 24 }
+                            "Start"
+                        }
+                    }
+                    QuickStart {
+                        projects,
+                        activities,
+                        billable,
+                        on_timer_changed: move |()| {},
+                    }
+                },
+            }
+        }
+    }
+}
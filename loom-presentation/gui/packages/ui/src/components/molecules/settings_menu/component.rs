@@ -7,11 +7,11 @@ use crate::components::molecules::theme_switcher::Theme;
 use dioxus::document::eval;
 use dioxus::prelude::*;
 use dioxus_free_icons::icons::hi_solid_icons::{
-    HiCog, HiDatabase, HiDesktopComputer, HiMoon, HiSun,
+    HiChartBar, HiCog, HiDatabase, HiDesktopComputer, HiMoon, HiSun,
 };
 use dioxus_free_icons::Icon;
 
-type AuthState = Signal<Option<Option<api::auth::UserInfo>>>;
+use crate::AuthState;
 
 fn apply_theme(theme: Theme) {
     match theme {
@@ -95,6 +95,15 @@ pub fn SettingsMenu() -> Element {
                             Icon { icon: HiDatabase, width: 14, height: 14 }
                             span { class: "settings-item-label", "Database" }
                         }
+                        DropdownMenuItem {
+                            value: "projections".to_string(),
+                            index: 4_usize,
+                            on_select: move |_: String| {
+                                navigator().push("/developer/projections");
+                            },
+                            Icon { icon: HiChartBar, width: 14, height: 14 }
+                            span { class: "settings-item-label", "Projections" }
+                        }
                     }
                 }
             }
@@ -1,6 +1,9 @@
 use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::HiSearch;
+use dioxus_free_icons::Icon;
 
 use crate::components::molecules::SettingsMenu;
+use crate::AuthState;
 
 #[component]
 pub fn Header(
@@ -8,7 +11,17 @@ pub fn Header(
     /// Pass an empty string to show only the actions area (e.g. on login/setup).
     #[props(default)]
     title: String,
+    /// Leave out the search box, whose hits link to views the desktop app
+    /// does not have.
+    #[props(default)]
+    offline: bool,
 ) -> Element {
+    let auth: AuthState = use_context();
+    let in_workspace = auth
+        .cloned()
+        .flatten()
+        .is_some_and(|user| user.workspace_id.is_some());
+
     rsx! {
         // Load global stylesheets here (in addition to DefaultLayout) so they
         // are never removed from the document head during route transitions.
@@ -22,9 +35,110 @@ pub fn Header(
                     h1 { class: "header-title", "{title}" }
                 }
                 div { class: "header-actions",
+                    if in_workspace && !offline {
+                        HeaderSearch {}
+                    }
                     SettingsMenu {}
                 }
             }
         }
     }
 }
+
+/// The view listing entities of a search hit's type.
+fn hit_path(entity_type: &str) -> &'static str {
+    match entity_type {
+        "customer" => "/customers",
+        "project" => "/projects",
+        "activity" => "/activities",
+        "tag" => "/tags",
+        _ => "/timesheets",
+    }
+}
+
+fn hit_label(entity_type: &str) -> &'static str {
+    match entity_type {
+        "customer" => "Customer",
+        "project" => "Project",
+        "activity" => "Activity",
+        "tag" => "Tag",
+        _ => "Entry",
+    }
+}
+
+/// Search box with a dropdown of the best matches across the workspace.
+#[component]
+fn HeaderSearch() -> Element {
+    let nav = use_navigator();
+    let mut query = use_signal(String::new);
+    let mut open = use_signal(|| false);
+
+    let hits = use_resource(move || async move {
+        let query = query.read().trim().to_string();
+        if query.chars().count() < 2 {
+            return vec![];
+        }
+        // Wait for a pause in typing; a new keystroke restarts the resource.
+        #[cfg(target_arch = "wasm32")]
+        gloo_timers::future::TimeoutFuture::new(200).await;
+        api::search::search(query).await.unwrap_or_default()
+    });
+
+    let hits = hits.read().clone().unwrap_or_default();
+    let show = *open.read() && !query.read().trim().is_empty();
+
+    rsx! {
+        div { class: "header-search",
+            span { class: "header-search-icon",
+                Icon { icon: HiSearch, width: 14, height: 14 }
+            }
+            input {
+                class: "header-search-input",
+                r#type: "search",
+                placeholder: "Search…",
+                "aria-label": "Search the workspace",
+                value: query.read().clone(),
+                oninput: move |e: FormEvent| {
+                    query.set(e.value());
+                    open.set(true);
+                },
+                onfocus: move |_| open.set(true),
+                onblur: move |_| open.set(false),
+                onkeydown: move |e: KeyboardEvent| {
+                    if e.key() == Key::Escape {
+                        open.set(false);
+                    }
+                },
+            }
+            if show {
+                div { class: "header-search-results", role: "listbox",
+                    if hits.is_empty() {
+                        p { class: "header-search-empty", "No matches" }
+                    }
+                    for (path, hit) in hits.into_iter().map(|h| (hit_path(&h.entity_type), h)) {
+                        button {
+                            key: "{hit.entity_id}",
+                            class: "header-search-hit",
+                            role: "option",
+                            // Mouse down fires before the input loses focus
+                            // and closes the dropdown.
+                            onmousedown: move |e: MouseEvent| {
+                                e.prevent_default();
+                                open.set(false);
+                                query.set(String::new());
+                                nav.push(path);
+                            },
+                            span { class: "header-search-hit-type", "{hit_label(&hit.entity_type)}" }
+                            span { class: "header-search-hit-title", "{hit.title}" }
+                            if let Some(date) = hit.start_time.as_deref().and_then(|t| t.get(..10)) {
+                                span { class: "header-search-hit-meta", "{date}" }
+                            } else if !hit.body.is_empty() {
+                                span { class: "header-search-hit-meta", "{hit.body}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,17 +1,20 @@
 use dioxus::prelude::*;
 use dioxus_free_icons::icons::hi_solid_icons::{
-    HiBriefcase, HiClock, HiCog, HiHashtag, HiHome, HiLogout, HiOfficeBuilding, HiPlay, HiStop,
-    HiTag,
+    HiBadgeCheck, HiBriefcase, HiCalendar, HiClipboardList, HiClock, HiCog, HiHashtag, HiHome,
+    HiLightningBolt, HiLogout, HiOfficeBuilding, HiPlay, HiRefresh, HiRss, HiStop, HiTag,
 };
 use dioxus_free_icons::Icon;
 
 use crate::components::atoms::{Button, ButtonVariant, Navbar, NavbarItem, ToastMessage, Toasts};
-
-/// Mirrors the `AuthState` type alias from the `web` crate.
-type AuthState = Signal<Option<Option<api::auth::UserInfo>>>;
+use crate::AuthState;
 
 #[component]
-pub fn Sidebar() -> Element {
+pub fn Sidebar(
+    /// Only link the views that work offline, plus the sync status, as in
+    /// the desktop app.
+    #[props(default)]
+    offline: bool,
+) -> Element {
     let auth: AuthState = use_context();
     let user = auth.cloned().flatten();
 
@@ -45,6 +48,10 @@ pub fn Sidebar() -> Element {
     }
 
     let is_running = running.read().is_some();
+    let is_paused = running
+        .read()
+        .as_ref()
+        .is_some_and(|ts| ts.paused_at.is_some());
 
     rsx! {
         document::Link { rel: "stylesheet", href: asset!("./style.css") }
@@ -71,40 +78,89 @@ pub fn Sidebar() -> Element {
                         Icon { icon: HiClock, width: 16, height: 16 }
                         "Timesheets"
                     }
-                    NavbarItem {
-                        index: 2usize,
-                        value: "customers".to_string(),
-                        to: "/customers",
-                        Icon { icon: HiOfficeBuilding, width: 16, height: 16 }
-                        "Customers"
+                    if !offline {
+                        NavbarItem {
+                            index: 2usize,
+                            value: "approvals".to_string(),
+                            to: "/approvals",
+                            Icon { icon: HiBadgeCheck, width: 16, height: 16 }
+                            "Approvals"
+                        }
                     }
                     NavbarItem {
                         index: 3usize,
-                        value: "projects".to_string(),
-                        to: "/projects",
-                        Icon { icon: HiBriefcase, width: 16, height: 16 }
-                        "Projects"
-                    }
-                    NavbarItem {
-                        index: 4usize,
-                        value: "activities".to_string(),
-                        to: "/activities",
-                        Icon { icon: HiTag, width: 16, height: 16 }
-                        "Activities"
+                        value: "work-time".to_string(),
+                        to: "/work-time",
+                        Icon { icon: HiCalendar, width: 16, height: 16 }
+                        "Work Time"
                     }
-                    NavbarItem {
-                        index: 5usize,
-                        value: "tags".to_string(),
-                        to: "/tags",
-                        Icon { icon: HiHashtag, width: 16, height: 16 }
-                        "Tags"
-                    }
-                    NavbarItem {
-                        index: 6usize,
-                        value: "settings".to_string(),
-                        to: "/settings",
-                        Icon { icon: HiCog, width: 16, height: 16 }
-                        "Settings"
+                    if offline {
+                        NavbarItem {
+                            index: 4usize,
+                            value: "sync".to_string(),
+                            to: "/sync",
+                            Icon { icon: HiRefresh, width: 16, height: 16 }
+                            "Sync"
+                        }
+                    } else {
+                        NavbarItem {
+                            index: 4usize,
+                            value: "customers".to_string(),
+                            to: "/customers",
+                            Icon { icon: HiOfficeBuilding, width: 16, height: 16 }
+                            "Customers"
+                        }
+                        NavbarItem {
+                            index: 5usize,
+                            value: "projects".to_string(),
+                            to: "/projects",
+                            Icon { icon: HiBriefcase, width: 16, height: 16 }
+                            "Projects"
+                        }
+                        NavbarItem {
+                            index: 6usize,
+                            value: "activities".to_string(),
+                            to: "/activities",
+                            Icon { icon: HiTag, width: 16, height: 16 }
+                            "Activities"
+                        }
+                        NavbarItem {
+                            index: 7usize,
+                            value: "tags".to_string(),
+                            to: "/tags",
+                            Icon { icon: HiHashtag, width: 16, height: 16 }
+                            "Tags"
+                        }
+                        NavbarItem {
+                            index: 8usize,
+                            value: "calendar".to_string(),
+                            to: "/calendar",
+                            Icon { icon: HiRss, width: 16, height: 16 }
+                            "Calendar"
+                        }
+                        NavbarItem {
+                            index: 9usize,
+                            value: "settings".to_string(),
+                            to: "/settings",
+                            Icon { icon: HiCog, width: 16, height: 16 }
+                            "Settings"
+                        }
+                        NavbarItem {
+                            index: 10usize,
+                            value: "webhooks".to_string(),
+                            to: "/webhooks",
+                            Icon { icon: HiLightningBolt, width: 16, height: 16 }
+                            "Webhooks"
+                        }
+                        if user.is_admin {
+                            NavbarItem {
+                                index: 11usize,
+                                value: "audit-log".to_string(),
+                                to: "/audit-log",
+                                Icon { icon: HiClipboardList, width: 16, height: 16 }
+                                "Audit Log"
+                            }
+                        }
                     }
                 }
             }
@@ -114,7 +170,9 @@ pub fn Sidebar() -> Element {
                         div { class: "sidebar-timer-info",
                             div { class: "sidebar-timer-indicator",
                                 span { class: "sidebar-timer-dot" }
-                                span { class: "sidebar-timer-label", "Timer Running" }
+                                span { class: "sidebar-timer-label",
+                                    if is_paused { "Timer Paused" } else { "Timer Running" }
+                                }
                             }
                             span { class: "sidebar-timer-elapsed",
                                 {
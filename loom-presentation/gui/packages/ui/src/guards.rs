@@ -0,0 +1,50 @@
+//! Route guards shared by the app shells.
+//!
+//! Each shell wraps these in its own layout components, as only the shell
+//! knows its `Route` type:
+//!
+//! ```rust,ignore
+//! #[component]
+//! fn RequireAuth() -> Element {
+//!     rsx! { AuthGuard { Outlet::<Route> {} } }
+//! }
+//! ```
+
+use dioxus::prelude::*;
+
+use crate::AuthState;
+
+/// Renders nothing while the session is being checked, redirects to
+/// `/login` when unauthenticated, and renders `children` otherwise.
+#[component]
+pub fn AuthGuard(children: Element) -> Element {
+    let nav = use_navigator();
+    let auth: AuthState = use_context();
+
+    match auth.cloned() {
+        None => rsx! {},
+        Some(None) => {
+            nav.replace("/login");
+            rsx! {}
+        }
+        Some(Some(_)) => children,
+    }
+}
+
+/// Redirects to `/select-workspace` when the authenticated user has not yet
+/// chosen a workspace for this session.  Goes inside an [`AuthGuard`].
+#[component]
+pub fn WorkspaceGuard(children: Element) -> Element {
+    let nav = use_navigator();
+    let auth: AuthState = use_context();
+
+    match auth.cloned() {
+        Some(Some(user)) if user.workspace_id.is_some() => children,
+        Some(Some(_)) => {
+            nav.replace("/select-workspace");
+            rsx! {}
+        }
+        // Loading or unauthenticated — the AuthGuard around handles these.
+        _ => rsx! {},
+    }
+}
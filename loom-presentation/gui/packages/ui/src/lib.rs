@@ -5,8 +5,10 @@ use dioxus::prelude::*;
 pub mod components;
 pub mod form_machine;
 pub mod formatting;
+pub mod guards;
 pub mod hooks;
 pub mod layouts;
+pub mod live;
 pub mod views;
 
 pub const FAVICON: Asset = asset!("/assets/favicon.svg");
@@ -74,6 +76,14 @@ pub fn GlobalStyles() -> Element {
     }
 }
 
+/// Three-state auth signal shared across the whole app, provided by the
+/// shell's `App`.
+///
+/// - `None`           → still checking (initial page load)
+/// - `Some(None)`     → confirmed not authenticated
+/// - `Some(Some(u))`  → confirmed authenticated as `u`
+pub type AuthState = Signal<Option<Option<api::auth::UserInfo>>>;
+
 /// Global shared state for the currently running timesheet.
 /// Provided by the top-level `Layout` and consumed by Sidebar, Dashboard, and Timesheets.
 pub type RunningTimer = Signal<Option<api::timesheet::TimesheetDto>>;
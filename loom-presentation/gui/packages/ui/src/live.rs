@@ -0,0 +1,133 @@
+//! Live updates from the server, see [`api::live`].
+//!
+//! The shell's `Layout` calls [`use_live_updates`] once: it follows the
+//! workspace's event stream, reloads the shared caches a change touches and
+//! counts a revision per [`Topic`].  Views that keep their own copy read
+//! [`use_live`] in their loading resource, so it runs again on a change made
+//! elsewhere, e.g. a timer stopped from the phone.
+
+use std::collections::HashMap;
+
+pub use api::live::Topic;
+use dioxus::prelude::*;
+
+use crate::{
+    ActivitiesCache, CustomersCache, ProjectsCache, RunningTimer, TagsCache, TimesheetsCache,
+};
+
+/// How often each topic changed since the app started.  Provided by
+/// [`use_live_updates`].
+pub type LiveRevisions = Signal<HashMap<Topic, u64>>;
+
+/// Changes whenever one of `topics` changes on the server.  Read it in a
+/// resource to reload then; it stays at 0 in shells without live updates.
+pub fn use_live(topics: &'static [Topic]) -> Memo<u64> {
+    let revisions = try_use_context::<LiveRevisions>();
+    use_memo(move || {
+        revisions.map_or(0, |r| {
+            let r = r.read();
+            topics.iter().filter_map(|t| r.get(t)).sum()
+        })
+    })
+}
+
+/// Follow the current workspace's changes and keep the shared caches
+/// current.  Call once in the shell's `Layout`, after the caches are
+/// provided.
+pub fn use_live_updates() {
+    let mut revisions: LiveRevisions = use_context_provider(|| Signal::new(HashMap::new()));
+    let mut running: RunningTimer = use_context();
+    let mut timesheets_cache: TimesheetsCache = use_context();
+    let mut customers_cache: CustomersCache = use_context();
+    let mut projects_cache: ProjectsCache = use_context();
+    let mut activities_cache: ActivitiesCache = use_context();
+    let mut tags_cache: TagsCache = use_context();
+
+    let changes = use_coroutine(move |mut rx: UnboundedReceiver<Topic>| async move {
+        use futures_util::StreamExt;
+
+        while let Some(topic) = rx.next().await {
+            // The change is projected already, so the reads are current.
+            match topic {
+                Topic::Timesheets => {
+                    if let Ok(r) = api::timesheet::running_timesheet().await {
+                        running.set(r);
+                    }
+                    if let Ok(list) = api::timesheet::list_timesheets().await {
+                        timesheets_cache.set(list);
+                    }
+                }
+                Topic::Customers => {
+                    if let Ok(list) = api::customer::list_customers().await {
+                        customers_cache.set(list);
+                    }
+                }
+                Topic::Projects => {
+                    if let Ok(list) = api::project::list_projects().await {
+                        projects_cache.set(list);
+                    }
+                }
+                Topic::Activities => {
+                    if let Ok(list) = api::activity::list_activities().await {
+                        activities_cache.set(list);
+                    }
+                }
+                Topic::Tags => {
+                    if let Ok(list) = api::tag::list_tags().await {
+                        tags_cache.set(list);
+                    }
+                }
+                _ => {}
+            }
+            *revisions.write().entry(topic).or_default() += 1;
+        }
+    });
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let auth: crate::AuthState = use_context();
+        let mut source = use_signal(|| None::<web_sys::EventSource>);
+
+        // Reconnect whenever the session changes: the stream follows the
+        // workspace selected in it.
+        use_effect(move || {
+            let signed_in = matches!(*auth.read(), Some(Some(_)));
+            if let Some(old) = source.write().take() {
+                old.close();
+            }
+            if signed_in {
+                source.set(connect(changes.tx()));
+            }
+        });
+        use_drop(move || {
+            if let Some(old) = source.write().take() {
+                old.close();
+            }
+        });
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = changes;
+}
+
+/// Open the event stream; the browser reconnects by itself after errors.
+#[cfg(target_arch = "wasm32")]
+fn connect(changes: UnboundedSender<Topic>) -> Option<web_sys::EventSource> {
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let source = web_sys::EventSource::new(api::live::LIVE_PATH).ok()?;
+    let on_change =
+        Closure::<dyn FnMut(web_sys::MessageEvent)>::new(move |event: web_sys::MessageEvent| {
+            if let Some(topic) = event.data().as_string().as_deref().and_then(Topic::parse) {
+                let _ = changes.unbounded_send(topic);
+            }
+        });
+    source
+        .add_event_listener_with_callback(
+            api::live::CHANGE_EVENT,
+            on_change.as_ref().unchecked_ref(),
+        )
+        .ok()?;
+    // The listener lives as long as the page; closing the source stops it.
+    on_change.forget();
+    Some(source)
+}
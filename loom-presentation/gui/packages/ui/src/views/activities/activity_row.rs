@@ -2,11 +2,12 @@ use crate::components::atoms::{
     Button, Input, TableCell, TableExpandRow, TableRow, ToastExt, Toasts,
 };
 use crate::form_machine::{new_form, FormAction, State};
+use crate::views::archive::{archive, ArchiveKind};
 use api::activity::ActivityDto;
 use api::customer::CustomerDto;
 use api::project::ProjectDto;
 use dioxus::prelude::*;
-use dioxus_free_icons::icons::hi_solid_icons::{HiPencil, HiRefresh, HiSave, HiX};
+use dioxus_free_icons::icons::hi_solid_icons::{HiArchive, HiPencil, HiRefresh, HiSave, HiX};
 use dioxus_free_icons::Icon;
 use loom_core::{
     tenant::activity::UpdateActivityInput,
@@ -21,6 +22,8 @@ pub(super) struct ActivityRowProps {
     pub customers: Signal<Vec<CustomerDto>>,
     pub editing_id: Signal<Option<String>>,
     pub col_count: usize,
+    /// Bumped when the row is archived.
+    pub archived_rev: Signal<u32>,
 }
 
 #[component]
@@ -29,6 +32,8 @@ pub(super) fn ActivityRow(props: ActivityRowProps) -> Element {
 
     let a = props.activity.clone();
     let aid = a.id.clone();
+    let archive_id = a.id.clone();
+    let archived_rev = props.archived_rev;
     let mut activities = props.activities;
     let projects = props.projects;
     let mut editing_id = props.editing_id;
@@ -130,6 +135,17 @@ pub(super) fn ActivityRow(props: ActivityRowProps) -> Element {
                         },
                         Icon { icon: HiPencil, width: 14, height: 14 }
                     }
+                    Button {
+                        onclick: move |_| {
+                            let id = archive_id.clone();
+                            async move {
+                                if archive(ArchiveKind::Activity, id.clone(), archived_rev, toasts).await {
+                                    activities.write().retain(|x| x.id != id);
+                                }
+                            }
+                        },
+                        Icon { icon: HiArchive, width: 14, height: 14 }
+                    }
                 }
             }
         }
@@ -1,5 +1,7 @@
 use crate::components::atoms::{ColumnDef, DataTable, ToastExt, Toasts};
 use crate::layouts::DefaultLayout;
+use crate::live::{use_live, Topic};
+use crate::views::archive::{ArchiveKind, ArchivedIsland};
 use crate::views::activities::activity_row::ActivityRow;
 use crate::views::activities::create_form::ActivityCreateForm;
 use crate::{ActivitiesCache, CustomersCache, ProjectsCache};
@@ -20,8 +22,11 @@ pub fn Activities() -> Element {
     let mut toasts: Toasts = use_context();
     let mut page = use_signal(|| 0_usize);
     let editing_id = use_signal(|| Option::<String>::None);
+    let archived_rev = use_signal(|| 0_u32);
+    let live = use_live(&[Topic::Activities, Topic::Projects]);
 
     use_resource(move || async move {
+        let _ = live();
         match api::activity::list_activities().await {
             Ok(list) => activities.set(list),
             Err(e) => toasts.push_error(e.to_string()),
@@ -51,7 +56,7 @@ pub fn Activities() -> Element {
         ColumnDef::new("Name"),
         ColumnDef::new("Project"),
         ColumnDef::new("Flags"),
-        ColumnDef::new("").width("80px"),
+        ColumnDef::new("").width("120px"),
     ];
     let col_count = columns.len();
 
@@ -85,10 +90,23 @@ pub fn Activities() -> Element {
                                 customers,
                                 editing_id,
                                 col_count,
+                                archived_rev,
                             }
                         }
                     }
                 }
+
+                ArchivedIsland {
+                    kind: ArchiveKind::Activity,
+                    revision: archived_rev,
+                    on_restored: move |_| {
+                        spawn(async move {
+                            if let Ok(list) = api::activity::list_activities().await {
+                                activities.set(list);
+                            }
+                        });
+                    },
+                }
             }
         }
     }
@@ -0,0 +1,314 @@
+use crate::components::atoms::{
+    Button, ButtonVariant, ColumnDef, DataTable, Input, TableCell, TableRow, ToastExt, Toasts,
+};
+use crate::formatting;
+use crate::layouts::DefaultLayout;
+use crate::live::{use_live, Topic};
+use api::approval::ApprovalDto;
+use api::timesheet::TimesheetDto;
+use chrono::{Datelike, Days, Utc};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::{HiCheck, HiEye, HiPaperAirplane, HiX};
+use dioxus_free_icons::Icon;
+
+/// Monday and Sunday of last week, `YYYY-MM-DD`.
+fn last_week() -> (String, String) {
+    let today = Utc::now().date_naive();
+    let monday = today - Days::new(u64::from(today.weekday().num_days_from_monday()) + 7);
+    let sunday = monday + Days::new(6);
+    (
+        monday.format("%Y-%m-%d").to_string(),
+        sunday.format("%Y-%m-%d").to_string(),
+    )
+}
+
+fn status_label(status: &str) -> &'static str {
+    match status {
+        "submitted" => "Awaiting approval",
+        "approved" => "Approved",
+        "rejected" => "Rejected",
+        _ => "Draft",
+    }
+}
+
+/// Submitting one's own periods for approval and reviewing those of others.
+#[component]
+pub fn Approvals() -> Element {
+    let mut toasts: Toasts = use_context();
+    let user_settings: crate::UserSettings = use_context();
+
+    let (default_from, default_to) = last_week();
+    let mut from = use_signal(move || default_from);
+    let mut to = use_signal(move || default_to);
+    let mut comment = use_signal(String::new);
+    let mut submitting = use_signal(|| false);
+
+    let mut mine = use_signal(Vec::<ApprovalDto>::new);
+    // `None` while the user may not review.
+    let mut pending = use_signal(|| None::<Vec<ApprovalDto>>);
+    let mut review_comments = use_signal(std::collections::HashMap::<String, String>::new);
+    let mut viewing = use_signal(|| None::<(String, Vec<TimesheetDto>)>);
+    let mut revision = use_signal(|| 0_u32);
+    let live = use_live(&[Topic::Approvals, Topic::Timesheets]);
+
+    use_resource(move || async move {
+        let _ = revision();
+        let _ = live();
+        match api::approval::my_approvals().await {
+            Ok(list) => mine.set(list),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        pending.set(api::approval::pending_approvals().await.ok());
+    });
+
+    let on_submit = move |_| async move {
+        submitting.set(true);
+        let note = comment.peek().trim().to_string();
+        let note = (!note.is_empty()).then_some(note);
+        match api::approval::submit_period(from.peek().clone(), to.peek().clone(), note).await {
+            Ok(a) => {
+                comment.set(String::new());
+                revision += 1;
+                toasts.push_success(format!("Submitted {} entries for approval", a.entry_count));
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        submitting.set(false);
+    };
+
+    let mut toggle_entries = move |approval_id: String| async move {
+        if viewing.peek().as_ref().is_some_and(|(id, _)| *id == approval_id) {
+            viewing.set(None);
+            return;
+        }
+        match api::approval::approval_entries(approval_id.clone()).await {
+            Ok(entries) => viewing.set(Some((approval_id, entries))),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
+
+    let mine_columns = vec![
+        ColumnDef::new("Period").width("220px"),
+        ColumnDef::new("Status").width("160px"),
+        ColumnDef::new("Entries").width("80px"),
+        ColumnDef::new("Comment"),
+        ColumnDef::new("").width("60px"),
+    ];
+    let pending_columns = vec![
+        ColumnDef::new("User").width("160px"),
+        ColumnDef::new("Period").width("220px"),
+        ColumnDef::new("Entries").width("80px"),
+        ColumnDef::new("Comment"),
+        ColumnDef::new("").width("140px"),
+    ];
+    let mine_list = mine.read().clone();
+    let pending_list = pending.read().clone();
+
+    let entries_view = move |approval_id: &str| -> Element {
+        let viewing = viewing.read();
+        let Some((_, entries)) = viewing.as_ref().filter(|(id, _)| id == approval_id) else {
+            return rsx! {};
+        };
+        let s = user_settings.read();
+        rsx! {
+            div { class: "flex flex-col gap-1 text-xs py-2",
+                for entry in entries.iter() {
+                    {
+                        let when = formatting::format_datetime(&entry.start_time, &s.timezone, &s.date_format);
+                        let minutes = entry.duration.unwrap_or(0) / 60;
+                        let breaks = (entry.break_duration > 0)
+                            .then(|| format!(" (break {}m)", entry.break_duration / 60))
+                            .unwrap_or_default();
+                        let what = entry.description.clone().unwrap_or_default();
+                        rsx! {
+                            span { key: "{entry.id}", class: "font-mono",
+                                "{when} · {minutes / 60}h {minutes % 60}m{breaks} {what}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    rsx! {
+        DefaultLayout {
+            div { class: "space-y-6",
+                div { class: "island",
+                    div { class: "island-header",
+                        span { class: "island-title", "Submit Period" }
+                    }
+                    div { class: "grid grid-cols-1 gap-4 md:grid-cols-3",
+                        div { class: "form-field",
+                            label { class: "form-label", r#for: "approval-from", "From" }
+                            input {
+                                id: "approval-from",
+                                r#type: "date",
+                                class: "input",
+                                value: from.read().clone(),
+                                oninput: move |e: FormEvent| from.set(e.value()),
+                            }
+                        }
+                        div { class: "form-field",
+                            label { class: "form-label", r#for: "approval-to", "To" }
+                            input {
+                                id: "approval-to",
+                                r#type: "date",
+                                class: "input",
+                                value: to.read().clone(),
+                                oninput: move |e: FormEvent| to.set(e.value()),
+                            }
+                        }
+                        div { class: "form-field",
+                            label { class: "form-label", r#for: "approval-comment", "Comment" }
+                            Input {
+                                id: "approval-comment",
+                                placeholder: "Optional note for the reviewer",
+                                value: comment.read().clone(),
+                                oninput: move |e: FormEvent| comment.set(e.value()),
+                            }
+                        }
+                    }
+                    div { class: "flex justify-end mt-4",
+                        Button {
+                            onclick: on_submit,
+                            disabled: *submitting.read(),
+                            Icon { icon: HiPaperAirplane, width: 14, height: 14 }
+                            "Submit for Approval"
+                        }
+                    }
+                }
+
+                if let Some(list) = pending_list {
+                    div { class: "island",
+                        div { class: "island-header",
+                            span { class: "island-title", "Awaiting Review" }
+                        }
+                        DataTable {
+                            columns: pending_columns,
+                            total: list.len(),
+                            page: 0,
+                            page_size: list.len().max(1),
+                            on_page_change: move |_| {},
+
+                            for a in list {
+                                {
+                                    let id_view = a.id.clone();
+                                    let id_approve = a.id.clone();
+                                    let id_reject = a.id.clone();
+                                    let id_comment = a.id.clone();
+                                    let who = a.user_name.clone().unwrap_or_else(|| a.user_id.clone());
+                                    let note = review_comments.read().get(&a.id).cloned().unwrap_or_default();
+                                    rsx! {
+                                        TableRow { key: "{a.id}",
+                                            TableCell { "{who}" }
+                                            TableCell { mono: true, "{a.period_start} – {a.period_end}" }
+                                            TableCell { "{a.entry_count}" }
+                                            TableCell {
+                                                div { class: "flex flex-col gap-1",
+                                                    if let Some(ref c) = a.comment {
+                                                        span { class: "text-xs text-secondary italic", "{c}" }
+                                                    }
+                                                    Input {
+                                                        placeholder: "Review comment",
+                                                        value: note,
+                                                        oninput: move |e: FormEvent| {
+                                                            review_comments.write().insert(id_comment.clone(), e.value());
+                                                        },
+                                                    }
+                                                    {entries_view(&a.id)}
+                                                }
+                                            }
+                                            TableCell {
+                                                div { class: "flex gap-1",
+                                                    Button {
+                                                        onclick: move |_| toggle_entries(id_view.clone()),
+                                                        Icon { icon: HiEye, width: 14, height: 14 }
+                                                    }
+                                                    Button {
+                                                        onclick: move |_| {
+                                                            let id = id_approve.clone();
+                                                            async move {
+                                                                let note = review_comments.peek().get(&id).cloned()
+                                                                    .filter(|c| !c.trim().is_empty());
+                                                                match api::approval::approve(id, note).await {
+                                                                    Ok(()) => {
+                                                                        revision += 1;
+                                                                        toasts.push_success("Period approved");
+                                                                    }
+                                                                    Err(e) => toasts.push_error(e.to_string()),
+                                                                }
+                                                            }
+                                                        },
+                                                        Icon { icon: HiCheck, width: 14, height: 14 }
+                                                    }
+                                                    Button {
+                                                        variant: ButtonVariant::Destructive,
+                                                        onclick: move |_| {
+                                                            let id = id_reject.clone();
+                                                            async move {
+                                                                let note = review_comments.peek().get(&id).cloned().unwrap_or_default();
+                                                                match api::approval::reject(id, note).await {
+                                                                    Ok(()) => {
+                                                                        revision += 1;
+                                                                        toasts.push_success("Period sent back for corrections");
+                                                                    }
+                                                                    Err(e) => toasts.push_error(e.to_string()),
+                                                                }
+                                                            }
+                                                        },
+                                                        Icon { icon: HiX, width: 14, height: 14 }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "island",
+                    div { class: "island-header",
+                        span { class: "island-title", "My Submissions" }
+                    }
+                    DataTable {
+                        columns: mine_columns,
+                        total: mine_list.len(),
+                        page: 0,
+                        page_size: mine_list.len().max(1),
+                        on_page_change: move |_| {},
+
+                        for a in mine_list {
+                            {
+                                let id_view = a.id.clone();
+                                rsx! {
+                                    TableRow { key: "{a.id}",
+                                        TableCell { mono: true, "{a.period_start} – {a.period_end}" }
+                                        TableCell { {status_label(&a.status)} }
+                                        TableCell { "{a.entry_count}" }
+                                        TableCell {
+                                            div { class: "flex flex-col gap-1",
+                                                if let Some(ref c) = a.comment {
+                                                    span { class: "text-xs text-secondary italic", "{c}" }
+                                                }
+                                                {entries_view(&a.id)}
+                                            }
+                                        }
+                                        TableCell {
+                                            Button {
+                                                onclick: move |_| toggle_entries(id_view.clone()),
+                                                Icon { icon: HiEye, width: 14, height: 14 }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
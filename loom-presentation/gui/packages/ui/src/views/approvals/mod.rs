@@ -0,0 +1,2 @@
+mod component;
+pub use component::Approvals;
@@ -0,0 +1,207 @@
+use crate::components::atoms::{Button, ButtonVariant, ToastExt, Toasts};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::{HiRefresh, HiTrash};
+use dioxus_free_icons::Icon;
+
+/// The kind of entity an [`ArchivedIsland`] lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ArchiveKind {
+    Customer,
+    Project,
+    Activity,
+    Tag,
+}
+
+impl ArchiveKind {
+    const fn plural(self) -> &'static str {
+        match self {
+            Self::Customer => "customers",
+            Self::Project => "projects",
+            Self::Activity => "activities",
+            Self::Tag => "tags",
+        }
+    }
+
+    /// `(id, name)` of every archived entity of this kind.
+    async fn list(self) -> Result<Vec<(String, String)>, ServerFnError> {
+        Ok(match self {
+            Self::Customer => api::customer::list_archived_customers()
+                .await?
+                .into_iter()
+                .map(|c| (c.id, c.name))
+                .collect(),
+            Self::Project => api::project::list_archived_projects()
+                .await?
+                .into_iter()
+                .map(|p| (p.id, p.name))
+                .collect(),
+            Self::Activity => api::activity::list_archived_activities()
+                .await?
+                .into_iter()
+                .map(|a| (a.id, a.name))
+                .collect(),
+            Self::Tag => api::tag::list_archived_tags()
+                .await?
+                .into_iter()
+                .map(|t| (t.id, t.name))
+                .collect(),
+        })
+    }
+
+    async fn restore(self, id: String) -> Result<(), ServerFnError> {
+        match self {
+            Self::Customer => api::customer::restore_customer(id).await,
+            Self::Project => api::project::restore_project(id).await,
+            Self::Activity => api::activity::restore_activity(id).await,
+            Self::Tag => api::tag::restore_tag(id).await,
+        }
+    }
+
+    async fn delete(self, id: String) -> Result<(), ServerFnError> {
+        match self {
+            Self::Customer => api::customer::delete_customer(id).await,
+            Self::Project => api::project::delete_project(id).await,
+            Self::Activity => api::activity::delete_activity(id).await,
+            Self::Tag => api::tag::delete_tag(id).await,
+        }
+    }
+}
+
+/// Archive one entity and bump `revision` so an open [`ArchivedIsland`]
+/// reloads.  Returns whether archiving succeeded.
+pub(crate) async fn archive(
+    kind: ArchiveKind,
+    id: String,
+    mut revision: Signal<u32>,
+    mut toasts: Toasts,
+) -> bool {
+    let result = match kind {
+        ArchiveKind::Customer => api::customer::archive_customer(id).await,
+        ArchiveKind::Project => api::project::archive_project(id).await,
+        ArchiveKind::Activity => api::activity::archive_activity(id).await,
+        ArchiveKind::Tag => api::tag::archive_tag(id).await,
+    };
+    match result {
+        Ok(()) => {
+            *revision.write() += 1;
+            toasts.push_success("Archived");
+            true
+        }
+        Err(e) => {
+            toasts.push_error(e.to_string());
+            false
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Props)]
+pub(crate) struct ArchivedIslandProps {
+    pub kind: ArchiveKind,
+    /// Bumped whenever an entity is archived or restored.
+    pub revision: Signal<u32>,
+    /// Called after an entity was restored, so the active list can reload.
+    pub on_restored: EventHandler<()>,
+}
+
+/// Collapsible list of archived entities with restore and delete actions.
+#[component]
+pub(crate) fn ArchivedIsland(props: ArchivedIslandProps) -> Element {
+    let mut toasts: Toasts = use_context();
+    let kind = props.kind;
+    let mut revision = props.revision;
+    let on_restored = props.on_restored;
+
+    let mut open = use_signal(|| false);
+    let mut confirm_delete = use_signal(|| Option::<String>::None);
+
+    let archived = use_resource(move || async move {
+        let _ = revision();
+        if open() {
+            kind.list().await
+        } else {
+            Ok(vec![])
+        }
+    });
+
+    let (items, error): (Vec<(String, String)>, Option<String>) = match &*archived.read() {
+        Some(Ok(list)) => (list.clone(), None),
+        Some(Err(e)) => (vec![], Some(e.to_string())),
+        None => (vec![], None),
+    };
+
+    rsx! {
+        div { class: "island",
+            div { class: "island-header",
+                span { class: "island-title", "Archived" }
+                Button {
+                    variant: ButtonVariant::Ghost,
+                    onclick: move |_| { let v = *open.peek(); open.set(!v); },
+                    if open() { "Hide" } else { "Show archived {kind.plural()}" }
+                }
+            }
+            if open() {
+                if let Some(e) = error {
+                    p { class: "text-sm text-red-500", "{e}" }
+                } else if items.is_empty() {
+                    p { class: "text-sm text-secondary", "Nothing archived." }
+                }
+                ul { class: "flex flex-col gap-2",
+                    for (id, name) in items {
+                        {
+                            let restore_id = id.clone();
+                            let delete_id = id.clone();
+                            let confirming = confirm_delete.read().as_deref() == Some(id.as_str());
+                            rsx! {
+                                li { key: "{id}", class: "flex items-center justify-between text-sm",
+                                    span { class: "text-secondary", "{name}" }
+                                    div { class: "flex gap-2",
+                                        Button {
+                                            variant: ButtonVariant::Ghost,
+                                            onclick: move |_| {
+                                                let id = restore_id.clone();
+                                                async move {
+                                                    match kind.restore(id).await {
+                                                        Ok(()) => {
+                                                            *revision.write() += 1;
+                                                            on_restored.call(());
+                                                            toasts.push_success("Restored");
+                                                        }
+                                                        Err(e) => toasts.push_error(e.to_string()),
+                                                    }
+                                                }
+                                            },
+                                            Icon { icon: HiRefresh, width: 14, height: 14 }
+                                            "Restore"
+                                        }
+                                        Button {
+                                            variant: if confirming { ButtonVariant::Destructive } else { ButtonVariant::Ghost },
+                                            onclick: move |_| {
+                                                let id = delete_id.clone();
+                                                async move {
+                                                    if confirm_delete.peek().as_deref() != Some(id.as_str()) {
+                                                        confirm_delete.set(Some(id));
+                                                        return;
+                                                    }
+                                                    confirm_delete.set(None);
+                                                    match kind.delete(id).await {
+                                                        Ok(()) => {
+                                                            *revision.write() += 1;
+                                                            toasts.push_success("Deleted permanently");
+                                                        }
+                                                        Err(e) => toasts.push_error(e.to_string()),
+                                                    }
+                                                }
+                                            },
+                                            Icon { icon: HiTrash, width: 14, height: 14 }
+                                            if confirming { "Confirm delete" } else { "Delete" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
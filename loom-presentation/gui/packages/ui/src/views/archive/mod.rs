@@ -0,0 +1,2 @@
+mod archived_island;
+pub(crate) use archived_island::{archive, ArchiveKind, ArchivedIsland};
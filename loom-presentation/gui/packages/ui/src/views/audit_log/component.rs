@@ -0,0 +1,242 @@
+use crate::components::atoms::{
+    ColumnDef, DataTable, Input, Select, SelectOption, TableCell, TableRow, ToastExt, Toasts,
+};
+use crate::formatting;
+use crate::layouts::DefaultLayout;
+use api::audit::{AuditEntryDto, AuditFilterDto, AuditScope};
+use dioxus::prelude::*;
+
+const PAGE_SIZE: usize = 50;
+
+fn aggregate_type_options(scope: AuditScope) -> Vec<SelectOption<String>> {
+    let types: &[(&str, &str)] = match scope {
+        AuditScope::Workspace => &[
+            ("", "All entities"),
+            ("timesheet", "Timesheets"),
+            ("customer", "Customers"),
+            ("project", "Projects"),
+            ("activity", "Activities"),
+            ("tag", "Tags"),
+            ("approval", "Approvals"),
+            ("work_contract", "Work contracts"),
+            ("holiday", "Holidays"),
+            ("calendar_rule", "Calendar rules"),
+            ("webhook", "Webhooks"),
+            ("report_schedule", "Report schedules"),
+            ("favorite", "Favorites"),
+            ("project_rate", "Project rates"),
+            ("activity_rate", "Activity rates"),
+        ],
+        AuditScope::Admin => &[
+            ("", "All entities"),
+            ("user", "Users"),
+            ("workspace", "Workspaces"),
+            ("workspace_role", "Roles"),
+            ("permission", "Permissions"),
+        ],
+    };
+    types
+        .iter()
+        .map(|(value, label)| SelectOption::new((*value).to_string(), *label))
+        .collect()
+}
+
+fn non_empty(s: String) -> Option<String> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+/// Admin page listing every recorded change in the workspace or admin database.
+#[component]
+pub fn AuditLog() -> Element {
+    let mut toasts: Toasts = use_context();
+    let user_settings: crate::UserSettings = use_context();
+
+    let mut scope = use_signal(AuditScope::default);
+    let mut aggregate_type = use_signal(String::new);
+    let mut entity_id = use_signal(String::new);
+    let mut actor_id = use_signal(String::new);
+    let mut from = use_signal(String::new);
+    let mut to = use_signal(String::new);
+    let mut page = use_signal(|| 0_usize);
+
+    let mut entries = use_signal(Vec::<AuditEntryDto>::new);
+    let mut loading = use_signal(|| true);
+
+    use_resource(move || async move {
+        let tz = user_settings.peek().timezone.clone();
+        let filter = AuditFilterDto {
+            scope: *scope.read(),
+            aggregate_type: non_empty(aggregate_type.read().clone()),
+            entity_id: non_empty(entity_id.read().clone()),
+            actor_id: non_empty(actor_id.read().clone()),
+            from: non_empty(from.read().clone()).map(|s| formatting::from_input(&s, &tz)),
+            to: non_empty(to.read().clone()).map(|s| formatting::from_input(&s, &tz)),
+            page: *page.read() as u64,
+            // One extra row tells us whether a next page exists.
+            page_size: (PAGE_SIZE + 1) as u64,
+        };
+        loading.set(true);
+        match api::audit::list_audit_log(filter).await {
+            Ok(list) => entries.set(list),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        loading.set(false);
+    });
+
+    let current_page = *page.read();
+    let total = current_page * PAGE_SIZE + entries.read().len();
+    let page_items: Vec<AuditEntryDto> = entries.read().iter().take(PAGE_SIZE).cloned().collect();
+
+    let columns = vec![
+        ColumnDef::new("When").width("160px"),
+        ColumnDef::new("Who").width("140px"),
+        ColumnDef::new("What").width("200px"),
+        ColumnDef::new("Changes"),
+    ];
+
+    rsx! {
+        DefaultLayout {
+            div { class: "space-y-6",
+                div { class: "island",
+                    div { class: "island-header",
+                        span { class: "island-title", "Filters" }
+                    }
+                    div { class: "grid grid-cols-1 gap-4 md:grid-cols-3",
+                        div { class: "form-field",
+                            label { class: "form-label", "Database" }
+                            Select::<AuditScope> {
+                                options: vec![
+                                    SelectOption::new(AuditScope::Workspace, "Current workspace"),
+                                    SelectOption::new(AuditScope::Admin, "Administration"),
+                                ],
+                                value: Some(*scope.read()),
+                                on_change: move |v| {
+                                    scope.set(v);
+                                    aggregate_type.set(String::new());
+                                    page.set(0);
+                                },
+                            }
+                        }
+                        div { class: "form-field",
+                            label { class: "form-label", "Entity type" }
+                            Select::<String> {
+                                options: aggregate_type_options(*scope.read()),
+                                value: Some(aggregate_type.read().clone()),
+                                on_change: move |v| {
+                                    aggregate_type.set(v);
+                                    page.set(0);
+                                },
+                            }
+                        }
+                        div { class: "form-field",
+                            label { class: "form-label", r#for: "audit-entity", "Entity ID" }
+                            Input {
+                                id: "audit-entity",
+                                placeholder: "Any",
+                                value: entity_id.read().clone(),
+                                oninput: move |e: FormEvent| {
+                                    entity_id.set(e.value());
+                                    page.set(0);
+                                },
+                            }
+                        }
+                        div { class: "form-field",
+                            label { class: "form-label", r#for: "audit-actor", "User ID" }
+                            Input {
+                                id: "audit-actor",
+                                placeholder: "Any",
+                                value: actor_id.read().clone(),
+                                oninput: move |e: FormEvent| {
+                                    actor_id.set(e.value());
+                                    page.set(0);
+                                },
+                            }
+                        }
+                        div { class: "form-field",
+                            label { class: "form-label", r#for: "audit-from", "From" }
+                            input {
+                                id: "audit-from",
+                                r#type: "datetime-local",
+                                class: "input",
+                                value: from.read().clone(),
+                                oninput: move |e: FormEvent| {
+                                    from.set(e.value());
+                                    page.set(0);
+                                },
+                            }
+                        }
+                        div { class: "form-field",
+                            label { class: "form-label", r#for: "audit-to", "To" }
+                            input {
+                                id: "audit-to",
+                                r#type: "datetime-local",
+                                class: "input",
+                                value: to.read().clone(),
+                                oninput: move |e: FormEvent| {
+                                    to.set(e.value());
+                                    page.set(0);
+                                },
+                            }
+                        }
+                    }
+                }
+
+                div { class: "island",
+                    div { class: "island-header",
+                        span { class: "island-title", "Audit Log" }
+                    }
+                    DataTable {
+                        columns,
+                        total,
+                        page: current_page,
+                        page_size: PAGE_SIZE,
+                        loading: *loading.read(),
+                        on_page_change: move |p| page.set(p),
+
+                        for entry in page_items {
+                            {
+                                let when = entry.recorded_at.as_deref().map_or_else(
+                                    || "—".to_string(),
+                                    |t| {
+                                        let s = user_settings.read();
+                                        formatting::format_datetime(t, &s.timezone, &s.date_format)
+                                    },
+                                );
+                                let who = entry.actor_name.clone()
+                                    .or_else(|| entry.actor_id.clone())
+                                    .unwrap_or_else(|| "System".to_string());
+                                rsx! {
+                                    TableRow { key: "{entry.global_position}",
+                                        TableCell { mono: true, "{when}" }
+                                        TableCell { "{who}" }
+                                        TableCell {
+                                            div { class: "flex flex-col gap-0.5",
+                                                span { class: "font-medium text-sm",
+                                                    "{entry.aggregate_type} {entry.action}"
+                                                }
+                                                span { class: "text-xs text-secondary font-mono",
+                                                    "{entry.aggregate_id}"
+                                                }
+                                            }
+                                        }
+                                        TableCell {
+                                            div { class: "flex flex-col gap-0.5 text-xs",
+                                                for change in entry.changes.iter() {
+                                                    span { "{change}" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::formatting;
+use api::audit::AuditEntryDto;
+use dioxus::prelude::*;
+
+#[derive(Clone, PartialEq, Props)]
+pub(crate) struct HistoryPanelProps {
+    /// ID of the timesheet, customer, project, … whose history is shown.
+    pub entity_id: String,
+}
+
+/// Chronological list of changes to a single entity, newest first.
+#[component]
+pub(crate) fn HistoryPanel(props: HistoryPanelProps) -> Element {
+    let user_settings: crate::UserSettings = use_context();
+    let entity_id = props.entity_id.clone();
+
+    let history = use_resource(move || {
+        let entity_id = entity_id.clone();
+        async move { api::audit::entity_history(entity_id).await }
+    });
+
+    let entries: Vec<AuditEntryDto> = match &*history.read() {
+        Some(Ok(list)) => list.clone(),
+        Some(Err(e)) => {
+            return rsx! {
+                p { class: "text-sm text-red-500", "{e}" }
+            }
+        }
+        None => {
+            return rsx! {
+                p { class: "text-sm text-secondary", "Loading history…" }
+            }
+        }
+    };
+
+    rsx! {
+        p { class: "text-xs font-medium text-secondary mb-2", "History" }
+        if entries.is_empty() {
+            p { class: "text-sm text-secondary", "No changes recorded yet." }
+        }
+        ul { class: "flex flex-col gap-2",
+            for entry in entries {
+                {
+                    let when = entry.recorded_at.as_deref().map_or_else(
+                        || "—".to_string(),
+                        |t| {
+                            let s = user_settings.read();
+                            formatting::format_datetime(t, &s.timezone, &s.date_format)
+                        },
+                    );
+                    let who = entry.actor_name.clone().unwrap_or_else(|| "System".to_string());
+                    rsx! {
+                        li { key: "{entry.global_position}", class: "flex flex-col gap-0.5 text-sm",
+                            div { class: "flex gap-2 text-xs text-secondary",
+                                span { class: "font-mono", "{when}" }
+                                span { "{who}" }
+                                span { class: "font-medium", "{entry.aggregate_type} {entry.action}" }
+                            }
+                            for change in entry.changes.iter() {
+                                span { class: "text-xs", "{change}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
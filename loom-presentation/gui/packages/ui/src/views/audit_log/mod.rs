@@ -0,0 +1,4 @@
+mod component;
+mod history_panel;
+pub use component::AuditLog;
+pub(crate) use history_panel::HistoryPanel;
@@ -0,0 +1,476 @@
+use crate::components::atoms::{
+    Button, ButtonVariant, ColumnDef, DataTable, Input, Select, SelectOption, TableCell, TableRow,
+    ToastExt, Toasts,
+};
+use crate::formatting;
+use crate::layouts::DefaultLayout;
+use crate::live::{use_live, Topic};
+use api::activity::ActivityDto;
+use api::calendar::{CalendarFeedDto, CalendarRuleDto, ImportCandidateDto, ImportSelectionDto};
+use api::project::ProjectDto;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::{HiDownload, HiPlus, HiRefresh, HiTrash, HiX};
+use dioxus_free_icons::Icon;
+
+/// An event of the uploaded calendar and whether to import it.
+#[derive(Debug, Clone, PartialEq)]
+struct Pick {
+    event: ImportCandidateDto,
+    selected: bool,
+}
+
+/// The absolute URL of a feed path, for pasting into calendar apps.
+fn feed_url(path: &str) -> String {
+    #[cfg(target_arch = "wasm32")]
+    {
+        if let Some(origin) = web_sys::window().and_then(|w| w.location().origin().ok()) {
+            return format!("{origin}{path}");
+        }
+    }
+    path.to_string()
+}
+
+fn minutes(start: &str, end: &str) -> i64 {
+    let parse = |s: &str| chrono::DateTime::parse_from_rfc3339(s).ok();
+    match (parse(start), parse(end)) {
+        (Some(start), Some(end)) => (end - start).num_minutes(),
+        _ => 0,
+    }
+}
+
+fn project_options(projects: &[ProjectDto]) -> Vec<SelectOption<String>> {
+    projects
+        .iter()
+        .map(|p| SelectOption::new(p.id.clone(), p.name.clone()))
+        .collect()
+}
+
+/// Activities usable with `project_id`, after a "none" option with an empty
+/// value.
+fn activity_options(
+    activities: &[ActivityDto],
+    project_id: Option<&str>,
+) -> Vec<SelectOption<String>> {
+    std::iter::once(SelectOption::new(String::new(), "No activity"))
+        .chain(
+            activities
+                .iter()
+                .filter(|a| a.project_id.is_none() || a.project_id.as_deref() == project_id)
+                .map(|a| SelectOption::new(a.id.clone(), a.name.clone())),
+        )
+        .collect()
+}
+
+/// Your private calendar feed, and importing calendar events as timesheets
+/// with keyword rules that pick their project and activity.
+#[component]
+pub fn Calendar() -> Element {
+    let mut toasts: Toasts = use_context();
+    let user_settings: crate::UserSettings = use_context();
+
+    let mut feed = use_signal(|| None::<CalendarFeedDto>);
+    // The path of a feed that was just turned on or reset; shown once.
+    let mut issued = use_signal(|| None::<String>);
+    let mut rules = use_signal(Vec::<CalendarRuleDto>::new);
+    let mut projects = use_signal(Vec::<ProjectDto>::new);
+    let mut activities = use_signal(Vec::<ActivityDto>::new);
+    let mut revision = use_signal(|| 0_u32);
+    let live = use_live(&[Topic::Projects, Topic::Activities]);
+
+    // New rule form
+    let mut rule_keyword = use_signal(String::new);
+    let mut rule_project = use_signal(|| None::<String>);
+    let mut rule_activity = use_signal(|| None::<String>);
+
+    let mut picks = use_signal(Vec::<Pick>::new);
+
+    use_resource(move || async move {
+        let _ = revision();
+        match api::calendar::get_calendar_feed().await {
+            Ok(f) => feed.set(f),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        match api::calendar::list_calendar_rules().await {
+            Ok(list) => rules.set(list),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    });
+
+    use_resource(move || async move {
+        let _ = live();
+        if let Ok(list) = api::project::list_projects().await {
+            projects.set(list);
+        }
+        if let Ok(list) = api::activity::list_activities().await {
+            activities.set(list);
+        }
+    });
+
+    let on_reset_feed = move |_| async move {
+        match api::calendar::reset_calendar_feed().await {
+            Ok(path) => {
+                issued.set(Some(feed_url(&path)));
+                revision += 1;
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
+
+    let on_disable_feed = move |_| async move {
+        match api::calendar::disable_calendar_feed().await {
+            Ok(()) => {
+                issued.set(None);
+                revision += 1;
+                toasts.push_success("Calendar feed turned off");
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
+
+    let on_add_rule = move |_| async move {
+        let keyword = rule_keyword.peek().trim().to_string();
+        let Some(project_id) = rule_project.peek().clone() else {
+            toasts.push_error("Choose a project");
+            return;
+        };
+        let activity_id = rule_activity.peek().clone().filter(|a| !a.is_empty());
+        match api::calendar::add_calendar_rule(keyword, project_id, activity_id).await {
+            Ok(_) => {
+                rule_keyword.set(String::new());
+                rule_project.set(None);
+                rule_activity.set(None);
+                revision += 1;
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
+
+    let on_upload = move |e: FormEvent| async move {
+        let Some(file) = e.files().into_iter().next() else {
+            return;
+        };
+        let ics = match file.read_string().await {
+            Ok(ics) => ics,
+            Err(e) => {
+                toasts.push_error(e.to_string());
+                return;
+            }
+        };
+        match api::calendar::preview_calendar_import(ics).await {
+            Ok(list) => picks.set(
+                list.into_iter()
+                    .map(|event| Pick {
+                        selected: !event.tracked,
+                        event,
+                    })
+                    .collect(),
+            ),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
+
+    let on_import = move |_| async move {
+        let billable = |project_id: &Option<String>| {
+            projects
+                .peek()
+                .iter()
+                .find(|p| Some(&p.id) == project_id.as_ref())
+                .is_none_or(|p| p.billable)
+        };
+        let selections: Vec<ImportSelectionDto> = picks
+            .peek()
+            .iter()
+            .filter(|p| p.selected)
+            .map(|p| ImportSelectionDto {
+                title: p.event.title.clone(),
+                start_time: p.event.start_time.clone(),
+                end_time: p.event.end_time.clone(),
+                project_id: p.event.project_id.clone(),
+                activity_id: p.event.activity_id.clone(),
+                billable: billable(&p.event.project_id),
+            })
+            .collect();
+        if selections.is_empty() {
+            toasts.push_error("Select the events to import");
+            return;
+        }
+        match api::calendar::import_calendar_events(selections).await {
+            Ok(created) => {
+                picks.set(Vec::new());
+                toasts.push_success(format!("Imported {} events", created.len()));
+            }
+            Err(e) => {
+                // Events before the failing one were imported.
+                toasts.push_error(e.to_string());
+            }
+        }
+    };
+
+    let project_name = move |id: &str| {
+        projects
+            .read()
+            .iter()
+            .find(|p| p.id == id)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| "unknown project".to_string())
+    };
+    let activity_name = move |id: &str| {
+        activities
+            .read()
+            .iter()
+            .find(|a| a.id == id)
+            .map(|a| a.name.clone())
+            .unwrap_or_else(|| "unknown activity".to_string())
+    };
+
+    let rule_columns = vec![
+        ColumnDef::new("Keyword"),
+        ColumnDef::new("Project"),
+        ColumnDef::new("Activity"),
+        ColumnDef::new("").width("60px"),
+    ];
+    let import_columns = vec![
+        ColumnDef::new("").width("40px"),
+        ColumnDef::new("Event"),
+        ColumnDef::new("Start").width("180px"),
+        ColumnDef::new("Minutes").width("90px").right(),
+        ColumnDef::new("Project").width("220px"),
+        ColumnDef::new("Activity").width("220px"),
+    ];
+
+    let rule_list = rules.read().clone();
+    let pick_list = picks.read().clone();
+    let selected_count = pick_list.iter().filter(|p| p.selected).count();
+    let project_list = projects.read().clone();
+    let activity_list = activities.read().clone();
+
+    rsx! {
+        DefaultLayout {
+            div { class: "space-y-6",
+                div { class: "island",
+                    div { class: "island-header",
+                        span { class: "island-title", "Calendar Feed" }
+                        span { class: "island-subtitle", "subscribe to your timesheets of the last year in any calendar app" }
+                    }
+                    div { class: "space-y-4",
+                        match feed.read().clone() {
+                            Some(f) => rsx! {
+                                p { class: "text-sm text-secondary",
+                                    "Your feed is on since {f.created_at}. Anyone with its URL can read your timesheets; reset it to lock out the old URL."
+                                }
+                            },
+                            None => rsx! {
+                                p { class: "text-sm text-secondary", "Your feed is off." }
+                            },
+                        }
+                        if let Some(url) = issued.read().clone() {
+                            div { class: "form-field",
+                                label { class: "form-label", "Feed URL — copy it now, it is not shown again" }
+                                Input { value: url, readonly: true }
+                            }
+                        }
+                    }
+                    div { class: "flex justify-end gap-2 mt-4",
+                        if feed.read().is_some() {
+                            Button {
+                                variant: ButtonVariant::Destructive,
+                                onclick: on_disable_feed,
+                                Icon { icon: HiX, width: 14, height: 14 }
+                                "Turn Off"
+                            }
+                            Button { onclick: on_reset_feed,
+                                Icon { icon: HiRefresh, width: 14, height: 14 }
+                                "Reset URL"
+                            }
+                        } else {
+                            Button { onclick: on_reset_feed,
+                                Icon { icon: HiPlus, width: 14, height: 14 }
+                                "Turn On"
+                            }
+                        }
+                    }
+                }
+
+                div { class: "island",
+                    div { class: "island-header",
+                        span { class: "island-title", "Import Events" }
+                        span { class: "island-subtitle", "turn meetings of an .ics file into timesheet entries" }
+                    }
+                    div { class: "form-field",
+                        label { class: "form-label", r#for: "calendar-file", "Calendar File" }
+                        input {
+                            id: "calendar-file",
+                            class: "form-input",
+                            r#type: "file",
+                            accept: ".ics,text/calendar",
+                            onchange: on_upload,
+                        }
+                    }
+                    if !pick_list.is_empty() {
+                        DataTable {
+                            columns: import_columns,
+                            total: pick_list.len(),
+                            page: 0,
+                            page_size: pick_list.len(),
+                            on_page_change: move |_| {},
+
+                            for (i, pick) in pick_list.into_iter().enumerate() {
+                                {
+                                    let start = {
+                                        let s = user_settings.read();
+                                        formatting::format_datetime(&pick.event.start_time, &s.timezone, &s.date_format)
+                                    };
+                                    let length = minutes(&pick.event.start_time, &pick.event.end_time);
+                                    let activity_choices = activity_options(&activity_list, pick.event.project_id.as_deref());
+                                    rsx! {
+                                        TableRow { key: "{i}", muted: !pick.selected,
+                                            TableCell {
+                                                input {
+                                                    r#type: "checkbox",
+                                                    class: "form-checkbox",
+                                                    checked: pick.selected,
+                                                    oninput: move |_| {
+                                                        if let Some(p) = picks.write().get_mut(i) {
+                                                            p.selected = !p.selected;
+                                                        }
+                                                    },
+                                                }
+                                            }
+                                            TableCell {
+                                                "{pick.event.title}"
+                                                if pick.event.tracked {
+                                                    span { class: "text-secondary", " — already tracked" }
+                                                }
+                                            }
+                                            TableCell { mono: true, "{start}" }
+                                            TableCell { mono: true, "{length}" }
+                                            TableCell {
+                                                Select::<String> {
+                                                    options: project_options(&project_list),
+                                                    value: pick.event.project_id.clone(),
+                                                    on_change: move |id: String| {
+                                                        if let Some(p) = picks.write().get_mut(i) {
+                                                            p.event.project_id = Some(id);
+                                                            p.event.activity_id = None;
+                                                        }
+                                                    },
+                                                    placeholder: "No project".to_string(),
+                                                }
+                                            }
+                                            TableCell {
+                                                Select::<String> {
+                                                    options: activity_choices,
+                                                    value: pick.event.activity_id.clone().or(Some(String::new())),
+                                                    on_change: move |id: String| {
+                                                        if let Some(p) = picks.write().get_mut(i) {
+                                                            p.event.activity_id = (!id.is_empty()).then_some(id);
+                                                        }
+                                                    },
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "flex justify-end gap-2 mt-4",
+                            Button {
+                                variant: ButtonVariant::Ghost,
+                                onclick: move |_| picks.set(Vec::new()),
+                                Icon { icon: HiX, width: 14, height: 14 }
+                                "Discard"
+                            }
+                            Button { onclick: on_import,
+                                Icon { icon: HiDownload, width: 14, height: 14 }
+                                "Import {selected_count} Events"
+                            }
+                        }
+                    }
+                }
+
+                div { class: "island",
+                    div { class: "island-header",
+                        span { class: "island-title", "Mapping Rules" }
+                        span { class: "island-subtitle", "events whose title contains the keyword get its project and activity; the longest keyword wins" }
+                    }
+                    div { class: "grid grid-cols-1 gap-4 md:grid-cols-4",
+                        div { class: "form-field",
+                            label { class: "form-label", r#for: "rule-keyword", "Keyword" }
+                            Input {
+                                id: "rule-keyword",
+                                placeholder: "Stand-up",
+                                value: rule_keyword.read().clone(),
+                                oninput: move |e: FormEvent| rule_keyword.set(e.value()),
+                            }
+                        }
+                        div { class: "form-field",
+                            label { class: "form-label", "Project" }
+                            Select::<String> {
+                                options: project_options(&project_list),
+                                value: rule_project.read().clone(),
+                                on_change: move |id: String| {
+                                    rule_project.set(Some(id));
+                                    rule_activity.set(None);
+                                },
+                                placeholder: "Select project…".to_string(),
+                            }
+                        }
+                        div { class: "form-field",
+                            label { class: "form-label", "Activity" }
+                            Select::<String> {
+                                options: activity_options(&activity_list, rule_project.read().as_deref()),
+                                value: rule_activity.read().clone(),
+                                on_change: move |id: String| rule_activity.set(Some(id)),
+                                placeholder: "No activity".to_string(),
+                            }
+                        }
+                        div { class: "flex items-end",
+                            Button { onclick: on_add_rule,
+                                Icon { icon: HiPlus, width: 14, height: 14 }
+                                "Add Rule"
+                            }
+                        }
+                    }
+                    if !rule_list.is_empty() {
+                        DataTable {
+                            columns: rule_columns,
+                            total: rule_list.len(),
+                            page: 0,
+                            page_size: rule_list.len(),
+                            on_page_change: move |_| {},
+
+                            for rule in rule_list {
+                                {
+                                    let id_remove = rule.id.clone();
+                                    let activity = rule.activity_id.as_deref().map_or_else(|| "—".to_string(), activity_name);
+                                    rsx! {
+                                        TableRow { key: "{rule.id}",
+                                            TableCell { "{rule.keyword}" }
+                                            TableCell { {project_name(&rule.project_id)} }
+                                            TableCell { "{activity}" }
+                                            TableCell {
+                                                Button {
+                                                    variant: ButtonVariant::Destructive,
+                                                    onclick: move |_| {
+                                                        let id = id_remove.clone();
+                                                        async move {
+                                                            match api::calendar::remove_calendar_rule(id).await {
+                                                                Ok(()) => revision += 1,
+                                                                Err(e) => toasts.push_error(e.to_string()),
+                                                            }
+                                                        }
+                                                    },
+                                                    Icon { icon: HiTrash, width: 14, height: 14 }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
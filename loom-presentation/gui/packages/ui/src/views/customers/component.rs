@@ -1,5 +1,7 @@
 use crate::components::atoms::{ColumnDef, DataTable, ToastExt, Toasts};
 use crate::layouts::DefaultLayout;
+use crate::live::{use_live, Topic};
+use crate::views::archive::{ArchiveKind, ArchivedIsland};
 use crate::views::customers::create_form::CustomerCreateForm;
 use crate::views::customers::customer_row::CustomerRow;
 use crate::CustomersCache;
@@ -16,8 +18,11 @@ pub fn Customers() -> Element {
     let mut toasts: Toasts = use_context();
     let mut page = use_signal(|| 0_usize);
     let editing_id = use_signal(|| Option::<String>::None);
+    let archived_rev = use_signal(|| 0_u32);
+    let live = use_live(&[Topic::Customers]);
 
     use_resource(move || async move {
+        let _ = live();
         match api::customer::list_customers().await {
             Ok(list) => {
                 customers.set(list);
@@ -44,7 +49,7 @@ pub fn Customers() -> Element {
         ColumnDef::new("Name"),
         ColumnDef::new("Currency / Timezone"),
         ColumnDef::new("Budget"),
-        ColumnDef::new("").width("80px"),
+        ColumnDef::new("").width("120px"),
     ];
     let col_count = columns.len();
 
@@ -74,10 +79,23 @@ pub fn Customers() -> Element {
                                 customers,
                                 editing_id,
                                 col_count,
+                                archived_rev,
                             }
                         }
                     }
                 }
+
+                ArchivedIsland {
+                    kind: ArchiveKind::Customer,
+                    revision: archived_rev,
+                    on_restored: move |_| {
+                        spawn(async move {
+                            if let Ok(list) = api::customer::list_customers().await {
+                                customers.set(list);
+                            }
+                        });
+                    },
+                }
             }
         }
     }
@@ -2,6 +2,7 @@ use crate::components::atoms::card::{Card, CardContent};
 use crate::components::atoms::{Button, ButtonVariant, Select, SelectOption, ToastExt, Toasts};
 use crate::formatting;
 use crate::layouts::DefaultLayout;
+use crate::live::{use_live, Topic};
 use crate::{ActivitiesCache, ProjectsCache, TimesheetsCache};
 use chrono::{Datelike, Duration, Utc};
 use dioxus::prelude::*;
@@ -37,6 +38,12 @@ fn fmt_hours(h: f32) -> String {
     }
 }
 
+fn fmt_seconds(secs: i64) -> String {
+    let sign = if secs < 0 { "-" } else { "" };
+    let secs = secs.abs();
+    format!("{sign}{}h {:02}m", secs / 3600, (secs % 3600) / 60)
+}
+
 fn fmt_hours_axis(v: f32) -> String {
     format!("{:.0}h", v)
 }
@@ -143,12 +150,23 @@ pub fn Dashboard() -> Element {
     let mut projects = use_signal(|| projects_cache.read().clone());
     let mut activities = use_signal(|| activities_cache.read().clone());
     let mut recent = use_signal(|| timesheets_cache.read().clone());
+    let mut budgets = use_signal(Vec::<api::budget::BudgetConsumptionDto>::new);
+    let mut overtime = use_signal(|| None::<api::work_contract::OvertimeBalanceDto>);
 
     let mut selected_project_id = use_signal(|| Option::<String>::None);
     let mut selected_activity_id = use_signal(|| Option::<String>::None);
     let elapsed_secs: crate::RunningElapsed = use_context();
+    let live = use_live(&[
+        Topic::Timesheets,
+        Topic::Customers,
+        Topic::Projects,
+        Topic::Activities,
+        Topic::WorkContracts,
+        Topic::Holidays,
+    ]);
 
     use_resource(move || async move {
+        let _ = live();
         if let Ok(list) = api::project::list_projects().await {
             projects.set(list);
         }
@@ -158,6 +176,12 @@ pub fn Dashboard() -> Element {
         if let Ok(list) = api::timesheet::list_timesheets().await {
             recent.set(list);
         }
+        if let Ok(list) = api::budget::list_budget_consumption().await {
+            budgets.set(list);
+        }
+        if let Ok(balance) = api::work_contract::my_overtime_balance().await {
+            overtime.set(balance);
+        }
     });
 
     let on_start = move |_| async move {
@@ -215,7 +239,9 @@ pub fn Dashboard() -> Element {
                                         div { class: "dashboard-timer-header",
                                             div { class: "dashboard-timer-status",
                                                 span { class: "timer-dot" }
-                                                span { class: "dashboard-timer-status-label", "Timer Running" }
+                                                span { class: "dashboard-timer-status-label",
+                                                    if ts.paused_at.is_some() { "Timer Paused" } else { "Timer Running" }
+                                                }
                                             }
                                             Button {
                                                 variant: ButtonVariant::Ghost,
@@ -300,6 +326,32 @@ pub fn Dashboard() -> Element {
                     }
                 }
 
+                // ── Overtime ─────────────────────────────────────────────────
+                if let Some(ref b) = *overtime.read() {
+                    div { class: "island",
+                        div { class: "island-header",
+                            span { class: "island-title", "Overtime Balance" }
+                            span { class: "island-subtitle", "until {b.until}" }
+                        }
+                        div { class: "flex items-center justify-between",
+                            div { class: "flex flex-col gap-1",
+                                span { class: "text-xs text-secondary",
+                                    "{fmt_seconds(b.worked_seconds)} worked · {fmt_seconds(b.expected_seconds)} expected"
+                                }
+                                if let Some(week) = b.weeks.last() {
+                                    span { class: "text-xs text-secondary",
+                                        "this week {fmt_seconds(week.worked_seconds - week.expected_seconds)}"
+                                    }
+                                }
+                            }
+                            span {
+                                class: if b.balance_seconds < 0 { "text-sm font-medium text-warning" } else { "text-sm font-medium text-success" },
+                                "{fmt_seconds(b.balance_seconds)}"
+                            }
+                        }
+                    }
+                }
+
                 // ── Charts ───────────────────────────────────────────────────
                 if stats.has_week_data {
                     div { class: "dash-charts-grid",
@@ -397,6 +449,67 @@ pub fn Dashboard() -> Element {
                     }
                 }
 
+                // ── Budgets ──────────────────────────────────────────────────
+                if !budgets.read().is_empty() {
+                    div { class: "island",
+                        div { class: "island-header",
+                            span { class: "island-title", "Budgets" }
+                            span { class: "island-subtitle", "current period" }
+                        }
+                        div { class: "flex flex-col gap-2",
+                            for b in budgets.read().iter() {
+                                {
+                                    let pct = b.percent_used.unwrap_or(0);
+                                    let state = match b.reached_threshold {
+                                        Some(_) if pct >= 100 => "dash-budget--over",
+                                        Some(_) => "dash-budget--warn",
+                                        None => "",
+                                    };
+                                    let remaining = [
+                                        b.remaining_seconds.map(fmt_seconds),
+                                        b.remaining_amount.map(|r| {
+                                            formatting::format_money(r, &workspace_settings.read().currency)
+                                        }),
+                                    ]
+                                    .into_iter()
+                                    .flatten()
+                                    .collect::<Vec<_>>()
+                                    .join(" · ");
+                                    let scope = if b.scope == "customer" { "Customer" } else { "Project" };
+                                    rsx! {
+                                        div { key: "{b.scope}-{b.id}", class: "dash-budget {state}",
+                                            div { class: "flex items-center justify-between",
+                                                div { class: "flex flex-col gap-1",
+                                                    span { class: "font-medium text-sm", "{b.name}" }
+                                                    span { class: "text-xs text-secondary",
+                                                        "{scope}"
+                                                        if let Some(ref period) = b.period {
+                                                            " · {period}"
+                                                        }
+                                                    }
+                                                }
+                                                div { class: "flex flex-col items-end gap-1",
+                                                    span { class: "text-sm font-medium", "{pct}%" }
+                                                    span { class: "text-xs text-secondary", "{remaining} left" }
+                                                    if let Some(t) = b.reached_threshold {
+                                                        span { class: "dash-budget-alert text-xs", "{t}% reached" }
+                                                    }
+                                                }
+                                            }
+                                            div { class: "dash-budget-bar",
+                                                div {
+                                                    class: "dash-budget-fill",
+                                                    style: "width: {pct.clamp(0, 100)}%",
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
                 // ── Recent Entries ───────────────────────────────────────────
                 if !recent.read().is_empty() {
                     div { class: "island",
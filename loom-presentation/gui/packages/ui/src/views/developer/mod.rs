@@ -1,2 +1,4 @@
 pub mod database;
+pub mod projections;
 pub use database::Database;
+pub use projections::Projections;
@@ -0,0 +1,260 @@
+use crate::components::atoms::{
+    Button, ColumnDef, DataTable, TableCell, TableExpandRow, TableRow, ToastExt, Toasts,
+};
+use crate::layouts::DefaultLayout;
+use api::developer::{AggregateLoadDto, DeadLetterDto, ProjectionStatusDto};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::HiRefresh;
+use dioxus_free_icons::Icon;
+
+fn format_lag(seconds: Option<i64>) -> String {
+    match seconds {
+        None => "—".to_string(),
+        Some(s) if s < 60 => format!("{s}s"),
+        Some(s) if s < 3600 => format!("{}m {}s", s / 60, s % 60),
+        Some(s) => format!("{}h {}m", s / 3600, (s % 3600) / 60),
+    }
+}
+
+fn format_micros(micros: u64) -> String {
+    if micros < 1000 {
+        format!("{micros} µs")
+    } else {
+        format!("{:.1} ms", micros as f64 / 1000.0)
+    }
+}
+
+/// Developer page showing checkpoint, lag and errors of every projection runner,
+/// plus aggregate load times and snapshot usage.
+#[component]
+pub fn Projections() -> Element {
+    let mut toasts: Toasts = use_context();
+
+    let mut statuses = use_signal(Vec::<ProjectionStatusDto>::new);
+    let mut dead_letters = use_signal(Vec::<DeadLetterDto>::new);
+    let mut loads = use_signal(Vec::<AggregateLoadDto>::new);
+    let mut loading = use_signal(|| true);
+    let mut refresh = use_signal(|| 0_u32);
+
+    use_resource(move || async move {
+        let _ = refresh.read();
+        loading.set(true);
+        match api::developer::projection_status().await {
+            Ok(list) => statuses.set(list),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        match api::developer::list_dead_letters().await {
+            Ok(list) => dead_letters.set(list),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        match api::developer::aggregate_loads().await {
+            Ok(list) => loads.set(list),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        loading.set(false);
+    });
+
+    let columns = vec![
+        ColumnDef::new("Runner"),
+        ColumnDef::new("Checkpoint").right().width("120px"),
+        ColumnDef::new("Head").right().width("120px"),
+        ColumnDef::new("Lag").right().width("140px"),
+        ColumnDef::new("Retries").right().width("90px"),
+        ColumnDef::new("Parked").right().width("90px"),
+        ColumnDef::new("Status").width("120px"),
+    ];
+    let col_count = columns.len();
+    let rows = statuses.read().clone();
+
+    let dead_letter_columns = vec![
+        ColumnDef::new("Database").width("180px"),
+        ColumnDef::new("Event").width("220px"),
+        ColumnDef::new("Error"),
+        ColumnDef::new("Retries").right().width("90px"),
+        ColumnDef::new("").width("180px"),
+    ];
+    let parked = dead_letters.read().clone();
+
+    let load_columns = vec![
+        ColumnDef::new("Aggregate"),
+        ColumnDef::new("Loads").right().width("90px"),
+        ColumnDef::new("From snapshot").right().width("130px"),
+        ColumnDef::new("Events replayed").right().width("140px"),
+        ColumnDef::new("Avg").right().width("100px"),
+        ColumnDef::new("Max").right().width("100px"),
+        ColumnDef::new("Snapshots").right().width("110px"),
+        ColumnDef::new("Invalidated").right().width("110px"),
+    ];
+    let load_rows = loads.read().clone();
+
+    rsx! {
+        DefaultLayout {
+            div { class: "island",
+                div { class: "island-header",
+                    span { class: "island-title", "Projections" }
+                    Button {
+                        onclick: move |_| {
+                            let v = *refresh.peek();
+                            refresh.set(v + 1);
+                        },
+                        Icon { icon: HiRefresh, width: 14, height: 14 }
+                    }
+                }
+                DataTable {
+                    columns,
+                    total: rows.len(),
+                    page: 0,
+                    page_size: rows.len().max(1),
+                    loading: *loading.read(),
+                    on_page_change: move |_| {},
+
+                    for status in rows {
+                        {
+                            let lag = format!(
+                                "{} events / {}",
+                                status.lag_events,
+                                format_lag(status.lag_seconds),
+                            );
+                            rsx! {
+                                TableRow { key: "{status.runner}",
+                                    TableCell { mono: true, "{status.runner}" }
+                                    TableCell { mono: true, "{status.position}" }
+                                    TableCell { mono: true, "{status.head_position}" }
+                                    TableCell { "{lag}" }
+                                    TableCell { "{status.retry_count}" }
+                                    TableCell { "{status.parked}" }
+                                    TableCell {
+                                        if status.last_error.is_some() {
+                                            span { class: "text-red-500 font-medium", "Failing" }
+                                        } else if status.lag_events > 0 {
+                                            span { class: "text-secondary", "Catching up" }
+                                        } else {
+                                            span { "Up to date" }
+                                        }
+                                    }
+                                }
+                                if let Some(error) = status.last_error.clone() {
+                                    TableExpandRow { col_count,
+                                        div { class: "flex flex-col gap-1 text-xs",
+                                            span { class: "text-red-500", "{error}" }
+                                            span { class: "font-mono",
+                                                "Event #{status.error_position.unwrap_or_default()} "
+                                                "{status.error_event_type.clone().unwrap_or_default()} on "
+                                                "{status.error_stream_id.clone().unwrap_or_default()}"
+                                            }
+                                            if let Some(payload) = status.error_payload.clone() {
+                                                pre { class: "font-mono whitespace-pre-wrap", "{payload}" }
+                                            }
+                                            span { class: "text-secondary", "Last attempt: {status.updated_at}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "island mt-6",
+                div { class: "island-header",
+                    span { class: "island-title", "Parked events" }
+                }
+                DataTable {
+                    columns: dead_letter_columns,
+                    total: parked.len(),
+                    page: 0,
+                    page_size: parked.len().max(1),
+                    loading: *loading.read(),
+                    on_page_change: move |_| {},
+
+                    for letter in parked {
+                        {
+                            let replay_db = letter.database.clone();
+                            let skip_db = letter.database.clone();
+                            let id = letter.id;
+                            rsx! {
+                                TableRow { key: "{letter.database}-{letter.id}",
+                                    TableCell { mono: true, "{letter.database}" }
+                                    TableCell {
+                                        div { class: "flex flex-col gap-0.5",
+                                            span { class: "font-medium text-sm", "{letter.event_type}" }
+                                            span { class: "text-xs text-secondary font-mono",
+                                                "#{letter.global_position} on {letter.stream_id}"
+                                            }
+                                        }
+                                    }
+                                    TableCell {
+                                        div { class: "flex flex-col gap-0.5 text-xs",
+                                            span { class: "text-red-500", "{letter.error}" }
+                                            span { class: "text-secondary", "Parked {letter.parked_at}" }
+                                        }
+                                    }
+                                    TableCell { "{letter.retry_count}" }
+                                    TableCell {
+                                        div { class: "flex gap-2",
+                                            Button {
+                                                onclick: move |_| {
+                                                    let database = replay_db.clone();
+                                                    async move {
+                                                        match api::developer::replay_dead_letter(database, id).await {
+                                                            Ok(()) => toasts.push_success("Event replayed"),
+                                                            Err(e) => toasts.push_error(e.to_string()),
+                                                        }
+                                                        let v = *refresh.peek();
+                                                        refresh.set(v + 1);
+                                                    }
+                                                },
+                                                "Replay"
+                                            }
+                                            Button {
+                                                onclick: move |_| {
+                                                    let database = skip_db.clone();
+                                                    async move {
+                                                        match api::developer::skip_dead_letter(database, id).await {
+                                                            Ok(()) => toasts.push_success("Event skipped"),
+                                                            Err(e) => toasts.push_error(e.to_string()),
+                                                        }
+                                                        let v = *refresh.peek();
+                                                        refresh.set(v + 1);
+                                                    }
+                                                },
+                                                "Skip"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "island mt-6",
+                div { class: "island-header",
+                    span { class: "island-title", "Aggregate loads" }
+                }
+                DataTable {
+                    columns: load_columns,
+                    total: load_rows.len(),
+                    page: 0,
+                    page_size: load_rows.len().max(1),
+                    loading: *loading.read(),
+                    on_page_change: move |_| {},
+
+                    for m in load_rows {
+                        TableRow { key: "{m.aggregate_type}",
+                            TableCell { mono: true, "{m.aggregate_type}" }
+                            TableCell { "{m.loads}" }
+                            TableCell { "{m.snapshot_hits}" }
+                            TableCell { "{m.events_replayed}" }
+                            TableCell { "{format_micros(m.average_micros)}" }
+                            TableCell { "{format_micros(m.max_micros)}" }
+                            TableCell { "{m.snapshots_taken}" }
+                            TableCell { "{m.snapshots_invalidated}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
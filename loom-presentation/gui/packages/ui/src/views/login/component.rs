@@ -6,7 +6,7 @@ use dioxus::prelude::*;
 use dioxus_free_icons::icons::hi_solid_icons::{HiLogin, HiRefresh};
 use dioxus_free_icons::Icon;
 
-type AuthState = Signal<Option<Option<api::auth::UserInfo>>>;
+use crate::AuthState;
 
 #[component]
 pub fn Login() -> Element {
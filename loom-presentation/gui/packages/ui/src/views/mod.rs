@@ -1,5 +1,12 @@
 pub mod activities;
 pub use activities::*;
+pub mod approvals;
+pub use approvals::*;
+pub mod archive;
+pub mod calendar;
+pub use calendar::*;
+pub mod audit_log;
+pub use audit_log::*;
 pub mod customers;
 pub use customers::*;
 pub mod dashboard;
@@ -20,3 +27,7 @@ pub mod settings;
 pub use settings::*;
 pub mod timesheets;
 pub use timesheets::*;
+pub mod webhooks;
+pub use webhooks::*;
+pub mod work_time;
+pub use work_time::*;
@@ -0,0 +1,95 @@
+use crate::components::atoms::{Button, ToastExt, Toasts};
+use crate::formatting;
+use api::time_travel::{AsOfDto, ProjectAsOfDto};
+use dioxus::prelude::*;
+
+#[derive(Clone, PartialEq, Props)]
+pub(super) struct AsOfPanelProps {
+    pub project_id: String,
+    pub currency: String,
+}
+
+/// Rates and logged time of a project as they were at a chosen point in time.
+#[component]
+pub(super) fn AsOfPanel(props: AsOfPanelProps) -> Element {
+    let mut toasts: Toasts = use_context();
+    let user_settings: crate::UserSettings = use_context();
+
+    let mut as_of = use_signal(String::new);
+    let mut approved_only = use_signal(|| false);
+    let mut report = use_signal(|| None::<Option<ProjectAsOfDto>>);
+    let mut loading = use_signal(|| false);
+
+    let project_id = props.project_id.clone();
+    let on_show = move |_| {
+        let project_id = project_id.clone();
+        async move {
+            let local = as_of.peek().clone();
+            if local.is_empty() {
+                return;
+            }
+            let instant = formatting::from_input(&local, &user_settings.peek().timezone);
+            loading.set(true);
+            let approved_only = *approved_only.peek();
+            match api::time_travel::project_as_of(project_id, AsOfDto::Instant(instant), approved_only).await {
+                Ok(r) => report.set(Some(r)),
+                Err(e) => toasts.push_error(e.to_string()),
+            }
+            loading.set(false);
+        }
+    };
+
+    rsx! {
+        p { class: "text-xs font-medium text-secondary mb-2 mt-4", "As of" }
+        div { class: "flex items-center gap-2",
+            input {
+                r#type: "datetime-local",
+                class: "input",
+                value: as_of.read().clone(),
+                oninput: move |e: FormEvent| as_of.set(e.value()),
+            }
+            label { class: "flex items-center gap-2 text-sm",
+                input {
+                    r#type: "checkbox",
+                    class: "form-checkbox",
+                    checked: *approved_only.read(),
+                    oninput: move |_| { let v = *approved_only.peek(); approved_only.set(!v); },
+                }
+                "Approved only"
+            }
+            Button {
+                onclick: on_show,
+                disabled: *loading.read(),
+                "Show"
+            }
+        }
+        match &*report.read() {
+            None => rsx! {},
+            Some(None) => rsx! {
+                p { class: "text-sm text-secondary mt-2", "The project did not exist yet." }
+            },
+            Some(Some(r)) => {
+                let hours = r.logged_seconds as f64 / 3600.0;
+                let amount = formatting::format_money(r.logged_amount, &props.currency);
+                rsx! {
+                    div { class: "flex flex-col gap-1 text-sm mt-2",
+                        span { "Name: {r.project.name}" }
+                        span { "Logged: {hours:.2} h in {r.timesheets.len()} entries, {amount}" }
+                        if r.rates.is_empty() {
+                            span { class: "text-secondary", "No rates set." }
+                        }
+                        for rate in r.rates.iter() {
+                            {
+                                let who = rate.user_id.clone().unwrap_or_else(|| "Default".to_string());
+                                let hourly = formatting::format_money(rate.hourly_rate, &props.currency);
+                                rsx! {
+                                    span { key: "{rate.id}", "Rate ({who}): {hourly}/h" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use crate::components::atoms::{ColumnDef, DataTable, ToastExt, Toasts};
 use crate::layouts::DefaultLayout;
+use crate::live::{use_live, Topic};
+use crate::views::archive::{ArchiveKind, ArchivedIsland};
 use crate::views::projects::create_form::ProjectCreateForm;
 use crate::views::projects::project_row::ProjectRow;
 use crate::{CustomersCache, ProjectsCache};
@@ -18,8 +20,11 @@ pub fn Projects() -> Element {
     let mut toasts: Toasts = use_context();
     let mut page = use_signal(|| 0_usize);
     let editing_id = use_signal(|| Option::<String>::None);
+    let archived_rev = use_signal(|| 0_u32);
+    let live = use_live(&[Topic::Projects, Topic::Customers]);
 
     use_resource(move || async move {
+        let _ = live();
         match api::project::list_projects().await {
             Ok(list) => projects.set(list),
             Err(e) => toasts.push_error(e.to_string()),
@@ -46,7 +51,7 @@ pub fn Projects() -> Element {
         ColumnDef::new("Customer"),
         ColumnDef::new("Budget"),
         ColumnDef::new("Flags"),
-        ColumnDef::new("").width("80px"),
+        ColumnDef::new("").width("120px"),
     ];
     let col_count = columns.len();
 
@@ -78,10 +83,23 @@ pub fn Projects() -> Element {
                                 customers,
                                 editing_id,
                                 col_count,
+                                archived_rev,
                             }
                         }
                     }
                 }
+
+                ArchivedIsland {
+                    kind: ArchiveKind::Project,
+                    revision: archived_rev,
+                    on_restored: move |_| {
+                        spawn(async move {
+                            if let Ok(list) = api::project::list_projects().await {
+                                projects.set(list);
+                            }
+                        });
+                    },
+                }
             }
         }
     }
@@ -1,3 +1,4 @@
+mod as_of_panel;
 mod component;
 mod create_form;
 mod project_row;
@@ -2,10 +2,14 @@ use crate::components::atoms::{
     Button, Input, TableCell, TableExpandRow, TableRow, ToastExt, Toasts,
 };
 use crate::form_machine::{new_form, FormAction, State};
+use crate::views::archive::{archive, ArchiveKind};
+use super::as_of_panel::AsOfPanel;
+use crate::views::audit_log::HistoryPanel;
+use crate::views::settings::RoundingFields;
 use api::customer::CustomerDto;
 use api::project::ProjectDto;
 use dioxus::prelude::*;
-use dioxus_free_icons::icons::hi_solid_icons::{HiPencil, HiRefresh, HiSave, HiX};
+use dioxus_free_icons::icons::hi_solid_icons::{HiArchive, HiClock, HiPencil, HiRefresh, HiSave, HiX};
 use dioxus_free_icons::Icon;
 use loom_core::{
     tenant::project::UpdateProjectInput,
@@ -19,6 +23,8 @@ pub(super) struct ProjectRowProps {
     pub customers: Signal<Vec<CustomerDto>>,
     pub editing_id: Signal<Option<String>>,
     pub col_count: usize,
+    /// Bumped when the row is archived.
+    pub archived_rev: Signal<u32>,
 }
 
 #[component]
@@ -27,10 +33,13 @@ pub(super) fn ProjectRow(props: ProjectRowProps) -> Element {
 
     let p = props.project.clone();
     let pid = p.id.clone();
+    let archive_id = p.id.clone();
+    let archived_rev = props.archived_rev;
     let mut projects = props.projects;
     let customers = props.customers;
     let mut editing_id = props.editing_id;
     let is_editing = editing_id.read().as_deref() == Some(p.id.as_str());
+    let mut show_history = use_signal(|| false);
 
     let customer_name = customers
         .read()
@@ -38,6 +47,12 @@ pub(super) fn ProjectRow(props: ProjectRowProps) -> Element {
         .find(|c| c.id == p.customer_id)
         .map(|c| c.name.clone())
         .unwrap_or_else(|| p.customer_id.clone());
+    let currency = customers
+        .read()
+        .iter()
+        .find(|c| c.id == p.customer_id)
+        .map(|c| c.currency.clone())
+        .unwrap_or_default();
 
     let mut edit_form = use_signal(new_form);
     let mut edit_name = use_signal(String::new);
@@ -48,6 +63,10 @@ pub(super) fn ProjectRow(props: ProjectRowProps) -> Element {
     let mut edit_time_budget = use_signal(String::new);
     let mut edit_money_budget = use_signal(String::new);
     let mut edit_budget_monthly = use_signal(|| false);
+    // The saved override, `None` while the broader rules apply.
+    let mut loaded_rounding = use_signal(|| None::<api::settings::RoundingDto>);
+    let mut edit_own_rounding = use_signal(|| false);
+    let mut edit_rounding = use_signal(api::settings::RoundingDto::default);
 
     let on_save = move |_| async move {
         let id = match editing_id.peek().clone() {
@@ -109,6 +128,16 @@ pub(super) fn ProjectRow(props: ProjectRowProps) -> Element {
             return;
         }
 
+        let rounding = edit_own_rounding.peek().then(|| edit_rounding.peek().clone());
+        if rounding != *loaded_rounding.peek() {
+            if let Err(e) = api::project::set_project_rounding(id.clone(), rounding.clone()).await {
+                edit_form.write().handle(&FormAction::Fail(e.to_string()));
+                toasts.push_error(e.to_string());
+                return;
+            }
+            loaded_rounding.set(rounding);
+        }
+
         let customer_id = projects
             .read()
             .iter()
@@ -175,6 +204,10 @@ pub(super) fn ProjectRow(props: ProjectRowProps) -> Element {
                         Icon { icon: HiX, width: 14, height: 14 }
                     }
                 } else {
+                    Button {
+                        onclick: move |_| { let v = *show_history.peek(); show_history.set(!v); },
+                        Icon { icon: HiClock, width: 14, height: 14 }
+                    }
                     Button {
                         onclick: move |_| {
                             let proj = projects.read()
@@ -190,15 +223,40 @@ pub(super) fn ProjectRow(props: ProjectRowProps) -> Element {
                                 edit_time_budget.set(pr.time_budget.map(|v| format!("{:.1}", v as f64 / 3600.0)).unwrap_or_default());
                                 edit_money_budget.set(pr.money_budget.map(|v| format!("{:.2}", v as f64 / 100.0)).unwrap_or_default());
                                 edit_budget_monthly.set(pr.budget_is_monthly);
+                                let id = pr.id.clone();
+                                spawn(async move {
+                                    if let Ok(rounding) = api::project::get_project_rounding(id).await {
+                                        edit_own_rounding.set(rounding.is_some());
+                                        edit_rounding.set(rounding.clone().unwrap_or_default());
+                                        loaded_rounding.set(rounding);
+                                    }
+                                });
                                 edit_form.write().handle(&FormAction::Reset);
                                 editing_id.set(Some(pr.id));
                             }
                         },
                         Icon { icon: HiPencil, width: 14, height: 14 }
                     }
+                    Button {
+                        onclick: move |_| {
+                            let id = archive_id.clone();
+                            async move {
+                                if archive(ArchiveKind::Project, id.clone(), archived_rev, toasts).await {
+                                    projects.write().retain(|x| x.id != id);
+                                }
+                            }
+                        },
+                        Icon { icon: HiArchive, width: 14, height: 14 }
+                    }
                 }
             }
         }
+        if *show_history.read() {
+            TableExpandRow { col_count: props.col_count,
+                HistoryPanel { entity_id: p.id.clone() }
+                AsOfPanel { project_id: p.id.clone(), currency }
+            }
+        }
         if is_editing {
             TableExpandRow { col_count: props.col_count,
                 div { class: "grid grid-cols-1 gap-4 md:grid-cols-3",
@@ -279,6 +337,20 @@ pub(super) fn ProjectRow(props: ProjectRowProps) -> Element {
                         }
                     }
                 }
+                div { class: "mt-4 space-y-4",
+                    label { class: "flex items-center gap-2 text-sm",
+                        input {
+                            r#type: "checkbox",
+                            class: "form-checkbox",
+                            checked: *edit_own_rounding.read(),
+                            oninput: move |_| { let v = *edit_own_rounding.peek(); edit_own_rounding.set(!v); },
+                        }
+                        "Own rounding rules (otherwise the customer's rules apply)"
+                    }
+                    if *edit_own_rounding.read() {
+                        RoundingFields { rounding: edit_rounding }
+                    }
+                }
                 if matches!(edit_form.read().state(), State::Error {}) {
                     p { class: "text-red-500 text-sm mt-2",
                         "{edit_form.read().message}"
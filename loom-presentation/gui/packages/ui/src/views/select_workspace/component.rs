@@ -3,7 +3,7 @@ use dioxus::prelude::*;
 use dioxus_free_icons::icons::hi_solid_icons::HiArrowRight;
 use dioxus_free_icons::Icon;
 
-type AuthState = Signal<Option<Option<api::auth::UserInfo>>>;
+use crate::AuthState;
 
 fn workspace_initial(ws: &WorkspaceDto) -> char {
     ws.name
@@ -0,0 +1,110 @@
+use crate::components::atoms::card::{Card, CardContent, CardFooter, CardHeader, CardTitle};
+use crate::components::atoms::{Button, ButtonVariant, Input, ToastExt, Toasts};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::{HiKey, HiPlus, HiTrash};
+use dioxus_free_icons::Icon;
+
+/// Personal API tokens for scripts and the `loom` CLI.  A new token is shown
+/// once, right after it has been issued.
+#[component]
+pub fn ApiTokens() -> Element {
+    let mut toasts: Toasts = use_context();
+    let mut name = use_signal(String::new);
+    let mut issued = use_signal(|| None::<String>);
+    let mut revision = use_signal(|| 0_u32);
+
+    let tokens = use_resource(move || async move {
+        let _ = revision();
+        api::api_token::list_api_tokens().await.unwrap_or_default()
+    });
+
+    let on_create = move |_| async move {
+        let token_name = name.peek().trim().to_string();
+        if token_name.is_empty() {
+            toasts.push_error("Give the token a name");
+            return;
+        }
+        match api::api_token::create_api_token(token_name).await {
+            Ok(token) => {
+                issued.set(Some(token));
+                name.set(String::new());
+                revision += 1;
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
+
+    rsx! {
+        Card { data_size: "md",
+            CardHeader {
+                CardTitle {
+                    div { class: "flex items-center gap-2",
+                        Icon { icon: HiKey, width: 18, height: 18 }
+                        "API Tokens"
+                    }
+                }
+            }
+            CardContent {
+                div { class: "space-y-4",
+                    p { class: "text-sm text-secondary",
+                        "Tokens act as you in this workspace, e.g. for the loom command line tool. Send them as "
+                        code { "Authorization: Bearer <token>" }
+                        "."
+                    }
+                    if let Some(token) = issued.read().clone() {
+                        div { class: "form-field",
+                            label { class: "form-label", "New Token — copy it now, it is not shown again" }
+                            Input { value: token, readonly: true }
+                        }
+                    }
+                    for token in tokens.read().clone().unwrap_or_default() {
+                        div { key: "{token.id}", class: "flex items-center justify-between text-sm",
+                            span {
+                                strong { "{token.name}" }
+                                match (&token.revoked_at, &token.last_used_at) {
+                                    (Some(revoked), _) => format!(" — revoked {revoked}"),
+                                    (None, Some(used)) => format!(" — last used {used}"),
+                                    (None, None) => format!(" — created {}, never used", token.created_at),
+                                }
+                            }
+                            if token.revoked_at.is_none() {
+                                Button {
+                                    variant: ButtonVariant::Destructive,
+                                    onclick: move |_| {
+                                        let id = token.id.clone();
+                                        async move {
+                                            match api::api_token::revoke_api_token(id).await {
+                                                Ok(()) => {
+                                                    revision += 1;
+                                                    toasts.push_success("Token revoked");
+                                                }
+                                                Err(e) => toasts.push_error(e.to_string()),
+                                            }
+                                        }
+                                    },
+                                    Icon { icon: HiTrash, width: 14, height: 14 }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            CardFooter {
+                div { class: "flex items-end gap-2",
+                    div { class: "form-field",
+                        label { class: "form-label", "Token Name" }
+                        Input {
+                            placeholder: "Laptop CLI",
+                            value: name.read().clone(),
+                            oninput: move |e: FormEvent| name.set(e.value()),
+                        }
+                    }
+                    Button { onclick: on_create,
+                        Icon { icon: HiPlus, width: 14, height: 14 }
+                        "Create Token"
+                    }
+                }
+            }
+        }
+    }
+}
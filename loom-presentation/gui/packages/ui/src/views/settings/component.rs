@@ -1,9 +1,12 @@
 use crate::components::atoms::card::{Card, CardContent, CardFooter, CardHeader, CardTitle};
 use crate::components::atoms::{Button, Input, SearchableSelect, Select, SelectOption, ToastExt, Toasts};
 use crate::layouts::DefaultLayout;
+use crate::views::settings::{ApiTokens, RoundingFields, ScheduledReports};
 use chrono::NaiveDate;
 use dioxus::prelude::*;
-use dioxus_free_icons::icons::hi_solid_icons::{HiOfficeBuilding, HiSave, HiUser};
+use dioxus_free_icons::icons::hi_solid_icons::{
+    HiBell, HiCalculator, HiClock, HiLockClosed, HiLockOpen, HiOfficeBuilding, HiSave, HiUser,
+};
 use dioxus_free_icons::Icon;
 
 // ── Helpers ───────────────────────────────────────────────────────────────────
@@ -125,6 +128,31 @@ pub fn Settings() -> Element {
     let mut ws_saving = use_signal(|| false);
     let mut ws_loaded = use_signal(|| false);
 
+    // ── Budget alert policy ───────────────────────────────────────────────────
+    let mut budget_thresholds = use_signal(|| "80, 100".to_string());
+    let mut block_over_budget = use_signal(|| false);
+    let mut budget_saving = use_signal(|| false);
+
+    // ── Time entry rules ──────────────────────────────────────────────────────
+    let mut entry_policy = use_signal(api::settings::TimeEntryPolicyDto::default);
+    let mut min_minutes = use_signal(String::new);
+    let mut max_hours = use_signal(String::new);
+    let mut policy_saving = use_signal(|| false);
+
+    // ── Rounding ──────────────────────────────────────────────────────────────
+    let mut rounding = use_signal(api::settings::RoundingDto::default);
+    let mut rounding_saving = use_signal(|| false);
+
+    // ── Forgotten timers ──────────────────────────────────────────────────────
+    let mut auto_stop = use_signal(api::settings::AutoStopPolicyDto::default);
+    let mut auto_stop_hours = use_signal(String::new);
+    let mut auto_stop_saving = use_signal(|| false);
+
+    // ── Period locks ──────────────────────────────────────────────────────────
+    let mut period_locks = use_signal(api::settings::PeriodLocksDto::default);
+    let mut lock_date = use_signal(String::new);
+    let mut locks_revision = use_signal(|| 0_u32);
+
     // Load both on mount — overwrites the context-seeded values with fresh data.
     use_resource(move || async move {
         match api::settings::get_user_settings().await {
@@ -152,6 +180,62 @@ pub fn Settings() -> Element {
         }
     });
 
+    use_resource(move || async move {
+        if let Ok(dto) = api::settings::get_budget_policy().await {
+            budget_thresholds.set(
+                dto.thresholds
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
+            block_over_budget.set(dto.block_over_budget);
+        }
+    });
+
+    use_resource(move || async move {
+        if let Ok(dto) = api::settings::get_time_entry_policy().await {
+            min_minutes.set(dto.min_duration.map(|s| (s / 60).to_string()).unwrap_or_default());
+            max_hours.set(dto.max_duration.map(|s| (s / 3600).to_string()).unwrap_or_default());
+            entry_policy.set(dto);
+        }
+    });
+
+    use_resource(move || async move {
+        if let Ok(dto) = api::settings::get_rounding().await {
+            rounding.set(dto);
+        }
+    });
+
+    use_resource(move || async move {
+        if let Ok(dto) = api::settings::get_auto_stop_policy().await {
+            auto_stop_hours.set(dto.max_duration.map(|s| (s / 3600).to_string()).unwrap_or_default());
+            auto_stop.set(dto);
+        }
+    });
+
+    use_resource(move || async move {
+        let _ = locks_revision();
+        if let Ok(dto) = api::settings::get_period_locks().await {
+            period_locks.set(dto);
+        }
+    });
+
+    let on_lock_period = move |_| async move {
+        let until = lock_date.peek().clone();
+        if until.is_empty() {
+            toasts.push_error("Pick the last day to lock");
+            return;
+        }
+        match api::settings::lock_period(None, until).await {
+            Ok(()) => {
+                locks_revision += 1;
+                toasts.push_success("Period locked");
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
+
     let on_save_user = move |_| async move {
         let timezone = user_timezone.peek().clone();
         let date_format = user_date_format.peek().clone();
@@ -198,6 +282,86 @@ pub fn Settings() -> Element {
         ws_saving.set(false);
     };
 
+    let on_save_budget = move |_| async move {
+        let parsed: Result<Vec<u8>, _> = budget_thresholds
+            .peek()
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(|t| t.trim_end_matches('%').parse::<u8>())
+            .collect();
+        let Ok(thresholds) = parsed else {
+            toasts.push_error("Thresholds must be whole percentages between 1 and 255");
+            return;
+        };
+        let block = *block_over_budget.peek();
+
+        budget_saving.set(true);
+        match api::settings::update_budget_policy(thresholds, block).await {
+            Ok(()) => toasts.push_success("Budget alerts saved"),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        budget_saving.set(false);
+    };
+
+    let on_save_policy = move |_| async move {
+        let min = min_minutes.peek().trim().to_string();
+        let max = max_hours.peek().trim().to_string();
+        let min_duration = if min.is_empty() { Ok(None) } else { min.parse::<i32>().map(|m| Some(m * 60)) };
+        let max_duration = if max.is_empty() { Ok(None) } else { max.parse::<i32>().map(|h| Some(h * 3600)) };
+        let (Ok(min_duration), Ok(max_duration)) = (min_duration, max_duration) else {
+            toasts.push_error("Durations must be whole numbers");
+            return;
+        };
+        let policy = api::settings::TimeEntryPolicyDto {
+            min_duration,
+            max_duration,
+            ..entry_policy.peek().clone()
+        };
+
+        policy_saving.set(true);
+        match api::settings::update_time_entry_policy(policy.clone()).await {
+            Ok(()) => {
+                entry_policy.set(policy);
+                toasts.push_success("Time entry rules saved");
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        policy_saving.set(false);
+    };
+
+    let on_save_rounding = move |_| async move {
+        rounding_saving.set(true);
+        match api::settings::update_rounding(rounding.peek().clone()).await {
+            Ok(()) => toasts.push_success("Rounding rules saved"),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        rounding_saving.set(false);
+    };
+
+    let on_save_auto_stop = move |_| async move {
+        let hours = auto_stop_hours.peek().trim().to_string();
+        let max_duration = if hours.is_empty() { Ok(None) } else { hours.parse::<i32>().map(|h| Some(h * 3600)) };
+        let Ok(max_duration) = max_duration else {
+            toasts.push_error("The maximum must be a whole number of hours");
+            return;
+        };
+        let policy = api::settings::AutoStopPolicyDto {
+            max_duration,
+            ..auto_stop.peek().clone()
+        };
+
+        auto_stop_saving.set(true);
+        match api::settings::update_auto_stop_policy(policy.clone()).await {
+            Ok(()) => {
+                auto_stop.set(policy);
+                toasts.push_success("Forgotten timer rules saved");
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        auto_stop_saving.set(false);
+    };
+
     rsx! {
         DefaultLayout {
             div { class: "space-y-6",
@@ -269,6 +433,10 @@ pub fn Settings() -> Element {
                             }
                         }
                     }
+
+                    ApiTokens {}
+
+                    ScheduledReports {}
                 }
 
                 // ── Workspace settings ────────────────────────────────────────
@@ -339,6 +507,306 @@ pub fn Settings() -> Element {
                             }
                         }
                     }
+
+                    Card { data_size: "md",
+                        CardHeader {
+                            CardTitle {
+                                div { class: "flex items-center gap-2",
+                                    Icon { icon: HiBell, width: 18, height: 18 }
+                                    "Budget Alerts"
+                                }
+                            }
+                        }
+                        CardContent {
+                            div { class: "space-y-4",
+                                div { class: "form-field",
+                                    label { class: "form-label", "Warning Thresholds (%)" }
+                                    Input {
+                                        placeholder: "80, 100",
+                                        value: budget_thresholds.read().clone(),
+                                        oninput: move |e: FormEvent| budget_thresholds.set(e.value()),
+                                    }
+                                }
+                                label { class: "flex items-center gap-2 text-sm",
+                                    input {
+                                        r#type: "checkbox",
+                                        class: "form-checkbox",
+                                        checked: *block_over_budget.read(),
+                                        oninput: move |_| { let v = *block_over_budget.peek(); block_over_budget.set(!v); },
+                                    }
+                                    "Block timers on over-budget projects"
+                                }
+                            }
+                        }
+                        CardFooter {
+                            Button {
+                                onclick: on_save_budget,
+                                disabled: *budget_saving.read(),
+                                Icon { icon: HiSave, width: 16, height: 16 }
+                                if *budget_saving.read() { "Saving…" } else { "Save Budget Alerts" }
+                            }
+                        }
+                    }
+
+                    Card { data_size: "md",
+                        CardHeader {
+                            CardTitle {
+                                div { class: "flex items-center gap-2",
+                                    Icon { icon: HiClock, width: 18, height: 18 }
+                                    "Time Entry Rules"
+                                }
+                            }
+                        }
+                        CardContent {
+                            div { class: "space-y-4",
+                                div { class: "grid grid-cols-1 gap-4 md:grid-cols-2",
+                                    div { class: "form-field",
+                                        label { class: "form-label", "Minimum Length (minutes)" }
+                                        Input {
+                                            placeholder: "No minimum",
+                                            value: min_minutes.read().clone(),
+                                            oninput: move |e: FormEvent| min_minutes.set(e.value()),
+                                        }
+                                    }
+                                    div { class: "form-field",
+                                        label { class: "form-label", "Maximum Length (hours)" }
+                                        Input {
+                                            placeholder: "No maximum",
+                                            value: max_hours.read().clone(),
+                                            oninput: move |e: FormEvent| max_hours.set(e.value()),
+                                        }
+                                    }
+                                }
+                                label { class: "flex items-center gap-2 text-sm",
+                                    input {
+                                        r#type: "checkbox",
+                                        class: "form-checkbox",
+                                        checked: entry_policy.read().disallow_overlaps,
+                                        oninput: move |_| entry_policy.write().disallow_overlaps ^= true,
+                                    }
+                                    "Disallow overlapping entries"
+                                }
+                                label { class: "flex items-center gap-2 text-sm",
+                                    input {
+                                        r#type: "checkbox",
+                                        class: "form-checkbox",
+                                        checked: entry_policy.read().disallow_future,
+                                        oninput: move |_| entry_policy.write().disallow_future ^= true,
+                                    }
+                                    "Disallow entries in the future"
+                                }
+                                label { class: "flex items-center gap-2 text-sm",
+                                    input {
+                                        r#type: "checkbox",
+                                        class: "form-checkbox",
+                                        checked: entry_policy.read().require_project,
+                                        oninput: move |_| entry_policy.write().require_project ^= true,
+                                    }
+                                    "Require a project when stopping"
+                                }
+                                label { class: "flex items-center gap-2 text-sm",
+                                    input {
+                                        r#type: "checkbox",
+                                        class: "form-checkbox",
+                                        checked: entry_policy.read().require_activity,
+                                        oninput: move |_| entry_policy.write().require_activity ^= true,
+                                    }
+                                    "Require an activity when stopping"
+                                }
+                                label { class: "flex items-center gap-2 text-sm",
+                                    input {
+                                        r#type: "checkbox",
+                                        class: "form-checkbox",
+                                        checked: entry_policy.read().require_description,
+                                        oninput: move |_| entry_policy.write().require_description ^= true,
+                                    }
+                                    "Require a description when stopping"
+                                }
+                            }
+                        }
+                        CardFooter {
+                            Button {
+                                onclick: on_save_policy,
+                                disabled: *policy_saving.read(),
+                                Icon { icon: HiSave, width: 16, height: 16 }
+                                if *policy_saving.read() { "Saving…" } else { "Save Time Entry Rules" }
+                            }
+                        }
+                    }
+
+                    Card { data_size: "md",
+                        CardHeader {
+                            CardTitle {
+                                div { class: "flex items-center gap-2",
+                                    Icon { icon: HiCalculator, width: 18, height: 18 }
+                                    "Rounding"
+                                }
+                            }
+                        }
+                        CardContent {
+                            div { class: "space-y-4",
+                                p { class: "text-sm text-secondary",
+                                    "Applies when entries are stopped or their times change. Customers and projects can override these rules."
+                                }
+                                RoundingFields { rounding }
+                            }
+                        }
+                        CardFooter {
+                            Button {
+                                onclick: on_save_rounding,
+                                disabled: *rounding_saving.read(),
+                                Icon { icon: HiSave, width: 16, height: 16 }
+                                if *rounding_saving.read() { "Saving…" } else { "Save Rounding Rules" }
+                            }
+                        }
+                    }
+
+                    Card { data_size: "md",
+                        CardHeader {
+                            CardTitle {
+                                div { class: "flex items-center gap-2",
+                                    Icon { icon: HiClock, width: 18, height: 18 }
+                                    "Forgotten Timers"
+                                }
+                            }
+                        }
+                        CardContent {
+                            div { class: "space-y-4",
+                                p { class: "text-sm text-secondary",
+                                    "Timers running longer than this, or past the daily stop time (UTC), are stopped automatically. The entry's history notes why."
+                                }
+                                div { class: "grid grid-cols-1 gap-4 md:grid-cols-2",
+                                    div { class: "form-field",
+                                        label { class: "form-label", "Stop After (hours)" }
+                                        Input {
+                                            placeholder: "Never",
+                                            value: auto_stop_hours.read().clone(),
+                                            oninput: move |e: FormEvent| auto_stop_hours.set(e.value()),
+                                        }
+                                    }
+                                    div { class: "form-field",
+                                        label { class: "form-label", "Daily Stop Time (UTC)" }
+                                        Input {
+                                            r#type: "time",
+                                            value: auto_stop.read().daily_stop_at.clone().unwrap_or_default(),
+                                            oninput: move |e: FormEvent| {
+                                                let v = e.value();
+                                                auto_stop.write().daily_stop_at = (!v.is_empty()).then_some(v);
+                                            },
+                                        }
+                                    }
+                                }
+                                div { class: "form-field",
+                                    label { class: "form-label", "Stopped Timers End" }
+                                    Select::<String> {
+                                        options: vec![
+                                            SelectOption::new("cutoff".to_string(), "When the limit was reached"),
+                                            SelectOption::new("last_activity".to_string(), "At the user's last activity before that"),
+                                        ],
+                                        value: Some(auto_stop.read().end.clone()),
+                                        on_change: move |v| auto_stop.write().end = v,
+                                    }
+                                }
+                                label { class: "flex items-center gap-2 text-sm",
+                                    input {
+                                        r#type: "checkbox",
+                                        class: "form-checkbox",
+                                        checked: auto_stop.read().notify,
+                                        oninput: move |_| auto_stop.write().notify ^= true,
+                                    }
+                                    "Email users when their timer was stopped"
+                                }
+                            }
+                        }
+                        CardFooter {
+                            Button {
+                                onclick: on_save_auto_stop,
+                                disabled: *auto_stop_saving.read(),
+                                Icon { icon: HiSave, width: 16, height: 16 }
+                                if *auto_stop_saving.read() { "Saving…" } else { "Save Forgotten Timer Rules" }
+                            }
+                        }
+                    }
+
+                    Card { data_size: "md",
+                        CardHeader {
+                            CardTitle {
+                                div { class: "flex items-center gap-2",
+                                    Icon { icon: HiLockClosed, width: 18, height: 18 }
+                                    "Period Locks"
+                                }
+                            }
+                        }
+                        CardContent {
+                            div { class: "space-y-4",
+                                p { class: "text-sm text-secondary",
+                                    match period_locks.read().until.clone() {
+                                        Some(until) => format!("Entries up to {until} are locked for everyone."),
+                                        None => "No workspace-wide lock.".to_string(),
+                                    }
+                                }
+                                div { class: "flex items-end gap-2",
+                                    div { class: "form-field",
+                                        label { class: "form-label", r#for: "lock-until", "Lock Up To" }
+                                        input {
+                                            id: "lock-until",
+                                            r#type: "date",
+                                            class: "input",
+                                            value: lock_date.read().clone(),
+                                            oninput: move |e: FormEvent| lock_date.set(e.value()),
+                                        }
+                                    }
+                                    Button { onclick: on_lock_period,
+                                        Icon { icon: HiLockClosed, width: 14, height: 14 }
+                                        "Lock"
+                                    }
+                                    if period_locks.read().until.is_some() {
+                                        Button {
+                                            onclick: move |_| async move {
+                                                match api::settings::unlock_period(None, None).await {
+                                                    Ok(()) => {
+                                                        locks_revision += 1;
+                                                        toasts.push_success("Lock lifted");
+                                                    }
+                                                    Err(e) => toasts.push_error(e.to_string()),
+                                                }
+                                            },
+                                            Icon { icon: HiLockOpen, width: 14, height: 14 }
+                                            "Lift Lock"
+                                        }
+                                    }
+                                }
+                                if !period_locks.read().users.is_empty() {
+                                    div { class: "space-y-2",
+                                        p { class: "form-label", "User Locks" }
+                                        for lock in period_locks.read().users.clone() {
+                                            div { key: "{lock.user_id}", class: "flex items-center justify-between text-sm",
+                                                span {
+                                                    {lock.user_name.clone().unwrap_or_else(|| lock.user_id.clone())}
+                                                    " — up to {lock.until}"
+                                                }
+                                                Button {
+                                                    onclick: move |_| {
+                                                        let user_id = lock.user_id.clone();
+                                                        async move {
+                                                            match api::settings::unlock_period(Some(user_id), None).await {
+                                                                Ok(()) => {
+                                                                    locks_revision += 1;
+                                                                    toasts.push_success("Lock lifted");
+                                                                }
+                                                                Err(e) => toasts.push_error(e.to_string()),
+                                                            }
+                                                        }
+                                                    },
+                                                    Icon { icon: HiLockOpen, width: 14, height: 14 }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -1,2 +1,8 @@
+mod api_tokens;
 mod component;
+mod reports;
+mod rounding;
+pub use api_tokens::ApiTokens;
 pub use component::{currency_options, timezone_options, Settings};
+pub use reports::ScheduledReports;
+pub use rounding::RoundingFields;
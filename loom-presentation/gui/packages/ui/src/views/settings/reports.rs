@@ -0,0 +1,206 @@
+use crate::components::atoms::card::{Card, CardContent, CardFooter, CardHeader, CardTitle};
+use crate::components::atoms::{
+    Button, ButtonVariant, Input, Select, SelectOption, ToastExt, Toasts,
+};
+use api::report::ReportScheduleInput;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::{HiMail, HiPaperAirplane, HiPlus, HiTrash};
+use dioxus_free_icons::Icon;
+
+fn options(values: &[(&str, &str)]) -> Vec<SelectOption<String>> {
+    values
+        .iter()
+        .map(|(val, label)| SelectOption::new(val.to_string(), *label))
+        .collect()
+}
+
+fn period_options() -> Vec<SelectOption<String>> {
+    options(&[
+        ("previous_day", "Previous day"),
+        ("previous_week", "Previous week"),
+        ("previous_month", "Previous month"),
+    ])
+}
+
+fn format_options() -> Vec<SelectOption<String>> {
+    options(&[("html", "HTML with CSV attachment"), ("csv", "CSV attachment only")])
+}
+
+fn scope_options() -> Vec<SelectOption<String>> {
+    options(&[("personal", "My entries"), ("workspace", "Everyone's entries")])
+}
+
+/// Hours and amounts per project, emailed on a cron schedule.  Reports of
+/// everyone's entries need the `report.manage` permission.
+#[component]
+pub fn ScheduledReports() -> Element {
+    let mut toasts: Toasts = use_context();
+    let mut name = use_signal(String::new);
+    let mut schedule = use_signal(|| "0 8 * * mon".to_string());
+    let mut period = use_signal(|| "previous_week".to_string());
+    let mut format = use_signal(|| "html".to_string());
+    let mut scope = use_signal(|| "personal".to_string());
+    let mut recipients = use_signal(String::new);
+    let mut revision = use_signal(|| 0_u32);
+
+    let reports = use_resource(move || async move {
+        let _ = revision();
+        api::report::list_report_schedules().await.unwrap_or_default()
+    });
+
+    let on_create = move |_| async move {
+        let input = ReportScheduleInput {
+            name: name.peek().trim().to_string(),
+            schedule: schedule.peek().trim().to_string(),
+            period: period.peek().clone(),
+            project_ids: vec![],
+            format: format.peek().clone(),
+            recipients: recipients
+                .peek()
+                .split([',', ';', ' '])
+                .map(str::trim)
+                .filter(|r| !r.is_empty())
+                .map(ToString::to_string)
+                .collect(),
+        };
+        match api::report::create_report_schedule(scope.peek().clone(), input).await {
+            Ok(_) => {
+                name.set(String::new());
+                recipients.set(String::new());
+                revision += 1;
+                toasts.push_success("Report scheduled");
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
+
+    rsx! {
+        Card { data_size: "md",
+            CardHeader {
+                CardTitle {
+                    div { class: "flex items-center gap-2",
+                        Icon { icon: HiMail, width: 18, height: 18 }
+                        "Scheduled Reports"
+                    }
+                }
+            }
+            CardContent {
+                div { class: "space-y-4",
+                    p { class: "text-sm text-secondary",
+                        "Hours and amounts per project, emailed to anyone on a schedule. Schedules are cron expressions in UTC, e.g. "
+                        code { "0 8 * * mon" }
+                        " for Monday mornings."
+                    }
+                    for report in reports.read().clone().unwrap_or_default() {
+                        div { key: "{report.id}", class: "flex items-center justify-between text-sm",
+                            span {
+                                strong { "{report.name}" }
+                                " — {report.schedule} to "
+                                {report.recipients.join(", ")}
+                                if let Some(next) = &report.next_run_at {
+                                    " — next {next}"
+                                }
+                                if let Some(error) = &report.last_error {
+                                    span { class: "text-destructive", " — failed: {error}" }
+                                } else if let Some(sent) = &report.last_sent_at {
+                                    " — last sent {sent}"
+                                }
+                            }
+                            div { class: "flex items-center gap-2",
+                                Button {
+                                    variant: ButtonVariant::Secondary,
+                                    onclick: {
+                                        let id = report.id.clone();
+                                        move |_| {
+                                            let id = id.clone();
+                                            async move {
+                                                match api::report::send_report_now(id).await {
+                                                    Ok(()) => toasts.push_success("Report sent"),
+                                                    Err(e) => toasts.push_error(e.to_string()),
+                                                }
+                                            }
+                                        }
+                                    },
+                                    Icon { icon: HiPaperAirplane, width: 14, height: 14 }
+                                }
+                                Button {
+                                    variant: ButtonVariant::Destructive,
+                                    onclick: move |_| {
+                                        let id = report.id.clone();
+                                        async move {
+                                            match api::report::remove_report_schedule(id).await {
+                                                Ok(()) => {
+                                                    revision += 1;
+                                                    toasts.push_success("Report removed");
+                                                }
+                                                Err(e) => toasts.push_error(e.to_string()),
+                                            }
+                                        }
+                                    },
+                                    Icon { icon: HiTrash, width: 14, height: 14 }
+                                }
+                            }
+                        }
+                    }
+                    div { class: "grid grid-cols-1 gap-4 md:grid-cols-2",
+                        div { class: "form-field",
+                            label { class: "form-label", "Report Name" }
+                            Input {
+                                placeholder: "Weekly hours",
+                                value: name.read().clone(),
+                                oninput: move |e: FormEvent| name.set(e.value()),
+                            }
+                        }
+                        div { class: "form-field",
+                            label { class: "form-label", "Schedule" }
+                            Input {
+                                value: schedule.read().clone(),
+                                oninput: move |e: FormEvent| schedule.set(e.value()),
+                            }
+                        }
+                    }
+                    div { class: "grid grid-cols-1 gap-4 md:grid-cols-3",
+                        div { class: "form-field",
+                            label { class: "form-label", "Period" }
+                            Select::<String> {
+                                options: period_options(),
+                                value: Some(period.read().clone()),
+                                on_change: move |v| period.set(v),
+                            }
+                        }
+                        div { class: "form-field",
+                            label { class: "form-label", "Covers" }
+                            Select::<String> {
+                                options: scope_options(),
+                                value: Some(scope.read().clone()),
+                                on_change: move |v| scope.set(v),
+                            }
+                        }
+                        div { class: "form-field",
+                            label { class: "form-label", "Format" }
+                            Select::<String> {
+                                options: format_options(),
+                                value: Some(format.read().clone()),
+                                on_change: move |v| format.set(v),
+                            }
+                        }
+                    }
+                    div { class: "form-field",
+                        label { class: "form-label", "Recipients" }
+                        Input {
+                            placeholder: "pm@example.com, lead@example.com",
+                            value: recipients.read().clone(),
+                            oninput: move |e: FormEvent| recipients.set(e.value()),
+                        }
+                    }
+                }
+            }
+            CardFooter {
+                Button { onclick: on_create,
+                    Icon { icon: HiPlus, width: 14, height: 14 }
+                    "Schedule Report"
+                }
+            }
+        }
+    }
+}
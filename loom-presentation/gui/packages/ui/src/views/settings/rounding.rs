@@ -0,0 +1,114 @@
+use crate::components::atoms::{Input, Select, SelectOption};
+use api::settings::{RoundingDto, RoundingRuleDto};
+use dioxus::prelude::*;
+
+fn mode_options() -> Vec<SelectOption<String>> {
+    [
+        ("none", "No rounding"),
+        ("closest", "Closest"),
+        ("up", "Up"),
+        ("down", "Down"),
+    ]
+    .into_iter()
+    .map(|(val, label)| SelectOption::new(val.to_string(), label))
+    .collect()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Part {
+    Begin,
+    End,
+    Duration,
+}
+
+impl Part {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Begin => "Round Begin",
+            Self::End => "Round End",
+            Self::Duration => "Round Duration",
+        }
+    }
+
+    fn get(self, rounding: &RoundingDto) -> Option<RoundingRuleDto> {
+        match self {
+            Self::Begin => rounding.begin.clone(),
+            Self::End => rounding.end.clone(),
+            Self::Duration => rounding.duration.clone(),
+        }
+    }
+
+    fn rule(self, rounding: &mut RoundingDto) -> &mut Option<RoundingRuleDto> {
+        match self {
+            Self::Begin => &mut rounding.begin,
+            Self::End => &mut rounding.end,
+            Self::Duration => &mut rounding.duration,
+        }
+    }
+}
+
+/// Editor for a set of rounding rules: direction and increment for begin,
+/// end and duration, and whether only the billed duration is rounded.
+#[component]
+pub fn RoundingFields(rounding: Signal<RoundingDto>) -> Element {
+    rsx! {
+        div { class: "space-y-4",
+            for part in [Part::Begin, Part::End, Part::Duration] {
+                RuleField { key: "{part.label()}", rounding, part }
+            }
+            label { class: "flex items-center gap-2 text-sm",
+                input {
+                    r#type: "checkbox",
+                    class: "form-checkbox",
+                    checked: rounding.read().billed_only,
+                    oninput: move |_| rounding.write().billed_only ^= true,
+                }
+                "Round the billed duration only and keep the real times"
+            }
+        }
+    }
+}
+
+#[component]
+fn RuleField(rounding: Signal<RoundingDto>, part: Part) -> Element {
+    let rule = part.get(&rounding.read());
+    let mode = rule.as_ref().map_or_else(|| "none".to_string(), |r| r.mode.clone());
+    let minutes = rule
+        .as_ref()
+        .filter(|r| r.minutes > 0)
+        .map(|r| r.minutes.to_string())
+        .unwrap_or_default();
+
+    rsx! {
+        div { class: "grid grid-cols-1 gap-4 md:grid-cols-2",
+            div { class: "form-field",
+                label { class: "form-label", "{part.label()}" }
+                Select::<String> {
+                    options: mode_options(),
+                    value: Some(mode),
+                    on_change: move |mode: String| {
+                        let mut dto = rounding.write();
+                        let rule = part.rule(&mut dto);
+                        let minutes = rule.as_ref().map_or(15, |r| r.minutes);
+                        *rule = (mode != "none").then_some(RoundingRuleDto { mode, minutes });
+                    },
+                }
+            }
+            if rule.is_some() {
+                div { class: "form-field",
+                    label { class: "form-label", "Increment (minutes)" }
+                    Input {
+                        placeholder: "15",
+                        value: minutes,
+                        oninput: move |e: FormEvent| {
+                            let minutes = e.value().trim().parse().unwrap_or(0);
+                            if let Some(rule) = part.rule(&mut rounding.write()) {
+                                rule.minutes = minutes;
+                            }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
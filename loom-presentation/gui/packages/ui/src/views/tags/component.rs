@@ -3,10 +3,12 @@ use crate::components::atoms::{
     Button, ColumnDef, DataTable, Input, TableCell, TableExpandRow, TableRow, ToastExt, Toasts,
 };
 use crate::layouts::DefaultLayout;
+use crate::live::{use_live, Topic};
+use crate::views::archive::{archive, ArchiveKind, ArchivedIsland};
 use crate::TagsCache;
 use api::tag::TagDto;
 use dioxus::prelude::*;
-use dioxus_free_icons::icons::hi_solid_icons::{HiPencil, HiPlus, HiSave, HiTag, HiX};
+use dioxus_free_icons::icons::hi_solid_icons::{HiArchive, HiPencil, HiPlus, HiSave, HiTag, HiX};
 use dioxus_free_icons::Icon;
 
 const PAGE_SIZE: usize = 20;
@@ -23,8 +25,11 @@ pub fn Tags() -> Element {
 
     let mut editing_id = use_signal(|| Option::<String>::None);
     let mut edit_name = use_signal(String::new);
+    let archived_rev = use_signal(|| 0_u32);
+    let live = use_live(&[Topic::Tags]);
 
     use_resource(move || async move {
+        let _ = live();
         match api::tag::list_tags().await {
             Ok(list) => tags.set(list),
             Err(e) => toasts.push_error(e.to_string()),
@@ -79,7 +84,7 @@ pub fn Tags() -> Element {
 
     let columns = vec![
         ColumnDef::new("Name"),
-        ColumnDef::new("").width("80px"),
+        ColumnDef::new("").width("120px"),
     ];
     let col_count = columns.len();
 
@@ -133,6 +138,7 @@ pub fn Tags() -> Element {
                             {
                                 let t = tag.clone();
                                 let tid = t.id.clone();
+                                let archive_id = t.id.clone();
                                 let is_editing = editing_id.read().as_deref() == Some(t.id.as_str());
 
                                 rsx! {
@@ -161,6 +167,17 @@ pub fn Tags() -> Element {
                                                     },
                                                     Icon { icon: HiPencil, width: 14, height: 14 }
                                                 }
+                                                Button {
+                                                    onclick: move |_| {
+                                                        let id = archive_id.clone();
+                                                        async move {
+                                                            if archive(ArchiveKind::Tag, id.clone(), archived_rev, toasts).await {
+                                                                tags.write().retain(|x| x.id != id);
+                                                            }
+                                                        }
+                                                    },
+                                                    Icon { icon: HiArchive, width: 14, height: 14 }
+                                                }
                                             }
                                         }
                                     }
@@ -194,6 +211,18 @@ pub fn Tags() -> Element {
                         }
                     }
                 }
+
+                ArchivedIsland {
+                    kind: ArchiveKind::Tag,
+                    revision: archived_rev,
+                    on_restored: move |_| {
+                        spawn(async move {
+                            if let Ok(list) = api::tag::list_tags().await {
+                                tags.set(list);
+                            }
+                        });
+                    },
+                }
             }
         }
     }
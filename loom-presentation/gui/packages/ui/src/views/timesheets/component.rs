@@ -1,4 +1,5 @@
 use crate::layouts::DefaultLayout;
+use crate::live::{use_live, Topic};
 use crate::views::timesheets::entry_table::EntryTable;
 use crate::views::timesheets::timer_card::TimerCard;
 use crate::{ActivitiesCache, ProjectsCache, TagsCache, TimesheetsCache};
@@ -11,6 +12,7 @@ pub fn Timesheets() -> Element {
     let activities_cache: ActivitiesCache = use_context();
     let tags_cache: TagsCache = use_context();
     let mut running: crate::RunningTimer = use_context();
+    let live = use_live(&[Topic::Timesheets, Topic::Projects, Topic::Activities, Topic::Tags]);
 
     let mut timesheets = use_signal(|| timesheets_cache.read().clone());
     let mut loading = use_signal(|| timesheets_cache.read().is_empty());
@@ -20,6 +22,7 @@ pub fn Timesheets() -> Element {
     let mut all_tags = use_signal(|| tags_cache.read().clone());
 
     use_resource(move || async move {
+        let _ = live();
         if let Ok(list) = api::timesheet::list_timesheets().await {
             timesheets.set(list);
         }
@@ -1,15 +1,17 @@
 use crate::components::atoms::{
-    Button, ColumnDef, DataTable, Input, Select, SelectOption, TableCell, TableExpandRow, TableRow,
+    Button, ButtonVariant, ColumnDef, DataTable, Input, Select, SelectOption, TableCell, TableExpandRow, TableRow,
     ToastExt, Toasts,
 };
 use crate::formatting;
+use crate::views::audit_log::HistoryPanel;
 use api::activity::ActivityDto;
 use api::project::ProjectDto;
 use api::tag::TagDto;
 use api::timesheet::TimesheetDto;
 use dioxus::prelude::*;
 use dioxus_free_icons::icons::hi_solid_icons::{
-    HiDownload, HiPencil, HiSave, HiTag, HiX,
+    HiClock, HiCollection, HiDownload, HiDuplicate, HiPencil, HiPlay, HiSave, HiScissors, HiTag,
+    HiTrash, HiX,
 };
 use dioxus_free_icons::Icon;
 
@@ -26,6 +28,7 @@ pub(super) struct EntryTableProps {
 #[component]
 pub(super) fn EntryTable(props: EntryTableProps) -> Element {
     let mut toasts: Toasts = use_context();
+    let mut running: crate::RunningTimer = use_context();
     let user_settings: crate::UserSettings = use_context();
     let workspace_settings: crate::WorkspaceSettings = use_context();
 
@@ -44,7 +47,42 @@ pub(super) fn EntryTable(props: EntryTableProps) -> Element {
     let mut edit_end_time = use_signal(|| Option::<String>::None);
 
     let mut tagging_id = use_signal(|| Option::<String>::None);
+    let mut history_id = use_signal(|| Option::<String>::None);
     let mut ts_tags = use_signal(Vec::<TagDto>::new);
+    let mut splitting_id = use_signal(|| Option::<String>::None);
+    let mut split_at = use_signal(String::new);
+    let mut confirm_delete_id = use_signal(|| Option::<String>::None);
+
+    let on_split = move |_| async move {
+        let id = match splitting_id.peek().clone() {
+            Some(id) => id,
+            None => return,
+        };
+        let at_local = split_at.peek().clone();
+        if at_local.is_empty() {
+            toasts.push_error("Pick the time to split at");
+            return;
+        }
+        let at = formatting::from_input(&at_local, &user_settings.peek().timezone);
+        match api::timesheet::split_timesheet(id.clone(), at).await {
+            Ok(piece) => {
+                let mut list = timesheets.write();
+                if let Some(item) = list.iter_mut().find(|x| x.id == id) {
+                    item.end_time = Some(piece.start_time.clone());
+                    item.duration = item.duration.map(|d| d - piece.duration.unwrap_or(0));
+                    item.rate = item
+                        .hourly_rate
+                        .zip(item.duration)
+                        .map(|(hr, d)| hr * i64::from(d) / 3600);
+                }
+                list.insert(0, piece);
+                drop(list);
+                splitting_id.set(None);
+                toasts.push_success("Timesheet split");
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
 
     let on_save_edit = move |_| async move {
         let id = match editing_id.peek().clone() {
@@ -135,7 +173,7 @@ pub(super) fn EntryTable(props: EntryTableProps) -> Element {
         ColumnDef::new("Start").width("160px"),
         ColumnDef::new("Duration").right().width("90px"),
         ColumnDef::new("Flags").width("100px"),
-        ColumnDef::new("").width("100px"),
+        ColumnDef::new("").width("240px"),
     ];
     let col_count = ts_columns.len();
 
@@ -159,17 +197,28 @@ pub(super) fn EntryTable(props: EntryTableProps) -> Element {
                         let tsid2 = t.id.clone();
                         let is_editing = editing_id.read().as_deref() == Some(t.id.as_str());
                         let is_tagging = tagging_id.read().as_deref() == Some(t.id.as_str());
+                        let is_history = history_id.read().as_deref() == Some(t.id.as_str());
+                        let is_splitting = splitting_id.read().as_deref() == Some(t.id.as_str());
+                        let confirming_delete = confirm_delete_id.read().as_deref() == Some(t.id.as_str());
+                        let merge_target = merge_candidate(&timesheets.read(), &t.id);
+                        let read_only = in_review(&t);
+                        let tsid_hist = t.id.clone();
                         let proj_name = t.project_id.as_ref()
                             .and_then(|pid| projects.read().iter().find(|p| &p.id == pid).map(|p| p.name.clone()))
                             .unwrap_or_else(|| "—".to_string());
                         let act_name = t.activity_id.as_ref()
                             .and_then(|aid| activities.read().iter().find(|a| &a.id == aid).map(|a| a.name.clone()))
                             .unwrap_or_else(|| "—".to_string());
-                        let duration_str = t.duration.map(|dur| {
+                        let format_duration = |dur: i32| {
                             let h = dur / 3600;
                             let m = (dur % 3600) / 60;
                             if h > 0 { format!("{h}h {m}m") } else { format!("{m}m") }
-                        });
+                        };
+                        let duration_str = t.duration.map(format_duration);
+                        let billed_str = t.billed_duration
+                            .filter(|billed| Some(*billed) != t.duration)
+                            .map(format_duration);
+                        let break_str = (t.break_duration > 0).then(|| format_duration(t.break_duration));
                         let date_str = {
                             let s = user_settings.read();
                             formatting::format_datetime(&t.start_time, &s.timezone, &s.date_format)
@@ -193,6 +242,12 @@ pub(super) fn EntryTable(props: EntryTableProps) -> Element {
                                     } else {
                                         span { class: "text-secondary", "—" }
                                     }
+                                    if let Some(ref b) = billed_str {
+                                        span { class: "block text-xs text-secondary", "billed {b}" }
+                                    }
+                                    if let Some(ref b) = break_str {
+                                        span { class: "block text-xs text-secondary", "break {b}" }
+                                    }
                                 }
                                 TableCell {
                                     div { class: "flex flex-col gap-0.5 text-xs",
@@ -207,17 +262,24 @@ pub(super) fn EntryTable(props: EntryTableProps) -> Element {
                                         if t.exported {
                                             span { class: "text-secondary", "Exported" }
                                         }
+                                        match t.approval_status.as_str() {
+                                            "submitted" => rsx! { span { class: "text-secondary", "Awaiting approval" } },
+                                            "approved" => rsx! { span { class: "text-success", "Approved" } },
+                                            "rejected" => rsx! { span { class: "text-warning", "Rejected" } },
+                                            _ => rsx! {},
+                                        }
                                     }
                                 }
                                 TableCell {
                                     div { class: "flex gap-1",
-                                        if is_editing || is_tagging {
+                                        if is_editing || is_tagging || is_history || is_splitting {
                                             Button {
-                                                onclick: move |_| { editing_id.set(None); tagging_id.set(None); },
+                                                onclick: move |_| { editing_id.set(None); tagging_id.set(None); history_id.set(None); splitting_id.set(None); },
                                                 Icon { icon: HiX, width: 14, height: 14 }
                                             }
                                         } else {
                                             Button {
+                                                disabled: read_only,
                                                 onclick: move |_| {
                                                     let edit_ts = timesheets.read()
                                                         .iter()
@@ -255,6 +317,14 @@ pub(super) fn EntryTable(props: EntryTableProps) -> Element {
                                                 },
                                                 Icon { icon: HiTag, width: 14, height: 14 }
                                             }
+                                            Button {
+                                                onclick: move |_| {
+                                                    history_id.set(Some(tsid_hist.clone()));
+                                                    editing_id.set(None);
+                                                    tagging_id.set(None);
+                                                },
+                                                Icon { icon: HiClock, width: 14, height: 14 }
+                                            }
                                             if !t.exported && t.end_time.is_some() {
                                                 {
                                                     let tsid_ex = t.id.clone();
@@ -279,6 +349,120 @@ pub(super) fn EntryTable(props: EntryTableProps) -> Element {
                                                     }
                                                 }
                                             }
+                                            if t.end_time.is_some() {
+                                                {
+                                                    let tsid_dup = t.id.clone();
+                                                    rsx! {
+                                                        Button {
+                                                            onclick: move |_| {
+                                                                let tsid_dup = tsid_dup.clone();
+                                                                async move {
+                                                                    match api::timesheet::duplicate_timesheet(tsid_dup, None).await {
+                                                                        Ok(copy) => {
+                                                                            timesheets.write().insert(0, copy);
+                                                                            toasts.push_success("Timesheet duplicated");
+                                                                        }
+                                                                        Err(e) => toasts.push_error(e.to_string()),
+                                                                    }
+                                                                }
+                                                            },
+                                                            Icon { icon: HiDuplicate, width: 14, height: 14 }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                            if t.end_time.is_some() && running.read().is_none() {
+                                                {
+                                                    let tsid_restart = t.id.clone();
+                                                    rsx! {
+                                                        Button {
+                                                            onclick: move |_| {
+                                                                let tsid_restart = tsid_restart.clone();
+                                                                async move {
+                                                                    match api::timesheet::restart_timesheet(tsid_restart).await {
+                                                                        Ok(started) => {
+                                                                            timesheets.write().insert(0, started.clone());
+                                                                            running.set(Some(started));
+                                                                            toasts.push_success("Timer restarted");
+                                                                        }
+                                                                        Err(e) => toasts.push_error(e.to_string()),
+                                                                    }
+                                                                }
+                                                            },
+                                                            Icon { icon: HiPlay, width: 14, height: 14 }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                            if !t.exported && !read_only && t.end_time.is_some() {
+                                                {
+                                                    let tsid_split = t.id.clone();
+                                                    let split_default = t.start_time.clone();
+                                                    rsx! {
+                                                        Button {
+                                                            onclick: move |_| {
+                                                                let tz = user_settings.peek().timezone.clone();
+                                                                split_at.set(formatting::to_input(&split_default, &tz));
+                                                                splitting_id.set(Some(tsid_split.clone()));
+                                                                editing_id.set(None);
+                                                                tagging_id.set(None);
+                                                            },
+                                                            Icon { icon: HiScissors, width: 14, height: 14 }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                            if let Some(other_id) = merge_target {
+                                                {
+                                                    let tsid_merge = t.id.clone();
+                                                    rsx! {
+                                                        Button {
+                                                            onclick: move |_| {
+                                                                let tsid_merge = tsid_merge.clone();
+                                                                let other_id = other_id.clone();
+                                                                async move {
+                                                                    match api::timesheet::merge_timesheets(other_id.clone(), tsid_merge.clone()).await {
+                                                                        Ok(()) => {
+                                                                            merge_locally(&mut timesheets.write(), &other_id, &tsid_merge);
+                                                                            toasts.push_success("Timesheets merged");
+                                                                        }
+                                                                        Err(e) => toasts.push_error(e.to_string()),
+                                                                    }
+                                                                }
+                                                            },
+                                                            Icon { icon: HiCollection, width: 14, height: 14 }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                            if !t.exported && !read_only {
+                                                {
+                                                    let tsid_del = t.id.clone();
+                                                    rsx! {
+                                                        Button {
+                                                            variant: if confirming_delete { ButtonVariant::Destructive } else { ButtonVariant::Secondary },
+                                                            onclick: move |_| {
+                                                                let tsid_del = tsid_del.clone();
+                                                                async move {
+                                                                    if !confirming_delete {
+                                                                        confirm_delete_id.set(Some(tsid_del));
+                                                                        return;
+                                                                    }
+                                                                    confirm_delete_id.set(None);
+                                                                    match api::timesheet::delete_timesheet(tsid_del.clone()).await {
+                                                                        Ok(()) => {
+                                                                            timesheets.write().retain(|x| x.id != tsid_del);
+                                                                            toasts.push_success("Timesheet deleted");
+                                                                        }
+                                                                        Err(e) => toasts.push_error(e.to_string()),
+                                                                    }
+                                                                }
+                                                            },
+                                                            Icon { icon: HiTrash, width: 14, height: 14 }
+                                                        }
+                                                    }
+                                                }
+                                            }
                                         }
                                     }
                                 }
@@ -367,6 +551,38 @@ pub(super) fn EntryTable(props: EntryTableProps) -> Element {
                                 }
                             }
 
+                            if is_splitting {
+                                TableExpandRow { col_count,
+                                    div { class: "flex items-end gap-2",
+                                        div { class: "form-field",
+                                            label { class: "form-label", r#for: "et-split", "Split at" }
+                                            input {
+                                                id: "et-split",
+                                                r#type: "datetime-local",
+                                                class: "input",
+                                                value: split_at.read().clone(),
+                                                oninput: move |e: FormEvent| split_at.set(e.value()),
+                                            }
+                                        }
+                                        Button { onclick: on_split,
+                                            Icon { icon: HiScissors, width: 14, height: 14 }
+                                            "Split"
+                                        }
+                                        Button {
+                                            onclick: move |_| splitting_id.set(None),
+                                            Icon { icon: HiX, width: 14, height: 14 }
+                                            "Cancel"
+                                        }
+                                    }
+                                }
+                            }
+
+                            if is_history {
+                                TableExpandRow { col_count,
+                                    HistoryPanel { entity_id: t.id.clone() }
+                                }
+                            }
+
                             if is_tagging {
                                 {
                                     let tsid_tag = t.id.clone();
@@ -420,3 +636,46 @@ pub(super) fn EntryTable(props: EntryTableProps) -> Element {
         }
     }
 }
+
+/// Entries awaiting approval or approved cannot be changed.
+fn in_review(t: &TimesheetDto) -> bool {
+    matches!(t.approval_status.as_str(), "submitted" | "approved")
+}
+
+/// The next older entry `id` can be merged with: stopped, not exported, not
+/// in review, and on the same project and activity.
+fn merge_candidate(list: &[TimesheetDto], id: &str) -> Option<String> {
+    let pos = list.iter().position(|x| x.id == id)?;
+    let (newer, older) = (&list[pos], list.get(pos + 1)?);
+    let mergeable = |t: &TimesheetDto| !t.exported && !in_review(t) && t.end_time.is_some();
+    (mergeable(newer)
+        && mergeable(older)
+        && newer.project_id.is_some()
+        && newer.project_id == older.project_id
+        && newer.activity_id == older.activity_id)
+        .then(|| older.id.clone())
+}
+
+/// Mirror a successful merge: the earlier entry now ends where the later one
+/// did, and the later one is gone.
+fn merge_locally(list: &mut Vec<TimesheetDto>, earlier_id: &str, later_id: &str) {
+    let Some(later) = list.iter().find(|x| x.id == later_id).cloned() else {
+        return;
+    };
+    if let Some(item) = list.iter_mut().find(|x| x.id == earlier_id) {
+        item.end_time = later.end_time.clone();
+        item.duration = later.end_time.as_deref().and_then(|end| {
+            let start = chrono::DateTime::parse_from_rfc3339(&item.start_time).ok()?;
+            let end = chrono::DateTime::parse_from_rfc3339(end).ok()?;
+            i32::try_from((end - start).num_seconds()).ok()
+        });
+        item.rate = item
+            .hourly_rate
+            .zip(item.duration)
+            .map(|(hr, d)| hr * i64::from(d) / 3600);
+        if item.description.as_deref().is_none_or(str::is_empty) {
+            item.description = later.description;
+        }
+    }
+    list.retain(|x| x.id != later_id);
+}
@@ -1,4 +1,6 @@
 mod component;
 mod entry_table;
+mod quick_start;
 mod timer_card;
 pub use component::Timesheets;
+pub use quick_start::QuickStart;
@@ -0,0 +1,181 @@
+use crate::components::atoms::{Button, ButtonVariant, ToastExt, Toasts};
+use api::activity::ActivityDto;
+use api::favorite::{CombinationDto, QuickStartDto};
+use api::project::ProjectDto;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::{HiPlay, HiStar, HiX};
+use dioxus_free_icons::Icon;
+
+#[derive(Clone, PartialEq, Props)]
+pub struct QuickStartProps {
+    pub projects: Signal<Vec<ProjectDto>>,
+    pub activities: Signal<Vec<ActivityDto>>,
+    pub billable: Signal<bool>,
+    pub on_timer_changed: EventHandler<()>,
+}
+
+/// Favorites plus recently and frequently used combinations, each startable
+/// with a single click.
+#[component]
+pub fn QuickStart(props: QuickStartProps) -> Element {
+    let mut running: crate::RunningTimer = use_context();
+    let mut toasts: Toasts = use_context();
+
+    let projects = props.projects;
+    let activities = props.activities;
+    let billable = props.billable;
+
+    let mut data = use_signal(QuickStartDto::default);
+
+    // Refresh whenever the running timer changes, so the entry that was just
+    // stopped shows up under "Recent".
+    use_resource(move || async move {
+        let _ = running.read().is_some();
+        if let Ok(d) = api::favorite::quick_start().await {
+            data.set(d);
+        }
+    });
+
+    let label = move |project_id: &str, activity_id: &str, description: &Option<String>| {
+        let project = projects
+            .read()
+            .iter()
+            .find(|p| p.id == project_id)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| "—".to_string());
+        let activity = activities
+            .read()
+            .iter()
+            .find(|a| a.id == activity_id)
+            .map(|a| a.name.clone())
+            .unwrap_or_else(|| "—".to_string());
+        match description {
+            Some(d) if !d.is_empty() => format!("{project} · {activity} · {d}"),
+            _ => format!("{project} · {activity}"),
+        }
+    };
+
+    let combination_row = move |title: &'static str, items: Vec<CombinationDto>| {
+        if items.is_empty() {
+            return rsx! {};
+        }
+        rsx! {
+            div { class: "space-y-2",
+                p { class: "text-xs font-medium uppercase text-muted-foreground", "{title}" }
+                div { class: "flex flex-wrap gap-2",
+                    for c in items {
+                        {
+                            let text = label(&c.project_id, &c.activity_id, &c.description);
+                            let source_id = c.last_timesheet_id.clone();
+                            let pin = c.clone();
+                            rsx! {
+                                div { key: "{title}-{c.last_timesheet_id}", class: "flex items-center gap-1",
+                                    Button {
+                                        variant: ButtonVariant::Outline,
+                                        onclick: move |_| {
+                                            let source_id = source_id.clone();
+                                            async move {
+                                                match api::timesheet::restart_timesheet(source_id).await {
+                                                    Ok(dto) => {
+                                                        running.set(Some(dto));
+                                                        props.on_timer_changed.call(());
+                                                    }
+                                                    Err(e) => toasts.push_error(e.to_string()),
+                                                }
+                                            }
+                                        },
+                                        Icon { icon: HiPlay, width: 14, height: 14 }
+                                        "{text}"
+                                    }
+                                    Button {
+                                        variant: ButtonVariant::Ghost,
+                                        onclick: move |_| {
+                                            let pin = pin.clone();
+                                            async move {
+                                                match api::favorite::pin_favorite(
+                                                    pin.project_id,
+                                                    pin.activity_id,
+                                                    pin.description,
+                                                    pin.tag_ids,
+                                                )
+                                                .await
+                                                {
+                                                    Ok(fav) => {
+                                                        data.write().favorites.push(fav);
+                                                        toasts.push_success("Added to favorites");
+                                                    }
+                                                    Err(e) => toasts.push_error(e.to_string()),
+                                                }
+                                            }
+                                        },
+                                        Icon { icon: HiStar, width: 14, height: 14 }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    let snapshot = data.read().clone();
+    if snapshot.favorites.is_empty() && snapshot.recent.is_empty() && snapshot.frequent.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "space-y-4",
+            if !snapshot.favorites.is_empty() {
+                div { class: "space-y-2",
+                    p { class: "text-xs font-medium uppercase text-muted-foreground", "Favorites" }
+                    div { class: "flex flex-wrap gap-2",
+                        for f in snapshot.favorites.clone() {
+                            {
+                                let text = label(&f.project_id, &f.activity_id, &f.description);
+                                let fid_start = f.id.clone();
+                                let fid_unpin = f.id.clone();
+                                rsx! {
+                                    div { key: "fav-{f.id}", class: "flex items-center gap-1",
+                                        Button {
+                                            onclick: move |_| {
+                                                let fid_start = fid_start.clone();
+                                                async move {
+                                                    let bill = *billable.peek();
+                                                    match api::favorite::start_favorite(fid_start, bill).await {
+                                                        Ok(dto) => {
+                                                            running.set(Some(dto));
+                                                            props.on_timer_changed.call(());
+                                                        }
+                                                        Err(e) => toasts.push_error(e.to_string()),
+                                                    }
+                                                }
+                                            },
+                                            Icon { icon: HiStar, width: 14, height: 14 }
+                                            "{text}"
+                                        }
+                                        Button {
+                                            variant: ButtonVariant::Ghost,
+                                            onclick: move |_| {
+                                                let fid_unpin = fid_unpin.clone();
+                                                async move {
+                                                    match api::favorite::unpin_favorite(fid_unpin.clone()).await {
+                                                        Ok(()) => data.write().favorites.retain(|x| x.id != fid_unpin),
+                                                        Err(e) => toasts.push_error(e.to_string()),
+                                                    }
+                                                }
+                                            },
+                                            Icon { icon: HiX, width: 14, height: 14 }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            {combination_row("Recent", snapshot.recent.clone())}
+            {combination_row("Frequent", snapshot.frequent.clone())}
+        }
+    }
+}
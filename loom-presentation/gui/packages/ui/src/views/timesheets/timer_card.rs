@@ -1,10 +1,13 @@
 use crate::components::atoms::card::{Card, CardContent, CardFooter, CardHeader, CardTitle};
-use crate::components::atoms::{Button, Input, Select, SelectOption, ToastExt, Toasts};
+use crate::components::atoms::{
+    Button, ButtonVariant, Input, Select, SelectOption, ToastExt, Toasts,
+};
 use crate::formatting;
+use crate::views::timesheets::quick_start::QuickStart;
 use api::activity::ActivityDto;
 use api::project::ProjectDto;
 use dioxus::prelude::*;
-use dioxus_free_icons::icons::hi_solid_icons::{HiClock, HiPlay, HiPlus, HiRefresh, HiStop};
+use dioxus_free_icons::icons::hi_solid_icons::{HiClock, HiPause, HiPlay, HiPlus, HiRefresh, HiStop};
 use dioxus_free_icons::Icon;
 
 #[derive(Clone, PartialEq, Props)]
@@ -132,6 +135,23 @@ pub(super) fn TimerCard(props: TimerCardProps) -> Element {
         }
     };
 
+    // Pause and resume refetch the timer so the break shows as recorded.
+    let on_pause_toggle = move |_| async move {
+        let Some(ts) = running.peek().clone() else { return };
+        let result = if ts.paused_at.is_some() {
+            api::timesheet::resume_timesheet(ts.id).await
+        } else {
+            api::timesheet::pause_timesheet(ts.id).await
+        };
+        if let Err(e) = result {
+            toasts.push_error(e.to_string());
+            return;
+        }
+        if let Ok(r) = api::timesheet::running_timesheet().await {
+            running.set(r);
+        }
+    };
+
     let on_create_manual = move |_| async move {
         let start_local = manual_start.peek().clone();
         let end_local = manual_end.peek().clone();
@@ -174,7 +194,7 @@ pub(super) fn TimerCard(props: TimerCardProps) -> Element {
                             CardTitle {
                                 div { class: "flex items-center gap-2",
                                     Icon { icon: HiClock, width: 18, height: 18 }
-                                    "Running Timer"
+                                    if ts.paused_at.is_some() { "Paused Timer" } else { "Running Timer" }
                                     span { class: "text-xs text-secondary font-normal ms-auto",
                                         {
                                             let s = user_settings.read();
@@ -233,6 +253,22 @@ pub(super) fn TimerCard(props: TimerCardProps) -> Element {
                                 Icon { icon: HiStop, width: 16, height: 16 }
                                 "Stop"
                             }
+                            if ts.paused_at.is_some() {
+                                Button { variant: ButtonVariant::Secondary, onclick: on_pause_toggle,
+                                    Icon { icon: HiPlay, width: 16, height: 16 }
+                                    "Resume"
+                                }
+                            } else {
+                                Button { variant: ButtonVariant::Secondary, onclick: on_pause_toggle,
+                                    Icon { icon: HiPause, width: 16, height: 16 }
+                                    "Pause"
+                                }
+                            }
+                            if ts.break_duration > 0 {
+                                span { class: "text-xs text-secondary",
+                                    "Breaks: {ts.break_duration / 3600}h {(ts.break_duration % 3600) / 60}m"
+                                }
+                            }
                         }
                     }
                 }
@@ -268,6 +304,14 @@ pub(super) fn TimerCard(props: TimerCardProps) -> Element {
                     }
 
                     if !*manual_mode.read() {
+                        CardContent {
+                            QuickStart {
+                                projects,
+                                activities,
+                                billable,
+                                on_timer_changed: props.on_timer_changed,
+                            }
+                        }
                         CardContent {
                             div { class: "grid grid-cols-1 gap-4 md:grid-cols-2",
                                 div { class: "form-field",
@@ -0,0 +1,354 @@
+use crate::components::atoms::{
+    Button, ButtonVariant, ColumnDef, DataTable, Input, TableCell, TableRow, ToastExt, Toasts,
+};
+use crate::layouts::DefaultLayout;
+use api::webhook::{WebhookDeliveryDto, WebhookDto};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::{
+    HiEye, HiKey, HiPencil, HiPlus, HiRefresh, HiSave, HiTrash, HiX,
+};
+use dioxus_free_icons::Icon;
+
+fn status_class(status: &str) -> &'static str {
+    match status {
+        "delivered" => "text-success",
+        "failed" => "text-warning",
+        _ => "text-secondary",
+    }
+}
+
+/// Outgoing webhooks of the workspace and their delivery log.
+#[component]
+pub fn Webhooks() -> Element {
+    let mut toasts: Toasts = use_context();
+
+    // `None` while the user may not manage webhooks.
+    let mut webhooks = use_signal(|| None::<Vec<WebhookDto>>);
+    let mut deliveries = use_signal(Vec::<WebhookDeliveryDto>::new);
+    let mut events = use_signal(Vec::<String>::new);
+    let mut revision = use_signal(|| 0_u32);
+    // Webhook whose deliveries are shown; all if `None`.
+    let mut log_filter = use_signal(|| None::<String>);
+    // Webhook whose secret is shown in clear.
+    let mut revealed = use_signal(|| None::<String>);
+
+    // Subscription form; changes the webhook being edited, if any.
+    let mut editing = use_signal(|| None::<String>);
+    let mut form_url = use_signal(String::new);
+    let mut form_events = use_signal(Vec::<String>::new);
+
+    use_resource(move || async move {
+        let _ = revision();
+        webhooks.set(api::webhook::list_webhooks().await.ok());
+        if events.peek().is_empty() {
+            if let Ok(list) = api::webhook::list_subscribable_events().await {
+                events.set(list);
+            }
+        }
+    });
+
+    use_resource(move || async move {
+        let _ = revision();
+        let filter = log_filter();
+        if webhooks.read().is_none() {
+            return;
+        }
+        match api::webhook::list_webhook_deliveries(filter).await {
+            Ok(list) => deliveries.set(list),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    });
+
+    let mut reset_form = move || {
+        editing.set(None);
+        form_url.set(String::new());
+        form_events.set(Vec::new());
+    };
+
+    let on_save = move |_| async move {
+        let url = form_url.peek().clone();
+        let event_types = form_events.peek().clone();
+        let result = match editing.peek().clone() {
+            Some(id) => api::webhook::change_webhook(id, url, event_types).await,
+            None => api::webhook::subscribe_webhook(url, event_types)
+                .await
+                .map(|w| revealed.set(Some(w.id))),
+        };
+        match result {
+            Ok(()) => {
+                reset_form();
+                revision += 1;
+                toasts.push_success("Webhook saved");
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
+
+    let webhook_url = move |webhook_id: &str| {
+        webhooks
+            .read()
+            .iter()
+            .flatten()
+            .find(|w| w.id == webhook_id)
+            .map(|w| w.url.clone())
+            .unwrap_or_else(|| "removed webhook".to_string())
+    };
+
+    let webhook_columns = vec![
+        ColumnDef::new("URL"),
+        ColumnDef::new("Events"),
+        ColumnDef::new("Secret").width("200px"),
+        ColumnDef::new("").width("200px"),
+    ];
+    let delivery_columns = vec![
+        ColumnDef::new("Queued").width("180px"),
+        ColumnDef::new("Event").width("200px"),
+        ColumnDef::new("Webhook"),
+        ColumnDef::new("Status").width("100px"),
+        ColumnDef::new("Attempts").width("90px").right(),
+        ColumnDef::new("Response").width("200px"),
+        ColumnDef::new("").width("60px"),
+    ];
+
+    let webhook_list = webhooks.read().clone();
+    let delivery_list = deliveries.read().clone();
+    let is_editing = editing.read().is_some();
+
+    rsx! {
+        DefaultLayout {
+            div { class: "space-y-6",
+                match webhook_list {
+                    None => rsx! {
+                        div { class: "island",
+                            p { class: "text-sm text-secondary", "You may not manage the webhooks of this workspace." }
+                        }
+                    },
+                    Some(list) => rsx! {
+                        div { class: "island",
+                            div { class: "island-header",
+                                span { class: "island-title",
+                                    if is_editing { "Change Webhook" } else { "New Webhook" }
+                                }
+                                span { class: "island-subtitle", "each request is signed in X-Loom-Signature with HMAC-SHA256 of the body" }
+                            }
+                            div { class: "space-y-4",
+                                div { class: "form-field",
+                                    label { class: "form-label", r#for: "webhook-url", "URL" }
+                                    Input {
+                                        id: "webhook-url",
+                                        placeholder: "https://example.com/hooks/loom",
+                                        value: form_url.read().clone(),
+                                        oninput: move |e: FormEvent| form_url.set(e.value()),
+                                    }
+                                }
+                                div { class: "form-field",
+                                    label { class: "form-label", "Events" }
+                                    div { class: "grid grid-cols-1 gap-2 md:grid-cols-4",
+                                        for event in events.read().clone() {
+                                            label { key: "{event}", class: "flex items-center gap-2 text-sm",
+                                                input {
+                                                    r#type: "checkbox",
+                                                    class: "form-checkbox",
+                                                    checked: form_events.read().contains(&event),
+                                                    oninput: {
+                                                        let event = event.clone();
+                                                        move |_| {
+                                                            let mut selected = form_events.write();
+                                                            if let Some(i) = selected.iter().position(|e| *e == event) {
+                                                                selected.remove(i);
+                                                            } else {
+                                                                selected.push(event.clone());
+                                                            }
+                                                        }
+                                                    },
+                                                }
+                                                "{event}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            div { class: "flex justify-end gap-2 mt-4",
+                                if is_editing {
+                                    Button {
+                                        variant: ButtonVariant::Ghost,
+                                        onclick: move |_| reset_form(),
+                                        Icon { icon: HiX, width: 14, height: 14 }
+                                        "Cancel"
+                                    }
+                                }
+                                Button { onclick: on_save,
+                                    if is_editing {
+                                        Icon { icon: HiSave, width: 14, height: 14 }
+                                        "Save Webhook"
+                                    } else {
+                                        Icon { icon: HiPlus, width: 14, height: 14 }
+                                        "Add Webhook"
+                                    }
+                                }
+                            }
+                        }
+
+                        div { class: "island",
+                            div { class: "island-header",
+                                span { class: "island-title", "Webhooks" }
+                            }
+                            DataTable {
+                                columns: webhook_columns,
+                                total: list.len(),
+                                page: 0,
+                                page_size: list.len().max(1),
+                                on_page_change: move |_| {},
+
+                                for w in list {
+                                    {
+                                        let id_edit = w.id.clone();
+                                        let id_reveal = w.id.clone();
+                                        let id_rotate = w.id.clone();
+                                        let id_remove = w.id.clone();
+                                        let id_log = w.id.clone();
+                                        let url_edit = w.url.clone();
+                                        let events_edit = w.event_types.clone();
+                                        let shown = revealed.read().as_deref() == Some(w.id.as_str());
+                                        rsx! {
+                                            TableRow { key: "{w.id}",
+                                                TableCell { mono: true, "{w.url}" }
+                                                TableCell { {w.event_types.join(", ")} }
+                                                TableCell { mono: true,
+                                                    if shown { "{w.secret}" } else { "••••••••" }
+                                                }
+                                                TableCell {
+                                                    div { class: "flex gap-1",
+                                                        Button {
+                                                            variant: ButtonVariant::Ghost,
+                                                            onclick: move |_| revealed.set((!shown).then(|| id_reveal.clone())),
+                                                            Icon { icon: HiEye, width: 14, height: 14 }
+                                                        }
+                                                        Button {
+                                                            variant: ButtonVariant::Ghost,
+                                                            onclick: move |_| {
+                                                                editing.set(Some(id_edit.clone()));
+                                                                form_url.set(url_edit.clone());
+                                                                form_events.set(events_edit.clone());
+                                                            },
+                                                            Icon { icon: HiPencil, width: 14, height: 14 }
+                                                        }
+                                                        Button {
+                                                            variant: ButtonVariant::Ghost,
+                                                            onclick: move |_| log_filter.set(Some(id_log.clone())),
+                                                            Icon { icon: HiRefresh, width: 14, height: 14 }
+                                                        }
+                                                        Button {
+                                                            variant: ButtonVariant::Ghost,
+                                                            onclick: move |_| {
+                                                                let id = id_rotate.clone();
+                                                                async move {
+                                                                    match api::webhook::rotate_webhook_secret(id.clone()).await {
+                                                                        Ok(_) => {
+                                                                            revealed.set(Some(id));
+                                                                            revision += 1;
+                                                                            toasts.push_success("Secret rotated");
+                                                                        }
+                                                                        Err(e) => toasts.push_error(e.to_string()),
+                                                                    }
+                                                                }
+                                                            },
+                                                            Icon { icon: HiKey, width: 14, height: 14 }
+                                                        }
+                                                        Button {
+                                                            variant: ButtonVariant::Destructive,
+                                                            onclick: move |_| {
+                                                                let id = id_remove.clone();
+                                                                async move {
+                                                                    match api::webhook::remove_webhook(id).await {
+                                                                        Ok(()) => revision += 1,
+                                                                        Err(e) => toasts.push_error(e.to_string()),
+                                                                    }
+                                                                }
+                                                            },
+                                                            Icon { icon: HiTrash, width: 14, height: 14 }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        div { class: "island",
+                            div { class: "island-header",
+                                span { class: "island-title", "Deliveries" }
+                                div { class: "flex items-center gap-2",
+                                    if let Some(id) = log_filter.read().clone() {
+                                        span { class: "island-subtitle", {webhook_url(&id)} }
+                                        Button {
+                                            variant: ButtonVariant::Ghost,
+                                            onclick: move |_| log_filter.set(None),
+                                            Icon { icon: HiX, width: 14, height: 14 }
+                                            "All Webhooks"
+                                        }
+                                    }
+                                    Button {
+                                        variant: ButtonVariant::Ghost,
+                                        onclick: move |_| revision += 1,
+                                        Icon { icon: HiRefresh, width: 14, height: 14 }
+                                    }
+                                }
+                            }
+                            DataTable {
+                                columns: delivery_columns,
+                                total: delivery_list.len(),
+                                page: 0,
+                                page_size: delivery_list.len().max(1),
+                                on_page_change: move |_| {},
+
+                                for d in delivery_list {
+                                    {
+                                        let id_redeliver = d.id;
+                                        let response = match (d.response_status, d.error.clone()) {
+                                            (Some(status), _) if d.status == "delivered" => format!("HTTP {status}"),
+                                            (_, Some(error)) => error,
+                                            _ => "—".to_string(),
+                                        };
+                                        rsx! {
+                                            TableRow { key: "{d.id}",
+                                                TableCell { mono: true, "{d.created_at}" }
+                                                TableCell { "{d.event_type}" }
+                                                TableCell { mono: true, {webhook_url(&d.webhook_id)} }
+                                                TableCell {
+                                                    span { class: status_class(&d.status), "{d.status}" }
+                                                }
+                                                TableCell { mono: true, "{d.attempts}" }
+                                                TableCell { "{response}" }
+                                                TableCell {
+                                                    if d.status != "pending" {
+                                                        Button {
+                                                            variant: ButtonVariant::Ghost,
+                                                            onclick: move |_| async move {
+                                                                match api::webhook::redeliver_webhook(id_redeliver).await {
+                                                                    Ok(()) => {
+                                                                        revision += 1;
+                                                                        toasts.push_success("Queued for redelivery");
+                                                                    }
+                                                                    Err(e) => toasts.push_error(e.to_string()),
+                                                                }
+                                                            },
+                                                            Icon { icon: HiRefresh, width: 14, height: 14 }
+                                                            "Redeliver"
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
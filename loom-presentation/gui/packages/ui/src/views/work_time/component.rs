@@ -0,0 +1,485 @@
+use crate::components::atoms::{
+    Button, ButtonVariant, ColumnDef, DataTable, Input, Select, SelectOption, TableCell, TableRow,
+    ToastExt, Toasts,
+};
+use crate::layouts::DefaultLayout;
+use crate::live::{use_live, Topic};
+use api::work_contract::{HolidayDto, OvertimeBalanceDto, WorkContractDto};
+use api::workspace::MemberDto;
+use chrono::{Datelike, Days, NaiveDate, Utc};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::hi_solid_icons::{HiPlus, HiRefresh, HiTrash};
+use dioxus_free_icons::Icon;
+
+const WEEKDAYS: [(u8, &str); 7] = [
+    (1, "Mon"),
+    (2, "Tue"),
+    (3, "Wed"),
+    (4, "Thu"),
+    (5, "Fri"),
+    (6, "Sat"),
+    (7, "Sun"),
+];
+
+/// Last day of the current month, `YYYY-MM-DD`.
+fn end_of_month() -> String {
+    let today = Utc::now().date_naive();
+    let (year, month) = if today.month() == 12 {
+        (today.year() + 1, 1)
+    } else {
+        (today.year(), today.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|first| first.checked_sub_days(Days::new(1)))
+        .unwrap_or(today)
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// Signed hours and minutes, e.g. `+2h 05m` or `-0h 30m`.
+fn fmt_balance(secs: i64) -> String {
+    let sign = if secs < 0 { "-" } else { "+" };
+    let secs = secs.abs();
+    format!("{sign}{}h {:02}m", secs / 3600, (secs % 3600) / 60)
+}
+
+fn fmt_hours(secs: i64) -> String {
+    format!("{}h {:02}m", secs / 3600, (secs.abs() % 3600) / 60)
+}
+
+fn fmt_weekdays(days: &[u8]) -> String {
+    WEEKDAYS
+        .iter()
+        .filter(|(d, _)| days.contains(d))
+        .map(|(_, label)| *label)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Work contracts, public holidays and the overtime balances they imply.
+#[component]
+pub fn WorkTime() -> Element {
+    let mut toasts: Toasts = use_context();
+
+    let mut mine = use_signal(|| None::<OvertimeBalanceDto>);
+    // `None` while the user may not manage contracts.
+    let mut contracts = use_signal(|| None::<Vec<WorkContractDto>>);
+    let mut report = use_signal(|| None::<Vec<OvertimeBalanceDto>>);
+    let mut holidays = use_signal(Vec::<HolidayDto>::new);
+    let mut members = use_signal(Vec::<MemberDto>::new);
+    let mut until = use_signal(end_of_month);
+    let mut revision = use_signal(|| 0_u32);
+    let live = use_live(&[Topic::WorkContracts, Topic::Holidays, Topic::Timesheets]);
+
+    // New contract form
+    let mut contract_user = use_signal(|| None::<String>);
+    let mut contract_hours = use_signal(|| "40".to_string());
+    let mut contract_days = use_signal(|| vec![1_u8, 2, 3, 4, 5]);
+    let mut contract_start = use_signal(|| Utc::now().format("%Y-%m-%d").to_string());
+    let mut contract_region = use_signal(String::new);
+
+    // New holiday form
+    let mut holiday_region = use_signal(String::new);
+    let mut holiday_date = use_signal(String::new);
+    let mut holiday_name = use_signal(String::new);
+
+    use_resource(move || async move {
+        let _ = revision();
+        let _ = live();
+        match api::work_contract::my_overtime_balance().await {
+            Ok(b) => mine.set(b),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+        if let Ok(list) = api::work_contract::list_holidays().await {
+            holidays.set(list);
+        }
+        contracts.set(api::work_contract::list_work_contracts().await.ok());
+        if contracts.peek().is_some() {
+            if let Ok(list) = api::workspace::list_members().await {
+                members.set(list);
+            }
+        }
+    });
+
+    use_resource(move || async move {
+        let _ = revision();
+        let _ = live();
+        let day = until();
+        if contracts.read().is_none() || day.is_empty() {
+            return;
+        }
+        match api::work_contract::overtime_balances(day).await {
+            Ok(list) => report.set(Some(list)),
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    });
+
+    let on_define = move |_| async move {
+        let Some(user_id) = contract_user.peek().clone() else {
+            toasts.push_error("Choose a member");
+            return;
+        };
+        let Ok(hours) = contract_hours.peek().trim().replace(',', ".").parse::<f64>() else {
+            toasts.push_error("Weekly hours must be a number");
+            return;
+        };
+        let region = contract_region.peek().trim().to_string();
+        match api::work_contract::define_work_contract(
+            user_id,
+            (hours * 60.0).round() as i32,
+            contract_days.peek().clone(),
+            contract_start.peek().clone(),
+            (!region.is_empty()).then_some(region),
+        )
+        .await
+        {
+            Ok(_) => {
+                revision += 1;
+                toasts.push_success("Contract saved");
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
+
+    let on_declare = move |_| async move {
+        match api::work_contract::declare_holiday(
+            holiday_region.peek().clone(),
+            holiday_date.peek().clone(),
+            holiday_name.peek().clone(),
+        )
+        .await
+        {
+            Ok(_) => {
+                holiday_date.set(String::new());
+                holiday_name.set(String::new());
+                revision += 1;
+                toasts.push_success("Holiday added");
+            }
+            Err(e) => toasts.push_error(e.to_string()),
+        }
+    };
+
+    let member_name = move |user_id: &str| {
+        members
+            .read()
+            .iter()
+            .find(|m| m.user_id == user_id)
+            .map(|m| m.name.clone())
+            .unwrap_or_else(|| user_id.to_string())
+    };
+
+    let report_columns = vec![
+        ColumnDef::new("Member"),
+        ColumnDef::new("Expected").width("140px").right(),
+        ColumnDef::new("Worked").width("140px").right(),
+        ColumnDef::new("Balance").width("140px").right(),
+    ];
+    let contract_columns = vec![
+        ColumnDef::new("Member"),
+        ColumnDef::new("Hours / week").width("120px").right(),
+        ColumnDef::new("Working days").width("200px"),
+        ColumnDef::new("From").width("120px"),
+        ColumnDef::new("Region").width("120px"),
+        ColumnDef::new("").width("60px"),
+    ];
+    let holiday_columns = vec![
+        ColumnDef::new("Date").width("140px"),
+        ColumnDef::new("Region").width("120px"),
+        ColumnDef::new("Name"),
+        ColumnDef::new("").width("60px"),
+    ];
+
+    let my_balance = mine.read().clone();
+    let report_list = report.read().clone();
+    let contract_list = contracts.read().clone();
+    let holiday_list = holidays.read().clone();
+    let may_manage = contract_list.is_some();
+
+    rsx! {
+        DefaultLayout {
+            div { class: "space-y-6",
+                div { class: "island",
+                    div { class: "island-header",
+                        span { class: "island-title", "My Balance" }
+                        if let Some(ref b) = my_balance {
+                            span { class: "island-subtitle", "until {b.until}" }
+                        }
+                    }
+                    match my_balance {
+                        Some(b) => rsx! {
+                            div { class: "grid grid-cols-2 gap-4 md:grid-cols-4",
+                                div { class: "flex flex-col gap-1",
+                                    span { class: "text-xs text-secondary", "Expected" }
+                                    span { class: "text-lg font-medium", "{fmt_hours(b.expected_seconds)}" }
+                                }
+                                div { class: "flex flex-col gap-1",
+                                    span { class: "text-xs text-secondary", "Worked" }
+                                    span { class: "text-lg font-medium", "{fmt_hours(b.worked_seconds)}" }
+                                }
+                                div { class: "flex flex-col gap-1",
+                                    span { class: "text-xs text-secondary", "Balance" }
+                                    span {
+                                        class: if b.balance_seconds < 0 { "text-lg font-medium text-warning" } else { "text-lg font-medium text-success" },
+                                        "{fmt_balance(b.balance_seconds)}"
+                                    }
+                                }
+                                if let Some(week) = b.weeks.last() {
+                                    div { class: "flex flex-col gap-1",
+                                        span { class: "text-xs text-secondary", "This week, since {week.week}" }
+                                        span { class: "text-lg font-medium",
+                                            "{fmt_balance(week.worked_seconds - week.expected_seconds)}"
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        None => rsx! {
+                            p { class: "text-sm text-secondary", "No work contract has been set up for you yet." }
+                        },
+                    }
+                }
+
+                if let Some(list) = report_list {
+                    div { class: "island",
+                        div { class: "island-header",
+                            span { class: "island-title", "Balances" }
+                            div { class: "flex items-center gap-2",
+                                label { class: "form-label", r#for: "overtime-until", "Until" }
+                                input {
+                                    id: "overtime-until",
+                                    r#type: "date",
+                                    class: "input",
+                                    value: until.read().clone(),
+                                    oninput: move |e: FormEvent| until.set(e.value()),
+                                }
+                                Button {
+                                    variant: ButtonVariant::Ghost,
+                                    onclick: move |_| revision += 1,
+                                    Icon { icon: HiRefresh, width: 14, height: 14 }
+                                }
+                            }
+                        }
+                        DataTable {
+                            columns: report_columns,
+                            total: list.len(),
+                            page: 0,
+                            page_size: list.len().max(1),
+                            on_page_change: move |_| {},
+
+                            for b in list {
+                                TableRow { key: "{b.user_id}",
+                                    TableCell { {b.user_name.clone().unwrap_or_else(|| b.user_id.clone())} }
+                                    TableCell { mono: true, "{fmt_hours(b.expected_seconds)}" }
+                                    TableCell { mono: true, "{fmt_hours(b.worked_seconds)}" }
+                                    TableCell { mono: true,
+                                        span { class: if b.balance_seconds < 0 { "text-warning" } else { "text-success" },
+                                            "{fmt_balance(b.balance_seconds)}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if let Some(list) = contract_list {
+                    div { class: "island",
+                        div { class: "island-header",
+                            span { class: "island-title", "Work Contracts" }
+                            span { class: "island-subtitle", "a later contract replaces an earlier one from its start date" }
+                        }
+                        div { class: "grid grid-cols-1 gap-4 md:grid-cols-3",
+                            div { class: "form-field",
+                                label { class: "form-label", "Member" }
+                                Select::<String> {
+                                    options: members.read().iter()
+                                        .map(|m| SelectOption::new(m.user_id.clone(), m.name.clone()))
+                                        .collect(),
+                                    value: contract_user.read().clone(),
+                                    on_change: move |id: String| contract_user.set(Some(id)),
+                                    placeholder: "Select member…".to_string(),
+                                }
+                            }
+                            div { class: "form-field",
+                                label { class: "form-label", r#for: "contract-hours", "Hours per week" }
+                                Input {
+                                    id: "contract-hours",
+                                    value: contract_hours.read().clone(),
+                                    oninput: move |e: FormEvent| contract_hours.set(e.value()),
+                                }
+                            }
+                            div { class: "form-field",
+                                label { class: "form-label", r#for: "contract-start", "Starts on" }
+                                input {
+                                    id: "contract-start",
+                                    r#type: "date",
+                                    class: "input",
+                                    value: contract_start.read().clone(),
+                                    oninput: move |e: FormEvent| contract_start.set(e.value()),
+                                }
+                            }
+                            div { class: "form-field md:col-span-2",
+                                label { class: "form-label", "Working days" }
+                                div { class: "flex flex-wrap gap-3",
+                                    for (day, day_name) in WEEKDAYS {
+                                        label { key: "{day}", class: "flex items-center gap-1 text-sm",
+                                            input {
+                                                r#type: "checkbox",
+                                                class: "form-checkbox",
+                                                checked: contract_days.read().contains(&day),
+                                                oninput: move |_| {
+                                                    let mut days = contract_days.write();
+                                                    if let Some(i) = days.iter().position(|d| *d == day) {
+                                                        days.remove(i);
+                                                    } else {
+                                                        days.push(day);
+                                                        days.sort_unstable();
+                                                    }
+                                                },
+                                            }
+                                            "{day_name}"
+                                        }
+                                    }
+                                }
+                            }
+                            div { class: "form-field",
+                                label { class: "form-label", r#for: "contract-region", "Holiday region" }
+                                Input {
+                                    id: "contract-region",
+                                    placeholder: "e.g. BY",
+                                    value: contract_region.read().clone(),
+                                    oninput: move |e: FormEvent| contract_region.set(e.value()),
+                                }
+                            }
+                        }
+                        div { class: "flex justify-end mt-4",
+                            Button { onclick: on_define,
+                                Icon { icon: HiPlus, width: 14, height: 14 }
+                                "Add Contract"
+                            }
+                        }
+                        DataTable {
+                            columns: contract_columns,
+                            total: list.len(),
+                            page: 0,
+                            page_size: list.len().max(1),
+                            on_page_change: move |_| {},
+
+                            for c in list {
+                                {
+                                    let id_remove = c.id.clone();
+                                    let hours = f64::from(c.weekly_minutes) / 60.0;
+                                    rsx! {
+                                        TableRow { key: "{c.id}",
+                                            TableCell { {member_name(&c.user_id)} }
+                                            TableCell { mono: true, "{hours:.1}" }
+                                            TableCell { {fmt_weekdays(&c.working_days)} }
+                                            TableCell { mono: true, "{c.starts_on}" }
+                                            TableCell { {c.region.clone().unwrap_or_else(|| "—".to_string())} }
+                                            TableCell {
+                                                Button {
+                                                    variant: ButtonVariant::Destructive,
+                                                    onclick: move |_| {
+                                                        let id = id_remove.clone();
+                                                        async move {
+                                                            match api::work_contract::remove_work_contract(id).await {
+                                                                Ok(()) => revision += 1,
+                                                                Err(e) => toasts.push_error(e.to_string()),
+                                                            }
+                                                        }
+                                                    },
+                                                    Icon { icon: HiTrash, width: 14, height: 14 }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "island",
+                    div { class: "island-header",
+                        span { class: "island-title", "Public Holidays" }
+                    }
+                    if may_manage {
+                        div { class: "grid grid-cols-1 gap-4 md:grid-cols-4",
+                            div { class: "form-field",
+                                label { class: "form-label", r#for: "holiday-region", "Region" }
+                                Input {
+                                    id: "holiday-region",
+                                    placeholder: "e.g. BY",
+                                    value: holiday_region.read().clone(),
+                                    oninput: move |e: FormEvent| holiday_region.set(e.value()),
+                                }
+                            }
+                            div { class: "form-field",
+                                label { class: "form-label", r#for: "holiday-date", "Date" }
+                                input {
+                                    id: "holiday-date",
+                                    r#type: "date",
+                                    class: "input",
+                                    value: holiday_date.read().clone(),
+                                    oninput: move |e: FormEvent| holiday_date.set(e.value()),
+                                }
+                            }
+                            div { class: "form-field",
+                                label { class: "form-label", r#for: "holiday-name", "Name" }
+                                Input {
+                                    id: "holiday-name",
+                                    placeholder: "e.g. New Year's Day",
+                                    value: holiday_name.read().clone(),
+                                    oninput: move |e: FormEvent| holiday_name.set(e.value()),
+                                }
+                            }
+                            div { class: "form-field flex items-end",
+                                Button { onclick: on_declare,
+                                    Icon { icon: HiPlus, width: 14, height: 14 }
+                                    "Add Holiday"
+                                }
+                            }
+                        }
+                    }
+                    DataTable {
+                        columns: holiday_columns,
+                        total: holiday_list.len(),
+                        page: 0,
+                        page_size: holiday_list.len().max(1),
+                        on_page_change: move |_| {},
+
+                        for h in holiday_list {
+                            {
+                                let id_remove = h.id.clone();
+                                rsx! {
+                                    TableRow { key: "{h.id}",
+                                        TableCell { mono: true, "{h.date}" }
+                                        TableCell { "{h.region}" }
+                                        TableCell { "{h.name}" }
+                                        TableCell {
+                                            if may_manage {
+                                                Button {
+                                                    variant: ButtonVariant::Destructive,
+                                                    onclick: move |_| {
+                                                        let id = id_remove.clone();
+                                                        async move {
+                                                            match api::work_contract::remove_holiday(id).await {
+                                                                Ok(()) => revision += 1,
+                                                                Err(e) => toasts.push_error(e.to_string()),
+                                                            }
+                                                        }
+                                                    },
+                                                    Icon { icon: HiTrash, width: 14, height: 14 }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -8,21 +8,16 @@ use ui::{
         atoms::{ToastMessage, ToastStack},
         organisms::{Header, Sidebar},
     },
+    guards::{AuthGuard, WorkspaceGuard},
     views::{
-        setup::Setup, Activities, Customers, Dashboard, Database, Login, Projects, SelectWorkspace,
-        Settings, Tags, Timesheets,
+        setup::Setup, Activities, Approvals, AuditLog, Calendar, Customers, Dashboard, Database,
+        Login, Projections, Projects, SelectWorkspace, Settings, Tags, Timesheets, Webhooks,
+        WorkTime,
     },
-    ActivitiesCache, CustomersCache, GlobalStyles, ProjectsCache, RunningElapsed, RunningTimer,
-    TagsCache, TimesheetsCache, UserSettings, WorkspaceSettings, FAVICON,
+    ActivitiesCache, AuthState, CustomersCache, GlobalStyles, ProjectsCache, RunningElapsed,
+    RunningTimer, TagsCache, TimesheetsCache, UserSettings, WorkspaceSettings, FAVICON,
 };
 
-/// Three-state auth signal shared across the whole app.
-///
-/// - `None`           → still checking (initial page load)
-/// - `Some(None)`     → confirmed not authenticated
-/// - `Some(Some(u))`  → confirmed authenticated as `u`
-pub type AuthState = Signal<Option<Option<UserInfo>>>;
-
 #[component]
 fn NotFound(route: Vec<String>) -> Element {
     rsx! {
@@ -68,15 +63,33 @@ enum Route {
                     #[route("/timesheets")]
                     Timesheets {},
 
+                    #[route("/approvals")]
+                    Approvals {},
+
+                    #[route("/work-time")]
+                    WorkTime {},
+
                     #[route("/tags")]
                     Tags {},
 
+                    #[route("/calendar")]
+                    Calendar {},
+
                     #[route("/settings")]
                     Settings {},
 
+                    #[route("/webhooks")]
+                    Webhooks {},
+
                     #[layout(RequireAdmin)]
                         #[route("/developer/database")]
                         Database {},
+
+                        #[route("/developer/projections")]
+                        Projections {},
+
+                        #[route("/audit-log")]
+                        AuditLog {},
                     #[end_layout]
                 #[end_layout]
             #[end_layout]
@@ -113,7 +126,9 @@ async fn main() {
         .with_same_site(tower_sessions::cookie::SameSite::Lax);
 
     let router = axum::Router::new()
+        .nest("/api/v1", api::rest::router())
         .serve_dioxus_application(dioxus_server::ServeConfig::new(), App)
+        .layer(axum::middleware::from_fn(audit_actor))
         .layer(session_layer);
 
     let listener = tokio::net::TcpListener::bind(address).await.unwrap();
@@ -122,6 +137,24 @@ async fn main() {
         .unwrap();
 }
 
+/// Records the session user as the actor of every event emitted while the
+/// request is handled, so the audit log can tell who changed what.  Callers
+/// of the REST API with a bearer token are recorded by its router.
+///
+/// Must run inside the session layer.
+#[cfg(feature = "server")]
+async fn audit_actor(
+    session: tower_sessions::Session,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let user: Option<UserInfo> = session.get("user").await.ok().flatten();
+    match user {
+        Some(user) => loom::audit::with_actor(user.id, next.run(request)).await,
+        None => next.run(request).await,
+    }
+}
+
 #[component]
 fn App() -> Element {
     // Global auth state — available to every component in the tree.
@@ -138,9 +171,15 @@ fn App() -> Element {
                 Route::Projects { .. } => 5,
                 Route::Activities { .. } => 6,
                 Route::Timesheets { .. } => 7,
-                Route::Tags { .. } => 8,
-                Route::Settings { .. } => 9,
-                Route::Database { .. } => 10,
+                Route::Approvals { .. } => 8,
+                Route::WorkTime { .. } => 9,
+                Route::Tags { .. } => 10,
+                Route::Calendar { .. } => 11,
+                Route::Settings { .. } => 12,
+                Route::Webhooks { .. } => 13,
+                Route::Database { .. } => 14,
+                Route::AuditLog { .. } => 15,
+                Route::Projections { .. } => 16,
                 _ => -1,
             }
         }
@@ -228,6 +267,9 @@ fn Layout() -> Element {
         })
     });
 
+    // Keep the timer and caches current when they change in another tab or app.
+    ui::live::use_live_updates();
+
     // Provide shared elapsed-seconds counter — updated by one coroutine, read everywhere.
     #[cfg(target_arch = "wasm32")]
     let mut elapsed: RunningElapsed = use_context_provider(|| Signal::new(0u64));
@@ -241,9 +283,15 @@ fn Layout() -> Element {
             loop {
                 if let Some(ref ts) = *running.read() {
                     let start_ms = js_sys::Date::parse(&ts.start_time);
-                    if !start_ms.is_nan() {
+                    // A paused timer stands still; breaks do not count.
+                    let now_ms = ts
+                        .paused_at
+                        .as_deref()
+                        .map_or_else(js_sys::Date::now, js_sys::Date::parse);
+                    if !start_ms.is_nan() && !now_ms.is_nan() {
+                        let breaks_ms = f64::from(ts.break_duration) * 1000.0;
                         #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-                        let secs = ((js_sys::Date::now() - start_ms) / 1000.0).max(0.0) as u64;
+                        let secs = ((now_ms - start_ms - breaks_ms) / 1000.0).max(0.0) as u64;
                         elapsed.set(secs);
                     }
                 } else {
@@ -297,9 +345,14 @@ fn Layout() -> Element {
         Route::Projects {} => "Projects",
         Route::Activities {} => "Activities",
         Route::Timesheets {} => "Timesheets",
+        Route::Approvals {} => "Approvals",
+        Route::WorkTime {} => "Work Time",
         Route::Tags {} => "Tags",
+        Route::Calendar {} => "Calendar",
         Route::Settings {} => "Settings",
-        Route::Database {} => "Developer",
+        Route::Webhooks {} => "Webhooks",
+        Route::Database {} | Route::Projections {} => "Developer",
+        Route::AuditLog {} => "Audit Log",
         Route::SelectWorkspace {} => "Workspaces",
         Route::Login {} | Route::Setup {} => "",
         Route::NotFound { .. } => "Not Found",
@@ -366,20 +419,12 @@ fn RequireSetupIncomplete() -> Element {
     }
 }
 
-/// Reads global auth state. Shows nothing while loading, redirects to /login
-/// when unauthenticated, and renders the outlet when authenticated.
+/// Shows nothing while loading, redirects to /login when unauthenticated,
+/// and renders the outlet when authenticated.
 #[component]
 fn RequireAuth() -> Element {
-    let nav = use_navigator();
-    let auth: AuthState = use_context();
-
-    match auth.cloned() {
-        None => rsx! {},
-        Some(None) => {
-            nav.replace(Route::Login {});
-            rsx! {}
-        }
-        Some(Some(_)) => rsx! { AnimatedOutlet::<Route> {} },
+    rsx! {
+        AuthGuard { AnimatedOutlet::<Route> {} }
     }
 }
 
@@ -387,20 +432,8 @@ fn RequireAuth() -> Element {
 /// chosen a workspace for this session.
 #[component]
 fn RequireWorkspace() -> Element {
-    let nav = use_navigator();
-    let auth: AuthState = use_context();
-
-    match auth.cloned() {
-        Some(Some(user)) if user.workspace_id.is_some() => {
-            rsx! { AnimatedOutlet::<Route> {} }
-        }
-        Some(Some(_)) => {
-            // Authenticated but no workspace selected yet.
-            nav.replace(Route::SelectWorkspace {});
-            rsx! {}
-        }
-        // Loading or unauthenticated — RequireAuth above handles these.
-        _ => rsx! {},
+    rsx! {
+        WorkspaceGuard { AnimatedOutlet::<Route> {} }
     }
 }
 
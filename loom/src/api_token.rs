@@ -0,0 +1,106 @@
+//! Personal API tokens for scripts and the `loom` CLI.
+//!
+//! A token is [`TOKEN_PREFIX`] followed by 64 hex characters and acts as its
+//! owner in the workspace it was issued in.  Only its SHA-256 hash is
+//! stored, so [`issue`] is the only place the token is ever seen.
+
+use anyhow::Result;
+use loom_core::shared::AggregateId;
+use loom_infrastructure_impl::{
+    Pool,
+    admin::{
+        api_token::repositories::{ApiTokenRepository, ApiTokenRow},
+        user::repositories::UserRepository,
+        workspace::repositories::WorkspaceRepository,
+    },
+};
+
+use crate::{
+    auth::CurrentUser,
+    secret_token::{self, hash},
+};
+
+/// Start of every token, so that leaked tokens are easy to spot.
+pub const TOKEN_PREFIX: &str = "loom_";
+
+/// The user and workspace a token acts for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenOwner {
+    pub user: CurrentUser,
+    pub workspace_id: String,
+}
+
+/// Issue a token for the user in `workspace_id`.  Returns the stored row and
+/// the token itself.
+///
+/// # Errors
+///
+/// Returns a validation error if `name` is blank.
+pub async fn issue(user_id: &str, workspace_id: &str, name: &str) -> Result<(ApiTokenRow, String)> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(crate::error::ValidationError::new("The token needs a name").into());
+    }
+
+    let token = secret_token::generate(TOKEN_PREFIX);
+    let repo = ApiTokenRepository::new(Pool::connect_admin().await?);
+    let row = repo
+        .insert(
+            &AggregateId::new().to_string(),
+            user_id,
+            workspace_id,
+            name,
+            &hash(&token),
+        )
+        .await?;
+    Ok((row, token))
+}
+
+/// The user's tokens in a workspace, newest first.
+pub async fn tokens(user_id: &str, workspace_id: &str) -> Result<Vec<ApiTokenRow>> {
+    let repo = ApiTokenRepository::new(Pool::connect_admin().await?);
+    Ok(repo.for_user(user_id, workspace_id).await?)
+}
+
+/// Revoke one of the user's tokens.  Returns `false` if the user has no
+/// active token with that id.
+pub async fn revoke(user_id: &str, token_id: &str) -> Result<bool> {
+    let repo = ApiTokenRepository::new(Pool::connect_admin().await?);
+    Ok(repo.revoke(token_id, user_id).await?)
+}
+
+/// The owner of an active token, or `None` if the token is unknown, revoked
+/// or its owner has left the workspace.  Records the use.
+pub async fn authenticate(token: &str) -> Result<Option<TokenOwner>> {
+    if !token.starts_with(TOKEN_PREFIX) {
+        return Ok(None);
+    }
+    let pool = Pool::connect_admin().await?;
+    let repo = ApiTokenRepository::new(pool.clone());
+    let Some(row) = repo.find_active(&hash(token)).await? else {
+        return Ok(None);
+    };
+
+    let is_member = WorkspaceRepository::from_pool(pool.clone())
+        .await?
+        .find_workspaces_for_user(&row.user_id)
+        .await?
+        .iter()
+        .any(|(id, _)| *id == row.workspace_id);
+    let user = UserRepository::from_pool(pool)
+        .await?
+        .find_view_by_id(&row.user_id)
+        .await?;
+    let Some(user) = user.filter(|_| is_member) else {
+        return Ok(None);
+    };
+
+    repo.touch(&row.id).await?;
+    Ok(Some(TokenOwner {
+        user: CurrentUser {
+            id: row.user_id,
+            email: user.get_email().to_string(),
+        },
+        workspace_id: row.workspace_id,
+    }))
+}
@@ -0,0 +1,101 @@
+//! Audit log controllers and actor tracking.
+//!
+//! Every event recorded by a controller is wrapped with [`envelope`], which
+//! attaches the acting user (set by the presentation layer via
+//! [`with_actor`]) as envelope metadata, together with the reason a
+//! background job gives via [`with_reason`].  The audit projector reads it back
+//! from the event store when it builds `projections__audit_log`.
+
+use std::{collections::HashMap, future::Future};
+
+use anyhow::Result;
+use eventually::message::{Envelope, Message};
+use loom_core::audit::{ACTOR_METADATA_KEY, REASON_METADATA_KEY};
+use loom_infrastructure_impl::{
+    Pool,
+    admin::user::repositories::UserRepository,
+    audit_log::repositories::{AuditLogFilter, AuditLogRepository, AuditLogRow},
+};
+
+/// Upper bound for a single page of audit entries.
+pub const MAX_PAGE_SIZE: u64 = 200;
+
+tokio::task_local! {
+    static ACTOR: String;
+    static REASON: String;
+}
+
+/// Run `future` with `actor` recorded as the author of every event it emits.
+pub async fn with_actor<F: Future>(actor: String, future: F) -> F::Output {
+    ACTOR.scope(actor, future).await
+}
+
+/// The user the current task acts on behalf of, if any.
+#[must_use]
+pub fn current_actor() -> Option<String> {
+    ACTOR.try_with(Clone::clone).ok()
+}
+
+/// Run `future` with `reason` recorded on every event it emits, explaining
+/// why a background job changed something on a user's behalf.
+pub async fn with_reason<F: Future>(reason: String, future: F) -> F::Output {
+    REASON.scope(reason, future).await
+}
+
+/// Wrap a domain event in an envelope carrying the current actor and reason.
+///
+/// Events emitted outside of [`with_actor`] (setup, background jobs) are
+/// recorded without an actor.
+pub fn envelope<T: Message>(event: T) -> Envelope<T> {
+    let mut envelope = Envelope::from(event);
+    if let Some(actor) = current_actor() {
+        envelope = envelope.with_metadata(ACTOR_METADATA_KEY.to_string(), actor);
+    }
+    if let Ok(reason) = REASON.try_with(Clone::clone) {
+        envelope = envelope.with_metadata(REASON_METADATA_KEY.to_string(), reason);
+    }
+    envelope
+}
+
+/// List audit entries of a workspace, newest first.
+pub async fn list(workspace_id: &str, mut filter: AuditLogFilter) -> Result<Vec<AuditLogRow>> {
+    filter.limit = filter.limit.clamp(1, MAX_PAGE_SIZE);
+    let pool = crate::tenant::tenant_pool(workspace_id).await?;
+    Ok(AuditLogRepository::new(pool).find(&filter).await?)
+}
+
+/// List audit entries of the admin database (users, workspaces, roles).
+pub async fn list_admin(mut filter: AuditLogFilter) -> Result<Vec<AuditLogRow>> {
+    filter.limit = filter.limit.clamp(1, MAX_PAGE_SIZE);
+    let pool = Pool::connect_admin().await?;
+    Ok(AuditLogRepository::new(pool).find(&filter).await?)
+}
+
+/// Full change history of a single entity (timesheet, customer, project, …).
+pub async fn history(workspace_id: &str, entity_id: &str) -> Result<Vec<AuditLogRow>> {
+    list(
+        workspace_id,
+        AuditLogFilter {
+            entity_id: Some(entity_id.to_string()),
+            limit: MAX_PAGE_SIZE,
+            ..AuditLogFilter::default()
+        },
+    )
+    .await
+}
+
+/// Resolve the actors of `rows` to user names; unknown IDs are left out.
+pub async fn actor_names(rows: &[AuditLogRow]) -> Result<HashMap<String, String>> {
+    let pool = Pool::connect_admin().await?;
+    let repo = UserRepository::from_pool(pool).await?;
+    let mut names = HashMap::new();
+    for actor in rows.iter().filter_map(|row| row.actor.as_deref()) {
+        if names.contains_key(actor) {
+            continue;
+        }
+        if let Some(user) = repo.find_view_by_id(actor).await? {
+            names.insert(actor.to_string(), user.get_name().to_string());
+        }
+    }
+    Ok(names)
+}
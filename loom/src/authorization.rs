@@ -54,6 +54,31 @@ impl AuthorizationService {
         Ok(count > 0)
     }
 
+    /// Returns `true` if the user holds the "admin" role **in the given
+    /// workspace**.  Unlike [`is_admin`], an admin of another workspace does
+    /// not count.
+    pub async fn is_admin_in(user_id: &str, workspace_id: &str) -> Result<bool> {
+        Self::is_admin_in_on(&Self::admin_pool().await?, user_id, workspace_id).await
+    }
+
+    /// Pool-injected version of [`is_admin_in`] — use this in tests.
+    pub async fn is_admin_in_on(pool: &AnyPool, user_id: &str, workspace_id: &str) -> Result<bool> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*)
+             FROM projections__workspace_user_roles wur
+             JOIN projections__workspace_roles wr
+               ON wur.workspace_role_id = wr.id
+             WHERE wur.user_id = $1
+               AND wur.workspace_id = $2
+               AND wr.name = 'admin'",
+        )
+        .bind(user_id)
+        .bind(workspace_id)
+        .fetch_one(pool)
+        .await?;
+        Ok(count > 0)
+    }
+
     // ── has_permission ────────────────────────────────────────────────────────
 
     /// Returns `true` if the user has the named permission **in the given
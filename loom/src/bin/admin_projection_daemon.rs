@@ -4,6 +4,7 @@ use anyhow::{Result, anyhow};
 use loom::infrastructure::{
     BackoffConfig, Pool, ProjectionDaemon, ProjectionRunner, ProjectionSource, SqlCheckpoint,
     admin::projectors::AdminProjector,
+    projection_health::{projectors::MonitoredProjector, repositories::ProjectionStatusRepository},
 };
 use loom_infrastructure_impl::ConnectedAdminPool;
 use tracing::warn;
@@ -54,11 +55,28 @@ async fn main() -> Result<()> {
     // tables, preventing FK race conditions between independent runners.
     daemon.register_with_config(
         ProjectionRunner::new(pool.clone().into_pool(), ProjectionSource::AllStreams),
-        AdminProjector::new(pool.clone()),
+        MonitoredProjector::new(
+            "admin_projection",
+            pool.clone(),
+            AdminProjector::new(pool.clone()),
+        )
+        .with_max_retries(loom::projection_health::max_retries()),
         SqlCheckpoint::new(pool.clone().into_pool(), "admin_projection").await?,
         backoff,
     );
 
+    let health_pool = pool.clone();
+    tokio::spawn(loom::projection_health::serve(
+        loom::projection_health::health_addr(
+            loom::projection_health::ADMIN_HEALTH_ADDR_VAR,
+            ([127, 0, 0, 1], 9101).into(),
+        ),
+        move || {
+            let pool = health_pool.clone();
+            async move { Ok(ProjectionStatusRepository::new(pool).all().await?) }
+        },
+    ));
+
     daemon.run_until_cancelled().await;
 
     Ok(())
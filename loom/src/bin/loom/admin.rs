@@ -0,0 +1,82 @@
+//! Commands that work on the databases directly, like the projection
+//! daemons, and read the same `DATABASE_*` environment.
+
+use anyhow::{Result, bail};
+use loom::{
+    infrastructure::{Pool, admin::workspace::repositories::WorkspaceRepository},
+    projection_health::{self, ProjectionDatabase},
+};
+use loom_infrastructure::query::Query;
+
+use crate::output::{Format, Table};
+
+/// Create the admin database, the first user and their workspace.
+pub async fn setup(
+    username: String,
+    email: String,
+    password: String,
+    workspace: String,
+) -> Result<()> {
+    loom::setup::init_admin_db().await?;
+    if loom::setup::is_setup_complete().await? {
+        bail!("Loom is already set up");
+    }
+    println!("Setting up workspace \"{workspace}\" for {email}…");
+    loom::setup::setup_application(username, email, password, workspace).await?;
+    println!("Done. Start the projection daemons, then sign in.");
+    Ok(())
+}
+
+/// Migrate the admin database and every workspace's database.
+pub async fn migrate() -> Result<()> {
+    let workspaces = loom::setup::migrate_all().await?;
+    println!("Migrated the admin database.");
+    for workspace_id in &workspaces {
+        println!("Migrated workspace {workspace_id}.");
+    }
+    Ok(())
+}
+
+/// Rebuild the projections of one workspace, or of the admin database and
+/// every workspace.
+pub async fn rebuild(workspace: Option<String>) -> Result<()> {
+    let databases = match workspace {
+        Some(workspace_id) => vec![ProjectionDatabase::Tenant(workspace_id)],
+        None => {
+            let workspaces = WorkspaceRepository::from_pool(Pool::connect_admin().await?)
+                .await?
+                .all()
+                .await?;
+            std::iter::once(ProjectionDatabase::Admin)
+                .chain(
+                    workspaces
+                        .iter()
+                        .map(|w| ProjectionDatabase::Tenant(w.get_id().to_string())),
+                )
+                .collect()
+        }
+    };
+    for database in &databases {
+        let runner = projection_health::runner_name(database);
+        let replayed = projection_health::rebuild(database).await?;
+        println!("Rebuilt {runner} from {replayed} events.");
+    }
+    Ok(())
+}
+
+/// Checkpoint, lag and errors of every projection runner.
+pub async fn projections(format: Format) -> Result<()> {
+    let statuses = projection_health::all_status().await?;
+    let mut table = Table::new(&["Runner", "Position", "Head", "Lag", "Parked", "Error"]);
+    for s in &statuses {
+        table.row(vec![
+            s.runner.clone(),
+            s.position.to_string(),
+            s.head_position.to_string(),
+            s.lag_events.to_string(),
+            s.parked.to_string(),
+            s.last_error.clone().unwrap_or_default(),
+        ]);
+    }
+    crate::output::print(format, &table, &statuses)
+}
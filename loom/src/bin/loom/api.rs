@@ -0,0 +1,208 @@
+//! The parts of the `/api/v1` REST API the CLI uses.
+//!
+//! The types mirror the JSON of the server's resources; only the fields the
+//! CLI shows or sends are declared.
+
+use anyhow::Result;
+use loom::remote::Remote;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::json;
+
+/// Largest page the server hands out.
+const PER_PAGE: usize = 200;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Timesheet {
+    pub id: String,
+    pub project_id: Option<String>,
+    pub activity_id: Option<String>,
+    /// RFC 3339.
+    pub start_time: String,
+    /// RFC 3339; `None` while the timer runs.
+    pub end_time: Option<String>,
+    /// Seconds, breaks excluded.
+    pub duration: Option<i32>,
+    pub description: Option<String>,
+    pub billable: bool,
+    /// Seconds.
+    pub break_duration: i32,
+    /// RFC 3339; set while a running timer is paused.
+    pub paused_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Project {
+    pub id: String,
+    pub customer_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Activity {
+    pub id: String,
+    /// `None` for global activities available in every project.
+    pub project_id: Option<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    pub scope: String,
+    pub name: String,
+    pub period: Option<String>,
+    pub time_budget: Option<i32>,
+    pub money_budget: Option<i64>,
+    pub used_seconds: i64,
+    pub used_amount: i64,
+    pub remaining_seconds: Option<i64>,
+    pub remaining_amount: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OvertimeBalance {
+    pub user_id: String,
+    pub user_name: Option<String>,
+    pub until: String,
+    pub expected_seconds: i64,
+    pub worked_seconds: i64,
+    pub balance_seconds: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IssuedApiToken {
+    pub id: String,
+    pub name: String,
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NewTimesheet {
+    pub project_id: Option<String>,
+    pub activity_id: Option<String>,
+    pub description: Option<String>,
+    pub billable: bool,
+}
+
+/// Fields left `None` are kept as they are.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TimesheetChanges {
+    pub description: Option<String>,
+    pub billable: Option<bool>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Page<T> {
+    data: Vec<T>,
+    total: usize,
+}
+
+pub struct Api {
+    remote: Remote,
+}
+
+impl Api {
+    pub const fn new(remote: Remote) -> Self {
+        Self { remote }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        Ok(serde_json::from_str(&self.remote.get(path).await?)?)
+    }
+
+    /// Every item of a paginated list.  `path` must not have a query yet.
+    async fn all<T: DeserializeOwned>(&self, path: &str, query: &str) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        for page in 1.. {
+            let batch: Page<T> = self
+                .get(&format!("{path}?page={page}&per_page={PER_PAGE}{query}"))
+                .await?;
+            let done = batch.data.is_empty();
+            items.extend(batch.data);
+            if done || items.len() >= batch.total {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    pub async fn projects(&self) -> Result<Vec<Project>> {
+        self.all("/api/v1/projects", "").await
+    }
+
+    pub async fn activities(&self) -> Result<Vec<Activity>> {
+        self.all("/api/v1/activities", "").await
+    }
+
+    /// The most recent entries, newest first.
+    pub async fn recent(&self) -> Result<Vec<Timesheet>> {
+        self.all("/api/v1/timesheets", "").await
+    }
+
+    /// Entries starting within `from..=to` (`YYYY-MM-DD`), oldest first.
+    pub async fn between(&self, from: &str, to: &str) -> Result<Vec<Timesheet>> {
+        self.all("/api/v1/timesheets", &format!("&from={from}&to={to}"))
+            .await
+    }
+
+    /// The running timer, if any.
+    pub async fn running(&self) -> Result<Option<Timesheet>> {
+        Ok(self
+            .recent()
+            .await?
+            .into_iter()
+            .find(|t| t.end_time.is_none()))
+    }
+
+    pub async fn timesheet(&self, id: &str) -> Result<Timesheet> {
+        self.get(&format!("/api/v1/timesheets/{id}")).await
+    }
+
+    pub async fn start(&self, input: &NewTimesheet) -> Result<Timesheet> {
+        let body = self
+            .remote
+            .post("/api/v1/timesheets", serde_json::to_string(input)?)
+            .await?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    pub async fn stop(&self, id: &str) -> Result<()> {
+        self.remote
+            .post(&format!("/api/v1/timesheets/{id}/stop"), String::new())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn update(&self, id: &str, changes: &TimesheetChanges) -> Result<()> {
+        self.remote
+            .put(
+                &format!("/api/v1/timesheets/{id}"),
+                serde_json::to_string(changes)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn budgets(&self) -> Result<Vec<BudgetUsage>> {
+        self.get("/api/v1/reports/budgets").await
+    }
+
+    pub async fn overtime(&self, until: &str) -> Result<Vec<OvertimeBalance>> {
+        self.get(&format!("/api/v1/reports/overtime?until={until}"))
+            .await
+    }
+
+    pub async fn create_token(&self, name: &str) -> Result<IssuedApiToken> {
+        let body = self
+            .remote
+            .post("/api/v1/tokens", json!({ "name": name }).to_string())
+            .await?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    pub async fn revoke_token(&self, id: &str) -> Result<()> {
+        self.remote.delete(&format!("/api/v1/tokens/{id}")).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+//! The server and API token remembered by `loom login`.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Overrides where the configuration is kept.
+const CONFIG_VAR: &str = "LOOM_CONFIG";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Config {
+    pub server: Option<String>,
+    pub token: Option<String>,
+    /// Set for tokens issued by `loom login`, so `loom logout` can revoke
+    /// them.
+    pub token_id: Option<String>,
+}
+
+/// `$LOOM_CONFIG`, else `cli.json` in `$XDG_CONFIG_HOME/loom` or
+/// `~/.config/loom`.
+fn path() -> Result<PathBuf> {
+    if let Some(path) = std::env::var_os(CONFIG_VAR) {
+        return Ok(PathBuf::from(path));
+    }
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .context("Cannot find the home directory; set LOOM_CONFIG")?;
+    Ok(base.join("loom").join("cli.json"))
+}
+
+impl Config {
+    /// The stored configuration, or an empty one before the first login.
+    pub fn load() -> Result<Self> {
+        let path = path()?;
+        match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("{} is not valid", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Store the configuration, readable by the current user only.
+    pub fn save(&self) -> Result<()> {
+        let path = path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(())
+    }
+
+    pub fn remove() -> Result<()> {
+        match std::fs::remove_file(path()?) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
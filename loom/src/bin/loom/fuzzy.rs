@@ -0,0 +1,113 @@
+//! Fuzzy matching of the project and activity names typed on the command
+//! line.
+
+use anyhow::{Result, bail};
+
+/// How well `query` matches `name`, higher is better; `None` if it does not
+/// match at all.  Case is ignored.  An exact match beats a prefix, which
+/// beats the start of a later word, then any substring, then the letters of
+/// `query` appearing in order.
+#[must_use]
+pub fn score(query: &str, name: &str) -> Option<u32> {
+    let query = query.trim().to_lowercase();
+    let name = name.to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    if name == query {
+        return Some(400);
+    }
+    if name.starts_with(&query) {
+        return Some(300);
+    }
+    if name
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.starts_with(&query))
+    {
+        return Some(250);
+    }
+    if name.contains(&query) {
+        return Some(200);
+    }
+    let mut letters = name.chars();
+    query
+        .chars()
+        .all(|q| letters.any(|n| n == q))
+        .then_some(100)
+}
+
+/// The single best match for `query` among `items`, which may also be
+/// named by their exact ID.
+///
+/// # Errors
+///
+/// Fails if nothing matches, or if several items match equally well.
+pub fn best<'a, T>(
+    kind: &str,
+    query: &str,
+    items: &'a [T],
+    id: impl Fn(&T) -> &str,
+    name: impl Fn(&T) -> &str,
+) -> Result<&'a T> {
+    if let Some(item) = items.iter().find(|item| id(item) == query.trim()) {
+        return Ok(item);
+    }
+    let scored: Vec<(u32, &T)> = items
+        .iter()
+        .filter_map(|item| score(query, name(item)).map(|s| (s, item)))
+        .collect();
+    let Some(top) = scored.iter().map(|(s, _)| *s).max() else {
+        bail!("No {kind} matches \"{query}\"");
+    };
+    let candidates: Vec<&T> = scored
+        .into_iter()
+        .filter(|(s, _)| *s == top)
+        .map(|(_, item)| item)
+        .collect();
+    match candidates.as_slice() {
+        [item] => Ok(item),
+        _ => {
+            let names: Vec<&str> = candidates.iter().map(|item| name(item)).collect();
+            bail!(
+                "\"{query}\" matches several {kind}s: {}; be more specific",
+                names.join(", ")
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pick<'a>(query: &str, names: &'a [&'a str]) -> Result<&'a str> {
+        best("project", query, names, |n| *n, |n| *n).map(|n| *n)
+    }
+
+    #[test]
+    fn ranks_exact_prefix_word_substring_and_letters() {
+        assert_eq!(score("Website", "website"), Some(400));
+        assert_eq!(score("web", "Website Relaunch"), Some(300));
+        assert_eq!(score("rel", "Website Relaunch"), Some(250));
+        assert_eq!(score("launch", "Website Relaunch"), Some(200));
+        assert_eq!(score("wsrl", "Website Relaunch"), Some(100));
+        assert_eq!(score("xyz", "Website Relaunch"), None);
+        assert_eq!(score("  ", "Website Relaunch"), None);
+    }
+
+    #[test]
+    fn picks_the_single_best_match() {
+        let names = ["Website", "Website Relaunch", "Intranet"];
+        assert_eq!(pick("website", &names).unwrap(), "Website");
+        assert_eq!(pick("relaunch", &names).unwrap(), "Website Relaunch");
+        assert_eq!(pick("intr", &names).unwrap(), "Intranet");
+    }
+
+    #[test]
+    fn refuses_ambiguous_and_unknown_names() {
+        let names = ["Website Relaunch", "Website Hosting", "Intranet"];
+        let ambiguous = pick("web", &names).unwrap_err().to_string();
+        assert!(ambiguous.contains("Website Relaunch, Website Hosting"));
+        assert!(pick("payroll", &names).is_err());
+    }
+}
@@ -0,0 +1,437 @@
+//! `loom`: track time from the terminal and administer a Loom installation.
+//!
+//! Time tracking talks to a Loom server through its REST API with a personal
+//! API token, which `loom login` issues and remembers.  The `admin` commands
+//! work on the databases directly and read the same environment as the
+//! server and the projection daemons.
+
+mod admin;
+mod api;
+mod config;
+mod fuzzy;
+mod output;
+mod track;
+
+use std::io::{BufRead, Write};
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use clap::{Args, Parser, Subcommand};
+use loom::remote::{Credentials, Remote, sign_in};
+
+use crate::{api::Api, config::Config, output::Format};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "loom",
+    version,
+    about = "Track time with Loom from the terminal"
+)]
+struct Cli {
+    /// Server URL, e.g. https://loom.example.com. Defaults to the one of
+    /// `loom login`.
+    #[arg(long, global = true, env = "LOOM_SERVER")]
+    server: Option<String>,
+    /// API token. Defaults to the one of `loom login`.
+    #[arg(long, global = true, env = "LOOM_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Args)]
+struct FormatArg {
+    /// How to print the result.
+    #[arg(long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+}
+
+#[derive(Debug, Args)]
+struct RangeArgs {
+    /// First day, YYYY-MM-DD. Defaults to this week's Monday.
+    #[arg(long)]
+    from: Option<NaiveDate>,
+    /// Last day, YYYY-MM-DD. Defaults to today.
+    #[arg(long)]
+    to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Sign in and remember a new API token for this computer, or remember
+    /// the token given with --token.
+    Login {
+        /// Email address to sign in with; the password is read from
+        /// LOOM_PASSWORD or prompted for.
+        #[arg(long)]
+        email: Option<String>,
+        /// Workspace to sign in to, if the account has several.
+        #[arg(long)]
+        workspace: Option<String>,
+    },
+    /// Revoke the token of `loom login` and forget it.
+    Logout,
+    #[command(flatten)]
+    Track(Track),
+    /// Administer the installation this computer runs.
+    Admin {
+        #[command(subcommand)]
+        admin: Admin,
+    },
+}
+
+/// Commands that work on the server of `loom login`.
+#[derive(Debug, Subcommand)]
+enum Track {
+    /// Start a timer, stopping the running one.
+    Start {
+        /// Project name or part of it.
+        project: Option<String>,
+        /// Activity name or part of it.
+        activity: Option<String>,
+        #[arg(short, long)]
+        description: Option<String>,
+        /// Track the time as not billable.
+        #[arg(long)]
+        non_billable: bool,
+    },
+    /// Stop the running timer.
+    Stop,
+    /// Show the running timer.
+    Status {
+        #[command(flatten)]
+        format: FormatArg,
+    },
+    /// List recent entries, or those of a date range.
+    Log {
+        #[command(flatten)]
+        range: RangeArgs,
+        /// Show at most this many entries.
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        #[command(flatten)]
+        format: FormatArg,
+    },
+    /// Change an entry, by default the latest one.
+    Edit {
+        /// Entry ID or its first characters, as shown by `loom log`.
+        id: Option<String>,
+        #[arg(short, long)]
+        description: Option<String>,
+        #[arg(long, conflicts_with = "not_billable")]
+        billable: bool,
+        #[arg(long)]
+        not_billable: bool,
+        /// New start: HH:MM on the entry's day, or YYYY-MM-DD HH:MM.
+        #[arg(long)]
+        start: Option<String>,
+        /// New end: HH:MM on the entry's day, or YYYY-MM-DD HH:MM.
+        #[arg(long)]
+        end: Option<String>,
+    },
+    /// List projects and their activities.
+    Projects {
+        #[command(flatten)]
+        format: FormatArg,
+    },
+    /// Budgets, overtime and tracked time.
+    Report {
+        #[command(subcommand)]
+        report: Report,
+    },
+    /// Export entries of a date range, as CSV unless asked otherwise.
+    Export {
+        #[command(flatten)]
+        range: RangeArgs,
+        #[arg(long, value_enum, default_value_t = Format::Csv)]
+        format: Format,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum Report {
+    /// Budget usage of customers and projects.
+    Budgets {
+        #[command(flatten)]
+        format: FormatArg,
+    },
+    /// Overtime balances.
+    Overtime {
+        /// Last day to include, YYYY-MM-DD. Defaults to today.
+        #[arg(long)]
+        until: Option<NaiveDate>,
+        #[command(flatten)]
+        format: FormatArg,
+    },
+    /// Your tracked time per project and activity.
+    Summary {
+        #[command(flatten)]
+        range: RangeArgs,
+        #[command(flatten)]
+        format: FormatArg,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum Admin {
+    /// Create the admin database, the first user and their workspace. The
+    /// user's password is read from LOOM_ADMIN_PASSWORD or prompted for.
+    Setup {
+        #[arg(long)]
+        username: String,
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        workspace: String,
+    },
+    /// Migrate the admin database and every workspace's database.
+    Migrate,
+    /// Rebuild read models from the event store. Stop the projection daemons
+    /// first.
+    Rebuild {
+        /// Only rebuild this workspace; by default the admin read models and
+        /// those of every workspace are rebuilt.
+        #[arg(long)]
+        workspace: Option<String>,
+    },
+    /// Show how far the projection daemons are.
+    Projections {
+        #[command(flatten)]
+        format: FormatArg,
+    },
+}
+
+/// The password from the environment variable `var`, else asked for on the
+/// terminal.
+fn read_password(var: &str) -> Result<String> {
+    if let Ok(password) = std::env::var(var) {
+        return Ok(password);
+    }
+    eprint!("Password: ");
+    std::io::stderr().flush()?;
+    // Hiding the input is best-effort: without `stty` the password echoes.
+    let hidden = std::process::Command::new("stty")
+        .arg("-echo")
+        .stdin(std::process::Stdio::inherit())
+        .status()
+        .is_ok_and(|s| s.success());
+    let mut password = String::new();
+    let read = std::io::stdin().lock().read_line(&mut password);
+    if hidden {
+        let _ = std::process::Command::new("stty")
+            .arg("echo")
+            .stdin(std::process::Stdio::inherit())
+            .status();
+        eprintln!();
+    }
+    read?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn prompt(label: &str) -> Result<String> {
+    eprint!("{label}: ");
+    std::io::stderr().flush()?;
+    let mut value = String::new();
+    std::io::stdin().lock().read_line(&mut value)?;
+    Ok(value.trim().to_string())
+}
+
+async fn login(
+    server: Option<String>,
+    email: Option<String>,
+    workspace: Option<String>,
+    token: Option<String>,
+) -> Result<()> {
+    let server = match server.or(Config::load()?.server) {
+        Some(server) => server,
+        None => prompt("Server")?,
+    };
+    let server = loom::remote::normalize_server(&server)?;
+
+    if let Some(token) = token {
+        let remote = Remote::new(&server, Credentials::Token(token.clone()))?;
+        Api::new(remote)
+            .projects()
+            .await
+            .context("The server does not accept the token")?;
+        Config {
+            server: Some(server.clone()),
+            token: Some(token),
+            token_id: None,
+        }
+        .save()?;
+        println!("Saved the token for {server}.");
+        return Ok(());
+    }
+
+    let email = match email {
+        Some(email) => email,
+        None => prompt("Email")?,
+    };
+    let password = read_password("LOOM_PASSWORD")?;
+    let (account, session) = sign_in(&server, &email, &password, workspace.as_deref()).await?;
+    let api = Api::new(Remote::new(&server, Credentials::Session(session))?);
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "this computer".to_string());
+    let issued = api.create_token(&format!("loom CLI on {host}")).await?;
+    Config {
+        server: Some(server.clone()),
+        token: Some(issued.token),
+        token_id: Some(issued.id),
+    }
+    .save()?;
+    println!("Signed in to {server} as {}.", account.email);
+    Ok(())
+}
+
+async fn logout(server: Option<String>) -> Result<()> {
+    let config = Config::load()?;
+    if let (Some(server), Some(token), Some(token_id)) = (
+        server.or(config.server),
+        config.token,
+        config.token_id.as_deref(),
+    ) {
+        let api = Api::new(Remote::new(&server, Credentials::Token(token))?);
+        if let Err(e) = api.revoke_token(token_id).await {
+            eprintln!("Could not revoke the token: {e:#}");
+        }
+    }
+    Config::remove()?;
+    println!("Signed out.");
+    Ok(())
+}
+
+/// The API of the given server and token, falling back to `loom login`'s.
+fn connect(server: Option<String>, token: Option<String>) -> Result<Api> {
+    let config = Config::load()?;
+    let server = server
+        .or(config.server)
+        .context("No server is set; run `loom login` or pass --server")?;
+    let token = token
+        .or(config.token)
+        .context("You are not signed in; run `loom login` or pass --token")?;
+    Ok(Api::new(Remote::new(&server, Credentials::Token(token))?))
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let Cli {
+        server,
+        token,
+        command,
+    } = cli;
+    match command {
+        Command::Login { email, workspace } => {
+            // An email asks for a new token even if LOOM_TOKEN is set.
+            let token = token.filter(|_| email.is_none());
+            login(server, email, workspace, token).await
+        }
+        Command::Logout => logout(server).await,
+        Command::Track(command) => track_time(&connect(server, token)?, command).await,
+        Command::Admin { admin } => match admin {
+            Admin::Setup {
+                username,
+                email,
+                workspace,
+            } => {
+                let password = read_password("LOOM_ADMIN_PASSWORD")?;
+                admin::setup(username, email, password, workspace).await
+            }
+            Admin::Migrate => admin::migrate().await,
+            Admin::Rebuild { workspace } => admin::rebuild(workspace).await,
+            Admin::Projections { format } => admin::projections(format.format).await,
+        },
+    }
+}
+
+async fn track_time(api: &Api, command: Track) -> Result<()> {
+    match command {
+        Track::Start {
+            project,
+            activity,
+            description,
+            non_billable,
+        } => track::start(api, project, activity, description, !non_billable).await,
+        Track::Stop => track::stop(api).await,
+        Track::Status { format } => track::status(api, format.format).await,
+        Track::Log {
+            range,
+            limit,
+            format,
+        } => track::log(api, range.from, range.to, limit, format.format).await,
+        Track::Edit {
+            id,
+            description,
+            billable,
+            not_billable,
+            start,
+            end,
+        } => {
+            let billable = (billable || not_billable).then_some(billable);
+            track::edit(
+                api,
+                track::Edit {
+                    id,
+                    description,
+                    billable,
+                    start,
+                    end,
+                },
+            )
+            .await
+        }
+        Track::Projects { format } => track::projects(api, format.format).await,
+        Track::Report { report } => match report {
+            Report::Budgets { format } => track::budgets(api, format.format).await,
+            Report::Overtime { until, format } => track::overtime(api, until, format.format).await,
+            Report::Summary { range, format } => {
+                track::summary(api, range.from, range.to, format.format).await
+            }
+        },
+        Track::Export { range, format } => track::export(api, range.from, range.to, format).await,
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = run(Cli::parse()).await {
+        eprintln!("Error: {e:#}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn cli_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_start_with_fuzzy_names() {
+        let cli = Cli::try_parse_from(["loom", "start", "web", "dev", "-d", "Login page"]).unwrap();
+        let Command::Track(Track::Start {
+            project,
+            activity,
+            description,
+            non_billable,
+        }) = cli.command
+        else {
+            panic!("expected start");
+        };
+        assert_eq!(project.as_deref(), Some("web"));
+        assert_eq!(activity.as_deref(), Some("dev"));
+        assert_eq!(description.as_deref(), Some("Login page"));
+        assert!(!non_billable);
+    }
+
+    #[test]
+    fn admin_password_is_not_an_argument() {
+        let setup = "loom admin setup --username ada --email ada@example.com --workspace Maths";
+        assert!(Cli::try_parse_from(setup.split(' ')).is_ok());
+        let with_password = format!("{setup} --password secret");
+        assert!(Cli::try_parse_from(with_password.split(' ')).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+//! Printing results as an aligned table, CSV or JSON.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    #[default]
+    Table,
+    Csv,
+    Json,
+}
+
+/// Rows of text cells under a header.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    #[must_use]
+    pub fn new(headers: &[&'static str]) -> Self {
+        Self {
+            headers: headers.to_vec(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    /// Columns padded to their widest cell, separated by two spaces.
+    #[must_use]
+    pub fn render_table(&self) -> String {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let mut out = String::new();
+        let header: Vec<String> = self.headers.iter().map(ToString::to_string).collect();
+        for row in std::iter::once(&header).chain(&self.rows) {
+            let line: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect();
+            let _ = writeln!(out, "{}", line.join("  ").trim_end());
+        }
+        out
+    }
+
+    /// RFC 4180 CSV with a header line.
+    #[must_use]
+    pub fn render_csv(&self) -> String {
+        let mut out = String::new();
+        let header: Vec<String> = self.headers.iter().map(ToString::to_string).collect();
+        for row in std::iter::once(&header).chain(&self.rows) {
+            let line: Vec<String> = row.iter().map(|cell| csv_cell(cell)).collect();
+            let _ = writeln!(out, "{}", line.join(","));
+        }
+        out
+    }
+}
+
+fn csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// Print `table`, or `value` as pretty JSON.
+pub fn print<T: Serialize + ?Sized>(format: Format, table: &Table, value: &T) -> Result<()> {
+    match format {
+        Format::Table => print!("{}", table.render_table()),
+        Format::Csv => print!("{}", table.render_csv()),
+        Format::Json => println!("{}", serde_json::to_string_pretty(value)?),
+    }
+    Ok(())
+}
+
+/// `h:mm`, with a leading `-` for negative durations.
+#[must_use]
+pub fn hours_minutes(seconds: i64) -> String {
+    let sign = if seconds < 0 { "-" } else { "" };
+    let minutes = seconds.abs() / 60;
+    format!("{sign}{}:{:02}", minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Table {
+        let mut table = Table::new(&["Project", "Hours"]);
+        table.row(vec!["Website".into(), "1:30".into()]);
+        table.row(vec!["Intranet, internal".into(), "12:05".into()]);
+        table
+    }
+
+    #[test]
+    fn aligns_table_columns() {
+        assert_eq!(
+            sample().render_table(),
+            "Project             Hours\n\
+             Website             1:30\n\
+             Intranet, internal  12:05\n"
+        );
+    }
+
+    #[test]
+    fn quotes_csv_cells_that_need_it() {
+        let mut table = sample();
+        table.row(vec!["Say \"hi\"".into(), "0:01".into()]);
+        assert_eq!(
+            table.render_csv(),
+            "Project,Hours\nWebsite,1:30\n\"Intranet, internal\",12:05\n\"Say \"\"hi\"\"\",0:01\n"
+        );
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(hours_minutes(0), "0:00");
+        assert_eq!(hours_minutes(5_400), "1:30");
+        assert_eq!(hours_minutes(-3_660), "-1:01");
+    }
+}
@@ -0,0 +1,512 @@
+//! Time tracking against the server: the running timer, recent entries,
+//! reports and exports.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result, bail};
+use chrono::{
+    DateTime, Datelike, Days, Local, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, TimeZone,
+    Utc,
+};
+use serde::Serialize;
+
+use crate::{
+    api::{Activity, Api, NewTimesheet, Project, Timesheet, TimesheetChanges},
+    fuzzy,
+    output::{self, Format, Table, hours_minutes},
+};
+
+/// Project and activity names by ID, for showing entries.
+struct Names {
+    projects: Vec<Project>,
+    activities: Vec<Activity>,
+}
+
+impl Names {
+    async fn load(api: &Api) -> Result<Self> {
+        Ok(Self {
+            projects: api.projects().await?,
+            activities: api.activities().await?,
+        })
+    }
+
+    fn project(&self, id: Option<&str>) -> String {
+        id.and_then(|id| self.projects.iter().find(|p| p.id == id))
+            .map(|p| p.name.clone())
+            .unwrap_or_default()
+    }
+
+    fn activity(&self, id: Option<&str>) -> String {
+        id.and_then(|id| self.activities.iter().find(|a| a.id == id))
+            .map(|a| a.name.clone())
+            .unwrap_or_default()
+    }
+
+    /// `Project / Activity — description`, leaving out what is not set.
+    fn describe(&self, t: &Timesheet) -> String {
+        let mut parts: Vec<String> = [
+            self.project(t.project_id.as_deref()),
+            self.activity(t.activity_id.as_deref()),
+        ]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect();
+        if parts.is_empty() {
+            parts.push("(no project)".to_string());
+        }
+        let mut text = parts.join(" / ");
+        if let Some(description) = t.description.as_deref().filter(|d| !d.is_empty()) {
+            text.push_str(" — ");
+            text.push_str(description);
+        }
+        text
+    }
+}
+
+fn parse_rfc3339(value: &str) -> Result<DateTime<Local>> {
+    Ok(DateTime::parse_from_rfc3339(value)
+        .with_context(|| format!("{value} is not a valid time"))?
+        .with_timezone(&Local))
+}
+
+fn local_date(value: &str) -> String {
+    parse_rfc3339(value).map_or_else(|_| value.to_string(), |t| t.format("%Y-%m-%d").to_string())
+}
+
+fn local_time(value: &str) -> String {
+    parse_rfc3339(value).map_or_else(|_| value.to_string(), |t| t.format("%H:%M").to_string())
+}
+
+/// Tracked seconds of an entry; for a running timer up to now or the pause.
+fn tracked_seconds(t: &Timesheet) -> i64 {
+    if let Some(duration) = t.duration {
+        return i64::from(duration);
+    }
+    let Ok(start) = parse_rfc3339(&t.start_time) else {
+        return 0;
+    };
+    let until = t
+        .paused_at
+        .as_deref()
+        .and_then(|p| parse_rfc3339(p).ok())
+        .unwrap_or_else(Local::now);
+    ((until - start).num_seconds() - i64::from(t.break_duration)).max(0)
+}
+
+/// A time given on the command line: `HH:MM` on `day`, `YYYY-MM-DD HH:MM`,
+/// or RFC 3339.  Returned as RFC 3339 in UTC.
+fn parse_time(value: &str, day: NaiveDate) -> Result<String> {
+    let value = value.trim();
+    let local = if let Ok(time) = NaiveTime::parse_from_str(value, "%H:%M") {
+        day.and_time(time)
+    } else if let Ok(datetime) = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M") {
+        datetime
+    } else {
+        return Ok(DateTime::parse_from_rfc3339(value)
+            .with_context(|| {
+                format!("\"{value}\" is neither HH:MM, YYYY-MM-DD HH:MM nor RFC 3339")
+            })?
+            .with_timezone(&Utc)
+            .to_rfc3339_opts(SecondsFormat::Secs, true));
+    };
+    let local = Local
+        .from_local_datetime(&local)
+        .earliest()
+        .with_context(|| format!("{value} does not exist in the local timezone"))?;
+    Ok(local
+        .with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+/// `from..=to` as given, defaulting to the current week up to today.
+fn range(from: Option<NaiveDate>, to: Option<NaiveDate>) -> (String, String) {
+    let today = Local::now().date_naive();
+    let monday = today - Days::new(u64::from(today.weekday().num_days_from_monday()));
+    (
+        from.unwrap_or(monday).to_string(),
+        to.unwrap_or(today).to_string(),
+    )
+}
+
+pub async fn start(
+    api: &Api,
+    project: Option<String>,
+    activity: Option<String>,
+    description: Option<String>,
+    billable: bool,
+) -> Result<()> {
+    let names = Names::load(api).await?;
+    let project = project
+        .map(|q| fuzzy::best("project", &q, &names.projects, |p| &p.id, |p| &p.name))
+        .transpose()?;
+    let activity = match activity {
+        Some(q) => {
+            // Global activities and those of the chosen project.
+            let candidates: Vec<Activity> = names
+                .activities
+                .iter()
+                .filter(|a| {
+                    a.project_id.is_none()
+                        || a.project_id.as_deref() == project.map(|p| p.id.as_str())
+                })
+                .cloned()
+                .collect();
+            Some(fuzzy::best("activity", &q, &candidates, |a| &a.id, |a| &a.name)?.clone())
+        }
+        None => None,
+    };
+
+    if let Some(running) = api.running().await? {
+        api.stop(&running.id).await?;
+        println!("Stopped {}", names.describe(&running));
+    }
+    let started = api
+        .start(&NewTimesheet {
+            project_id: project.map(|p| p.id.clone()),
+            activity_id: activity.map(|a| a.id),
+            description,
+            billable,
+        })
+        .await?;
+    println!(
+        "Started {} at {}",
+        names.describe(&started),
+        local_time(&started.start_time)
+    );
+    Ok(())
+}
+
+pub async fn stop(api: &Api) -> Result<()> {
+    let Some(running) = api.running().await? else {
+        bail!("No timer is running");
+    };
+    api.stop(&running.id).await?;
+    let names = Names::load(api).await?;
+    println!(
+        "Stopped {} after {}",
+        names.describe(&running),
+        hours_minutes(tracked_seconds(&running))
+    );
+    Ok(())
+}
+
+pub async fn status(api: &Api, format: Format) -> Result<()> {
+    let running = api.running().await?;
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&running)?);
+        return Ok(());
+    }
+    let Some(running) = running else {
+        println!("No timer is running.");
+        return Ok(());
+    };
+    let names = Names::load(api).await?;
+    let paused = if running.paused_at.is_some() {
+        ", paused"
+    } else {
+        ""
+    };
+    println!(
+        "{} since {} ({}{paused})",
+        names.describe(&running),
+        local_time(&running.start_time),
+        hours_minutes(tracked_seconds(&running))
+    );
+    Ok(())
+}
+
+fn entry_table(names: &Names, entries: &[Timesheet], full_ids: bool) -> Table {
+    let mut table = Table::new(&[
+        "ID",
+        "Date",
+        "Start",
+        "End",
+        "Duration",
+        "Project",
+        "Activity",
+        "Description",
+        "Billable",
+    ]);
+    for t in entries {
+        let id = if full_ids {
+            t.id.clone()
+        } else {
+            t.id.chars().take(8).collect()
+        };
+        table.row(vec![
+            id,
+            local_date(&t.start_time),
+            local_time(&t.start_time),
+            t.end_time.as_deref().map(local_time).unwrap_or_default(),
+            hours_minutes(tracked_seconds(t)),
+            names.project(t.project_id.as_deref()),
+            names.activity(t.activity_id.as_deref()),
+            t.description.clone().unwrap_or_default(),
+            if t.billable { "yes" } else { "no" }.to_string(),
+        ]);
+    }
+    table
+}
+
+pub async fn log(
+    api: &Api,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    limit: usize,
+    format: Format,
+) -> Result<()> {
+    let mut entries = if from.is_some() || to.is_some() {
+        let (from, to) = range(from, to);
+        let mut entries = api.between(&from, &to).await?;
+        entries.reverse();
+        entries
+    } else {
+        api.recent().await?
+    };
+    entries.truncate(limit);
+    let names = Names::load(api).await?;
+    output::print(format, &entry_table(&names, &entries, false), &entries)
+}
+
+/// The entry an ID or the start of one refers to, or the latest entry.
+async fn find_entry(api: &Api, id: Option<&str>) -> Result<Timesheet> {
+    let recent = api.recent().await?;
+    let Some(id) = id else {
+        return recent.into_iter().next().context("You have no entries yet");
+    };
+    let matching: Vec<&Timesheet> = recent.iter().filter(|t| t.id.starts_with(id)).collect();
+    match matching.as_slice() {
+        [entry] => Ok((*entry).clone()),
+        [] => api.timesheet(id).await,
+        _ => bail!("\"{id}\" is the start of several entries' IDs"),
+    }
+}
+
+pub struct Edit {
+    pub id: Option<String>,
+    pub description: Option<String>,
+    pub billable: Option<bool>,
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+pub async fn edit(api: &Api, edit: Edit) -> Result<()> {
+    let entry = find_entry(api, edit.id.as_deref()).await?;
+    let day = parse_rfc3339(&entry.start_time)?.date_naive();
+    let start = edit.start.map(|s| parse_time(&s, day)).transpose()?;
+    let end = edit.end.map(|e| parse_time(&e, day)).transpose()?;
+    if end.is_some() && entry.end_time.is_none() {
+        bail!("The entry is still running; stop it first");
+    }
+    api.update(
+        &entry.id,
+        &TimesheetChanges {
+            description: edit.description,
+            billable: edit.billable,
+            start_time: start,
+            end_time: end,
+        },
+    )
+    .await?;
+    let names = Names::load(api).await?;
+    println!(
+        "Updated {} from {}",
+        names.describe(&entry),
+        local_date(&entry.start_time)
+    );
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SummaryRow {
+    project: String,
+    activity: String,
+    entries: usize,
+    seconds: i64,
+}
+
+/// Tracked time per project and activity within `from..=to`.
+pub async fn summary(
+    api: &Api,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    format: Format,
+) -> Result<()> {
+    let (from, to) = range(from, to);
+    let entries = api.between(&from, &to).await?;
+    let names = Names::load(api).await?;
+
+    let mut totals: BTreeMap<(String, String), (usize, i64)> = BTreeMap::new();
+    for t in &entries {
+        let key = (
+            names.project(t.project_id.as_deref()),
+            names.activity(t.activity_id.as_deref()),
+        );
+        let total = totals.entry(key).or_default();
+        total.0 += 1;
+        total.1 += tracked_seconds(t);
+    }
+    let rows: Vec<SummaryRow> = totals
+        .into_iter()
+        .map(|((project, activity), (entries, seconds))| SummaryRow {
+            project,
+            activity,
+            entries,
+            seconds,
+        })
+        .collect();
+
+    let mut table = Table::new(&["Project", "Activity", "Entries", "Hours"]);
+    for r in &rows {
+        table.row(vec![
+            r.project.clone(),
+            r.activity.clone(),
+            r.entries.to_string(),
+            hours_minutes(r.seconds),
+        ]);
+    }
+    table.row(vec![
+        "Total".to_string(),
+        String::new(),
+        entries.len().to_string(),
+        hours_minutes(rows.iter().map(|r| r.seconds).sum()),
+    ]);
+    output::print(format, &table, &rows)
+}
+
+pub async fn budgets(api: &Api, format: Format) -> Result<()> {
+    let budgets = api.budgets().await?;
+    let mut table = Table::new(&["Scope", "Name", "Period", "Used", "Budget", "Remaining"]);
+    for b in &budgets {
+        let (used, budget, remaining) = match b.time_budget {
+            Some(seconds) => (
+                hours_minutes(b.used_seconds),
+                hours_minutes(i64::from(seconds)),
+                b.remaining_seconds.map(hours_minutes).unwrap_or_default(),
+            ),
+            None => (
+                cents(b.used_amount),
+                b.money_budget.map(cents).unwrap_or_default(),
+                b.remaining_amount.map(cents).unwrap_or_default(),
+            ),
+        };
+        table.row(vec![
+            b.scope.clone(),
+            b.name.clone(),
+            b.period.clone().unwrap_or_default(),
+            used,
+            budget,
+            remaining,
+        ]);
+    }
+    output::print(format, &table, &budgets)
+}
+
+fn cents(amount: i64) -> String {
+    let sign = if amount < 0 { "-" } else { "" };
+    format!("{sign}{}.{:02}", amount.abs() / 100, amount.abs() % 100)
+}
+
+pub async fn overtime(api: &Api, until: Option<NaiveDate>, format: Format) -> Result<()> {
+    let until = until
+        .unwrap_or_else(|| Local::now().date_naive())
+        .to_string();
+    let balances = api.overtime(&until).await?;
+    let mut table = Table::new(&["User", "Expected", "Worked", "Balance"]);
+    for b in &balances {
+        table.row(vec![
+            b.user_name.clone().unwrap_or_else(|| b.user_id.clone()),
+            hours_minutes(b.expected_seconds),
+            hours_minutes(b.worked_seconds),
+            hours_minutes(b.balance_seconds),
+        ]);
+    }
+    output::print(format, &table, &balances)
+}
+
+/// Every entry within `from..=to`, oldest first, with full IDs.
+pub async fn export(
+    api: &Api,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    format: Format,
+) -> Result<()> {
+    let (from, to) = range(from, to);
+    let entries = api.between(&from, &to).await?;
+    let names = Names::load(api).await?;
+    output::print(format, &entry_table(&names, &entries, true), &entries)
+}
+
+/// The projects of the workspace with the activities `start` offers for them.
+pub async fn projects(api: &Api, format: Format) -> Result<()> {
+    let names = Names::load(api).await?;
+    let mut table = Table::new(&["Project", "Activities"]);
+    for p in &names.projects {
+        let activities: Vec<&str> = names
+            .activities
+            .iter()
+            .filter(|a| a.project_id.as_deref() == Some(p.id.as_str()))
+            .map(|a| a.name.as_str())
+            .collect();
+        table.row(vec![p.name.clone(), activities.join(", ")]);
+    }
+    let global: Vec<&str> = names
+        .activities
+        .iter()
+        .filter(|a| a.project_id.is_none())
+        .map(|a| a.name.as_str())
+        .collect();
+    table.row(vec!["(any project)".to_string(), global.join(", ")]);
+    output::print(format, &table, &names.projects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_clock_times_on_the_given_day() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let parsed = parse_time("09:30", day).unwrap();
+        let local = parse_rfc3339(&parsed).unwrap();
+        assert_eq!(
+            local.format("%Y-%m-%d %H:%M").to_string(),
+            "2026-03-02 09:30"
+        );
+
+        let parsed = parse_time("2026-03-03 17:05", day).unwrap();
+        let local = parse_rfc3339(&parsed).unwrap();
+        assert_eq!(
+            local.format("%Y-%m-%d %H:%M").to_string(),
+            "2026-03-03 17:05"
+        );
+
+        assert_eq!(
+            parse_time("2026-03-02T08:00:00+01:00", day).unwrap(),
+            "2026-03-02T07:00:00Z"
+        );
+        assert!(parse_time("half past nine", day).is_err());
+    }
+
+    #[test]
+    fn counts_tracked_time_without_breaks() {
+        let entry = Timesheet {
+            start_time: "2026-03-02T08:00:00Z".to_string(),
+            paused_at: Some("2026-03-02T10:00:00Z".to_string()),
+            break_duration: 900,
+            ..Timesheet::default()
+        };
+        assert_eq!(tracked_seconds(&entry), 2 * 3600 - 900);
+        let stopped = Timesheet {
+            duration: Some(60),
+            ..entry
+        };
+        assert_eq!(tracked_seconds(&stopped), 60);
+    }
+
+    #[test]
+    fn formats_cents() {
+        assert_eq!(cents(123_456), "1234.56");
+        assert_eq!(cents(-5), "-0.05");
+    }
+}
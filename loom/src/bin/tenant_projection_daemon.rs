@@ -3,8 +3,10 @@ use std::time::Duration;
 use anyhow::{Result, anyhow};
 use loom::infrastructure::{
     BackoffConfig, Pool, ProjectionDaemon, ProjectionRunner, ProjectionSource, SqlCheckpoint,
+    projection_health::{projectors::MonitoredProjector, repositories::ProjectionStatusRepository},
     tenant::projectors::TenantProjector,
 };
+use loom::tenant::{budget::BudgetMonitor, webhook::WebhookDispatcher};
 use loom_infrastructure::query::Query;
 use loom_infrastructure_impl::ConnectedAdminPool;
 use tracing::warn;
@@ -62,6 +64,7 @@ async fn main() -> Result<()> {
     };
 
     let mut daemon = ProjectionDaemon::new();
+    let mut health_pools = Vec::new();
 
     for workspace in workspaces {
         let tenant_token = workspace.get_id().to_string();
@@ -86,10 +89,24 @@ async fn main() -> Result<()> {
 
         let checkpoint_name = format!("tenant_projection_{tenant_token}");
         let checkpoint = SqlCheckpoint::new(pool.clone().into_pool(), &checkpoint_name).await?;
+        health_pools.push(pool.clone());
 
         daemon.register_with_config(
             ProjectionRunner::new(pool.clone().into_pool(), ProjectionSource::AllStreams),
-            TenantProjector::new(pool.clone()),
+            MonitoredProjector::new(
+                checkpoint_name,
+                pool.clone(),
+                WebhookDispatcher::new(
+                    tenant_token.clone(),
+                    pool.clone(),
+                    BudgetMonitor::new(
+                        tenant_token.clone(),
+                        pool.clone(),
+                        TenantProjector::new(pool.clone()),
+                    ),
+                ),
+            )
+            .with_max_retries(loom::projection_health::max_retries()),
             checkpoint,
             backoff.clone(),
         );
@@ -97,6 +114,29 @@ async fn main() -> Result<()> {
         tracing::info!(tenant_token = %tenant_token, "Registered TenantProjector.");
     }
 
+    tokio::spawn(loom::projection_health::serve(
+        loom::projection_health::health_addr(
+            loom::projection_health::TENANT_HEALTH_ADDR_VAR,
+            ([127, 0, 0, 1], 9102).into(),
+        ),
+        move || {
+            let pools = health_pools.clone();
+            async move {
+                let mut statuses = Vec::new();
+                for pool in pools {
+                    statuses.extend(ProjectionStatusRepository::new(pool).all().await?);
+                }
+                Ok(statuses)
+            }
+        },
+    ));
+
+    // The background jobs re-read the workspace list every round, so they
+    // also serve workspaces created after the daemon started.
+    tokio::spawn(loom::tenant::auto_stop::stop_forever());
+    tokio::spawn(loom::tenant::report::send_forever());
+    tokio::spawn(loom::tenant::webhook::deliver_forever());
+
     daemon.run_until_cancelled().await;
 
     Ok(())
@@ -1,9 +1,17 @@
 #![allow(clippy::missing_errors_doc)]
 
+pub mod api_token;
+pub mod audit;
 pub mod auth;
 pub mod authorization;
 pub mod error;
+pub mod live;
+pub mod mail;
+pub mod projection_health;
+pub mod remote;
+mod secret_token;
 pub mod setup;
+pub mod sync;
 pub mod tenant;
 pub mod user_settings;
 pub mod workspace;
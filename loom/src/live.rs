@@ -0,0 +1,326 @@
+//! Live updates: tells connected clients which parts of a workspace changed.
+//!
+//! The projection daemons run in their own processes, so the web server
+//! follows the tenant runner's checkpoint instead.  Once the daemon has
+//! projected new events, every [`subscribe`]r of the workspace receives the
+//! [`Change`]s they caused and can reload the matching read models, which
+//! are up to date by then.  A workspace is only watched while somebody
+//! listens.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{LazyLock, Mutex},
+    time::Duration,
+};
+
+use anyhow::Result;
+use loom_infrastructure_impl::{
+    ConnectedTenantPool, RawEvent, projection_health::repositories::ProjectionStatusRepository,
+    sync::repositories::SyncRepository,
+};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::projection_health::{ProjectionDatabase, runner_name};
+
+/// Pause between two looks at the checkpoint.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Batches a slow subscriber may fall behind before it misses some.
+const CAPACITY: usize = 64;
+/// More new events than this are not looked at one by one; everything is
+/// reported as changed instead.
+const MAX_EVENTS: i64 = 500;
+
+/// A part of the workspace clients cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Topic {
+    /// Timesheets, including the running timer.
+    Timesheets,
+    Approvals,
+    Customers,
+    Projects,
+    Activities,
+    Tags,
+    Rates,
+    Favorites,
+    WorkContracts,
+    Holidays,
+}
+
+impl Topic {
+    pub const ALL: [Self; 10] = [
+        Self::Timesheets,
+        Self::Approvals,
+        Self::Customers,
+        Self::Projects,
+        Self::Activities,
+        Self::Tags,
+        Self::Rates,
+        Self::Favorites,
+        Self::WorkContracts,
+        Self::Holidays,
+    ];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Timesheets => "timesheets",
+            Self::Approvals => "approvals",
+            Self::Customers => "customers",
+            Self::Projects => "projects",
+            Self::Activities => "activities",
+            Self::Tags => "tags",
+            Self::Rates => "rates",
+            Self::Favorites => "favorites",
+            Self::WorkContracts => "work_contracts",
+            Self::Holidays => "holidays",
+        }
+    }
+
+    /// The topic an event of `event_type` changes; `None` for events no
+    /// client caches, such as webhooks.
+    #[must_use]
+    pub fn of(event_type: &str) -> Option<Self> {
+        // Longer prefixes first: `ProjectRateSet` is about rates.
+        const PREFIXES: &[(&str, Topic)] = &[
+            ("TagTimesheet", Topic::Timesheets),
+            ("Timesheet", Topic::Timesheets),
+            ("Approval", Topic::Approvals),
+            ("Customer", Topic::Customers),
+            ("ProjectRate", Topic::Rates),
+            ("ActivityRate", Topic::Rates),
+            ("Project", Topic::Projects),
+            ("Activity", Topic::Activities),
+            ("Tag", Topic::Tags),
+            ("Favorite", Topic::Favorites),
+            ("WorkContract", Topic::WorkContracts),
+            ("Holiday", Topic::Holidays),
+        ];
+        PREFIXES
+            .iter()
+            .find(|(prefix, _)| event_type.starts_with(prefix))
+            .map(|(_, topic)| *topic)
+    }
+}
+
+/// Something in a workspace changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Change<'a> {
+    pub topic: Topic,
+    /// Only this user is told, e.g. about their own timesheets; `None` for
+    /// everybody in the workspace.
+    pub user_id: Option<&'a str>,
+}
+
+/// The changes of one look at the checkpoint, without duplicates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Changes(BTreeSet<(Topic, Option<String>)>);
+
+impl Changes {
+    fn everything() -> Self {
+        Self(Topic::ALL.into_iter().map(|t| (t, None)).collect())
+    }
+
+    fn insert(&mut self, change: Change<'_>) {
+        self.0
+            .insert((change.topic, change.user_id.map(str::to_string)));
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The topics `user_id` is told about.
+    #[must_use]
+    pub fn topics_for(&self, user_id: &str) -> BTreeSet<Topic> {
+        self.0
+            .iter()
+            .filter(|(_, only)| only.as_deref().is_none_or(|only| only == user_id))
+            .map(|(topic, _)| *topic)
+            .collect()
+    }
+}
+
+type Hub = HashMap<String, broadcast::Sender<Changes>>;
+
+static HUB: LazyLock<Mutex<Hub>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Listen to the changes of a workspace, starting now.
+///
+/// Must be called within a Tokio runtime: the first subscriber of a
+/// workspace starts watching it.
+pub fn subscribe(workspace_id: &str) -> Subscription {
+    let mut hub = HUB
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let receiver = if let Some(sender) = hub.get(workspace_id) {
+        sender.subscribe()
+    } else {
+        let (sender, receiver) = broadcast::channel(CAPACITY);
+        hub.insert(workspace_id.to_string(), sender.clone());
+        tokio::spawn(watch(workspace_id.to_string(), sender));
+        receiver
+    };
+    Subscription { receiver }
+}
+
+/// The changes of one workspace, as they are projected.
+pub struct Subscription {
+    receiver: broadcast::Receiver<Changes>,
+}
+
+impl Subscription {
+    /// The next changes.  A subscriber too slow to keep up is told that
+    /// everything changed.
+    pub async fn recv(&mut self) -> Option<Changes> {
+        match self.receiver.recv().await {
+            Ok(changes) => Some(changes),
+            Err(broadcast::error::RecvError::Lagged(_)) => Some(Changes::everything()),
+            Err(broadcast::error::RecvError::Closed) => None,
+        }
+    }
+}
+
+/// Whether nobody listens to `sender` anymore; it is then removed from the
+/// hub, so the next subscriber starts a new watch.
+fn abandoned(workspace_id: &str, sender: &broadcast::Sender<Changes>) -> bool {
+    let mut hub = HUB
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if sender.receiver_count() > 0 {
+        return false;
+    }
+    if hub
+        .get(workspace_id)
+        .is_some_and(|s| s.same_channel(sender))
+    {
+        hub.remove(workspace_id);
+    }
+    true
+}
+
+async fn watch(workspace_id: String, sender: broadcast::Sender<Changes>) {
+    let mut pool = None;
+    let mut position = None;
+    while !abandoned(&workspace_id, &sender) {
+        match poll(&workspace_id, &mut pool, &mut position).await {
+            Ok(changes) if !changes.is_empty() => {
+                let _ = sender.send(changes);
+            }
+            Ok(_) => {}
+            Err(error) => {
+                // Connect again next time.
+                pool = None;
+                warn!(
+                    workspace_id = %workspace_id,
+                    error = %error,
+                    "Failed to look for live updates."
+                );
+            }
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// The changes projected since `position`, which moves on to the current
+/// checkpoint.  The first look only sets the position.
+async fn poll(
+    workspace_id: &str,
+    pool: &mut Option<ConnectedTenantPool>,
+    position: &mut Option<i64>,
+) -> Result<Changes> {
+    let pool = match pool {
+        Some(pool) => pool.clone(),
+        None => pool
+            .insert(crate::tenant::tenant_pool(workspace_id).await?)
+            .clone(),
+    };
+    let runner = runner_name(&ProjectionDatabase::Tenant(workspace_id.to_string()));
+    let projected = ProjectionStatusRepository::new(pool.clone())
+        .position(&runner)
+        .await?
+        .unwrap_or_default();
+    let Some(after) = position.replace(projected) else {
+        return Ok(Changes::default());
+    };
+    // Nothing new, or the projections were rebuilt from scratch.
+    if projected <= after {
+        return Ok(Changes::default());
+    }
+    if projected - after > MAX_EVENTS {
+        return Ok(Changes::everything());
+    }
+
+    let repo = SyncRepository::new(pool);
+    let events: Vec<RawEvent> = repo
+        .events_after(after, projected - after)
+        .await?
+        .into_iter()
+        .filter(|e| e.global_position <= projected)
+        .collect();
+    changes(&repo, &events).await
+}
+
+async fn changes(repo: &SyncRepository, events: &[RawEvent]) -> Result<Changes> {
+    let mut owners: HashMap<&str, Option<String>> = HashMap::new();
+    let mut changes = Changes::default();
+    for event in events {
+        let Some(topic) = Topic::of(&event.event_type) else {
+            continue;
+        };
+        // Timesheets are private to whoever tracked them.
+        let user_id = if event.event_type.starts_with("Timesheet") {
+            if !owners.contains_key(event.stream_id.as_str()) {
+                let owner = crate::sync::server::owner(repo, &event.stream_id).await?;
+                owners.insert(&event.stream_id, owner);
+            }
+            match &owners[event.stream_id.as_str()] {
+                Some(owner) => Some(owner.as_str()),
+                None => continue,
+            }
+        } else {
+            None
+        };
+        changes.insert(Change { topic, user_id });
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_event_types_to_topics() {
+        assert_eq!(Topic::of("TimesheetStopped"), Some(Topic::Timesheets));
+        assert_eq!(Topic::of("TimesheetAmended"), Some(Topic::Timesheets));
+        assert_eq!(Topic::of("TagTimesheetTagged"), Some(Topic::Timesheets));
+        assert_eq!(Topic::of("TagRenamed"), Some(Topic::Tags));
+        assert_eq!(Topic::of("ProjectRateSet"), Some(Topic::Rates));
+        assert_eq!(Topic::of("ProjectArchived"), Some(Topic::Projects));
+        assert_eq!(Topic::of("WebhookSubscribed"), None);
+    }
+
+    #[test]
+    fn private_changes_reach_their_user_only() {
+        let mut changes = Changes::default();
+        changes.insert(Change {
+            topic: Topic::Timesheets,
+            user_id: Some("alice"),
+        });
+        changes.insert(Change {
+            topic: Topic::Projects,
+            user_id: None,
+        });
+        assert_eq!(
+            changes.topics_for("alice"),
+            BTreeSet::from([Topic::Timesheets, Topic::Projects])
+        );
+        assert_eq!(changes.topics_for("bob"), BTreeSet::from([Topic::Projects]));
+        assert_eq!(
+            Changes::everything().topics_for("bob").len(),
+            Topic::ALL.len()
+        );
+    }
+}
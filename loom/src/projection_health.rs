@@ -0,0 +1,467 @@
+//! Projection daemon health: status queries and a small HTTP endpoint.
+//!
+//! The daemons wrap their projectors in [`MonitoredProjector`], which keeps
+//! `projection_status` up to date.  [`serve`] exposes that table on
+//! `/health` (JSON, `503` while a runner is failing) and `/metrics`
+//! (Prometheus text format).
+//!
+//! Events a runner still cannot handle after [`max_retries`] retries are
+//! parked in `projection_dead_letters`; [`replay_dead_letter`] and
+//! [`skip_dead_letter`] resolve them once the cause has been fixed.
+//! [`rebuild`] replays a whole database when a fix changes past results.
+
+use std::{
+    collections::HashSet, fmt::Write as _, future::Future, net::SocketAddr, sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{Result, bail};
+use loom_infrastructure::query::Query;
+use loom_infrastructure_impl::{
+    Pool, Projector, RawEvent, StateConnected,
+    admin::{projectors::AdminProjector, workspace::repositories::WorkspaceRepository},
+    projection_health::{
+        projectors::MonitoredProjector,
+        repositories::{
+            DeadLetterRepository, DeadLetterRow, DeadLetterStatus, ProjectionStatus,
+            ProjectionStatusRepository, ProjectionTablesRepository,
+        },
+    },
+    tenant::projectors::TenantProjector,
+    time_travel::{Cutoff, HistoricalRepository},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    time::timeout,
+};
+use tracing::{info, warn};
+
+use crate::tenant::{budget::BudgetMonitor, webhook::WebhookDispatcher};
+
+/// Environment variable overriding the admin daemon's health address.
+pub const ADMIN_HEALTH_ADDR_VAR: &str = "ADMIN_PROJECTION_HEALTH_ADDR";
+/// Environment variable overriding the tenant daemon's health address.
+pub const TENANT_HEALTH_ADDR_VAR: &str = "TENANT_PROJECTION_HEALTH_ADDR";
+
+/// Environment variable overriding how often a failing event is retried
+/// before it is parked.
+pub const MAX_RETRIES_VAR: &str = "PROJECTION_MAX_RETRIES";
+/// Retries before a failing event is parked, unless overridden.
+pub const DEFAULT_MAX_RETRIES: u32 = 5;
+
+/// The database a projection runner works on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectionDatabase {
+    Admin,
+    Tenant(String),
+}
+
+/// Retries before a failing event is parked, from [`MAX_RETRIES_VAR`].
+#[must_use]
+pub fn max_retries() -> u32 {
+    std::env::var(MAX_RETRIES_VAR)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_RETRIES)
+}
+
+/// Status of the admin projection runner.
+pub async fn admin_status() -> Result<Vec<ProjectionStatus>> {
+    let pool = Pool::connect_admin().await?;
+    Ok(ProjectionStatusRepository::new(pool).all().await?)
+}
+
+/// Status of the projection runner of a single workspace.
+pub async fn tenant_status(workspace_id: &str) -> Result<Vec<ProjectionStatus>> {
+    let pool = crate::tenant::tenant_pool(workspace_id).await?;
+    Ok(ProjectionStatusRepository::new(pool).all().await?)
+}
+
+/// Status of the admin runner followed by the runners of every workspace.
+///
+/// Workspaces whose database cannot be reached are skipped.
+pub async fn all_status() -> Result<Vec<ProjectionStatus>> {
+    let mut statuses = admin_status().await?;
+    let workspaces = WorkspaceRepository::from_pool(Pool::connect_admin().await?)
+        .await?
+        .all()
+        .await?;
+    for workspace in workspaces {
+        match tenant_status(&workspace.get_id().to_string()).await {
+            Ok(tenant) => statuses.extend(tenant),
+            Err(error) => warn!(
+                workspace_id = %workspace.get_id(),
+                error = %error,
+                "Failed to read projection status."
+            ),
+        }
+    }
+    Ok(statuses)
+}
+
+/// Events parked in the given database that still await a decision.
+pub async fn dead_letters(database: &ProjectionDatabase) -> Result<Vec<DeadLetterRow>> {
+    Ok(match database {
+        ProjectionDatabase::Admin => {
+            DeadLetterRepository::new(Pool::connect_admin().await?)
+                .list(Some(DeadLetterStatus::Parked))
+                .await?
+        }
+        ProjectionDatabase::Tenant(workspace_id) => {
+            DeadLetterRepository::new(crate::tenant::tenant_pool(workspace_id).await?)
+                .list(Some(DeadLetterStatus::Parked))
+                .await?
+        }
+    })
+}
+
+/// Parked events of the admin database and every workspace.
+///
+/// Workspaces whose database cannot be reached are skipped.
+pub async fn all_dead_letters() -> Result<Vec<(ProjectionDatabase, DeadLetterRow)>> {
+    let mut parked: Vec<_> = dead_letters(&ProjectionDatabase::Admin)
+        .await?
+        .into_iter()
+        .map(|row| (ProjectionDatabase::Admin, row))
+        .collect();
+    let workspaces = WorkspaceRepository::from_pool(Pool::connect_admin().await?)
+        .await?
+        .all()
+        .await?;
+    for workspace in workspaces {
+        let database = ProjectionDatabase::Tenant(workspace.get_id().to_string());
+        match dead_letters(&database).await {
+            Ok(rows) => parked.extend(rows.into_iter().map(|row| (database.clone(), row))),
+            Err(error) => warn!(
+                workspace_id = %workspace.get_id(),
+                error = %error,
+                "Failed to read dead letters."
+            ),
+        }
+    }
+    Ok(parked)
+}
+
+/// Apply a parked event again, e.g. after deploying a projector fix.
+///
+/// The event runs through the same projectors as in the daemon, so webhooks
+/// and budget alerts it missed while parked are sent now.  If it fails again
+/// it stays parked with the new error.
+pub async fn replay_dead_letter(database: &ProjectionDatabase, id: i64) -> Result<()> {
+    match database {
+        ProjectionDatabase::Admin => {
+            let pool = Pool::connect_admin().await?;
+            let repo = DeadLetterRepository::new(pool.clone());
+            let event = parked_event(&repo, id).await?;
+            let result = AdminProjector::new(pool).handle(event).await;
+            finish_replay(&repo, id, result).await
+        }
+        ProjectionDatabase::Tenant(workspace_id) => {
+            let pool = crate::tenant::tenant_pool(workspace_id).await?;
+            let repo = DeadLetterRepository::new(pool.clone());
+            let event = parked_event(&repo, id).await?;
+            let result = WebhookDispatcher::new(
+                workspace_id.clone(),
+                pool.clone(),
+                BudgetMonitor::new(
+                    workspace_id.clone(),
+                    pool.clone(),
+                    TenantProjector::new(pool),
+                ),
+            )
+            .handle(event)
+            .await;
+            finish_replay(&repo, id, result).await
+        }
+    }
+}
+
+/// Drop a parked event for good; the projections will never see it.
+pub async fn skip_dead_letter(database: &ProjectionDatabase, id: i64) -> Result<()> {
+    match database {
+        ProjectionDatabase::Admin => {
+            let repo = DeadLetterRepository::new(Pool::connect_admin().await?);
+            parked_event(&repo, id).await?;
+            repo.resolve(id, DeadLetterStatus::Skipped).await?;
+        }
+        ProjectionDatabase::Tenant(workspace_id) => {
+            let repo = DeadLetterRepository::new(crate::tenant::tenant_pool(workspace_id).await?);
+            parked_event(&repo, id).await?;
+            repo.resolve(id, DeadLetterStatus::Skipped).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn parked_event<Scope>(repo: &DeadLetterRepository<Scope>, id: i64) -> Result<RawEvent> {
+    let Some(row) = repo.find(id).await? else {
+        bail!("dead letter {id} not found");
+    };
+    if row.status != DeadLetterStatus::Parked {
+        bail!("dead letter {id} is already {}", row.status.as_str());
+    }
+    Ok(row.to_raw_event()?)
+}
+
+async fn finish_replay<Scope>(
+    repo: &DeadLetterRepository<Scope>,
+    id: i64,
+    result: Result<(), loom_infrastructure_impl::Error>,
+) -> Result<()> {
+    match result {
+        Ok(()) => {
+            repo.resolve(id, DeadLetterStatus::Replayed).await?;
+            Ok(())
+        }
+        Err(error) => {
+            repo.record_replay_failure(id, &error.to_string()).await?;
+            Err(error.into())
+        }
+    }
+}
+
+/// Name of the runner the daemon uses for `database`.
+#[must_use]
+pub fn runner_name(database: &ProjectionDatabase) -> String {
+    match database {
+        ProjectionDatabase::Admin => "admin_projection".to_string(),
+        ProjectionDatabase::Tenant(workspace_id) => format!("tenant_projection_{workspace_id}"),
+    }
+}
+
+/// Rebuild the projections of a database from its event store, e.g. after
+/// a projector fix changed how past events are projected.
+///
+/// The projection tables are emptied and every event up to the runner's
+/// recorded position is replayed, so the daemon carries on from its own
+/// checkpoint afterwards.  Events that were parked or skipped before are left
+/// out, and an event that fails now is retried and parked like in the daemon,
+/// so one bad event cannot leave the projections half-built.  Webhooks and
+/// budget alerts are not sent again.  Stop the daemon for the database first.
+/// Returns the number of replayed events.
+///
+/// # Errors
+///
+/// Returns an error if the database fails, including when a failing event
+/// cannot be parked.
+pub async fn rebuild(database: &ProjectionDatabase) -> Result<usize> {
+    let runner = runner_name(database);
+    match database {
+        ProjectionDatabase::Admin => {
+            let pool = Pool::connect_admin().await?;
+            let events = events_to_rebuild(pool.clone(), &runner).await?;
+            ProjectionTablesRepository::new(pool.clone())
+                .clear()
+                .await?;
+            let projector = AdminProjector::new(pool.clone());
+            replay(MonitoredProjector::new(runner, pool, projector), &events).await
+        }
+        ProjectionDatabase::Tenant(workspace_id) => {
+            let pool = crate::tenant::tenant_pool(workspace_id).await?;
+            let events = events_to_rebuild(pool.clone(), &runner).await?;
+            ProjectionTablesRepository::new(pool.clone())
+                .clear()
+                .await?;
+            let projector = TenantProjector::new(pool.clone());
+            replay(MonitoredProjector::new(runner, pool, projector), &events).await
+        }
+    }
+}
+
+/// Events up to the runner's checkpoint, without those it parked or that
+/// were skipped since.
+async fn events_to_rebuild<Scope>(
+    pool: Pool<Scope, StateConnected>,
+    runner: &str,
+) -> Result<Vec<RawEvent>> {
+    let position = ProjectionStatusRepository::new(pool.clone())
+        .position(runner)
+        .await?
+        .unwrap_or_default();
+    let left_out: HashSet<i64> = DeadLetterRepository::new(pool.clone())
+        .list(None)
+        .await?
+        .into_iter()
+        .filter(|row| row.runner == runner && row.status != DeadLetterStatus::Replayed)
+        .map(|row| row.global_position)
+        .collect();
+    let events = HistoricalRepository::new(pool, Cutoff::GlobalPosition(position))
+        .events(None)
+        .await?;
+    Ok(events
+        .into_iter()
+        .filter(|event| !left_out.contains(&event.global_position))
+        .collect())
+}
+
+/// Feed `events` to `projector` in order.  A failing event is retried until
+/// the projector parks it, as the daemon would.
+async fn replay<Scope, P>(
+    projector: MonitoredProjector<Scope, P>,
+    events: &[RawEvent],
+) -> Result<usize>
+where
+    Scope: Clone + Send + Sync + 'static,
+    P: Projector<Error = loom_infrastructure_impl::Error> + Send,
+{
+    let retries = max_retries();
+    let mut projector = projector.with_max_retries(retries);
+    for event in events {
+        let mut attempt = 1;
+        while let Err(error) = projector.handle(event.clone()).await {
+            if attempt > retries {
+                return Err(error.into());
+            }
+            attempt += 1;
+        }
+    }
+    Ok(events.len())
+}
+
+/// The address from the environment variable `var`, or `default` if unset or
+/// invalid.
+#[must_use]
+pub fn health_addr(var: &str, default: SocketAddr) -> SocketAddr {
+    std::env::var(var)
+        .ok()
+        .and_then(|addr| addr.parse().ok())
+        .unwrap_or(default)
+}
+
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Serve `/health` and `/metrics` on `addr` until the task is dropped.
+///
+/// `statuses` is called once per request.  Each connection is handled in its
+/// own task and dropped if the request does not arrive within a few seconds,
+/// so a stalled client cannot block the probes.
+///
+/// # Errors
+///
+/// Returns an error if `addr` cannot be bound.
+pub async fn serve<F, Fut>(addr: SocketAddr, statuses: F) -> Result<()>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Vec<ProjectionStatus>>> + Send,
+{
+    let listener = TcpListener::bind(addr).await?;
+    info!(%addr, "Serving projection health.");
+    let statuses = Arc::new(statuses);
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(respond(stream, Arc::clone(&statuses)));
+            }
+            Err(error) => {
+                warn!(error = %error, "Failed to accept health connection.");
+                // Accept errors such as running out of file descriptors do
+                // not clear up immediately.
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+    }
+}
+
+async fn respond<F, Fut>(mut stream: TcpStream, statuses: Arc<F>)
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<Vec<ProjectionStatus>>>,
+{
+    let mut buffer = [0_u8; 1024];
+    let read = match timeout(READ_TIMEOUT, stream.read(&mut buffer)).await {
+        Ok(Ok(read)) => read,
+        Ok(Err(error)) => {
+            warn!(error = %error, "Failed to read health request.");
+            return;
+        }
+        Err(_) => {
+            warn!("Timed out waiting for a health request.");
+            return;
+        }
+    };
+    let request = String::from_utf8_lossy(&buffer[..read]);
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+    let (status, content_type, body) = match path {
+        "/health" | "/metrics" => match statuses().await {
+            Ok(list) if path == "/metrics" => {
+                ("200 OK", "text/plain; version=0.0.4", render_metrics(&list))
+            }
+            Ok(list) => {
+                let code = if list.iter().any(ProjectionStatus::is_failing) {
+                    "503 Service Unavailable"
+                } else {
+                    "200 OK"
+                };
+                let body = serde_json::to_string(&list).unwrap_or_default();
+                (code, "application/json", body)
+            }
+            Err(error) => ("503 Service Unavailable", "text/plain", error.to_string()),
+        },
+        _ => ("404 Not Found", "text/plain", "not found".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    if let Err(error) = stream.write_all(response.as_bytes()).await {
+        warn!(error = %error, "Failed to write health response.");
+    }
+}
+
+/// Render statuses in the Prometheus text exposition format.
+#[must_use]
+pub fn render_metrics(statuses: &[ProjectionStatus]) -> String {
+    let gauges: [(&str, &str, fn(&ProjectionStatus) -> Option<i64>); 7] = [
+        (
+            "loom_projection_position",
+            "Global position of the last projected event.",
+            |s| Some(s.position),
+        ),
+        (
+            "loom_projection_head_position",
+            "Global position of the newest event in the store.",
+            |s| Some(s.head_position),
+        ),
+        (
+            "loom_projection_lag_events",
+            "Number of events not yet projected.",
+            |s| Some(s.lag_events),
+        ),
+        (
+            "loom_projection_lag_seconds",
+            "Age of the oldest unprojected event relative to the newest event.",
+            |s| s.lag_seconds,
+        ),
+        (
+            "loom_projection_retry_count",
+            "Retries of the currently failing event.",
+            |s| Some(i64::from(s.retry_count)),
+        ),
+        (
+            "loom_projection_failing",
+            "1 if the runner is stuck on a failing event.",
+            |s| Some(i64::from(s.is_failing())),
+        ),
+        (
+            "loom_projection_parked_events",
+            "Events parked in the dead-letter table.",
+            |s| Some(s.parked),
+        ),
+    ];
+
+    let mut out = String::new();
+    for (name, help, value) in gauges {
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} gauge");
+        for status in statuses {
+            if let Some(v) = value(status) {
+                let _ = writeln!(out, "{name}{{runner=\"{}\"}} {v}", status.runner);
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,230 @@
+//! A client for the HTTP API of a Loom server, shared by offline sync and
+//! the `loom` CLI.
+//!
+//! [`sign_in`] starts a session with email and password; a [`Remote`] then
+//! sends that session cookie, or a personal API token instead.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use reqwest::{
+    RequestBuilder,
+    header::{AUTHORIZATION, CONTENT_TYPE, COOKIE, SET_COOKIE},
+};
+use serde_json::{Value, json};
+
+use crate::error::ValidationError;
+
+/// Name of the server's session cookie.
+const SESSION_COOKIE: &str = "id";
+/// How long the server may take to answer.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// The server session has expired or the token was revoked; the user has to
+/// sign in again.
+#[derive(Debug, thiserror::Error)]
+#[error("Sign in to the server again")]
+pub struct SignInRequired;
+
+/// The server-side identity of a user in the workspace they track time in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Account {
+    pub user_id: String,
+    pub name: String,
+    pub email: String,
+    pub workspace_id: String,
+    pub workspace_name: Option<String>,
+}
+
+/// How requests prove who sends them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Credentials {
+    /// `id=...`, as returned by [`sign_in`].
+    Session(String),
+    /// A personal API token.
+    Token(String),
+}
+
+pub struct Remote {
+    http: reqwest::Client,
+    server: String,
+    credentials: Credentials,
+}
+
+impl Remote {
+    /// # Errors
+    ///
+    /// Returns a validation error if `server` is not an `http(s)` URL.
+    pub fn new(server: &str, credentials: Credentials) -> Result<Self> {
+        Ok(Self {
+            http: http_client()?,
+            server: normalize_server(server)?,
+            credentials,
+        })
+    }
+
+    #[must_use]
+    pub fn server(&self) -> &str {
+        &self.server
+    }
+
+    pub async fn get(&self, path: &str) -> Result<String> {
+        self.send(self.http.get(self.url(path))).await
+    }
+
+    pub async fn post(&self, path: &str, body: String) -> Result<String> {
+        self.send(
+            self.http
+                .post(self.url(path))
+                .header(CONTENT_TYPE, "application/json")
+                .body(body),
+        )
+        .await
+    }
+
+    pub async fn put(&self, path: &str, body: String) -> Result<String> {
+        self.send(
+            self.http
+                .put(self.url(path))
+                .header(CONTENT_TYPE, "application/json")
+                .body(body),
+        )
+        .await
+    }
+
+    pub async fn delete(&self, path: &str) -> Result<String> {
+        self.send(self.http.delete(self.url(path))).await
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.server)
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<String> {
+        let request = match &self.credentials {
+            Credentials::Session(session) => request.header(COOKIE, session),
+            Credentials::Token(token) => request.header(AUTHORIZATION, format!("Bearer {token}")),
+        };
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("{} cannot be reached", self.server))?;
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(SignInRequired.into());
+        }
+        let body = response.text().await?;
+        if !status.is_success() {
+            bail!("The server answered {status}: {body}");
+        }
+        Ok(body)
+    }
+}
+
+fn http_client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent(concat!("loom/", env!("CARGO_PKG_VERSION")))
+        .build()?)
+}
+
+/// `server` without trailing slashes.
+///
+/// # Errors
+///
+/// Returns a validation error if `server` is not an `http(s)` URL.
+pub fn normalize_server(server: &str) -> Result<String> {
+    let server = server.trim().trim_end_matches('/');
+    let valid = url::Url::parse(server)
+        .is_ok_and(|u| matches!(u.scheme(), "http" | "https") && u.host().is_some());
+    if !valid {
+        return Err(
+            ValidationError::new("The server URL must start with http:// or https://").into(),
+        );
+    }
+    Ok(server.to_string())
+}
+
+/// Sign in to `server` and select the workspace named or identified by
+/// `workspace`, which may be left out if the user belongs to just one.
+///
+/// Returns the account and the session cookie.
+///
+/// # Errors
+///
+/// Returns a validation error if the credentials are wrong or the workspace
+/// is ambiguous or unknown.
+pub async fn sign_in(
+    server: &str,
+    email: &str,
+    password: &str,
+    workspace: Option<&str>,
+) -> Result<(Account, String)> {
+    let server = normalize_server(server)?;
+    let response = http_client()?
+        .post(format!("{server}/api/login"))
+        .header(CONTENT_TYPE, "application/json")
+        .body(json!({ "email": email, "password": password }).to_string())
+        .send()
+        .await
+        .context("The server cannot be reached")?;
+    if !response.status().is_success() {
+        return Err(ValidationError::new("The email or password is wrong").into());
+    }
+    let session = response
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .filter_map(|v| v.split(';').next())
+        .find(|v| v.starts_with(&format!("{SESSION_COOKIE}=")))
+        .map(ToString::to_string)
+        .context("The server did not start a session")?;
+    let remote = Remote::new(&server, Credentials::Session(session.clone()))?;
+
+    let workspaces: Vec<Value> = serde_json::from_str(&remote.get("/api/v1/workspaces").await?)?;
+    let chosen = match workspace.map(str::trim).filter(|w| !w.is_empty()) {
+        Some(wanted) => workspaces
+            .iter()
+            .find(|w| w["id"] == wanted || w["name"] == wanted),
+        None if workspaces.len() == 1 => workspaces.first(),
+        None => {
+            return Err(ValidationError::new(
+                "You belong to several workspaces; enter the one to track time in",
+            )
+            .into());
+        }
+    };
+    let Some(chosen) = chosen else {
+        return Err(ValidationError::new("No such workspace").into());
+    };
+    let workspace_id = chosen["id"].as_str().unwrap_or_default().to_string();
+    remote
+        .post(
+            "/api/workspaces/select",
+            json!({ "workspace_id": workspace_id }).to_string(),
+        )
+        .await?;
+
+    let me: Value = serde_json::from_str(&remote.get("/api/auth/me").await?)?;
+    let user_id = me["id"].as_str().context("Not signed in")?.to_string();
+    let members: Vec<Value> =
+        serde_json::from_str(&remote.get("/api/v1/workspaces/current/members").await?)?;
+    let name = members
+        .iter()
+        .find(|m| m["user_id"] == user_id.as_str())
+        .and_then(|m| m["name"].as_str())
+        .unwrap_or(email)
+        .to_string();
+
+    Ok((
+        Account {
+            user_id,
+            name,
+            email: me["email"].as_str().unwrap_or(email).to_string(),
+            workspace_id,
+            workspace_name: chosen["name"].as_str().map(ToString::to_string),
+        },
+        session,
+    ))
+}
@@ -0,0 +1,15 @@
+//! Bearer tokens that are shown to their owner once and only stored as a
+//! SHA-256 hash, such as API tokens and calendar feed tokens.
+
+use sha2::{Digest, Sha256};
+
+/// A new token: `prefix`, so that leaked tokens are easy to spot, followed
+/// by 64 random hex characters.
+pub(crate) fn generate(prefix: &str) -> String {
+    format!("{prefix}{}", hex::encode(rand::random::<[u8; 32]>()))
+}
+
+/// The hash a token is stored and looked up by.
+pub(crate) fn hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -1,11 +1,11 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use eventually::aggregate::{Root, repository::Saver};
 use loom_core::admin::{
     user::{UserEvent, UserId},
     workspace::{Workspace, WorkspaceEvent, WorkspaceId},
     workspace_role::{WorkspaceRole, WorkspaceRoleEvent, WorkspaceRoleId},
 };
-use loom_infrastructure::database::Migrate;
+use loom_infrastructure::{database::Migrate, query::Query};
 use loom_infrastructure_impl::{
     Pool, ScopeDefault, ScopeTenant, StateDisconnected,
     admin::{
@@ -32,6 +32,37 @@ pub async fn init_admin_db() -> Result<()> {
     Ok(())
 }
 
+/// Ensures the tenant database of a workspace exists and all its migrations
+/// are up to date.
+pub async fn init_tenant_db(workspace_id: &str) -> Result<()> {
+    let default_pool = Pool::<ScopeDefault, StateDisconnected>::connect_default().await?;
+    Initializer::new(SqliteInitializationStrategy)
+        .initialize_tenant(&default_pool, Some(workspace_id))
+        .await?;
+    let tenant_pool = Pool::<ScopeTenant, StateDisconnected>::connect_tenant(workspace_id).await?;
+    tenant_pool.migrate_database().await?;
+    Ok(())
+}
+
+/// Migrate the admin database and then the tenant database of every
+/// workspace.  Returns the IDs of the migrated workspaces.
+pub async fn migrate_all() -> Result<Vec<String>> {
+    init_admin_db().await?;
+    let workspaces = WorkspaceRepository::from_pool(Pool::connect_admin().await?)
+        .await?
+        .all()
+        .await?;
+    let mut migrated = Vec::with_capacity(workspaces.len());
+    for workspace in workspaces {
+        let workspace_id = workspace.get_id().to_string();
+        init_tenant_db(&workspace_id)
+            .await
+            .with_context(|| format!("failed to migrate workspace {workspace_id}"))?;
+        migrated.push(workspace_id);
+    }
+    Ok(migrated)
+}
+
 /// Returns `true` if at least one user exists, meaning setup has already been run.
 pub async fn is_setup_complete() -> Result<bool> {
     let pool = Pool::connect_admin().await?;
@@ -53,63 +84,100 @@ pub async fn setup_application(
         anyhow::bail!("application is already set up");
     }
 
+    create_workspace_owner(
+        UserId::new(),
+        username,
+        email,
+        &password,
+        WorkspaceId::new(),
+        Some(workspace_name),
+    )
+    .await
+}
+
+/// Set up the local replica of the desktop app for a user and workspace of
+/// the server, keeping their IDs so that synced events refer to the same
+/// aggregates.  The password is hashed locally, so the user can sign in
+/// while offline.
+pub async fn setup_replica(
+    user_id: &str,
+    name: String,
+    email: String,
+    password: &str,
+    workspace_id: &str,
+    workspace_name: Option<String>,
+) -> Result<()> {
+    let pool = Pool::connect_admin().await?;
+    if UserRepository::from_pool(pool)
+        .await?
+        .has_at_least_one_user()
+        .await?
+    {
+        anyhow::bail!("application is already set up");
+    }
+    create_workspace_owner(
+        user_id.parse()?,
+        name,
+        email,
+        password,
+        workspace_id.parse()?,
+        workspace_name,
+    )
+    .await
+}
+
+/// Create a user, a workspace with an "admin" role held by that user, and
+/// the workspace's tenant database.
+async fn create_workspace_owner(
+    user_id: UserId,
+    username: String,
+    email: String,
+    password: &str,
+    workspace_id: WorkspaceId,
+    workspace_name: Option<String>,
+) -> Result<()> {
+    let pool = Pool::connect_admin().await?;
+    let user_repo = UserRepository::from_pool(pool.clone()).await?;
+
     // 1. Create the admin user.
-    let password = hash_password(&password)?;
-    let user_id = UserId::new();
-    let mut user_root = Root::<loom_core::admin::user::User>::record_new(
+    let password = hash_password(password)?;
+    let mut user_root = Root::<loom_core::admin::user::User>::record_new(crate::audit::envelope(
         UserEvent::Created {
             id: user_id.clone(),
             name: username,
             email,
             password,
-        }
-        .into(),
-    )?;
+        },
+    ))?;
     user_repo.save(&mut user_root).await?;
 
     // 2. Create the workspace (save first so the projection row exists before the role).
-    let workspace_id = WorkspaceId::new();
     let workspace_repo = WorkspaceRepository::from_pool(pool.clone()).await?;
-    let mut workspace_root = Root::<Workspace>::record_new(
-        WorkspaceEvent::Created {
+    let mut workspace_root =
+        Root::<Workspace>::record_new(crate::audit::envelope(WorkspaceEvent::Created {
             id: workspace_id.clone(),
-            name: Some(workspace_name),
-        }
-        .into(),
-    )?;
+            name: workspace_name,
+        }))?;
     workspace_repo.save(&mut workspace_root).await?;
 
     // 3. Create the "admin" role for this workspace.
     let role_repo = WorkspaceRoleRepository::from_pool(pool.clone()).await?;
     let role_id = WorkspaceRoleId::new();
-    let mut role_root = Root::<WorkspaceRole>::record_new(
-        WorkspaceRoleEvent::Created {
+    let mut role_root =
+        Root::<WorkspaceRole>::record_new(crate::audit::envelope(WorkspaceRoleEvent::Created {
             id: role_id.clone(),
             workspace_id: workspace_id.clone(),
             name: Some("admin".to_string()),
-        }
-        .into(),
-    )?;
+        }))?;
     role_repo.save(&mut role_root).await?;
 
     // 4. Assign the user to the workspace with the admin role.
-    workspace_root.record_that(
-        WorkspaceEvent::UserRoleAssigned {
-            user_id,
-            workspace_role_id: role_id,
-        }
-        .into(),
-    )?;
+    workspace_root.record_that(crate::audit::envelope(WorkspaceEvent::UserRoleAssigned {
+        user_id,
+        workspace_role_id: role_id,
+    }))?;
     workspace_repo.save(&mut workspace_root).await?;
 
     // 5. Create and migrate the tenant database for this workspace.
-    let tenant_token = workspace_id.to_string();
-    let default_pool = Pool::<ScopeDefault, StateDisconnected>::connect_default().await?;
-    Initializer::new(SqliteInitializationStrategy)
-        .initialize_tenant(&default_pool, Some(&tenant_token))
-        .await?;
-    let tenant_pool = Pool::<ScopeTenant, StateDisconnected>::connect_tenant(&tenant_token).await?;
-    tenant_pool.migrate_database().await?;
-
-    Ok(())
+    init_tenant_db(&workspace_id.to_string()).await
 }
@@ -0,0 +1,333 @@
+//! The replica side of offline sync: signing in to the server, pulling its
+//! events and pushing local timesheet changes.
+
+use std::{sync::LazyLock, time::Duration};
+
+use anyhow::{Result, bail};
+use chrono::{SecondsFormat, Utc};
+use loom_infrastructure_impl::{
+    ConnectedTenantPool, RawEvent,
+    sync::repositories::{ConflictRow, SyncRepository},
+};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use super::{
+    Outcome, StreamChanges, StreamResult, SyncBatch, SyncEvent, SyncPush, server::MAX_PULL,
+};
+pub use crate::remote::{Account, SignInRequired};
+use crate::{
+    error::ValidationError,
+    remote::{Credentials, Remote, normalize_server, sign_in},
+};
+
+/// Keys of `sync_state`.
+const SERVER_KEY: &str = "server";
+const SESSION_KEY: &str = "session";
+const POSITION_KEY: &str = "position";
+const LAST_SYNC_KEY: &str = "last_sync";
+const LAST_ERROR_KEY: &str = "last_error";
+
+/// Pause between two rounds of [`sync_forever`].
+const SYNC_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Keeps [`sync_forever`] and a sync started by hand from interleaving.
+static SYNC_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// The state of the replica as shown in the app.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncStatus {
+    /// Base URL of the server, once connected.
+    pub server: Option<String>,
+    pub signed_in: bool,
+    /// RFC 3339; the last sync that went through.
+    pub last_sync: Option<String>,
+    /// Why the last sync failed, e.g. because the server is unreachable.
+    pub last_error: Option<String>,
+    /// Local timesheet events the server has not confirmed yet.
+    pub pending: i64,
+    /// Local changes the server refused and the user has not dismissed.
+    pub conflicts: usize,
+}
+
+/// What one round of [`sync_once`] did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    pub pulled: usize,
+    pub pushed: usize,
+    pub conflicts: usize,
+}
+
+/// Sign in to `server`, setting up the local replica on first use.
+///
+/// # Errors
+///
+/// Returns a validation error if the URL is invalid, the credentials are
+/// wrong, or the replica already holds another user or workspace.
+pub async fn connect(
+    server: &str,
+    email: &str,
+    password: &str,
+    workspace: Option<&str>,
+) -> Result<Account> {
+    let server = normalize_server(server)?;
+    let (account, session) = sign_in(&server, email, password, workspace).await?;
+
+    match super::replica::replica_workspace().await? {
+        None if !crate::setup::is_setup_complete().await? => {
+            crate::setup::setup_replica(
+                &account.user_id,
+                account.name.clone(),
+                account.email.clone(),
+                password,
+                &account.workspace_id,
+                account.workspace_name.clone(),
+            )
+            .await?;
+        }
+        Some(workspace_id) if workspace_id == account.workspace_id => {}
+        _ => {
+            return Err(
+                ValidationError::new("This app already tracks time for another workspace").into(),
+            );
+        }
+    }
+
+    let repo = SyncRepository::new(crate::tenant::tenant_pool(&account.workspace_id).await?);
+    repo.set_state(SERVER_KEY, &server).await?;
+    repo.set_state(SESSION_KEY, &session).await?;
+    Ok(account)
+}
+
+/// Pull the server's events, then push the local timesheet changes.
+///
+/// # Errors
+///
+/// Returns [`SignInRequired`] if the server session has expired, or an
+/// error if the server cannot be reached.
+pub async fn sync_once(workspace_id: &str) -> Result<SyncReport> {
+    let _guard = SYNC_LOCK.lock().await;
+    let pool = crate::tenant::tenant_pool(workspace_id).await?;
+    let repo = SyncRepository::new(pool.clone());
+
+    let result: Result<SyncReport> = async {
+        let Some(server) = repo.state(SERVER_KEY).await? else {
+            bail!("Not connected to a server");
+        };
+        let Some(session) = repo.state(SESSION_KEY).await? else {
+            return Err(SignInRequired.into());
+        };
+        let remote = Remote::new(&server, Credentials::Session(session))?;
+        let mut report = SyncReport::default();
+        pull(&remote, &repo, &pool, &mut report).await?;
+        push(&remote, &repo, &pool, &mut report).await?;
+        if report.pushed > 0 {
+            pull(&remote, &repo, &pool, &mut report).await?;
+        }
+        Ok(report)
+    }
+    .await;
+
+    match &result {
+        Ok(_) => {
+            repo.set_state(LAST_SYNC_KEY, &now()).await?;
+            repo.clear_state(LAST_ERROR_KEY).await?;
+        }
+        Err(e) => {
+            if e.is::<SignInRequired>() {
+                repo.clear_state(SESSION_KEY).await?;
+            }
+            repo.set_state(LAST_ERROR_KEY, &format!("{e:#}")).await?;
+        }
+    }
+    result
+}
+
+/// Sync every few seconds, for as long as the task runs.  Failures, e.g.
+/// while offline, are logged and retried in the next round.
+pub async fn sync_forever(workspace_id: &str) {
+    loop {
+        if let Err(e) = sync_once(workspace_id).await {
+            warn!(workspace_id, error = %e, "Sync failed");
+        }
+        tokio::time::sleep(SYNC_INTERVAL).await;
+    }
+}
+
+async fn pull(
+    remote: &Remote,
+    repo: &SyncRepository,
+    pool: &ConnectedTenantPool,
+    report: &mut SyncReport,
+) -> Result<()> {
+    let mut after: i64 = repo
+        .state(POSITION_KEY)
+        .await?
+        .and_then(|p| p.parse().ok())
+        .unwrap_or_default();
+    loop {
+        let batch: SyncBatch = serde_json::from_str(
+            &remote
+                .get(&format!(
+                    "/api/v1/sync/events?after={after}&limit={MAX_PULL}"
+                ))
+                .await?,
+        )?;
+        for event in batch.events {
+            receive(repo, pool, event.into_raw()?, report).await?;
+        }
+        if batch.position <= after {
+            return Ok(());
+        }
+        after = batch.position;
+        repo.set_state(POSITION_KEY, &after.to_string()).await?;
+    }
+}
+
+/// Store a server event unless the replica has it already.  Local changes
+/// the server has not seen yet lose against it.
+async fn receive(
+    repo: &SyncRepository,
+    pool: &ConnectedTenantPool,
+    event: RawEvent,
+    report: &mut SyncReport,
+) -> Result<()> {
+    let version: i64 = event.version.try_into().unwrap_or_default();
+    let synced = repo.synced_version(&event.stream_id).await?;
+    if version <= synced {
+        // Pushed from here, or pulled before.
+        return Ok(());
+    }
+    let unconfirmed = repo
+        .stream_events(&event.stream_id)
+        .await?
+        .iter()
+        .any(|e| i64::try_from(e.version).unwrap_or_default() > synced);
+    if unconfirmed {
+        set_aside(
+            repo,
+            pool,
+            &event.stream_id,
+            version,
+            "Changed on the server while you were offline",
+        )
+        .await?;
+        report.conflicts += 1;
+    }
+    repo.append(&event).await?;
+    report.pulled += 1;
+    Ok(())
+}
+
+async fn push(
+    remote: &Remote,
+    repo: &SyncRepository,
+    pool: &ConnectedTenantPool,
+    report: &mut SyncReport,
+) -> Result<()> {
+    let mut streams: Vec<StreamChanges> = Vec::new();
+    for event in repo.pending().await? {
+        let event = SyncEvent::from_raw(&event)?;
+        if let Some(stream) = streams.iter_mut().find(|s| s.stream_id == event.stream_id) {
+            stream.events.push(event);
+        } else {
+            streams.push(StreamChanges {
+                stream_id: event.stream_id.clone(),
+                base_version: repo.synced_version(&event.stream_id).await?,
+                events: vec![event],
+            });
+        }
+    }
+    if streams.is_empty() {
+        return Ok(());
+    }
+
+    let body = serde_json::to_string(&SyncPush { streams })?;
+    let results: Vec<StreamResult> =
+        serde_json::from_str(&remote.post("/api/v1/sync/timesheets", body).await?)?;
+    for result in results {
+        match result.outcome {
+            // The server records the changes with its own rates and
+            // rounding; its events replace the local ones on the next pull.
+            Outcome::Accepted => {
+                repo.discard_pending(&result.stream_id).await?;
+                super::replica::reproject_timesheet(pool, &result.stream_id).await?;
+                report.pushed += 1;
+            }
+            // The server's events are pulled and win in the next round.
+            Outcome::Conflict => {}
+            Outcome::Rejected => {
+                set_aside(
+                    repo,
+                    pool,
+                    &result.stream_id,
+                    result.version,
+                    result.reason.as_deref().unwrap_or("Refused by the server"),
+                )
+                .await?;
+                report.conflicts += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Drop the unconfirmed local events of a stream, keep them as a conflict
+/// and bring the timesheet's projection back in line.
+async fn set_aside(
+    repo: &SyncRepository,
+    pool: &ConnectedTenantPool,
+    stream_id: &str,
+    server_version: i64,
+    reason: &str,
+) -> Result<()> {
+    let discarded = repo.discard_pending(stream_id).await?;
+    let local = discarded
+        .iter()
+        .map(SyncEvent::from_raw)
+        .collect::<Result<Vec<_>>>()?;
+    repo.record_conflict(
+        stream_id,
+        server_version,
+        &serde_json::to_string(&local)?,
+        reason,
+    )
+    .await?;
+    if discarded
+        .iter()
+        .any(|e| e.event_type.starts_with("Timesheet"))
+    {
+        super::replica::reproject_timesheet(pool, stream_id).await?;
+    }
+    Ok(())
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// # Errors
+///
+/// Returns an error if the replica cannot be read.
+pub async fn status(workspace_id: &str) -> Result<SyncStatus> {
+    let repo = SyncRepository::new(crate::tenant::tenant_pool(workspace_id).await?);
+    Ok(SyncStatus {
+        server: repo.state(SERVER_KEY).await?,
+        signed_in: repo.state(SESSION_KEY).await?.is_some(),
+        last_sync: repo.state(LAST_SYNC_KEY).await?,
+        last_error: repo.state(LAST_ERROR_KEY).await?,
+        pending: repo.pending_count().await?,
+        conflicts: repo.conflicts().await?.len(),
+    })
+}
+
+/// Local changes the server refused, newest first.
+pub async fn conflicts(workspace_id: &str) -> Result<Vec<ConflictRow>> {
+    let repo = SyncRepository::new(crate::tenant::tenant_pool(workspace_id).await?);
+    Ok(repo.conflicts().await?)
+}
+
+pub async fn dismiss_conflict(workspace_id: &str, id: i64) -> Result<()> {
+    let repo = SyncRepository::new(crate::tenant::tenant_pool(workspace_id).await?);
+    Ok(repo.dismiss_conflict(id).await?)
+}
@@ -0,0 +1,120 @@
+//! Offline time tracking: a local replica of one workspace that syncs its
+//! timesheets with the server.
+//!
+//! The desktop app runs the regular controllers against a local SQLite
+//! replica set up with [`crate::setup::setup_replica`].  [`client::sync_once`]
+//! first pulls the server's events (see [`server::events_after`]) into it
+//! and then pushes the timesheet events recorded locally since
+//! (see [`server::apply_timesheet_changes`]).  The server replays them the
+//! way its controllers record online changes, with its own rounding and
+//! rates, and the replica swaps its events for the server's.
+//!
+//! Each stream remembers the last version the server has confirmed.  When
+//! the server has moved on in the meantime — the same entry was edited in
+//! the web app while the laptop was offline — the server wins: the local
+//! changes are dropped and kept in `sync_conflicts` for the user to redo.
+//! Only the changes of [`TimesheetEvent::is_offline_change`] can be pushed.
+//!
+//! [`TimesheetEvent::is_offline_change`]: loom_core::tenant::timesheet::TimesheetEvent::is_offline_change
+
+pub mod client;
+pub mod replica;
+pub mod server;
+
+use anyhow::Result;
+use loom_infrastructure_impl::RawEvent;
+use serde::{Deserialize, Serialize};
+
+/// One event as exchanged between server and replica.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncEvent {
+    pub stream_id: String,
+    pub version: i64,
+    /// Global position on the side that sent the event.
+    pub position: i64,
+    pub event_type: String,
+    /// The event as stored in the event store, JSON.
+    pub payload: String,
+    /// The envelope metadata, a JSON object.
+    pub metadata: String,
+    pub schema_version: i64,
+}
+
+impl SyncEvent {
+    /// # Errors
+    ///
+    /// Returns an error if the payload is not UTF-8 or the metadata cannot
+    /// be serialized.
+    pub fn from_raw(event: &RawEvent) -> Result<Self> {
+        Ok(Self {
+            stream_id: event.stream_id.clone(),
+            version: event.version.try_into().unwrap_or_default(),
+            position: event.global_position,
+            event_type: event.event_type.clone(),
+            payload: String::from_utf8(event.payload_bytes.clone())?,
+            metadata: serde_json::to_string(&event.metadata)?,
+            schema_version: event.schema_version.try_into().unwrap_or_default(),
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the metadata is not a JSON object.
+    pub fn into_raw(self) -> Result<RawEvent> {
+        Ok(RawEvent {
+            stream_id: self.stream_id,
+            version: self.version.try_into().unwrap_or_default(),
+            global_position: self.position,
+            event_type: self.event_type,
+            payload_bytes: self.payload.into_bytes(),
+            metadata: serde_json::from_str(&self.metadata)?,
+            schema_version: self.schema_version.try_into().unwrap_or_default(),
+        })
+    }
+}
+
+/// A page of server events.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncBatch {
+    pub events: Vec<SyncEvent>,
+    /// The global position the next pull continues after.  Equal to the
+    /// requested one once everything has been pulled.
+    pub position: i64,
+}
+
+/// The local changes of one timesheet.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamChanges {
+    pub stream_id: String,
+    /// The version the changes were recorded on; 0 for a new entry.
+    pub base_version: i64,
+    pub events: Vec<SyncEvent>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncPush {
+    pub streams: Vec<StreamChanges>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    /// The changes have been recorded.
+    #[default]
+    Accepted,
+    /// The entry has changed on the server since `base_version`.
+    Conflict,
+    /// The changes are not allowed, e.g. because the period is locked.
+    Rejected,
+}
+
+/// What became of the [`StreamChanges`] of one timesheet.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamResult {
+    pub stream_id: String,
+    pub outcome: Outcome,
+    /// The version of the entry on the server afterwards.
+    pub version: i64,
+    /// Why the changes were rejected.
+    pub reason: Option<String>,
+}
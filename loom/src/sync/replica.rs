@@ -0,0 +1,110 @@
+//! Background work of the desktop app's local replica.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use loom_infrastructure::query::Query;
+use loom_infrastructure_impl::{
+    BackoffConfig, ConnectedTenantPool, Pool, ProjectionDaemon, ProjectionRunner, ProjectionSource,
+    Projector, SqlCheckpoint,
+    admin::{projectors::AdminProjector, workspace::repositories::WorkspaceRepository},
+    projection_health::projectors::MonitoredProjector,
+    sync::repositories::SyncRepository,
+    tenant::{
+        projectors::TenantProjector, search::projectors::SearchProjector,
+        timesheet::projectors::TimesheetProjector,
+    },
+};
+use tracing::warn;
+
+/// Pause between two looks for the replica's workspace.
+const WORKSPACE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The workspace the replica holds, once connected to a server.
+pub async fn replica_workspace() -> Result<Option<String>> {
+    let pool = Pool::connect_admin().await?;
+    let repo = WorkspaceRepository::from_pool(pool).await?;
+    Ok(repo.all().await?.first().map(|w| w.get_id().to_string()))
+}
+
+fn backoff() -> BackoffConfig {
+    BackoffConfig {
+        min_idle_ms: 20,
+        max_idle_ms: 200,
+        ..Default::default()
+    }
+}
+
+/// Everything the replica needs in the background: the admin projections
+/// right away and, once connected to a workspace, its projections and the
+/// periodic sync (see [`super::client::sync_forever`]).
+///
+/// Runs until the task is cancelled.
+pub async fn run() -> Result<()> {
+    let admin_pool = Pool::connect_admin().await?;
+    ProjectionRunner::new(admin_pool.clone().into_pool(), ProjectionSource::AllStreams)
+        .run_migrations()
+        .await?;
+    let mut admin = ProjectionDaemon::new();
+    admin.register_with_config(
+        ProjectionRunner::new(admin_pool.clone().into_pool(), ProjectionSource::AllStreams),
+        MonitoredProjector::new(
+            "admin_projection",
+            admin_pool.clone(),
+            AdminProjector::new(admin_pool.clone()),
+        )
+        .with_max_retries(crate::projection_health::max_retries()),
+        SqlCheckpoint::new(admin_pool.clone().into_pool(), "admin_projection").await?,
+        backoff(),
+    );
+    tokio::spawn(async move { admin.run_until_cancelled().await });
+
+    let workspace_id = loop {
+        match replica_workspace().await {
+            Ok(Some(workspace_id)) => break workspace_id,
+            Ok(None) => {}
+            Err(e) => warn!(error = %e, "Failed to look up the replica workspace"),
+        }
+        tokio::time::sleep(WORKSPACE_POLL_INTERVAL).await;
+    };
+
+    let pool = crate::tenant::tenant_pool(&workspace_id).await?;
+    ProjectionRunner::new(pool.clone().into_pool(), ProjectionSource::AllStreams)
+        .run_migrations()
+        .await?;
+    let checkpoint_name = format!("tenant_projection_{workspace_id}");
+    let mut tenant = ProjectionDaemon::new();
+    tenant.register_with_config(
+        ProjectionRunner::new(pool.clone().into_pool(), ProjectionSource::AllStreams),
+        MonitoredProjector::new(
+            checkpoint_name.clone(),
+            pool.clone(),
+            TenantProjector::new(pool.clone()),
+        )
+        .with_max_retries(crate::projection_health::max_retries()),
+        SqlCheckpoint::new(pool.clone().into_pool(), &checkpoint_name).await?,
+        backoff(),
+    );
+    tokio::spawn(async move { tenant.run_until_cancelled().await });
+
+    super::client::sync_forever(&workspace_id).await;
+    Ok(())
+}
+
+/// Rebuild the projection row and search entry of a timesheet from the
+/// events left in its stream, after unconfirmed local events have been
+/// dropped.
+pub(super) async fn reproject_timesheet(pool: &ConnectedTenantPool, stream_id: &str) -> Result<()> {
+    let mut projector = TimesheetProjector::new(pool.clone());
+    projector.forget(stream_id).await?;
+    for event in SyncRepository::new(pool.clone())
+        .stream_events(stream_id)
+        .await?
+    {
+        projector.handle(event).await?;
+    }
+    SearchProjector::new(pool.clone())
+        .index("timesheet", stream_id)
+        .await?;
+    Ok(())
+}
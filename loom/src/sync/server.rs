@@ -0,0 +1,464 @@
+//! The server side of offline sync, behind `/api/v1/sync`.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use eventually::{
+    aggregate::{
+        Root,
+        repository::{GetError, Getter, SaveError, Saver},
+    },
+    message::Message,
+};
+use loom_core::{
+    permissions,
+    tenant::{
+        approval::ApprovalEvent,
+        calendar_rule::CalendarRuleEvent,
+        favorite::FavoriteEvent,
+        report_schedule::ReportScheduleEvent,
+        timesheet::{AggregateError, DomainError, Timesheet, TimesheetEvent, TimesheetId},
+        work_contract::WorkContractEvent,
+    },
+};
+use loom_infrastructure_impl::{
+    ConnectedTenantPool, sync::repositories::SyncRepository,
+    tenant::timesheet::repositories::TimesheetRepository,
+};
+
+use super::{Outcome, StreamChanges, StreamResult, SyncBatch, SyncEvent};
+use crate::{authorization::AuthorizationService, error::ValidationError};
+
+/// Most events handed out by one pull.
+pub const MAX_PULL: i64 = 500;
+
+/// Who is handed the events of an aggregate by a pull.
+#[derive(Debug, Clone, Copy)]
+enum Audience {
+    /// Every member: what time entries refer to.
+    Everyone,
+    /// The user the aggregate belongs to, and everyone holding the
+    /// permission, if any.
+    Owner(Option<&'static str>),
+}
+
+/// The aggregates a pull hands out, by event type prefix.  Events of any
+/// other aggregate, such as webhooks with their secrets, are left out.
+const PULLED: [(&str, Audience); 11] = [
+    ("Customer", Audience::Everyone),
+    ("Project", Audience::Everyone),
+    ("Activity", Audience::Everyone),
+    ("Tag", Audience::Everyone),
+    ("Holiday", Audience::Everyone),
+    ("Timesheet", Audience::Owner(None)),
+    ("Favorite", Audience::Owner(None)),
+    ("CalendarRule", Audience::Owner(None)),
+    ("ReportSchedule", Audience::Owner(None)),
+    (
+        "Approval",
+        Audience::Owner(Some(permissions::TIMESHEET_APPROVE)),
+    ),
+    (
+        "WorkContract",
+        Audience::Owner(Some(permissions::WORK_CONTRACT_MANAGE)),
+    ),
+];
+
+/// Events after the global position `after`, as seen by `user_id`: the
+/// shared catalogue of the workspace, and of the aggregates that belong to a
+/// user only those of `user_id` unless a permission shows everyone's.
+pub async fn events_after(
+    workspace_id: &str,
+    user_id: &str,
+    after: i64,
+    limit: i64,
+) -> Result<SyncBatch> {
+    let pool = crate::tenant::tenant_pool(workspace_id).await?;
+    let repo = SyncRepository::new(pool);
+    let raw = repo.events_after(after, limit.clamp(1, MAX_PULL)).await?;
+    let position = raw.last().map_or(after, |e| e.global_position);
+
+    let admin = AuthorizationService::is_admin_in(user_id, workspace_id).await?;
+    let mut granted: HashMap<&str, bool> = HashMap::new();
+    let mut own_streams: HashMap<String, bool> = HashMap::new();
+    let mut events = Vec::with_capacity(raw.len());
+    for event in raw {
+        let Some((_, audience)) = PULLED
+            .iter()
+            .find(|(prefix, _)| event.event_type.starts_with(prefix))
+        else {
+            continue;
+        };
+        if let Audience::Owner(permission) = *audience {
+            let everyone = match permission {
+                None => false,
+                Some(_) if admin => true,
+                Some(permission) => match granted.get(permission) {
+                    Some(granted) => *granted,
+                    None => {
+                        let has =
+                            AuthorizationService::has_permission(user_id, workspace_id, permission)
+                                .await?;
+                        granted.insert(permission, has);
+                        has
+                    }
+                },
+            };
+            if !everyone {
+                let own = match own_streams.get(&event.stream_id) {
+                    Some(own) => *own,
+                    None => {
+                        let own = owner(&repo, &event.stream_id).await?.as_deref() == Some(user_id);
+                        own_streams.insert(event.stream_id.clone(), own);
+                        own
+                    }
+                };
+                if !own {
+                    continue;
+                }
+            }
+        }
+        events.push(SyncEvent::from_raw(&event)?);
+    }
+    Ok(SyncBatch { events, position })
+}
+
+/// The user an aggregate belongs to, read from the event that created the
+/// stream `stream_id`: who tracked a timesheet, requested an approval,
+/// pinned a favorite and so on.  `None` for shared aggregates.
+pub(crate) async fn owner(repo: &SyncRepository, stream_id: &str) -> Result<Option<String>> {
+    let Some(first) = repo.first_event(stream_id).await? else {
+        return Ok(None);
+    };
+    let payload = first.payload_bytes.as_slice();
+    let owner = match first.event_type.as_str() {
+        "TimesheetStarted" => match serde_json::from_slice(payload)? {
+            TimesheetEvent::Started { user_id, .. } => Some(user_id),
+            _ => None,
+        },
+        "ApprovalRequested" => match serde_json::from_slice(payload)? {
+            ApprovalEvent::Requested { user_id, .. } => Some(user_id),
+            _ => None,
+        },
+        "WorkContractDefined" => match serde_json::from_slice(payload)? {
+            WorkContractEvent::Defined { user_id, .. } => Some(user_id),
+            _ => None,
+        },
+        "FavoritePinned" => match serde_json::from_slice(payload)? {
+            FavoriteEvent::Pinned { user_id, .. } => Some(user_id),
+            _ => None,
+        },
+        "CalendarRuleAdded" => match serde_json::from_slice(payload)? {
+            CalendarRuleEvent::Added { user_id, .. } => Some(user_id),
+            _ => None,
+        },
+        "ReportScheduleCreated" => match serde_json::from_slice(payload)? {
+            ReportScheduleEvent::Created { owner_id, .. } => Some(owner_id),
+            _ => None,
+        },
+        _ => None,
+    };
+    Ok(owner.map(|owner| owner.to_string()))
+}
+
+/// The permission needed to push `event`.
+#[must_use]
+pub const fn required_permission(event: &TimesheetEvent) -> &'static str {
+    match event {
+        TimesheetEvent::Started { .. } => permissions::TIMESHEET_CREATE,
+        TimesheetEvent::Deleted => permissions::TIMESHEET_DELETE,
+        _ => permissions::TIMESHEET_UPDATE,
+    }
+}
+
+/// The timesheet event a pushed event carries.
+///
+/// # Errors
+///
+/// Returns a validation error if the payload is unreadable or its type does
+/// not match the event's `event_type`.
+pub fn decode(event: &SyncEvent) -> Result<TimesheetEvent> {
+    let decoded: TimesheetEvent = serde_json::from_str(&event.payload)
+        .map_err(|e| ValidationError::new(format!("Unreadable event: {e}")))?;
+    if decoded.name() != event.event_type {
+        return Err(ValidationError::new(format!(
+            "{} was sent as {}",
+            decoded.name(),
+            event.event_type
+        ))
+        .into());
+    }
+    Ok(decoded)
+}
+
+/// Record the offline changes of `user_id` to their own timesheets.
+///
+/// Each timesheet is handled on its own: its changes are recorded together
+/// if the entry is still at `base_version` on the server and passes the
+/// checks of the online controllers, and not at all otherwise.  Only the
+/// times and fields the replica recorded are taken over; amounts are
+/// worked out anew.
+///
+/// # Errors
+///
+/// Returns an error only if the server fails; changes it refuses are
+/// reported in the stream's result.
+pub async fn apply_timesheet_changes(
+    workspace_id: &str,
+    user_id: &str,
+    changes: Vec<StreamChanges>,
+) -> Result<Vec<StreamResult>> {
+    let pool = crate::tenant::tenant_pool(workspace_id).await?;
+    let repo = TimesheetRepository::from_pool(pool.clone()).await?;
+
+    let mut results = Vec::with_capacity(changes.len());
+    for stream in changes {
+        let stream_id = stream.stream_id.clone();
+        let result = match apply_stream(workspace_id, user_id, &pool, &repo, stream).await {
+            Ok(result) => result,
+            Err(e) => {
+                let version = server_version(&repo, &stream_id.parse()?).await?;
+                refusal(stream_id, version, &e).ok_or(e)?
+            }
+        };
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// The result of a stream whose changes failed with `e`: rejected if they
+/// break a rule or do not apply to the entry, a conflict if someone else
+/// changed the entry meanwhile.  `None` if the server itself failed.
+fn refusal(stream_id: String, version: i64, e: &anyhow::Error) -> Option<StreamResult> {
+    let rejected = |reason: String| StreamResult {
+        stream_id: stream_id.clone(),
+        outcome: Outcome::Rejected,
+        version,
+        reason: Some(reason),
+    };
+    if let Some(invalid) = e.downcast_ref::<ValidationError>() {
+        return Some(rejected(invalid.to_string()));
+    }
+    if let Some(refused) = e.downcast_ref::<DomainError>() {
+        return Some(rejected(refused.to_string()));
+    }
+    if let Some(refused) = e.downcast_ref::<AggregateError>() {
+        return Some(rejected(refused.to_string()));
+    }
+    if let Some(SaveError::Conflict(_)) = e.downcast_ref::<SaveError>() {
+        return Some(StreamResult {
+            stream_id,
+            outcome: Outcome::Conflict,
+            version,
+            reason: None,
+        });
+    }
+    None
+}
+
+async fn server_version(repo: &TimesheetRepository, id: &TimesheetId) -> Result<i64> {
+    match repo.get(id).await {
+        Ok(root) => Ok(root.version().try_into().unwrap_or_default()),
+        Err(GetError::NotFound) => Ok(0),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn apply_stream(
+    workspace_id: &str,
+    user_id: &str,
+    pool: &ConnectedTenantPool,
+    repo: &TimesheetRepository,
+    changes: StreamChanges,
+) -> Result<StreamResult> {
+    let timesheet_id: TimesheetId = changes.stream_id.parse()?;
+    let current = match repo.get(&timesheet_id).await {
+        Ok(root) => Some(root),
+        Err(GetError::NotFound) => None,
+        Err(e) => return Err(e.into()),
+    };
+    let version: i64 = current
+        .as_ref()
+        .map_or(0, |root| root.version().try_into().unwrap_or_default());
+    if version != changes.base_version {
+        return Ok(StreamResult {
+            stream_id: changes.stream_id,
+            outcome: Outcome::Conflict,
+            version,
+            reason: None,
+        });
+    }
+
+    let mut events = Vec::with_capacity(changes.events.len());
+    for event in &changes.events {
+        let event = decode(event)?;
+        if !event.is_offline_change() {
+            return Err(ValidationError::new(format!(
+                "{} cannot be recorded offline",
+                event.name()
+            ))
+            .into());
+        }
+        events.push(event);
+    }
+    let mut events = events.into_iter();
+
+    let new = current.is_none();
+    let mut root = match current {
+        Some(root) => {
+            if root.user_id().to_string() != user_id {
+                return Err(ValidationError::new("Not your time entry").into());
+            }
+            crate::tenant::timesheet::ensure_entry_unlocked(workspace_id, user_id, &root).await?;
+            root
+        }
+        None => match events.next() {
+            Some(TimesheetEvent::Started {
+                id,
+                user_id: owner,
+                project_id,
+                activity_id,
+                start_time,
+                timezone,
+                billable,
+            }) => {
+                if owner.to_string() != user_id {
+                    return Err(ValidationError::new("Not your time entry").into());
+                }
+                if id != timesheet_id {
+                    return Err(ValidationError::new("The timer belongs to another entry").into());
+                }
+                let start_time = DateTime::parse_from_rfc3339(&start_time)
+                    .map_err(|e| ValidationError::new(format!("Invalid start time: {e}")))?
+                    .with_timezone(&Utc)
+                    .to_rfc3339();
+                Root::<Timesheet>::record_new(crate::audit::envelope(TimesheetEvent::Started {
+                    id,
+                    user_id: owner,
+                    project_id,
+                    activity_id,
+                    start_time,
+                    timezone,
+                    billable,
+                }))?
+            }
+            _ => {
+                return Err(
+                    ValidationError::new("A new time entry must start with a timer").into(),
+                );
+            }
+        },
+    };
+    // The replica's amounts, rounding and breaks are not taken over; the
+    // changes are replayed as the controllers would record them online.
+    let mut deleted = false;
+    for event in events {
+        deleted = matches!(event, TimesheetEvent::Deleted);
+        crate::tenant::timesheet::replay_offline_change(workspace_id, pool, &mut root, event)
+            .await?;
+    }
+    if !deleted {
+        crate::tenant::timesheet::check_offline_entry(workspace_id, pool, &root, new).await?;
+    }
+    repo.save(&mut root).await?;
+
+    Ok(StreamResult {
+        stream_id: changes.stream_id,
+        outcome: Outcome::Accepted,
+        version: root.version().try_into().unwrap_or_default(),
+        reason: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use eventually::{aggregate::Aggregate, version::ConflictError};
+    use loom_core::{shared::AggregateId, tenant::approval::ApprovalId};
+
+    use super::*;
+
+    fn started() -> Timesheet {
+        Timesheet::apply(
+            None,
+            TimesheetEvent::Started {
+                id: TimesheetId::new(),
+                user_id: AggregateId::new(),
+                project_id: None,
+                activity_id: None,
+                start_time: "2026-10-19T09:00:00+00:00".to_string(),
+                timezone: "UTC".to_string(),
+                billable: true,
+            },
+        )
+        .unwrap()
+    }
+
+    /// What the push loop reports for each stream, given how applying it
+    /// went.
+    fn settle(attempts: Vec<(&str, Result<StreamResult>)>) -> Result<Vec<StreamResult>> {
+        attempts
+            .into_iter()
+            .map(|(stream_id, attempt)| {
+                attempt.or_else(|e| refusal(stream_id.to_string(), 2, &e).ok_or(e))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_refused_stream_does_not_fail_the_push() {
+        let accepted = StreamResult {
+            stream_id: "valid".to_string(),
+            outcome: Outcome::Accepted,
+            version: 3,
+            reason: None,
+        };
+        let deleted = Timesheet::apply(Some(started()), TimesheetEvent::Deleted).unwrap();
+        let on_deleted = Timesheet::apply(
+            Some(deleted),
+            TimesheetEvent::Updated {
+                description: None,
+                billable: false,
+            },
+        )
+        .unwrap_err();
+        let submitted = Timesheet::apply(
+            Some(started()),
+            TimesheetEvent::Submitted {
+                approval_id: ApprovalId::new(),
+            },
+        )
+        .unwrap();
+        let approved = Timesheet::apply(Some(submitted), TimesheetEvent::Approved).unwrap();
+        let on_approved = Timesheet::apply(Some(approved), TimesheetEvent::Deleted).unwrap_err();
+
+        let results = settle(vec![
+            ("valid", Ok(accepted.clone())),
+            ("deleted", Err(on_deleted.into())),
+            ("approved", Err(on_approved.into())),
+            (
+                "moved-on",
+                Err(SaveError::Conflict(ConflictError {
+                    expected: 1,
+                    actual: 2,
+                })
+                .into()),
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(results[0], accepted);
+        for (result, stream_id) in results[1..3].iter().zip(["deleted", "approved"]) {
+            assert_eq!(result.stream_id, stream_id);
+            assert_eq!(result.outcome, Outcome::Rejected);
+            assert_eq!(result.version, 2);
+            assert!(result.reason.is_some());
+        }
+        assert_eq!(results[3].outcome, Outcome::Conflict);
+    }
+
+    #[test]
+    fn server_failures_fail_the_push() {
+        assert!(settle(vec![("broken", Err(anyhow::anyhow!("database is gone")))]).is_err());
+    }
+}
@@ -7,7 +7,10 @@ use loom_core::tenant::{
     activity::{Activity, ActivityEvent, ActivityId, CreateActivityInput, UpdateActivityInput},
     project::ProjectId,
 };
-use loom_infrastructure_impl::tenant::activity::repositories::{ActivityRepository, ActivityRow};
+use loom_infrastructure_impl::tenant::{
+    activity::repositories::{ActivityRepository, ActivityRow},
+    timesheet::repositories::TimesheetRepository,
+};
 
 pub async fn list(workspace_id: &str) -> Result<Vec<ActivityRow>> {
     let pool = super::tenant_pool(workspace_id).await?;
@@ -15,6 +18,12 @@ pub async fn list(workspace_id: &str) -> Result<Vec<ActivityRow>> {
     Ok(repo.all().await?)
 }
 
+pub async fn list_archived(workspace_id: &str) -> Result<Vec<ActivityRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ActivityRepository::from_pool(pool).await?;
+    Ok(repo.archived().await?)
+}
+
 pub async fn create(
     workspace_id: &str,
     project_id: Option<String>,
@@ -26,14 +35,11 @@ pub async fn create(
     let repo = ActivityRepository::from_pool(pool).await?;
     let id = ActivityId::new();
     let pid: Option<ProjectId> = project_id.as_deref().map(str::parse).transpose()?;
-    let mut root = Root::<Activity>::record_new(
-        ActivityEvent::Created {
-            id: id.clone(),
-            project_id: pid.clone(),
-            name: name.clone(),
-        }
-        .into(),
-    )?;
+    let mut root = Root::<Activity>::record_new(crate::audit::envelope(ActivityEvent::Created {
+        id: id.clone(),
+        project_id: pid.clone(),
+        name: name.clone(),
+    }))?;
     repo.save(&mut root).await?;
     Ok(ActivityRow {
         id: id.to_string(),
@@ -42,6 +48,7 @@ pub async fn create(
         comment: None,
         visible: true,
         billable: true,
+        archived: false,
     })
 }
 
@@ -59,15 +66,56 @@ pub async fn update(
     let repo = ActivityRepository::from_pool(pool).await?;
     let agg_id: ActivityId = id.parse()?;
     let mut root = repo.get(&agg_id).await?;
-    root.record_that(
-        ActivityEvent::Updated {
-            name,
-            comment,
-            visible,
-            billable,
-        }
-        .into(),
-    )?;
+    root.record_that(crate::audit::envelope(ActivityEvent::Updated {
+        name,
+        comment,
+        visible,
+        billable,
+    }))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+pub async fn archive(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ActivityRepository::from_pool(pool).await?;
+    let agg_id: ActivityId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    if root.archived() {
+        return Err(crate::error::ValidationError::new("The activity is already archived").into());
+    }
+    root.record_that(crate::audit::envelope(ActivityEvent::Archived))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+pub async fn restore(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ActivityRepository::from_pool(pool).await?;
+    let agg_id: ActivityId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    if !root.archived() {
+        return Err(crate::error::ValidationError::new("The activity is not archived").into());
+    }
+    root.record_that(crate::audit::envelope(ActivityEvent::Restored))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// Permanently delete an activity no timesheet is booked on.
+pub async fn delete(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let timesheets = TimesheetRepository::from_pool(pool.clone()).await?;
+    if timesheets.count_for_activity(id).await? > 0 {
+        return Err(crate::error::ValidationError::new(
+            "Timesheets are booked on the activity — archive it instead",
+        )
+        .into());
+    }
+    let repo = ActivityRepository::from_pool(pool).await?;
+    let agg_id: ActivityId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    root.record_that(crate::audit::envelope(ActivityEvent::Deleted))?;
     repo.save(&mut root).await?;
     Ok(())
 }
@@ -38,22 +38,20 @@ pub async fn set_default(
     if let Some(existing) = repo.default_for_activity(&activity_id).await? {
         let existing_id: ActivityRateId = existing.id.parse()?;
         let mut root = repo.get(&existing_id).await?;
-        root.record_that(ActivityRateEvent::Removed.into())?;
+        root.record_that(crate::audit::envelope(ActivityRateEvent::Removed))?;
         repo.save(&mut root).await?;
     }
 
     let id = ActivityRateId::new();
     let aid: ActivityId = activity_id.parse()?;
-    let mut root = Root::<ActivityRate>::record_new(
-        ActivityRateEvent::Set {
+    let mut root =
+        Root::<ActivityRate>::record_new(crate::audit::envelope(ActivityRateEvent::Set {
             id: id.clone(),
             activity_id: aid,
             user_id: None,
             hourly_rate,
             internal_rate,
-        }
-        .into(),
-    )?;
+        }))?;
     repo.save(&mut root).await?;
 
     Ok(ActivityRateRow {
@@ -71,7 +69,7 @@ pub async fn remove_default(workspace_id: &str, activity_id: &str) -> Result<()>
     if let Some(existing) = repo.default_for_activity(activity_id).await? {
         let existing_id: ActivityRateId = existing.id.parse()?;
         let mut root = repo.get(&existing_id).await?;
-        root.record_that(ActivityRateEvent::Removed.into())?;
+        root.record_that(crate::audit::envelope(ActivityRateEvent::Removed))?;
         repo.save(&mut root).await?;
     }
     Ok(())
@@ -0,0 +1,257 @@
+//! Submission and review of a user's time entries for a period.
+//!
+//! Submitting puts every draft or rejected entry of the period under review,
+//! which makes it read-only.  Approving keeps it that way for good; rejecting
+//! reopens the entries for corrections and a new submission.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::Utc;
+use eventually::aggregate::{
+    Root,
+    repository::{Getter, Saver},
+};
+use loom_core::tenant::{
+    approval::{Approval, ApprovalEvent, ApprovalId, ApprovalStatus},
+    timesheet::{TimesheetEvent, TimesheetId},
+};
+use loom_infrastructure_impl::{
+    Pool,
+    admin::user::repositories::UserRepository,
+    tenant::{
+        approval::repositories::{ApprovalRepository, ApprovalRow},
+        timesheet::repositories::{TimesheetRepository, TimesheetRow},
+    },
+};
+
+use crate::authorization::AuthorizationService;
+
+/// The submissions of a user, newest period first.
+pub async fn for_user(workspace_id: &str, user_id: &str) -> Result<Vec<ApprovalRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ApprovalRepository::from_pool(pool).await?;
+    Ok(repo.for_user(user_id).await?)
+}
+
+/// Submissions awaiting review, oldest first.
+pub async fn pending(workspace_id: &str) -> Result<Vec<ApprovalRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ApprovalRepository::from_pool(pool).await?;
+    Ok(repo.pending().await?)
+}
+
+pub async fn find(workspace_id: &str, approval_id: &str) -> Result<Option<ApprovalRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ApprovalRepository::from_pool(pool).await?;
+    Ok(repo.find(approval_id).await?)
+}
+
+/// The entries covered by a submission.
+pub async fn entries(workspace_id: &str, approval_id: &str) -> Result<Vec<TimesheetRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TimesheetRepository::from_pool(pool).await?;
+    Ok(repo.for_approval(approval_id).await?)
+}
+
+/// Resolve the submitters of `rows` to user names; unknown IDs are left out.
+pub async fn user_names(rows: &[ApprovalRow]) -> Result<HashMap<String, String>> {
+    let pool = Pool::connect_admin().await?;
+    let repo = UserRepository::from_pool(pool).await?;
+    let mut names = HashMap::new();
+    for row in rows {
+        if names.contains_key(&row.user_id) {
+            continue;
+        }
+        if let Some(user) = repo.find_view_by_id(&row.user_id).await? {
+            names.insert(row.user_id.clone(), user.get_name().to_string());
+        }
+    }
+    Ok(names)
+}
+
+/// Submit the user's draft and rejected entries that start within
+/// `from..=to` (`YYYY-MM-DD`).  Entries already under review or approved are
+/// left alone.
+///
+/// # Errors
+///
+/// Returns a validation error if the period is invalid, a timer of the period
+/// is still running, or there is nothing to submit.
+pub async fn submit(
+    workspace_id: &str,
+    user_id: &str,
+    from: &str,
+    to: &str,
+    comment: Option<String>,
+) -> Result<ApprovalRow> {
+    let (start, end) = super::timesheet::day_range(from, to)?;
+    let pool = super::tenant_pool(workspace_id).await?;
+    let timesheets = TimesheetRepository::from_pool(pool.clone()).await?;
+    let approvals = ApprovalRepository::from_pool(pool).await?;
+
+    let rows = timesheets.starting_between(user_id, &start, &end).await?;
+    if rows.iter().any(|row| row.end_time.is_none()) {
+        return Err(crate::error::ValidationError::new(
+            "Stop the running timer before submitting the period",
+        )
+        .into());
+    }
+    let open: Vec<&TimesheetRow> = rows
+        .iter()
+        .filter(|row| {
+            row.approval_status == ApprovalStatus::Draft.as_str()
+                || row.approval_status == ApprovalStatus::Rejected.as_str()
+        })
+        .collect();
+    if open.is_empty() {
+        return Err(crate::error::ValidationError::new(
+            "There are no entries to submit in this period",
+        )
+        .into());
+    }
+
+    let id = ApprovalId::new();
+    let comment = comment.filter(|c| !c.trim().is_empty());
+    let submitted_at = Utc::now().to_rfc3339();
+
+    // Record on every entry before saving anything so a refused entry leaves
+    // no partial submission behind.
+    let mut timesheet_ids = Vec::with_capacity(open.len());
+    let mut roots = Vec::with_capacity(open.len());
+    for row in &open {
+        let agg_id: TimesheetId = row.id.parse()?;
+        let mut root = timesheets.get(&agg_id).await?;
+        root.record_that(crate::audit::envelope(TimesheetEvent::Submitted {
+            approval_id: id.clone(),
+        }))?;
+        timesheet_ids.push(agg_id);
+        roots.push(root);
+    }
+    let mut approval =
+        Root::<Approval>::record_new(crate::audit::envelope(ApprovalEvent::Requested {
+            id: id.clone(),
+            user_id: user_id.parse()?,
+            period_start: from.to_string(),
+            period_end: to.to_string(),
+            timesheet_ids,
+            comment: comment.clone(),
+            submitted_at: submitted_at.clone(),
+        }))?;
+    approvals.save(&mut approval).await?;
+    for root in &mut roots {
+        timesheets.save(root).await?;
+    }
+
+    Ok(ApprovalRow {
+        id: id.to_string(),
+        user_id: user_id.to_string(),
+        period_start: from.to_string(),
+        period_end: to.to_string(),
+        status: ApprovalStatus::Submitted.as_str().to_string(),
+        comment,
+        entry_count: i32::try_from(roots.len()).unwrap_or(i32::MAX),
+        submitted_at,
+        reviewer_id: None,
+        reviewed_at: None,
+    })
+}
+
+/// Approve a submission; its entries become read-only.
+///
+/// # Errors
+///
+/// Returns a validation error if the submission was already reviewed or the
+/// reviewer submitted it themselves.
+pub async fn approve(
+    workspace_id: &str,
+    approval_id: &str,
+    reviewer_id: &str,
+    comment: Option<String>,
+) -> Result<()> {
+    review(
+        workspace_id,
+        approval_id,
+        reviewer_id,
+        ApprovalEvent::Granted {
+            reviewer_id: reviewer_id.parse()?,
+            comment: comment.filter(|c| !c.trim().is_empty()),
+            reviewed_at: Utc::now().to_rfc3339(),
+        },
+        TimesheetEvent::Approved,
+    )
+    .await
+}
+
+/// Reject a submission; its entries reopen for edits.  A comment telling the
+/// user what to correct is required.
+///
+/// # Errors
+///
+/// Returns a validation error if the comment is empty, the submission was
+/// already reviewed or the reviewer submitted it themselves.
+pub async fn reject(
+    workspace_id: &str,
+    approval_id: &str,
+    reviewer_id: &str,
+    comment: String,
+) -> Result<()> {
+    if comment.trim().is_empty() {
+        return Err(crate::error::ValidationError::new(
+            "Tell the user what to correct when rejecting",
+        )
+        .into());
+    }
+    review(
+        workspace_id,
+        approval_id,
+        reviewer_id,
+        ApprovalEvent::Rejected {
+            reviewer_id: reviewer_id.parse()?,
+            comment,
+            reviewed_at: Utc::now().to_rfc3339(),
+        },
+        TimesheetEvent::Rejected,
+    )
+    .await
+}
+
+async fn review(
+    workspace_id: &str,
+    approval_id: &str,
+    reviewer_id: &str,
+    event: ApprovalEvent,
+    entry_event: TimesheetEvent,
+) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let approvals = ApprovalRepository::from_pool(pool.clone()).await?;
+    let timesheets = TimesheetRepository::from_pool(pool).await?;
+
+    let agg_id: ApprovalId = approval_id.parse()?;
+    let mut approval = approvals.get(&agg_id).await?;
+    if approval.status() != ApprovalStatus::Submitted {
+        return Err(
+            crate::error::ValidationError::new("The submission has already been reviewed").into(),
+        );
+    }
+    if approval.user_id().to_string() == reviewer_id
+        && !AuthorizationService::is_admin(reviewer_id).await?
+    {
+        return Err(
+            crate::error::ValidationError::new("You cannot review your own submission").into(),
+        );
+    }
+
+    let mut roots = Vec::with_capacity(approval.timesheet_ids().len());
+    for timesheet_id in approval.timesheet_ids() {
+        let mut root = timesheets.get(timesheet_id).await?;
+        root.record_that(crate::audit::envelope(entry_event.clone()))?;
+        roots.push(root);
+    }
+    approval.record_that(crate::audit::envelope(event))?;
+    approvals.save(&mut approval).await?;
+    for root in &mut roots {
+        timesheets.save(root).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,193 @@
+//! Stopping forgotten timers.
+//!
+//! The tenant projection daemon runs [`stop_forever`], which looks at the
+//! running timers of every workspace and stops those its [`AutoStopPolicy`]
+//! says ran for too long, like the user would have: the timer is rounded and
+//! rated and recorded as a regular `Stopped` event.  The events carry the
+//! reason in their metadata, so it shows up in the entry's history, and the
+//! user is emailed if the policy asks for it and mail is set up (see
+//! [`crate::mail`]).
+
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use loom_core::tenant::timesheet::{AutoStop, AutoStopEnd, AutoStopPolicy};
+use loom_infrastructure_impl::{
+    ConnectedTenantPool, Pool,
+    admin::user::repositories::UserRepository,
+    audit_log::repositories::{AuditLogFilter, AuditLogRepository},
+    tenant::timesheet::repositories::{TimesheetRepository, TimesheetRow},
+};
+use tracing::{info, warn};
+
+use crate::mail::{self, Email, MailTransport};
+
+/// Pause between two rounds of [`stop_forever`].
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Audit entries looked at to find a user's last activity.
+const ACTIVITY_LOOKBACK: u64 = 20;
+
+fn parse_time(time: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(time)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// The last time the timer's owner changed something in the workspace
+/// since it was started, apart from the timer itself.
+async fn last_activity(
+    pool: &ConnectedTenantPool,
+    timer: &TimesheetRow,
+    start: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>> {
+    let rows = AuditLogRepository::new(pool.clone())
+        .find(&AuditLogFilter {
+            actor: Some(timer.user_id.clone()),
+            from: Some(start.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            limit: ACTIVITY_LOOKBACK,
+            ..AuditLogFilter::default()
+        })
+        .await?;
+    Ok(rows
+        .iter()
+        .filter(|row| row.aggregate_id != timer.id)
+        .find_map(|row| row.recorded_at.as_deref().and_then(parse_time)))
+}
+
+/// Tell the owner of `timer` that it was stopped.
+async fn notify(
+    transport: &dyn MailTransport,
+    timer: &TimesheetRow,
+    stop: &AutoStop,
+) -> Result<()> {
+    let users = UserRepository::from_pool(Pool::connect_admin().await?).await?;
+    let Some(user) = users.find_view_by_id(&timer.user_id).await? else {
+        return Ok(());
+    };
+    let stamp = |t: DateTime<Utc>| t.format("%Y-%m-%d %H:%M UTC").to_string();
+    transport
+        .send(&Email {
+            to: vec![user.get_email().to_string()],
+            subject: "Your timer was stopped".to_string(),
+            text: format!(
+                "Hello {},\n\nyour timer started at {} was still running.  {} at {}.\n\n\
+                 Please check the entry and correct it if you worked longer.\n",
+                user.get_name(),
+                parse_time(&timer.start_time).map_or_else(|| timer.start_time.clone(), stamp),
+                stop.reason,
+                stamp(stop.end),
+            ),
+            html: None,
+            attachments: vec![],
+        })
+        .await
+}
+
+/// Stop the workspace's timers that ran for too long according to
+/// `policy`, and return how many were stopped.
+pub async fn stop_due(
+    transport: Option<&dyn MailTransport>,
+    workspace_id: &str,
+    pool: &ConnectedTenantPool,
+    policy: &AutoStopPolicy,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    if policy.is_off() {
+        return Ok(0);
+    }
+    let timers = TimesheetRepository::from_pool(pool.clone())
+        .await?
+        .running()
+        .await?;
+
+    let mut stopped = 0;
+    for timer in &timers {
+        let Some(start) = parse_time(&timer.start_time) else {
+            continue;
+        };
+        let last_activity = match policy.end {
+            AutoStopEnd::LastActivity => last_activity(pool, timer, start).await?,
+            AutoStopEnd::Cutoff => None,
+        };
+        let Some(stop) = policy.check(start, last_activity, now) else {
+            continue;
+        };
+        let stopping = super::timesheet::stop_at(workspace_id, &timer.id, stop.end);
+        if let Err(error) = crate::audit::with_reason(stop.reason.to_string(), stopping).await {
+            warn!(
+                workspace_id = %workspace_id,
+                timesheet_id = %timer.id,
+                error = %error,
+                "Failed to stop a forgotten timer."
+            );
+            continue;
+        }
+        stopped += 1;
+        info!(
+            workspace_id = %workspace_id,
+            timesheet_id = %timer.id,
+            reason = %stop.reason,
+            "Stopped a forgotten timer."
+        );
+        if policy.notify
+            && let Some(transport) = transport
+            && let Err(error) = notify(transport, timer, &stop).await
+        {
+            warn!(
+                workspace_id = %workspace_id,
+                timesheet_id = %timer.id,
+                error = %error,
+                "Failed to tell the user their timer was stopped."
+            );
+        }
+    }
+    Ok(stopped)
+}
+
+/// Stop forgotten timers of every workspace, round after round.  Users are
+/// emailed through the transport configured in the environment, if any.
+pub async fn stop_forever() {
+    let transport = mail::from_env().unwrap_or_else(|error| {
+        warn!(
+            error = %error,
+            "Failed to set up the mail transport; stopped timers are not mailed."
+        );
+        None
+    });
+    let mut tenants = super::TenantPools::default();
+    loop {
+        let pools = match tenants.refresh().await {
+            Ok(pools) => pools,
+            Err(error) => {
+                warn!(error = %error, "Failed to list the workspaces.");
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+        };
+        for (workspace_id, pool) in pools {
+            let result = match crate::workspace::get_workspace_settings(workspace_id).await {
+                Ok(settings) => {
+                    let policy = settings.auto_stop_policy;
+                    stop_due(
+                        transport.as_deref(),
+                        workspace_id,
+                        pool,
+                        &policy,
+                        Utc::now(),
+                    )
+                    .await
+                }
+                Err(error) => Err(error),
+            };
+            if let Err(error) = result {
+                warn!(
+                    workspace_id = %workspace_id,
+                    error = %error,
+                    "Failed to stop forgotten timers."
+                );
+            }
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
@@ -0,0 +1,371 @@
+//! Budget consumption of customers and projects, and threshold alerts.
+//!
+//! [`consumption`] measures the logged time and money of every budgeted
+//! customer and project against its budget.  The tenant projection daemon
+//! wraps its projector in [`BudgetMonitor`], which records a
+//! `BudgetThresholdReached` event whenever usage crosses one of the
+//! workspace's warning thresholds.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use eventually::aggregate::repository::{Getter, Saver};
+use loom_core::tenant::{
+    budget::{BudgetUsage, period_of, period_range},
+    customer::{CustomerEvent, CustomerId},
+    project::{ProjectEvent, ProjectId},
+};
+use loom_infrastructure_impl::{
+    ConnectedTenantPool, Projector, RawEvent,
+    tenant::{
+        budget::repositories::BudgetRepository, customer::repositories::CustomerRepository,
+        project::repositories::ProjectRepository, timesheet::repositories::TimesheetRepository,
+    },
+};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Budget usage of one customer or project in its current period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetConsumption {
+    /// `customer` or `project`.
+    pub scope: String,
+    pub id: String,
+    pub name: String,
+    /// `YYYY-MM` for monthly budgets.
+    pub period: Option<String>,
+    pub time_budget: Option<i32>,
+    pub money_budget: Option<i64>,
+    pub used_seconds: i64,
+    pub used_amount: i64,
+    pub remaining_seconds: Option<i64>,
+    pub remaining_amount: Option<i64>,
+    pub percent_used: Option<i64>,
+    /// Highest warning threshold reached so far.
+    pub reached_threshold: Option<u8>,
+}
+
+/// What a budget is set on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    Customer,
+    Project,
+}
+
+impl Scope {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Customer => "customer",
+            Self::Project => "project",
+        }
+    }
+}
+
+struct Budget {
+    time_budget: Option<i32>,
+    money_budget: Option<i64>,
+    budget_is_monthly: bool,
+}
+
+impl Budget {
+    const fn is_set(&self) -> bool {
+        self.time_budget.is_some() || self.money_budget.is_some()
+    }
+}
+
+/// Usage of a budget in the period `at` falls into, and that period.
+async fn measure(
+    repo: &BudgetRepository,
+    scope: Scope,
+    id: &str,
+    budget: &Budget,
+    at: DateTime<Utc>,
+) -> Result<(BudgetUsage, Option<String>)> {
+    let period = period_of(budget.budget_is_monthly, at);
+    let range = period.as_deref().and_then(period_range);
+    let (used_seconds, used_amount) = match scope {
+        Scope::Customer => repo.customer_usage(id, range.as_ref()).await?,
+        Scope::Project => repo.project_usage(id, range.as_ref()).await?,
+    };
+    let usage = BudgetUsage {
+        time_budget: budget.time_budget,
+        money_budget: budget.money_budget,
+        used_seconds,
+        used_amount,
+    };
+    Ok((usage, period))
+}
+
+/// Budget usage of every customer and project that has a budget.
+pub async fn consumption(workspace_id: &str) -> Result<Vec<BudgetConsumption>> {
+    let thresholds = crate::workspace::get_workspace_settings(workspace_id)
+        .await?
+        .budget_thresholds;
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = BudgetRepository::new(pool.clone());
+
+    let mut targets = Vec::new();
+    for c in CustomerRepository::from_pool(pool.clone())
+        .await?
+        .all()
+        .await?
+    {
+        let budget = Budget {
+            time_budget: c.time_budget,
+            money_budget: c.money_budget,
+            budget_is_monthly: c.budget_is_monthly,
+        };
+        targets.push((Scope::Customer, c.id, c.name, budget));
+    }
+    for p in ProjectRepository::from_pool(pool).await?.all().await? {
+        let budget = Budget {
+            time_budget: p.time_budget,
+            money_budget: p.money_budget,
+            budget_is_monthly: p.budget_is_monthly,
+        };
+        targets.push((Scope::Project, p.id, p.name, budget));
+    }
+
+    let now = Utc::now();
+    let mut result = Vec::new();
+    for (scope, id, name, budget) in targets {
+        if !budget.is_set() {
+            continue;
+        }
+        let (usage, period) = measure(&repo, scope, &id, &budget, now).await?;
+        result.push(BudgetConsumption {
+            scope: scope.as_str().to_string(),
+            id,
+            name,
+            period,
+            time_budget: budget.time_budget,
+            money_budget: budget.money_budget,
+            used_seconds: usage.used_seconds,
+            used_amount: usage.used_amount,
+            remaining_seconds: usage.remaining_seconds(),
+            remaining_amount: usage.remaining_amount(),
+            percent_used: usage.percent_used(),
+            reached_threshold: usage.reached(&thresholds).into_iter().max(),
+        });
+    }
+    Ok(result)
+}
+
+/// Whether the project or its customer has used up its budget.
+pub async fn is_over_budget(pool: &ConnectedTenantPool, project_id: &str) -> Result<bool> {
+    let Some(project) = ProjectRepository::from_pool(pool.clone())
+        .await?
+        .find(project_id)
+        .await?
+    else {
+        return Ok(false);
+    };
+    let repo = BudgetRepository::new(pool.clone());
+    let now = Utc::now();
+    for (scope, id) in [
+        (Scope::Project, project_id),
+        (Scope::Customer, project.customer_id.as_str()),
+    ] {
+        if let Some(budget) = budget_of(pool, scope, id).await?
+            && budget.is_set()
+            && measure(&repo, scope, id, &budget, now)
+                .await?
+                .0
+                .is_over_budget()
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// The budget set on a customer or project, if it exists.
+async fn budget_of(pool: &ConnectedTenantPool, scope: Scope, id: &str) -> Result<Option<Budget>> {
+    Ok(match scope {
+        Scope::Customer => CustomerRepository::from_pool(pool.clone())
+            .await?
+            .find(id)
+            .await?
+            .map(|row| Budget {
+                time_budget: row.time_budget,
+                money_budget: row.money_budget,
+                budget_is_monthly: row.budget_is_monthly,
+            }),
+        Scope::Project => ProjectRepository::from_pool(pool.clone())
+            .await?
+            .find(id)
+            .await?
+            .map(|row| Budget {
+                time_budget: row.time_budget,
+                money_budget: row.money_budget,
+                budget_is_monthly: row.budget_is_monthly,
+            }),
+    })
+}
+
+/// Record `BudgetThresholdReached` for every threshold the customer or
+/// project has newly reached in the period `at` falls into.
+async fn check_thresholds(
+    pool: &ConnectedTenantPool,
+    thresholds: &[u8],
+    scope: Scope,
+    id: &str,
+    at: DateTime<Utc>,
+) -> Result<()> {
+    let Some(budget) = budget_of(pool, scope, id).await? else {
+        return Ok(());
+    };
+    if !budget.is_set() {
+        return Ok(());
+    }
+    let (usage, period) =
+        measure(&BudgetRepository::new(pool.clone()), scope, id, &budget, at).await?;
+    let (used_seconds, used_amount) = (usage.used_seconds, usage.used_amount);
+
+    match scope {
+        Scope::Customer => {
+            let repos = CustomerRepository::from_pool(pool.clone()).await?;
+            let mut root = repos.get(&id.parse::<CustomerId>()?).await?;
+            let mut reached = false;
+            for threshold in usage.reached(thresholds) {
+                if root.has_reached(period.as_deref(), threshold) {
+                    continue;
+                }
+                root.record_that(crate::audit::envelope(
+                    CustomerEvent::BudgetThresholdReached {
+                        threshold,
+                        period: period.clone(),
+                        used_seconds,
+                        used_amount,
+                    },
+                ))?;
+                reached = true;
+            }
+            if reached {
+                repos.save(&mut root).await?;
+            }
+        }
+        Scope::Project => {
+            let repos = ProjectRepository::from_pool(pool.clone()).await?;
+            let mut root = repos.get(&id.parse::<ProjectId>()?).await?;
+            let mut reached = false;
+            for threshold in usage.reached(thresholds) {
+                if root.has_reached(period.as_deref(), threshold) {
+                    continue;
+                }
+                root.record_that(crate::audit::envelope(
+                    ProjectEvent::BudgetThresholdReached {
+                        threshold,
+                        period: period.clone(),
+                        used_seconds,
+                        used_amount,
+                    },
+                ))?;
+                reached = true;
+            }
+            if reached {
+                repos.save(&mut root).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Wraps the tenant projector and checks budgets after each event that can
+/// change a customer's or project's usage.
+///
+/// The check runs after the inner projector so it sees the updated
+/// timesheet projection.  A failed check is logged and never holds up the
+/// projection.
+pub struct BudgetMonitor<P> {
+    workspace_id: String,
+    pool: ConnectedTenantPool,
+    inner: P,
+}
+
+impl<P> BudgetMonitor<P> {
+    #[must_use]
+    pub fn new(workspace_id: impl Into<String>, pool: ConnectedTenantPool, inner: P) -> Self {
+        Self {
+            workspace_id: workspace_id.into(),
+            pool,
+            inner,
+        }
+    }
+
+    async fn check(&self, event: &RawEvent) -> Result<()> {
+        // Time counts towards the period the entry started in; a budget
+        // change applies to the period it was made in.
+        let (project_id, at) = match event.event_type.as_str() {
+            "TimesheetStopped"
+            | "TimesheetAmended"
+            | "TimesheetTimeUpdated"
+            | "TimesheetReassigned" => {
+                let Some(timesheet) = TimesheetRepository::from_pool(self.pool.clone())
+                    .await?
+                    .find(&event.stream_id)
+                    .await?
+                else {
+                    return Ok(());
+                };
+                let at = DateTime::parse_from_rfc3339(&timesheet.start_time)?.with_timezone(&Utc);
+                (timesheet.project_id, at)
+            }
+            "ProjectBudgetUpdated" => (
+                Some(event.stream_id.clone()),
+                self.recorded_at(event).await?,
+            ),
+            "CustomerBudgetUpdated" => (None, self.recorded_at(event).await?),
+            _ => return Ok(()),
+        };
+
+        let thresholds = crate::workspace::get_workspace_settings(&self.workspace_id)
+            .await?
+            .budget_thresholds;
+        let customer_id = match project_id {
+            Some(project_id) => {
+                check_thresholds(&self.pool, &thresholds, Scope::Project, &project_id, at).await?;
+                ProjectRepository::from_pool(self.pool.clone())
+                    .await?
+                    .find(&project_id)
+                    .await?
+                    .map(|p| p.customer_id)
+            }
+            None if event.event_type == "CustomerBudgetUpdated" => Some(event.stream_id.clone()),
+            None => None,
+        };
+        if let Some(customer_id) = customer_id {
+            check_thresholds(&self.pool, &thresholds, Scope::Customer, &customer_id, at).await?;
+        }
+        Ok(())
+    }
+
+    /// When the event was stored, or now if the store does not know.
+    async fn recorded_at(&self, event: &RawEvent) -> Result<DateTime<Utc>> {
+        Ok(BudgetRepository::new(self.pool.clone())
+            .recorded_at(event.global_position)
+            .await?
+            .unwrap_or_else(Utc::now))
+    }
+}
+
+#[async_trait]
+impl<P> Projector for BudgetMonitor<P>
+where
+    P: Projector<Error = loom_infrastructure_impl::Error> + Send + Sync,
+{
+    type Error = loom_infrastructure_impl::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        self.inner.handle(event.clone()).await?;
+        if let Err(error) = self.check(&event).await {
+            warn!(
+                workspace_id = %self.workspace_id,
+                event_type = %event.event_type,
+                error = %error,
+                "Failed to check budgets."
+            );
+        }
+        Ok(())
+    }
+}
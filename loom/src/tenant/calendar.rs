@@ -0,0 +1,354 @@
+//! Calendar integration.
+//!
+//! Each user can have a private iCalendar feed of their timesheets per
+//! workspace, reachable without signing in through a URL containing a
+//! [`FEED_TOKEN_PREFIX`] token.  Like an API token, it is only stored as a
+//! hash, so [`reset_feed`] is the only place it is ever seen.
+//!
+//! Going the other way, the events of an iCalendar file can be imported as
+//! timesheets.  [`preview_import`] suggests a project and activity for each
+//! event from the user's keyword rules; [`import`] creates the entries the
+//! user picked.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use eventually::aggregate::{
+    Root,
+    repository::{Getter, Saver},
+};
+use loom_core::tenant::{
+    activity::ActivityId,
+    calendar::{self, FeedEvent},
+    calendar_rule::{CalendarRule, CalendarRuleEvent, CalendarRuleId},
+    project::ProjectId,
+};
+use loom_infrastructure_impl::{
+    Pool,
+    admin::{
+        calendar_feed::repositories::{CalendarFeedRepository, CalendarFeedRow},
+        workspace::repositories::WorkspaceRepository,
+    },
+    tenant::{
+        activity::repositories::ActivityRepository,
+        calendar_rule::repositories::{CalendarRuleRepository, CalendarRuleRow},
+        project::repositories::ProjectRepository,
+        timesheet::repositories::{TimesheetRepository, TimesheetRow},
+    },
+};
+
+use crate::secret_token::{self, hash};
+
+/// Start of every feed token, so that leaked feed URLs are easy to spot.
+pub const FEED_TOKEN_PREFIX: &str = "loomcal_";
+
+/// How far back the feed reaches.
+const FEED_DAYS: i64 = 366;
+
+/// The user's feed in a workspace, if they turned it on.
+pub async fn feed(user_id: &str, workspace_id: &str) -> Result<Option<CalendarFeedRow>> {
+    let repo = CalendarFeedRepository::new(Pool::connect_admin().await?);
+    Ok(repo.for_user(user_id, workspace_id).await?)
+}
+
+/// Turn on the user's feed, or give it a new token so that the old URL
+/// stops working.  Returns the token.
+pub async fn reset_feed(user_id: &str, workspace_id: &str) -> Result<String> {
+    let token = secret_token::generate(FEED_TOKEN_PREFIX);
+    let repo = CalendarFeedRepository::new(Pool::connect_admin().await?);
+    repo.replace(user_id, workspace_id, &hash(&token)).await?;
+    Ok(token)
+}
+
+/// Turn off the user's feed.  Returns `false` if it was off already.
+pub async fn disable_feed(user_id: &str, workspace_id: &str) -> Result<bool> {
+    let repo = CalendarFeedRepository::new(Pool::connect_admin().await?);
+    Ok(repo.delete(user_id, workspace_id).await?)
+}
+
+/// The feed a token belongs to as iCalendar text, or `None` if the token is
+/// unknown or its owner has left the workspace.
+///
+/// Holds one event per stopped timesheet of the last year, titled with its
+/// project and activity.
+pub async fn render_feed(token: &str) -> Result<Option<String>> {
+    if !token.starts_with(FEED_TOKEN_PREFIX) {
+        return Ok(None);
+    }
+    let pool = Pool::connect_admin().await?;
+    let Some(feed) = CalendarFeedRepository::new(pool.clone())
+        .find(&hash(token))
+        .await?
+    else {
+        return Ok(None);
+    };
+    let is_member = WorkspaceRepository::from_pool(pool)
+        .await?
+        .find_workspaces_for_user(&feed.user_id)
+        .await?
+        .iter()
+        .any(|(id, _)| *id == feed.workspace_id);
+    if !is_member {
+        return Ok(None);
+    }
+
+    let now = Utc::now();
+    let pool = super::tenant_pool(&feed.workspace_id).await?;
+    let entries = TimesheetRepository::from_pool(pool.clone())
+        .await?
+        .starting_between(
+            &feed.user_id,
+            &(now - Duration::days(FEED_DAYS)).to_rfc3339(),
+            &now.to_rfc3339(),
+        )
+        .await?;
+    let names = names(&pool).await?;
+    let events: Vec<FeedEvent> = entries
+        .iter()
+        .filter_map(|entry| feed_event(entry, &names))
+        .collect();
+    Ok(Some(calendar::render("Loom timesheets", &events, now)))
+}
+
+/// Project and activity names by ID, archived ones included.
+async fn names(
+    pool: &loom_infrastructure_impl::ConnectedTenantPool,
+) -> Result<HashMap<String, String>> {
+    let projects = ProjectRepository::from_pool(pool.clone()).await?;
+    let activities = ActivityRepository::from_pool(pool.clone()).await?;
+    let mut names = HashMap::new();
+    for project in projects
+        .all()
+        .await?
+        .into_iter()
+        .chain(projects.archived().await?)
+    {
+        names.insert(project.id, project.name);
+    }
+    for activity in activities
+        .all()
+        .await?
+        .into_iter()
+        .chain(activities.archived().await?)
+    {
+        names.insert(activity.id, activity.name);
+    }
+    Ok(names)
+}
+
+fn feed_event(entry: &TimesheetRow, names: &HashMap<String, String>) -> Option<FeedEvent> {
+    let parse = |s: &str| {
+        chrono::DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    };
+    let start = parse(&entry.start_time)?;
+    let end = parse(entry.end_time.as_deref()?)?;
+    let titles: Vec<&str> = [&entry.project_id, &entry.activity_id]
+        .into_iter()
+        .filter_map(|id| names.get(id.as_deref()?).map(String::as_str))
+        .collect();
+    Some(FeedEvent {
+        uid: format!("{}@loom", entry.id),
+        start,
+        end,
+        summary: if titles.is_empty() {
+            "Time entry".to_string()
+        } else {
+            titles.join(" / ")
+        },
+        description: entry.description.clone(),
+    })
+}
+
+/// The user's rules in the order they were added.
+pub async fn rules(workspace_id: &str, user_id: &str) -> Result<Vec<CalendarRuleRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = CalendarRuleRepository::from_pool(pool).await?;
+    Ok(repo.for_user(user_id).await?)
+}
+
+/// Map imported events whose title contains `keyword` to a project and,
+/// optionally, an activity.
+///
+/// # Errors
+///
+/// Returns a validation error if `keyword` is blank, or an error if an ID is
+/// invalid or the rule cannot be saved.
+pub async fn add_rule(
+    workspace_id: &str,
+    user_id: &str,
+    keyword: &str,
+    project_id: &str,
+    activity_id: Option<&str>,
+) -> Result<CalendarRuleRow> {
+    let keyword = keyword.trim();
+    if keyword.is_empty() {
+        return Err(crate::error::ValidationError::new("The rule needs a keyword").into());
+    }
+    let activity_id = activity_id.filter(|a| !a.is_empty());
+
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = CalendarRuleRepository::from_pool(pool).await?;
+    let id = CalendarRuleId::new();
+    let mut root =
+        Root::<CalendarRule>::record_new(crate::audit::envelope(CalendarRuleEvent::Added {
+            id: id.clone(),
+            user_id: user_id.parse()?,
+            keyword: keyword.to_string(),
+            project_id: project_id.parse::<ProjectId>()?,
+            activity_id: activity_id.map(str::parse::<ActivityId>).transpose()?,
+        }))?;
+    repo.save(&mut root).await?;
+
+    Ok(CalendarRuleRow {
+        id: id.to_string(),
+        user_id: user_id.to_string(),
+        keyword: keyword.to_string(),
+        project_id: project_id.to_string(),
+        activity_id: activity_id.map(ToString::to_string),
+    })
+}
+
+/// # Errors
+///
+/// Returns an error if the rule belongs to someone else, cannot be found or
+/// saved.
+pub async fn remove_rule(workspace_id: &str, user_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = CalendarRuleRepository::from_pool(pool).await?;
+    let mut root = repo.get(&id.parse::<CalendarRuleId>()?).await?;
+    if root.user_id().to_string() != user_id {
+        return Err(crate::error::ValidationError::new("The rule belongs to someone else").into());
+    }
+    root.record_that(crate::audit::envelope(CalendarRuleEvent::Removed))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// An event of an imported calendar, ready to become a timesheet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportCandidate {
+    pub title: String,
+    /// RFC-3339.
+    pub start_time: String,
+    /// RFC-3339.
+    pub end_time: String,
+    /// Suggested by the rule matching the title, if any.
+    pub project_id: Option<String>,
+    pub activity_id: Option<String>,
+    /// Whether the user already has an entry with exactly these times, e.g.
+    /// because the file was imported before.
+    pub tracked: bool,
+}
+
+/// The timed events of an iCalendar file with the project and activity the
+/// user's rules suggest, oldest first.
+///
+/// # Errors
+///
+/// Returns a validation error if the file holds no timed events.
+pub async fn preview_import(
+    workspace_id: &str,
+    user_id: &str,
+    ics: &str,
+) -> Result<Vec<ImportCandidate>> {
+    let mut events = calendar::parse(ics);
+    if events.is_empty() {
+        return Err(crate::error::ValidationError::new(
+            "The file contains no calendar events with a start and end time",
+        )
+        .into());
+    }
+    events.sort_by_key(|e| e.start);
+
+    let pool = super::tenant_pool(workspace_id).await?;
+    let rules = CalendarRuleRepository::from_pool(pool.clone())
+        .await?
+        .for_user(user_id)
+        .await?;
+    let (first, last) = (events[0].start, events.iter().map(|e| e.end).max());
+    let tracked: Vec<(String, String)> = TimesheetRepository::from_pool(pool)
+        .await?
+        .starting_between(
+            user_id,
+            &first.to_rfc3339(),
+            &last.unwrap_or(first).to_rfc3339(),
+        )
+        .await?
+        .into_iter()
+        .filter_map(|t| Some((t.start_time, t.end_time?)))
+        .collect();
+    let same_time = |start: &str, end: &str| {
+        let parse = |s: &str| chrono::DateTime::parse_from_rfc3339(s).ok();
+        tracked
+            .iter()
+            .any(|(s, e)| parse(s) == parse(start) && parse(e) == parse(end))
+    };
+
+    Ok(events
+        .into_iter()
+        .map(|event| {
+            let rule = calendar::matching_rule(&event.summary, &rules, |r| &r.keyword);
+            let (start_time, end_time) = (event.start.to_rfc3339(), event.end.to_rfc3339());
+            ImportCandidate {
+                tracked: same_time(&start_time, &end_time),
+                title: event.summary,
+                start_time,
+                end_time,
+                project_id: rule.map(|r| r.project_id.clone()),
+                activity_id: rule.and_then(|r| r.activity_id.clone()),
+            }
+        })
+        .collect())
+}
+
+/// An event the user picked for import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportSelection {
+    pub title: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub project_id: Option<String>,
+    pub activity_id: Option<String>,
+    pub billable: bool,
+}
+
+/// Create a stopped timesheet for each selected event, titled like the
+/// event, through [`super::timesheet::create_manual`] so that the
+/// workspace's time entry policy and period locks apply.
+///
+/// # Errors
+///
+/// Returns an error for the first event that cannot be imported, naming the
+/// event if it breaks a rule; the events before it stay imported.
+pub async fn import(
+    workspace_id: &str,
+    user_id: &str,
+    selections: Vec<ImportSelection>,
+) -> Result<Vec<TimesheetRow>> {
+    let mut created = Vec::with_capacity(selections.len());
+    for selection in selections {
+        let title = selection.title.clone();
+        let row = super::timesheet::create_manual(
+            workspace_id,
+            user_id,
+            selection.project_id.filter(|p| !p.is_empty()),
+            selection.activity_id.filter(|a| !a.is_empty()),
+            selection.start_time,
+            selection.end_time,
+            Some(selection.title).filter(|t| !t.trim().is_empty()),
+            selection.billable,
+        )
+        .await
+        .map_err(
+            |e| match e.downcast_ref::<crate::error::ValidationError>() {
+                Some(ve) => crate::error::ValidationError::new(format!("{title}: {ve}")).into(),
+                None => e,
+            },
+        )?;
+        created.push(row);
+    }
+    Ok(created)
+}
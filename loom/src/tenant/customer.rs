@@ -3,10 +3,15 @@ use eventually::aggregate::{
     Root,
     repository::{Getter, Saver},
 };
-use loom_core::tenant::customer::{
-    CreateCustomerInput, Customer, CustomerEvent, CustomerId, UpdateCustomerInput,
+use loom_core::tenant::{
+    customer::{CreateCustomerInput, Customer, CustomerEvent, CustomerId, UpdateCustomerInput},
+    project::{ProjectEvent, ProjectId},
+    timesheet::Rounding,
+};
+use loom_infrastructure_impl::tenant::{
+    customer::repositories::{CustomerRepository, CustomerRow},
+    project::repositories::ProjectRepository,
 };
-use loom_infrastructure_impl::tenant::customer::repositories::{CustomerRepository, CustomerRow};
 
 pub async fn list(workspace_id: &str) -> Result<Vec<CustomerRow>> {
     let pool = super::tenant_pool(workspace_id).await?;
@@ -14,6 +19,12 @@ pub async fn list(workspace_id: &str) -> Result<Vec<CustomerRow>> {
     Ok(repo.all().await?)
 }
 
+pub async fn list_archived(workspace_id: &str) -> Result<Vec<CustomerRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = CustomerRepository::from_pool(pool).await?;
+    Ok(repo.archived().await?)
+}
+
 pub async fn create(
     workspace_id: &str,
     name: String,
@@ -29,15 +40,12 @@ pub async fn create(
     let pool = super::tenant_pool(workspace_id).await?;
     let repo = CustomerRepository::from_pool(pool).await?;
     let id = CustomerId::new();
-    let mut root = Root::<Customer>::record_new(
-        CustomerEvent::Created {
-            id: id.clone(),
-            name: name.clone(),
-            currency: currency.clone(),
-            timezone: timezone.clone(),
-        }
-        .into(),
-    )?;
+    let mut root = Root::<Customer>::record_new(crate::audit::envelope(CustomerEvent::Created {
+        id: id.clone(),
+        name: name.clone(),
+        currency: currency.clone(),
+        timezone: timezone.clone(),
+    }))?;
     repo.save(&mut root).await?;
     Ok(CustomerRow {
         id: id.to_string(),
@@ -50,6 +58,7 @@ pub async fn create(
         time_budget: None,
         money_budget: None,
         budget_is_monthly: false,
+        archived: false,
     })
 }
 
@@ -74,17 +83,14 @@ pub async fn update(
     let repo = CustomerRepository::from_pool(pool).await?;
     let agg_id: CustomerId = id.parse()?;
     let mut root = repo.get(&agg_id).await?;
-    root.record_that(
-        CustomerEvent::Updated {
-            name,
-            comment,
-            currency,
-            timezone,
-            country,
-            visible,
-        }
-        .into(),
-    )?;
+    root.record_that(crate::audit::envelope(CustomerEvent::Updated {
+        name,
+        comment,
+        currency,
+        timezone,
+        country,
+        visible,
+    }))?;
     repo.save(&mut root).await?;
     Ok(())
 }
@@ -100,14 +106,111 @@ pub async fn set_budget(
     let repo = CustomerRepository::from_pool(pool).await?;
     let agg_id: CustomerId = id.parse()?;
     let mut root = repo.get(&agg_id).await?;
-    root.record_that(
-        CustomerEvent::BudgetUpdated {
-            time_budget,
-            money_budget,
-            budget_is_monthly,
+    root.record_that(crate::audit::envelope(CustomerEvent::BudgetUpdated {
+        time_budget,
+        money_budget,
+        budget_is_monthly,
+    }))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// The customer's own rounding rules, `None` when it uses the
+/// workspace rules.
+pub async fn rounding(workspace_id: &str, id: &str) -> Result<Option<Rounding>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = CustomerRepository::from_pool(pool).await?;
+    Ok(repo.rounding(id).await?)
+}
+
+pub async fn set_rounding(workspace_id: &str, id: &str, rounding: Option<Rounding>) -> Result<()> {
+    if let Some(rounding) = &rounding {
+        rounding
+            .validate()
+            .map_err(crate::error::ValidationError::new)?;
+    }
+
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = CustomerRepository::from_pool(pool).await?;
+    let agg_id: CustomerId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    root.record_that(crate::audit::envelope(CustomerEvent::RoundingUpdated {
+        rounding,
+    }))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// Archive a customer together with each of its projects that is not
+/// archived yet.
+pub async fn archive(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = CustomerRepository::from_pool(pool.clone()).await?;
+    let agg_id: CustomerId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    if root.archived() {
+        return Err(crate::error::ValidationError::new("The customer is already archived").into());
+    }
+    root.record_that(crate::audit::envelope(CustomerEvent::Archived))?;
+    repo.save(&mut root).await?;
+
+    let projects = ProjectRepository::from_pool(pool).await?;
+    for row in projects.by_customer(id).await? {
+        let project_id: ProjectId = row.id.parse()?;
+        let mut project = projects.get(&project_id).await?;
+        if project.archived() {
+            continue;
         }
-        .into(),
-    )?;
+        project.record_that(crate::audit::envelope(ProjectEvent::Archived {
+            with_customer: true,
+        }))?;
+        projects.save(&mut project).await?;
+    }
+    Ok(())
+}
+
+/// Restore a customer and the projects that were archived along with it.
+pub async fn restore(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = CustomerRepository::from_pool(pool.clone()).await?;
+    let agg_id: CustomerId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    if !root.archived() {
+        return Err(crate::error::ValidationError::new("The customer is not archived").into());
+    }
+    root.record_that(crate::audit::envelope(CustomerEvent::Restored))?;
+    repo.save(&mut root).await?;
+
+    let projects = ProjectRepository::from_pool(pool).await?;
+    for row in projects.by_customer(id).await? {
+        let project_id: ProjectId = row.id.parse()?;
+        let mut project = projects.get(&project_id).await?;
+        if !project.archived_with_customer() {
+            continue;
+        }
+        project.record_that(crate::audit::envelope(ProjectEvent::Restored))?;
+        projects.save(&mut project).await?;
+    }
+    Ok(())
+}
+
+/// Permanently delete a customer without projects.
+///
+/// Timesheets are booked on projects, so a customer without projects is
+/// referenced by none.
+pub async fn delete(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let projects = ProjectRepository::from_pool(pool.clone()).await?;
+    if !projects.by_customer(id).await?.is_empty() {
+        return Err(crate::error::ValidationError::new(
+            "The customer still has projects — delete them first or archive the customer",
+        )
+        .into());
+    }
+    let repo = CustomerRepository::from_pool(pool).await?;
+    let agg_id: CustomerId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    root.record_that(crate::audit::envelope(CustomerEvent::Deleted))?;
     repo.save(&mut root).await?;
     Ok(())
 }
@@ -0,0 +1,133 @@
+use anyhow::Result;
+use eventually::aggregate::{
+    Root,
+    repository::{Getter, Saver},
+};
+use loom_core::tenant::{
+    activity::ActivityId,
+    favorite::{Favorite, FavoriteEvent, FavoriteId},
+    project::ProjectId,
+    tag::TagId,
+};
+use loom_infrastructure_impl::tenant::{
+    combination::repositories::{CombinationRepository, CombinationRow},
+    favorite::repositories::{FavoriteRepository, FavoriteRow},
+    timesheet::repositories::TimesheetRow,
+};
+
+/// How many recent and frequent combinations are offered for quick start.
+const COMBINATION_LIMIT: i64 = 8;
+
+pub async fn list(workspace_id: &str, user_id: &str) -> Result<Vec<FavoriteRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = FavoriteRepository::from_pool(pool).await?;
+    Ok(repo.for_user(user_id).await?)
+}
+
+/// The combinations the user most recently worked on.
+pub async fn recent(workspace_id: &str, user_id: &str) -> Result<Vec<CombinationRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    Ok(CombinationRepository::new(pool)
+        .recent(user_id, COMBINATION_LIMIT)
+        .await?)
+}
+
+/// The combinations the user most frequently worked on.
+pub async fn frequent(workspace_id: &str, user_id: &str) -> Result<Vec<CombinationRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    Ok(CombinationRepository::new(pool)
+        .frequent(user_id, COMBINATION_LIMIT)
+        .await?)
+}
+
+/// Pin a combination for the user.
+///
+/// # Errors
+///
+/// Returns an error if an ID is invalid or the favorite cannot be saved.
+pub async fn pin(
+    workspace_id: &str,
+    user_id: &str,
+    project_id: &str,
+    activity_id: &str,
+    description: Option<String>,
+    tag_ids: Vec<String>,
+) -> Result<FavoriteRow> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = FavoriteRepository::from_pool(pool).await?;
+
+    let id = FavoriteId::new();
+    let description = description.filter(|d| !d.trim().is_empty());
+    let mut root = Root::<Favorite>::record_new(crate::audit::envelope(FavoriteEvent::Pinned {
+        id: id.clone(),
+        user_id: user_id.parse()?,
+        project_id: project_id.parse::<ProjectId>()?,
+        activity_id: activity_id.parse::<ActivityId>()?,
+        description: description.clone(),
+        tag_ids: tag_ids
+            .iter()
+            .map(|t| t.parse::<TagId>())
+            .collect::<Result<_, _>>()?,
+    }))?;
+    repo.save(&mut root).await?;
+
+    Ok(FavoriteRow {
+        id: id.to_string(),
+        user_id: user_id.to_string(),
+        project_id: project_id.to_string(),
+        activity_id: activity_id.to_string(),
+        description,
+        tag_ids,
+    })
+}
+
+/// # Errors
+///
+/// Returns an error if the favorite belongs to someone else, cannot be found
+/// or saved.
+pub async fn unpin(workspace_id: &str, user_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = FavoriteRepository::from_pool(pool).await?;
+    let mut root = own(&repo, user_id, id).await?;
+    root.record_that(crate::audit::envelope(FavoriteEvent::Unpinned))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// Start a timer from one of the user's favorites.
+///
+/// # Errors
+///
+/// Returns an error if the favorite belongs to someone else, a timer is
+/// already running, or the timer cannot be saved.
+pub async fn start(
+    workspace_id: &str,
+    user_id: &str,
+    id: &str,
+    billable: bool,
+) -> Result<TimesheetRow> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = FavoriteRepository::from_pool(pool).await?;
+    let favorite = own(&repo, user_id, id).await?;
+    let tag_ids: Vec<String> = favorite.tag_ids().iter().map(ToString::to_string).collect();
+    super::timesheet::start_tagged(
+        workspace_id,
+        user_id,
+        Some(favorite.project_id().to_string()),
+        Some(favorite.activity_id().to_string()),
+        favorite.description().map(ToString::to_string),
+        billable,
+        &tag_ids,
+    )
+    .await
+}
+
+async fn own(repo: &FavoriteRepository, user_id: &str, id: &str) -> Result<Root<Favorite>> {
+    let root = repo.get(&id.parse::<FavoriteId>()?).await?;
+    if root.user_id().to_string() != user_id {
+        return Err(
+            crate::error::ValidationError::new("The favorite belongs to someone else").into(),
+        );
+    }
+    Ok(root)
+}
@@ -1,11 +1,21 @@
 pub mod activity;
 pub mod activity_rate;
+pub mod approval;
+pub mod auto_stop;
+pub mod budget;
+pub mod calendar;
 pub mod customer;
+pub mod favorite;
 pub mod project;
 pub mod project_rate;
+pub mod report;
+pub mod search;
 pub mod tag;
+pub mod time_travel;
 pub mod timesheet;
 pub mod user;
+pub mod webhook;
+pub mod work_contract;
 
 /// Open a connection pool to the given workspace's tenant database.
 ///
@@ -20,3 +30,59 @@ pub(super) async fn tenant_pool(
     >::connect_tenant(workspace_id)
     .await?)
 }
+
+/// The tenant pools of every workspace, for background jobs that visit all
+/// workspaces round after round.
+///
+/// Pools stay open between rounds; [`TenantPools::refresh`] connects to
+/// workspaces created since the last round and drops deleted ones, so jobs
+/// pick up new workspaces without a restart.
+#[derive(Default)]
+pub struct TenantPools {
+    admin: Option<loom_infrastructure_impl::ConnectedAdminPool>,
+    pools: Vec<(String, loom_infrastructure_impl::ConnectedTenantPool)>,
+}
+
+impl TenantPools {
+    /// Re-read the workspace list and return a pool per workspace.  A
+    /// workspace whose database cannot be reached is logged and tried again
+    /// next time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the workspace list cannot be read.
+    pub async fn refresh(
+        &mut self,
+    ) -> anyhow::Result<&[(String, loom_infrastructure_impl::ConnectedTenantPool)]> {
+        use loom_infrastructure::query::Query;
+        use loom_infrastructure_impl::{Pool, admin::workspace::repositories::WorkspaceRepository};
+
+        let admin = match &self.admin {
+            Some(admin) => admin.clone(),
+            None => self.admin.insert(Pool::connect_admin().await?).clone(),
+        };
+        let workspace_ids: Vec<String> = WorkspaceRepository::from_pool(admin)
+            .await?
+            .all()
+            .await?
+            .iter()
+            .map(|workspace| workspace.get_id().to_string())
+            .collect();
+
+        self.pools.retain(|(id, _)| workspace_ids.contains(id));
+        for workspace_id in workspace_ids {
+            if self.pools.iter().any(|(id, _)| *id == workspace_id) {
+                continue;
+            }
+            match tenant_pool(&workspace_id).await {
+                Ok(pool) => self.pools.push((workspace_id, pool)),
+                Err(error) => tracing::warn!(
+                    workspace_id = %workspace_id,
+                    error = %error,
+                    "Failed to connect to the tenant database."
+                ),
+            }
+        }
+        Ok(&self.pools)
+    }
+}
@@ -6,8 +6,14 @@ use eventually::aggregate::{
 use loom_core::tenant::{
     customer::CustomerId,
     project::{CreateProjectInput, Project, ProjectEvent, ProjectId, UpdateProjectInput},
+    timesheet::Rounding,
+};
+use loom_infrastructure_impl::tenant::{
+    activity::repositories::ActivityRepository,
+    customer::repositories::CustomerRepository,
+    project::repositories::{ProjectRepository, ProjectRow},
+    timesheet::repositories::TimesheetRepository,
 };
-use loom_infrastructure_impl::tenant::project::repositories::{ProjectRepository, ProjectRow};
 
 pub async fn list(workspace_id: &str) -> Result<Vec<ProjectRow>> {
     let pool = super::tenant_pool(workspace_id).await?;
@@ -15,6 +21,12 @@ pub async fn list(workspace_id: &str) -> Result<Vec<ProjectRow>> {
     Ok(repo.all().await?)
 }
 
+pub async fn list_archived(workspace_id: &str) -> Result<Vec<ProjectRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ProjectRepository::from_pool(pool).await?;
+    Ok(repo.archived().await?)
+}
+
 pub async fn create(workspace_id: &str, customer_id: String, name: String) -> Result<ProjectRow> {
     crate::error::validate(CreateProjectInput { name: name.clone() })?;
 
@@ -22,14 +34,11 @@ pub async fn create(workspace_id: &str, customer_id: String, name: String) -> Re
     let repo = ProjectRepository::from_pool(pool).await?;
     let id = ProjectId::new();
     let cid: CustomerId = customer_id.parse()?;
-    let mut root = Root::<Project>::record_new(
-        ProjectEvent::Created {
-            id: id.clone(),
-            customer_id: cid.clone(),
-            name: name.clone(),
-        }
-        .into(),
-    )?;
+    let mut root = Root::<Project>::record_new(crate::audit::envelope(ProjectEvent::Created {
+        id: id.clone(),
+        customer_id: cid.clone(),
+        name: name.clone(),
+    }))?;
     repo.save(&mut root).await?;
     Ok(ProjectRow {
         id: id.to_string(),
@@ -42,6 +51,7 @@ pub async fn create(workspace_id: &str, customer_id: String, name: String) -> Re
         time_budget: None,
         money_budget: None,
         budget_is_monthly: false,
+        archived: false,
     })
 }
 
@@ -60,16 +70,13 @@ pub async fn update(
     let repo = ProjectRepository::from_pool(pool).await?;
     let agg_id: ProjectId = id.parse()?;
     let mut root = repo.get(&agg_id).await?;
-    root.record_that(
-        ProjectEvent::Updated {
-            name,
-            comment,
-            order_number,
-            visible,
-            billable,
-        }
-        .into(),
-    )?;
+    root.record_that(crate::audit::envelope(ProjectEvent::Updated {
+        name,
+        comment,
+        order_number,
+        visible,
+        billable,
+    }))?;
     repo.save(&mut root).await?;
     Ok(())
 }
@@ -85,14 +92,102 @@ pub async fn set_budget(
     let repo = ProjectRepository::from_pool(pool).await?;
     let agg_id: ProjectId = id.parse()?;
     let mut root = repo.get(&agg_id).await?;
-    root.record_that(
-        ProjectEvent::BudgetUpdated {
-            time_budget,
-            money_budget,
-            budget_is_monthly,
-        }
-        .into(),
-    )?;
+    root.record_that(crate::audit::envelope(ProjectEvent::BudgetUpdated {
+        time_budget,
+        money_budget,
+        budget_is_monthly,
+    }))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// The project's own rounding rules, `None` when it uses the
+/// customer's rules.
+pub async fn rounding(workspace_id: &str, id: &str) -> Result<Option<Rounding>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ProjectRepository::from_pool(pool).await?;
+    Ok(repo.rounding(id).await?)
+}
+
+pub async fn set_rounding(workspace_id: &str, id: &str, rounding: Option<Rounding>) -> Result<()> {
+    if let Some(rounding) = &rounding {
+        rounding
+            .validate()
+            .map_err(crate::error::ValidationError::new)?;
+    }
+
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ProjectRepository::from_pool(pool).await?;
+    let agg_id: ProjectId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    root.record_that(crate::audit::envelope(ProjectEvent::RoundingUpdated {
+        rounding,
+    }))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+pub async fn archive(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ProjectRepository::from_pool(pool).await?;
+    let agg_id: ProjectId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    if root.archived() {
+        return Err(crate::error::ValidationError::new("The project is already archived").into());
+    }
+    root.record_that(crate::audit::envelope(ProjectEvent::Archived {
+        with_customer: false,
+    }))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// Restore an archived project whose customer is not archived.
+pub async fn restore(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ProjectRepository::from_pool(pool.clone()).await?;
+    let agg_id: ProjectId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    if !root.archived() {
+        return Err(crate::error::ValidationError::new("The project is not archived").into());
+    }
+    let customer = CustomerRepository::from_pool(pool)
+        .await?
+        .get(root.customer_id())
+        .await?;
+    if customer.archived() {
+        return Err(crate::error::ValidationError::new(
+            "The project's customer is archived — restore the customer first",
+        )
+        .into());
+    }
+    root.record_that(crate::audit::envelope(ProjectEvent::Restored))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// Permanently delete a project that has no timesheets and no activities of
+/// its own.
+pub async fn delete(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let timesheets = TimesheetRepository::from_pool(pool.clone()).await?;
+    if timesheets.count_for_project(id).await? > 0 {
+        return Err(crate::error::ValidationError::new(
+            "Timesheets are booked on the project — archive it instead",
+        )
+        .into());
+    }
+    let activities = ActivityRepository::from_pool(pool.clone()).await?;
+    if activities.count_for_project(id).await? > 0 {
+        return Err(crate::error::ValidationError::new(
+            "The project still has activities — delete them first or archive the project",
+        )
+        .into());
+    }
+    let repo = ProjectRepository::from_pool(pool).await?;
+    let agg_id: ProjectId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    root.record_that(crate::audit::envelope(ProjectEvent::Deleted))?;
     repo.save(&mut root).await?;
     Ok(())
 }
@@ -35,22 +35,20 @@ pub async fn set_default(
     if let Some(existing) = repo.default_for_project(&project_id).await? {
         let existing_id: ProjectRateId = existing.id.parse()?;
         let mut root = repo.get(&existing_id).await?;
-        root.record_that(ProjectRateEvent::Removed.into())?;
+        root.record_that(crate::audit::envelope(ProjectRateEvent::Removed))?;
         repo.save(&mut root).await?;
     }
 
     let id = ProjectRateId::new();
     let pid: ProjectId = project_id.parse()?;
-    let mut root = Root::<ProjectRate>::record_new(
-        ProjectRateEvent::Set {
+    let mut root =
+        Root::<ProjectRate>::record_new(crate::audit::envelope(ProjectRateEvent::Set {
             id: id.clone(),
             project_id: pid,
             user_id: None,
             hourly_rate,
             internal_rate,
-        }
-        .into(),
-    )?;
+        }))?;
     repo.save(&mut root).await?;
 
     Ok(ProjectRateRow {
@@ -68,7 +66,7 @@ pub async fn remove_default(workspace_id: &str, project_id: &str) -> Result<()>
     if let Some(existing) = repo.default_for_project(project_id).await? {
         let existing_id: ProjectRateId = existing.id.parse()?;
         let mut root = repo.get(&existing_id).await?;
-        root.record_that(ProjectRateEvent::Removed.into())?;
+        root.record_that(crate::audit::envelope(ProjectRateEvent::Removed))?;
         repo.save(&mut root).await?;
     }
     Ok(())
@@ -0,0 +1,518 @@
+//! Reports of hours and amounts per project, and emailing them on a
+//! schedule.
+//!
+//! [`project_summary`] is the report itself.  A scheduled report says whose
+//! entries it covers, which period and projects, in which [`ReportFormat`]
+//! and to whom it is sent, and when: a cron expression in UTC (see
+//! [`Schedule`]).  The tenant projection daemon runs [`send_forever`], which
+//! sends every report whose schedule fired since it was last due through the
+//! transport configured in the environment (see [`crate::mail`]).  A report
+//! that cannot be sent is logged and skipped until its schedule fires
+//! again.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use eventually::aggregate::{
+    Root,
+    repository::{Getter, Saver},
+};
+use loom_core::tenant::{
+    overtime,
+    project::ProjectId,
+    report::{self, ProjectInfo, ProjectLine, ReportPeriod},
+    report_schedule::{
+        ReportFormat, ReportParameters, ReportSchedule, ReportScheduleEvent, ReportScheduleId,
+        ReportScope, Schedule,
+    },
+};
+use loom_infrastructure_impl::{
+    ConnectedTenantPool, Pool,
+    admin::workspace::repositories::WorkspaceRepository,
+    tenant::{
+        customer::repositories::CustomerRepository,
+        project::repositories::ProjectRepository,
+        report_schedule::repositories::{
+            ReportRunRepository, ReportRunRow, ReportScheduleRepository, ReportScheduleRow,
+        },
+        timesheet::repositories::{TimesheetRepository, TimesheetRow},
+    },
+};
+use tracing::{info, warn};
+
+use crate::mail::{self, Attachment, Email, MailTransport};
+
+/// Pause between two rounds of [`send_forever`].
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Recipients a report may have.
+const MAX_RECIPIENTS: usize = 20;
+
+/// What a scheduled report covers and where it goes, as entered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportDefinition {
+    pub name: String,
+    /// Cron expression in UTC.
+    pub schedule: String,
+    /// See [`ReportPeriod::as_str`].
+    pub period: String,
+    /// Only these projects; all if empty.
+    pub project_ids: Vec<String>,
+    /// See [`ReportFormat::as_str`].
+    pub format: String,
+    pub recipients: Vec<String>,
+}
+
+/// A definition checked and in the shape the aggregate stores.
+struct Validated {
+    name: String,
+    schedule: String,
+    parameters: ReportParameters,
+    format: ReportFormat,
+    recipients: Vec<String>,
+}
+
+fn validate(definition: &ReportDefinition) -> Result<Validated> {
+    let invalid = |msg: String| anyhow::Error::from(crate::error::ValidationError::new(msg));
+    let name = definition.name.trim().to_string();
+    if name.is_empty() {
+        return Err(invalid("The report needs a name".to_string()));
+    }
+    let schedule = definition.schedule.trim().to_string();
+    Schedule::parse(&schedule).map_err(invalid)?;
+    let period = ReportPeriod::parse(&definition.period)
+        .ok_or_else(|| invalid(format!("Unknown period: {}", definition.period)))?;
+    let format = ReportFormat::parse(&definition.format)
+        .ok_or_else(|| invalid(format!("Unknown format: {}", definition.format)))?;
+
+    let mut recipients: Vec<String> = definition
+        .recipients
+        .iter()
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+        .collect();
+    recipients.sort_unstable();
+    recipients.dedup();
+    if recipients.is_empty() {
+        return Err(invalid("Add at least one recipient".to_string()));
+    }
+    if recipients.len() > MAX_RECIPIENTS {
+        return Err(invalid(format!(
+            "A report can have at most {MAX_RECIPIENTS} recipients"
+        )));
+    }
+    if let Some(bad) = recipients.iter().find(|r| !mail::is_address(r)) {
+        return Err(invalid(format!("Not an email address: {bad}")));
+    }
+
+    let project_ids = definition
+        .project_ids
+        .iter()
+        .filter(|p| !p.is_empty())
+        .map(|p| p.parse::<ProjectId>())
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Validated {
+        name,
+        schedule,
+        parameters: ReportParameters {
+            period,
+            project_ids,
+        },
+        format,
+        recipients,
+    })
+}
+
+/// The scheduled reports the user owns.
+pub async fn schedules(workspace_id: &str, user_id: &str) -> Result<Vec<ReportScheduleRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ReportScheduleRepository::from_pool(pool).await?;
+    Ok(repo.for_owner(user_id).await?)
+}
+
+/// When each report of the workspace was last due and whether it was sent.
+pub async fn runs(workspace_id: &str) -> Result<Vec<ReportRunRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    Ok(ReportRunRepository::new(pool).all().await?)
+}
+
+/// When `row` is due next, as RFC-3339.
+#[must_use]
+pub fn next_run_at(row: &ReportScheduleRow) -> Option<String> {
+    Schedule::parse(&row.schedule)
+        .ok()?
+        .next_after(Utc::now())
+        .map(|t| t.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+/// Schedule a report owned by `owner_id`.  Whether the owner may see
+/// everyone's entries is for the caller to check.
+///
+/// # Errors
+///
+/// Returns a validation error if the name is blank, the schedule, period or
+/// format is invalid, or a recipient is not an email address.
+pub async fn create(
+    workspace_id: &str,
+    owner_id: &str,
+    scope: ReportScope,
+    definition: &ReportDefinition,
+) -> Result<ReportScheduleRow> {
+    let v = validate(definition)?;
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ReportScheduleRepository::from_pool(pool).await?;
+
+    let id = ReportScheduleId::new();
+    let mut root =
+        Root::<ReportSchedule>::record_new(crate::audit::envelope(ReportScheduleEvent::Created {
+            id: id.clone(),
+            owner_id: owner_id.parse()?,
+            name: v.name.clone(),
+            scope,
+            schedule: v.schedule.clone(),
+            parameters: v.parameters.clone(),
+            format: v.format,
+            recipients: v.recipients.clone(),
+        }))?;
+    repo.save(&mut root).await?;
+
+    Ok(ReportScheduleRow {
+        id: id.to_string(),
+        owner_id: owner_id.to_string(),
+        name: v.name,
+        scope,
+        schedule: v.schedule,
+        parameters: v.parameters,
+        format: v.format,
+        recipients: v.recipients,
+    })
+}
+
+async fn owned(
+    repo: &ReportScheduleRepository,
+    owner_id: &str,
+    id: &str,
+) -> Result<Root<ReportSchedule>> {
+    let root = repo.get(&id.parse::<ReportScheduleId>()?).await?;
+    if root.owner_id().to_string() != owner_id {
+        return Err(
+            crate::error::ValidationError::new("The report belongs to someone else").into(),
+        );
+    }
+    Ok(root)
+}
+
+/// # Errors
+///
+/// Returns a validation error like [`create`] or if the report belongs to
+/// someone else, or an error if it cannot be found or saved.
+pub async fn change(
+    workspace_id: &str,
+    owner_id: &str,
+    id: &str,
+    definition: &ReportDefinition,
+) -> Result<()> {
+    let v = validate(definition)?;
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ReportScheduleRepository::from_pool(pool).await?;
+    let mut root = owned(&repo, owner_id, id).await?;
+    root.record_that(crate::audit::envelope(ReportScheduleEvent::Changed {
+        name: v.name,
+        schedule: v.schedule,
+        parameters: v.parameters,
+        format: v.format,
+        recipients: v.recipients,
+    }))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// # Errors
+///
+/// Returns an error if the report belongs to someone else, cannot be found
+/// or saved.
+pub async fn remove(workspace_id: &str, owner_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = ReportScheduleRepository::from_pool(pool).await?;
+    let mut root = owned(&repo, owner_id, id).await?;
+    root.record_that(crate::audit::envelope(ReportScheduleEvent::Removed))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// Hours and amounts per project of the stopped entries that started from
+/// `from` up to, not including, `to`.  `user_id` limits the report to one
+/// user's entries and `project_ids`, if not empty, to those projects.
+pub async fn project_summary(
+    workspace_id: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+    user_id: Option<&str>,
+    project_ids: &[String],
+) -> Result<Vec<ProjectLine>> {
+    let currency = crate::workspace::get_workspace_settings(workspace_id)
+        .await?
+        .currency;
+    let pool = super::tenant_pool(workspace_id).await?;
+    let bound = |day: NaiveDate| day.and_time(chrono::NaiveTime::MIN).and_utc().to_rfc3339();
+    let entries = TimesheetRepository::from_pool(pool.clone())
+        .await?
+        .stopped_between(&bound(from), &bound(to))
+        .await?;
+
+    let customers = CustomerRepository::from_pool(pool.clone()).await?;
+    let customers: HashMap<String, (String, String)> = customers
+        .all()
+        .await?
+        .into_iter()
+        .chain(customers.archived().await?)
+        .map(|c| (c.id, (c.name, c.currency)))
+        .collect();
+    let projects = ProjectRepository::from_pool(pool).await?;
+    let projects: HashMap<String, ProjectInfo> = projects
+        .all()
+        .await?
+        .into_iter()
+        .chain(projects.archived().await?)
+        .map(|p| {
+            let (customer, currency) = customers
+                .get(&p.customer_id)
+                .cloned()
+                .unwrap_or_else(|| (String::new(), currency.clone()));
+            let info = ProjectInfo {
+                customer,
+                project: p.name,
+                currency,
+            };
+            (p.id, info)
+        })
+        .collect();
+
+    let wanted = |entry: &TimesheetRow| {
+        user_id.is_none_or(|u| entry.user_id == u)
+            && (project_ids.is_empty()
+                || entry
+                    .project_id
+                    .as_ref()
+                    .is_some_and(|p| project_ids.contains(p)))
+    };
+    Ok(report::summarize(
+        entries.iter().filter(|entry| wanted(entry)).map(|entry| {
+            let amount = if entry.billable {
+                entry.rate.unwrap_or_default()
+            } else {
+                0
+            };
+            (
+                entry.project_id.as_deref(),
+                i64::from(entry.duration.unwrap_or_default()),
+                amount,
+            )
+        }),
+        |id| projects.get(id).cloned(),
+        &currency,
+    ))
+}
+
+/// The email of a scheduled report sent on `today`.
+pub async fn render(
+    workspace_id: &str,
+    report: &ReportScheduleRow,
+    today: NaiveDate,
+) -> Result<Email> {
+    let week_start = crate::workspace::get_workspace_settings(workspace_id)
+        .await?
+        .week_start;
+    let (from, to) = report
+        .parameters
+        .period
+        .range(today, overtime::first_weekday(&week_start));
+    let until = to.pred_opt().unwrap_or(from);
+    let owner = match report.scope {
+        ReportScope::Personal => Some(report.owner_id.as_str()),
+        ReportScope::Workspace => None,
+    };
+    let project_ids: Vec<String> = report
+        .parameters
+        .project_ids
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let lines = project_summary(workspace_id, from, to, owner, &project_ids).await?;
+
+    let text = report::render_text(&report.name, from, until, &lines);
+    Ok(Email {
+        to: report.recipients.clone(),
+        subject: format!("{}: {from} to {until}", report.name),
+        html: match report.format {
+            ReportFormat::Html => Some(report::render_html(&report.name, from, until, &lines)),
+            ReportFormat::Csv => None,
+        },
+        text,
+        attachments: vec![Attachment {
+            filename: format!("report-{from}-{until}.csv"),
+            content_type: "text/csv; charset=utf-8".to_string(),
+            content: report::render_csv(&lines),
+        }],
+    })
+}
+
+/// Whether the owner of a report still belongs to the workspace.
+async fn owner_is_member(workspace_id: &str, owner_id: &str) -> Result<bool> {
+    Ok(WorkspaceRepository::from_pool(Pool::connect_admin().await?)
+        .await?
+        .find_workspaces_for_user(owner_id)
+        .await?
+        .iter()
+        .any(|(id, _)| id == workspace_id))
+}
+
+async fn send(
+    transport: &dyn MailTransport,
+    workspace_id: &str,
+    report: &ReportScheduleRow,
+    today: NaiveDate,
+) -> Result<()> {
+    if !owner_is_member(workspace_id, &report.owner_id).await? {
+        anyhow::bail!("the owner of the report has left the workspace");
+    }
+    transport
+        .send(&render(workspace_id, report, today).await?)
+        .await
+}
+
+/// Send one of the user's reports right away, as if it were due today.
+///
+/// # Errors
+///
+/// Returns a validation error if no mail transport is configured or the
+/// report belongs to someone else, or an error if it cannot be sent.
+pub async fn send_now(workspace_id: &str, owner_id: &str, id: &str) -> Result<()> {
+    let Some(transport) = mail::from_env()? else {
+        return Err(crate::error::ValidationError::new(
+            "Sending email is not set up on this server",
+        )
+        .into());
+    };
+    let pool = super::tenant_pool(workspace_id).await?;
+    let Some(report) = ReportScheduleRepository::from_pool(pool)
+        .await?
+        .find(id)
+        .await?
+        .filter(|r| r.owner_id == owner_id)
+    else {
+        return Err(
+            crate::error::ValidationError::new("The report belongs to someone else").into(),
+        );
+    };
+    send(
+        transport.as_ref(),
+        workspace_id,
+        &report,
+        Utc::now().date_naive(),
+    )
+    .await
+}
+
+/// Send the workspace's reports whose schedule fired since they were last
+/// due, and return how many were sent.  Reports seen for the first time are
+/// first due the next time their schedule fires.
+pub async fn send_due(
+    transport: &dyn MailTransport,
+    workspace_id: &str,
+    pool: &ConnectedTenantPool,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let reports = ReportScheduleRepository::from_pool(pool.clone())
+        .await?
+        .all()
+        .await?;
+    if reports.is_empty() {
+        return Ok(0);
+    }
+    let runs = ReportRunRepository::new(pool.clone());
+    let last_due: HashMap<String, String> = runs
+        .all()
+        .await?
+        .into_iter()
+        .map(|r| (r.schedule_id, r.last_due_at))
+        .collect();
+    let stamp = |t: DateTime<Utc>| t.to_rfc3339_opts(SecondsFormat::Secs, true);
+
+    let mut sent = 0;
+    for report in &reports {
+        let Ok(schedule) = Schedule::parse(&report.schedule) else {
+            continue;
+        };
+        let Some(last_due) = last_due
+            .get(&report.id)
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        else {
+            runs.begin(&report.id, &stamp(now)).await?;
+            continue;
+        };
+        let Some(due) = schedule.last_between(last_due.with_timezone(&Utc), now) else {
+            continue;
+        };
+        let error = match send(transport, workspace_id, report, due.date_naive()).await {
+            Ok(()) => {
+                sent += 1;
+                None
+            }
+            Err(error) => {
+                warn!(
+                    workspace_id = %workspace_id,
+                    report_id = %report.id,
+                    error = %error,
+                    "Failed to send a scheduled report."
+                );
+                Some(error.to_string())
+            }
+        };
+        runs.record(&report.id, &stamp(due), error.as_deref())
+            .await?;
+    }
+    Ok(sent)
+}
+
+/// Send the due reports of every workspace, round after round,
+/// through the transport configured in the environment.  Without one, no
+/// reports are sent.
+pub async fn send_forever() {
+    let transport = match mail::from_env() {
+        Ok(Some(transport)) => transport,
+        Ok(None) => {
+            info!(
+                "Neither {} nor {} is set; scheduled reports are not sent.",
+                mail::SMTP_URL_VAR,
+                mail::MAIL_DROP_DIR_VAR
+            );
+            return;
+        }
+        Err(error) => {
+            warn!(error = %error, "Failed to set up the mail transport; no reports are sent.");
+            return;
+        }
+    };
+    let mut tenants = super::TenantPools::default();
+    loop {
+        match tenants.refresh().await {
+            Ok(pools) => {
+                for (workspace_id, pool) in pools {
+                    if let Err(error) =
+                        send_due(transport.as_ref(), workspace_id, pool, Utc::now()).await
+                    {
+                        warn!(
+                            workspace_id = %workspace_id,
+                            error = %error,
+                            "Failed to send scheduled reports."
+                        );
+                    }
+                }
+            }
+            Err(error) => warn!(error = %error, "Failed to list the workspaces."),
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
@@ -0,0 +1,23 @@
+use anyhow::Result;
+use loom_infrastructure_impl::tenant::search::repositories::{
+    EntryScope, SearchHit, SearchRepository,
+};
+
+/// How many hits a search returns at most.
+const SEARCH_LIMIT: u32 = 20;
+
+/// Search the workspace for `query`, best match first.  Besides customers,
+/// projects, activities and tags, members find their own time entries;
+/// admins and approvers of this workspace find everyone's.
+pub async fn search(workspace_id: &str, user_id: &str, query: &str) -> Result<Vec<SearchHit>> {
+    let entries = if super::timesheet::sees_all_entries(workspace_id, user_id).await? {
+        EntryScope::All
+    } else {
+        EntryScope::Own(user_id)
+    };
+
+    let pool = super::tenant_pool(workspace_id).await?;
+    Ok(SearchRepository::new(pool)
+        .search(query, entries, SEARCH_LIMIT)
+        .await?)
+}
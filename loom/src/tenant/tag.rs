@@ -21,23 +21,27 @@ pub async fn list_for_timesheet(workspace_id: &str, timesheet_id: &str) -> Resul
     Ok(repo.for_timesheet(timesheet_id).await?)
 }
 
+pub async fn list_archived(workspace_id: &str) -> Result<Vec<TagRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TagRepository::from_pool(pool).await?;
+    Ok(repo.archived().await?)
+}
+
 pub async fn create(workspace_id: &str, name: String) -> Result<TagRow> {
     crate::error::validate(CreateTagInput { name: name.clone() })?;
 
     let pool = super::tenant_pool(workspace_id).await?;
     let repo = TagRepository::from_pool(pool).await?;
     let id = TagId::new();
-    let mut root = Root::<Tag>::record_new(
-        TagEvent::Created {
-            id: id.clone(),
-            name: name.clone(),
-        }
-        .into(),
-    )?;
+    let mut root = Root::<Tag>::record_new(crate::audit::envelope(TagEvent::Created {
+        id: id.clone(),
+        name: name.clone(),
+    }))?;
     repo.save(&mut root).await?;
     Ok(TagRow {
         id: id.to_string(),
         name,
+        archived: false,
     })
 }
 
@@ -48,7 +52,7 @@ pub async fn rename(workspace_id: &str, id: &str, name: String) -> Result<()> {
     let repo = TagRepository::from_pool(pool).await?;
     let agg_id: TagId = id.parse()?;
     let mut root = repo.get(&agg_id).await?;
-    root.record_that(TagEvent::Renamed { name }.into())?;
+    root.record_that(crate::audit::envelope(TagEvent::Renamed { name }))?;
     repo.save(&mut root).await?;
     Ok(())
 }
@@ -59,12 +63,9 @@ pub async fn tag_timesheet(workspace_id: &str, tag_id: &str, timesheet_id: &str)
     let agg_id: TagId = tag_id.parse()?;
     let ts_id: TimesheetId = timesheet_id.parse()?;
     let mut root = repo.get(&agg_id).await?;
-    root.record_that(
-        TagEvent::TimesheetTagged {
-            timesheet_id: ts_id,
-        }
-        .into(),
-    )?;
+    root.record_that(crate::audit::envelope(TagEvent::TimesheetTagged {
+        timesheet_id: ts_id,
+    }))?;
     repo.save(&mut root).await?;
     Ok(())
 }
@@ -75,12 +76,52 @@ pub async fn untag_timesheet(workspace_id: &str, tag_id: &str, timesheet_id: &st
     let agg_id: TagId = tag_id.parse()?;
     let ts_id: TimesheetId = timesheet_id.parse()?;
     let mut root = repo.get(&agg_id).await?;
-    root.record_that(
-        TagEvent::TimesheetUntagged {
-            timesheet_id: ts_id,
-        }
-        .into(),
-    )?;
+    root.record_that(crate::audit::envelope(TagEvent::TimesheetUntagged {
+        timesheet_id: ts_id,
+    }))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+pub async fn archive(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TagRepository::from_pool(pool).await?;
+    let agg_id: TagId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    if root.archived() {
+        return Err(crate::error::ValidationError::new("The tag is already archived").into());
+    }
+    root.record_that(crate::audit::envelope(TagEvent::Archived))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+pub async fn restore(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TagRepository::from_pool(pool).await?;
+    let agg_id: TagId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    if !root.archived() {
+        return Err(crate::error::ValidationError::new("The tag is not archived").into());
+    }
+    root.record_that(crate::audit::envelope(TagEvent::Restored))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// Permanently delete a tag that no timesheet carries.
+pub async fn delete(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TagRepository::from_pool(pool).await?;
+    if repo.timesheet_count(id).await? > 0 {
+        return Err(crate::error::ValidationError::new(
+            "The tag is used by timesheets — archive it instead",
+        )
+        .into());
+    }
+    let agg_id: TagId = id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    root.record_that(crate::audit::envelope(TagEvent::Deleted))?;
     repo.save(&mut root).await?;
     Ok(())
 }
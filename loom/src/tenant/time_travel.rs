@@ -0,0 +1,120 @@
+//! Time-travel reads: aggregates and reports as they were at a past point.
+//!
+//! Used for dispute resolution and period closing ("what did this project's
+//! rates and logged time look like on March 31st?").
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use eventually::aggregate::{
+    Aggregate, Root,
+    repository::{GetError, Getter},
+};
+use loom_core::tenant::approval::ApprovalStatus;
+use loom_infrastructure_impl::{
+    tenant::{
+        project::repositories::{ProjectRepository, ProjectRow},
+        project_rate::repositories::{ProjectRateRepository, ProjectRateRow},
+        timesheet::repositories::{TimesheetRepository, TimesheetRow},
+    },
+    time_travel::{HistoricalRepository, rebuild_tenant},
+};
+
+use crate::error::ValidationError;
+
+pub use loom_infrastructure_impl::time_travel::Cutoff;
+
+/// The cutoff at the RFC 3339 instant `instant`, in any offset.
+///
+/// # Errors
+///
+/// Returns a validation error if `instant` is not an RFC 3339 date and time.
+pub fn recorded_at(instant: &str) -> Result<Cutoff> {
+    let instant = DateTime::parse_from_rfc3339(instant)
+        .map_err(|e| ValidationError::new(format!("Invalid instant '{instant}': {e}")))?;
+    Ok(Cutoff::RecordedAt(instant.with_timezone(&Utc)))
+}
+
+/// Load an aggregate the way it looked at `cutoff`.
+///
+/// Returns `None` if the aggregate did not exist yet.
+pub async fn load_as_of<T>(
+    workspace_id: &str,
+    id: &T::Id,
+    cutoff: Cutoff,
+) -> Result<Option<Root<T>>>
+where
+    T: Aggregate + Send + Sync,
+    T::Id: Send + Sync,
+    T::Event: serde::de::DeserializeOwned,
+    T::Error: std::error::Error + Send + Sync + 'static,
+{
+    let pool = super::tenant_pool(workspace_id).await?;
+    match HistoricalRepository::new(pool, cutoff).get(id).await {
+        Ok(root) => Ok(Some(root)),
+        Err(GetError::NotFound) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// A project's rates and logged time at a point in the past.
+#[derive(Debug, Clone)]
+pub struct ProjectAsOf {
+    pub project: ProjectRow,
+    pub rates: Vec<ProjectRateRow>,
+    pub timesheets: Vec<TimesheetRow>,
+    /// Sum of the durations of the reported stopped timesheets, in seconds.
+    pub logged_seconds: i64,
+    /// Sum of the billable amounts of the reported stopped timesheets, in
+    /// cents.
+    pub logged_amount: i64,
+}
+
+/// Rebuild the workspace projections up to `cutoff` and report on one project
+/// as `user_id` sees it: like the live views, only admins and approvers see
+/// everyone's entries, other members their own.  With `approved_only` only
+/// entries approved at that point are counted.
+///
+/// Returns `None` if the project did not exist at that point.
+pub async fn project_as_of(
+    workspace_id: &str,
+    user_id: &str,
+    project_id: &str,
+    cutoff: Cutoff,
+    approved_only: bool,
+) -> Result<Option<ProjectAsOf>> {
+    let everyone = super::timesheet::sees_all_entries(workspace_id, user_id).await?;
+    let source = super::tenant_pool(workspace_id).await?;
+    let scratch = rebuild_tenant(&source, cutoff).await?;
+
+    let projects = ProjectRepository::from_pool(scratch.pool()).await?;
+    let Some(project) = projects.find(project_id).await? else {
+        return Ok(None);
+    };
+    let rates = ProjectRateRepository::from_pool(scratch.pool())
+        .await?
+        .for_project(project_id)
+        .await?;
+    let mut timesheets = TimesheetRepository::from_pool(scratch.pool())
+        .await?
+        .for_project(project_id)
+        .await?;
+    timesheets.retain(|t| {
+        (everyone || t.user_id == user_id)
+            && (!approved_only || t.approval_status == ApprovalStatus::Approved.as_str())
+    });
+
+    let logged_seconds = timesheets
+        .iter()
+        .filter_map(|t| t.duration)
+        .map(i64::from)
+        .sum();
+    let logged_amount = timesheets.iter().filter_map(|t| t.rate).sum();
+
+    Ok(Some(ProjectAsOf {
+        project,
+        rates,
+        timesheets,
+        logged_seconds,
+        logged_amount,
+    }))
+}
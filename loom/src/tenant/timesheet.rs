@@ -1,26 +1,39 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use eventually::aggregate::{
-    Root,
-    repository::{Getter, Saver},
+use eventually::{
+    aggregate::{
+        Root,
+        repository::{GetError, Getter, Saver},
+    },
+    message::Message,
 };
 use loom_core::{
+    permissions,
     shared::AggregateId,
     tenant::{
         activity::ActivityId,
+        approval::ApprovalStatus,
         project::ProjectId,
-        timesheet::{Timesheet, TimesheetEvent, TimesheetId},
+        tag::{TagEvent, TagId},
+        timesheet::{
+            PolicyViolation, Rounding, TimeEntryPolicy, Timesheet, TimesheetEvent, TimesheetId,
+        },
     },
 };
 use loom_infrastructure_impl::{
     ConnectedTenantPool,
     tenant::{
         activity_rate::repositories::ActivityRateRepository,
+        customer::repositories::CustomerRepository,
+        project::repositories::ProjectRepository,
         project_rate::repositories::ProjectRateRepository,
+        tag::repositories::TagRepository,
         timesheet::repositories::{TimesheetRepository, TimesheetRow},
     },
 };
 
+use crate::authorization::AuthorizationService;
+
 pub async fn recent(workspace_id: &str, user_id: &str) -> Result<Vec<TimesheetRow>> {
     let pool = super::tenant_pool(workspace_id).await?;
     let repo = TimesheetRepository::from_pool(pool).await?;
@@ -33,6 +46,45 @@ pub async fn running(workspace_id: &str, user_id: &str) -> Result<Option<Timeshe
     Ok(repo.running_for_user(user_id).await?)
 }
 
+/// Whether `user_id` sees everyone's entries in this workspace rather than
+/// only their own: admins and approvers do.
+pub(crate) async fn sees_all_entries(workspace_id: &str, user_id: &str) -> Result<bool> {
+    Ok(
+        AuthorizationService::is_admin_in(user_id, workspace_id).await?
+            || AuthorizationService::has_permission(
+                user_id,
+                workspace_id,
+                permissions::TIMESHEET_APPROVE,
+            )
+            .await?,
+    )
+}
+
+/// A single timesheet, or `None` if it does not exist.
+pub async fn find(workspace_id: &str, timesheet_id: &str) -> Result<Option<TimesheetRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TimesheetRepository::from_pool(pool).await?;
+    Ok(repo.find(timesheet_id).await?)
+}
+
+/// The user's timesheets that start on the days `from..=to` (`YYYY-MM-DD`),
+/// oldest first.
+///
+/// # Errors
+///
+/// Returns a validation error if a date is invalid or `to` is before `from`.
+pub async fn between(
+    workspace_id: &str,
+    user_id: &str,
+    from: &str,
+    to: &str,
+) -> Result<Vec<TimesheetRow>> {
+    let (from, to) = day_range(from, to)?;
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TimesheetRepository::from_pool(pool).await?;
+    Ok(repo.starting_between(user_id, &from, &to).await?)
+}
+
 pub async fn start(
     workspace_id: &str,
     user_id: &str,
@@ -42,7 +94,7 @@ pub async fn start(
     billable: bool,
 ) -> Result<TimesheetRow> {
     let pool = super::tenant_pool(workspace_id).await?;
-    let repo = TimesheetRepository::from_pool(pool).await?;
+    let repo = TimesheetRepository::from_pool(pool.clone()).await?;
 
     // Enforce: only one running timer per user at a time.
     if repo.running_for_user(user_id).await?.is_some() {
@@ -52,15 +104,23 @@ pub async fn start(
         .into());
     }
 
+    if let Some(project_id) = project_id.as_deref() {
+        ensure_budget_left(workspace_id, &pool, project_id).await?;
+    }
+
+    let now = Utc::now();
+    ensure_unlocked(workspace_id, user_id, user_id, now).await?;
+    check_policy(&pool, &policy(workspace_id).await?, user_id, now, None, &[]).await?;
+
     let id = TimesheetId::new();
     let uid: AggregateId = user_id.parse()?;
     let pid: Option<ProjectId> = project_id.as_deref().map(str::parse).transpose()?;
     let aid: Option<ActivityId> = activity_id.as_deref().map(str::parse).transpose()?;
-    let start_time = Utc::now().to_rfc3339();
+    let start_time = now.to_rfc3339();
     let timezone = "UTC".to_string();
 
-    let mut root = Root::<Timesheet>::record_new(
-        TimesheetEvent::Started {
+    let mut root =
+        Root::<Timesheet>::record_new(crate::audit::envelope(TimesheetEvent::Started {
             id: id.clone(),
             user_id: uid,
             project_id: pid,
@@ -68,17 +128,12 @@ pub async fn start(
             start_time: start_time.clone(),
             timezone: timezone.clone(),
             billable,
-        }
-        .into(),
-    )?;
+        }))?;
     if description.is_some() {
-        root.record_that(
-            TimesheetEvent::Updated {
-                description: description.clone(),
-                billable,
-            }
-            .into(),
-        )?;
+        root.record_that(crate::audit::envelope(TimesheetEvent::Updated {
+            description: description.clone(),
+            billable,
+        }))?;
     }
     repo.save(&mut root).await?;
 
@@ -98,14 +153,97 @@ pub async fn start(
         fixed_rate: None,
         internal_rate: None,
         rate: None,
+        billed_duration: None,
+        approval_status: ApprovalStatus::Draft.as_str().to_string(),
+        approval_id: None,
+        break_duration: 0,
+        paused_at: None,
     })
 }
 
+/// Start a timer with the given tags.  Tags that are archived or gone are
+/// left out.
+///
+/// # Errors
+///
+/// Returns an error if the timer cannot be started or tagged.
+pub(crate) async fn start_tagged(
+    workspace_id: &str,
+    user_id: &str,
+    project_id: Option<String>,
+    activity_id: Option<String>,
+    description: Option<String>,
+    billable: bool,
+    tag_ids: &[String],
+) -> Result<TimesheetRow> {
+    let row = start(
+        workspace_id,
+        user_id,
+        project_id,
+        activity_id,
+        description,
+        billable,
+    )
+    .await?;
+    let tags = TagRepository::from_pool(super::tenant_pool(workspace_id).await?).await?;
+    let timesheet_id: TimesheetId = row.id.parse()?;
+    for tag_id in tag_ids {
+        let mut root = match tags.get(&tag_id.parse::<TagId>()?).await {
+            Ok(root) => root,
+            Err(GetError::NotFound) => continue,
+            Err(e) => return Err(e.into()),
+        };
+        if root.archived() {
+            continue;
+        }
+        root.record_that(crate::audit::envelope(TagEvent::TimesheetTagged {
+            timesheet_id: timesheet_id.clone(),
+        }))?;
+        tags.save(&mut root).await?;
+    }
+    Ok(row)
+}
+
+/// Start a new timer cloned from one of the user's entries: same project,
+/// activity, description, billable flag and tags.
+///
+/// # Errors
+///
+/// Returns an error if the entry belongs to someone else or has been
+/// deleted, a timer is already running, or the new timer cannot be saved.
+pub async fn restart(
+    workspace_id: &str,
+    user_id: &str,
+    timesheet_id: &str,
+) -> Result<TimesheetRow> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TimesheetRepository::from_pool(pool.clone()).await?;
+    let source = existing(&repo, &timesheet_id.parse()?).await?;
+    if source.user_id().to_string() != user_id {
+        return Err(
+            crate::error::ValidationError::new("Only your own entries can be restarted").into(),
+        );
+    }
+
+    let row = start(
+        workspace_id,
+        user_id,
+        source.project_id().map(ToString::to_string),
+        source.activity_id().map(ToString::to_string),
+        source.description().map(ToString::to_string),
+        source.billable(),
+    )
+    .await?;
+    copy_tags(&pool, source.id(), &row.id.parse()?).await?;
+    Ok(row)
+}
+
 /// # Errors
 ///
 /// Returns an error if the timesheet cannot be found or saved.
 pub async fn reassign(
     workspace_id: &str,
+    actor_id: &str,
     timesheet_id: &str,
     project_id: String,
     activity_id: String,
@@ -114,15 +252,13 @@ pub async fn reassign(
     let repo = TimesheetRepository::from_pool(pool).await?;
     let agg_id: TimesheetId = timesheet_id.parse()?;
     let mut root = repo.get(&agg_id).await?;
+    ensure_entry_unlocked(workspace_id, actor_id, &root).await?;
     let pid: ProjectId = project_id.parse()?;
     let aid: ActivityId = activity_id.parse()?;
-    root.record_that(
-        TimesheetEvent::Reassigned {
-            project_id: pid,
-            activity_id: aid,
-        }
-        .into(),
-    )?;
+    root.record_that(crate::audit::envelope(TimesheetEvent::Reassigned {
+        project_id: pid,
+        activity_id: aid,
+    }))?;
     repo.save(&mut root).await?;
     Ok(())
 }
@@ -132,6 +268,7 @@ pub async fn reassign(
 /// Returns an error if the timesheet cannot be found or saved.
 pub async fn update(
     workspace_id: &str,
+    actor_id: &str,
     timesheet_id: &str,
     description: Option<String>,
     billable: bool,
@@ -140,13 +277,14 @@ pub async fn update(
     let repo = TimesheetRepository::from_pool(pool).await?;
     let agg_id: TimesheetId = timesheet_id.parse()?;
     let mut root = repo.get(&agg_id).await?;
-    root.record_that(
-        TimesheetEvent::Updated {
-            description,
-            billable,
-        }
-        .into(),
-    )?;
+    ensure_entry_unlocked(workspace_id, actor_id, &root).await?;
+    if root.end_time().is_some() {
+        check_fields(workspace_id, &root, description.as_deref()).await?;
+    }
+    root.record_that(crate::audit::envelope(TimesheetEvent::Updated {
+        description,
+        billable,
+    }))?;
     repo.save(&mut root).await?;
     Ok(())
 }
@@ -154,47 +292,108 @@ pub async fn update(
 /// # Errors
 ///
 /// Returns an error if the timesheet cannot be found or saved.
-pub async fn stop(workspace_id: &str, timesheet_id: &str) -> Result<()> {
+pub async fn stop(workspace_id: &str, actor_id: &str, timesheet_id: &str) -> Result<()> {
     let pool = super::tenant_pool(workspace_id).await?;
     let ts_repo = TimesheetRepository::from_pool(pool.clone()).await?;
 
     let agg_id: TimesheetId = timesheet_id.parse()?;
     let mut root = ts_repo.get(&agg_id).await?;
+    ensure_entry_unlocked(workspace_id, actor_id, &root).await?;
+    check_fields(workspace_id, &root, root.description()).await?;
 
-    let end_time = Utc::now();
-    let end_rfc = end_time.to_rfc3339();
-    let duration = chrono::DateTime::parse_from_rfc3339(root.start_time())
-        .ok()
-        .map_or(0, |start| {
-            #[allow(clippy::cast_possible_truncation)]
-            let secs = (end_time - start.with_timezone(&Utc)).num_seconds() as i32;
-            secs
-        });
-
-    // Look up the applicable rate (only possible when project/activity are assigned)
-    let project_id = root.project_id().map(std::string::ToString::to_string);
-    let activity_id = root.activity_id().map(std::string::ToString::to_string);
-    let (hourly_rate, internal_rate) = match (&project_id, &activity_id) {
-        (Some(pid), Some(aid)) => resolve_rate(&pool, pid, aid).await,
-        _ => (None, None),
+    // A paused timer ends when it was paused.
+    let start = parse_datetime_utc(root.start_time())?;
+    let end = match root.paused_at() {
+        Some(paused_at) => parse_datetime_utc(paused_at)?,
+        None => Utc::now(),
     };
-    let rate = hourly_rate.map(|hr| hr * i64::from(duration) / 3600);
+    let breaks = root.break_duration();
+    record_rated_stop(workspace_id, &pool, &mut root, start, end, breaks).await?;
+    ts_repo.save(&mut root).await?;
+    Ok(())
+}
 
-    root.record_that(
-        TimesheetEvent::Stopped {
-            end_time: end_rfc,
-            duration,
-            hourly_rate,
-            fixed_rate: None,
-            internal_rate,
-            rate,
-        }
-        .into(),
-    )?;
+/// Stop a forgotten timer at `end` on the user's behalf.  Unlike [`stop`],
+/// neither required fields nor period locks hold it back.  A paused timer
+/// ends when it was paused, if that is earlier.
+///
+/// # Errors
+///
+/// Returns an error if the timer is not running, or the timesheet cannot be
+/// found or saved.
+pub async fn stop_at(workspace_id: &str, timesheet_id: &str, end: DateTime<Utc>) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let ts_repo = TimesheetRepository::from_pool(pool.clone()).await?;
+
+    let agg_id: TimesheetId = timesheet_id.parse()?;
+    let mut root = ts_repo.get(&agg_id).await?;
+    if root.end_time().is_some() {
+        return Err(crate::error::ValidationError::new("The timer is not running").into());
+    }
+    let start = parse_datetime_utc(root.start_time())?;
+    let end = match root.paused_at() {
+        Some(paused_at) => parse_datetime_utc(paused_at)?.min(end),
+        None => end,
+    };
+    let breaks = root.break_duration();
+    record_rated_stop(workspace_id, &pool, &mut root, start, end, breaks).await?;
     ts_repo.save(&mut root).await?;
     Ok(())
 }
 
+/// Pause a running timer, e.g. for a lunch break.
+///
+/// # Errors
+///
+/// Returns an error if the timer is stopped or already paused, or the
+/// timesheet cannot be found or saved.
+pub async fn pause(workspace_id: &str, actor_id: &str, timesheet_id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TimesheetRepository::from_pool(pool).await?;
+    let agg_id: TimesheetId = timesheet_id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    ensure_entry_unlocked(workspace_id, actor_id, &root).await?;
+    if root.end_time().is_some() {
+        return Err(
+            crate::error::ValidationError::new("Only a running timer can be paused").into(),
+        );
+    }
+    if root.paused_at().is_some() {
+        return Err(crate::error::ValidationError::new("The timer is already paused").into());
+    }
+    root.record_that(crate::audit::envelope(TimesheetEvent::Paused {
+        paused_at: Utc::now().to_rfc3339(),
+    }))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// Resume a paused timer.  The break is excluded from the duration.
+///
+/// # Errors
+///
+/// Returns an error if the timer is not paused, or the timesheet cannot be
+/// found or saved.
+pub async fn resume(workspace_id: &str, actor_id: &str, timesheet_id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TimesheetRepository::from_pool(pool).await?;
+    let agg_id: TimesheetId = timesheet_id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    ensure_entry_unlocked(workspace_id, actor_id, &root).await?;
+    let Some(paused_at) = root.paused_at() else {
+        return Err(crate::error::ValidationError::new("The timer is not paused").into());
+    };
+    let paused_at = parse_datetime_utc(paused_at)?;
+    let now = Utc::now();
+    let break_duration = i32::try_from((now - paused_at).num_seconds().max(0)).unwrap_or(i32::MAX);
+    root.record_that(crate::audit::envelope(TimesheetEvent::Resumed {
+        resumed_at: now.to_rfc3339(),
+        break_duration,
+    }))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
 /// # Errors
 ///
 /// Returns an error if the timesheet cannot be found or saved.
@@ -203,11 +402,57 @@ pub async fn export(workspace_id: &str, timesheet_id: &str) -> Result<()> {
     let repo = TimesheetRepository::from_pool(pool).await?;
     let agg_id: TimesheetId = timesheet_id.parse()?;
     let mut root = repo.get(&agg_id).await?;
-    root.record_that(TimesheetEvent::Exported.into())?;
+    root.record_that(crate::audit::envelope(TimesheetEvent::Exported))?;
     repo.save(&mut root).await?;
     Ok(())
 }
 
+/// Export every stopped, not yet exported entry that starts within
+/// `from..=to` (`YYYY-MM-DD`), optionally only approved ones.
+///
+/// Returns the exported entries.
+///
+/// # Errors
+///
+/// Returns an error if a date is invalid or a timesheet cannot be saved.
+pub async fn export_period(
+    workspace_id: &str,
+    from: &str,
+    to: &str,
+    approved_only: bool,
+) -> Result<Vec<TimesheetRow>> {
+    let (from, to) = day_range(from, to)?;
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TimesheetRepository::from_pool(pool).await?;
+    let rows = repo.exportable_between(&from, &to, approved_only).await?;
+    for row in &rows {
+        let agg_id: TimesheetId = row.id.parse()?;
+        let mut root = repo.get(&agg_id).await?;
+        root.record_that(crate::audit::envelope(TimesheetEvent::Exported))?;
+        repo.save(&mut root).await?;
+    }
+    Ok(rows)
+}
+
+/// Turn the inclusive days `from..=to` (`YYYY-MM-DD`) into RFC-3339 UTC
+/// bounds `[from, to)` for comparing with stored start times.
+pub(crate) fn day_range(from: &str, to: &str) -> Result<(String, String)> {
+    let parse = |day: &str| {
+        chrono::NaiveDate::parse_from_str(day, "%Y-%m-%d")
+            .map_err(|_| crate::error::ValidationError::new(format!("Invalid date: {day}")))
+    };
+    let (from, to) = (parse(from)?, parse(to)?);
+    if to < from {
+        return Err(crate::error::ValidationError::new(
+            "The end date must not be before the start date",
+        )
+        .into());
+    }
+    let bound =
+        |day: chrono::NaiveDate| day.and_time(chrono::NaiveTime::MIN).and_utc().to_rfc3339();
+    Ok((bound(from), bound(to + chrono::Days::new(1))))
+}
+
 /// Create a completed timesheet from explicit start and end times.
 ///
 /// Used for manual ("after the fact") time entry.  Times are accepted as either
@@ -233,14 +478,21 @@ pub async fn create_manual(
     if end_dt <= start_dt {
         return Err(crate::error::ValidationError::new("End time must be after start time").into());
     }
-    #[allow(clippy::cast_possible_truncation)]
-    let duration = (end_dt - start_dt).num_seconds() as i32;
-    let start_rfc = start_dt.to_rfc3339();
-    let end_rfc = end_dt.to_rfc3339();
 
     let pool = super::tenant_pool(workspace_id).await?;
     let repo = TimesheetRepository::from_pool(pool.clone()).await?;
 
+    let policy = policy(workspace_id).await?;
+    policy
+        .check_on_stop(
+            project_id.as_deref(),
+            activity_id.as_deref(),
+            description.as_deref(),
+        )
+        .map_err(violation)?;
+    ensure_unlocked(workspace_id, user_id, user_id, start_dt).await?;
+    check_policy(&pool, &policy, user_id, start_dt, Some(end_dt), &[]).await?;
+
     let id = TimesheetId::new();
     let uid: AggregateId = user_id.parse()?;
     let pid: Option<ProjectId> = project_id.as_deref().map(str::parse).transpose()?;
@@ -248,45 +500,24 @@ pub async fn create_manual(
     let pid_str = pid.as_ref().map(ToString::to_string);
     let aid_str = aid.as_ref().map(ToString::to_string);
 
-    let (hourly_rate, internal_rate) = match (&pid_str, &aid_str) {
-        (Some(p), Some(a)) => resolve_rate(&pool, p, a).await,
-        _ => (None, None),
-    };
-    let rate = hourly_rate.map(|hr| hr * i64::from(duration) / 3600);
-
-    let mut root = Root::<Timesheet>::record_new(
-        TimesheetEvent::Started {
+    let mut root =
+        Root::<Timesheet>::record_new(crate::audit::envelope(TimesheetEvent::Started {
             id: id.clone(),
             user_id: uid,
             project_id: pid,
             activity_id: aid,
-            start_time: start_rfc.clone(),
+            start_time: start_dt.to_rfc3339(),
             timezone: "UTC".to_string(),
             billable,
-        }
-        .into(),
-    )?;
-    root.record_that(
-        TimesheetEvent::Stopped {
-            end_time: end_rfc.clone(),
-            duration,
-            hourly_rate,
-            fixed_rate: None,
-            internal_rate,
-            rate,
-        }
-        .into(),
-    )?;
+        }))?;
+    let billing = record_rated_stop(workspace_id, &pool, &mut root, start_dt, end_dt, 0).await?;
     if let Some(ref desc) = description
         && !desc.is_empty()
     {
-        root.record_that(
-            TimesheetEvent::Updated {
-                description: Some(desc.clone()),
-                billable,
-            }
-            .into(),
-        )?;
+        root.record_that(crate::audit::envelope(TimesheetEvent::Updated {
+            description: Some(desc.clone()),
+            billable,
+        }))?;
     }
     repo.save(&mut root).await?;
 
@@ -295,17 +526,22 @@ pub async fn create_manual(
         user_id: user_id.to_string(),
         project_id: pid_str,
         activity_id: aid_str,
-        start_time: start_rfc,
-        end_time: Some(end_rfc),
-        duration: Some(duration),
+        start_time: root.start_time().to_string(),
+        end_time: root.end_time().map(ToString::to_string),
+        duration: root.duration(),
         description,
         timezone: "UTC".to_string(),
         billable,
         exported: false,
-        hourly_rate,
+        hourly_rate: billing.hourly_rate,
         fixed_rate: None,
-        internal_rate,
-        rate,
+        internal_rate: billing.internal_rate,
+        rate: billing.rate,
+        billed_duration: Some(billing.billed_duration),
+        approval_status: ApprovalStatus::Draft.as_str().to_string(),
+        approval_id: None,
+        break_duration: 0,
+        paused_at: None,
     })
 }
 
@@ -314,44 +550,817 @@ pub async fn create_manual(
 /// For a stopped timesheet both `start_time` and `end_time` must be supplied.
 /// For a running timer supply only `start_time`; `end_time` must be `None`.
 /// Times are accepted as RFC-3339 or `datetime-local` (`YYYY-MM-DDTHH:MM`) UTC.
+/// A stopped timesheet is rounded and its amount recalculated.
 ///
 /// # Errors
 ///
 /// Returns an error if the times are invalid, out of order, or the timesheet cannot be saved.
 pub async fn update_time(
     workspace_id: &str,
+    actor_id: &str,
     timesheet_id: &str,
     start_time: String,
     end_time: Option<String>,
 ) -> Result<()> {
     let start_dt = parse_datetime_utc(&start_time)?;
-    let (end_rfc, duration) = if let Some(ref et) = end_time {
-        let end_dt = parse_datetime_utc(et)?;
-        if end_dt <= start_dt {
-            return Err(
-                crate::error::ValidationError::new("End time must be after start time").into(),
-            );
-        }
-        #[allow(clippy::cast_possible_truncation)]
-        let dur = (end_dt - start_dt).num_seconds() as i32;
-        (Some(end_dt.to_rfc3339()), Some(dur))
+    let end_dt = end_time.as_deref().map(parse_datetime_utc).transpose()?;
+    if end_dt.is_some_and(|end_dt| end_dt <= start_dt) {
+        return Err(crate::error::ValidationError::new("End time must be after start time").into());
+    }
+
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TimesheetRepository::from_pool(pool.clone()).await?;
+    let agg_id: TimesheetId = timesheet_id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    ensure_entry_unlocked(workspace_id, actor_id, &root).await?;
+    ensure_unlocked(
+        workspace_id,
+        actor_id,
+        &root.user_id().to_string(),
+        start_dt,
+    )
+    .await?;
+    check_policy(
+        &pool,
+        &policy(workspace_id).await?,
+        &root.user_id().to_string(),
+        start_dt,
+        end_dt,
+        &[timesheet_id],
+    )
+    .await?;
+    // Completed entries are rounded and rated anew for the corrected times.
+    if let Some(end_dt) = end_dt {
+        let breaks = root.break_duration();
+        record_rated_stop(workspace_id, &pool, &mut root, start_dt, end_dt, breaks).await?;
     } else {
-        (None, None)
+        root.record_that(crate::audit::envelope(TimesheetEvent::TimeUpdated {
+            start_time: start_dt.to_rfc3339(),
+            end_time: None,
+            duration: None,
+        }))?;
+    }
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// Delete a mistaken timesheet together with its tags.
+///
+/// # Errors
+///
+/// Returns an error if the timesheet has been exported, cannot be found or saved.
+pub async fn delete(workspace_id: &str, actor_id: &str, timesheet_id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TimesheetRepository::from_pool(pool.clone()).await?;
+    let agg_id: TimesheetId = timesheet_id.parse()?;
+    let mut root = repo.get(&agg_id).await?;
+    ensure_entry_unlocked(workspace_id, actor_id, &root).await?;
+    ensure_not_exported(&root)?;
+
+    untag_all(&pool, &agg_id).await?;
+    root.record_that(crate::audit::envelope(TimesheetEvent::Deleted))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// Copy a stopped timesheet, tags included, to a new start time.  The copy
+/// belongs to `user_id`, who may only copy other users' entries if they see
+/// everyone's.
+///
+/// The copy keeps the duration of the original.  Without `start_time` it
+/// starts at the same time of day as the original, today.
+///
+/// # Errors
+///
+/// Returns an error if the timesheet belongs to someone else, has been
+/// deleted or is still running, the time is invalid, or the copy cannot be
+/// saved.
+pub async fn duplicate(
+    workspace_id: &str,
+    user_id: &str,
+    timesheet_id: &str,
+    start_time: Option<String>,
+) -> Result<TimesheetRow> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TimesheetRepository::from_pool(pool.clone()).await?;
+    let source = existing(&repo, &timesheet_id.parse()?).await?;
+    if source.user_id().to_string() != user_id && !sees_all_entries(workspace_id, user_id).await? {
+        return Err(
+            crate::error::ValidationError::new("Only your own entries can be copied").into(),
+        );
+    }
+    let (start, end) = completed_span(&source)?;
+
+    let new_start = match start_time {
+        Some(s) => parse_datetime_utc(&s)?,
+        None => Utc::now().date_naive().and_time(start.time()).and_utc(),
     };
+    let new_end = new_start + (end - start);
+    ensure_unlocked(workspace_id, user_id, user_id, new_start).await?;
+    check_policy(
+        &pool,
+        &policy(workspace_id).await?,
+        user_id,
+        new_start,
+        Some(new_end),
+        &[],
+    )
+    .await?;
+    let uid: AggregateId = user_id.parse()?;
+    record_copy(
+        workspace_id,
+        &pool,
+        &repo,
+        &source,
+        &uid,
+        new_start,
+        new_end,
+    )
+    .await
+}
+
+/// Split a stopped timesheet at `at` into two adjoining entries.
+///
+/// The original ends at `at`; the returned new entry covers the rest and
+/// carries the same assignment, description and tags.  Both pieces get their
+/// rates resolved anew.
+///
+/// # Errors
+///
+/// Returns an error if the timesheet is running, exported or has breaks, `at`
+/// does not fall inside it, or either piece cannot be saved.
+pub async fn split(
+    workspace_id: &str,
+    actor_id: &str,
+    timesheet_id: &str,
+    at: String,
+) -> Result<TimesheetRow> {
+    let at = parse_datetime_utc(&at)?;
 
     let pool = super::tenant_pool(workspace_id).await?;
-    let repo = TimesheetRepository::from_pool(pool).await?;
+    let repo = TimesheetRepository::from_pool(pool.clone()).await?;
     let agg_id: TimesheetId = timesheet_id.parse()?;
     let mut root = repo.get(&agg_id).await?;
-    root.record_that(
+    ensure_entry_unlocked(workspace_id, actor_id, &root).await?;
+    ensure_not_exported(&root)?;
+    let (start, end) = completed_span(&root)?;
+    if root.break_duration() > 0 {
+        return Err(
+            crate::error::ValidationError::new("Timesheets with breaks cannot be split").into(),
+        );
+    }
+    if at <= start || at >= end {
+        return Err(crate::error::ValidationError::new(
+            "The split point must lie between the start and end of the timesheet",
+        )
+        .into());
+    }
+    let policy = policy(workspace_id).await?;
+    let now = Utc::now();
+    for (from, to) in [(start, at), (at, end)] {
+        policy.check_times(from, Some(to), now).map_err(violation)?;
+    }
+
+    let source = (*root).clone();
+    record_rated_stop(workspace_id, &pool, &mut root, start, at, 0).await?;
+    repo.save(&mut root).await?;
+
+    let uid = source.user_id().clone();
+    record_copy(workspace_id, &pool, &repo, &source, &uid, at, end).await
+}
+
+/// Merge two stopped timesheets of the same user, project and activity.
+///
+/// The earlier entry, which must end where the later one starts, is
+/// extended to the end of the later one, which is deleted.  The later
+/// entry's tags are carried over and the rate is resolved anew.
+///
+/// # Errors
+///
+/// Returns an error if either timesheet is deleted, running or exported, they
+/// differ in user, project or activity, they are not adjacent, or they cannot
+/// be saved.
+pub async fn merge(
+    workspace_id: &str,
+    actor_id: &str,
+    first_id: &str,
+    second_id: &str,
+) -> Result<()> {
+    if first_id == second_id {
+        return Err(
+            crate::error::ValidationError::new("A timesheet cannot be merged with itself").into(),
+        );
+    }
+
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = TimesheetRepository::from_pool(pool.clone()).await?;
+    let first = existing(&repo, &first_id.parse()?).await?;
+    let second = existing(&repo, &second_id.parse()?).await?;
+    for t in [&first, &second] {
+        ensure_not_exported(t)?;
+        ensure_entry_unlocked(workspace_id, actor_id, t).await?;
+    }
+    if first.user_id() != second.user_id()
+        || first.project_id() != second.project_id()
+        || first.activity_id() != second.activity_id()
+    {
+        return Err(crate::error::ValidationError::new(
+            "Only timesheets of the same user, project and activity can be merged",
+        )
+        .into());
+    }
+
+    let (first_span, second_span) = (completed_span(&first)?, completed_span(&second)?);
+    let (mut earlier, earlier_span, mut later, later_span) = if first_span.0 <= second_span.0 {
+        (first, first_span, second, second_span)
+    } else {
+        (second, second_span, first, first_span)
+    };
+    if later_span.0 < earlier_span.1 {
+        return Err(
+            crate::error::ValidationError::new("Overlapping timesheets cannot be merged").into(),
+        );
+    }
+    if later_span.0 > earlier_span.1 {
+        return Err(crate::error::ValidationError::new(
+            "Only timesheets that follow on from each other can be merged",
+        )
+        .into());
+    }
+    check_policy(
+        &pool,
+        &policy(workspace_id).await?,
+        &earlier.user_id().to_string(),
+        earlier_span.0,
+        Some(later_span.1),
+        &[first_id, second_id],
+    )
+    .await?;
+
+    let breaks = earlier.break_duration() + later.break_duration();
+    record_rated_stop(
+        workspace_id,
+        &pool,
+        &mut earlier,
+        earlier_span.0,
+        later_span.1,
+        breaks,
+    )
+    .await?;
+    if earlier.description().is_none_or(str::is_empty)
+        && let Some(description) = later.description().filter(|d| !d.is_empty())
+    {
+        let billable = earlier.billable();
+        earlier.record_that(crate::audit::envelope(TimesheetEvent::Updated {
+            description: Some(description.to_string()),
+            billable,
+        }))?;
+    }
+    repo.save(&mut earlier).await?;
+
+    copy_tags(&pool, later.id(), earlier.id()).await?;
+    untag_all(&pool, later.id()).await?;
+    later.record_that(crate::audit::envelope(TimesheetEvent::Deleted))?;
+    repo.save(&mut later).await?;
+    Ok(())
+}
+
+/// Record on `root` a change its owner made on an offline replica, the way
+/// the matching controller would have.  Only the instants and fields the
+/// replica recorded are taken over: breaks, rounding and amounts are worked
+/// out here.  [`check_offline_entry`] checks the outcome.
+///
+/// # Errors
+///
+/// Returns a validation error if the change cannot be made offline or does
+/// not apply to the entry.
+pub(crate) async fn replay_offline_change(
+    workspace_id: &str,
+    pool: &ConnectedTenantPool,
+    root: &mut Root<Timesheet>,
+    event: TimesheetEvent,
+) -> Result<()> {
+    let instant = |s: &str| {
+        parse_datetime_utc(s).map_err(|e| crate::error::ValidationError::new(e.to_string()))
+    };
+    match event {
+        TimesheetEvent::Stopped { end_time, .. } | TimesheetEvent::Amended { end_time, .. } => {
+            let start = instant(root.start_time())?;
+            let end = instant(&end_time)?;
+            // A correction already rated by the preceding `TimeUpdated`.
+            if root.end_time().map(instant).transpose()? == Some(end) {
+                return Ok(());
+            }
+            let end = match root.paused_at() {
+                Some(paused_at) => instant(paused_at)?.min(end),
+                None => end,
+            };
+            if end <= start {
+                return Err(crate::error::ValidationError::new(
+                    "End time must be after start time",
+                )
+                .into());
+            }
+            let breaks = root.break_duration();
+            record_rated_stop(workspace_id, pool, root, start, end, breaks).await?;
+        }
+        TimesheetEvent::Paused { paused_at } => {
+            root.record_that(crate::audit::envelope(TimesheetEvent::Paused {
+                paused_at: instant(&paused_at)?.to_rfc3339(),
+            }))?;
+        }
+        TimesheetEvent::Resumed { resumed_at, .. } => {
+            let Some(paused_at) = root.paused_at() else {
+                return Err(crate::error::ValidationError::new("The timer is not paused").into());
+            };
+            let paused_at = instant(paused_at)?;
+            let resumed_at = instant(&resumed_at)?;
+            let break_duration =
+                i32::try_from((resumed_at - paused_at).num_seconds().max(0)).unwrap_or(i32::MAX);
+            root.record_that(crate::audit::envelope(TimesheetEvent::Resumed {
+                resumed_at: resumed_at.to_rfc3339(),
+                break_duration,
+            }))?;
+        }
         TimesheetEvent::TimeUpdated {
-            start_time: start_dt.to_rfc3339(),
-            end_time: end_rfc,
-            duration,
+            start_time,
+            end_time,
+            ..
+        } => {
+            let start = instant(&start_time)?;
+            let end = end_time.as_deref().map(instant).transpose()?;
+            if let Some(end) = end {
+                if end <= start {
+                    return Err(crate::error::ValidationError::new(
+                        "End time must be after start time",
+                    )
+                    .into());
+                }
+                let breaks = root.break_duration();
+                record_rated_stop(workspace_id, pool, root, start, end, breaks).await?;
+            } else {
+                root.record_that(crate::audit::envelope(TimesheetEvent::TimeUpdated {
+                    start_time: start.to_rfc3339(),
+                    end_time: None,
+                    duration: None,
+                }))?;
+            }
+        }
+        TimesheetEvent::Deleted => {
+            ensure_not_exported(root)?;
+            untag_all(pool, root.id()).await?;
+            root.record_that(crate::audit::envelope(TimesheetEvent::Deleted))?;
+        }
+        event @ (TimesheetEvent::Updated { .. } | TimesheetEvent::Reassigned { .. }) => {
+            root.record_that(crate::audit::envelope(event))?;
+        }
+        event => {
+            return Err(crate::error::ValidationError::new(format!(
+                "{} cannot be recorded offline",
+                event.name()
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Check an entry changed offline by [`replay_offline_change`] against the
+/// rules the controllers apply online: period locks, the time entry policy
+/// with its overlaps and required fields, a single running timer and, for
+/// `new` entries, the project budget.
+///
+/// # Errors
+///
+/// Returns a validation error if the entry breaks one of the rules.
+pub(crate) async fn check_offline_entry(
+    workspace_id: &str,
+    pool: &ConnectedTenantPool,
+    timesheet: &Timesheet,
+    new: bool,
+) -> Result<()> {
+    let id = timesheet.id().to_string();
+    // Offline changes are only accepted from the entry's owner.
+    let user_id = timesheet.user_id().to_string();
+    ensure_entry_unlocked(workspace_id, &user_id, timesheet).await?;
+    if new && let Some(project_id) = timesheet.project_id() {
+        ensure_budget_left(workspace_id, pool, &project_id.to_string()).await?;
+    }
+
+    let start = parse_datetime_utc(timesheet.start_time())?;
+    let end = timesheet.end_time().map(parse_datetime_utc).transpose()?;
+    check_policy(
+        pool,
+        &policy(workspace_id).await?,
+        &user_id,
+        start,
+        end,
+        &[&id],
+    )
+    .await?;
+    if end.is_some() {
+        return check_fields(workspace_id, timesheet, timesheet.description()).await;
+    }
+    let running = TimesheetRepository::from_pool(pool.clone())
+        .await?
+        .running_for_user(&user_id)
+        .await?;
+    if running.is_some_and(|row| row.id != id) {
+        return Err(crate::error::ValidationError::new(
+            "A timer is already running — stop it before starting a new one",
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Refuse new time on `project_id` once it has used up its budget, if the
+/// workspace blocks over-budget projects.
+async fn ensure_budget_left(
+    workspace_id: &str,
+    pool: &ConnectedTenantPool,
+    project_id: &str,
+) -> Result<()> {
+    if crate::workspace::get_workspace_settings(workspace_id)
+        .await?
+        .block_over_budget
+        && super::budget::is_over_budget(pool, project_id).await?
+    {
+        return Err(crate::error::ValidationError::new(
+            "The project has used up its budget — timers can no longer be started on it",
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// The workspace's time entry policy.
+async fn policy(workspace_id: &str) -> Result<TimeEntryPolicy> {
+    Ok(crate::workspace::get_workspace_settings(workspace_id)
+        .await?
+        .time_entry_policy)
+}
+
+fn violation(violation: PolicyViolation) -> anyhow::Error {
+    crate::error::ValidationError::new(violation.to_string()).into()
+}
+
+/// Check an entry's times against the policy, including overlaps with the
+/// user's other entries.  `exclude` lists timesheets that do not count, such
+/// as the entry being changed.
+async fn check_policy(
+    pool: &ConnectedTenantPool,
+    policy: &TimeEntryPolicy,
+    user_id: &str,
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+    exclude: &[&str],
+) -> Result<()> {
+    policy
+        .check_times(start, end, Utc::now())
+        .map_err(violation)?;
+    if !policy.disallow_overlaps {
+        return Ok(());
+    }
+
+    // The text comparison in the repository is widened by a second on each
+    // side; the exact check happens on the parsed times below.
+    let margin = chrono::Duration::seconds(1);
+    let to = end.map_or_else(
+        || "9999-12-31T23:59:59+00:00".to_string(),
+        |end| (end + margin).to_rfc3339(),
+    );
+    let candidates = TimesheetRepository::from_pool(pool.clone())
+        .await?
+        .for_user_between(user_id, &(start - margin).to_rfc3339(), &to)
+        .await?;
+    let mut others = Vec::with_capacity(candidates.len());
+    for row in candidates {
+        if exclude.contains(&row.id.as_str()) {
+            continue;
         }
-        .into(),
-    )?;
+        let other_end = row
+            .end_time
+            .as_deref()
+            .map(parse_datetime_utc)
+            .transpose()?;
+        others.push((parse_datetime_utc(&row.start_time)?, other_end));
+    }
+    policy
+        .check_overlaps(start, end, &others)
+        .map_err(violation)
+}
+
+/// Check the fields the policy requires on a stopped entry.
+async fn check_fields(
+    workspace_id: &str,
+    timesheet: &Timesheet,
+    description: Option<&str>,
+) -> Result<()> {
+    policy(workspace_id)
+        .await?
+        .check_on_stop(
+            timesheet.project_id().map(ToString::to_string).as_deref(),
+            timesheet.activity_id().map(ToString::to_string).as_deref(),
+            description,
+        )
+        .map_err(violation)
+}
+
+/// Refuse an entry of `user_id` starting at `start` if it falls into a locked
+/// period, unless `actor_id`, the user making the change, may edit locked
+/// entries.
+///
+/// Exporting is not a change to the entry and stays possible.
+async fn ensure_unlocked(
+    workspace_id: &str,
+    actor_id: &str,
+    user_id: &str,
+    start: DateTime<Utc>,
+) -> Result<()> {
+    let locks = crate::workspace::period_locks(workspace_id).await?;
+    let Some(until) = locks.locked_until(user_id) else {
+        return Ok(());
+    };
+    if !locks.is_locked(user_id, start) {
+        return Ok(());
+    }
+    if AuthorizationService::is_admin(actor_id).await?
+        || AuthorizationService::has_permission(
+            actor_id,
+            workspace_id,
+            permissions::TIMESHEET_EDIT_LOCKED,
+        )
+        .await?
+    {
+        return Ok(());
+    }
+    Err(crate::error::ValidationError::new(format!("Time entries up to {until} are locked")).into())
+}
+
+/// [`ensure_unlocked`] for an existing entry, which must not be awaiting
+/// approval or approved either.
+pub(crate) async fn ensure_entry_unlocked(
+    workspace_id: &str,
+    actor_id: &str,
+    timesheet: &Timesheet,
+) -> Result<()> {
+    match timesheet.approval() {
+        ApprovalStatus::Submitted => {
+            return Err(crate::error::ValidationError::new(
+                "The entry awaits approval and cannot be changed",
+            )
+            .into());
+        }
+        ApprovalStatus::Approved => {
+            return Err(crate::error::ValidationError::new(
+                "The entry has been approved and can no longer be changed",
+            )
+            .into());
+        }
+        ApprovalStatus::Draft | ApprovalStatus::Rejected => {}
+    }
+    ensure_unlocked(
+        workspace_id,
+        actor_id,
+        &timesheet.user_id().to_string(),
+        parse_datetime_utc(timesheet.start_time())?,
+    )
+    .await
+}
+
+fn ensure_not_exported(timesheet: &Timesheet) -> Result<()> {
+    if timesheet.exported() {
+        return Err(crate::error::ValidationError::new(
+            "The timesheet has been exported and can no longer be changed",
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// The timesheet `id`, unless it does not exist or has been deleted.
+async fn existing(repo: &TimesheetRepository, id: &TimesheetId) -> Result<Root<Timesheet>> {
+    let root = repo.get(id).await?;
+    if root.deleted() {
+        return Err(GetError::NotFound.into());
+    }
+    Ok(root)
+}
+
+/// Start and end of a stopped timesheet.
+fn completed_span(timesheet: &Timesheet) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+    let Some(end_time) = timesheet.end_time() else {
+        return Err(crate::error::ValidationError::new(
+            "The timer is still running — stop it first",
+        )
+        .into());
+    };
+    Ok((
+        parse_datetime_utc(timesheet.start_time())?,
+        parse_datetime_utc(end_time)?,
+    ))
+}
+
+/// What the event recorded by [`record_rated_stop`] bills.
+struct Billing {
+    hourly_rate: Option<i64>,
+    internal_rate: Option<i64>,
+    rate: Option<i64>,
+    billed_duration: i32,
+}
+
+/// Record a `Stopped` event ending the timesheet at `end` with `breaks`
+/// seconds of breaks, with the applicable rounding applied and rates resolved
+/// for the billed duration.  A timesheet that was already stopped is
+/// corrected with an `Amended` event instead.  Rounding that moves the start
+/// is recorded as a `TimeUpdated` first.
+async fn record_rated_stop(
+    workspace_id: &str,
+    pool: &ConnectedTenantPool,
+    root: &mut Root<Timesheet>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    breaks: i32,
+) -> Result<Billing> {
+    let stopped = root.end_time().is_some();
+    let project_id = root.project_id().map(ToString::to_string);
+    let activity_id = root.activity_id().map(ToString::to_string);
+    let rounding = rounding(workspace_id, pool, project_id.as_deref()).await?;
+    let rounded = rounding.apply_with_breaks(start, end, breaks);
+    if rounded.start != parse_datetime_utc(root.start_time())? {
+        root.record_that(crate::audit::envelope(TimesheetEvent::TimeUpdated {
+            start_time: rounded.start.to_rfc3339(),
+            end_time: Some(rounded.end.to_rfc3339()),
+            duration: Some(rounded.duration),
+        }))?;
+    }
+
+    let (hourly_rate, internal_rate) = match (&project_id, &activity_id) {
+        (Some(p), Some(a)) => resolve_rate(pool, p, a).await,
+        _ => (None, None),
+    };
+    let rate = hourly_rate.map(|hr| hr * i64::from(rounded.billed_duration) / 3600);
+
+    let end_time = rounded.end.to_rfc3339();
+    let billed_duration = Some(rounded.billed_duration);
+    let rounding = (!rounding.is_off()).then_some(rounding);
+    let event = if stopped {
+        TimesheetEvent::Amended {
+            end_time,
+            duration: rounded.duration,
+            hourly_rate,
+            fixed_rate: None,
+            internal_rate,
+            rate,
+            billed_duration,
+            rounding,
+            break_duration: breaks,
+        }
+    } else {
+        TimesheetEvent::Stopped {
+            end_time,
+            duration: rounded.duration,
+            hourly_rate,
+            fixed_rate: None,
+            internal_rate,
+            rate,
+            billed_duration,
+            rounding,
+            break_duration: breaks,
+        }
+    };
+    root.record_that(crate::audit::envelope(event))?;
+    Ok(Billing {
+        hourly_rate,
+        internal_rate,
+        rate,
+        billed_duration: rounded.billed_duration,
+    })
+}
+
+/// The rounding rules for an entry of `project_id`: the project's own, else
+/// its customer's, else the workspace's.
+async fn rounding(
+    workspace_id: &str,
+    pool: &ConnectedTenantPool,
+    project_id: Option<&str>,
+) -> Result<Rounding> {
+    if let Some(project_id) = project_id {
+        let projects = ProjectRepository::from_pool(pool.clone()).await?;
+        if let Some(rounding) = projects.rounding(project_id).await? {
+            return Ok(rounding);
+        }
+        let customers = CustomerRepository::from_pool(pool.clone()).await?;
+        if let Some(project) = projects.find(project_id).await?
+            && let Some(rounding) = customers.rounding(&project.customer_id).await?
+        {
+            return Ok(rounding);
+        }
+    }
+    Ok(crate::workspace::get_workspace_settings(workspace_id)
+        .await?
+        .rounding)
+}
+
+/// Record a completed copy of `source` for `user_id` running from `start` to
+/// `end`, with the source's assignment, description, billable flag and tags.
+async fn record_copy(
+    workspace_id: &str,
+    pool: &ConnectedTenantPool,
+    repo: &TimesheetRepository,
+    source: &Timesheet,
+    user_id: &AggregateId,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<TimesheetRow> {
+    let id = TimesheetId::new();
+    let mut root =
+        Root::<Timesheet>::record_new(crate::audit::envelope(TimesheetEvent::Started {
+            id: id.clone(),
+            user_id: user_id.clone(),
+            project_id: source.project_id().cloned(),
+            activity_id: source.activity_id().cloned(),
+            start_time: start.to_rfc3339(),
+            timezone: source.timezone().to_string(),
+            billable: source.billable(),
+        }))?;
+    let billing = record_rated_stop(
+        workspace_id,
+        pool,
+        &mut root,
+        start,
+        end,
+        source.break_duration(),
+    )
+    .await?;
+    if let Some(description) = source.description() {
+        root.record_that(crate::audit::envelope(TimesheetEvent::Updated {
+            description: Some(description.to_string()),
+            billable: source.billable(),
+        }))?;
+    }
     repo.save(&mut root).await?;
+    copy_tags(pool, source.id(), &id).await?;
+
+    Ok(TimesheetRow {
+        id: id.to_string(),
+        user_id: user_id.to_string(),
+        project_id: root.project_id().map(ToString::to_string),
+        activity_id: root.activity_id().map(ToString::to_string),
+        start_time: root.start_time().to_string(),
+        end_time: root.end_time().map(ToString::to_string),
+        duration: root.duration(),
+        description: root.description().map(ToString::to_string),
+        timezone: root.timezone().to_string(),
+        billable: root.billable(),
+        exported: false,
+        hourly_rate: billing.hourly_rate,
+        fixed_rate: None,
+        internal_rate: billing.internal_rate,
+        rate: billing.rate,
+        billed_duration: Some(billing.billed_duration),
+        approval_status: ApprovalStatus::Draft.as_str().to_string(),
+        approval_id: None,
+        break_duration: root.break_duration(),
+        paused_at: None,
+    })
+}
+
+/// Tag `to` with every tag `from` carries that `to` lacks.
+async fn copy_tags(pool: &ConnectedTenantPool, from: &TimesheetId, to: &TimesheetId) -> Result<()> {
+    let tags = TagRepository::from_pool(pool.clone()).await?;
+    let existing: Vec<String> = tags
+        .for_timesheet(&to.to_string())
+        .await?
+        .into_iter()
+        .map(|t| t.id)
+        .collect();
+    for row in tags.for_timesheet(&from.to_string()).await? {
+        if existing.contains(&row.id) {
+            continue;
+        }
+        let tag_id: TagId = row.id.parse()?;
+        let mut root = tags.get(&tag_id).await?;
+        root.record_that(crate::audit::envelope(TagEvent::TimesheetTagged {
+            timesheet_id: to.clone(),
+        }))?;
+        tags.save(&mut root).await?;
+    }
+    Ok(())
+}
+
+/// Remove every tag from the timesheet.
+async fn untag_all(pool: &ConnectedTenantPool, timesheet_id: &TimesheetId) -> Result<()> {
+    let tags = TagRepository::from_pool(pool.clone()).await?;
+    for row in tags.for_timesheet(&timesheet_id.to_string()).await? {
+        let tag_id: TagId = row.id.parse()?;
+        let mut root = tags.get(&tag_id).await?;
+        root.record_that(crate::audit::envelope(TagEvent::TimesheetUntagged {
+            timesheet_id: timesheet_id.clone(),
+        }))?;
+        tags.save(&mut root).await?;
+    }
     Ok(())
 }
 
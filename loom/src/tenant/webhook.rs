@@ -0,0 +1,420 @@
+//! Outgoing webhooks: subscriptions, signed deliveries and their log.
+//!
+//! The tenant projection daemon wraps its projector in [`WebhookDispatcher`],
+//! which queues a delivery in `webhook_deliveries` for every subscription
+//! that wants an event.  [`deliver_due`] sends whatever is due as a JSON
+//! `POST` signed with the subscription's secret (see [`sign`]).  A delivery
+//! that fails is retried after [`retry_delay`] until [`MAX_ATTEMPTS`] are
+//! used up; [`redeliver`] queues it again by hand.
+//!
+//! [`MAX_ATTEMPTS`]: loom_core::tenant::webhook::MAX_ATTEMPTS
+
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{Result, bail};
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use eventually::aggregate::{
+    Root,
+    repository::{Getter, Saver},
+};
+use hmac::{Hmac, Mac};
+use loom_core::{
+    audit::ACTOR_METADATA_KEY,
+    tenant::webhook::{Webhook, WebhookEvent, WebhookId, is_subscribable, retry_delay},
+};
+use loom_infrastructure_impl::{
+    ConnectedTenantPool, Projector, RawEvent,
+    tenant::webhook::repositories::{
+        DeliveryRow, WebhookDeliveryRepository, WebhookRepository, WebhookRow,
+    },
+};
+use serde_json::{Value, json};
+use sha2::Sha256;
+use tracing::warn;
+
+/// Name of the event, e.g. `TimesheetStopped`.
+pub const EVENT_HEADER: &str = "X-Loom-Event";
+/// Id of the delivery; the same for every retry of it.
+pub const DELIVERY_HEADER: &str = "X-Loom-Delivery";
+/// `sha256=` followed by the hex HMAC-SHA256 of the body, keyed with the
+/// subscription's secret.
+pub const SIGNATURE_HEADER: &str = "X-Loom-Signature";
+
+/// How long an endpoint may take to answer.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Deliveries sent per workspace and round of [`deliver_forever`].
+const BATCH_SIZE: i64 = 50;
+/// Pause between two rounds of [`deliver_forever`].
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Deliveries shown in the log.
+const LOG_LIMIT: i64 = 200;
+
+fn validate(url: &str, event_types: &mut Vec<String>) -> Result<()> {
+    let valid_url = url::Url::parse(url)
+        .is_ok_and(|u| matches!(u.scheme(), "http" | "https") && u.host().is_some());
+    if !valid_url {
+        return Err(crate::error::ValidationError::new(
+            "The URL must start with http:// or https://",
+        )
+        .into());
+    }
+    event_types.sort_unstable();
+    event_types.dedup();
+    if event_types.is_empty() {
+        return Err(crate::error::ValidationError::new("Choose at least one event").into());
+    }
+    if let Some(unknown) = event_types.iter().find(|t| !is_subscribable(t)) {
+        return Err(crate::error::ValidationError::new(format!("Unknown event: {unknown}")).into());
+    }
+    Ok(())
+}
+
+fn generate_secret() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
+
+/// Every webhook of the workspace.
+pub async fn webhooks(workspace_id: &str) -> Result<Vec<WebhookRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = WebhookRepository::from_pool(pool).await?;
+    Ok(repo.all().await?)
+}
+
+/// Subscribe `url` to `event_types` with a freshly generated secret.
+///
+/// # Errors
+///
+/// Returns a validation error if the URL is not an `http(s)` URL or an event
+/// cannot be subscribed to.
+pub async fn subscribe(
+    workspace_id: &str,
+    url: String,
+    mut event_types: Vec<String>,
+) -> Result<WebhookRow> {
+    let url = url.trim().to_string();
+    validate(&url, &mut event_types)?;
+
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = WebhookRepository::from_pool(pool).await?;
+
+    let id = WebhookId::new();
+    let secret = generate_secret();
+    let mut root = Root::<Webhook>::record_new(crate::audit::envelope(WebhookEvent::Subscribed {
+        id: id.clone(),
+        url: url.clone(),
+        secret: secret.clone(),
+        event_types: event_types.clone(),
+    }))?;
+    repo.save(&mut root).await?;
+
+    Ok(WebhookRow {
+        id: id.to_string(),
+        url,
+        secret,
+        event_types,
+        // Not known until the event has been projected.
+        since_position: 0,
+    })
+}
+
+/// # Errors
+///
+/// Returns a validation error if the URL is not an `http(s)` URL or an event
+/// cannot be subscribed to, or an error if the webhook cannot be found or
+/// saved.
+pub async fn change(
+    workspace_id: &str,
+    id: &str,
+    url: String,
+    mut event_types: Vec<String>,
+) -> Result<()> {
+    let url = url.trim().to_string();
+    validate(&url, &mut event_types)?;
+
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = WebhookRepository::from_pool(pool).await?;
+    let mut root = repo.get(&id.parse::<WebhookId>()?).await?;
+    root.record_that(crate::audit::envelope(WebhookEvent::Changed {
+        url,
+        event_types,
+    }))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// Replace the signing secret and return the new one.  Deliveries still
+/// queued are signed with it as well.
+pub async fn rotate_secret(workspace_id: &str, id: &str) -> Result<String> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = WebhookRepository::from_pool(pool).await?;
+    let mut root = repo.get(&id.parse::<WebhookId>()?).await?;
+    let secret = generate_secret();
+    root.record_that(crate::audit::envelope(WebhookEvent::SecretRotated {
+        secret: secret.clone(),
+    }))?;
+    repo.save(&mut root).await?;
+    Ok(secret)
+}
+
+/// Unsubscribe; deliveries still queued are given up on.
+pub async fn remove(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = WebhookRepository::from_pool(pool).await?;
+    let mut root = repo.get(&id.parse::<WebhookId>()?).await?;
+    root.record_that(crate::audit::envelope(WebhookEvent::Removed))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// The most recent deliveries of one webhook, or of all, newest first.
+pub async fn deliveries(workspace_id: &str, webhook_id: Option<&str>) -> Result<Vec<DeliveryRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    Ok(WebhookDeliveryRepository::new(pool)
+        .list(webhook_id, LOG_LIMIT)
+        .await?)
+}
+
+/// Queue the event of a logged delivery once more.
+///
+/// # Errors
+///
+/// Returns an error if the delivery does not exist or its webhook has been
+/// removed.
+pub async fn redeliver(workspace_id: &str, delivery_id: i64) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = WebhookDeliveryRepository::new(pool.clone());
+    let Some(delivery) = repo.find(delivery_id).await? else {
+        bail!("delivery {delivery_id} not found");
+    };
+    let webhooks = WebhookRepository::from_pool(pool).await?.all().await?;
+    if !webhooks.iter().any(|w| w.id == delivery.webhook_id) {
+        return Err(crate::error::ValidationError::new("The webhook has been removed").into());
+    }
+    repo.redeliver(&delivery).await?;
+    Ok(())
+}
+
+/// Value of [`SIGNATURE_HEADER`] for `body`.
+///
+/// # Panics
+///
+/// Never: HMAC accepts keys of any length.
+#[must_use]
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// The request body announcing `event`.
+///
+/// # Errors
+///
+/// Returns an error if the event payload is not valid JSON.
+pub fn payload(workspace_id: &str, event: &RawEvent) -> Result<String> {
+    let data: Value = serde_json::from_slice(&event.payload_bytes)?;
+    Ok(json!({
+        "event": event.event_type,
+        "workspace_id": workspace_id,
+        "aggregate_id": event.stream_id,
+        "version": event.version,
+        "position": event.global_position,
+        "actor": event.metadata.get(ACTOR_METADATA_KEY),
+        "data": data,
+    })
+    .to_string())
+}
+
+/// An HTTP client for [`send`].
+///
+/// # Errors
+///
+/// Returns an error if the TLS backend cannot be initialized.
+pub fn client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent(concat!("loom-webhooks/", env!("CARGO_PKG_VERSION")))
+        .build()?)
+}
+
+/// `POST` a delivery to `url` and return the HTTP status of the answer.
+///
+/// # Errors
+///
+/// Returns an error if the endpoint cannot be reached or does not answer in
+/// time.
+pub async fn send(
+    client: &reqwest::Client,
+    url: &str,
+    secret: &str,
+    delivery: &DeliveryRow,
+) -> Result<u16> {
+    let response = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, &delivery.event_type)
+        .header(DELIVERY_HEADER, delivery.id.to_string())
+        .header(SIGNATURE_HEADER, sign(secret, delivery.payload.as_bytes()))
+        .body(delivery.payload.clone())
+        .send()
+        .await?;
+    Ok(response.status().as_u16())
+}
+
+/// Send the workspace's due deliveries and record the outcome of each.
+/// Returns the number of attempts made.
+pub async fn deliver_due(client: &reqwest::Client, pool: &ConnectedTenantPool) -> Result<usize> {
+    let repo = WebhookDeliveryRepository::new(pool.clone());
+    let due = repo.due(BATCH_SIZE).await?;
+    if due.is_empty() {
+        return Ok(0);
+    }
+    let webhooks: HashMap<String, WebhookRow> = WebhookRepository::from_pool(pool.clone())
+        .await?
+        .all()
+        .await?
+        .into_iter()
+        .map(|w| (w.id.clone(), w))
+        .collect();
+
+    for delivery in &due {
+        let Some(webhook) = webhooks.get(&delivery.webhook_id) else {
+            repo.mark_failed(delivery.id, None, None, "The webhook has been removed")
+                .await?;
+            continue;
+        };
+        let (status, error) = match send(client, &webhook.url, &webhook.secret, delivery).await {
+            Ok(status) if (200..300).contains(&status) => {
+                repo.mark_delivered(delivery.id, i32::from(status)).await?;
+                continue;
+            }
+            Ok(status) => (Some(i32::from(status)), format!("HTTP {status}")),
+            Err(error) => (None, error.to_string()),
+        };
+        let attempts = u32::try_from(delivery.attempts).unwrap_or_default() + 1;
+        let next_attempt_at = retry_delay(attempts)
+            .map(|delay| (Utc::now() + delay).to_rfc3339_opts(SecondsFormat::Secs, true));
+        repo.mark_failed(delivery.id, next_attempt_at, status, &error)
+            .await?;
+    }
+    Ok(due.len())
+}
+
+/// Deliver the due webhooks of every workspace, round after round.  Errors
+/// are logged and the workspace is tried again next round.
+pub async fn deliver_forever() {
+    let client = match client() {
+        Ok(client) => client,
+        Err(error) => {
+            warn!(error = %error, "Failed to create the webhook client; no webhooks are sent.");
+            return;
+        }
+    };
+    let mut tenants = super::TenantPools::default();
+    loop {
+        match tenants.refresh().await {
+            Ok(pools) => {
+                for (workspace_id, pool) in pools {
+                    if let Err(error) = deliver_due(&client, pool).await {
+                        warn!(
+                            workspace_id = %workspace_id,
+                            error = %error,
+                            "Failed to deliver webhooks."
+                        );
+                    }
+                }
+            }
+            Err(error) => warn!(error = %error, "Failed to list the workspaces."),
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Wraps the tenant projector and queues a delivery for every webhook
+/// subscribed to the event.
+///
+/// Deliveries are queued after the inner projector ran, so a webhook sees
+/// the subscriptions as of its own event.  The subscriptions are cached and
+/// reloaded after every webhook event.  A delivery that cannot be queued is
+/// logged and never holds up the projection.
+pub struct WebhookDispatcher<P> {
+    workspace_id: String,
+    pool: ConnectedTenantPool,
+    inner: P,
+    webhooks: Option<Vec<WebhookRow>>,
+}
+
+impl<P> WebhookDispatcher<P> {
+    #[must_use]
+    pub fn new(workspace_id: impl Into<String>, pool: ConnectedTenantPool, inner: P) -> Self {
+        Self {
+            workspace_id: workspace_id.into(),
+            pool,
+            inner,
+            webhooks: None,
+        }
+    }
+
+    async fn dispatch(&mut self, event: &RawEvent) -> Result<()> {
+        if event.event_type.starts_with("Webhook") {
+            self.webhooks = None;
+            return Ok(());
+        }
+        if !is_subscribable(&event.event_type) {
+            return Ok(());
+        }
+        if self.webhooks.is_none() {
+            let webhooks = WebhookRepository::from_pool(self.pool.clone())
+                .await?
+                .all()
+                .await?;
+            self.webhooks = Some(webhooks);
+        }
+        let mut wanting = self
+            .webhooks
+            .iter()
+            .flatten()
+            .filter(|w| w.wants(&event.event_type, event.global_position))
+            .peekable();
+        if wanting.peek().is_none() {
+            return Ok(());
+        }
+
+        let body = payload(&self.workspace_id, event)?;
+        let repo = WebhookDeliveryRepository::new(self.pool.clone());
+        for webhook in wanting {
+            repo.enqueue(
+                &webhook.id,
+                event.global_position,
+                &event.stream_id,
+                &event.event_type,
+                &body,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<P> Projector for WebhookDispatcher<P>
+where
+    P: Projector<Error = loom_infrastructure_impl::Error> + Send + Sync,
+{
+    type Error = loom_infrastructure_impl::Error;
+
+    async fn handle(&mut self, event: RawEvent) -> Result<(), Self::Error> {
+        self.inner.handle(event.clone()).await?;
+        if let Err(error) = self.dispatch(&event).await {
+            warn!(
+                workspace_id = %self.workspace_id,
+                event_type = %event.event_type,
+                error = %error,
+                "Failed to queue webhook deliveries."
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,372 @@
+//! Work contracts, public holidays and the overtime balance they imply.
+//!
+//! The balance compares the time a user logged with the time their
+//! contracts expected from them, week by week in the workspace's
+//! `week_start`; see [`loom_core::tenant::overtime`] for the rules.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use chrono::{NaiveDate, Utc};
+use eventually::aggregate::{
+    Root,
+    repository::{Getter, Saver},
+};
+use loom_core::tenant::{
+    holiday::{Holiday, HolidayEvent, HolidayId},
+    overtime::{self, Holidays, Schedule, WeekBalance},
+    work_contract::{WorkContract, WorkContractEvent, WorkContractId},
+};
+use loom_infrastructure_impl::{
+    ConnectedTenantPool,
+    tenant::{
+        holiday::repositories::{HolidayRepository, HolidayRow},
+        work_contract::repositories::{WorkContractRepository, WorkContractRow},
+    },
+};
+
+/// A user's overtime (positive) or undertime (negative) up to a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OvertimeBalance {
+    pub user_id: String,
+    pub user_name: Option<String>,
+    /// `YYYY-MM-DD`, inclusive.
+    pub until: String,
+    pub expected_seconds: i64,
+    pub worked_seconds: i64,
+    pub balance_seconds: i64,
+    /// Oldest first.
+    pub weeks: Vec<WeekBalance>,
+}
+
+fn parse_day(day: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(day, "%Y-%m-%d")
+        .map_err(|_| crate::error::ValidationError::new(format!("Invalid date: {day}")).into())
+}
+
+fn validate_contract(
+    weekly_minutes: i32,
+    working_days: &mut Vec<u8>,
+    starts_on: &str,
+) -> Result<()> {
+    if !(0..=7 * 24 * 60).contains(&weekly_minutes) {
+        return Err(
+            crate::error::ValidationError::new("Weekly hours must be between 0 and 168").into(),
+        );
+    }
+    working_days.sort_unstable();
+    working_days.dedup();
+    if working_days.is_empty() || working_days.iter().any(|d| !(1..=7).contains(d)) {
+        return Err(crate::error::ValidationError::new("Choose at least one working day").into());
+    }
+    parse_day(starts_on)?;
+    Ok(())
+}
+
+fn normalize_region(region: Option<String>) -> Option<String> {
+    region
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+}
+
+/// Every work contract of the workspace.
+pub async fn contracts(workspace_id: &str) -> Result<Vec<WorkContractRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = WorkContractRepository::from_pool(pool).await?;
+    Ok(repo.all().await?)
+}
+
+/// Define a new contract for a user.  It supersedes the user's earlier
+/// contracts from `starts_on` (`YYYY-MM-DD`) on.
+///
+/// # Errors
+///
+/// Returns a validation error if the hours, working days or start date are
+/// invalid.
+pub async fn define(
+    workspace_id: &str,
+    user_id: &str,
+    weekly_minutes: i32,
+    mut working_days: Vec<u8>,
+    starts_on: String,
+    region: Option<String>,
+) -> Result<WorkContractRow> {
+    validate_contract(weekly_minutes, &mut working_days, &starts_on)?;
+    let region = normalize_region(region);
+
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = WorkContractRepository::from_pool(pool).await?;
+
+    let id = WorkContractId::new();
+    let mut root =
+        Root::<WorkContract>::record_new(crate::audit::envelope(WorkContractEvent::Defined {
+            id: id.clone(),
+            user_id: user_id.parse()?,
+            weekly_minutes,
+            working_days: working_days.clone(),
+            starts_on: starts_on.clone(),
+            region: region.clone(),
+        }))?;
+    repo.save(&mut root).await?;
+
+    Ok(WorkContractRow {
+        id: id.to_string(),
+        user_id: user_id.to_string(),
+        weekly_minutes,
+        working_days,
+        starts_on,
+        region,
+    })
+}
+
+/// # Errors
+///
+/// Returns a validation error if the hours, working days or start date are
+/// invalid, or an error if the contract cannot be found or saved.
+pub async fn change(
+    workspace_id: &str,
+    id: &str,
+    weekly_minutes: i32,
+    mut working_days: Vec<u8>,
+    starts_on: String,
+    region: Option<String>,
+) -> Result<()> {
+    validate_contract(weekly_minutes, &mut working_days, &starts_on)?;
+
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = WorkContractRepository::from_pool(pool).await?;
+    let mut root = repo.get(&id.parse::<WorkContractId>()?).await?;
+    root.record_that(crate::audit::envelope(WorkContractEvent::Changed {
+        weekly_minutes,
+        working_days,
+        starts_on,
+        region: normalize_region(region),
+    }))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// # Errors
+///
+/// Returns an error if the contract cannot be found or saved.
+pub async fn remove(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = WorkContractRepository::from_pool(pool).await?;
+    let mut root = repo.get(&id.parse::<WorkContractId>()?).await?;
+    root.record_that(crate::audit::envelope(WorkContractEvent::Removed))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// Every public holiday of the workspace.
+pub async fn holidays(workspace_id: &str) -> Result<Vec<HolidayRow>> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = HolidayRepository::from_pool(pool).await?;
+    Ok(repo.all().await?)
+}
+
+/// Declare a public holiday of `region` on `date` (`YYYY-MM-DD`).
+///
+/// # Errors
+///
+/// Returns a validation error if the region or name is empty or the date is
+/// invalid.
+pub async fn declare_holiday(
+    workspace_id: &str,
+    region: String,
+    date: String,
+    name: String,
+) -> Result<HolidayRow> {
+    let Some(region) = normalize_region(Some(region)) else {
+        return Err(crate::error::ValidationError::new("The region must not be empty").into());
+    };
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(crate::error::ValidationError::new("The name must not be empty").into());
+    }
+    parse_day(&date)?;
+
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = HolidayRepository::from_pool(pool).await?;
+
+    let id = HolidayId::new();
+    let mut root = Root::<Holiday>::record_new(crate::audit::envelope(HolidayEvent::Declared {
+        id: id.clone(),
+        region: region.clone(),
+        date: date.clone(),
+        name: name.clone(),
+    }))?;
+    repo.save(&mut root).await?;
+
+    Ok(HolidayRow {
+        id: id.to_string(),
+        region,
+        date,
+        name,
+    })
+}
+
+/// # Errors
+///
+/// Returns an error if the holiday cannot be found or saved.
+pub async fn remove_holiday(workspace_id: &str, id: &str) -> Result<()> {
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = HolidayRepository::from_pool(pool).await?;
+    let mut root = repo.get(&id.parse::<HolidayId>()?).await?;
+    root.record_that(crate::audit::envelope(HolidayEvent::Removed))?;
+    repo.save(&mut root).await?;
+    Ok(())
+}
+
+/// The user's balance up to and including `until` (`YYYY-MM-DD`), or `None`
+/// if they have no contract.
+///
+/// # Errors
+///
+/// Returns a validation error if `until` is not a date.
+pub async fn balance(
+    workspace_id: &str,
+    user_id: &str,
+    until: &str,
+) -> Result<Option<OvertimeBalance>> {
+    let until_day = parse_day(until)?;
+    let week_start = crate::workspace::get_workspace_settings(workspace_id)
+        .await?
+        .week_start;
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = WorkContractRepository::from_pool(pool.clone()).await?;
+    let contracts = repo.for_user(user_id).await?;
+    if contracts.is_empty() {
+        return Ok(None);
+    }
+    let holidays = load_holidays(&pool).await?;
+    Ok(Some(
+        measure(
+            &repo,
+            user_id,
+            &contracts,
+            &holidays,
+            until_day,
+            &week_start,
+        )
+        .await?,
+    ))
+}
+
+/// The user's balance up to and including today (UTC).
+///
+/// # Errors
+///
+/// Returns an error if the contracts or timesheets cannot be read.
+pub async fn balance_today(workspace_id: &str, user_id: &str) -> Result<Option<OvertimeBalance>> {
+    let today = Utc::now().format("%Y-%m-%d").to_string();
+    balance(workspace_id, user_id, &today).await
+}
+
+/// The balance of every user with a contract up to and including `until`
+/// (`YYYY-MM-DD`), with their names.
+///
+/// # Errors
+///
+/// Returns a validation error if `until` is not a date.
+pub async fn balances(workspace_id: &str, until: &str) -> Result<Vec<OvertimeBalance>> {
+    let until_day = parse_day(until)?;
+    let week_start = crate::workspace::get_workspace_settings(workspace_id)
+        .await?
+        .week_start;
+    let names: BTreeMap<String, String> = crate::workspace::members(workspace_id)
+        .await?
+        .into_iter()
+        .map(|m| (m.user_id, m.name))
+        .collect();
+    let pool = super::tenant_pool(workspace_id).await?;
+    let repo = WorkContractRepository::from_pool(pool.clone()).await?;
+    let holidays = load_holidays(&pool).await?;
+
+    let mut by_user: BTreeMap<String, Vec<WorkContractRow>> = BTreeMap::new();
+    for row in repo.all().await? {
+        by_user.entry(row.user_id.clone()).or_default().push(row);
+    }
+
+    let mut result = Vec::with_capacity(by_user.len());
+    for (user_id, contracts) in by_user {
+        let mut balance = measure(
+            &repo,
+            &user_id,
+            &contracts,
+            &holidays,
+            until_day,
+            &week_start,
+        )
+        .await?;
+        balance.user_name = names.get(&user_id).cloned();
+        result.push(balance);
+    }
+    result.sort_by(|a, b| a.user_name.cmp(&b.user_name));
+    Ok(result)
+}
+
+async fn load_holidays(pool: &ConnectedTenantPool) -> Result<Holidays> {
+    Ok(HolidayRepository::from_pool(pool.clone())
+        .await?
+        .all()
+        .await?
+        .into_iter()
+        .filter_map(|h| Some((h.region, parse_day(&h.date).ok()?)))
+        .collect())
+}
+
+async fn measure(
+    repo: &WorkContractRepository,
+    user_id: &str,
+    contracts: &[WorkContractRow],
+    holidays: &Holidays,
+    until: NaiveDate,
+    week_start: &str,
+) -> Result<OvertimeBalance> {
+    let schedules: Vec<Schedule> = contracts
+        .iter()
+        .filter_map(|c| {
+            Some(Schedule {
+                starts_on: parse_day(&c.starts_on).ok()?,
+                weekly_minutes: c.weekly_minutes,
+                working_days: c.working_days.clone(),
+                region: c.region.clone(),
+            })
+        })
+        .collect();
+
+    let mut worked = BTreeMap::new();
+    if let Some(start) = schedules.iter().map(|s| s.starts_on).min()
+        && start <= until
+    {
+        let (from, to) = super::timesheet::day_range(
+            &start.format("%Y-%m-%d").to_string(),
+            &until.format("%Y-%m-%d").to_string(),
+        )?;
+        for (day, seconds) in repo.worked_by_day(user_id, &from, &to).await? {
+            if let Ok(day) = parse_day(&day) {
+                worked.insert(day, seconds);
+            }
+        }
+    }
+
+    let weeks = overtime::weekly_balances(
+        &schedules,
+        holidays,
+        &worked,
+        until,
+        overtime::first_weekday(week_start),
+    );
+    let expected_seconds = weeks.iter().map(|w| w.expected_seconds).sum();
+    let worked_seconds = weeks.iter().map(|w| w.worked_seconds).sum();
+    Ok(OvertimeBalance {
+        user_id: user_id.to_string(),
+        user_name: None,
+        until: until.format("%Y-%m-%d").to_string(),
+        expected_seconds,
+        worked_seconds,
+        balance_seconds: worked_seconds - expected_seconds,
+        weeks,
+    })
+}
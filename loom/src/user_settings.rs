@@ -27,14 +27,11 @@ pub async fn update_user_settings(
         .get(&agg_id)
         .await
         .map_err(|e| anyhow::anyhow!("{e}"))?;
-    root.record_that(
-        UserEvent::SettingsUpdated {
-            timezone,
-            date_format,
-            language,
-        }
-        .into(),
-    )?;
+    root.record_that(crate::audit::envelope(UserEvent::SettingsUpdated {
+        timezone,
+        date_format,
+        language,
+    }))?;
     repo.save(&mut root)
         .await
         .map_err(|e| anyhow::anyhow!("{e}"))
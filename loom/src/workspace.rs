@@ -1,7 +1,16 @@
 use anyhow::Result;
 use eventually::aggregate::repository::{Getter, Saver};
-use loom_core::admin::workspace::{WorkspaceEvent, WorkspaceId};
-use loom_infrastructure_impl::{Pool, admin::workspace::repositories::WorkspaceRepository};
+use loom_core::{
+    admin::{
+        user::UserId,
+        workspace::{WorkspaceEvent, WorkspaceId},
+    },
+    tenant::timesheet::{AutoStopPolicy, PeriodLocks, Rounding, TimeEntryPolicy},
+};
+use loom_infrastructure_impl::{
+    Pool,
+    admin::{user::repositories::UserRepository, workspace::repositories::WorkspaceRepository},
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -21,6 +30,25 @@ pub async fn list_user_workspaces(user_id: &str) -> Result<Vec<WorkspaceInfo>> {
         .collect())
 }
 
+/// A user who belongs to a workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceMember {
+    pub user_id: String,
+    pub name: String,
+}
+
+/// Returns the members of the given workspace, ordered by name.
+pub async fn members(workspace_id: &str) -> Result<Vec<WorkspaceMember>> {
+    let pool = Pool::connect_admin().await?;
+    let repo = WorkspaceRepository::from_pool(pool).await?;
+    Ok(repo
+        .members(workspace_id)
+        .await?
+        .into_iter()
+        .map(|(user_id, name)| WorkspaceMember { user_id, name })
+        .collect())
+}
+
 /// Returns the current settings for the given workspace.
 pub async fn get_workspace_settings(
     workspace_id: &str,
@@ -49,16 +77,241 @@ pub async fn update_workspace_settings(
         .get(&agg_id)
         .await
         .map_err(|e| anyhow::anyhow!("{e}"))?;
-    root.record_that(
-        WorkspaceEvent::SettingsUpdated {
-            name,
-            timezone,
-            date_format,
-            currency,
-            week_start,
+    root.record_that(crate::audit::envelope(WorkspaceEvent::SettingsUpdated {
+        name,
+        timezone,
+        date_format,
+        currency,
+        week_start,
+    }))?;
+    repo.save(&mut root)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))
+}
+
+/// Records a `WorkspaceBudgetPolicyUpdated` event for the given workspace.
+///
+/// Thresholds are percentages of a budget; they are stored sorted and
+/// without duplicates.
+pub async fn update_budget_policy(
+    workspace_id: &str,
+    mut thresholds: Vec<u8>,
+    block_over_budget: bool,
+) -> Result<()> {
+    if thresholds.contains(&0) {
+        return Err(
+            crate::error::ValidationError::new("Budget thresholds must be at least 1%").into(),
+        );
+    }
+    thresholds.sort_unstable();
+    thresholds.dedup();
+
+    let pool = Pool::connect_admin().await?;
+    let repo = WorkspaceRepository::from_pool(pool).await?;
+
+    let agg_id: WorkspaceId = workspace_id.parse()?;
+    let mut root = repo
+        .get(&agg_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    root.record_that(crate::audit::envelope(
+        WorkspaceEvent::BudgetPolicyUpdated {
+            thresholds,
+            block_over_budget,
+        },
+    ))?;
+    repo.save(&mut root)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))
+}
+
+/// Records a `WorkspaceTimeEntryPolicyUpdated` event for the given workspace.
+pub async fn update_time_entry_policy(workspace_id: &str, policy: TimeEntryPolicy) -> Result<()> {
+    policy
+        .validate()
+        .map_err(crate::error::ValidationError::new)?;
+
+    let pool = Pool::connect_admin().await?;
+    let repo = WorkspaceRepository::from_pool(pool).await?;
+
+    let agg_id: WorkspaceId = workspace_id.parse()?;
+    let mut root = repo
+        .get(&agg_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    root.record_that(crate::audit::envelope(
+        WorkspaceEvent::TimeEntryPolicyUpdated { policy },
+    ))?;
+    repo.save(&mut root)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))
+}
+
+/// Records a `WorkspaceRoundingUpdated` event for the given workspace.
+pub async fn update_rounding(workspace_id: &str, rounding: Rounding) -> Result<()> {
+    rounding
+        .validate()
+        .map_err(crate::error::ValidationError::new)?;
+
+    let pool = Pool::connect_admin().await?;
+    let repo = WorkspaceRepository::from_pool(pool).await?;
+
+    let agg_id: WorkspaceId = workspace_id.parse()?;
+    let mut root = repo
+        .get(&agg_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    root.record_that(crate::audit::envelope(WorkspaceEvent::RoundingUpdated {
+        rounding,
+    }))?;
+    repo.save(&mut root)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))
+}
+
+/// Records a `WorkspaceAutoStopPolicyUpdated` event for the given workspace.
+pub async fn update_auto_stop_policy(workspace_id: &str, policy: AutoStopPolicy) -> Result<()> {
+    policy
+        .validate()
+        .map_err(crate::error::ValidationError::new)?;
+
+    let pool = Pool::connect_admin().await?;
+    let repo = WorkspaceRepository::from_pool(pool).await?;
+
+    let agg_id: WorkspaceId = workspace_id.parse()?;
+    let mut root = repo
+        .get(&agg_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    root.record_that(crate::audit::envelope(
+        WorkspaceEvent::AutoStopPolicyUpdated { policy },
+    ))?;
+    repo.save(&mut root)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))
+}
+
+/// Returns the current period locks of the given workspace.
+pub async fn period_locks(workspace_id: &str) -> Result<PeriodLocks> {
+    let pool = Pool::connect_admin().await?;
+    let repo = WorkspaceRepository::from_pool(pool).await?;
+    Ok(repo.period_locks(workspace_id).await?)
+}
+
+/// A lock that applies to a single user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserPeriodLock {
+    pub user_id: String,
+    pub user_name: Option<String>,
+    /// `YYYY-MM-DD`.
+    pub until: String,
+}
+
+/// Returns the per-user period locks of the given workspace with the users'
+/// names.
+pub async fn user_period_locks(workspace_id: &str) -> Result<Vec<UserPeriodLock>> {
+    let pool = Pool::connect_admin().await?;
+    let locks = WorkspaceRepository::from_pool(pool.clone())
+        .await?
+        .period_locks(workspace_id)
+        .await?;
+    let users = UserRepository::from_pool(pool).await?;
+    let mut result = Vec::with_capacity(locks.users.len());
+    for (user_id, until) in locks.users {
+        let user_name = users
+            .find_view_by_id(&user_id)
+            .await?
+            .map(|u| u.get_name().to_string());
+        result.push(UserPeriodLock {
+            user_id,
+            user_name,
+            until,
+        });
+    }
+    Ok(result)
+}
+
+/// Records a `WorkspacePeriodLocked` event, locking every time entry that
+/// starts on or before `until` (`YYYY-MM-DD`) for `user_id` or, without it,
+/// for the whole workspace.
+///
+/// A lock only moves forward; use [`unlock_period`] to move it back.
+pub async fn lock_period(workspace_id: &str, user_id: Option<String>, until: String) -> Result<()> {
+    let Some(date) = PeriodLocks::parse_date(&until) else {
+        return Err(crate::error::ValidationError::new("The lock date must be YYYY-MM-DD").into());
+    };
+
+    let pool = Pool::connect_admin().await?;
+    let repo = WorkspaceRepository::from_pool(pool).await?;
+
+    let agg_id: WorkspaceId = workspace_id.parse()?;
+    let mut root = repo
+        .get(&agg_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    if let Some(current) = root
+        .period_locks
+        .lock_of(user_id.as_deref())
+        .and_then(PeriodLocks::parse_date)
+        && current >= date
+    {
+        return Err(crate::error::ValidationError::new(format!(
+            "The period is already locked up to {current}"
+        ))
+        .into());
+    }
+    let user_id: Option<UserId> = user_id.as_deref().map(str::parse).transpose()?;
+    root.record_that(crate::audit::envelope(WorkspaceEvent::PeriodLocked {
+        user_id,
+        until: date.format("%Y-%m-%d").to_string(),
+    }))?;
+    repo.save(&mut root)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))
+}
+
+/// Records a `WorkspacePeriodUnlocked` event, moving the lock of `user_id`
+/// (or of the workspace) back to `until`, or lifting it without `until`.
+pub async fn unlock_period(
+    workspace_id: &str,
+    user_id: Option<String>,
+    until: Option<String>,
+) -> Result<()> {
+    let pool = Pool::connect_admin().await?;
+    let repo = WorkspaceRepository::from_pool(pool).await?;
+
+    let agg_id: WorkspaceId = workspace_id.parse()?;
+    let mut root = repo
+        .get(&agg_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    let Some(current) = root
+        .period_locks
+        .lock_of(user_id.as_deref())
+        .and_then(PeriodLocks::parse_date)
+    else {
+        return Err(crate::error::ValidationError::new("The period is not locked").into());
+    };
+    let until = match until.as_deref().map(PeriodLocks::parse_date) {
+        None => None,
+        Some(Some(date)) if date < current => Some(date.format("%Y-%m-%d").to_string()),
+        Some(Some(_)) => {
+            return Err(crate::error::ValidationError::new(format!(
+                "The new lock date must be before {current}"
+            ))
+            .into());
+        }
+        Some(None) => {
+            return Err(
+                crate::error::ValidationError::new("The lock date must be YYYY-MM-DD").into(),
+            );
         }
-        .into(),
-    )?;
+    };
+    let user_id: Option<UserId> = user_id.as_deref().map(str::parse).transpose()?;
+    root.record_that(crate::audit::envelope(WorkspaceEvent::PeriodUnlocked {
+        user_id,
+        until,
+    }))?;
     repo.save(&mut root)
         .await
         .map_err(|e| anyhow::anyhow!("{e}"))
@@ -8,6 +8,7 @@
 /// Security scenarios covered:
 ///   - Admin flag correctly detected via role name                ✓
 ///   - Non-admin roles do NOT grant admin                         ✓
+///   - `is_admin_in` is scoped to the workspace                   ✓
 ///   - Unknown users are never treated as admin                   ✓
 ///   - Empty / zero-length `user_id` is safe                        ✓
 ///   - SQL injection in `user_id` returns false (parameterised)     ✓
@@ -178,6 +179,27 @@ async fn is_admin_returns_false_for_user_with_non_admin_role() {
     );
 }
 
+/// The admin role of one workspace must not make the user an admin of
+/// another one.
+#[tokio::test]
+async fn is_admin_in_is_scoped_to_workspace() {
+    let db = TestFixture::setup().await;
+    seed(db.admin.as_ref()).await;
+
+    assert!(
+        AuthorizationService::is_admin_in_on(db.admin.as_ref(), ADMIN_USER_ID, WORKSPACE_ID)
+            .await
+            .unwrap()
+    );
+    let other_workspace = "00000000-0000-0000-ffff-000000000001";
+    assert!(
+        !AuthorizationService::is_admin_in_on(db.admin.as_ref(), ADMIN_USER_ID, other_workspace)
+            .await
+            .unwrap(),
+        "admin in WORKSPACE_ID must not be admin of a different workspace"
+    );
+}
+
 #[tokio::test]
 async fn is_admin_returns_false_for_unknown_user() {
     let db = TestFixture::setup().await;
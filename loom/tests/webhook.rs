@@ -0,0 +1,142 @@
+/// Tests for webhook delivery against a local HTTP stand-in.
+///
+/// The stand-in accepts a single request on an ephemeral port, answers it
+/// with a fixed status and hands the raw request back to the test.  No
+/// database is involved: `send` only needs the delivery itself.
+use loom::infrastructure::tenant::webhook::repositories::{DeliveryRow, DeliveryStatus};
+use loom::tenant::webhook::{client, send, sign};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    sync::oneshot,
+};
+
+// ── helpers ──────────────────────────────────────────────────────────────────
+
+/// A raw HTTP request as received by the stand-in.
+struct Received {
+    head: String,
+    body: String,
+}
+
+impl Received {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.head.lines().skip(1).find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+    }
+}
+
+/// Serve one request with `status` and return the URL to send it to.
+async fn stand_in(status: u16) -> (String, oneshot::Receiver<Received>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let (tx, rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let received = loop {
+            let n = socket.read(&mut chunk).await.unwrap();
+            buffer.extend_from_slice(&chunk[..n]);
+            let text = String::from_utf8_lossy(&buffer).into_owned();
+            let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                continue;
+            };
+            let received = Received {
+                head: head.to_string(),
+                body: body.to_string(),
+            };
+            let length: usize = received
+                .header("content-length")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_default();
+            if received.body.len() >= length || n == 0 {
+                break received;
+            }
+        };
+        socket
+            .write_all(format!("HTTP/1.1 {status} Status\r\ncontent-length: 0\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let _ = tx.send(received);
+    });
+    (url, rx)
+}
+
+fn delivery(payload: &str) -> DeliveryRow {
+    DeliveryRow {
+        id: 42,
+        webhook_id: "0194b2a4-0000-7000-8000-000000000001".to_string(),
+        global_position: 7,
+        redelivery: 0,
+        stream_id: "0194b2a4-0000-7000-8000-000000000002".to_string(),
+        event_type: "TimesheetStopped".to_string(),
+        payload: payload.to_string(),
+        status: DeliveryStatus::Pending,
+        attempts: 0,
+        next_attempt_at: None,
+        last_attempt_at: None,
+        response_status: None,
+        error: None,
+        created_at: "2026-10-19T08:00:00Z".to_string(),
+    }
+}
+
+// ── tests ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn signature_is_hmac_sha256_of_the_body() {
+    // RFC 4231, test case 2.
+    assert_eq!(
+        sign("Jefe", b"what do ya want for nothing?"),
+        "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
+
+#[tokio::test]
+async fn delivery_is_posted_with_event_and_signature_headers() {
+    let (url, received) = stand_in(204).await;
+    let body = r#"{"event":"TimesheetStopped","position":7}"#;
+
+    let status = send(&client().unwrap(), &url, "s3cret", &delivery(body))
+        .await
+        .unwrap();
+    assert_eq!(status, 204);
+
+    let received = received.await.unwrap();
+    assert!(received.head.starts_with("POST /hook HTTP/1.1"));
+    assert_eq!(received.body, body);
+    assert_eq!(received.header("content-type"), Some("application/json"));
+    assert_eq!(received.header("x-loom-event"), Some("TimesheetStopped"));
+    assert_eq!(received.header("x-loom-delivery"), Some("42"));
+    assert_eq!(
+        received.header("x-loom-signature"),
+        Some(sign("s3cret", body.as_bytes()).as_str())
+    );
+}
+
+#[tokio::test]
+async fn error_status_is_reported_not_raised() {
+    let (url, received) = stand_in(503).await;
+
+    let status = send(&client().unwrap(), &url, "s3cret", &delivery("{}"))
+        .await
+        .unwrap();
+    assert_eq!(status, 503);
+    received.await.unwrap();
+}
+
+#[tokio::test]
+async fn unreachable_endpoint_is_an_error() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    drop(listener);
+
+    assert!(
+        send(&client().unwrap(), &url, "s3cret", &delivery("{}"))
+            .await
+            .is_err()
+    );
+}